@@ -1,10 +1,75 @@
-use log::{debug, warn};
+use log::{debug, info, warn};
 use rose_file_readers::{AipFile, StbFile, StbReadOptions, VirtualFilesystem};
-use std::collections::HashMap;
+use std::{collections::HashMap, path::Path};
 
-use rose_data::AiDatabase;
+use rose_data::{AiDatabase, AiDatabaseOptions, NpcId};
 
-pub fn get_ai_database(vfs: &VirtualFilesystem) -> Result<AiDatabase, anyhow::Error> {
+fn load_npc_ai_overrides(path: &Path) -> HashMap<NpcId, AipFile> {
+    let mut overrides = HashMap::new();
+    let entries = match std::fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(error) => {
+            warn!(
+                "Failed to read npc AI override directory {}, error: {:?}",
+                path.to_string_lossy(),
+                error
+            );
+            return overrides;
+        }
+    };
+
+    for entry in entries.filter_map(Result::ok) {
+        let path = entry.path();
+        if !path
+            .extension()
+            .map_or(false, |extension| extension.eq_ignore_ascii_case("yaml"))
+        {
+            continue;
+        }
+
+        let Some(npc_id) = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.parse::<u16>().ok())
+            .and_then(NpcId::new)
+        else {
+            warn!(
+                "Ignoring npc AI override {}, file name must be a npc id",
+                path.to_string_lossy()
+            );
+            continue;
+        };
+
+        match std::fs::read_to_string(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|yaml| AipFile::from_yaml(&yaml))
+        {
+            Ok(aip) => {
+                info!(
+                    "Loaded AI override for npc {} from {}",
+                    npc_id.get(),
+                    path.to_string_lossy()
+                );
+                overrides.insert(npc_id, aip);
+            }
+            Err(error) => {
+                warn!(
+                    "Failed to parse {}, error: {:?}",
+                    path.to_string_lossy(),
+                    error
+                );
+            }
+        }
+    }
+
+    overrides
+}
+
+pub fn get_ai_database(
+    vfs: &VirtualFilesystem,
+    language: usize,
+    options: &AiDatabaseOptions,
+) -> Result<AiDatabase, anyhow::Error> {
     let ai_s_stb = vfs.read_file_with::<StbFile, _>(
         "3DDATA/AI/AI_S.STB",
         &StbReadOptions {
@@ -14,10 +79,14 @@ pub fn get_ai_database(vfs: &VirtualFilesystem) -> Result<AiDatabase, anyhow::Er
     )?;
     let mut strings = HashMap::new();
 
+    // Each language is a column, fall back to english when there is no translation
     for row in 0..ai_s_stb.rows() {
-        let english = ai_s_stb.get(row, 1);
-        if !english.is_empty() {
-            strings.insert(row as u16, english.to_string());
+        let text = ai_s_stb
+            .try_get(row, language)
+            .filter(|text| !text.is_empty())
+            .unwrap_or_else(|| ai_s_stb.get(row, 1));
+        if !text.is_empty() {
+            strings.insert(row as u16, text.to_string());
         }
     }
 
@@ -40,6 +109,13 @@ pub fn get_ai_database(vfs: &VirtualFilesystem) -> Result<AiDatabase, anyhow::Er
         }
     }
 
+    let npc_override_aips = options
+        .npc_ai_override_path
+        .as_deref()
+        .filter(|path| path.is_dir())
+        .map(load_npc_ai_overrides)
+        .unwrap_or_default();
+
     debug!("Loaded {} AI files", aips.len());
-    Ok(AiDatabase { strings, aips })
+    Ok(AiDatabase::new(strings, aips, npc_override_aips))
 }
@@ -1,4 +1,7 @@
+use std::time::Duration;
+
 use rose_data::AnimationEventFlags;
+use rose_file_readers::ZmoFile;
 
 pub fn get_animation_event_flags() -> Vec<AnimationEventFlags> {
     let mut animation_event_flags = vec![AnimationEventFlags::NONE; 100];
@@ -60,3 +63,27 @@ pub fn get_animation_event_flags() -> Vec<AnimationEventFlags> {
 
     animation_event_flags
 }
+
+/// Returns the start time and flags of each frame in the motion which has an animation event
+pub fn get_motion_frame_events(zmo: &ZmoFile) -> Vec<(Duration, AnimationEventFlags)> {
+    let animation_event_flags = get_animation_event_flags();
+
+    zmo.frame_events
+        .iter()
+        .enumerate()
+        .filter_map(|(frame, &event)| {
+            let flags = animation_event_flags
+                .get(event as usize)
+                .copied()
+                .unwrap_or(AnimationEventFlags::NONE);
+            if flags.is_empty() || zmo.fps == 0 {
+                return None;
+            }
+
+            Some((
+                Duration::from_nanos((frame as u64 * 1_000_000_000) / zmo.fps as u64),
+                flags,
+            ))
+        })
+        .collect()
+}
@@ -6,6 +6,11 @@ use rose_data::{
 };
 use rose_file_readers::{StbFile, VfsPathBuf, VirtualFilesystem, ZmoFile};
 
+use crate::{
+    animation_event_flags::get_motion_frame_events, data_decoder::IroseSkillType,
+    skill_database::StbSkill,
+};
+
 fn get_action_map() -> EnumMap<CharacterMotionAction, MotionId> {
     enum_map! {
         CharacterMotionAction::Stop1 => MotionId::new(0),
@@ -54,16 +59,15 @@ fn load_motion_file_data(
 
     if options.load_frame_data {
         let zmo = vfs.read_file::<ZmoFile, _>(&path).ok()?;
-        Some(MotionFileData {
+        Some(MotionFileData::new(
             path,
-            duration: zmo.get_duration(),
-            total_attack_frames: zmo.total_attack_frames,
-        })
+            zmo.get_duration(),
+            zmo.num_frames,
+            zmo.total_attack_frames,
+            get_motion_frame_events(&zmo),
+        ))
     } else {
-        Some(MotionFileData {
-            path,
-            ..Default::default()
-        })
+        Some(MotionFileData::with_path(path))
     }
 }
 
@@ -99,11 +103,25 @@ pub fn get_character_motion_database(
         }
     }
 
+    // Characters may only use the motions of emote skills as an emote
+    let list_skill = StbSkill(vfs.read_file::<StbFile, _>("3DDATA/STB/LIST_SKILL.STB")?);
+    let mut emote_motions = Vec::new();
+    for id in 1..list_skill.rows() {
+        if matches!(list_skill.get_skill_type(id), Some(IroseSkillType::Emote)) {
+            if let Some(motion_id) = list_skill.get_action_motion_id(id) {
+                if !emote_motions.contains(&motion_id) {
+                    emote_motions.push(motion_id);
+                }
+            }
+        }
+    }
+
     Ok(CharacterMotionDatabase::new(
         num_character_motion_weapons,
         motion_indices,
         motion_datas,
         get_action_map(),
         get_vehicle_action_map(),
+        emote_motions,
     ))
 }
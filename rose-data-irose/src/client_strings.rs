@@ -11,14 +11,7 @@ pub fn get_client_strings(
     let get_string = |id: u16| -> &'static str {
         let mut key = ArrayString::<16>::new();
         write!(&mut key, "{}", id).ok();
-        unsafe {
-            std::mem::transmute(
-                string_database
-                    .client_strings
-                    .get_text_string(string_database.language, &key)
-                    .unwrap_or(""),
-            )
-        }
+        unsafe { std::mem::transmute(string_database.get_client_string(&key)) }
     };
 
     Ok(Arc::new(ClientStrings {
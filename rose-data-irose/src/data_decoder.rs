@@ -5,8 +5,9 @@ use num_traits::{FromPrimitive, ToPrimitive};
 
 use rose_data::{
     AbilityType, AmmoIndex, ClanMemberPosition, DataDecoder, EffectBulletMoveType, EquipmentIndex,
-    ItemClass, ItemReference, ItemType, SkillActionMode, SkillBasicCommand, SkillTargetFilter,
-    SkillType, StatusEffectClearedByType, StatusEffectType, VehiclePartIndex, VehicleType,
+    ItemClass, ItemReference, ItemType, SkillActionMode, SkillBasicCommand, SkillPageKind,
+    SkillPageType, SkillTargetFilter, SkillType, StatusEffectClearedByType, StatusEffectType,
+    VehiclePartIndex, VehicleType,
 };
 
 macro_rules! impl_conversions {
@@ -431,22 +432,14 @@ impl_conversions!(
     decode_skill_target_filter
 );
 
-#[derive(Copy, Clone, Debug, FromPrimitive)]
+#[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive)]
 pub enum IroseSkillPageType {
     Basic = 0,
     Active = 1,
     Passive = 2,
     Clan = 3,
 }
-
-impl FromStr for IroseSkillPageType {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let value = s.parse::<usize>().map_err(|_| ())?;
-        FromPrimitive::from_usize(value).ok_or(())
-    }
-}
+impl_conversions!(IroseSkillPageType, SkillPageKind, decode_skill_page_kind);
 
 #[derive(FromPrimitive, ToPrimitive)]
 pub enum IroseSkillType {
@@ -470,6 +463,7 @@ pub enum IroseSkillType {
     Warp = 18,
     SelfAndTarget = 19,
     Resurrection = 20,
+    Toggle = 21,
 }
 impl_conversions!(IroseSkillType, SkillType, decode_skill_type);
 
@@ -524,6 +518,14 @@ impl DataDecoder for IroseDataDecoder {
         decode_ammo_index(id)
     }
 
+    fn decode_skill_page_kind(&self, page: SkillPageType) -> Option<SkillPageKind> {
+        decode_skill_page_kind(page)
+    }
+
+    fn encode_skill_page_kind(&self, kind: SkillPageKind) -> Option<SkillPageType> {
+        encode_skill_page_kind(kind)
+    }
+
     fn encode_clan_member_position(&self, position: ClanMemberPosition) -> Option<usize> {
         encode_clan_member_position(position)
     }
@@ -1187,6 +1189,7 @@ pub fn decode_skill_type(id: usize) -> Option<SkillType> {
         IroseSkillType::Warp => Some(SkillType::Warp),
         IroseSkillType::SelfAndTarget => Some(SkillType::SelfAndTarget),
         IroseSkillType::Resurrection => Some(SkillType::Resurrection),
+        IroseSkillType::Toggle => Some(SkillType::Toggle),
     }
 }
 
@@ -1212,6 +1215,7 @@ pub fn encode_skill_type(skill_type: SkillType) -> Option<usize> {
         SkillType::Warp => IroseSkillType::Warp.to_usize(),
         SkillType::SelfAndTarget => IroseSkillType::SelfAndTarget.to_usize(),
         SkillType::Resurrection => IroseSkillType::Resurrection.to_usize(),
+        SkillType::Toggle => IroseSkillType::Toggle.to_usize(),
     }
 }
 
@@ -1238,3 +1242,21 @@ pub fn encode_clan_member_position(position: ClanMemberPosition) -> Option<usize
         ClanMemberPosition::Master => IroseClanMemberPosition::Master.to_usize(),
     }
 }
+
+pub fn decode_skill_page_kind(page: SkillPageType) -> Option<SkillPageKind> {
+    match FromPrimitive::from_usize(page)? {
+        IroseSkillPageType::Basic => Some(SkillPageKind::Basic),
+        IroseSkillPageType::Active => Some(SkillPageKind::Active),
+        IroseSkillPageType::Passive => Some(SkillPageKind::Passive),
+        IroseSkillPageType::Clan => Some(SkillPageKind::Clan),
+    }
+}
+
+pub fn encode_skill_page_kind(kind: SkillPageKind) -> Option<SkillPageType> {
+    match kind {
+        SkillPageKind::Basic => IroseSkillPageType::Basic.to_usize(),
+        SkillPageKind::Active => IroseSkillPageType::Active.to_usize(),
+        SkillPageKind::Passive => IroseSkillPageType::Passive.to_usize(),
+        SkillPageKind::Clan => IroseSkillPageType::Clan.to_usize(),
+    }
+}
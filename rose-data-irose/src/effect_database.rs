@@ -1,7 +1,11 @@
-use std::{num::NonZeroUsize, sync::Arc, time::Duration};
+use std::{collections::HashSet, num::NonZeroUsize, sync::Arc, time::Duration};
 
-use rose_data::{EffectData, EffectDatabase, EffectFileId, EffectId, SoundId};
-use rose_file_readers::{stb_column, StbFile, VfsPathBuf, VirtualFilesystem};
+use rose_data::{
+    EffectData, EffectDatabase, EffectDatabaseOptions, EffectFileId, EffectId, SoundId,
+};
+use rose_file_readers::{
+    stb_column, EftFile, PtlFile, StbFile, VfsPathBuf, VirtualFilesystem, ZmoFile, ZmoReadOptions,
+};
 
 use crate::data_decoder::IroseEffectBulletMoveType;
 
@@ -54,7 +58,95 @@ fn load_effect(data: &StbEffect, id: usize) -> Option<EffectData> {
     })
 }
 
-pub fn get_effect_database(vfs: &VirtualFilesystem) -> Result<Arc<EffectDatabase>, anyhow::Error> {
+/// Returns how long an animation plays for, None if it repeats forever
+fn get_animation_duration(
+    vfs: &VirtualFilesystem,
+    path: &VfsPathBuf,
+    repeat_count: u32,
+) -> Option<Duration> {
+    if repeat_count == 0 {
+        return None;
+    }
+
+    let zmo = vfs
+        .read_file_with::<ZmoFile, _>(
+            path,
+            &ZmoReadOptions {
+                skip_animation: true,
+            },
+        )
+        .ok()?;
+    if zmo.fps == 0 {
+        return Some(Duration::ZERO);
+    }
+    Some(zmo.get_duration() * repeat_count)
+}
+
+/// Returns how long a particle system plays for, None if any of its sequences loop forever
+fn get_particle_duration(vfs: &VirtualFilesystem, path: &VfsPathBuf) -> Option<Duration> {
+    let ptl = vfs.read_file::<PtlFile, _>(path).ok()?;
+    let mut duration = Duration::ZERO;
+
+    for sequence in ptl.sequences.iter() {
+        if sequence.num_loops <= 0 {
+            return None;
+        }
+
+        // Each loop lasts until the longest lived particle has expired
+        let sequence_duration =
+            Duration::from_secs_f32(sequence.life.end().max(0.0) * sequence.num_loops as f32);
+        duration = duration.max(sequence_duration);
+    }
+
+    Some(duration)
+}
+
+/// Returns how long an effect file plays for, None if it loops forever or can not be read
+fn get_effect_file_duration(vfs: &VirtualFilesystem, path: &VfsPathBuf) -> Option<Duration> {
+    let eft = vfs.read_file::<EftFile, _>(path).ok()?;
+    let mut duration = Duration::ZERO;
+
+    for particle in eft.particles.iter() {
+        let mut particle_duration = get_particle_duration(vfs, &particle.particle_file)?;
+        if let Some(animation_file) = particle.animation_file.as_ref() {
+            particle_duration = particle_duration.max(get_animation_duration(
+                vfs,
+                animation_file,
+                particle.animation_repeat_count,
+            )?);
+        }
+
+        duration =
+            duration.max(Duration::from_millis(particle.start_delay as u64) + particle_duration);
+    }
+
+    for mesh in eft.meshes.iter() {
+        let mut mesh_duration = Duration::ZERO;
+        if let Some(mesh_animation_file) = mesh.mesh_animation_file.as_ref() {
+            mesh_duration = mesh_duration.max(get_animation_duration(
+                vfs,
+                mesh_animation_file,
+                mesh.repeat_count,
+            )?);
+        }
+        if let Some(animation_file) = mesh.animation_file.as_ref() {
+            mesh_duration = mesh_duration.max(get_animation_duration(
+                vfs,
+                animation_file,
+                mesh.animation_repeat_count,
+            )?);
+        }
+
+        duration = duration.max(Duration::from_millis(mesh.start_delay as u64) + mesh_duration);
+    }
+
+    Some(duration)
+}
+
+pub fn get_effect_database(
+    vfs: &VirtualFilesystem,
+    options: &EffectDatabaseOptions,
+) -> Result<Arc<EffectDatabase>, anyhow::Error> {
     let stb_effect_files = vfs.read_file::<StbFile, _>("3DDATA/STB/FILE_EFFECT.STB")?;
 
     let mut effect_files = Vec::new();
@@ -73,5 +165,27 @@ pub fn get_effect_database(vfs: &VirtualFilesystem) -> Result<Arc<EffectDatabase
         effects.push(load_effect(&stb_effects, row));
     }
 
-    Ok(Arc::new(EffectDatabase::new(effects, effect_files)))
+    // Only bullet durations are used by the server, casting time comes from the casting
+    // motion rather than its effect, so avoid reading every EFT, PTL and ZMO file
+    let mut effect_file_durations = vec![None; effect_files.len()];
+    if options.load_bullet_durations {
+        let bullet_effect_files: HashSet<usize> = effects
+            .iter()
+            .flatten()
+            .filter_map(|effect_data| effect_data.bullet_effect)
+            .map(|bullet_effect| bullet_effect.get() as usize)
+            .collect();
+
+        for index in bullet_effect_files {
+            if let Some(Some(path)) = effect_files.get(index) {
+                effect_file_durations[index] = get_effect_file_duration(vfs, path);
+            }
+        }
+    }
+
+    Ok(Arc::new(EffectDatabase::new(
+        effects,
+        effect_files,
+        effect_file_durations,
+    )))
 }
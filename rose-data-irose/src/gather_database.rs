@@ -0,0 +1,20 @@
+use log::debug;
+
+use rose_data::{GatherDatabase, GatherNodeData};
+use rose_file_readers::VirtualFilesystem;
+
+const GATHER_NODES_PATH: &str = "3DDATA/STB/GATHER_NODES.YAML";
+
+/// Gather nodes are not part of the irose data, so they are only loaded when a file has been
+/// added to the data overlay
+pub fn get_gather_database(vfs: &VirtualFilesystem) -> Result<GatherDatabase, anyhow::Error> {
+    if !vfs.exists(GATHER_NODES_PATH) {
+        return Ok(GatherDatabase::default());
+    }
+
+    let file = vfs.open_file(GATHER_NODES_PATH)?;
+    let nodes: Vec<GatherNodeData> = serde_yaml::from_slice(file.as_bytes())?;
+
+    debug!("Loaded {} gather nodes", nodes.len());
+    Ok(GatherDatabase::new(nodes))
+}
@@ -4,6 +4,7 @@ mod character_motion_database;
 mod client_strings;
 mod data_decoder;
 mod effect_database;
+mod gather_database;
 mod item_database;
 mod job_class_database;
 mod npc_database;
@@ -11,8 +12,11 @@ mod quest_database;
 mod skill_database;
 mod skybox_database;
 mod sound_database;
+mod sprite_database;
 mod status_effect_database;
 mod string_database;
+mod teleport_anchor_database;
+mod ui_string_ids;
 mod warp_gate_database;
 mod zone_database;
 
@@ -22,6 +26,7 @@ pub use character_motion_database::get_character_motion_database;
 pub use client_strings::get_client_strings;
 pub use data_decoder::get_data_decoder;
 pub use effect_database::get_effect_database;
+pub use gather_database::get_gather_database;
 pub use item_database::get_item_database;
 pub use job_class_database::get_job_class_database;
 pub use npc_database::get_npc_database;
@@ -29,15 +34,18 @@ pub use quest_database::get_quest_database;
 pub use skill_database::{get_skill_database, SKILL_PAGE_SIZE};
 pub use skybox_database::get_skybox_database;
 pub use sound_database::get_sound_database;
+pub use sprite_database::get_sprite_database;
 pub use status_effect_database::get_status_effect_database;
 pub use string_database::get_string_database;
+pub use teleport_anchor_database::get_teleport_anchor_database;
+pub use ui_string_ids::{find_missing_ui_strings, get_ui_string_ids};
 pub use warp_gate_database::get_warp_gate_database;
 pub use zone_database::{get_zone_database, get_zone_list};
 
 pub use data_decoder::{
     decode_ability_type, decode_ammo_index, decode_clan_member_position, decode_equipment_index,
-    decode_item_base1000, decode_item_type, decode_vehicle_part_index, encode_ability_type,
-    encode_ammo_index, encode_clan_member_position, encode_equipment_index, encode_item_class,
-    encode_item_type, encode_skill_target_filter, encode_skill_type, encode_vehicle_part_index,
-    IroseSkillPageType,
+    decode_item_base1000, decode_item_type, decode_skill_page_kind, decode_vehicle_part_index,
+    encode_ability_type, encode_ammo_index, encode_clan_member_position, encode_equipment_index,
+    encode_item_class, encode_item_type, encode_skill_page_kind, encode_skill_target_filter,
+    encode_skill_type, encode_vehicle_part_index, IroseSkillPageType,
 };
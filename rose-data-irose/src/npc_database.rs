@@ -12,7 +12,7 @@ use rose_data::{
 };
 use rose_file_readers::{stb_column, ChrFile, StbFile, VfsPathBuf, VirtualFilesystem, ZmoFile};
 
-use crate::data_decoder::decode_item_base1000;
+use crate::{animation_event_flags::get_motion_frame_events, data_decoder::decode_item_base1000};
 
 struct StbNpc(StbFile);
 
@@ -113,16 +113,15 @@ fn load_motion_file_data(
 
     if options.load_frame_data {
         let zmo = vfs.read_file::<ZmoFile, _>(&path).ok()?;
-        Some(MotionFileData {
+        Some(MotionFileData::new(
             path,
-            duration: zmo.get_duration(),
-            total_attack_frames: zmo.total_attack_frames,
-        })
+            zmo.get_duration(),
+            zmo.num_frames,
+            zmo.total_attack_frames,
+            get_motion_frame_events(&zmo),
+        ))
     } else {
-        Some(MotionFileData {
-            path,
-            ..Default::default()
-        })
+        Some(MotionFileData::with_path(path))
     }
 }
 
@@ -1,8 +1,14 @@
-use log::{debug, warn};
-use std::{collections::HashMap, sync::Arc};
+use log::{debug, info, warn};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
-use rose_data::{QuestData, QuestDatabase, StringDatabase, WorldTicks};
-use rose_file_readers::{stb_column, QsdFile, StbFile, StbReadOptions, VirtualFilesystem};
+use rose_data::{QuestData, QuestDatabase, QuestDatabaseOptions, StringDatabase, WorldTicks};
+use rose_file_readers::{
+    editor_friendly, stb_column, QsdFile, StbFile, StbReadOptions, VirtualFilesystem,
+};
 
 struct StbQuest(StbFile);
 
@@ -10,9 +16,31 @@ impl StbQuest {
     stb_column! { 1, get_time_limit, WorldTicks }
 }
 
+fn find_yaml_files(path: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(path)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            find_yaml_files(&path, files)?;
+        } else if path
+            .extension()
+            .map_or(false, |extension| extension.eq_ignore_ascii_case("yaml"))
+        {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+fn load_yaml_qsd_file(path: &Path) -> Result<QsdFile, anyhow::Error> {
+    let data = std::fs::read_to_string(path)?;
+    Ok(editor_friendly::QsdFile::from_yaml(&data)?.into())
+}
+
 pub fn get_quest_database(
     vfs: &VirtualFilesystem,
     string_database: Arc<StringDatabase>,
+    options: &QuestDatabaseOptions,
 ) -> Result<QuestDatabase, anyhow::Error> {
     let quest_s_stb = vfs.read_file_with::<StbFile, _>(
         "3DDATA/QUESTDATA/QUEST_S.STB",
@@ -23,10 +51,14 @@ pub fn get_quest_database(
     )?;
     let mut strings = HashMap::new();
 
+    // Each language is a column, fall back to english when there is no translation
     for row in 0..quest_s_stb.rows() {
-        let english = quest_s_stb.get(row, 1);
-        if !english.is_empty() {
-            strings.insert(row as u16, english.to_string());
+        let text = quest_s_stb
+            .try_get(row, string_database.language)
+            .filter(|text| !text.is_empty())
+            .unwrap_or_else(|| quest_s_stb.get(row, 1));
+        if !text.is_empty() {
+            strings.insert(row as u16, text.to_string());
         }
     }
 
@@ -74,11 +106,54 @@ pub fn get_quest_database(
         }
     }
 
+    if let Some(yaml_qsd_path) = options.yaml_qsd_path.as_ref().filter(|path| path.is_dir()) {
+        let mut yaml_files = Vec::new();
+        if let Err(error) = find_yaml_files(yaml_qsd_path, &mut yaml_files) {
+            warn!(
+                "Failed to read yaml QSD directory {}, error: {:?}",
+                yaml_qsd_path.to_string_lossy(),
+                error
+            );
+        }
+        yaml_files.sort();
+
+        for yaml_path in yaml_files {
+            match load_yaml_qsd_file(&yaml_path) {
+                Ok(qsd) => {
+                    info!(
+                        "Loaded {} QSD triggers from {}",
+                        qsd.triggers.len(),
+                        yaml_path.to_string_lossy()
+                    );
+                    triggers.extend(qsd.triggers);
+                }
+                Err(error) => warn!(
+                    "Failed to parse {}, error: {:?}",
+                    yaml_path.to_string_lossy(),
+                    error
+                ),
+            }
+        }
+    }
+
     let mut triggers_by_hash = HashMap::new();
     for key in triggers.keys() {
         triggers_by_hash.insert(key.as_str().into(), key.clone());
     }
 
+    // Not every client has tutorial data, any cell which names a QSD trigger is used
+    let mut tutorial_triggers = Vec::new();
+    if let Ok(tutorial_stb) = vfs.read_file::<StbFile, _>("3DDATA/STB/FILE_TUTORIAL.STB") {
+        for row in 0..tutorial_stb.rows() {
+            for column in 0..tutorial_stb.columns() {
+                let trigger_name = tutorial_stb.get(row, column);
+                if triggers.contains_key(trigger_name) {
+                    tutorial_triggers.push(trigger_name.into());
+                }
+            }
+        }
+    }
+
     debug!("Loaded {} QSD triggers", triggers.len());
     Ok(QuestDatabase {
         _string_database: string_database,
@@ -86,5 +161,6 @@ pub fn get_quest_database(
         strings,
         triggers,
         triggers_by_hash,
+        tutorial_triggers,
     })
 }
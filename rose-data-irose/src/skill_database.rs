@@ -9,7 +9,8 @@ use std::{
 use rose_data::{
     AbilityType, EffectFileId, EffectId, ItemClass, JobClassId, MotionId, NpcId, SkillActionMode,
     SkillAddAbility, SkillCastingEffect, SkillCooldown, SkillData, SkillDatabase, SkillId,
-    SkillPageType, SkillTargetFilter, SoundId, StatusEffectId, StringDatabase, ZoneId,
+    SkillPageType, SkillRuleData, SkillTargetFilter, SoundId, StatusEffectId, StringDatabase,
+    ZoneId,
 };
 use rose_file_readers::{stb_column, StbFile, VirtualFilesystem};
 
@@ -20,6 +21,8 @@ use crate::data_decoder::{
 
 pub const SKILL_PAGE_SIZE: usize = 30;
 
+const SKILL_RULES_PATH: &str = "3DDATA/STB/SKILL_RULES.YAML";
+
 pub struct StbSkill(pub StbFile);
 
 #[allow(dead_code)]
@@ -256,6 +259,8 @@ fn load_skill(data: &StbSkill, string_database: &StringDatabase, id: usize) -> O
         learn_point_cost: data.get_learn_skill_points(id).unwrap_or(0),
         power: data.get_power(id).unwrap_or(0),
         required_ability: data.get_required_abilities(id),
+        required_combo_skill_id: None,
+        combo_timeout: Duration::ZERO,
         required_job_class: data.get_required_job_class(id),
         required_planet: data.get_require_planet_index(id),
         required_skills: data.get_required_skills(id),
@@ -273,6 +278,7 @@ fn load_skill(data: &StbSkill, string_database: &StringDatabase, id: usize) -> O
             .get_target_filter(id)
             .and_then(|x| x.try_into().ok())
             .unwrap_or(SkillTargetFilter::OnlySelf),
+        toggle_mana_drain: 0,
         use_ability: data.get_use_abilities(id),
         warp_zone_id: data.get_warp_zone_id(id),
         warp_zone_x: data.get_warp_zone_xpos(id).unwrap_or(0) as f32 * 1000.0,
@@ -291,6 +297,20 @@ pub fn get_skill_database(
         skills.push(load_skill(&data, &string_database, id));
     }
 
+    if vfs.exists(SKILL_RULES_PATH) {
+        let file = vfs.open_file(SKILL_RULES_PATH)?;
+        let skill_rules: Vec<SkillRuleData> = serde_yaml::from_slice(file.as_bytes())?;
+
+        debug!("Loaded rules for {} skills", skill_rules.len());
+        for skill_rule in skill_rules {
+            if let Some(Some(skill_data)) = skills.get_mut(skill_rule.skill.get() as usize) {
+                skill_data.required_combo_skill_id = skill_rule.required_combo_skill;
+                skill_data.combo_timeout = Duration::from_millis(skill_rule.combo_timeout_millis);
+                skill_data.toggle_mana_drain = skill_rule.toggle_mana_drain;
+            }
+        }
+    }
+
     debug!("Loaded {} skills", skills.len());
     Ok(SkillDatabase::new(string_database, skills))
 }
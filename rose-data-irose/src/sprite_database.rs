@@ -0,0 +1,47 @@
+use enum_map::enum_map;
+use log::warn;
+
+use rose_data::{SpriteData, SpriteDatabase, SpriteSheet, SpriteSheetType};
+use rose_file_readers::{TsiFile, VfsPathBuf, VirtualFilesystem};
+
+const SPRITE_RESOURCE_PATH: &str = "3DDATA/CONTROL/RES/";
+
+fn load_sprite_sheet(vfs: &VirtualFilesystem, filename: &str) -> SpriteSheet {
+    let tsi = match vfs.read_file::<TsiFile, _>(&format!("{}{}", SPRITE_RESOURCE_PATH, filename)) {
+        Ok(tsi) => tsi,
+        Err(error) => {
+            warn!("Failed to load sprite sheet {}: {:?}", filename, error);
+            return SpriteSheet::default();
+        }
+    };
+
+    SpriteSheet {
+        textures: tsi
+            .textures
+            .iter()
+            .map(|texture| {
+                VfsPathBuf::new(&format!("{}{}", SPRITE_RESOURCE_PATH, texture.filename))
+            })
+            .collect(),
+        sprites: tsi
+            .sprites
+            .into_iter()
+            .map(|sprite| SpriteData {
+                texture_index: sprite.texture_id as usize,
+                left: sprite.left,
+                top: sprite.top,
+                right: sprite.right,
+                bottom: sprite.bottom,
+                name: sprite.name,
+            })
+            .collect(),
+    }
+}
+
+pub fn get_sprite_database(vfs: &VirtualFilesystem) -> Result<SpriteDatabase, anyhow::Error> {
+    Ok(SpriteDatabase::new(enum_map! {
+        SpriteSheetType::Item => load_sprite_sheet(vfs, "ITEM1.TSI"),
+        SpriteSheetType::Skill => load_sprite_sheet(vfs, "SKILLICON.TSI"),
+        SpriteSheetType::StatusEffect => load_sprite_sheet(vfs, "STATEICON.TSI"),
+    }))
+}
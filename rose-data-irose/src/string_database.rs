@@ -2,24 +2,28 @@ use std::sync::Arc;
 
 use enum_map::enum_map;
 
-use rose_data::{ItemType, StringDatabase};
-use rose_file_readers::{StlFile, StlReadOptions, VirtualFilesystem};
+use rose_data::{
+    ItemType, StringDatabase, StringDatabaseOptions, STRING_DATABASE_FALLBACK_LANGUAGE,
+};
+use rose_file_readers::{LtbFile, StlFile, StlReadOptions, VirtualFilesystem};
 
 use crate::{
     encode_ability_type, encode_clan_member_position, encode_item_class,
     encode_skill_target_filter, encode_skill_type,
 };
 
+const CONVERSATION_STRINGS_PATH: &str = "3DDATA/EVENT/ULNGTB_CON.LTB";
+
 pub fn get_string_database(
     vfs: &VirtualFilesystem,
-    language: usize,
+    options: &StringDatabaseOptions,
 ) -> Result<Arc<StringDatabase>, anyhow::Error> {
     let stl_read_options = StlReadOptions {
-        language_filter: Some(vec![language]),
+        language_filter: Some(vec![options.language, STRING_DATABASE_FALLBACK_LANGUAGE]),
     };
 
     Ok(Arc::new(StringDatabase {
-        language,
+        language: options.language,
         encode_ability_type,
         encode_clan_member_position,
         encode_item_class,
@@ -30,6 +34,12 @@ pub fn get_string_database(
         clan: vfs.read_file_with::<StlFile, _>("3DDATA/STB/STR_CLAN.STL", &stl_read_options)?,
         client_strings: vfs
             .read_file_with::<StlFile, _>("3DDATA/STB/LIST_STRING.STL", &stl_read_options)?,
+        // Only clients with the CON conversation system have the conversation strings
+        conversation: if vfs.exists(CONVERSATION_STRINGS_PATH) {
+            vfs.read_file::<LtbFile, _>(CONVERSATION_STRINGS_PATH)?
+        } else {
+            LtbFile::default()
+        },
         item: enum_map! {
             ItemType::Face => vfs.read_file_with::<StlFile, _>("3DDATA/STB/LIST_FACEITEM_S.STL", &stl_read_options)?,
             ItemType::Head => vfs.read_file_with::<StlFile, _>("3DDATA/STB/LIST_CAP_S.STL", &stl_read_options)?,
@@ -0,0 +1,22 @@
+use log::debug;
+
+use rose_data::{TeleportAnchorData, TeleportAnchorDatabase};
+use rose_file_readers::VirtualFilesystem;
+
+const TELEPORT_ANCHORS_PATH: &str = "3DDATA/STB/TELEPORT_ANCHORS.YAML";
+
+/// Teleport anchors are not part of the irose data, so they are only loaded when a file has been
+/// added to the data overlay
+pub fn get_teleport_anchor_database(
+    vfs: &VirtualFilesystem,
+) -> Result<TeleportAnchorDatabase, anyhow::Error> {
+    if !vfs.exists(TELEPORT_ANCHORS_PATH) {
+        return Ok(TeleportAnchorDatabase::default());
+    }
+
+    let file = vfs.open_file(TELEPORT_ANCHORS_PATH)?;
+    let anchors: Vec<TeleportAnchorData> = serde_yaml::from_slice(file.as_bytes())?;
+
+    debug!("Loaded {} teleport anchors", anchors.len());
+    Ok(TeleportAnchorDatabase::new(anchors))
+}
@@ -0,0 +1,39 @@
+use std::fmt::Write;
+
+use arrayvec::ArrayString;
+
+use rose_data::{StringDatabase, UiStringIds};
+use rose_file_readers::{IdFile, VirtualFilesystem};
+
+pub fn get_ui_string_ids(vfs: &VirtualFilesystem) -> Result<UiStringIds, anyhow::Error> {
+    let id_file = vfs.read_file::<IdFile, _>("3DDATA/CONTROL/XML/UI_STRID.ID")?;
+
+    Ok(UiStringIds::new(
+        id_file
+            .iter()
+            .filter_map(|(name, &id)| Some((name.clone(), u16::try_from(id).ok()?)))
+            .collect(),
+    ))
+}
+
+/// Returns the name and id of every UI string id which has no text in LIST_STRING.STL for
+/// the string database language, these would show as empty messages in the client.
+pub fn find_missing_ui_strings(
+    string_database: &StringDatabase,
+    ui_string_ids: &UiStringIds,
+) -> Vec<(String, u16)> {
+    let mut missing: Vec<(String, u16)> = ui_string_ids
+        .iter()
+        .filter(|&(_, id)| {
+            let mut key = ArrayString::<16>::new();
+            write!(&mut key, "{}", id).ok();
+            string_database
+                .client_strings
+                .get_text_string(string_database.language, &key)
+                .map_or(true, |text| text.is_empty())
+        })
+        .map(|(name, id)| (name.to_string(), id))
+        .collect();
+    missing.sort_by_key(|(_, id)| *id);
+    missing
+}
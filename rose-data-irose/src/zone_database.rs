@@ -1,19 +1,24 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
-use bevy::math::{Quat, Vec2, Vec3, Vec3Swizzles};
+use bevy::math::{Affine3A, Quat, Vec2, Vec3, Vec3Swizzles};
 use log::debug;
 
 use rose_data::{
-    NpcConversationId, NpcId, SkyboxId, StringDatabase, ZoneData, ZoneDatabase, ZoneEventObject,
-    ZoneId, ZoneList, ZoneListEntry, ZoneMonsterSpawnPoint, ZoneNpcSpawn, WORLD_TICKS_PER_DAY,
+    NpcConversationId, NpcId, SkyboxId, StringDatabase, ZoneCollision, ZoneCollisionMesh, ZoneData,
+    ZoneDatabase, ZoneEventObject, ZoneHeightmap, ZoneId, ZoneList, ZoneListEntry,
+    ZoneMonsterSpawnPoint, ZoneNpcSpawn, ZonePvpData, WORLD_TICKS_PER_DAY,
 };
 use rose_file_readers::{
-    stb_column, IfoEventObject, IfoFile, IfoMonsterSpawn, IfoMonsterSpawnPoint, IfoNpc,
-    IfoReadOptions, StbFile, VfsPath, VfsPathBuf, VirtualFilesystem, ZonFile, ZonReadOptions,
+    stb_column, types::Vec4, HimFile, IfoEventObject, IfoFile, IfoMonsterSpawn,
+    IfoMonsterSpawnPoint, IfoNpc, IfoObject, IfoReadOptions, RoseFile, RoseFileReader, StbFile,
+    VfsPath, VfsPathBuf, VirtualFilesystem, ZmsFile, ZonFile, ZonReadOptions, ZscCollisionFlags,
+    ZscFile,
 };
 
 const MIN_SECTOR_SIZE: u32 = 5000;
 const MAX_SECTOR_SIZE: u32 = 12000;
+const COLLISION_CELL_SIZE: f32 = 2000.0;
+const ZONE_PVP_PATH: &str = "3DDATA/STB/ZONE_PVP.YAML";
 
 pub struct StbZone(pub StbFile);
 
@@ -129,6 +134,152 @@ fn create_event_object(
     }
 }
 
+struct ZoneObjectCollisionLoader<'a> {
+    vfs: &'a VirtualFilesystem,
+    zsc: Option<ZscFile>,
+    meshes: HashMap<u16, Option<Arc<ZoneCollisionMesh>>>,
+}
+
+impl<'a> ZoneObjectCollisionLoader<'a> {
+    fn new(vfs: &'a VirtualFilesystem, zsc_path: Option<&str>) -> Self {
+        Self {
+            vfs,
+            zsc: zsc_path.and_then(|path| vfs.read_file::<ZscFile, _>(path).ok()),
+            meshes: HashMap::new(),
+        }
+    }
+
+    fn get_mesh(&mut self, mesh_id: u16) -> Option<Arc<ZoneCollisionMesh>> {
+        let zsc = self.zsc.as_ref()?;
+        let vfs = self.vfs;
+
+        self.meshes
+            .entry(mesh_id)
+            .or_insert_with(|| {
+                let zms = vfs
+                    .read_file::<ZmsFile, _>(zsc.meshes.get(mesh_id as usize)?)
+                    .ok()?;
+
+                // Mesh vertices are in metres, whereas zone coordinates are in centimetres
+                let vertex = |index: u16| {
+                    zms.position
+                        .get(index as usize)
+                        .map(|position| Vec3::from(*position) * 100.0)
+                };
+                let triangles = zms
+                    .indices
+                    .chunks_exact(3)
+                    .filter_map(|triangle| {
+                        Some([
+                            vertex(triangle[0])?,
+                            vertex(triangle[1])?,
+                            vertex(triangle[2])?,
+                        ])
+                    })
+                    .collect();
+                ZoneCollisionMesh::new(triangles).map(Arc::new)
+            })
+            .clone()
+    }
+
+    fn add_object(
+        &mut self,
+        collision: &mut ZoneCollision,
+        object: &IfoObject,
+        object_offset: Vec3,
+        max_extent: f32,
+    ) {
+        let Some(zsc_object) = self
+            .zsc
+            .as_ref()
+            .and_then(|zsc| zsc.objects.get(object.object_id as usize))
+        else {
+            return;
+        };
+
+        let object_transform = Affine3A::from_scale_rotation_translation(
+            Vec3::new(object.scale.x, object.scale.y, object.scale.z),
+            to_quat(
+                object.rotation.x,
+                object.rotation.y,
+                object.rotation.z,
+                object.rotation.w,
+            ),
+            Vec3::new(object.position.x, object.position.y, object.position.z) + object_offset,
+        );
+
+        let mut part_transforms: Vec<Affine3A> = Vec::with_capacity(zsc_object.parts.len());
+        let mut collision_parts = Vec::new();
+        for part in zsc_object.parts.iter() {
+            let local_transform = Affine3A::from_scale_rotation_translation(
+                Vec3::new(part.scale.x, part.scale.y, part.scale.z),
+                vec4_to_quat(&part.rotation),
+                Vec3::new(part.position.x, part.position.y, part.position.z),
+            );
+            let parent_transform = part
+                .parent
+                .and_then(|parent| part_transforms.get(parent as usize))
+                .unwrap_or(&object_transform);
+            let part_transform = *parent_transform * local_transform;
+            part_transforms.push(part_transform);
+
+            if part.collision_shape.is_some()
+                && !part
+                    .collision_flags
+                    .intersects(ZscCollisionFlags::HEIGHT_ONLY | ZscCollisionFlags::PASSTHROUGH)
+            {
+                collision_parts.push((part.mesh_id, part_transform));
+            }
+        }
+
+        for (mesh_id, part_transform) in collision_parts {
+            if let Some(mesh) = self.get_mesh(mesh_id) {
+                let (min, max) = mesh.world_bounds(&part_transform);
+
+                // Ignore huge objects like ground planes which would block everything
+                if (max - min).max_element() * 0.5 < max_extent {
+                    collision.add_object(mesh, part_transform);
+                }
+            }
+        }
+    }
+}
+
+fn to_quat(x: f32, y: f32, z: f32, w: f32) -> Quat {
+    let quat = Quat::from_xyzw(x, y, z, w);
+    if quat.length_squared() < f32::EPSILON {
+        Quat::IDENTITY
+    } else {
+        quat.normalize()
+    }
+}
+
+fn vec4_to_quat(rotation: &Vec4<f32>) -> Quat {
+    to_quat(rotation.x, rotation.y, rotation.z, rotation.w)
+}
+
+/// FNV-1a hash of the zone files, so the checksum is stable between runs and platforms.
+struct ZoneChecksum(u32);
+
+impl ZoneChecksum {
+    fn new() -> Self {
+        Self(0x811c9dc5)
+    }
+
+    fn read_file<'a, T: RoseFile, P: Into<VfsPath<'a>>>(
+        &mut self,
+        vfs: &VirtualFilesystem,
+        path: P,
+        options: &T::ReadOptions,
+    ) -> Result<T, anyhow::Error> {
+        let file = vfs.open_file(path)?;
+        for byte in file.as_bytes() {
+            self.0 = (self.0 ^ *byte as u32).wrapping_mul(0x01000193);
+        }
+        T::read(RoseFileReader::from(&file), options)
+    }
+}
+
 fn load_zone(
     vfs: &VirtualFilesystem,
     data: &StbZone,
@@ -141,8 +292,10 @@ fn load_zone(
         .parent()
         .ok_or(LoadZoneError::ZonFileInvalidPath)?;
 
-    let zon_file: ZonFile = vfs
-        .read_file_with(
+    let mut checksum = ZoneChecksum::new();
+    let zon_file: ZonFile = checksum
+        .read_file(
+            vfs,
             &zone_file,
             &ZonReadOptions {
                 skip_zone_info: false,
@@ -158,6 +311,15 @@ fn load_zone(
     let mut event_objects = Vec::new();
 
     let mut num_blocks = 0;
+    let mut heightmap = ZoneHeightmap::new(
+        16.0 * zon_file.grid_per_patch * zon_file.grid_size,
+        zon_file.grid_size,
+    );
+    let mut collision = ZoneCollision::new(COLLISION_CELL_SIZE);
+    let mut cnst_collision_loader =
+        ZoneObjectCollisionLoader::new(vfs, data.get_zone_cnst_table(id));
+    let mut deco_collision_loader =
+        ZoneObjectCollisionLoader::new(vfs, data.get_zone_deco_table(id));
     let mut min_block_x = None;
     let mut min_block_y = None;
     let mut max_block_x = None;
@@ -177,8 +339,8 @@ fn load_zone(
         skip_npcs: false,
         skip_animated_objects: true,
         skip_collision_objects: true,
-        skip_cnst_objects: true,
-        skip_deco_objects: true,
+        skip_cnst_objects: false,
+        skip_deco_objects: false,
         skip_effect_objects: true,
         skip_sound_objects: true,
         skip_water_planes: true,
@@ -187,7 +349,8 @@ fn load_zone(
 
     for block_y in 0..64u32 {
         for block_x in 0..64u32 {
-            if let Ok(ifo_file) = vfs.read_file_with::<IfoFile, _>(
+            if let Ok(ifo_file) = checksum.read_file::<IfoFile, _>(
+                vfs,
                 zone_base_directory.join(format!("{}_{}.IFO", block_x, block_y)),
                 &ifo_read_options,
             ) {
@@ -211,6 +374,22 @@ fn load_zone(
                         block_y as i32,
                     )
                 }));
+                for object in ifo_file.cnst_objects.iter() {
+                    cnst_collision_loader.add_object(
+                        &mut collision,
+                        object,
+                        objects_offset,
+                        heightmap.block_size,
+                    );
+                }
+                for object in ifo_file.deco_objects.iter() {
+                    deco_collision_loader.add_object(
+                        &mut collision,
+                        object,
+                        objects_offset,
+                        heightmap.block_size,
+                    );
+                }
                 num_blocks += 1;
 
                 min_block_x = Some(min_block_x.map_or(block_x, |value| u32::min(value, block_x)));
@@ -218,6 +397,14 @@ fn load_zone(
                 max_block_x = Some(max_block_x.map_or(block_x, |value| u32::max(value, block_x)));
                 max_block_y = Some(max_block_y.map_or(block_y, |value| u32::max(value, block_y)));
             }
+
+            if let Ok(him_file) = checksum.read_file::<HimFile, _>(
+                vfs,
+                zone_base_directory.join(format!("{}_{}.HIM", block_x, block_y)),
+                &Default::default(),
+            ) {
+                heightmap.add_block(block_x as i32, block_y as i32, him_file);
+            }
         }
     }
 
@@ -268,12 +455,13 @@ fn load_zone(
         .as_ref()
         .map_or("", |x| unsafe { std::mem::transmute(x.description) });
     debug!(
-        "Loaded zone {} {} blocks: {}, spawns: {}, npcs: {}, sectors ({}, {}), start: {}",
+        "Loaded zone {} {} blocks: {}, spawns: {}, npcs: {}, collision objects: {}, sectors ({}, {}), start: {}",
         id,
         name,
         num_blocks,
         monster_spawns.len(),
         npcs.len(),
+        collision.len(),
         num_sectors_x,
         num_sectors_y,
         start_position.xy(),
@@ -319,6 +507,10 @@ fn load_zone(
             .get_zone_night_time(id)
             .unwrap_or((5 * WORLD_TICKS_PER_DAY / 6) as u32),
         skybox_id: data.get_zone_skybox_id(id),
+        heightmap,
+        collision,
+        checksum: checksum.0,
+        pvp: None,
     })
 }
 
@@ -333,6 +525,18 @@ pub fn get_zone_database(
         zones.push(load_zone(vfs, &data, &string_database, id).ok());
     }
 
+    if vfs.exists(ZONE_PVP_PATH) {
+        let file = vfs.open_file(ZONE_PVP_PATH)?;
+        let zone_pvps: Vec<ZonePvpData> = serde_yaml::from_slice(file.as_bytes())?;
+
+        debug!("Loaded PvP rules for {} zones", zone_pvps.len());
+        for zone_pvp in zone_pvps {
+            if let Some(Some(zone_data)) = zones.get_mut(zone_pvp.zone.get() as usize) {
+                zone_data.pvp = Some(zone_pvp);
+            }
+        }
+    }
+
     Ok(ZoneDatabase::new(string_database, zones))
 }
 
@@ -0,0 +1,168 @@
+//! Compares walking the idle trigger of many npcs through the precompiled `AiProgram` against the
+//! previous implementation, which looked up each `AipFile` in a `HashMap` and resolved ids and
+//! strings as every action was run.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::{collections::HashMap, time::Duration};
+
+use rose_data::{AiAction, AiDatabase, MotionId, QuestTriggerHash, SkillId};
+use rose_file_readers::{
+    AipAction, AipCondition, AipEvent, AipFile, AipMessageType, AipOperatorType, AipSkillTarget,
+    AipTrigger, AipVariableType,
+};
+
+const NUM_AI_FILES: u16 = 200;
+const NUM_EVENTS: usize = 8;
+const NUM_NPCS: usize = 1000;
+
+fn create_aip(index: u16) -> AipFile {
+    let events = (0..NUM_EVENTS)
+        .map(|event| AipEvent {
+            name: format!("event {}", event),
+            conditions: vec![
+                AipCondition::HealthPercent(AipOperatorType::LessThan, 50),
+                AipCondition::IsDaytime(event % 2 == 0),
+                AipCondition::Variable(AipVariableType::Ai, event, AipOperatorType::Equals, 0),
+            ],
+            actions: vec![
+                AipAction::Emote(event as i32 + 1),
+                AipAction::UseSkill(AipSkillTarget::Target, index as i32 + 1, 10),
+                AipAction::Message(AipMessageType::Say, event),
+                AipAction::DoQuestTrigger(format!("trigger-{}-{}", index, event)),
+            ],
+        })
+        .collect();
+
+    AipFile {
+        idle_trigger_interval: Duration::from_secs(4),
+        damage_trigger_new_target_chance: 50,
+        trigger_on_created: None,
+        trigger_on_idle: Some(AipTrigger {
+            name: format!("idle {}", index),
+            events,
+        }),
+        trigger_on_attack_move: None,
+        trigger_on_damaged: None,
+        trigger_on_kill: None,
+        trigger_on_dead: None,
+    }
+}
+
+fn create_strings() -> HashMap<u16, String> {
+    (0..NUM_EVENTS as u16)
+        .map(|index| (index, format!("message {}", index)))
+        .collect()
+}
+
+fn create_aips() -> HashMap<u16, AipFile> {
+    (1..=NUM_AI_FILES)
+        .map(|index| (index, create_aip(index)))
+        .collect()
+}
+
+fn npc_ai_indices() -> Vec<u16> {
+    (0..NUM_NPCS)
+        .map(|npc| (npc as u16 % NUM_AI_FILES) + 1)
+        .collect()
+}
+
+fn run_aip_files(
+    aips: &HashMap<u16, AipFile>,
+    strings: &HashMap<u16, String>,
+    npcs: &[u16],
+) -> usize {
+    let mut count = 0;
+    for ai_index in npcs {
+        let Some(trigger) = aips
+            .get(ai_index)
+            .and_then(|aip| aip.trigger_on_idle.as_ref())
+        else {
+            continue;
+        };
+
+        for event in trigger.events.iter() {
+            count += black_box(&event.conditions).len();
+            for action in event.actions.iter() {
+                match *action {
+                    AipAction::Emote(motion_id) => {
+                        black_box(MotionId::new(motion_id as u16));
+                    }
+                    AipAction::UseSkill(_, skill_id, motion_id) => {
+                        black_box((
+                            SkillId::new(skill_id as u16),
+                            MotionId::new(motion_id as u16),
+                            MotionId::new(motion_id as u16 + 1),
+                        ));
+                    }
+                    AipAction::Message(_, string_id) => {
+                        black_box(strings.get(&(string_id as u16)));
+                    }
+                    AipAction::DoQuestTrigger(ref trigger_name) => {
+                        black_box(QuestTriggerHash::from(trigger_name.as_str()));
+                    }
+                    _ => {}
+                }
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+fn run_ai_programs(ai: &AiDatabase, npcs: &[u16]) -> usize {
+    let mut count = 0;
+    for ai_index in npcs {
+        let Some(program) = ai.get_ai(*ai_index as usize) else {
+            continue;
+        };
+        let Some(trigger) = program.trigger_on_idle.as_ref() else {
+            continue;
+        };
+
+        for event in program.get_trigger_events(trigger) {
+            count += black_box(program.get_event_conditions(event)).len();
+            for action in program.get_event_actions(event) {
+                match action {
+                    AiAction::Emote(motion_id) => {
+                        black_box(motion_id);
+                    }
+                    AiAction::UseSkill(_, skill_id, cast_motion_id, action_motion_id) => {
+                        black_box((skill_id, cast_motion_id, action_motion_id));
+                    }
+                    AiAction::Message(_, message) => {
+                        black_box(message);
+                    }
+                    AiAction::DoQuestTrigger(trigger_hash) => {
+                        black_box(trigger_hash);
+                    }
+                    _ => {}
+                }
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+fn ai_trigger_benchmark(c: &mut Criterion) {
+    let npcs = npc_ai_indices();
+    let strings = create_strings();
+    let aips = create_aips();
+    let ai = AiDatabase::new(create_strings(), create_aips(), HashMap::new());
+
+    // Both implementations must visit the same conditions and actions
+    assert_eq!(
+        run_aip_files(&aips, &strings, &npcs),
+        run_ai_programs(&ai, &npcs)
+    );
+
+    let mut group = c.benchmark_group("ai_idle_trigger");
+    group.bench_function("aip_file", |b| {
+        b.iter(|| run_aip_files(&aips, &strings, &npcs))
+    });
+    group.bench_function("ai_program", |b| b.iter(|| run_ai_programs(&ai, &npcs)));
+    group.finish();
+}
+
+criterion_group!(benches, ai_trigger_benchmark);
+criterion_main!(benches);
@@ -1,18 +1,267 @@
-use rose_file_readers::AipFile;
-use std::collections::HashMap;
+use rose_file_readers::{
+    AipAbilityType, AipAction, AipAttackNearbyStat, AipCondition, AipDistance, AipEvent, AipFile,
+    AipItemBase1000, AipMessageType, AipMonsterSpawnState, AipMoveMode, AipMoveOrigin,
+    AipNearbyAlly, AipResultOperator, AipSkillTarget, AipSpawnNpcOrigin, AipTrigger,
+    AipVariableType,
+};
+use std::{collections::HashMap, ops::Range, path::PathBuf, sync::Arc, time::Duration};
+
+use crate::{MotionId, NpcId, QuestTriggerHash, SkillId, ZoneId};
+
+/// An AIP action with its ids and strings resolved at load time.
+///
+/// Actions which can never do anything, such as those referencing an invalid npc or skill, or
+/// client side only actions, are removed when the program is compiled.
+#[derive(Debug)]
+pub enum AiAction {
+    Stop,
+    Emote(MotionId),
+    MoveRandomDistance(AipMoveOrigin, AipMoveMode, AipDistance),
+    AttackNearbyEntityByStat(AipDistance, AipAbilityType, AipAttackNearbyStat),
+    MoveAwayFromTarget(AipMoveMode, AipDistance),
+    TransformNpc(NpcId),
+    SpawnNpc(NpcId, AipDistance, AipSpawnNpcOrigin, bool),
+    NearbyAlliesAttackTarget(AipDistance, AipNearbyAlly, Option<usize>),
+    AttackNearChar,
+    AttackFindChar,
+    AttackAttacker,
+    RunAway(AipDistance),
+    DropRandomItem(Vec<AipItemBase1000>),
+    KillSelf,
+    /// Skill, cast motion, action motion
+    UseSkill(AipSkillTarget, SkillId, MotionId, MotionId),
+    SetVariable(AipVariableType, usize, AipResultOperator, i32),
+    Message(AipMessageType, Arc<str>),
+    MoveNearOwner,
+    DoQuestTrigger(QuestTriggerHash),
+    AttackOwnerTarget,
+    SetPvpFlag(Option<ZoneId>, bool),
+    SetMonsterSpawnState(Option<ZoneId>, AipMonsterSpawnState),
+    GiveItemToOwner(AipItemBase1000, usize),
+}
+
+impl AiAction {
+    fn compile(action: AipAction, strings: &HashMap<u16, Arc<str>>) -> Option<Self> {
+        Some(match action {
+            AipAction::Stop => AiAction::Stop,
+            AipAction::Emote(motion_id) => AiAction::Emote(MotionId::new(motion_id as u16)),
+            AipAction::Say(_) | AipAction::SpecialAttack => return None,
+            AipAction::MoveRandomDistance(origin, move_mode, distance) => {
+                AiAction::MoveRandomDistance(origin, move_mode, distance)
+            }
+            AipAction::AttackNearbyEntityByStat(distance, ability_type, stat) => {
+                AiAction::AttackNearbyEntityByStat(distance, ability_type, stat)
+            }
+            AipAction::MoveAwayFromTarget(move_mode, distance) => {
+                AiAction::MoveAwayFromTarget(move_mode, distance)
+            }
+            AipAction::TransformNpc(npc_id) => AiAction::TransformNpc(NpcId::new(npc_id as u16)?),
+            AipAction::SpawnNpc(npc_id, distance, origin, is_owner) => {
+                AiAction::SpawnNpc(NpcId::new(npc_id as u16)?, distance, origin, is_owner)
+            }
+            AipAction::NearbyAlliesAttackTarget(distance, nearby_ally, limit) => {
+                AiAction::NearbyAlliesAttackTarget(distance, nearby_ally, limit)
+            }
+            AipAction::AttackNearChar => AiAction::AttackNearChar,
+            AipAction::AttackFindChar => AiAction::AttackFindChar,
+            AipAction::AttackAttacker => AiAction::AttackAttacker,
+            AipAction::RunAway(distance) => AiAction::RunAway(distance),
+            AipAction::DropRandomItem(items) => AiAction::DropRandomItem(items),
+            AipAction::KillSelf => AiAction::KillSelf,
+            AipAction::UseSkill(target, skill_id, motion_id) => AiAction::UseSkill(
+                target,
+                SkillId::new(skill_id as u16)?,
+                MotionId::new(motion_id as u16),
+                MotionId::new(motion_id as u16 + 1),
+            ),
+            AipAction::SetVariable(variable_type, variable_id, operator, value) => {
+                AiAction::SetVariable(variable_type, variable_id, operator, value)
+            }
+            AipAction::Message(message_type, string_id) => {
+                AiAction::Message(message_type, strings.get(&(string_id as u16))?.clone())
+            }
+            AipAction::MoveNearOwner => AiAction::MoveNearOwner,
+            AipAction::DoQuestTrigger(trigger_name) => {
+                AiAction::DoQuestTrigger(trigger_name.as_str().into())
+            }
+            AipAction::AttackOwnerTarget => AiAction::AttackOwnerTarget,
+            AipAction::SetPvpFlag(zone_id, value) => AiAction::SetPvpFlag(
+                zone_id.and_then(|zone_id| ZoneId::new(zone_id as u16)),
+                value,
+            ),
+            AipAction::SetMonsterSpawnState(zone_id, state) => AiAction::SetMonsterSpawnState(
+                zone_id.and_then(|zone_id| ZoneId::new(zone_id as u16)),
+                state,
+            ),
+            AipAction::GiveItemToOwner(item, quantity) => AiAction::GiveItemToOwner(item, quantity),
+        })
+    }
+}
+
+pub struct AiEvent {
+    pub name: String,
+    conditions: Range<usize>,
+    actions: Range<usize>,
+}
+
+pub struct AiTrigger {
+    pub name: String,
+    events: Range<usize>,
+}
+
+/// An AIP file flattened into contiguous event, condition and action lists.
+pub struct AiProgram {
+    pub idle_trigger_interval: Duration,
+    pub damage_trigger_new_target_chance: u32,
+    pub trigger_on_created: Option<AiTrigger>,
+    pub trigger_on_idle: Option<AiTrigger>,
+    pub trigger_on_attack_move: Option<AiTrigger>,
+    pub trigger_on_damaged: Option<AiTrigger>,
+    pub trigger_on_kill: Option<AiTrigger>,
+    pub trigger_on_dead: Option<AiTrigger>,
+    events: Vec<AiEvent>,
+    conditions: Vec<AipCondition>,
+    actions: Vec<AiAction>,
+}
+
+impl AiProgram {
+    pub fn compile(aip: AipFile, strings: &HashMap<u16, Arc<str>>) -> Self {
+        let mut program = Self {
+            idle_trigger_interval: aip.idle_trigger_interval,
+            damage_trigger_new_target_chance: aip.damage_trigger_new_target_chance,
+            trigger_on_created: None,
+            trigger_on_idle: None,
+            trigger_on_attack_move: None,
+            trigger_on_damaged: None,
+            trigger_on_kill: None,
+            trigger_on_dead: None,
+            events: Vec::new(),
+            conditions: Vec::new(),
+            actions: Vec::new(),
+        };
+
+        program.trigger_on_created = program.compile_trigger(aip.trigger_on_created, strings);
+        program.trigger_on_idle = program.compile_trigger(aip.trigger_on_idle, strings);
+        program.trigger_on_attack_move =
+            program.compile_trigger(aip.trigger_on_attack_move, strings);
+        program.trigger_on_damaged = program.compile_trigger(aip.trigger_on_damaged, strings);
+        program.trigger_on_kill = program.compile_trigger(aip.trigger_on_kill, strings);
+        program.trigger_on_dead = program.compile_trigger(aip.trigger_on_dead, strings);
+        program
+    }
+
+    fn compile_trigger(
+        &mut self,
+        trigger: Option<AipTrigger>,
+        strings: &HashMap<u16, Arc<str>>,
+    ) -> Option<AiTrigger> {
+        let trigger = trigger?;
+        let events_start = self.events.len();
+
+        for AipEvent {
+            name,
+            conditions,
+            actions,
+        } in trigger.events
+        {
+            let conditions_start = self.conditions.len();
+            self.conditions.extend(conditions);
+
+            let actions_start = self.actions.len();
+            self.actions.extend(
+                actions
+                    .into_iter()
+                    .filter_map(|action| AiAction::compile(action, strings)),
+            );
+
+            self.events.push(AiEvent {
+                name,
+                conditions: conditions_start..self.conditions.len(),
+                actions: actions_start..self.actions.len(),
+            });
+        }
+
+        Some(AiTrigger {
+            name: trigger.name,
+            events: events_start..self.events.len(),
+        })
+    }
+
+    pub fn get_trigger_events(&self, trigger: &AiTrigger) -> &[AiEvent] {
+        &self.events[trigger.events.clone()]
+    }
+
+    pub fn get_event_conditions(&self, event: &AiEvent) -> &[AipCondition] {
+        &self.conditions[event.conditions.clone()]
+    }
+
+    pub fn get_event_actions(&self, event: &AiEvent) -> &[AiAction] {
+        &self.actions[event.actions.clone()]
+    }
+}
+
+#[derive(Default)]
+pub struct AiDatabaseOptions {
+    /// Directory containing yaml AIP files named by npc id, which replace the
+    /// AI from FILE_AI.STB for that npc
+    pub npc_ai_override_path: Option<PathBuf>,
+}
 
 pub struct AiDatabase {
-    pub strings: HashMap<u16, String>,
-    pub aips: HashMap<u16, AipFile>,
+    strings: HashMap<u16, Arc<str>>,
+    programs: Vec<Option<AiProgram>>,
+    npc_overrides: HashMap<NpcId, usize>,
 }
 
 impl AiDatabase {
-    pub fn get_ai(&self, index: usize) -> Option<&AipFile> {
-        self.aips.get(&(index as u16))
+    pub fn new(
+        strings: HashMap<u16, String>,
+        aips: HashMap<u16, AipFile>,
+        npc_override_aips: HashMap<NpcId, AipFile>,
+    ) -> Self {
+        // Messages share the database strings rather than each holding a copy
+        let strings: HashMap<u16, Arc<str>> = strings
+            .into_iter()
+            .map(|(index, string)| (index, Arc::from(string)))
+            .collect();
+
+        let mut programs = Vec::new();
+        for (index, aip) in aips {
+            let index = index as usize;
+            if programs.len() <= index {
+                programs.resize_with(index + 1, || None);
+            }
+            programs[index] = Some(AiProgram::compile(aip, &strings));
+        }
+
+        // Overrides are appended after the stock programs so they can be used by ai index
+        let mut npc_overrides = HashMap::new();
+        for (npc_id, aip) in npc_override_aips {
+            npc_overrides.insert(npc_id, programs.len());
+            programs.push(Some(AiProgram::compile(aip, &strings)));
+        }
+
+        Self {
+            strings,
+            programs,
+            npc_overrides,
+        }
+    }
+
+    pub fn get_ai(&self, index: usize) -> Option<&AiProgram> {
+        self.programs.get(index).and_then(Option::as_ref)
+    }
+
+    /// Returns the ai index to use for a npc, preferring any override over its stock ai file
+    pub fn get_npc_ai_index(&self, npc_id: NpcId, ai_file_index: usize) -> Option<usize> {
+        self.npc_overrides
+            .get(&npc_id)
+            .copied()
+            .or(Some(ai_file_index).filter(|ai_file_index| *ai_file_index != 0))
     }
 
     #[allow(dead_code)]
     pub fn get_ai_string(&self, index: usize) -> Option<&str> {
-        self.strings.get(&(index as u16)).map(String::as_str)
+        self.strings.get(&(index as u16)).map(AsRef::as_ref)
     }
 }
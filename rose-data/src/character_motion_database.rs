@@ -42,6 +42,7 @@ pub struct CharacterMotionDatabase {
     motion_data: Vec<Vec<Option<MotionFileData>>>, // [gender][motion id]
     action_map: EnumMap<CharacterMotionAction, MotionId>,
     vehicle_action_map: EnumMap<VehicleMotionAction, u16>,
+    emote_motions: Vec<MotionId>,
 }
 
 pub struct CharacterMotionDatabaseOptions {
@@ -55,6 +56,7 @@ impl CharacterMotionDatabase {
         motion_paths: Vec<Vec<Option<MotionFileData>>>,
         action_map: EnumMap<CharacterMotionAction, MotionId>,
         vehicle_action_map: EnumMap<VehicleMotionAction, u16>,
+        emote_motions: Vec<MotionId>,
     ) -> Self {
         Self {
             weapon_type_count,
@@ -62,9 +64,15 @@ impl CharacterMotionDatabase {
             motion_data: motion_paths,
             action_map,
             vehicle_action_map,
+            emote_motions,
         }
     }
 
+    /// Returns true if a character is allowed to use the motion as an emote
+    pub fn is_emote_motion(&self, motion_id: MotionId) -> bool {
+        self.emote_motions.contains(&motion_id)
+    }
+
     pub fn get_character_motion(
         &self,
         motion_id: MotionId,
@@ -1,6 +1,7 @@
+use enum_map::Enum;
 use serde::{Deserialize, Serialize};
 
-#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Enum, Serialize, Deserialize)]
 pub enum ClanMemberPosition {
     Penalty,
     Junior,
@@ -10,3 +11,27 @@ pub enum ClanMemberPosition {
     DeputyMaster,
     Master,
 }
+
+impl ClanMemberPosition {
+    pub fn promoted(self) -> Option<Self> {
+        match self {
+            ClanMemberPosition::Penalty => Some(ClanMemberPosition::Junior),
+            ClanMemberPosition::Junior => Some(ClanMemberPosition::Senior),
+            ClanMemberPosition::Senior => Some(ClanMemberPosition::Veteran),
+            ClanMemberPosition::Veteran => Some(ClanMemberPosition::Commander),
+            ClanMemberPosition::Commander => Some(ClanMemberPosition::DeputyMaster),
+            ClanMemberPosition::DeputyMaster | ClanMemberPosition::Master => None,
+        }
+    }
+
+    pub fn demoted(self) -> Option<Self> {
+        match self {
+            ClanMemberPosition::Penalty | ClanMemberPosition::Master => None,
+            ClanMemberPosition::Junior => Some(ClanMemberPosition::Penalty),
+            ClanMemberPosition::Senior => Some(ClanMemberPosition::Junior),
+            ClanMemberPosition::Veteran => Some(ClanMemberPosition::Senior),
+            ClanMemberPosition::Commander => Some(ClanMemberPosition::Veteran),
+            ClanMemberPosition::DeputyMaster => Some(ClanMemberPosition::Commander),
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     AbilityType, AmmoIndex, ClanMemberPosition, EquipmentIndex, ItemClass, ItemReference, ItemType,
-    VehiclePartIndex,
+    SkillPageKind, SkillPageType, VehiclePartIndex,
 };
 
 pub trait DataDecoder {
@@ -14,5 +14,8 @@ pub trait DataDecoder {
     fn decode_vehicle_part_index(&self, id: usize) -> Option<VehiclePartIndex>;
     fn decode_ammo_index(&self, id: usize) -> Option<AmmoIndex>;
 
+    fn decode_skill_page_kind(&self, page: SkillPageType) -> Option<SkillPageKind>;
+    fn encode_skill_page_kind(&self, kind: SkillPageKind) -> Option<SkillPageType>;
+
     fn encode_clan_member_position(&self, position: ClanMemberPosition) -> Option<usize>;
 }
@@ -42,17 +42,42 @@ pub struct EffectData {
     pub hit_sound_id: Option<SoundId>,
 }
 
+impl EffectData {
+    /// Returns the time taken for the bullet to travel the given distance
+    pub fn get_bullet_travel_time(&self, distance: f32) -> Duration {
+        match self.bullet_move_type {
+            Some(EffectBulletMoveType::Linear) | Some(EffectBulletMoveType::Parabola)
+                if self.bullet_speed > 0.0 =>
+            {
+                Duration::from_secs_f32(distance / self.bullet_speed)
+            }
+            _ => Duration::ZERO,
+        }
+    }
+}
+
+pub struct EffectDatabaseOptions {
+    /// Read the effect files used as bullets to calculate how long they play for
+    pub load_bullet_durations: bool,
+}
+
 #[derive(Debug)]
 pub struct EffectDatabase {
     effects: Vec<Option<EffectData>>,
     effect_files: Vec<Option<VfsPathBuf>>,
+    effect_file_durations: Vec<Option<Duration>>,
 }
 
 impl EffectDatabase {
-    pub fn new(effects: Vec<Option<EffectData>>, effect_files: Vec<Option<VfsPathBuf>>) -> Self {
+    pub fn new(
+        effects: Vec<Option<EffectData>>,
+        effect_files: Vec<Option<VfsPathBuf>>,
+        effect_file_durations: Vec<Option<Duration>>,
+    ) -> Self {
         Self {
             effects,
             effect_files,
+            effect_file_durations,
         }
     }
 
@@ -66,6 +91,25 @@ impl EffectDatabase {
             .and_then(|x| x.as_ref())
     }
 
+    /// Returns how long the effect file plays for, None if it loops forever or its
+    /// duration was not loaded. Only the durations of bullet effect files are loaded.
+    pub fn get_effect_file_duration(&self, id: EffectFileId) -> Option<Duration> {
+        self.effect_file_durations
+            .get(id.get() as usize)
+            .copied()
+            .flatten()
+    }
+
+    /// Returns the time taken for the bullet of an effect to reach its target, limited by how
+    /// long the bullet effect file plays for so the result matches what the client shows
+    pub fn get_bullet_travel_time(&self, effect_data: &EffectData, distance: f32) -> Duration {
+        let travel_time = effect_data.get_bullet_travel_time(distance);
+        effect_data
+            .bullet_effect
+            .and_then(|bullet_effect| self.get_effect_file_duration(bullet_effect))
+            .map_or(travel_time, |lifetime| travel_time.min(lifetime))
+    }
+
     pub fn iter_files(&self) -> impl Iterator<Item = (EffectFileId, &VfsPathBuf)> {
         self.effect_files
             .iter()
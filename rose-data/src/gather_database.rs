@@ -0,0 +1,46 @@
+use serde::Deserialize;
+
+use crate::{ItemReference, MotionId, ZoneId};
+
+/// An item which can be rolled when gathering from a node
+#[derive(Clone, Debug, Deserialize)]
+pub struct GatherItemData {
+    pub item: ItemReference,
+    pub quantity: u32,
+    pub weight: u32,
+}
+
+/// An event object which can be gathered from, such as a herb node or fishing spot
+#[derive(Clone, Debug, Deserialize)]
+pub struct GatherNodeData {
+    pub name: String,
+    pub zone: ZoneId,
+    pub event_id: u16,
+    pub gather_millis: u64,
+    pub max_gathers: u32,
+    pub respawn_secs: u64,
+    #[serde(default)]
+    pub motion_id: Option<MotionId>,
+    pub items: Vec<GatherItemData>,
+}
+
+#[derive(Default)]
+pub struct GatherDatabase {
+    nodes: Vec<GatherNodeData>,
+}
+
+impl GatherDatabase {
+    pub fn new(nodes: Vec<GatherNodeData>) -> Self {
+        Self { nodes }
+    }
+
+    pub fn get_node(&self, index: usize) -> Option<&GatherNodeData> {
+        self.nodes.get(index)
+    }
+
+    pub fn find_node(&self, zone: ZoneId, event_id: u16) -> Option<usize> {
+        self.nodes
+            .iter()
+            .position(|node| node.zone == zone && node.event_id == event_id)
+    }
+}
@@ -2,9 +2,9 @@ use enum_map::Enum;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 
-use crate::{BaseItemData, ItemClass, ItemReference, ItemType};
+use crate::{BaseItemData, ItemClass, ItemReference, ItemType, NpcId};
 
-const MAX_STACKABLE_ITEM_QUANTITY: u32 = 999;
+pub const MAX_STACKABLE_ITEM_QUANTITY: u32 = 999;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Enum, Serialize, Deserialize)]
 pub enum EquipmentIndex {
@@ -39,6 +39,29 @@ impl From<EquipmentIndex> for ItemType {
     }
 }
 
+/// Visual only equipment slots, which are displayed over the equipped item by clients that
+/// support them
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Enum, Serialize, Deserialize)]
+pub enum CostumeIndex {
+    Head,
+    Body,
+    Back,
+    Hands,
+    Feet,
+}
+
+impl From<CostumeIndex> for ItemType {
+    fn from(costume_index: CostumeIndex) -> Self {
+        match costume_index {
+            CostumeIndex::Head => ItemType::Head,
+            CostumeIndex::Body => ItemType::Body,
+            CostumeIndex::Back => ItemType::Back,
+            CostumeIndex::Hands => ItemType::Hands,
+            CostumeIndex::Feet => ItemType::Feet,
+        }
+    }
+}
+
 impl TryFrom<ItemType> for EquipmentIndex {
     type Error = ();
 
@@ -157,10 +180,19 @@ impl From<&EquipmentItem> for ItemReference {
     }
 }
 
+/// A monster which has been captured into a summon item
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct CapturedNpc {
+    pub npc_id: NpcId,
+    pub level: u32,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
 pub struct StackableItem {
     pub item: ItemReference,
     pub quantity: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub captured_npc: Option<CapturedNpc>,
 }
 
 #[derive(Debug)]
@@ -173,7 +205,11 @@ pub enum StackError {
 impl StackableItem {
     pub fn new(item: ItemReference, quantity: u32) -> Option<StackableItem> {
         if item.item_type.is_stackable_item() && item.item_number != 0 && quantity > 0 {
-            Some(StackableItem { item, quantity })
+            Some(StackableItem {
+                item,
+                quantity,
+                captured_npc: None,
+            })
         } else {
             None
         }
@@ -184,7 +220,10 @@ impl StackableItem {
     }
 
     pub fn can_stack_with(&self, stackable: &StackableItem) -> Result<(), StackError> {
-        if self.item != stackable.item {
+        if self.captured_npc.is_some() || stackable.captured_npc.is_some() {
+            // Each captured monster is unique
+            Err(StackError::NotStackable)
+        } else if self.item != stackable.item {
             Err(StackError::NotSameItem)
         } else if self.quantity + stackable.quantity > MAX_STACKABLE_ITEM_QUANTITY {
             Err(StackError::PartialStack(
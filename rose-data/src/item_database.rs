@@ -170,6 +170,7 @@ impl ItemType {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub enum ItemClass {
     Unknown,
 
@@ -291,6 +292,7 @@ impl ItemClass {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct BaseItemData {
     pub id: ItemReference,
     pub name: &'static str,
@@ -319,44 +321,52 @@ pub struct BaseItemData {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct FaceItemData {
     pub item_data: BaseItemData,
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct HeadItemData {
     pub item_data: BaseItemData,
     pub hair_type: u32,
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct BodyItemData {
     pub item_data: BaseItemData,
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct HandsItemData {
     pub item_data: BaseItemData,
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct BackItemData {
     pub item_data: BaseItemData,
     pub move_speed: u32,
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct FeetItemData {
     pub item_data: BaseItemData,
     pub move_speed: u32,
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct JewelleryItemData {
     pub item_data: BaseItemData,
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct GemItemData {
     pub item_data: BaseItemData,
     pub gem_add_ability: ArrayVec<(AbilityType, i32), 2>,
@@ -365,6 +375,7 @@ pub struct GemItemData {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct WeaponItemData {
     pub item_data: BaseItemData,
     pub attack_range: i32,
@@ -381,12 +392,14 @@ pub struct WeaponItemData {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct SubWeaponItemData {
     pub item_data: BaseItemData,
     pub gem_position: u32,
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct ConsumableItemData {
     pub item_data: BaseItemData,
     pub store_skin: i32,
@@ -404,24 +417,27 @@ pub struct ConsumableItemData {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct MaterialItemData {
     pub item_data: BaseItemData,
     pub bullet_effect_id: Option<EffectId>,
 }
 
 #[derive(Debug)]
-
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct QuestItemData {
     pub item_data: BaseItemData,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub enum VehicleType {
     Cart,
     CastleGear,
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct VehicleItemData {
     pub item_data: BaseItemData,
     pub vehicle_type: VehicleType,
@@ -453,6 +469,7 @@ pub struct VehicleItemData {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct ItemGradeData {
     pub attack: i32,
     pub hit: i32,
@@ -464,6 +481,7 @@ pub struct ItemGradeData {
 
 #[allow(dead_code)]
 #[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub enum ItemData<'a> {
     Face(&'a FaceItemData),
     Head(&'a HeadItemData),
@@ -816,4 +834,22 @@ impl ItemDatabase {
             )),
         }
     }
+
+    /// Iterates over every item of every item type
+    pub fn iter_all_items(&self) -> impl Iterator<Item = ItemReference> + '_ {
+        (0..ItemType::LENGTH)
+            .map(ItemType::from_usize)
+            .flat_map(|item_type| self.iter_items(item_type))
+    }
+
+    /// Iterates over the base item data of every item of the given item type
+    pub fn iter_base_items(
+        &self,
+        item_type: ItemType,
+    ) -> impl Iterator<Item = (ItemReference, &BaseItemData)> + '_ {
+        self.iter_items(item_type).filter_map(|item_reference| {
+            self.get_base_item(item_reference)
+                .map(|base_item_data| (item_reference, base_item_data))
+        })
+    }
 }
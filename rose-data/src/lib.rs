@@ -66,24 +66,30 @@ mod clan;
 mod client_strings;
 mod data_decoder;
 mod effect_database;
+mod gather_database;
 mod item;
 mod item_database;
 mod job_class_database;
 mod motion_file_data;
 mod npc_database;
 mod quest_database;
+mod schedule_window;
 mod skill_database;
 mod skybox_database;
 mod sound_database;
+mod sprite_database;
 mod status_effect_database;
 mod string_database;
+mod teleport_anchor_database;
+mod ui_string_ids;
 mod warp_gate_database;
 mod world;
+mod zone_collision;
 mod zone_database;
 mod zone_list;
 
 pub use ability::AbilityType;
-pub use ai_database::AiDatabase;
+pub use ai_database::{AiAction, AiDatabase, AiDatabaseOptions, AiEvent, AiProgram, AiTrigger};
 pub use animation_event_flags::AnimationEventFlags;
 pub use character_motion_database::{
     CharacterMotionAction, CharacterMotionDatabase, CharacterMotionDatabaseOptions,
@@ -93,11 +99,13 @@ pub use clan::ClanMemberPosition;
 pub use client_strings::ClientStrings;
 pub use data_decoder::DataDecoder;
 pub use effect_database::{
-    EffectBulletMoveType, EffectData, EffectDatabase, EffectFileId, EffectId,
+    EffectBulletMoveType, EffectData, EffectDatabase, EffectDatabaseOptions, EffectFileId, EffectId,
 };
+pub use gather_database::{GatherDatabase, GatherItemData, GatherNodeData};
 pub use item::{
-    AmmoIndex, EquipmentIndex, EquipmentItem, Item, ItemSlotBehaviour, ItemWeaponType, StackError,
-    StackableItem, StackableSlotBehaviour, VehiclePartIndex,
+    AmmoIndex, CapturedNpc, CostumeIndex, EquipmentIndex, EquipmentItem, Item, ItemSlotBehaviour,
+    ItemWeaponType, StackError, StackableItem, StackableSlotBehaviour, VehiclePartIndex,
+    MAX_STACKABLE_ITEM_QUANTITY,
 };
 pub use item_database::{
     BackItemData, BaseItemData, BodyItemData, ConsumableItemData, FaceItemData, FeetItemData,
@@ -111,25 +119,35 @@ pub use npc_database::{
     NpcConversationData, NpcConversationId, NpcData, NpcDatabase, NpcDatabaseOptions, NpcId,
     NpcMotionAction, NpcStoreTabData, NpcStoreTabId,
 };
-pub use quest_database::{QuestData, QuestDatabase, QuestTrigger, QuestTriggerHash};
+pub use quest_database::{
+    QuestData, QuestDatabase, QuestDatabaseOptions, QuestTrigger, QuestTriggerHash,
+};
+pub use schedule_window::ScheduleWindow;
 pub use skill_database::{
     SkillActionMode, SkillAddAbility, SkillBasicCommand, SkillCastingEffect, SkillCooldown,
-    SkillCooldownGroup, SkillData, SkillDatabase, SkillId, SkillPageType, SkillTargetFilter,
-    SkillType,
+    SkillCooldownGroup, SkillData, SkillDatabase, SkillId, SkillPageKind, SkillPageType,
+    SkillRuleData, SkillTargetFilter, SkillType,
 };
 pub use skybox_database::{SkyboxData, SkyboxDatabase, SkyboxId, SkyboxState};
 pub use sound_database::{SoundData, SoundDatabase, SoundId};
+pub use sprite_database::{SpriteData, SpriteDatabase, SpriteSheet, SpriteSheetType};
 pub use status_effect_database::{
     StatusEffectClearedByType, StatusEffectData, StatusEffectDatabase, StatusEffectId,
     StatusEffectType,
 };
-pub use string_database::StringDatabase;
+pub use string_database::{
+    StringDatabase, StringDatabaseOptions, STRING_DATABASE_FALLBACK_LANGUAGE,
+};
+pub use teleport_anchor_database::{TeleportAnchorData, TeleportAnchorDatabase};
+pub use ui_string_ids::UiStringIds;
 pub use warp_gate_database::{WarpGateData, WarpGateDatabase, WarpGateId};
 pub use world::{
     WorldTicks, WORLD_DAYS_PER_MONTH, WORLD_MONTH_PER_YEAR, WORLD_TICKS_PER_DAY,
     WORLD_TICKS_PER_MONTH, WORLD_TICKS_PER_YEAR, WORLD_TICK_DURATION,
 };
+pub use zone_collision::{ZoneCollision, ZoneCollisionMesh};
 pub use zone_database::{
-    ZoneData, ZoneDatabase, ZoneEventObject, ZoneId, ZoneMonsterSpawnPoint, ZoneNpcSpawn,
+    ZoneData, ZoneDatabase, ZoneEventObject, ZoneHeightmap, ZoneId, ZoneMonsterSpawnPoint,
+    ZoneNpcSpawn, ZonePvpData, ZoneSafeArea,
 };
 pub use zone_list::{ZoneList, ZoneListEntry};
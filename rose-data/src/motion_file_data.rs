@@ -1,9 +1,11 @@
 use bevy::reflect::Reflect;
 use serde::{Deserialize, Serialize};
-use std::{str::FromStr, time::Duration};
+use std::{collections::HashMap, str::FromStr, time::Duration};
 
 use rose_file_readers::VfsPathBuf;
 
+use crate::AnimationEventFlags;
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize, Reflect)]
 pub struct MotionId(u16);
 
@@ -12,6 +14,77 @@ id_wrapper_impl!(MotionId, u16);
 #[derive(Clone, Default)]
 pub struct MotionFileData {
     pub path: VfsPathBuf,
-    pub duration: Duration,
-    pub total_attack_frames: usize,
+    duration: Duration,
+    total_frames: usize,
+    total_attack_frames: usize,
+    /// Start time and flags of each frame with an animation event
+    frame_events: Vec<(Duration, AnimationEventFlags)>,
+    /// Start time of each frame with an animation event, keyed by single event flag
+    event_frame_times: HashMap<AnimationEventFlags, Vec<Duration>>,
+}
+
+impl MotionFileData {
+    pub fn new(
+        path: VfsPathBuf,
+        duration: Duration,
+        total_frames: usize,
+        total_attack_frames: usize,
+        frame_events: Vec<(Duration, AnimationEventFlags)>,
+    ) -> Self {
+        let mut event_frame_times: HashMap<AnimationEventFlags, Vec<Duration>> = HashMap::new();
+        for &(time, flags) in frame_events.iter() {
+            for flag in flags.iter() {
+                event_frame_times.entry(flag).or_default().push(time);
+            }
+        }
+
+        Self {
+            path,
+            duration,
+            total_frames,
+            total_attack_frames,
+            frame_events,
+            event_frame_times,
+        }
+    }
+
+    /// Creates motion data for a motion whose frame data was not loaded
+    pub fn with_path(path: VfsPathBuf) -> Self {
+        Self {
+            path,
+            ..Default::default()
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    pub fn total_frames(&self) -> usize {
+        self.total_frames
+    }
+
+    pub fn total_attack_frames(&self) -> usize {
+        self.total_attack_frames
+    }
+
+    /// Returns the start time and flags of each frame with an animation event
+    pub fn frame_events(&self) -> &[(Duration, AnimationEventFlags)] {
+        &self.frame_events
+    }
+
+    /// Returns the start time of every frame with the given single animation event flag
+    pub fn event_frame_times(&self, flag: AnimationEventFlags) -> &[Duration] {
+        self.event_frame_times
+            .get(&flag)
+            .map_or(&[], |times| times.as_slice())
+    }
+
+    /// Returns the time of the first frame with any of the given animation event flags
+    pub fn find_frame_event(&self, flags: AnimationEventFlags) -> Option<Duration> {
+        flags
+            .iter()
+            .filter_map(|flag| self.event_frame_times(flag).first().copied())
+            .min()
+    }
 }
@@ -18,11 +18,13 @@ pub struct NpcId(NonZeroU16);
 id_wrapper_impl!(NpcId, NonZeroU16, u16);
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct NpcConversationId(String);
 
 id_wrapper_impl!(NpcConversationId, String);
 
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct NpcStoreTabId(NonZeroU16);
 
 id_wrapper_impl!(NpcStoreTabId, NonZeroU16, u16);
@@ -42,6 +44,7 @@ pub enum NpcMotionAction {
     Etc,
 }
 
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct NpcData {
     pub id: NpcId,
     pub name: &'static str,
@@ -86,9 +89,11 @@ pub struct NpcData {
     pub create_sound_id: Option<SoundId>,
     pub death_quest_trigger_name: String,
     pub npc_height: i32,
+    #[cfg_attr(feature = "serialize", serde(skip))]
     pub motion_data: Vec<(MotionId, MotionFileData)>,
 }
 
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct NpcConversationData {
     pub index: usize,
     pub name: String,
@@ -97,6 +102,7 @@ pub struct NpcConversationData {
     pub filename: String,
 }
 
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct NpcStoreTabData {
     pub name: &'static str,
     pub items: HashMap<u16, ItemReference>,
@@ -173,4 +179,12 @@ impl NpcDatabase {
     pub fn iter(&self) -> impl Iterator<Item = &NpcData> {
         self.npcs.iter().filter_map(|npc_data| npc_data.as_ref())
     }
+
+    pub fn iter_conversations(&self) -> impl Iterator<Item = &NpcConversationData> {
+        self.conversation_files.values()
+    }
+
+    pub fn iter_store_tabs(&self) -> impl Iterator<Item = (NpcStoreTabId, &NpcStoreTabData)> {
+        self.store_tabs.iter().map(|(id, data)| (*id, data))
+    }
 }
@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, num::Wrapping, sync::Arc};
+use std::{collections::HashMap, num::Wrapping, path::PathBuf, sync::Arc};
 
 use crate::{StringDatabase, WorldTicks};
 
@@ -14,6 +14,13 @@ pub struct QuestData {
     pub time_limit: Option<WorldTicks>,
 }
 
+#[derive(Default)]
+pub struct QuestDatabaseOptions {
+    /// Directory containing editor friendly yaml QSD files which are loaded
+    /// after, and override triggers from, the binary QSD files
+    pub yaml_qsd_path: Option<PathBuf>,
+}
+
 #[derive(Copy, Clone, Hash, Eq, PartialEq, Debug, Serialize, Deserialize)]
 pub struct QuestTriggerHash {
     pub hash: u32,
@@ -86,6 +93,8 @@ pub struct QuestDatabase {
     pub strings: HashMap<u16, String>,
     pub triggers: HashMap<String, QuestTrigger>,
     pub triggers_by_hash: HashMap<QuestTriggerHash, String>,
+    /// Triggers run when a new character first joins the world
+    pub tutorial_triggers: Vec<QuestTriggerHash>,
 }
 
 impl QuestDatabase {
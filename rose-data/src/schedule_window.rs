@@ -0,0 +1,79 @@
+use chrono::{Datelike, Timelike};
+use serde::Deserialize;
+
+/// A recurring local time window, used by the event calendar and zone PvP schedules.
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[derive(Clone, Debug, Deserialize)]
+pub struct ScheduleWindow {
+    /// Days the window is active, 0 = Monday. Empty means every day.
+    #[serde(default)]
+    pub weekdays: Vec<u32>,
+    pub start_hour: u32,
+    pub end_hour: u32,
+}
+
+impl ScheduleWindow {
+    pub fn is_active_now(&self) -> bool {
+        let now = chrono::Local::now();
+        self.is_active(now.weekday().num_days_from_monday(), now.hour())
+    }
+
+    pub fn is_active(&self, weekday: u32, hour: u32) -> bool {
+        if !self.weekdays.is_empty() && !self.weekdays.contains(&weekday) {
+            return false;
+        }
+
+        if self.start_hour <= self.end_hour {
+            hour >= self.start_hour && hour < self.end_hour
+        } else {
+            // Window wraps over midnight
+            hour >= self.start_hour || hour < self.end_hour
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_within_day() {
+        let window = ScheduleWindow {
+            weekdays: Vec::new(),
+            start_hour: 9,
+            end_hour: 17,
+        };
+        assert!(!window.is_active(0, 8));
+        assert!(window.is_active(0, 9));
+        assert!(window.is_active(6, 16));
+        assert!(!window.is_active(6, 17));
+    }
+
+    #[test]
+    fn window_wraps_over_midnight() {
+        let window = ScheduleWindow {
+            weekdays: Vec::new(),
+            start_hour: 22,
+            end_hour: 2,
+        };
+        assert!(!window.is_active(0, 21));
+        assert!(window.is_active(0, 22));
+        assert!(window.is_active(0, 23));
+        assert!(window.is_active(0, 0));
+        assert!(window.is_active(0, 1));
+        assert!(!window.is_active(0, 2));
+        assert!(!window.is_active(0, 12));
+    }
+
+    #[test]
+    fn window_only_on_weekdays() {
+        let window = ScheduleWindow {
+            weekdays: vec![5, 6],
+            start_hour: 20,
+            end_hour: 23,
+        };
+        assert!(!window.is_active(4, 21));
+        assert!(window.is_active(5, 21));
+        assert!(window.is_active(6, 21));
+    }
+}
@@ -20,7 +20,17 @@ id_wrapper_impl!(SkillId, NonZeroU16, u16);
 
 pub type SkillPageType = usize;
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub enum SkillPageKind {
+    Basic,
+    Active,
+    Passive,
+    Clan,
+}
+
 #[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct SkillAddAbility {
     pub ability_type: AbilityType,
     pub rate: i32,
@@ -28,6 +38,7 @@ pub struct SkillAddAbility {
 }
 
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub enum SkillActionMode {
     Stop,
     Attack,
@@ -35,6 +46,7 @@ pub enum SkillActionMode {
 }
 
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub enum SkillTargetFilter {
     OnlySelf,
     Group,
@@ -50,6 +62,7 @@ pub enum SkillTargetFilter {
 }
 
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub enum SkillType {
     BasicAction,
     CreateWindow,
@@ -71,9 +84,12 @@ pub enum SkillType {
     Warp,
     SelfAndTarget,
     Resurrection,
+    /// Switched on and off, keeps its status effects active while draining mana
+    Toggle,
 }
 
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub enum SkillBasicCommand {
     Sit,
     PickupItem,
@@ -121,6 +137,7 @@ impl SkillType {
 pub type SkillCooldownGroup = NonZeroUsize;
 
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub enum SkillCooldown {
     Skill {
         duration: Duration,
@@ -132,13 +149,36 @@ pub enum SkillCooldown {
 }
 
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct SkillCastingEffect {
     pub effect_file_id: EffectFileId,
     pub effect_dummy_bone_id: Option<usize>,
 }
 
+/// Skill rules which are not part of the irose data, these are only set when a SKILL_RULES.YAML
+/// file has been added to the data overlay
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+#[derive(Clone, Debug, Deserialize)]
+pub struct SkillRuleData {
+    pub skill: SkillId,
+    /// Base skill which must be the caster's previous skill for this skill to be used
+    #[serde(default)]
+    pub required_combo_skill: Option<SkillId>,
+    /// Time after the previous skill was used that this skill can follow it in a combo
+    #[serde(default = "default_combo_timeout_millis")]
+    pub combo_timeout_millis: u64,
+    /// Mana drained every second whilst a toggle skill is active
+    #[serde(default)]
+    pub toggle_mana_drain: u32,
+}
+
+fn default_combo_timeout_millis() -> u64 {
+    3000
+}
+
 // TODO: Make SkillData an enum on SkillType with relevant fields only?
 #[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct SkillData {
     pub id: SkillId,
     pub name: &'static str,
@@ -157,6 +197,9 @@ pub struct SkillData {
     pub required_job_class: Option<JobClassId>,
     pub required_planet: Option<NonZeroUsize>,
     pub required_skills: ArrayVec<(SkillId, i32), 3>,
+    /// Base skill which must be the caster's previous skill, used within combo_timeout
+    pub required_combo_skill_id: Option<SkillId>,
+    pub combo_timeout: Duration,
     pub required_union: ArrayVec<NonZeroUsize, 3>,
     pub required_equipment_class: ArrayVec<ItemClass, 5>,
 
@@ -190,6 +233,7 @@ pub struct SkillData {
     pub success_ratio: i32,
     pub summon_npc_id: Option<NpcId>,
     pub target_filter: SkillTargetFilter,
+    pub toggle_mana_drain: u32,
     pub warp_zone_id: Option<ZoneId>,
     pub warp_zone_x: f32,
     pub warp_zone_y: f32,
@@ -215,4 +259,25 @@ impl SkillDatabase {
     pub fn iter(&self) -> impl Iterator<Item = &SkillData> {
         self.skills.iter().filter_map(|x| x.as_ref())
     }
+
+    /// Iterates over the skills which appear on the given skill page
+    pub fn iter_page(&self, page: SkillPageType) -> impl Iterator<Item = &SkillData> {
+        self.iter()
+            .filter(move |skill_data| skill_data.page == page)
+    }
+
+    /// Iterates over the skills which require exactly the given job class
+    pub fn iter_job_class(&self, job_class_id: JobClassId) -> impl Iterator<Item = &SkillData> {
+        self.iter()
+            .filter(move |skill_data| skill_data.required_job_class == Some(job_class_id))
+    }
+
+    /// Iterates over the base level of each skill, excluding the higher levels of a skill
+    pub fn iter_base_skills(&self) -> impl Iterator<Item = &SkillData> {
+        self.iter().filter(|skill_data| {
+            skill_data
+                .base_skill_id
+                .map_or(true, |base_skill_id| base_skill_id == skill_data.id)
+        })
+    }
 }
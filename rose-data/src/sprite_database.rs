@@ -0,0 +1,79 @@
+use enum_map::{Enum, EnumMap};
+use rose_file_readers::VfsPathBuf;
+
+#[derive(Enum, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SpriteSheetType {
+    Item,
+    Skill,
+    StatusEffect,
+}
+
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+pub struct SpriteData {
+    pub texture_index: usize,
+    pub left: i32,
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+    pub name: String,
+}
+
+impl SpriteData {
+    pub fn width(&self) -> i32 {
+        self.right - self.left
+    }
+
+    pub fn height(&self) -> i32 {
+        self.bottom - self.top
+    }
+}
+
+#[derive(Default)]
+pub struct SpriteSheet {
+    pub textures: Vec<VfsPathBuf>,
+    /// Sprites indexed by icon id
+    pub sprites: Vec<SpriteData>,
+}
+
+impl SpriteSheet {
+    pub fn get_sprite(&self, icon_id: usize) -> Option<&SpriteData> {
+        self.sprites.get(icon_id)
+    }
+
+    pub fn get_sprite_texture(&self, sprite: &SpriteData) -> Option<&VfsPathBuf> {
+        self.textures.get(sprite.texture_index)
+    }
+}
+
+pub struct SpriteDatabase {
+    sheets: EnumMap<SpriteSheetType, SpriteSheet>,
+}
+
+impl SpriteDatabase {
+    pub fn new(sheets: EnumMap<SpriteSheetType, SpriteSheet>) -> Self {
+        Self { sheets }
+    }
+
+    pub fn get_sheet(&self, sheet_type: SpriteSheetType) -> &SpriteSheet {
+        &self.sheets[sheet_type]
+    }
+
+    pub fn get_sprite(&self, sheet_type: SpriteSheetType, icon_id: usize) -> Option<&SpriteData> {
+        self.sheets[sheet_type].get_sprite(icon_id)
+    }
+
+    /// Returns the texture path and sprite rectangle for the given icon id
+    pub fn get_sprite_with_texture(
+        &self,
+        sheet_type: SpriteSheetType,
+        icon_id: usize,
+    ) -> Option<(&VfsPathBuf, &SpriteData)> {
+        let sheet = &self.sheets[sheet_type];
+        let sprite = sheet.get_sprite(icon_id)?;
+        Some((sheet.get_sprite_texture(sprite)?, sprite))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (SpriteSheetType, &SpriteSheet)> {
+        self.sheets.iter()
+    }
+}
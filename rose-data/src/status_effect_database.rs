@@ -88,6 +88,16 @@ impl StatusEffectType {
                 | StatusEffectType::AdditionalDamageRate
         )
     }
+
+    pub fn is_clear(&self) -> bool {
+        matches!(
+            *self,
+            StatusEffectType::ClearGood
+                | StatusEffectType::ClearBad
+                | StatusEffectType::ClearAll
+                | StatusEffectType::ClearInvisible
+        )
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
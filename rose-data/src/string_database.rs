@@ -2,10 +2,18 @@ use arrayvec::ArrayString;
 use enum_map::EnumMap;
 use std::fmt::Write;
 
-use rose_file_readers::{StlFile, StlItemEntry, StlNormalEntry, StlQuestEntry};
+use rose_file_readers::{LtbFile, StlFile, StlItemEntry, StlNormalEntry, StlQuestEntry};
 
 use crate::{AbilityType, ClanMemberPosition, ItemClass, ItemType, SkillTargetFilter, SkillType};
 
+pub struct StringDatabaseOptions {
+    /// Language index used for the strings of loaded game data
+    pub language: usize,
+}
+
+/// Strings which have no translation in the selected language use the english column
+pub const STRING_DATABASE_FALLBACK_LANGUAGE: usize = 1;
+
 // Strictly speaking we should abstract away from StlFile here, but it is not worth
 // the effort until a ROSE version comes along which does not use STL...
 pub struct StringDatabase {
@@ -20,6 +28,8 @@ pub struct StringDatabase {
     pub ability: StlFile,
     pub clan: StlFile,
     pub client_strings: StlFile,
+    /// Conversation strings used by the CON conversation scripts, one column per language
+    pub conversation: LtbFile,
     pub item: EnumMap<ItemType, StlFile>,
     pub item_prefix: StlFile,
     pub item_class: StlFile,
@@ -38,6 +48,13 @@ pub struct StringDatabase {
 }
 
 impl StringDatabase {
+    fn get_text_string<'a>(&self, stl: &'a StlFile, key: &str) -> &'a str {
+        stl.get_text_string(self.language, key)
+            .filter(|text| !text.is_empty())
+            .or_else(|| stl.get_text_string(STRING_DATABASE_FALLBACK_LANGUAGE, key))
+            .unwrap_or("")
+    }
+
     pub fn get_ability_type(&self, ability_type: AbilityType) -> &str {
         let index = if let Some(index) = (self.encode_ability_type)(ability_type) {
             index as u16
@@ -47,9 +64,7 @@ impl StringDatabase {
 
         let mut key = ArrayString::<16>::new();
         write!(&mut key, "{}", index).ok();
-        self.ability
-            .get_text_string(self.language, &key)
-            .unwrap_or("")
+        self.get_text_string(&self.ability, &key)
     }
 
     pub fn get_clan_member_position(&self, position: ClanMemberPosition) -> &str {
@@ -61,12 +76,28 @@ impl StringDatabase {
 
         let mut key = ArrayString::<16>::new();
         write!(&mut key, "{}", index).ok();
-        self.clan.get_text_string(self.language, &key).unwrap_or("")
+        self.get_text_string(&self.clan, &key)
+    }
+
+    pub fn get_client_string(&self, key: &str) -> &str {
+        self.get_text_string(&self.client_strings, key)
+    }
+
+    pub fn get_conversation_string(&self, index: usize) -> Option<String> {
+        self.conversation
+            .get_string(index, self.language)
+            .or_else(|| {
+                self.conversation
+                    .get_string(index, STRING_DATABASE_FALLBACK_LANGUAGE)
+            })
     }
 
     pub fn get_item(&self, item_type: ItemType, key: &str) -> Option<StlItemEntry> {
-        let index = self.item[item_type].lookup_key(key)?;
-        self.item[item_type].get_item_entry(self.language, index)
+        let stl = &self.item[item_type];
+        let index = stl.lookup_key(key)?;
+        stl.get_item_entry(self.language, index)
+            .filter(|entry| !entry.name.is_empty())
+            .or_else(|| stl.get_item_entry(STRING_DATABASE_FALLBACK_LANGUAGE, index))
     }
 
     pub fn get_item_class(&self, item_class: ItemClass) -> &str {
@@ -77,41 +108,49 @@ impl StringDatabase {
         };
         let mut key = ArrayString::<16>::new();
         write!(&mut key, "{}", index).ok();
-        self.item_class
-            .get_text_string(self.language, &key)
-            .unwrap_or("")
+        self.get_text_string(&self.item_class, &key)
     }
 
     pub fn get_job_name(&self, job: u16) -> &str {
         let mut key = ArrayString::<16>::new();
         write!(&mut key, "{}", job).ok();
-        self.job.get_text_string(self.language, &key).unwrap_or("")
+        self.get_text_string(&self.job, &key)
     }
 
     pub fn get_job_class_name(&self, key: &str) -> &str {
-        self.job_class
-            .get_text_string(self.language, key)
-            .unwrap_or("")
+        self.get_text_string(&self.job_class, key)
     }
 
     pub fn get_npc(&self, key: &str) -> Option<StlNormalEntry> {
-        let index = self.npc.lookup_key(key)?;
-        self.npc.get_normal_entry(self.language, index)
+        let stl = &self.npc;
+        let index = stl.lookup_key(key)?;
+        stl.get_normal_entry(self.language, index)
+            .filter(|entry| !entry.text.is_empty())
+            .or_else(|| stl.get_normal_entry(STRING_DATABASE_FALLBACK_LANGUAGE, index))
     }
 
     pub fn get_npc_store_tab(&self, key: &str) -> Option<StlNormalEntry> {
-        let index = self.npc_store_tabs.lookup_key(key)?;
-        self.npc_store_tabs.get_normal_entry(self.language, index)
+        let stl = &self.npc_store_tabs;
+        let index = stl.lookup_key(key)?;
+        stl.get_normal_entry(self.language, index)
+            .filter(|entry| !entry.text.is_empty())
+            .or_else(|| stl.get_normal_entry(STRING_DATABASE_FALLBACK_LANGUAGE, index))
     }
 
     pub fn get_quest(&self, key: &str) -> Option<StlQuestEntry> {
-        let index = self.quest.lookup_key(key)?;
-        self.quest.get_quest_entry(self.language, index)
+        let stl = &self.quest;
+        let index = stl.lookup_key(key)?;
+        stl.get_quest_entry(self.language, index)
+            .filter(|entry| !entry.name.is_empty())
+            .or_else(|| stl.get_quest_entry(STRING_DATABASE_FALLBACK_LANGUAGE, index))
     }
 
     pub fn get_skill(&self, key: &str) -> Option<StlItemEntry> {
-        let index = self.skill.lookup_key(key)?;
-        self.skill.get_item_entry(self.language, index)
+        let stl = &self.skill;
+        let index = stl.lookup_key(key)?;
+        stl.get_item_entry(self.language, index)
+            .filter(|entry| !entry.name.is_empty())
+            .or_else(|| stl.get_item_entry(STRING_DATABASE_FALLBACK_LANGUAGE, index))
     }
 
     pub fn get_skill_target_filter(&self, skill_target_filter: SkillTargetFilter) -> &str {
@@ -122,9 +161,7 @@ impl StringDatabase {
         };
         let mut key = ArrayString::<16>::new();
         write!(&mut key, "{}", index).ok();
-        self.skill_target
-            .get_text_string(self.language, &key)
-            .unwrap_or("")
+        self.get_text_string(&self.skill_target, &key)
     }
 
     pub fn get_skill_type(&self, skill_type: SkillType) -> &str {
@@ -135,18 +172,22 @@ impl StringDatabase {
         };
         let mut key = ArrayString::<16>::new();
         write!(&mut key, "{}", index).ok();
-        self.skill_type
-            .get_text_string(self.language, &key)
-            .unwrap_or("")
+        self.get_text_string(&self.skill_type, &key)
     }
 
     pub fn get_status_effect(&self, key: &str) -> Option<StlQuestEntry> {
-        let index = self.status_effect.lookup_key(key)?;
-        self.status_effect.get_quest_entry(self.language, index)
+        let stl = &self.status_effect;
+        let index = stl.lookup_key(key)?;
+        stl.get_quest_entry(self.language, index)
+            .filter(|entry| !entry.name.is_empty())
+            .or_else(|| stl.get_quest_entry(STRING_DATABASE_FALLBACK_LANGUAGE, index))
     }
 
     pub fn get_zone(&self, key: &str) -> Option<StlItemEntry> {
-        let index = self.zone.lookup_key(key)?;
-        self.zone.get_item_entry(self.language, index)
+        let stl = &self.zone;
+        let index = stl.lookup_key(key)?;
+        stl.get_item_entry(self.language, index)
+            .filter(|entry| !entry.name.is_empty())
+            .or_else(|| stl.get_item_entry(STRING_DATABASE_FALLBACK_LANGUAGE, index))
     }
 }
@@ -0,0 +1,33 @@
+use serde::Deserialize;
+
+use crate::ZoneId;
+
+/// A named position which can be teleported to with /goto
+#[derive(Clone, Debug, Deserialize)]
+pub struct TeleportAnchorData {
+    pub name: String,
+    pub zone: ZoneId,
+    pub x: f32,
+    pub y: f32,
+}
+
+#[derive(Default)]
+pub struct TeleportAnchorDatabase {
+    anchors: Vec<TeleportAnchorData>,
+}
+
+impl TeleportAnchorDatabase {
+    pub fn new(anchors: Vec<TeleportAnchorData>) -> Self {
+        Self { anchors }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &TeleportAnchorData> {
+        self.anchors.iter()
+    }
+
+    pub fn find_anchor(&self, name: &str) -> Option<&TeleportAnchorData> {
+        self.anchors
+            .iter()
+            .find(|anchor| anchor.name.eq_ignore_ascii_case(name))
+    }
+}
@@ -0,0 +1,29 @@
+use std::collections::HashMap;
+
+/// Maps the names used by the client UI to string ids in LIST_STRING.STL
+#[derive(Default)]
+pub struct UiStringIds {
+    ids: HashMap<String, u16>,
+}
+
+impl UiStringIds {
+    pub fn new(ids: HashMap<String, u16>) -> Self {
+        Self { ids }
+    }
+
+    pub fn get(&self, name: &str) -> Option<u16> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u16)> {
+        self.ids.iter().map(|(name, &id)| (name.as_str(), id))
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+}
@@ -0,0 +1,336 @@
+use bevy::math::{Affine3A, Vec3, Vec3Swizzles};
+use std::{collections::HashMap, hash::Hasher, sync::Arc};
+
+/// The collision triangles of a mesh in its local space, shared by every object which uses it.
+pub struct ZoneCollisionMesh {
+    triangles: Vec<[Vec3; 3]>,
+    min: Vec3,
+    max: Vec3,
+}
+
+impl ZoneCollisionMesh {
+    pub fn new(triangles: Vec<[Vec3; 3]>) -> Option<Self> {
+        let mut min = Vec3::splat(f32::MAX);
+        let mut max = Vec3::splat(f32::MIN);
+        for vertex in triangles.iter().flatten() {
+            min = min.min(*vertex);
+            max = max.max(*vertex);
+        }
+
+        if triangles.is_empty() {
+            None
+        } else {
+            Some(Self {
+                triangles,
+                min,
+                max,
+            })
+        }
+    }
+
+    /// Returns the world space (min, max) of the mesh when placed with the given transform
+    pub fn world_bounds(&self, transform: &Affine3A) -> (Vec3, Vec3) {
+        let mut min = Vec3::splat(f32::MAX);
+        let mut max = Vec3::splat(f32::MIN);
+
+        for i in 0..8 {
+            let corner = Vec3::new(
+                if i & 1 == 0 { self.min.x } else { self.max.x },
+                if i & 2 == 0 { self.min.y } else { self.max.y },
+                if i & 4 == 0 { self.min.z } else { self.max.z },
+            );
+            let corner = transform.transform_point3(corner);
+            min = min.min(corner);
+            max = max.max(corner);
+        }
+
+        (min, max)
+    }
+
+    fn hash_contents(&self, state: &mut impl Hasher) {
+        state.write_u64(self.triangles.len() as u64);
+        for vertex in self.triangles.iter().flatten() {
+            for value in vertex.to_array() {
+                state.write_u32(value.to_bits());
+            }
+        }
+    }
+}
+
+// Slightly tilted from straight up, so the ray does not pass exactly along the shared edges of
+// axis aligned faces and count one crossing twice
+const CONTAINS_RAY_DIRECTION: Vec3 = Vec3::new(0.0013, 0.0007, 1.0);
+
+struct ZoneCollisionObject {
+    mesh: Arc<ZoneCollisionMesh>,
+    inverse_transform: Affine3A,
+    world_min: Vec3,
+    world_max: Vec3,
+}
+
+/// Returns the distance along the ray to the triangle as a multiple of direction.
+fn intersect_triangle(origin: Vec3, direction: Vec3, triangle: &[Vec3; 3]) -> Option<f32> {
+    let edge1 = triangle[1] - triangle[0];
+    let edge2 = triangle[2] - triangle[0];
+    let p = direction.cross(edge2);
+    let det = edge1.dot(p);
+    if det.abs() < f32::EPSILON {
+        return None;
+    }
+
+    let inv_det = 1.0 / det;
+    let s = origin - triangle[0];
+    let u = s.dot(p) * inv_det;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+
+    let q = s.cross(edge1);
+    let v = direction.dot(q) * inv_det;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+
+    Some(edge2.dot(q) * inv_det)
+}
+
+fn intersects_aabb(origin: Vec3, direction: Vec3, max_t: f32, min: Vec3, max: Vec3) -> bool {
+    let mut t_min = 0.0f32;
+    let mut t_max = max_t;
+
+    for axis in 0..3 {
+        if direction[axis].abs() < f32::EPSILON {
+            if origin[axis] < min[axis] || origin[axis] > max[axis] {
+                return false;
+            }
+        } else {
+            let t1 = (min[axis] - origin[axis]) / direction[axis];
+            let t2 = (max[axis] - origin[axis]) / direction[axis];
+            t_min = t_min.max(t1.min(t2));
+            t_max = t_max.min(t1.max(t2));
+            if t_min > t_max {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+impl ZoneCollisionObject {
+    /// Returns the earliest intersection of the segment as a fraction of its length
+    fn intersect_segment(&self, from: Vec3, to: Vec3) -> Option<f32> {
+        // Affine transforms preserve the fraction along the segment, so test in mesh space
+        let origin = self.inverse_transform.transform_point3(from);
+        let direction = self.inverse_transform.transform_point3(to) - origin;
+        if !intersects_aabb(origin, direction, 1.0, self.mesh.min, self.mesh.max) {
+            return None;
+        }
+
+        self.mesh
+            .triangles
+            .iter()
+            .filter_map(|triangle| intersect_triangle(origin, direction, triangle))
+            .filter(|t| *t > 0.0 && *t <= 1.0)
+            .min_by(|a, b| a.total_cmp(b))
+    }
+
+    /// Meshes are treated as closed, so a position is inside when a ray cast up from it crosses
+    /// the mesh an odd number of times.
+    fn contains(&self, position: Vec3) -> bool {
+        let origin = self.inverse_transform.transform_point3(position);
+        let direction = self
+            .inverse_transform
+            .transform_vector3(CONTAINS_RAY_DIRECTION);
+        if !intersects_aabb(origin, direction, f32::MAX, self.mesh.min, self.mesh.max) {
+            return false;
+        }
+
+        let crossings = self
+            .mesh
+            .triangles
+            .iter()
+            .filter_map(|triangle| intersect_triangle(origin, direction, triangle))
+            .filter(|t| *t > 0.0)
+            .count();
+        crossings % 2 == 1
+    }
+}
+
+/// A static grid of object collision meshes for a zone, used to validate movement.
+pub struct ZoneCollision {
+    pub cell_size: f32,
+    objects: Vec<ZoneCollisionObject>,
+    cells: HashMap<(i32, i32), Vec<u32>>,
+}
+
+impl ZoneCollision {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            objects: Vec::new(),
+            cells: HashMap::new(),
+        }
+    }
+
+    pub fn add_object(&mut self, mesh: Arc<ZoneCollisionMesh>, transform: Affine3A) {
+        let index = self.objects.len() as u32;
+        let (world_min, world_max) = mesh.world_bounds(&transform);
+        let (min_x, min_y) = self.get_cell(world_min);
+        let (max_x, max_y) = self.get_cell(world_max);
+
+        for cell_y in min_y..=max_y {
+            for cell_x in min_x..=max_x {
+                self.cells.entry((cell_x, cell_y)).or_default().push(index);
+            }
+        }
+
+        self.objects.push(ZoneCollisionObject {
+            mesh,
+            inverse_transform: transform.inverse(),
+            world_min,
+            world_max,
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    /// Feeds the mesh and placement of every object into the hasher, so caches derived from the
+    /// collision can detect when it has changed.
+    pub fn hash_contents(&self, state: &mut impl Hasher) {
+        state.write_u32(self.cell_size.to_bits());
+        state.write_u64(self.objects.len() as u64);
+        for object in self.objects.iter() {
+            object.mesh.hash_contents(state);
+            for value in object.inverse_transform.to_cols_array() {
+                state.write_u32(value.to_bits());
+            }
+        }
+    }
+
+    fn get_cell(&self, position: Vec3) -> (i32, i32) {
+        let cell = (position.xy() / self.cell_size).floor();
+        (cell.x as i32, cell.y as i32)
+    }
+
+    /// Returns true if the position is inside of an object
+    pub fn is_blocked(&self, position: Vec3) -> bool {
+        self.cells
+            .get(&self.get_cell(position))
+            .map_or(false, |cell| {
+                cell.iter().any(|&index| {
+                    let object = &self.objects[index as usize];
+                    position.cmpge(object.world_min).all()
+                        && position.cmple(object.world_max).all()
+                        && object.contains(position)
+                })
+            })
+    }
+
+    /// Returns the earliest point along the segment which hits an object, as a fraction of
+    /// the segment length.
+    pub fn intersect_segment(&self, from: Vec3, to: Vec3) -> Option<f32> {
+        let segment_min = from.min(to);
+        let segment_max = from.max(to);
+        let (min_x, min_y) = self.get_cell(segment_min);
+        let (max_x, max_y) = self.get_cell(segment_max);
+
+        let mut candidates = Vec::new();
+        for cell_y in min_y..=max_y {
+            for cell_x in min_x..=max_x {
+                if let Some(cell) = self.cells.get(&(cell_x, cell_y)) {
+                    candidates.extend(cell.iter().copied());
+                }
+            }
+        }
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        candidates
+            .into_iter()
+            .map(|index| &self.objects[index as usize])
+            .filter(|object| {
+                segment_max.cmpge(object.world_min).all()
+                    && segment_min.cmple(object.world_max).all()
+            })
+            .filter_map(|object| object.intersect_segment(from, to))
+            .min_by(|a, b| a.total_cmp(b))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    // A closed axis aligned box mesh
+    pub(crate) fn box_mesh(min: Vec3, max: Vec3) -> ZoneCollisionMesh {
+        let corner = |i: usize| {
+            Vec3::new(
+                if i & 1 == 0 { min.x } else { max.x },
+                if i & 2 == 0 { min.y } else { max.y },
+                if i & 4 == 0 { min.z } else { max.z },
+            )
+        };
+        let faces = [
+            [0, 1, 3, 2],
+            [4, 5, 7, 6],
+            [0, 1, 5, 4],
+            [2, 3, 7, 6],
+            [0, 2, 6, 4],
+            [1, 3, 7, 5],
+        ];
+        let mut triangles = Vec::new();
+        for [a, b, c, d] in faces {
+            triangles.push([corner(a), corner(b), corner(c)]);
+            triangles.push([corner(a), corner(c), corner(d)]);
+        }
+        ZoneCollisionMesh::new(triangles).unwrap()
+    }
+
+    fn bridge_collision() -> ZoneCollision {
+        let mut collision = ZoneCollision::new(1000.0);
+
+        // Bridge deck high above the ground, with a pillar under one end
+        collision.add_object(
+            Arc::new(box_mesh(
+                Vec3::new(0.0, -200.0, 500.0),
+                Vec3::new(2000.0, 200.0, 600.0),
+            )),
+            Affine3A::IDENTITY,
+        );
+        collision.add_object(
+            Arc::new(box_mesh(
+                Vec3::new(-100.0, -100.0, 0.0),
+                Vec3::new(100.0, 100.0, 500.0),
+            )),
+            Affine3A::from_translation(Vec3::new(1800.0, 0.0, 0.0)),
+        );
+        collision
+    }
+
+    #[test]
+    fn walk_under_bridge() {
+        let collision = bridge_collision();
+        let from = Vec3::new(500.0, -1000.0, 50.0);
+        let to = Vec3::new(500.0, 1000.0, 50.0);
+        assert_eq!(collision.intersect_segment(from, to), None);
+        assert!(!collision.is_blocked(Vec3::new(500.0, 0.0, 50.0)));
+    }
+
+    #[test]
+    fn blocked_by_pillar() {
+        let collision = bridge_collision();
+        let from = Vec3::new(1800.0, -1000.0, 50.0);
+        let to = Vec3::new(1800.0, 1000.0, 50.0);
+        let hit = collision.intersect_segment(from, to).unwrap();
+        assert!((hit - 0.45).abs() < 0.001);
+        assert!(collision.is_blocked(Vec3::new(1800.0, 0.0, 50.0)));
+        assert!(collision.is_blocked(Vec3::new(1000.0, 0.0, 550.0)));
+    }
+}
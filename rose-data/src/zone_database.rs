@@ -3,15 +3,18 @@ use bevy::{
     reflect::Reflect,
 };
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, num::NonZeroU16, str::FromStr, sync::Arc};
+use std::{collections::HashMap, hash::Hasher, num::NonZeroU16, str::FromStr, sync::Arc};
 
-use crate::{NpcConversationId, NpcId, SkyboxId, StringDatabase};
+use rose_file_readers::HimFile;
+
+use crate::{NpcConversationId, NpcId, ScheduleWindow, SkyboxId, StringDatabase, ZoneCollision};
 
 #[derive(Copy, Clone, Debug, Deserialize, Serialize, Hash, PartialEq, Eq, Reflect)]
 pub struct ZoneId(pub NonZeroU16);
 
 id_wrapper_impl!(ZoneId, NonZeroU16, u16);
 
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct ZoneMonsterSpawnPoint {
     pub position: Vec3,
     pub basic_spawns: Vec<(NpcId, usize)>,
@@ -22,6 +25,7 @@ pub struct ZoneMonsterSpawnPoint {
     pub tactic_points: u32,
 }
 
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct ZoneNpcSpawn {
     pub npc_id: NpcId,
     pub position: Vec3,
@@ -29,6 +33,7 @@ pub struct ZoneNpcSpawn {
     pub conversation: NpcConversationId,
 }
 
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct ZoneEventObject {
     pub event_id: u16,
     pub map_chunk_x: i32,
@@ -36,6 +41,167 @@ pub struct ZoneEventObject {
     pub position: Vec3,
 }
 
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+#[derive(Clone, Debug, Deserialize)]
+pub struct ZoneSafeArea {
+    pub x: f32,
+    pub y: f32,
+    pub radius: f32,
+}
+
+/// PvP rules for a zone, these are not part of the irose data so they are only set when a
+/// ZONE_PVP.YAML file has been added to the data overlay
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+#[derive(Clone, Debug, Deserialize)]
+pub struct ZonePvpData {
+    pub zone: ZoneId,
+    /// Characters can attack each other in this zone
+    pub pvp: bool,
+    /// When not empty, PvP is only allowed during these windows
+    #[serde(default)]
+    pub schedule: Vec<ScheduleWindow>,
+    /// Characters within this distance of the start and revive positions can not attack or be
+    /// attacked by other characters
+    #[serde(default)]
+    pub spawn_safe_radius: f32,
+    #[serde(default)]
+    pub safe_areas: Vec<ZoneSafeArea>,
+}
+
+pub struct ZoneHeightmap {
+    pub block_size: f32,
+    pub grid_size: f32,
+    blocks: HashMap<(i32, i32), HimFile>,
+}
+
+impl ZoneHeightmap {
+    pub fn new(block_size: f32, grid_size: f32) -> Self {
+        Self {
+            block_size,
+            grid_size,
+            blocks: HashMap::new(),
+        }
+    }
+
+    pub fn add_block(&mut self, block_x: i32, block_y: i32, heightmap: HimFile) {
+        self.blocks.insert((block_x, block_y), heightmap);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    pub fn num_blocks(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Feeds every heightmap block into the hasher in block order, so caches derived from the
+    /// heightmap can detect when it has changed.
+    pub fn hash_contents(&self, state: &mut impl Hasher) {
+        state.write_u32(self.block_size.to_bits());
+        state.write_u32(self.grid_size.to_bits());
+
+        let mut blocks: Vec<_> = self.blocks.iter().collect();
+        blocks.sort_by_key(|(&block, _)| block);
+        state.write_u64(blocks.len() as u64);
+        for (&(block_x, block_y), heightmap) in blocks {
+            state.write_i32(block_x);
+            state.write_i32(block_y);
+            state.write_u32(heightmap.width);
+            state.write_u32(heightmap.height);
+            for height in heightmap.heights.iter() {
+                state.write_u32(height.to_bits());
+            }
+        }
+    }
+
+    /// Returns the world space (min, max) covered by the loaded heightmap blocks
+    pub fn bounds(&self) -> Option<(Vec2, Vec2)> {
+        let min_block_x = self.blocks.keys().map(|(x, _)| *x).min()?;
+        let max_block_x = self.blocks.keys().map(|(x, _)| *x).max()?;
+        let min_block_y = self.blocks.keys().map(|(_, y)| *y).min()?;
+        let max_block_y = self.blocks.keys().map(|(_, y)| *y).max()?;
+
+        Some((
+            Vec2::new(
+                min_block_x as f32 * self.block_size,
+                (65 - max_block_y - 1) as f32 * self.block_size,
+            ),
+            Vec2::new(
+                (max_block_x + 1) as f32 * self.block_size,
+                (65 - min_block_y) as f32 * self.block_size,
+            ),
+        ))
+    }
+
+    /// Returns the terrain height at the given world position, interpolated between the
+    /// nearest four heightmap points, or None if there is no heightmap loaded there.
+    pub fn get_height(&self, position: Vec2) -> Option<f32> {
+        if self.grid_size <= 0.0 || self.block_size <= 0.0 {
+            return None;
+        }
+
+        // Heightmap blocks are laid out with the y axis inverted relative to world space
+        let block_x = (position.x / self.block_size).floor();
+        let block_y = ((65.0 * self.block_size - position.y) / self.block_size).floor();
+        let heightmap = self.blocks.get(&(block_x as i32, block_y as i32))?;
+
+        let grid_x = (position.x - block_x * self.block_size) / self.grid_size;
+        let grid_y =
+            (65.0 * self.block_size - position.y - block_y * self.block_size) / self.grid_size;
+        let x0 = grid_x.floor() as i32;
+        let y0 = grid_y.floor() as i32;
+        let fx = grid_x - x0 as f32;
+        let fy = grid_y - y0 as f32;
+
+        let h00 = heightmap.get_clamped(x0, y0);
+        let h10 = heightmap.get_clamped(x0 + 1, y0);
+        let h01 = heightmap.get_clamped(x0, y0 + 1);
+        let h11 = heightmap.get_clamped(x0 + 1, y0 + 1);
+        let top = h00 + (h10 - h00) * fx;
+        let bottom = h01 + (h11 - h01) * fx;
+        Some(top + (bottom - top) * fy)
+    }
+
+    /// Clamps the height of a position to be no lower than the terrain and no higher than
+    /// max_height_above_terrain above it.
+    pub fn clamp_height(&self, position: Vec3, max_height_above_terrain: f32) -> Vec3 {
+        if let Some(terrain_height) = self.get_height(position.xy()) {
+            Vec3::new(
+                position.x,
+                position.y,
+                position
+                    .z
+                    .clamp(terrain_height, terrain_height + max_height_above_terrain),
+            )
+        } else {
+            position
+        }
+    }
+
+    /// Checks that the straight line between two points does not pass below the terrain.
+    ///
+    /// Sampled every grid cell, any sample without heightmap data is treated as clear.
+    pub fn has_line_of_sight(&self, from: Vec3, to: Vec3) -> bool {
+        let distance = from.xy().distance(to.xy());
+        let num_samples = (distance / self.grid_size).ceil() as i32;
+
+        for i in 1..num_samples {
+            let t = i as f32 / num_samples as f32;
+            let sample = from.lerp(to, t);
+
+            if let Some(terrain_height) = self.get_height(sample.xy()) {
+                if terrain_height > sample.z {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+}
+
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct ZoneData {
     pub id: ZoneId,
     pub name: &'static str,
@@ -58,8 +224,19 @@ pub struct ZoneData {
     pub evening_time: u32,
     pub night_time: u32,
     pub skybox_id: Option<SkyboxId>,
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    pub heightmap: ZoneHeightmap,
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    pub collision: ZoneCollision,
+    /// Checksum of the zone files read at load time, used to detect clients with modified maps
+    pub checksum: u32,
+    pub pvp: Option<ZonePvpData>,
 }
 
+// Collision is tested slightly above the ground so we can stand on top of objects
+const COLLISION_TEST_HEIGHT: f32 = 50.0;
+const COLLISION_TRACE_STEP: f32 = 50.0;
+
 impl ZoneData {
     pub fn get_closest_revive_position(&self, origin: Vec3) -> Option<Vec3> {
         let mut closest = None;
@@ -74,6 +251,86 @@ impl ZoneData {
 
         closest.map(|(_, p)| *p)
     }
+
+    /// Returns true if the position is within one of the zone's PvP safe areas
+    pub fn is_pvp_safe_area(&self, position: Vec2) -> bool {
+        let Some(pvp) = self.pvp.as_ref() else {
+            return false;
+        };
+
+        if pvp.spawn_safe_radius > 0.0
+            && std::iter::once(&self.start_position)
+                .chain(self.revive_positions.iter())
+                .any(|spawn_position| {
+                    spawn_position.xy().distance(position) < pvp.spawn_safe_radius
+                })
+        {
+            return true;
+        }
+
+        pvp.safe_areas.iter().any(|safe_area| {
+            Vec2::new(safe_area.x, safe_area.y).distance(position) < safe_area.radius
+        })
+    }
+
+    fn get_collision_test_position(&self, position: Vec3) -> Vec3 {
+        let ground_height = self
+            .heightmap
+            .get_height(position.xy())
+            .map_or(position.z, |height| f32::max(height, position.z));
+
+        Vec3::new(
+            position.x,
+            position.y,
+            ground_height + COLLISION_TEST_HEIGHT,
+        )
+    }
+
+    /// Returns true if the position is inside of a zone object
+    pub fn is_position_blocked(&self, position: Vec3) -> bool {
+        self.collision
+            .is_blocked(self.get_collision_test_position(position))
+    }
+
+    /// Returns the furthest position along the straight line from `from` to `to` which does
+    /// not collide with any zone objects.
+    ///
+    /// When already stuck inside an object, the move is only allowed if it leaves the object.
+    pub fn trace_movement(&self, from: Vec3, to: Vec3) -> Vec3 {
+        if self.collision.is_empty() {
+            return to;
+        }
+
+        let mut escaping = self.is_position_blocked(from);
+        if escaping && self.is_position_blocked(to) {
+            return from;
+        }
+
+        let distance = from.xy().distance(to.xy());
+        let num_steps = (distance / COLLISION_TRACE_STEP).ceil() as i32;
+        let mut last_valid = from;
+        let mut last_test_position = self.get_collision_test_position(from);
+
+        for i in 1..=num_steps {
+            let sample = from.lerp(to, i as f32 / num_steps as f32);
+            let test_position = self.get_collision_test_position(sample);
+
+            if escaping {
+                escaping = self.collision.is_blocked(test_position);
+            } else if self
+                .collision
+                .intersect_segment(last_test_position, test_position)
+                .is_some()
+            {
+                return last_valid;
+            }
+
+            last_valid = sample;
+            last_test_position = test_position;
+        }
+
+        to
+    }
 }
 
 pub struct ZoneDatabase {
@@ -99,4 +356,142 @@ impl ZoneDatabase {
             None => None,
         }
     }
+
+    pub fn find_zone_by_name(&self, name: &str) -> Option<&ZoneData> {
+        self.iter()
+            .find(|zone_data| zone_data.name.eq_ignore_ascii_case(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::zone_collision::tests::box_mesh;
+    use bevy::math::Affine3A;
+
+    // A single 20x20 block with a 3x3 heightmap rising 10 per grid cell along x
+    fn test_heightmap() -> ZoneHeightmap {
+        let mut heightmap = ZoneHeightmap::new(20.0, 10.0);
+        heightmap.add_block(
+            0,
+            64,
+            HimFile {
+                width: 3,
+                height: 3,
+                heights: vec![0.0, 10.0, 20.0, 0.0, 10.0, 20.0, 0.0, 10.0, 20.0],
+            },
+        );
+        heightmap
+    }
+
+    #[test]
+    fn heightmap_interpolates_between_grid_points() {
+        let heightmap = test_heightmap();
+        assert_eq!(heightmap.get_height(Vec2::new(10.0, 20.0)), Some(10.0));
+        assert_eq!(heightmap.get_height(Vec2::new(5.0, 15.0)), Some(5.0));
+        assert_eq!(heightmap.get_height(Vec2::new(15.0, 5.0)), Some(15.0));
+    }
+
+    #[test]
+    fn heightmap_edges() {
+        let heightmap = test_heightmap();
+
+        // The last row and column of grid points are clamped rather than read out of bounds
+        let height = heightmap.get_height(Vec2::new(19.5, 0.5)).unwrap();
+        assert!((height - 19.5).abs() < 0.001);
+
+        // Outside of any loaded block there is no height
+        assert_eq!(heightmap.get_height(Vec2::new(-1.0, 10.0)), None);
+        assert_eq!(heightmap.get_height(Vec2::new(25.0, 10.0)), None);
+        assert_eq!(heightmap.get_height(Vec2::new(10.0, 25.0)), None);
+        assert_eq!(ZoneHeightmap::new(20.0, 0.0).get_height(Vec2::ZERO), None);
+
+        assert_eq!(
+            heightmap.bounds(),
+            Some((Vec2::new(0.0, 0.0), Vec2::new(20.0, 20.0)))
+        );
+    }
+
+    #[test]
+    fn heightmap_clamp_height() {
+        let heightmap = test_heightmap();
+        assert_eq!(
+            heightmap.clamp_height(Vec3::new(10.0, 10.0, -50.0), 100.0),
+            Vec3::new(10.0, 10.0, 10.0)
+        );
+        assert_eq!(
+            heightmap.clamp_height(Vec3::new(10.0, 10.0, 500.0), 100.0),
+            Vec3::new(10.0, 10.0, 110.0)
+        );
+        assert_eq!(
+            heightmap.clamp_height(Vec3::new(50.0, 10.0, -50.0), 100.0),
+            Vec3::new(50.0, 10.0, -50.0)
+        );
+    }
+
+    #[test]
+    fn heightmap_line_of_sight() {
+        let heightmap = test_heightmap();
+        assert!(
+            heightmap.has_line_of_sight(Vec3::new(0.0, 10.0, 30.0), Vec3::new(19.0, 10.0, 30.0))
+        );
+        assert!(!heightmap.has_line_of_sight(Vec3::new(0.0, 10.0, 5.0), Vec3::new(19.0, 10.0, 5.0)));
+    }
+
+    fn test_zone(collision: ZoneCollision) -> ZoneData {
+        ZoneData {
+            id: ZoneId::new(1).unwrap(),
+            name: "Test",
+            description: "",
+            sector_size: 5000,
+            grid_per_patch: 4.0,
+            grid_size: 250.0,
+            event_objects: Vec::new(),
+            monster_spawns: Vec::new(),
+            npcs: Vec::new(),
+            sectors_base_position: Vec2::ZERO,
+            num_sectors_x: 1,
+            num_sectors_y: 1,
+            start_position: Vec3::ZERO,
+            revive_positions: Vec::new(),
+            event_positions: HashMap::new(),
+            day_cycle: 0,
+            morning_time: 0,
+            day_time: 0,
+            evening_time: 0,
+            night_time: 0,
+            skybox_id: None,
+            heightmap: ZoneHeightmap::new(16000.0, 250.0),
+            collision,
+            checksum: 0,
+            pvp: None,
+        }
+    }
+
+    #[test]
+    fn trace_movement_stops_at_objects() {
+        let mut collision = ZoneCollision::new(1000.0);
+        collision.add_object(
+            Arc::new(box_mesh(
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(400.0, 400.0, 300.0),
+            )),
+            Affine3A::IDENTITY,
+        );
+        let zone = test_zone(collision);
+
+        // Walking into the object stops before it
+        let stopped =
+            zone.trace_movement(Vec3::new(-500.0, 200.0, 0.0), Vec3::new(1000.0, 200.0, 0.0));
+        assert!(stopped.x < 0.0);
+
+        // Walking out of the object is allowed, but not moving within it
+        let inside = Vec3::new(200.0, 200.0, 0.0);
+        let outside = Vec3::new(1000.0, 200.0, 0.0);
+        assert_eq!(zone.trace_movement(inside, outside), outside);
+        assert_eq!(
+            zone.trace_movement(inside, Vec3::new(300.0, 200.0, 0.0)),
+            inside
+        );
+    }
 }
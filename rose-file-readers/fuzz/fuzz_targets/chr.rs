@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rose_file_readers::{ChrFile, RoseFile, RoseFileReader};
+
+fuzz_target!(|data: &[u8]| {
+    let _ = ChrFile::read(RoseFileReader::from(data), &Default::default());
+});
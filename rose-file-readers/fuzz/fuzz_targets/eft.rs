@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rose_file_readers::{EftFile, RoseFile, RoseFileReader};
+
+fuzz_target!(|data: &[u8]| {
+    let _ = EftFile::read(RoseFileReader::from(data), &Default::default());
+});
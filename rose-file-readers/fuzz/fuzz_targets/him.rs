@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rose_file_readers::{HimFile, RoseFile, RoseFileReader};
+
+fuzz_target!(|data: &[u8]| {
+    let _ = HimFile::read(RoseFileReader::from(data), &Default::default());
+});
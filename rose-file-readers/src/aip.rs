@@ -1,4 +1,6 @@
 use log::warn;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use std::{
     num::NonZeroU8,
     ops::{Range, RangeInclusive},
@@ -8,7 +10,7 @@ use thiserror::Error;
 
 use crate::{reader::RoseFileReader, RoseFile};
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, JsonSchema, Serialize, Deserialize)]
 pub enum AipAbilityType {
     Level,
     Attack,
@@ -30,7 +32,7 @@ fn decode_ability_type(value: u8) -> Result<AipAbilityType, AipReadError> {
     }
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, JsonSchema, Serialize, Deserialize)]
 pub enum AipOperatorType {
     Equals,
     GreaterThan,
@@ -52,7 +54,7 @@ fn decode_operator_type(value: u8) -> Result<AipOperatorType, AipReadError> {
     }
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, JsonSchema, Serialize, Deserialize)]
 pub enum AipResultOperator {
     Set,
     Add,
@@ -68,20 +70,20 @@ fn decode_result_operator_type(value: u8) -> Result<AipResultOperator, AipReadEr
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, JsonSchema, Serialize, Deserialize)]
 pub enum AipHaveStatusTarget {
     This,
     Target,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, JsonSchema, Serialize, Deserialize)]
 pub enum AipHaveStatusType {
     Good,
     Bad,
     Any,
 }
 
-#[derive(Debug)]
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
 pub struct AipConditionFindNearbyEntities {
     pub distance: AipDistance,
     pub is_allied: bool,
@@ -90,45 +92,45 @@ pub struct AipConditionFindNearbyEntities {
     pub count: i32,
 }
 
-#[derive(Debug)]
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
 pub struct AipConditionMonthDayTime {
     pub month_day: Option<NonZeroU8>,
     pub day_minutes_range: RangeInclusive<i32>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
 pub struct AipConditionWeekDayTime {
     pub week_day: u8,
     pub day_minutes_range: RangeInclusive<i32>,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, JsonSchema, Serialize, Deserialize)]
 pub enum AipMoveMode {
     Walk,
     Run,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, JsonSchema, Serialize, Deserialize)]
 pub enum AipAttackNearbyStat {
     Lowest,
     Highest,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, JsonSchema, Serialize, Deserialize)]
 pub enum AipMoveOrigin {
     Spawn,
     CurrentPosition,
     FindChar,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, JsonSchema, Serialize, Deserialize)]
 pub enum AipSpawnNpcOrigin {
     CurrentPosition,
     AttackerPosition,
     TargetPosition,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, JsonSchema, Serialize, Deserialize)]
 pub enum AipSkillTarget {
     FindChar,
     Target,
@@ -136,7 +138,7 @@ pub enum AipSkillTarget {
     NearChar,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, JsonSchema, Serialize, Deserialize)]
 pub enum AipVariableType {
     LocalNpcObject,
     Ai,
@@ -144,34 +146,34 @@ pub enum AipVariableType {
     Economy,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, JsonSchema, Serialize, Deserialize)]
 pub enum AipMessageType {
     Say,
     Shout,
     Announce,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, JsonSchema, Serialize, Deserialize)]
 pub enum AipMonsterSpawnState {
     Disabled,
     Enabled,
     Toggle,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, JsonSchema, Serialize, Deserialize)]
 pub enum AipDamageType {
     Received,
     Given,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, JsonSchema, Serialize, Deserialize)]
 pub enum AipDistanceOrigin {
     Spawn,
     Owner,
     Target,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, JsonSchema, Serialize, Deserialize)]
 pub enum AipNearbyAlly {
     Ally,
     WithNpcId(AipNpcId),
@@ -186,7 +188,7 @@ pub type AipMotionId = i32;
 pub type AipZoneId = usize;
 pub type AipIsSpawnOwner = bool;
 
-#[derive(Debug)]
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
 pub enum AipCondition {
     CompareAttackerAndTargetAbilityValue(AipOperatorType, AipAbilityType),
     FindNearbyEntities(AipConditionFindNearbyEntities),
@@ -213,7 +215,7 @@ pub enum AipCondition {
     ZoneTime(RangeInclusive<u32>),
 }
 
-#[derive(Debug)]
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
 pub enum AipAction {
     Stop,
     Emote(AipMotionId),
@@ -242,17 +244,20 @@ pub enum AipAction {
     GiveItemToOwner(AipItemBase1000, usize),
 }
 
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
 pub struct AipEvent {
     pub name: String,
     pub conditions: Vec<AipCondition>,
     pub actions: Vec<AipAction>,
 }
 
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
 pub struct AipTrigger {
     pub name: String,
     pub events: Vec<AipEvent>,
 }
 
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
 pub struct AipFile {
     pub idle_trigger_interval: Duration,
     pub damage_trigger_new_target_chance: u32,
@@ -268,6 +273,17 @@ pub struct AipFile {
 pub enum AipReadError {
     #[error("Invalid value")]
     InvalidValue,
+    #[error("Unexpected number of bytes read for opcode {0:X}")]
+    UnexpectedOpcodeSize(u32),
+}
+
+impl AipFile {
+    /// Reads an AIP file from yaml, as written by rose-conv
+    pub fn from_yaml(yaml: &str) -> Result<Self, anyhow::Error> {
+        Ok(serde_yaml::with::singleton_map_recursive::deserialize(
+            serde_yaml::Deserializer::from_str(yaml),
+        )?)
+    }
 }
 
 impl RoseFile for AipFile {
@@ -653,15 +669,12 @@ impl RoseFile for AipFile {
                         }
                         _ => {
                             warn!("Unimplemented AIP condition opcode: {:X}", opcode);
-                            reader.skip(size_bytes - 8);
+                            reader.skip(size_bytes.saturating_sub(8));
                         }
                     }
-                    assert_eq!(
-                        reader.position(),
-                        condition_start_position + size_bytes,
-                        "Unexpected number of bytes read for opcode {:X}",
-                        opcode
-                    );
+                    if reader.position() != condition_start_position + size_bytes {
+                        return Err(AipReadError::UnexpectedOpcodeSize(opcode).into());
+                    }
                 }
 
                 let num_actions = reader.read_u32()?;
@@ -992,15 +1005,12 @@ impl RoseFile for AipFile {
                         }
                         _ => {
                             warn!("Unimplemented AIP action opcode: {:#X}", opcode);
-                            reader.skip(size_bytes - 8);
+                            reader.skip(size_bytes.saturating_sub(8));
                         }
                     }
-                    assert_eq!(
-                        reader.position(),
-                        action_start_position + size_bytes,
-                        "Unexpected number of bytes read for opcode {:X}",
-                        opcode
-                    );
+                    if reader.position() != action_start_position + size_bytes {
+                        return Err(AipReadError::UnexpectedOpcodeSize(opcode).into());
+                    }
                 }
 
                 events.push(AipEvent {
@@ -142,7 +142,7 @@ impl AruaVfsIndex {
         }
 
         let mut reader = RoseFileReader::from(&data[8..]);
-        let mut files = HashMap::with_capacity(file_count as usize);
+        let mut files = HashMap::with_capacity(reader.capacity_hint(file_count as usize));
         for _ in 0..file_count {
             let text_hash = reader.read_u32()?;
             let size = reader.read_u32()?;
@@ -259,7 +259,10 @@ impl VirtualFilesystemDevice for AruaVfsIndex {
             .files
             .get(&FileNameHash::from(path_str).hash)
             .ok_or_else(|| VfsError::FileNotFound(vfs_path.path().into()))?;
-        let file_data = &self.mmap[offset as usize..offset as usize + size as usize];
+        let file_data = self
+            .mmap
+            .get(offset as usize..(offset as usize).saturating_add(size as usize))
+            .ok_or_else(|| VfsError::InvalidFileEntry(vfs_path.path().into()))?;
 
         let crypto = match vfs_path.path().extension().and_then(|x| x.to_str()) {
             Some("CHR") => Some((CHR_KEY, CHR_IV, true)),
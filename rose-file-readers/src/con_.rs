@@ -101,7 +101,7 @@ impl RoseFile for ConFile {
         let num_menus = reader.read_u32()? as usize;
         let menus_offset = conversation_offset + reader.read_u32()? as u64;
 
-        let mut initial_messages = Vec::with_capacity(num_messages);
+        let mut initial_messages = Vec::with_capacity(reader.capacity_hint(num_messages));
         for i in 0..num_messages {
             reader.set_position(messages_offset + i as u64 * 4);
             let message_offset = messages_offset + reader.read_u32()? as u64;
@@ -124,7 +124,7 @@ impl RoseFile for ConFile {
             })
         }
 
-        let mut menus = Vec::with_capacity(num_menus);
+        let mut menus = Vec::with_capacity(reader.capacity_hint(num_menus));
         for i in 0..num_menus {
             reader.set_position(menus_offset + i as u64 * 4);
             let menu_offset = menus_offset + reader.read_u32()? as u64;
@@ -136,7 +136,8 @@ impl RoseFile for ConFile {
             let key_a = menu_message_count as u8;
             let key_b = menu_size as u8;
 
-            let mut menu_messages = Vec::with_capacity(menu_message_count as usize);
+            let mut menu_messages =
+                Vec::with_capacity(reader.capacity_hint(menu_message_count as usize));
             for j in 0..menu_message_count {
                 reader.set_position(menu_offset + 8 + j as u64 * 4);
                 let menu_message_offset =
@@ -60,7 +60,7 @@ impl RoseFile for EftFile {
         let sound_repeat_count = reader.read_u32()?;
 
         let num_particles = reader.read_u32()? as usize;
-        let mut particles = Vec::with_capacity(num_particles);
+        let mut particles = Vec::with_capacity(reader.capacity_hint(num_particles));
         for _ in 0..num_particles {
             let skip_len = reader.read_u32()? as u64;
             reader.skip(skip_len);
@@ -105,7 +105,7 @@ impl RoseFile for EftFile {
         }
 
         let num_meshes = reader.read_u32()? as usize;
-        let mut meshes = Vec::with_capacity(num_meshes);
+        let mut meshes = Vec::with_capacity(reader.capacity_hint(num_meshes));
         for _ in 0..num_meshes {
             let skip_len = reader.read_u32()? as u64;
             reader.skip(skip_len);
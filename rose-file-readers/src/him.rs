@@ -22,8 +22,12 @@ impl RoseFile for HimFile {
     fn read(mut reader: RoseFileReader, _: &Self::ReadOptions) -> Result<Self, anyhow::Error> {
         let width = reader.read_u32()?;
         let height = reader.read_u32()?;
+        if width == 0 || height == 0 {
+            return Err(anyhow::anyhow!("Invalid HIM size: {}x{}", width, height));
+        }
         reader.skip(8);
-        let mut heights = Vec::with_capacity((width * height) as usize);
+        let mut heights =
+            Vec::with_capacity(reader.capacity_hint(width as usize * height as usize));
 
         for _ in 0..height {
             for _ in 0..width {
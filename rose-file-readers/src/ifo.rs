@@ -169,7 +169,7 @@ impl RoseFile for IfoFile {
                 Some(BlockType::AnimatedObject) => {
                     if !read_options.skip_animated_objects {
                         let object_count = reader.read_u32()? as usize;
-                        animated_objects.reserve_exact(object_count);
+                        animated_objects.reserve_exact(reader.capacity_hint(object_count));
 
                         for _ in 0..object_count {
                             animated_objects.push(read_object(&mut reader)?);
@@ -179,7 +179,7 @@ impl RoseFile for IfoFile {
                 Some(BlockType::CollisionObject) => {
                     if !read_options.skip_collision_objects {
                         let object_count = reader.read_u32()? as usize;
-                        collision_objects.reserve_exact(object_count);
+                        collision_objects.reserve_exact(reader.capacity_hint(object_count));
 
                         for _ in 0..object_count {
                             let object = read_object(&mut reader)?;
@@ -190,7 +190,7 @@ impl RoseFile for IfoFile {
                 Some(BlockType::CnstObject) => {
                     if !read_options.skip_cnst_objects {
                         let object_count = reader.read_u32()? as usize;
-                        cnst_objects.reserve_exact(object_count);
+                        cnst_objects.reserve_exact(reader.capacity_hint(object_count));
 
                         for _ in 0..object_count {
                             cnst_objects.push(read_object(&mut reader)?);
@@ -200,7 +200,7 @@ impl RoseFile for IfoFile {
                 Some(BlockType::DecoObject) => {
                     if !read_options.skip_deco_objects {
                         let object_count = reader.read_u32()? as usize;
-                        cnst_objects.reserve_exact(object_count);
+                        cnst_objects.reserve_exact(reader.capacity_hint(object_count));
 
                         for _ in 0..object_count {
                             deco_objects.push(read_object(&mut reader)?);
@@ -210,7 +210,7 @@ impl RoseFile for IfoFile {
                 Some(BlockType::EventObject) => {
                     if !read_options.skip_event_objects {
                         let object_count = reader.read_u32()? as usize;
-                        event_objects.reserve_exact(object_count);
+                        event_objects.reserve_exact(reader.capacity_hint(object_count));
 
                         for _ in 0..object_count {
                             let object = read_object(&mut reader)?;
@@ -227,7 +227,7 @@ impl RoseFile for IfoFile {
                 Some(BlockType::Npc) => {
                     if !read_options.skip_npcs {
                         let object_count = reader.read_u32()? as usize;
-                        npcs.reserve_exact(object_count);
+                        npcs.reserve_exact(reader.capacity_hint(object_count));
 
                         for _ in 0..object_count {
                             let object = read_object(&mut reader)?;
@@ -244,14 +244,15 @@ impl RoseFile for IfoFile {
                 Some(BlockType::MonsterSpawn) => {
                     if !read_options.skip_monster_spawns {
                         let object_count = reader.read_u32()? as usize;
-                        monster_spawns.reserve_exact(object_count);
+                        monster_spawns.reserve_exact(reader.capacity_hint(object_count));
 
                         for _ in 0..object_count {
                             let object = read_object(&mut reader)?;
                             let _spawn_name = reader.read_u8_length_string()?;
 
                             let basic_count = reader.read_u32()?;
-                            let mut basic_spawns = Vec::with_capacity(basic_count as usize);
+                            let mut basic_spawns =
+                                Vec::with_capacity(reader.capacity_hint(basic_count as usize));
                             for _ in 0..basic_count {
                                 let _monster_name = reader.read_u8_length_string()?;
                                 let monster_id = reader.read_u32()?;
@@ -263,7 +264,8 @@ impl RoseFile for IfoFile {
                             }
 
                             let tactic_count = reader.read_u32()?;
-                            let mut tactic_spawns = Vec::with_capacity(basic_count as usize);
+                            let mut tactic_spawns =
+                                Vec::with_capacity(reader.capacity_hint(tactic_count as usize));
                             for _ in 0..tactic_count {
                                 let _monster_name = reader.read_u8_length_string()?;
                                 let monster_id = reader.read_u32()?;
@@ -295,7 +297,7 @@ impl RoseFile for IfoFile {
                         water_size = reader.read_f32()?;
 
                         let object_count = reader.read_u32()? as usize;
-                        water_planes.reserve_exact(object_count);
+                        water_planes.reserve_exact(reader.capacity_hint(object_count));
 
                         for _ in 0..object_count {
                             let start = reader.read_vector3_f32()?;
@@ -307,7 +309,7 @@ impl RoseFile for IfoFile {
                 Some(BlockType::Warp) => {
                     if !read_options.skip_warp_objects {
                         let object_count = reader.read_u32()? as usize;
-                        warps.reserve_exact(object_count);
+                        warps.reserve_exact(reader.capacity_hint(object_count));
 
                         for _ in 0..object_count {
                             let object = read_object(&mut reader)?;
@@ -318,7 +320,7 @@ impl RoseFile for IfoFile {
                 Some(BlockType::EffectObject) => {
                     if !read_options.skip_effect_objects {
                         let object_count = reader.read_u32()? as usize;
-                        effect_objects.reserve_exact(object_count);
+                        effect_objects.reserve_exact(reader.capacity_hint(object_count));
 
                         for _ in 0..object_count {
                             let object = read_object(&mut reader)?;
@@ -333,7 +335,7 @@ impl RoseFile for IfoFile {
                 Some(BlockType::SoundObject) => {
                     if !read_options.skip_sound_objects {
                         let object_count = reader.read_u32()? as usize;
-                        sound_objects.reserve_exact(object_count);
+                        sound_objects.reserve_exact(reader.capacity_hint(object_count));
 
                         for _ in 0..object_count {
                             let object = read_object(&mut reader)?;
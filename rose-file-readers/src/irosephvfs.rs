@@ -140,7 +140,7 @@ impl IrosePhVfsIndex {
         let num_vfs = reader.read_u32_xor(0)? as usize;
         reader.skip(8); // unknown
 
-        let mut storages = Vec::with_capacity(num_vfs);
+        let mut storages = Vec::with_capacity(reader.capacity_hint(num_vfs));
         for _ in 0..num_vfs {
             let filename = reader.read_u16_length_string()?;
             let idx_offset = reader.read_u32()?;
@@ -156,7 +156,7 @@ impl IrosePhVfsIndex {
 
             let mut storage = Storage {
                 mmap,
-                files: HashMap::with_capacity(num_files),
+                files: HashMap::with_capacity(reader.capacity_hint(num_files)),
             };
 
             for _ in 0..num_files {
@@ -193,7 +193,10 @@ impl VirtualFilesystemDevice for IrosePhVfsIndex {
         for storage in &self.storages {
             if let Some(entry) = storage.files.get(&path_hash) {
                 return Ok(VfsFile::View(
-                    &storage.mmap[entry.offset..entry.offset + entry.size],
+                    storage
+                        .mmap
+                        .get(entry.offset..entry.offset.saturating_add(entry.size))
+                        .ok_or_else(|| VfsError::InvalidFileEntry(vfs_path.path().into()))?,
                 ));
             }
         }
@@ -11,13 +11,17 @@ pub struct LtbFile {
 
 impl LtbFile {
     pub fn get_string(&self, row: usize, column: usize) -> Option<String> {
+        if row >= self.rows || column >= self.columns {
+            return None;
+        }
+
         let &(offset, size) = self.cells.get(row * self.columns + column)?;
         if offset < self.data_offset || size == 0 {
             None
         } else {
             let offset = (offset - self.data_offset) as usize / 2;
             Some(String::from_utf16_lossy(
-                &self.data[offset..offset + size as usize],
+                self.data.get(offset..offset + size as usize)?,
             ))
         }
     }
@@ -31,7 +35,7 @@ impl RoseFile for LtbFile {
         let columns = reader.read_u32()? as usize;
         let rows = reader.read_u32()? as usize;
 
-        let mut cells = Vec::with_capacity(rows * columns);
+        let mut cells = Vec::with_capacity(reader.capacity_hint(rows * columns));
         for _ in 0..rows {
             for _ in 0..columns {
                 let position = reader.read_u32()?;
@@ -79,7 +79,7 @@ impl RoseFile for PtlFile {
 
     fn read(mut reader: RoseFileReader, _: &Self::ReadOptions) -> Result<Self, anyhow::Error> {
         let num_sequences = reader.read_u32()? as usize;
-        let mut sequences = Vec::with_capacity(num_sequences);
+        let mut sequences = Vec::with_capacity(reader.capacity_hint(num_sequences));
 
         for _ in 0..num_sequences {
             let name = reader.read_u32_length_string()?.to_string();
@@ -111,7 +111,7 @@ impl RoseFile for PtlFile {
             let blend_op = reader.read_u32()?;
 
             let num_keyframes = reader.read_u32()? as usize;
-            let mut keyframes = Vec::with_capacity(num_keyframes);
+            let mut keyframes = Vec::with_capacity(reader.capacity_hint(num_keyframes));
             for _ in 0..num_keyframes {
                 let keyframe_type = reader.read_u32()?;
                 let start_time_min = reader.read_f32()?;
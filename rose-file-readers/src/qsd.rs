@@ -670,7 +670,49 @@ pub mod editor_friendly {
         }
     }
 
+    impl From<QsdFile> for super::QsdFile {
+        fn from(file: QsdFile) -> Self {
+            let mut triggers = std::collections::HashMap::new();
+
+            for group in file.groups {
+                let mut previous_trigger_name: Option<String> = None;
+
+                for trigger in group.triggers {
+                    let trigger_name = trigger.name;
+                    triggers.insert(
+                        trigger_name.clone(),
+                        super::QsdTrigger {
+                            name: trigger_name.clone(),
+                            conditions: trigger.conditions,
+                            rewards: trigger.rewards,
+                            next_trigger_name: None,
+                        },
+                    );
+
+                    if let Some(previous_trigger_name) = previous_trigger_name.take() {
+                        if let Some(previous_trigger) = triggers.get_mut(&previous_trigger_name) {
+                            previous_trigger.next_trigger_name = Some(trigger_name.clone());
+                        }
+                    }
+
+                    if trigger.check_next {
+                        previous_trigger_name = Some(trigger_name);
+                    }
+                }
+            }
+
+            super::QsdFile { triggers }
+        }
+    }
+
     impl QsdFile {
+        /// Reads an editor friendly QSD file from yaml, as written by rose-conv
+        pub fn from_yaml(yaml: &str) -> Result<Self, anyhow::Error> {
+            Ok(serde_yaml::with::singleton_map_recursive::deserialize(
+                serde_yaml::Deserializer::from_str(yaml),
+            )?)
+        }
+
         fn read_narose667(_reader: RoseFileReader) -> Result<Self, anyhow::Error> {
             // TODO: QsdFile::read_narose667
             Ok(QsdFile::default())
@@ -685,7 +727,7 @@ pub mod editor_friendly {
             for _ in 0..group_count {
                 let trigger_count = reader.read_u32()?;
                 let group_name = reader.read_u16_length_string()?;
-                let mut triggers = Vec::with_capacity(trigger_count as usize);
+                let mut triggers = Vec::with_capacity(reader.capacity_hint(trigger_count as usize));
 
                 for _ in 0..trigger_count {
                     let (trigger_name, rewards, conditions, check_next) =
@@ -1909,7 +1951,7 @@ fn read_trigger(
             }
             _ => {
                 warn!("Unimplemented QSD condition opcode: {:X}", opcode);
-                reader.skip(size_bytes - 8);
+                reader.skip(size_bytes.saturating_sub(8));
             }
         }
 
@@ -2309,7 +2351,7 @@ fn read_trigger(
             }
             _ => {
                 warn!("Unimplemented QSD action opcode: {:X}", opcode);
-                reader.skip(size_bytes - 8);
+                reader.skip(size_bytes.saturating_sub(8));
             }
         }
 
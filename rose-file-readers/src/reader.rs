@@ -90,7 +90,14 @@ impl<'a> RoseFileReader<'a> {
     }
 
     pub fn skip(&mut self, distance: u64) {
-        self.cursor.set_position(self.cursor.position() + distance);
+        self.cursor
+            .set_position(self.cursor.position().saturating_add(distance));
+    }
+
+    /// Clamps an element count read from the file to the number of remaining bytes,
+    /// so a corrupt count can not be used to allocate an unbounded amount of memory.
+    pub fn capacity_hint(&self, count: usize) -> usize {
+        count.min(self.cursor.remaining())
     }
 
     pub fn position(&self) -> u64 {
@@ -130,7 +137,7 @@ impl<'a> RoseFileReader<'a> {
     }
 
     pub fn read_u64(&mut self) -> Result<u64, ReadError> {
-        if self.cursor.remaining() < 4 {
+        if self.cursor.remaining() < 8 {
             Err(ReadError::UnexpectedEof)
         } else {
             Ok(self.cursor.get_u64_le())
@@ -179,7 +186,9 @@ impl<'a> RoseFileReader<'a> {
 
     #[allow(clippy::uninit_vec)]
     pub fn read_vec<T>(&mut self, elements: usize) -> Result<Vec<T>, ReadError> {
-        let bytes_length = std::mem::size_of::<T>() * elements;
+        let bytes_length = std::mem::size_of::<T>()
+            .checked_mul(elements)
+            .ok_or(ReadError::UnexpectedEof)?;
         if self.cursor.remaining() < bytes_length {
             Err(ReadError::UnexpectedEof)
         } else {
@@ -251,14 +260,14 @@ impl<'a> RoseFileReader<'a> {
     }
 
     pub fn read_fixed_length_bytes(&mut self, length: usize) -> Result<&'a [u8], ReadError> {
-        if self.cursor.remaining() < length {
-            Err(ReadError::UnexpectedEof)
-        } else {
-            let start = self.cursor.position() as usize;
-            let end = start + length;
-            self.cursor.set_position(end as u64);
-            Ok(&self.cursor.get_ref()[start..end])
-        }
+        let data: &'a [u8] = self.cursor.get_ref();
+        let start = self.cursor.position() as usize;
+        let bytes = start
+            .checked_add(length)
+            .and_then(|end| data.get(start..end))
+            .ok_or(ReadError::UnexpectedEof)?;
+        self.cursor.set_position((start + length) as u64);
+        Ok(bytes)
     }
 
     pub fn read_variable_length_bytes(&mut self) -> Result<&'a [u8], ReadError> {
@@ -289,7 +298,7 @@ impl<'a> RoseFileReader<'a> {
 
     pub fn read_null_terminated_bytes(&mut self) -> Result<&'a [u8], ReadError> {
         let start = self.cursor.position() as usize;
-        let end = self.cursor.get_ref().len() - 1;
+        let end = self.cursor.get_ref().len();
 
         for i in start..end {
             if self.cursor.get_ref()[i] == 0 {
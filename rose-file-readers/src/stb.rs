@@ -63,6 +63,14 @@ impl StbFile {
         let column_count = reader.read_u32()? as usize;
         let _row_height = reader.read_u32()?;
 
+        if row_count == 0 || column_count == 0 {
+            return Err(anyhow!(
+                "Invalid STB size: {} rows, {} columns",
+                row_count,
+                column_count
+            ));
+        }
+
         // column widths
         if version == 0 {
             reader.skip(size_of::<u32>() as u64);
@@ -70,7 +78,7 @@ impl StbFile {
             reader.skip((size_of::<u16>() * (column_count + 1)) as u64);
         }
 
-        let mut column_names = Vec::with_capacity(column_count);
+        let mut column_names = Vec::with_capacity(reader.capacity_hint(column_count));
         for _ in 0..column_count {
             column_names.push(String::from(reader.read_u16_length_string()?));
         }
@@ -81,13 +89,13 @@ impl StbFile {
 
         reader.read_u16_length_string()?; // Ignore column title line
 
-        let mut row_names = Vec::with_capacity(row_count);
+        let mut row_names = Vec::with_capacity(reader.capacity_hint(row_count));
         for _ in 0..rows {
             row_names.push(String::from(reader.read_u16_length_string()?));
         }
 
         let mut data = Vec::with_capacity(reader.remaining());
-        let mut cells = Vec::with_capacity(row_count * column_count);
+        let mut cells = Vec::with_capacity(reader.capacity_hint(row_count * column_count));
 
         reader.set_position(data_position);
         for _ in 0..rows {
@@ -25,6 +25,7 @@ impl StlLanguage {
 }
 
 #[allow(dead_code)]
+#[derive(Default)]
 pub struct StlFile {
     data: Vec<u8>,
     string_keys: HashMap<String, u32>,
@@ -48,6 +49,36 @@ pub struct StlQuestEntry<'a> {
     pub end_message: &'a str,
 }
 
+/// Stores each unique string once, many entries share the same text across languages or
+/// have empty descriptions.
+#[derive(Default)]
+struct StlStringArena<'a> {
+    data: Vec<u8>,
+    interned: HashMap<&'a [u8], (u32, u32)>,
+}
+
+impl<'a> StlStringArena<'a> {
+    fn intern(&mut self, bytes: &'a [u8]) -> (u32, u32) {
+        if let Some(entry) = self.interned.get(bytes) {
+            return *entry;
+        }
+
+        let entry = (self.data.len() as u32, bytes.len() as u32);
+        self.data.extend_from_slice(bytes);
+        self.interned.insert(bytes, entry);
+        entry
+    }
+}
+
+fn read_stl_entry<'a>(
+    reader: &mut RoseFileReader<'a>,
+    data: &mut StlStringArena<'a>,
+) -> Result<(u32, u32), anyhow::Error> {
+    let text_bytes = reader.read_variable_length_bytes()?;
+    let _ = str::from_utf8(text_bytes)?;
+    Ok(data.intern(text_bytes))
+}
+
 #[derive(Default)]
 pub struct StlReadOptions {
     pub language_filter: Option<Vec<usize>>,
@@ -79,8 +110,8 @@ impl RoseFile for StlFile {
         };
 
         let key_count = reader.read_u32()? as usize;
-        let mut string_keys = HashMap::with_capacity(key_count);
-        let mut integer_keys = HashMap::with_capacity(key_count);
+        let mut string_keys = HashMap::with_capacity(reader.capacity_hint(key_count));
+        let mut integer_keys = HashMap::with_capacity(reader.capacity_hint(key_count));
         for i in 0..key_count {
             let key = reader.read_variable_length_string()?;
             let index = reader.read_u32()?;
@@ -89,24 +120,13 @@ impl RoseFile for StlFile {
         }
 
         let language_count = reader.read_u32()? as usize;
-        let mut data = Vec::new();
-
-        let read_stl_entry = |reader: &mut RoseFileReader,
-                              data: &mut Vec<u8>|
-         -> Result<(u32, u32), anyhow::Error> {
-            let text_bytes = reader.read_variable_length_bytes()?;
-            let _ = str::from_utf8(text_bytes)?;
-            let text_bytes_length = text_bytes.len();
-            let text_offset = data.len();
-            data.extend_from_slice(text_bytes);
-            Ok((text_offset as u32, text_bytes_length as u32))
-        };
+        let mut data = StlStringArena::default();
 
-        let mut languages = Vec::with_capacity(language_count);
+        let mut languages = Vec::with_capacity(reader.capacity_hint(language_count));
         for language_index in 0..language_count {
             let language_offset = reader.read_u32()?;
             let language_save_position = reader.position();
-            let mut language = StlLanguage::with_capacity(key_count);
+            let mut language = StlLanguage::with_capacity(reader.capacity_hint(key_count));
             reader.set_position(language_offset as u64);
 
             if let Some(language_filter) = read_options.language_filter.as_ref() {
@@ -154,7 +174,7 @@ impl RoseFile for StlFile {
         }
 
         Ok(StlFile {
-            data,
+            data: data.data,
             string_keys,
             integer_keys,
             languages,
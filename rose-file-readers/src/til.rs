@@ -21,7 +21,10 @@ impl RoseFile for TilFile {
     fn read(mut reader: RoseFileReader, _: &Self::ReadOptions) -> Result<Self, anyhow::Error> {
         let width = reader.read_u32()?;
         let height = reader.read_u32()?;
-        let mut tiles = Vec::with_capacity((width * height) as usize);
+        if width == 0 || height == 0 {
+            return Err(anyhow::anyhow!("Invalid TIL size: {}x{}", width, height));
+        }
+        let mut tiles = Vec::with_capacity(reader.capacity_hint(width as usize * height as usize));
 
         for _ in 0..height {
             for _ in 0..width {
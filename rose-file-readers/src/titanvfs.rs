@@ -139,7 +139,7 @@ impl TitanVfsIndex {
         }
 
         let mut reader = RoseFileReader::from(&data[8..]);
-        let mut files = HashMap::with_capacity(file_count as usize);
+        let mut files = HashMap::with_capacity(reader.capacity_hint(file_count as usize));
         for _ in 0..file_count {
             let text_hash = reader.read_u32()?;
             let size = reader.read_u32()?;
@@ -167,7 +167,9 @@ impl VirtualFilesystemDevice for TitanVfsIndex {
             .ok_or_else(|| VfsError::FileNotFound(vfs_path.path().into()))?;
 
         Ok(VfsFile::View(
-            &self.mmap[offset as usize..offset as usize + size as usize],
+            self.mmap
+                .get(offset as usize..(offset as usize).saturating_add(size as usize))
+                .ok_or_else(|| VfsError::InvalidFileEntry(vfs_path.path().into()))?,
         ))
     }
 
@@ -38,10 +38,14 @@ impl VfsIndex {
         let current_version = reader.read_u32()?;
 
         let num_vfs = reader.read_u32()? as usize;
-        let mut storages = Vec::with_capacity(num_vfs);
+        let mut storages = Vec::with_capacity(reader.capacity_hint(num_vfs));
         for _ in 0..num_vfs {
-            let (filename, _, _) =
-                EUC_KR.decode(reader.read_u16_length_bytes()?.split_last().unwrap().1);
+            let (filename, _, _) = EUC_KR.decode(
+                reader
+                    .read_u16_length_bytes()?
+                    .split_last()
+                    .map_or(&[][..], |(_, name)| name),
+            );
             let offset = reader.read_u32()? as u64;
 
             let next_vfs_position = reader.position();
@@ -61,12 +65,16 @@ impl VfsIndex {
 
             let mut storage = Storage {
                 mmap,
-                files: HashMap::with_capacity(num_files),
+                files: HashMap::with_capacity(reader.capacity_hint(num_files)),
             };
 
             for _ in 0..num_files {
-                let (filename, _, _) =
-                    EUC_KR.decode(reader.read_u16_length_bytes()?.split_last().unwrap().1);
+                let (filename, _, _) = EUC_KR.decode(
+                    reader
+                        .read_u16_length_bytes()?
+                        .split_last()
+                        .map_or(&[][..], |(_, name)| name),
+                );
                 let offset = reader.read_u32()? as usize;
                 let size = reader.read_u32()? as usize;
                 let _block_size = reader.read_u32()?;
@@ -101,7 +109,9 @@ impl VirtualFilesystemDevice for VfsIndex {
         for vfs in &self.storages {
             if let Some(entry) = vfs.files.get(vfs_path.path()) {
                 return Ok(VfsFile::View(
-                    &vfs.mmap[entry.offset..entry.offset + entry.size],
+                    vfs.mmap
+                        .get(entry.offset..entry.offset.saturating_add(entry.size))
+                        .ok_or_else(|| VfsError::InvalidFileEntry(vfs_path.path().into()))?,
                 ));
             }
         }
@@ -12,10 +12,22 @@ pub enum VfsFile<'a> {
     View(&'a [u8]),
 }
 
+impl<'a> VfsFile<'a> {
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            VfsFile::Buffer(vec) => vec,
+            VfsFile::View(buf) => buf,
+        }
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum VfsError {
     #[error("File {0} not found")]
     FileNotFound(PathBuf),
+
+    #[error("File {0} is outside of the archive bounds")]
+    InvalidFileEntry(PathBuf),
 }
 
 impl<'a> From<&'a VfsFile<'a>> for RoseFileReader<'a> {
@@ -29,7 +29,7 @@ impl RoseFile for ZmdFile {
         };
 
         let bone_count = reader.read_u32()? as usize;
-        let mut bones = Vec::with_capacity(bone_count);
+        let mut bones = Vec::with_capacity(reader.capacity_hint(bone_count));
         for _ in 0..bone_count {
             let parent = reader.read_u32()? as u16;
             let _name = reader.read_null_terminated_string()?;
@@ -43,7 +43,7 @@ impl RoseFile for ZmdFile {
         }
 
         let dummy_bone_count = reader.read_u32()? as usize;
-        let mut dummy_bones = Vec::with_capacity(dummy_bone_count);
+        let mut dummy_bones = Vec::with_capacity(reader.capacity_hint(dummy_bone_count));
         for _ in 0..dummy_bone_count {
             let _name = reader.read_null_terminated_string()?;
             let parent = reader.read_u32()? as u16;
@@ -61,22 +61,24 @@ impl RoseFile for ZmoFile {
 
         if !read_options.skip_animation {
             let channel_count = reader.read_u32()? as usize;
-            channels.reserve_exact(channel_count);
+            channels.reserve_exact(reader.capacity_hint(channel_count));
+            let frame_capacity = reader.capacity_hint(num_frames.saturating_mul(channel_count))
+                / channel_count.max(1);
             for _ in 0..channel_count {
                 let channel_type = reader.read_u32()?;
                 let channel_bone_index = reader.read_u32()?;
                 let channel = match channel_type {
                     1 => ZmoChannel::Empty,
-                    2 => ZmoChannel::Position(Vec::with_capacity(num_frames)),
-                    4 => ZmoChannel::Rotation(Vec::with_capacity(num_frames)),
-                    8 => ZmoChannel::Normal(Vec::with_capacity(num_frames)),
-                    16 => ZmoChannel::Alpha(Vec::with_capacity(num_frames)),
-                    32 => ZmoChannel::UV1(Vec::with_capacity(num_frames)),
-                    64 => ZmoChannel::UV2(Vec::with_capacity(num_frames)),
-                    128 => ZmoChannel::UV3(Vec::with_capacity(num_frames)),
-                    256 => ZmoChannel::UV4(Vec::with_capacity(num_frames)),
-                    512 => ZmoChannel::Texture(Vec::with_capacity(num_frames)),
-                    1024 => ZmoChannel::Scale(Vec::with_capacity(num_frames)),
+                    2 => ZmoChannel::Position(Vec::with_capacity(frame_capacity)),
+                    4 => ZmoChannel::Rotation(Vec::with_capacity(frame_capacity)),
+                    8 => ZmoChannel::Normal(Vec::with_capacity(frame_capacity)),
+                    16 => ZmoChannel::Alpha(Vec::with_capacity(frame_capacity)),
+                    32 => ZmoChannel::UV1(Vec::with_capacity(frame_capacity)),
+                    64 => ZmoChannel::UV2(Vec::with_capacity(frame_capacity)),
+                    128 => ZmoChannel::UV3(Vec::with_capacity(frame_capacity)),
+                    256 => ZmoChannel::UV4(Vec::with_capacity(frame_capacity)),
+                    512 => ZmoChannel::Texture(Vec::with_capacity(frame_capacity)),
+                    1024 => ZmoChannel::Scale(Vec::with_capacity(frame_capacity)),
                     invalid => bail!("Invalid ZMO channel type: {}", invalid),
                 };
                 channels.push((channel_bone_index, channel));
@@ -81,7 +81,7 @@ impl ZmsFile {
 
         let read_vertex_f32x2 =
             |vertex_count, reader: &mut RoseFileReader| -> Result<Vec<[f32; 2]>, anyhow::Error> {
-                let mut values = Vec::with_capacity(vertex_count);
+                let mut values = Vec::with_capacity(reader.capacity_hint(vertex_count));
                 for _ in 0..vertex_count {
                     let _vertex_id = reader.read_u32()?;
                     let value_x = reader.read_f32()?;
@@ -92,7 +92,7 @@ impl ZmsFile {
             };
         let read_vertex_f32x3 =
             |vertex_count, reader: &mut RoseFileReader| -> Result<Vec<[f32; 3]>, anyhow::Error> {
-                let mut values = Vec::with_capacity(vertex_count);
+                let mut values = Vec::with_capacity(reader.capacity_hint(vertex_count));
                 for _ in 0..vertex_count {
                     let _vertex_id = reader.read_u32()?;
                     let value = reader.read_vector3_f32()?;
@@ -102,7 +102,7 @@ impl ZmsFile {
             };
         let read_vertex_f32x4 =
             |vertex_count, reader: &mut RoseFileReader| -> Result<Vec<[f32; 4]>, anyhow::Error> {
-                let mut values = Vec::with_capacity(vertex_count);
+                let mut values = Vec::with_capacity(reader.capacity_hint(vertex_count));
                 for _ in 0..vertex_count {
                     let _vertex_id = reader.read_u32()?;
                     let value = reader.read_vector4_f32()?;
@@ -139,8 +139,8 @@ impl ZmsFile {
         let (bone_weights, bone_indices) = if format.contains(ZmsFormatFlags::BONE_WEIGHT)
             && format.contains(ZmsFormatFlags::BONE_INDEX)
         {
-            let mut bone_weights = Vec::with_capacity(vertex_count);
-            let mut bone_indices = Vec::with_capacity(vertex_count);
+            let mut bone_weights = Vec::with_capacity(reader.capacity_hint(vertex_count));
+            let mut bone_indices = Vec::with_capacity(reader.capacity_hint(vertex_count));
             for _ in 0..vertex_count {
                 let _vertex_id = reader.read_u32()?;
                 let weight = reader.read_vector4_f32()?;
@@ -201,7 +201,7 @@ impl ZmsFile {
         };
 
         let triangle_count = reader.read_u32()? as usize;
-        let mut indices = Vec::with_capacity(triangle_count);
+        let mut indices = Vec::with_capacity(reader.capacity_hint(triangle_count));
         for _ in 0..triangle_count {
             let _vertex_id = reader.read_u32()?;
             indices.push(reader.read_u32()? as u16);
@@ -273,8 +273,8 @@ impl ZmsFile {
         let (bone_weights, bone_indices) = if format.contains(ZmsFormatFlags::BONE_WEIGHT)
             && format.contains(ZmsFormatFlags::BONE_INDEX)
         {
-            let mut bone_weights = Vec::with_capacity(vertex_count);
-            let mut bone_indices = Vec::with_capacity(vertex_count);
+            let mut bone_weights = Vec::with_capacity(reader.capacity_hint(vertex_count));
+            let mut bone_indices = Vec::with_capacity(reader.capacity_hint(vertex_count));
             for _ in 0..vertex_count {
                 let weight = reader.read_vector4_f32()?;
                 let index = reader.read_vector4_u16()?;
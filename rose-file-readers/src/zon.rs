@@ -83,7 +83,7 @@ impl RoseFile for ZonFile {
                 Some(BlockType::EventPositions) => {
                     if !read_options.skip_event_positions {
                         let object_count = reader.read_u32()? as usize;
-                        event_positions.reserve_exact(object_count);
+                        event_positions.reserve_exact(reader.capacity_hint(object_count));
                         for _ in 0..object_count {
                             let position = reader.read_vector3_f32()?;
                             let name = reader.read_u8_length_string()?;
@@ -94,7 +94,7 @@ impl RoseFile for ZonFile {
                 Some(BlockType::Textures) => {
                     if !read_options.skip_textures {
                         let texture_count = reader.read_u32()? as usize;
-                        tile_textures.reserve_exact(texture_count);
+                        tile_textures.reserve_exact(reader.capacity_hint(texture_count));
                         for _ in 0..texture_count {
                             tile_textures.push(reader.read_u8_length_string()?.into());
                         }
@@ -103,7 +103,7 @@ impl RoseFile for ZonFile {
                 Some(BlockType::Tiles) => {
                     if !read_options.skip_tiles {
                         let tile_count = reader.read_u32()? as usize;
-                        tiles.reserve_exact(tile_count);
+                        tiles.reserve_exact(reader.capacity_hint(tile_count));
                         for _ in 0..tile_count {
                             let layer1 = reader.read_u32()?;
                             let layer2 = reader.read_u32()?;
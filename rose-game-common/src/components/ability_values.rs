@@ -134,6 +134,7 @@ pub struct AbilityValues {
     pub max_damage_sources: usize,
     pub drop_rate: i32,
     pub max_weight: i32,
+    pub max_summon_points: i32,
     pub summon_owner_level: Option<i32>,
     pub summon_skill_level: Option<i32>,
     pub adjust: AbilityValuesAdjust,
@@ -216,6 +217,10 @@ impl AbilityValues {
         self.max_weight
     }
 
+    pub fn get_max_summon_points(&self) -> i32 {
+        self.max_summon_points
+    }
+
     pub fn get_npc_store_buy_rate(&self) -> i32 {
         self.npc_store_buy_rate
     }
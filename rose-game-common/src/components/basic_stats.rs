@@ -11,7 +11,7 @@ pub enum BasicStatType {
     Sense,
 }
 
-#[derive(Component, Clone, Debug, Deserialize, Serialize, Reflect)]
+#[derive(Component, Clone, Debug, PartialEq, Deserialize, Serialize, Reflect)]
 pub struct BasicStats {
     pub strength: i32,
     pub dexterity: i32,
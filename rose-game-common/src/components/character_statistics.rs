@@ -0,0 +1,35 @@
+use std::collections::HashMap;
+
+use bevy::ecs::prelude::Component;
+use serde::{Deserialize, Serialize};
+
+use rose_data::NpcId;
+
+use crate::components::Money;
+
+#[derive(Component, Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CharacterStatistics {
+    pub monster_kills: u32,
+    pub npc_kills: HashMap<NpcId, u32>,
+    pub deaths: u32,
+    /// Money from drops, quest rewards and selling to npc or personal stores
+    pub money_earned: Money,
+    pub distance_walked: f64,
+    pub quests_completed: u32,
+}
+
+impl CharacterStatistics {
+    pub fn add_monster_kill(&mut self, npc_id: NpcId) {
+        self.monster_kills += 1;
+        *self.npc_kills.entry(npc_id).or_default() += 1;
+    }
+
+    pub fn add_money_earned(&mut self, money: Money) {
+        self.money_earned = self.money_earned + money;
+    }
+
+    pub fn get_npc_kills(&self, npc_id: NpcId) -> u32 {
+        self.npc_kills.get(&npc_id).copied().unwrap_or(0)
+    }
+}
@@ -1,8 +1,11 @@
 use std::num::{NonZeroU16, NonZeroU32};
 
 use bevy::prelude::{Deref, DerefMut};
+use enum_map::{enum_map, Enum, EnumMap};
 use serde::{Deserialize, Serialize};
 
+use rose_data::ClanMemberPosition;
+
 #[derive(Deref, DerefMut, Copy, Clone, Debug, Serialize, Deserialize)]
 pub struct ClanUniqueId(pub NonZeroU32);
 
@@ -34,3 +37,42 @@ pub enum ClanMark {
         crc16: u16,
     },
 }
+
+#[derive(Copy, Clone, Debug, Enum, Serialize, Deserialize)]
+pub enum ClanPermission {
+    Invite,
+    Kick,
+    SetNotice,
+    VaultDeposit,
+    VaultWithdraw,
+    VaultViewLog,
+}
+
+/// The clan member positions which are allowed each permission, the clan master is always
+/// allowed everything so the clan can never be locked out of changing them.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ClanPermissions(pub EnumMap<ClanPermission, EnumMap<ClanMemberPosition, bool>>);
+
+impl ClanPermissions {
+    pub fn is_allowed(&self, position: ClanMemberPosition, permission: ClanPermission) -> bool {
+        matches!(position, ClanMemberPosition::Master) || self.0[permission][position]
+    }
+}
+
+impl Default for ClanPermissions {
+    fn default() -> Self {
+        let from_position =
+            |minimum_position: ClanMemberPosition| -> EnumMap<ClanMemberPosition, bool> {
+                enum_map! { position => position >= minimum_position }
+            };
+
+        Self(enum_map! {
+            ClanPermission::Invite => from_position(ClanMemberPosition::Commander),
+            ClanPermission::Kick => from_position(ClanMemberPosition::Commander),
+            ClanPermission::SetNotice => from_position(ClanMemberPosition::DeputyMaster),
+            ClanPermission::VaultDeposit => from_position(ClanMemberPosition::Junior),
+            ClanPermission::VaultWithdraw => from_position(ClanMemberPosition::Commander),
+            ClanPermission::VaultViewLog => from_position(ClanMemberPosition::Master),
+        })
+    }
+}
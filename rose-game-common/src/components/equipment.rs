@@ -3,8 +3,8 @@ use enum_map::EnumMap;
 use serde::{Deserialize, Serialize};
 
 use rose_data::{
-    AmmoIndex, EquipmentIndex, EquipmentItem, ItemDatabase, ItemReference, ItemType, StackableItem,
-    VehiclePartIndex, WeaponItemData,
+    AmmoIndex, CostumeIndex, EquipmentIndex, EquipmentItem, ItemDatabase, ItemReference, ItemType,
+    StackableItem, VehiclePartIndex, WeaponItemData,
 };
 
 #[derive(Component, Clone, Debug, Default, Deserialize, Serialize)]
@@ -12,6 +12,9 @@ pub struct Equipment {
     pub equipped_items: EnumMap<EquipmentIndex, Option<EquipmentItem>>,
     pub equipped_vehicle: EnumMap<VehiclePartIndex, Option<EquipmentItem>>,
     pub equipped_ammo: EnumMap<AmmoIndex, Option<StackableItem>>,
+    /// Only visible to clients which support costume slots, and do not affect ability values
+    #[serde(default)]
+    pub equipped_costumes: EnumMap<CostumeIndex, Option<EquipmentItem>>,
 }
 
 pub trait EquipmentItemReference {
@@ -80,6 +83,14 @@ impl Equipment {
         &mut self.equipped_items[index]
     }
 
+    pub fn get_costume_item(&self, index: CostumeIndex) -> Option<&EquipmentItem> {
+        self.equipped_costumes[index].as_ref()
+    }
+
+    pub fn get_costume_slot_mut(&mut self, index: CostumeIndex) -> &mut Option<EquipmentItem> {
+        &mut self.equipped_costumes[index]
+    }
+
     pub fn get_vehicle_slot_mut(&mut self, index: VehiclePartIndex) -> &mut Option<EquipmentItem> {
         &mut self.equipped_vehicle[index]
     }
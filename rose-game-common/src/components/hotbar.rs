@@ -1,7 +1,7 @@
 use bevy::ecs::prelude::Component;
 use serde::{Deserialize, Serialize};
 
-use crate::components::{ItemSlot, SkillSlot};
+use crate::components::{Equipment, Inventory, ItemSlot, SkillList, SkillSlot};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum HotbarSlot {
@@ -32,4 +32,93 @@ impl Hotbar {
         *page_slot = slot;
         Some(())
     }
+
+    /// Returns false if the slot references an item or skill which the character does not have.
+    pub fn is_valid_slot(
+        slot: &HotbarSlot,
+        equipment: &Equipment,
+        inventory: &Inventory,
+        skill_list: &SkillList,
+    ) -> bool {
+        match *slot {
+            HotbarSlot::Inventory(item_slot) => match item_slot {
+                ItemSlot::Equipment(index) => equipment.get_equipment_item(index).is_some(),
+                ItemSlot::Ammo(index) => equipment.get_ammo_item(index).is_some(),
+                ItemSlot::Vehicle(index) => equipment.get_vehicle_item(index).is_some(),
+                ItemSlot::Inventory(..) => inventory.get_item(item_slot).is_some(),
+            },
+            HotbarSlot::Skill(skill_slot) => skill_list.get_skill(skill_slot).is_some(),
+            HotbarSlot::Command(_)
+            | HotbarSlot::Emote(_)
+            | HotbarSlot::Dialog(_)
+            | HotbarSlot::ClanSkill(_) => true,
+        }
+    }
+
+    /// Clears every slot which is no longer valid, returning the index of each cleared slot.
+    pub fn remove_invalid_slots(
+        &mut self,
+        equipment: &Equipment,
+        inventory: &Inventory,
+        skill_list: &SkillList,
+    ) -> Vec<usize> {
+        let mut removed = Vec::new();
+
+        for (page_index, page) in self.pages.iter_mut().enumerate() {
+            for (index, page_slot) in page.iter_mut().enumerate() {
+                if page_slot.as_ref().map_or(false, |slot| {
+                    !Self::is_valid_slot(slot, equipment, inventory, skill_list)
+                }) {
+                    *page_slot = None;
+                    removed.push(page_index * HOTBAR_PAGE_SIZE + index);
+                }
+            }
+        }
+
+        removed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rose_data::{EquipmentIndex, EquipmentItem, ItemReference, ItemType};
+
+    use super::*;
+
+    #[test]
+    fn equipped_weapon_slot_is_valid() {
+        let mut equipment = Equipment::new();
+        let inventory = Inventory::new();
+        let skill_list = SkillList::default();
+        let mut hotbar = Hotbar::new();
+        let weapon_slot = HotbarSlot::Inventory(ItemSlot::Equipment(EquipmentIndex::Weapon));
+        hotbar.set_slot(0, Some(weapon_slot.clone()));
+
+        assert!(!Hotbar::is_valid_slot(
+            &weapon_slot,
+            &equipment,
+            &inventory,
+            &skill_list
+        ));
+
+        equipment.equipped_items[EquipmentIndex::Weapon] =
+            EquipmentItem::new(ItemReference::new(ItemType::Weapon, 1), 100);
+        assert!(Hotbar::is_valid_slot(
+            &weapon_slot,
+            &equipment,
+            &inventory,
+            &skill_list
+        ));
+        assert!(hotbar
+            .remove_invalid_slots(&equipment, &inventory, &skill_list)
+            .is_empty());
+        assert!(hotbar.pages[0][0].is_some());
+
+        equipment.equipped_items[EquipmentIndex::Weapon] = None;
+        assert_eq!(
+            hotbar.remove_invalid_slots(&equipment, &inventory, &skill_list),
+            vec![0]
+        );
+        assert!(hotbar.pages[0][0].is_none());
+    }
 }
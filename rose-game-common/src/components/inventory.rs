@@ -387,6 +387,32 @@ impl Inventory {
             .find_item(item_reference)
     }
 
+    /// Sorts a page by key and restacks its items, returning the previous and new slot of every
+    /// item which was in the page.
+    pub fn sort_page_by_key<K: Ord>(
+        &mut self,
+        page_type: InventoryPageType,
+        mut key: impl FnMut(&Item) -> K,
+    ) -> Vec<(ItemSlot, ItemSlot)> {
+        let page = self.get_page_mut(page_type);
+        let mut items: Vec<(usize, Item)> = page
+            .slots
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(index, slot)| slot.take().map(|item| (index, item)))
+            .collect();
+        items.sort_by_key(|(_, item)| key(item));
+
+        let mut moved_items = Vec::with_capacity(items.len());
+        for (index, item) in items {
+            let (slot, _) = page
+                .try_add_item(item)
+                .expect("sorted items must fit in the page they were taken from");
+            moved_items.push((ItemSlot::Inventory(page_type, index), slot));
+        }
+        moved_items
+    }
+
     pub fn has_empty_slot(&self, page_type: InventoryPageType) -> bool {
         self.get_page(page_type)
             .slots
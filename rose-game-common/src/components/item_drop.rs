@@ -11,6 +11,28 @@ pub enum DroppedItem {
     Money(Money),
 }
 
+impl DroppedItem {
+    /// Stack another dropped item onto this one, only money or the same stackable item can be
+    /// stacked, otherwise the other item is returned unchanged
+    pub fn try_stack_with(&mut self, other: DroppedItem) -> Result<(), DroppedItem> {
+        match (self, other) {
+            (DroppedItem::Money(money), DroppedItem::Money(other_money)) => {
+                *money = *money + other_money;
+                Ok(())
+            }
+            (DroppedItem::Item(item), DroppedItem::Item(Item::Stackable(other_stackable))) => {
+                if item.can_stack_with(&other_stackable).is_ok() {
+                    item.try_stack_with(other_stackable).ok();
+                    Ok(())
+                } else {
+                    Err(DroppedItem::Item(Item::Stackable(other_stackable)))
+                }
+            }
+            (_, other) => Err(other),
+        }
+    }
+}
+
 impl<T: Into<Item>> From<T> for DroppedItem {
     fn from(item: T) -> Self {
         DroppedItem::Item(item.into())
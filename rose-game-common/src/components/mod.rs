@@ -2,6 +2,7 @@ mod ability_values;
 mod basic_stats;
 mod character_delete_time;
 mod character_info;
+mod character_statistics;
 mod clan;
 mod equipment;
 mod experience_points;
@@ -27,7 +28,8 @@ pub use ability_values::{AbilityValues, AbilityValuesAdjust, DamageCategory, Dam
 pub use basic_stats::{BasicStatType, BasicStats};
 pub use character_delete_time::CharacterDeleteTime;
 pub use character_info::{CharacterGender, CharacterInfo, CharacterUniqueId};
-pub use clan::{ClanLevel, ClanMark, ClanPoints, ClanUniqueId};
+pub use character_statistics::CharacterStatistics;
+pub use clan::{ClanLevel, ClanMark, ClanPermission, ClanPermissions, ClanPoints, ClanUniqueId};
 pub use equipment::{Equipment, EquipmentItemDatabase, EquipmentItemReference};
 pub use experience_points::ExperiencePoints;
 pub use health_points::HealthPoints;
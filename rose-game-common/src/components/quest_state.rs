@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use bevy::ecs::prelude::Component;
 use bitvec::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -80,6 +82,9 @@ pub struct QuestState {
     pub union_variables: [u16; 10],
     pub quest_switches: BitArr!(for 1024, in u32, Lsb0),
     pub active_quests: [Option<ActiveQuest>; 10],
+    /// When each repeatable quest was last completed, as a unix timestamp
+    #[serde(default)]
+    pub repeatable_quest_completions: HashMap<usize, i64>,
 }
 
 impl QuestState {
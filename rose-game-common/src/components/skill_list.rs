@@ -3,10 +3,10 @@ use serde::{Deserialize, Serialize};
 
 use rose_data::{SkillData, SkillDatabase, SkillId, SkillPageType};
 
-#[derive(Copy, Clone, Debug, Deserialize, Serialize)]
+#[derive(Copy, Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 pub struct SkillSlot(pub SkillPageType, pub usize);
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct SkillPage {
     pub page_type: SkillPageType,
     pub skills: Vec<Option<SkillId>>,
@@ -65,7 +65,7 @@ impl SkillPage {
     }
 }
 
-#[derive(Component, Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Component, Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
 pub struct SkillList {
     pub pages: Vec<SkillPage>,
 }
@@ -81,6 +81,20 @@ impl SkillList {
             .find(|page| page.page_type == page_type)
     }
 
+    /// Adds any pages from template which are missing, and grows any page smaller than the
+    /// template's page of the same type.
+    pub fn add_missing_pages(&mut self, template: &SkillList) {
+        for template_page in template.pages.iter() {
+            if let Some(page) = self.get_page_mut(template_page.page_type) {
+                if page.skills.len() < template_page.skills.len() {
+                    page.skills.resize(template_page.skills.len(), None);
+                }
+            } else {
+                self.pages.push(template_page.clone());
+            }
+        }
+    }
+
     pub fn add_skill(&mut self, skill_data: &SkillData) -> Option<(SkillSlot, SkillId)> {
         self.get_page_mut(skill_data.page)
             .and_then(|page| page.add_skill(skill_data))
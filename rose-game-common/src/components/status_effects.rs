@@ -3,7 +3,10 @@ use enum_map::EnumMap;
 use serde::{Deserialize, Serialize};
 use std::time::{Duration, Instant};
 
-use rose_data::{StatusEffectData, StatusEffectId, StatusEffectType};
+use rose_data::{
+    StatusEffectClearedByType, StatusEffectData, StatusEffectDatabase, StatusEffectId,
+    StatusEffectType,
+};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ActiveStatusEffect {
@@ -44,10 +47,27 @@ impl StatusEffects {
         Self::default()
     }
 
+    /// The remaining duration of each active status effect at `now`.
+    pub fn remaining_times(&self, now: Instant) -> EnumMap<StatusEffectType, Option<Duration>> {
+        self.expire_times.map(|_, expire_time| {
+            expire_time.map(|expire_time| expire_time.saturating_duration_since(now))
+        })
+    }
+
+    /// Only one status effect of each type can be active, a stronger application overrides the
+    /// active effect, an equal application of the same effect refreshes its duration, and a
+    /// weaker application is rejected.
     pub fn can_apply(&self, status_effect_data: &StatusEffectData, value: i32) -> bool {
         match &self.active[status_effect_data.status_effect_type] {
             Some(status_effect) => {
-                status_effect_data.can_be_reapplied && value > status_effect.value
+                if value < status_effect.value {
+                    false
+                } else if status_effect.id == status_effect_data.id && value == status_effect.value
+                {
+                    true
+                } else {
+                    status_effect_data.can_be_reapplied
+                }
             }
             None => true,
         }
@@ -124,9 +144,266 @@ impl StatusEffects {
         }
     }
 
+    /// Removes the active status effects which are cleared by the given clear type, status
+    /// effects with a value greater than max_value resist being cleared.
+    ///
+    /// Returns the types of the removed status effects.
+    pub fn clear_status_effects(
+        &mut self,
+        status_effects_regen: &mut StatusEffectsRegen,
+        status_effect_database: &StatusEffectDatabase,
+        clear_type: StatusEffectType,
+        max_value: Option<i32>,
+    ) -> Vec<StatusEffectType> {
+        let mut cleared = Vec::new();
+
+        for (status_effect_type, status_effect_slot) in self.active.iter_mut() {
+            let Some(status_effect) = status_effect_slot.as_ref() else {
+                continue;
+            };
+
+            if matches!(status_effect_type, StatusEffectType::DecreaseLifeTime)
+                || max_value.map_or(false, |max_value| status_effect.value > max_value)
+            {
+                continue;
+            }
+
+            let Some(status_effect_data) =
+                status_effect_database.get_status_effect(status_effect.id)
+            else {
+                continue;
+            };
+
+            let is_cleared = match clear_type {
+                StatusEffectType::ClearGood => matches!(
+                    status_effect_data.cleared_by_type,
+                    StatusEffectClearedByType::ClearGood
+                ),
+                StatusEffectType::ClearBad => matches!(
+                    status_effect_data.cleared_by_type,
+                    StatusEffectClearedByType::ClearBad
+                ),
+                StatusEffectType::ClearAll => !matches!(
+                    status_effect_data.cleared_by_type,
+                    StatusEffectClearedByType::ClearNone
+                ),
+                StatusEffectType::ClearInvisible => matches!(
+                    status_effect_type,
+                    StatusEffectType::Disguise | StatusEffectType::Transparent
+                ),
+                _ => false,
+            };
+
+            if is_cleared {
+                *status_effect_slot = None;
+                self.expire_times[status_effect_type] = None;
+                status_effects_regen.regens[status_effect_type] = None;
+                cleared.push(status_effect_type);
+            }
+        }
+
+        cleared
+    }
+
     pub fn get_status_effect_value(&self, status_effect_type: StatusEffectType) -> Option<i32> {
         self.active[status_effect_type]
             .as_ref()
             .map(|status_effect| status_effect.value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use enum_map::Enum;
+
+    use rose_data::StatusEffectClearedByType;
+
+    use super::*;
+
+    fn status_effect_types() -> impl Iterator<Item = StatusEffectType> {
+        (0..StatusEffectType::LENGTH).map(StatusEffectType::from_usize)
+    }
+
+    fn is_stackable(status_effect_type: StatusEffectType) -> bool {
+        !matches!(
+            status_effect_type,
+            StatusEffectType::ClearGood
+                | StatusEffectType::ClearBad
+                | StatusEffectType::ClearAll
+                | StatusEffectType::ClearInvisible
+                | StatusEffectType::DecreaseLifeTime
+        )
+    }
+
+    fn status_effect_data(
+        id: u16,
+        status_effect_type: StatusEffectType,
+        can_be_reapplied: bool,
+    ) -> StatusEffectData {
+        StatusEffectData {
+            id: StatusEffectId::new(id).unwrap(),
+            name: "",
+            description: "",
+            start_message: "",
+            end_message: "",
+            status_effect_type,
+            can_be_reapplied,
+            cleared_by_type: StatusEffectClearedByType::ClearNone,
+            apply_status_effects: Default::default(),
+            apply_per_second_value: 0,
+            effect_file_id: None,
+            icon_id: 0,
+        }
+    }
+
+    fn apply(
+        status_effects: &mut StatusEffects,
+        status_effect_data: &StatusEffectData,
+        expire_time: Instant,
+        value: i32,
+    ) -> bool {
+        status_effects.can_apply(status_effect_data, value)
+            && status_effects.apply_status_effect(status_effect_data, expire_time, value)
+    }
+
+    #[test]
+    fn stronger_status_effect_overrides() {
+        let now = Instant::now();
+
+        for status_effect_type in status_effect_types().filter(|x| is_stackable(*x)) {
+            let weak = status_effect_data(1, status_effect_type, true);
+            let strong = status_effect_data(2, status_effect_type, true);
+            let mut status_effects = StatusEffects::new();
+
+            assert!(apply(&mut status_effects, &weak, now, 10));
+            assert!(
+                apply(
+                    &mut status_effects,
+                    &strong,
+                    now + Duration::from_secs(5),
+                    20
+                ),
+                "{:?}",
+                status_effect_type
+            );
+
+            let active = status_effects.active[status_effect_type].as_ref().unwrap();
+            assert_eq!(active.id, strong.id);
+            assert_eq!(active.value, 20);
+            assert_eq!(
+                status_effects.expire_times[status_effect_type],
+                Some(now + Duration::from_secs(5))
+            );
+        }
+    }
+
+    #[test]
+    fn weaker_status_effect_rejected() {
+        let now = Instant::now();
+
+        for status_effect_type in status_effect_types().filter(|x| is_stackable(*x)) {
+            let strong = status_effect_data(1, status_effect_type, true);
+            let weak = status_effect_data(2, status_effect_type, true);
+            let mut status_effects = StatusEffects::new();
+
+            assert!(apply(&mut status_effects, &strong, now, 20));
+            assert!(
+                !apply(&mut status_effects, &weak, now + Duration::from_secs(5), 10),
+                "{:?}",
+                status_effect_type
+            );
+            assert!(!apply(&mut status_effects, &strong, now, 10));
+
+            let active = status_effects.active[status_effect_type].as_ref().unwrap();
+            assert_eq!(active.id, strong.id);
+            assert_eq!(active.value, 20);
+            assert_eq!(status_effects.expire_times[status_effect_type], Some(now));
+        }
+    }
+
+    #[test]
+    fn same_status_effect_refreshes_duration() {
+        let now = Instant::now();
+
+        for status_effect_type in status_effect_types().filter(|x| is_stackable(*x)) {
+            for can_be_reapplied in [false, true] {
+                let data = status_effect_data(1, status_effect_type, can_be_reapplied);
+                let mut status_effects = StatusEffects::new();
+
+                assert!(apply(&mut status_effects, &data, now, 10));
+                assert!(
+                    apply(&mut status_effects, &data, now + Duration::from_secs(5), 10),
+                    "{:?}",
+                    status_effect_type
+                );
+                assert_eq!(
+                    status_effects.get_status_effect_value(status_effect_type),
+                    Some(10)
+                );
+                assert_eq!(
+                    status_effects.expire_times[status_effect_type],
+                    Some(now + Duration::from_secs(5))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn status_effect_not_reapplied_rejected() {
+        let now = Instant::now();
+
+        for status_effect_type in status_effect_types().filter(|x| is_stackable(*x)) {
+            let first = status_effect_data(1, status_effect_type, false);
+            let second = status_effect_data(2, status_effect_type, false);
+            let mut status_effects = StatusEffects::new();
+
+            assert!(apply(&mut status_effects, &first, now, 10));
+            assert!(
+                !apply(&mut status_effects, &second, now, 20),
+                "{:?}",
+                status_effect_type
+            );
+            assert!(!apply(&mut status_effects, &first, now, 20));
+            assert_eq!(
+                status_effects.active[status_effect_type]
+                    .as_ref()
+                    .map(|active| active.id),
+                Some(first.id)
+            );
+        }
+    }
+
+    #[test]
+    fn different_status_effect_types_stack() {
+        let now = Instant::now();
+        let mut status_effects = StatusEffects::new();
+
+        for (index, status_effect_type) in status_effect_types()
+            .filter(|x| is_stackable(*x))
+            .enumerate()
+        {
+            let data = status_effect_data(index as u16 + 1, status_effect_type, false);
+            assert!(apply(&mut status_effects, &data, now, 10));
+        }
+
+        for status_effect_type in status_effect_types() {
+            assert_eq!(
+                status_effects.active[status_effect_type].is_some(),
+                is_stackable(status_effect_type)
+            );
+        }
+    }
+
+    #[test]
+    fn non_stackable_status_effect_not_applied() {
+        let now = Instant::now();
+
+        for status_effect_type in status_effect_types().filter(|x| !is_stackable(*x)) {
+            let data = status_effect_data(1, status_effect_type, true);
+            let mut status_effects = StatusEffects::new();
+
+            assert!(!apply(&mut status_effects, &data, now, 10));
+            assert!(status_effects.active[status_effect_type].is_none());
+        }
+    }
+}
@@ -24,6 +24,72 @@ pub enum PassiveRecoveryState {
     Sitting,
 }
 
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LevelUpXpTable {
+    #[default]
+    Irose,
+    /// The experience table of the earlier naRose servers, which matches irose up to level 50
+    Narose,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AbilityValueCalculatorOptions {
+    /// Table of the experience required to level up, every other formula is the irose one
+    pub levelup_xp_table: LevelUpXpTable,
+    /// Characters stop gaining levels at this level, 0 for no limit
+    pub max_level: u32,
+    /// Basic stats can not be increased with stat points beyond this value
+    pub max_basic_stat: i32,
+    /// Limit for the attack speed calculated from equipment and passive skills, 0 for no limit
+    pub max_attack_speed: i32,
+    /// Limit for the run and vehicle speed calculated from equipment and passive skills, 0 for
+    /// no limit
+    pub max_move_speed: f32,
+    /// Stat point cost per point of the basic stat being increased
+    pub basic_stat_cost_rate: f32,
+    /// Multiplier applied to the experience required to level up
+    pub levelup_xp_rate: f32,
+    /// Skill points rewarded per level on level up
+    pub levelup_skill_points_rate: f32,
+    /// Stat points rewarded per level on level up, in addition to levelup_stat_points_base
+    pub levelup_stat_points_rate: f32,
+    pub levelup_stat_points_base: u32,
+}
+
+impl Default for AbilityValueCalculatorOptions {
+    fn default() -> Self {
+        Self {
+            levelup_xp_table: LevelUpXpTable::default(),
+            max_level: 0,
+            max_basic_stat: 300,
+            max_attack_speed: 0,
+            max_move_speed: 0.0,
+            basic_stat_cost_rate: 0.2,
+            levelup_xp_rate: 1.0,
+            levelup_skill_points_rate: 0.5,
+            levelup_stat_points_rate: 0.8,
+            levelup_stat_points_base: 10,
+        }
+    }
+}
+
+impl AbilityValueCalculatorOptions {
+    pub fn is_max_level(&self, level: u32) -> bool {
+        self.max_level != 0 && level >= self.max_level
+    }
+
+    /// Returns the level limited to max_level
+    pub fn clamp_level(&self, level: u32) -> u32 {
+        if self.max_level == 0 {
+            level
+        } else {
+            level.min(self.max_level)
+        }
+    }
+}
+
 pub trait AbilityValueCalculator {
     fn calculate(
         &self,
@@ -2,6 +2,9 @@ mod ability;
 mod drop_table;
 mod password;
 
-pub use ability::{AbilityValueCalculator, Damage, PassiveRecoveryState};
+pub use ability::{
+    AbilityValueCalculator, AbilityValueCalculatorOptions, Damage, LevelUpXpTable,
+    PassiveRecoveryState,
+};
 pub use drop_table::DropTable;
 pub use password::Password;
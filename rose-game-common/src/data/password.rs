@@ -4,13 +4,29 @@ use serde::{Deserialize, Serialize};
 pub enum Password {
     Plaintext(String),
     Md5(String),
+    /// The md5 hex digest of `seed` as 8 hex digits followed by the sha256 hex digest of the
+    /// password md5 hex digest, so the password itself is never sent.
+    Seeded {
+        seed: u32,
+        hash: String,
+    },
 }
 
 impl Password {
-    pub fn to_md5(&self) -> String {
+    /// Returns the md5 hex digest of the password, which is not known for a seeded password
+    pub fn to_md5(&self) -> Option<String> {
         match self {
-            Password::Plaintext(plaintext) => format!("{:x}", md5::compute(plaintext)),
-            Password::Md5(md5) => md5.clone(),
+            Password::Plaintext(plaintext) => Some(format!("{:x}", md5::compute(plaintext))),
+            Password::Md5(md5) => Some(md5.clone()),
+            Password::Seeded { .. } => None,
         }
     }
+
+    /// Returns the hash of a seeded password for the sha256 hex digest of the password md5
+    pub fn seeded_hash(seed: u32, password_md5_sha256: &str) -> String {
+        format!(
+            "{:x}",
+            md5::compute(format!("{:08x}{}", seed, password_md5_sha256))
+        )
+    }
 }
@@ -3,14 +3,15 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     components::{
-        BasicStatType, CharacterGender, CharacterUniqueId, ClanMark, HotbarSlot, ItemSlot, Level,
-        SkillSlot,
+        BasicStatType, CharacterGender, CharacterUniqueId, ClanMark, ClanPermissions, HotbarSlot,
+        InventoryPageType, ItemSlot, Level, Money, SkillSlot,
     },
     data::Password,
     messages::{ClientEntityId, PartyItemSharing, PartyRejectInviteReason, PartyXpSharing},
 };
 use rose_data::{
-    AmmoIndex, EquipmentIndex, Item, MotionId, QuestTriggerHash, VehiclePartIndex, WarpGateId,
+    AmmoIndex, CostumeIndex, EquipmentIndex, Item, MotionId, QuestTriggerHash, VehiclePartIndex,
+    WarpGateId,
 };
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -20,6 +21,13 @@ pub struct NpcStoreBuyItem {
     pub quantity: usize,
 }
 
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum InventorySortType {
+    ItemType,
+    /// Most valuable items first
+    Value,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum ClientMessage {
     ConnectionRequest {
@@ -65,7 +73,10 @@ pub enum ClientMessage {
         login_token: u32,
         password: Password,
     },
-    JoinZoneRequest,
+    JoinZoneRequest {
+        /// Checksum of the client zone data, only sent by patched clients
+        zone_checksum: Option<u32>,
+    },
     Chat {
         text: String,
     },
@@ -97,12 +108,18 @@ pub enum ClientMessage {
         vehicle_part_index: VehiclePartIndex,
         item_slot: Option<ItemSlot>,
     },
+    ChangeCostume {
+        costume_index: CostumeIndex,
+        item_slot: Option<ItemSlot>,
+    },
     IncreaseBasicStat {
         basic_stat_type: BasicStatType,
     },
     PickupItemDrop {
         target_entity_id: ClientEntityId,
     },
+    /// Pickup the nearest item drop which the character is allowed to pickup
+    PickupNearestItemDrop,
     Logout,
     ReturnToCharacterSelect,
     ReviveCurrentZone,
@@ -148,6 +165,10 @@ pub enum ClientMessage {
         skill_slot: SkillSlot,
         position: Vec2,
     },
+    ToggleSkill {
+        skill_slot: SkillSlot,
+        enabled: bool,
+    },
     NpcStoreTransaction {
         npc_entity_id: ClientEntityId,
         buy_items: Vec<NpcStoreBuyItem>,
@@ -176,6 +197,12 @@ pub enum ClientMessage {
     PartyKick {
         character_id: CharacterUniqueId,
     },
+    PartyVoteKick {
+        character_id: CharacterUniqueId,
+    },
+    PartyVoteKickReply {
+        accept: bool,
+    },
     PartyAcceptCreateInvite {
         owner_entity_id: ClientEntityId,
     },
@@ -223,6 +250,13 @@ pub enum ClientMessage {
         item: Item,
         is_premium: bool,
     },
+    BankDepositInventoryPage {
+        page_type: InventoryPageType,
+    },
+    SortInventory {
+        page_type: InventoryPageType,
+        sort_type: InventorySortType,
+    },
     RepairItemUsingItem {
         use_item_slot: ItemSlot,
         item_slot: ItemSlot,
@@ -241,4 +275,72 @@ pub enum ClientMessage {
         level: Level,
         job: u16,
     },
+    ClanInvite {
+        name: String,
+    },
+    ClanInviteAccept {
+        inviter_name: String,
+    },
+    ClanInviteReject {
+        inviter_name: String,
+    },
+    ClanKick {
+        name: String,
+    },
+    ClanPromote {
+        name: String,
+    },
+    ClanDemote {
+        name: String,
+    },
+    ClanSetNotice {
+        notice: String,
+    },
+    ClanQuit,
+    ClanVaultOpen,
+    ClanVaultDeposit {
+        item_slot: ItemSlot,
+        item: Item,
+    },
+    ClanVaultWithdraw {
+        vault_slot: usize,
+        item: Item,
+    },
+    ClanVaultGetLog,
+    ClanDonateMoney {
+        money: Money,
+    },
+    ClanGetPermissions,
+    ClanSetPermissions {
+        permissions: ClanPermissions,
+    },
+    ClanSetRecruitment {
+        /// An empty notice removes the clan from the recruitment board
+        notice: String,
+    },
+    ClanSearchRecruitment {
+        query: String,
+    },
+    ClanApply {
+        clan_name: String,
+        message: String,
+    },
+    ClanGetApplications,
+    ClanApplicationReply {
+        name: String,
+        accepted: bool,
+    },
+    InspectCharacter {
+        target_entity_id: ClientEntityId,
+    },
+    SetAllowInspect {
+        allow_inspect: bool,
+    },
+    SetActiveTitle {
+        title_id: Option<u16>,
+    },
+    RequestCharacterStatistics,
+    SetAutoLoot {
+        auto_loot: bool,
+    },
 }
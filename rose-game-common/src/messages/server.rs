@@ -5,18 +5,18 @@ use std::{num::NonZeroUsize, time::Duration};
 use thiserror::Error;
 
 use rose_data::{
-    AbilityType, AmmoIndex, ClanMemberPosition, EquipmentIndex, EquipmentItem, Item, ItemReference,
-    MotionId, NpcId, QuestTriggerHash, SkillId, StackableItem, StatusEffectType, VehiclePartIndex,
-    WorldTicks, ZoneId,
+    AbilityType, AmmoIndex, ClanMemberPosition, CostumeIndex, EquipmentIndex, EquipmentItem, Item,
+    ItemReference, MotionId, NpcId, QuestTriggerHash, SkillId, StackableItem, StatusEffectType,
+    VehiclePartIndex, WorldTicks, ZoneId,
 };
 
 use crate::{
     components::{
         ActiveStatusEffect, BasicStatType, BasicStats, CharacterDeleteTime, CharacterInfo,
-        CharacterUniqueId, ClanLevel, ClanMark, ClanPoints, ClanUniqueId, DroppedItem, Equipment,
-        ExperiencePoints, HealthPoints, Hotbar, HotbarSlot, Inventory, ItemSlot, Level, ManaPoints,
-        Money, MoveMode, MoveSpeed, Npc, QuestState, SkillList, SkillPoints, SkillSlot, Stamina,
-        StatPoints, Team, UnionMembership,
+        CharacterStatistics, CharacterUniqueId, ClanLevel, ClanMark, ClanPermissions, ClanPoints,
+        ClanUniqueId, DroppedItem, Equipment, ExperiencePoints, HealthPoints, Hotbar, HotbarSlot,
+        Inventory, ItemSlot, Level, ManaPoints, Money, MoveMode, MoveSpeed, Npc, QuestState,
+        SkillList, SkillPoints, SkillSlot, Stamina, StatPoints, Team, UnionMembership,
     },
     data::Damage,
     messages::{ClientEntityId, PartyItemSharing, PartyRejectInviteReason, PartyXpSharing},
@@ -30,6 +30,8 @@ pub enum ConnectionRequestError {
     InvalidToken,
     #[error("Invalid password")]
     InvalidPassword,
+    #[error("Account already has a character in game")]
+    AlreadyLoggedIn,
 }
 
 #[derive(Copy, Clone, Debug, Error, Serialize, Deserialize)]
@@ -42,6 +44,15 @@ pub enum LoginError {
     InvalidPassword,
     #[error("Already logged in")]
     AlreadyLoggedIn,
+    #[error("Too many failed login attempts")]
+    TooManyAttempts,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChannelListItem {
+    pub id: u8,
+    pub name: String,
+    pub percent_full: u16,
 }
 
 #[derive(Copy, Clone, Debug, Error, Serialize, Deserialize)]
@@ -167,6 +178,13 @@ pub struct SpawnEntityCharacter {
     pub team: Team,
     pub personal_store_info: Option<(i32, String)>,
     pub clan_membership: Option<CharacterClanMembership>,
+    pub title: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CharacterTitle {
+    pub id: u16,
+    pub name: String,
 }
 
 #[allow(dead_code)]
@@ -292,6 +310,76 @@ pub enum ClanCreateError {
     UnmetCondition,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum ClanInviteError {
+    Failed,
+    NoPermission,
+    AlreadyInClan,
+    ClanFull,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum ClanVaultError {
+    Failed,
+    NoPermission,
+    VaultFull,
+    InventoryFull,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum ClanApplicationError {
+    Failed,
+    NoPermission,
+    NotRecruiting,
+    AlreadyInClan,
+    AlreadyApplied,
+    ClanFull,
+    /// Applications can only be accepted while the applicant is online
+    ApplicantOffline,
+    /// The recruitment notice or application message is too long
+    TooLong,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum InspectCharacterError {
+    NotFound,
+    Private,
+    TooFrequent,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum ClanVaultAction {
+    Deposit,
+    Withdraw,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ClanVaultLogEntry {
+    pub name: String,
+    pub action: ClanVaultAction,
+    pub item: Item,
+    /// Seconds since the unix epoch
+    pub timestamp: u64,
+}
+
+/// A clan which has published a recruitment notice.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ClanRecruitmentListing {
+    pub name: String,
+    pub level: ClanLevel,
+    pub member_count: usize,
+    pub notice: String,
+}
+
+/// A character asking to join a clan.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ClanApplication {
+    pub name: String,
+    pub level: Level,
+    pub job: u16,
+    pub message: String,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ClanMemberInfo {
     pub name: String,
@@ -318,7 +406,7 @@ pub enum ServerMessage {
     },
     ChannelList {
         server_id: usize,
-        channels: Vec<(u8, String)>,
+        channels: Vec<ChannelListItem>,
     },
     ChannelListError {
         error: ChannelListError,
@@ -381,6 +469,8 @@ pub enum ServerMessage {
         world_price_rate: i32,
         item_price_rate: i32,
         town_price_rate: i32,
+        /// Checksum of the server zone data, only sent to clients which sent their own
+        zone_checksum: Option<u32>,
     },
     AttackEntity {
         entity_id: ClientEntityId,
@@ -468,6 +558,7 @@ pub enum ServerMessage {
         spawn_command_state: SpawnCommandState,
         move_mode: MoveMode,
         status_effects: ActiveStatusEffects,
+        event_status: i16,
     },
     StopMoveEntity {
         entity_id: ClientEntityId,
@@ -510,6 +601,11 @@ pub enum ServerMessage {
         vehicle_part_index: VehiclePartIndex,
         item: Option<EquipmentItem>,
     },
+    UpdateCostume {
+        entity_id: ClientEntityId,
+        costume_index: CostumeIndex,
+        item: Option<EquipmentItem>,
+    },
     UpdateInventory {
         items: Vec<(ItemSlot, Option<Item>)>,
         money: Option<Money>,
@@ -558,6 +654,8 @@ pub enum ServerMessage {
         wait_duration: Duration,
     },
     ReturnToCharacterSelect,
+    /// Closes the client connection without a reply
+    Disconnect,
     QuestTriggerResult {
         trigger_hash: QuestTriggerHash,
         success: bool,
@@ -654,6 +752,11 @@ pub enum ServerMessage {
         entity_id: ClientEntityId,
         reason: CancelCastingSkillReason,
     },
+    ToggleSkill {
+        entity_id: ClientEntityId,
+        skill_id: SkillId,
+        enabled: bool,
+    },
     NpcStoreTransactionError {
         error: NpcStoreTransactionError,
     },
@@ -710,6 +813,12 @@ pub enum ServerMessage {
     PartyMemberKicked {
         character_id: CharacterUniqueId,
     },
+    PartyVoteKickStarted {
+        character_id: CharacterUniqueId,
+    },
+    PartyVoteKickFailed {
+        character_id: CharacterUniqueId,
+    },
     PartyMemberUpdateInfo {
         member_info: PartyMemberInfoOnline,
     },
@@ -798,4 +907,87 @@ pub enum ServerMessage {
     ClanMemberList {
         members: Vec<ClanMemberInfo>,
     },
+    ClanInvite {
+        inviter_name: String,
+    },
+    ClanInviteRejected {
+        name: String,
+    },
+    ClanInviteError {
+        error: ClanInviteError,
+    },
+    ClanMemberJoined {
+        member: ClanMemberInfo,
+    },
+    ClanMemberKicked {
+        name: String,
+    },
+    ClanMemberQuit {
+        name: String,
+    },
+    ClanMemberPositionChanged {
+        name: String,
+        position: ClanMemberPosition,
+    },
+    ClanNotice {
+        notice: String,
+    },
+    CharacterLeaveClan {
+        client_entity_id: ClientEntityId,
+    },
+    ClanVaultItems {
+        items: Vec<(usize, Option<Item>)>,
+    },
+    ClanVaultLog {
+        entries: Vec<ClanVaultLogEntry>,
+    },
+    ClanVaultError {
+        error: ClanVaultError,
+    },
+    ClanPermissions {
+        permissions: ClanPermissions,
+    },
+    ClanRecruitmentList {
+        listings: Vec<ClanRecruitmentListing>,
+    },
+    ClanApplications {
+        applications: Vec<ClanApplication>,
+    },
+    ClanApplicationReceived {
+        application: ClanApplication,
+    },
+    ClanApplicationSent {
+        clan_name: String,
+    },
+    ClanApplicationRejected {
+        clan_name: String,
+    },
+    ClanApplicationError {
+        error: ClanApplicationError,
+    },
+    InspectCharacter {
+        entity_id: ClientEntityId,
+        name: String,
+        level: Level,
+        job: u16,
+        equipment: Equipment,
+    },
+    InspectCharacterError {
+        error: InspectCharacterError,
+    },
+    CharacterTitles {
+        titles: Vec<CharacterTitle>,
+        active: Option<u16>,
+    },
+    UpdateCharacterTitle {
+        entity_id: ClientEntityId,
+        title: Option<String>,
+    },
+    CharacterStatistics {
+        statistics: CharacterStatistics,
+    },
+    SetEventStatus {
+        entity_id: ClientEntityId,
+        event_status: i16,
+    },
 }
@@ -14,29 +14,77 @@ use rose_game_common::{
         AbilityValues, BasicStatType, BasicStats, CharacterInfo, DamageCategory, DamageType,
         Equipment, EquipmentItemDatabase, ItemSlot, Level, Money, SkillList, StatusEffects,
     },
-    data::{AbilityValueCalculator, Damage, PassiveRecoveryState},
+    data::{
+        AbilityValueCalculator, AbilityValueCalculatorOptions, Damage, LevelUpXpTable,
+        PassiveRecoveryState,
+    },
 };
 
-const MAX_BASIC_STAT_VALUE: i32 = 300;
-
 pub struct AbilityValuesData {
     item_database: Arc<ItemDatabase>,
     skill_database: Arc<SkillDatabase>,
     npc_database: Arc<NpcDatabase>,
+    options: AbilityValueCalculatorOptions,
 }
 
 pub fn get_ability_value_calculator(
     item_database: Arc<ItemDatabase>,
     skill_database: Arc<SkillDatabase>,
     npc_database: Arc<NpcDatabase>,
+    options: AbilityValueCalculatorOptions,
 ) -> Box<impl AbilityValueCalculator + Send + Sync> {
     Box::new(AbilityValuesData {
         item_database,
         skill_database,
         npc_database,
+        options,
     })
 }
 
+fn irose_levelup_require_xp(level: u32) -> u64 {
+    match level as u64 {
+        0..=15 => (((level + 3) * (level + 5) * (level + 10)) as f64 * 0.7) as u64,
+        16..=60 => (((level - 5) * (level + 2) * (level + 2)) as f64 * 2.2) as u64,
+        61..=113 => (((level - 11) * (level) * (level + 4)) as f64 * 2.5) as u64,
+        114..=150 => (((level - 31) * (level - 20) * (level + 4)) as f64 * 3.8) as u64,
+        151..=189 => (((level - 67) * (level - 20) * (level - 10)) as f64 * 6.0) as u64,
+        190..=u64::MAX => {
+            ((level - 90) * (level - 120) * (level - 60) * (level - 170) * (level - 188)) as u64
+        }
+    }
+}
+
+// From CCal::Get_NeedRawEXP in Common/CCal.cpp of the naRose server, which matches irose up to
+// level 50, including the drop in required experience from level 100 to 101
+fn narose_levelup_require_xp(level: u32) -> u64 {
+    let level = level as u64;
+    match level {
+        0..=15 => (((level + 3) * (level + 5) * (level + 10)) as f64 * 0.7) as u64,
+        16..=50 => (((level - 5) * (level + 2) * (level + 2)) as f64 * 2.2) as u64,
+        51..=100 => (level - 38) * (level - 5) * (level + 2) * 9,
+        101..=139 => (level + 220) * (level + 34) * (level + 22),
+        140..=u64::MAX => (level - 126) * (level + 57) * (level - 6) * 31,
+    }
+}
+
+impl AbilityValuesData {
+    fn limit_attack_speed(&self, attack_speed: i32) -> i32 {
+        if self.options.max_attack_speed > 0 {
+            attack_speed.min(self.options.max_attack_speed)
+        } else {
+            attack_speed
+        }
+    }
+
+    fn limit_move_speed(&self, move_speed: f32) -> f32 {
+        if self.options.max_move_speed > 0.0 {
+            move_speed.min(self.options.max_move_speed)
+        } else {
+            move_speed
+        }
+    }
+}
+
 impl AbilityValueCalculator for AbilityValuesData {
     fn calculate_npc(
         &self,
@@ -108,6 +156,7 @@ impl AbilityValueCalculator for AbilityValuesData {
             max_damage_sources: ((npc_data.health_points / 8) + 4) as usize,
             drop_rate: 0,
             max_weight: 0,
+            max_summon_points: 0,
             summon_owner_level: owner_level,
             summon_skill_level,
             adjust: status_effects.into(),
@@ -184,18 +233,18 @@ impl AbilityValueCalculator for AbilityValuesData {
             is_driving: false,
             damage_category: DamageCategory::Character,
             walk_speed: 200.0,
-            run_speed: calculate_run_speed(
+            run_speed: self.limit_move_speed(calculate_run_speed(
                 &self.item_database,
                 &basic_stats,
                 &equipment_ability_values,
                 equipment,
                 &passive_ability_values,
-            ),
-            vehicle_move_speed: calculate_vehicle_move_speed(
+            )),
+            vehicle_move_speed: self.limit_move_speed(calculate_vehicle_move_speed(
                 &self.item_database,
                 &vehicle_ability_values,
                 equipment,
-            ),
+            )),
             max_health: calculate_max_health(
                 character_info,
                 level,
@@ -244,7 +293,7 @@ impl AbilityValueCalculator for AbilityValuesData {
                 equipment,
                 &passive_ability_values,
             ) + job_add_attack,
-            attack_speed,
+            attack_speed: self.limit_attack_speed(attack_speed),
             passive_attack_speed,
             attack_range: calculate_attack_range(&self.item_database, equipment),
             hit: calculate_hit(
@@ -292,11 +341,11 @@ impl AbilityValueCalculator for AbilityValuesData {
                 equipment,
             ) + job_add_attack,
             vehicle_attack_range: calculate_vehicle_attack_range(&self.item_database, equipment),
-            vehicle_attack_speed: calculate_vehicle_attack_speed(
+            vehicle_attack_speed: self.limit_attack_speed(calculate_vehicle_attack_speed(
                 &self.item_database,
                 equipment,
                 &vehicle_ability_values,
-            ),
+            )),
             vehicle_hit: calculate_vehicle_hit(
                 &self.item_database,
                 &vehicle_basic_stats,
@@ -337,6 +386,7 @@ impl AbilityValueCalculator for AbilityValuesData {
                 &equipment_ability_values,
                 &passive_ability_values,
             ),
+            max_summon_points: calculate_max_summon_points(&passive_ability_values),
             summon_owner_level: None,
             summon_skill_level: None,
             adjust: status_effects.into(),
@@ -699,32 +749,29 @@ impl AbilityValueCalculator for AbilityValuesData {
             BasicStatType::Sense => basic_stats.sense,
         };
 
-        if current > MAX_BASIC_STAT_VALUE {
+        if current >= self.options.max_basic_stat {
             None
         } else {
-            Some((current as f32 * 0.2) as u32)
+            Some((current as f32 * self.options.basic_stat_cost_rate) as u32)
         }
     }
 
     fn calculate_levelup_require_xp(&self, level: u32) -> u64 {
-        match level as u64 {
-            0..=15 => (((level + 3) * (level + 5) * (level + 10)) as f64 * 0.7) as u64,
-            16..=60 => (((level - 5) * (level + 2) * (level + 2)) as f64 * 2.2) as u64,
-            61..=113 => (((level - 11) * (level) * (level + 4)) as f64 * 2.5) as u64,
-            114..=150 => (((level - 31) * (level - 20) * (level + 4)) as f64 * 3.8) as u64,
-            151..=189 => (((level - 67) * (level - 20) * (level - 10)) as f64 * 6.0) as u64,
-            190..=u64::MAX => {
-                ((level - 90) * (level - 120) * (level - 60) * (level - 170) * (level - 188)) as u64
-            }
-        }
+        let require_xp = match self.options.levelup_xp_table {
+            LevelUpXpTable::Irose => irose_levelup_require_xp(level),
+            LevelUpXpTable::Narose => narose_levelup_require_xp(level),
+        };
+
+        (require_xp as f64 * self.options.levelup_xp_rate as f64) as u64
     }
 
     fn calculate_levelup_reward_skill_points(&self, level: u32) -> u32 {
-        (level + 2) / 2
+        ((level + 2) as f32 * self.options.levelup_skill_points_rate) as u32
     }
 
     fn calculate_levelup_reward_stat_points(&self, level: u32) -> u32 {
-        (level as f32 * 0.8) as u32 + 10
+        (level as f32 * self.options.levelup_stat_points_rate) as u32
+            + self.options.levelup_stat_points_base
     }
 
     fn calculate_reward_value(
@@ -2294,6 +2341,10 @@ fn calculate_save_mana(
     (save_mana + passive_save_mana) as i32
 }
 
+fn calculate_max_summon_points(passive_ability_values: &PassiveSkillAbilityValues) -> i32 {
+    50 + passive_ability_values.value.max_summons
+}
+
 fn calculate_max_weight(
     item_database: &ItemDatabase,
     level: &Level,
@@ -0,0 +1,185 @@
+use std::{collections::HashMap, sync::Arc};
+
+use enum_map::enum_map;
+
+use rose_data::{ItemDatabase, MotionId, NpcDatabase, SkillDatabase, StringDatabase};
+use rose_data_irose::{
+    encode_ability_type, encode_clan_member_position, encode_item_class,
+    encode_skill_target_filter, encode_skill_type,
+};
+use rose_game_common::{
+    components::{BasicStatType, BasicStats},
+    data::{AbilityValueCalculator, AbilityValueCalculatorOptions, LevelUpXpTable},
+};
+use rose_game_irose::data::get_ability_value_calculator;
+
+fn get_calculator(
+    options: AbilityValueCalculatorOptions,
+) -> Box<dyn AbilityValueCalculator + Send + Sync> {
+    let string_database = Arc::new(StringDatabase {
+        language: 1,
+        encode_ability_type,
+        encode_clan_member_position,
+        encode_item_class,
+        encode_skill_target_filter,
+        encode_skill_type,
+        ability: Default::default(),
+        clan: Default::default(),
+        client_strings: Default::default(),
+        conversation: Default::default(),
+        item: Default::default(),
+        item_prefix: Default::default(),
+        item_class: Default::default(),
+        job: Default::default(),
+        job_class: Default::default(),
+        npc: Default::default(),
+        npc_store_tabs: Default::default(),
+        planet: Default::default(),
+        quest: Default::default(),
+        skill: Default::default(),
+        skill_target: Default::default(),
+        skill_type: Default::default(),
+        status_effect: Default::default(),
+        union: Default::default(),
+        zone: Default::default(),
+    });
+
+    get_ability_value_calculator(
+        Arc::new(ItemDatabase::new(
+            string_database.clone(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+        )),
+        Arc::new(SkillDatabase::new(string_database.clone(), Vec::new())),
+        Arc::new(NpcDatabase::new(
+            string_database,
+            Vec::new(),
+            HashMap::new(),
+            HashMap::new(),
+            enum_map! { _ => MotionId::new(0) },
+        )),
+        options,
+    )
+}
+
+fn get_calculator_with_xp_table(
+    levelup_xp_table: LevelUpXpTable,
+) -> Box<dyn AbilityValueCalculator + Send + Sync> {
+    get_calculator(AbilityValueCalculatorOptions {
+        levelup_xp_table,
+        ..Default::default()
+    })
+}
+
+fn basic_stats(value: i32) -> BasicStats {
+    BasicStats {
+        strength: value,
+        dexterity: value,
+        intelligence: value,
+        concentration: value,
+        charm: value,
+        sense: value,
+    }
+}
+
+#[test]
+fn irose_reference_values() {
+    let calculator = get_calculator(Default::default());
+
+    assert_eq!(
+        calculator.calculate_basic_stat_increase_cost(&basic_stats(50), BasicStatType::Strength),
+        Some(10)
+    );
+    assert_eq!(
+        calculator.calculate_basic_stat_increase_cost(&basic_stats(300), BasicStatType::Sense),
+        None
+    );
+    assert_eq!(calculator.calculate_levelup_reward_skill_points(10), 6);
+    assert_eq!(calculator.calculate_levelup_reward_stat_points(10), 18);
+    assert_eq!(calculator.calculate_give_stamina(100, 10, 100), 15);
+
+    assert_eq!(calculator.calculate_levelup_require_xp(1), 184);
+    assert_eq!(calculator.calculate_levelup_require_xp(20), 15972);
+    assert_eq!(calculator.calculate_levelup_require_xp(60), 465124);
+    assert_eq!(calculator.calculate_levelup_require_xp(100), 2314000);
+    assert_eq!(calculator.calculate_levelup_require_xp(200), 443520000);
+}
+
+#[test]
+fn narose_xp_table_range_boundaries() {
+    let irose = get_calculator_with_xp_table(LevelUpXpTable::Irose);
+    let narose = get_calculator_with_xp_table(LevelUpXpTable::Narose);
+
+    // Worked by hand from each range of CCal::Get_NeedRawEXP of the naRose server
+    for (level, require_xp) in [
+        (15, 6300),
+        (16, 7840),
+        (50, 267696),
+        (51, 285246),
+        (60, 675180),
+        (100, 5407020),
+        (101, 5330205),
+        (120, 7435120),
+        (139, 9999227),
+        (140, 11456732),
+        (150, 22177152),
+        (200, 114374252),
+    ] {
+        assert_eq!(
+            narose.calculate_levelup_require_xp(level),
+            require_xp,
+            "level {}",
+            level
+        );
+    }
+
+    // The two tables only diverge after level 50
+    for level in 1..=50 {
+        assert_eq!(
+            narose.calculate_levelup_require_xp(level),
+            irose.calculate_levelup_require_xp(level)
+        );
+    }
+    assert_ne!(
+        narose.calculate_levelup_require_xp(51),
+        irose.calculate_levelup_require_xp(51)
+    );
+}
+
+#[test]
+fn options_override_formula_coefficients() {
+    for levelup_xp_table in [LevelUpXpTable::Irose, LevelUpXpTable::Narose] {
+        let default_calculator = get_calculator_with_xp_table(levelup_xp_table);
+        let calculator = get_calculator(AbilityValueCalculatorOptions {
+            levelup_xp_table,
+            max_basic_stat: 100,
+            levelup_xp_rate: 2.0,
+            levelup_stat_points_base: 0,
+            ..Default::default()
+        });
+
+        assert_eq!(
+            calculator
+                .calculate_basic_stat_increase_cost(&basic_stats(100), BasicStatType::Dexterity),
+            None
+        );
+        assert_eq!(calculator.calculate_levelup_reward_stat_points(10), 8);
+        assert_eq!(
+            calculator.calculate_levelup_require_xp(60),
+            default_calculator.calculate_levelup_require_xp(60) * 2
+        );
+    }
+}
@@ -1,9 +1,11 @@
 use bytes::{Buf, BufMut, Bytes, BytesMut};
 use log::trace;
+use std::time::Duration;
 use thiserror::Error;
 use tokio::{
     io::{AsyncReadExt, AsyncWriteExt, BufWriter},
     net::TcpStream,
+    time::Instant,
 };
 
 use crate::{Packet, PacketCodec};
@@ -18,12 +20,17 @@ pub enum ConnectionError {
 
     #[error("failed to decrypt packet body")]
     DecryptBodyFailed,
+
+    #[error("no packets received within idle timeout")]
+    IdleTimeout,
 }
 
 pub struct Connection<'a> {
     stream: BufWriter<TcpStream>,
     buffer: BytesMut,
     packet_codec: &'a (dyn PacketCodec + Send + Sync),
+    idle_timeout: Option<Duration>,
+    last_received: Instant,
 }
 
 impl<'a> Connection<'a> {
@@ -32,9 +39,17 @@ impl<'a> Connection<'a> {
             stream: BufWriter::new(socket),
             buffer: BytesMut::with_capacity(4 * 1024),
             packet_codec,
+            idle_timeout: None,
+            last_received: Instant::now(),
         }
     }
 
+    /// Fail read_packet when no packet has been received from the peer within this time, so a
+    /// half-open connection does not stay open forever.
+    pub fn set_idle_timeout(&mut self, idle_timeout: Option<Duration>) {
+        self.idle_timeout = idle_timeout;
+    }
+
     pub async fn shutdown(&mut self) {
         let _ = self.stream.shutdown().await;
     }
@@ -45,7 +60,20 @@ impl<'a> Connection<'a> {
 
         loop {
             while self.buffer.len() < read_length {
-                match self.stream.read_buf(&mut self.buffer).await {
+                // The deadline is measured from the last packet rather than the start of this
+                // read, as read_packet is cancelled whenever the server sends a packet first
+                let read_result = if let Some(idle_timeout) = self.idle_timeout {
+                    tokio::time::timeout_at(
+                        self.last_received + idle_timeout,
+                        self.stream.read_buf(&mut self.buffer),
+                    )
+                    .await
+                    .map_err(|_| ConnectionError::IdleTimeout)?
+                } else {
+                    self.stream.read_buf(&mut self.buffer).await
+                };
+
+                match read_result {
                     Ok(_) => {
                         if self.buffer.is_empty() {
                             return Err(ConnectionError::ConnectionLost.into());
@@ -79,6 +107,7 @@ impl<'a> Connection<'a> {
                 self.buffer.advance(read_length - size);
 
                 trace!(target: "packets", "RECV [{:03X}] {:02x?}", command, &data[..]);
+                self.last_received = Instant::now();
                 return Ok(Packet { command, data });
             } else {
                 return Err(ConnectionError::DecryptBodyFailed.into());
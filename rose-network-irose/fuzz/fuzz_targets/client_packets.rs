@@ -0,0 +1,89 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rose_network_common::Packet;
+use rose_network_irose::{game_client_packets::*, login_client_packets, world_client_packets};
+
+macro_rules! fuzz_packets {
+    ($packet:expr, [$($ty:path),* $(,)?]) => {
+        $(
+            if let Ok(decoded) = <$ty>::try_from($packet) {
+                let _ = Packet::from(&decoded);
+            }
+        )*
+    };
+}
+
+fuzz_target!(|data: &[u8]| {
+    if data.len() < 2 {
+        return;
+    }
+
+    let packet = Packet {
+        command: u16::from_le_bytes([data[0], data[1]]),
+        data: data[2..].to_vec().into(),
+    };
+
+    fuzz_packets!(
+        &packet,
+        [
+            PacketClientAttack,
+            PacketClientBankMoveItem,
+            PacketClientBankOpen,
+            PacketClientCastSkillSelf,
+            PacketClientCastSkillTargetEntity,
+            PacketClientCastSkillTargetPosition,
+            PacketClientChangeAmmo,
+            PacketClientChangeCostume,
+            PacketClientChangeEquipment,
+            PacketClientChangeVehiclePart,
+            PacketClientChat,
+            PacketClientClanCommand,
+            PacketClientClanPermissions,
+            PacketClientClanRecruitment,
+            PacketClientClanVault,
+            PacketClientConnectRequest,
+            PacketClientCraftItem,
+            PacketClientDropItemFromInventory,
+            PacketClientEmote,
+            PacketClientIncreaseBasicStat,
+            PacketClientInspectCharacter,
+            PacketClientInventoryOperation,
+            PacketClientJoinZone,
+            PacketClientLevelUpSkill,
+            PacketClientMove,
+            PacketClientMoveCollision,
+            PacketClientMoveToggle,
+            PacketClientNpcStoreTransaction,
+            PacketClientPartyReply,
+            PacketClientPartyRequest,
+            PacketClientPartyUpdateRules,
+            PacketClientPersonalStoreBuyItem,
+            PacketClientPersonalStoreListItems,
+            PacketClientPickupItemDrop,
+            PacketClientPickupNearestItemDrop,
+            PacketClientQuestRequest,
+            PacketClientRepairItemUsingItem,
+            PacketClientRepairItemUsingNpc,
+            PacketClientRequestCharacterStatistics,
+            PacketClientReviveRequest,
+            PacketClientSetActiveTitle,
+            PacketClientSetAutoLoot,
+            PacketClientSetHotbarSlot,
+            PacketClientSetReviveZone,
+            PacketClientToggleSkill,
+            PacketClientUseItem,
+            PacketClientWarpGateRequest,
+            login_client_packets::PacketClientChannelList,
+            login_client_packets::PacketClientConnect,
+            login_client_packets::PacketClientLoginRequest,
+            login_client_packets::PacketClientSelectServer,
+            world_client_packets::PacketClientCharacterList,
+            world_client_packets::PacketClientClanCommand,
+            world_client_packets::PacketClientConnectRequest,
+            world_client_packets::PacketClientCreateCharacter,
+            world_client_packets::PacketClientDeleteCharacter,
+            world_client_packets::PacketClientSelectCharacter,
+        ]
+    );
+});
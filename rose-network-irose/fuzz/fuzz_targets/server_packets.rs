@@ -0,0 +1,120 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rose_network_common::Packet;
+use rose_network_irose::{game_server_packets::*, login_server_packets, world_server_packets};
+
+macro_rules! fuzz_packets {
+    ($packet:expr, [$($ty:path),* $(,)?]) => {
+        $(
+            if let Ok(decoded) = <$ty>::try_from($packet) {
+                let _ = Packet::from(&decoded);
+            }
+        )*
+    };
+}
+
+fuzz_target!(|data: &[u8]| {
+    if data.len() < 2 {
+        return;
+    }
+
+    let packet = Packet {
+        command: u16::from_le_bytes([data[0], data[1]]),
+        data: data[2..].to_vec().into(),
+    };
+
+    fuzz_packets!(
+        &packet,
+        [
+            PacketConnectionReply,
+            PacketServerAdjustPosition,
+            PacketServerAnnounceChat,
+            PacketServerApplySkillDamage,
+            PacketServerApplySkillEffect,
+            PacketServerAttackEntity,
+            PacketServerBankOpen,
+            PacketServerBankTransaction,
+            PacketServerCancelCastingSkill,
+            PacketServerCastSkillSelf,
+            PacketServerCastSkillTargetEntity,
+            PacketServerCastSkillTargetPosition,
+            PacketServerChangeNpcId,
+            PacketServerCharacterInventory,
+            PacketServerCharacterQuestData,
+            PacketServerCharacterStatistics,
+            PacketServerCharacterTitle,
+            PacketServerClanCommand,
+            PacketServerClanPermissions,
+            PacketServerClanRecruitment,
+            PacketServerClanVault,
+            PacketServerClosePersonalStore,
+            PacketServerCraftItem,
+            PacketServerDamageEntity,
+            PacketServerFinishCastingSkill,
+            PacketServerInspectCharacter,
+            PacketServerJoinZone,
+            PacketServerLearnSkillResult,
+            PacketServerLevelUpSkillResult,
+            PacketServerLocalChat,
+            PacketServerLogoutResult,
+            PacketServerMoveEntity,
+            PacketServerMoveToggle,
+            PacketServerNpcStoreTransactionError,
+            PacketServerOpenPersonalStore,
+            PacketServerPartyMemberRewardItem,
+            PacketServerPartyMemberUpdateInfo,
+            PacketServerPartyMembers,
+            PacketServerPartyReply,
+            PacketServerPartyRequest,
+            PacketServerPartyUpdateRules,
+            PacketServerPersonalStoreItemList,
+            PacketServerPersonalStoreTransactionResult,
+            PacketServerPersonalStoreTransactionUpdateMoneyAndInventory,
+            PacketServerPickupItemDropResult,
+            PacketServerQuestResult,
+            PacketServerRemoveEntities,
+            PacketServerRepairedItemUsingNpc,
+            PacketServerRewardItems,
+            PacketServerRewardMoney,
+            PacketServerRunNpcDeathTrigger,
+            PacketServerSelectCharacter,
+            PacketServerSetEventStatus,
+            PacketServerSetHotbarSlot,
+            PacketServerShoutChat,
+            PacketServerSpawnEntityCharacter,
+            PacketServerSpawnEntityItemDrop,
+            PacketServerSpawnEntityMonster,
+            PacketServerSpawnEntityNpc,
+            PacketServerStartCastingSkill,
+            PacketServerStopMoveEntity,
+            PacketServerTeleport,
+            PacketServerToggleSkill,
+            PacketServerUpdateAbilityValue,
+            PacketServerUpdateAmmo,
+            PacketServerUpdateBasicStat,
+            PacketServerUpdateCostume,
+            PacketServerUpdateEquipment,
+            PacketServerUpdateInventory,
+            PacketServerUpdateItemLife,
+            PacketServerUpdateLevel,
+            PacketServerUpdateMoney,
+            PacketServerUpdateSpeed,
+            PacketServerUpdateStatusEffects,
+            PacketServerUpdateVehiclePart,
+            PacketServerUpdateXpStamina,
+            PacketServerUseEmote,
+            PacketServerUseItem,
+            PacketServerWhisper,
+            login_server_packets::PacketConnectionReply,
+            login_server_packets::PacketServerChannelList,
+            login_server_packets::PacketServerLoginReply,
+            login_server_packets::PacketServerSelectServer,
+            world_server_packets::PacketConnectionReply,
+            world_server_packets::PacketServerCharacterList,
+            world_server_packets::PacketServerCreateCharacterReply,
+            world_server_packets::PacketServerDeleteCharacterReply,
+            world_server_packets::PacketServerMoveServer,
+        ]
+    );
+});
@@ -0,0 +1,76 @@
+use std::str::FromStr;
+
+use crate::IROSE_112_TABLE;
+
+/// irose client builds, only builds whose packet table and layouts are known are listed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IroseClientVersion {
+    #[default]
+    Irose112,
+}
+
+impl IroseClientVersion {
+    /// Returns the built in packet codec table of the client build
+    pub fn packet_table(self) -> &'static [u8; 256] {
+        match self {
+            Self::Irose112 => &IROSE_112_TABLE,
+        }
+    }
+}
+
+impl FromStr for IroseClientVersion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "112" => Ok(Self::Irose112),
+            _ => Err(format!("Unknown irose client version {}", s)),
+        }
+    }
+}
+
+/// The packet codec table, packet layout variant and optional capabilities of connected clients.
+#[derive(Clone, Copy, Debug)]
+pub struct IroseClientProfile {
+    pub version: IroseClientVersion,
+    pub packet_table: &'static [u8; 256],
+    /// Patched clients which understand the clan recruitment board packets
+    pub clan_recruitment: bool,
+    /// Patched clients which understand the costume slot packets
+    pub costume_slots: bool,
+    /// Patched clients which understand the character title packets and spawn sub flag
+    pub titles: bool,
+}
+
+impl IroseClientProfile {
+    pub fn new(version: IroseClientVersion, packet_table: &'static [u8; 256]) -> Self {
+        Self {
+            version,
+            packet_table,
+            clan_recruitment: false,
+            costume_slots: false,
+            titles: false,
+        }
+    }
+
+    pub fn with_clan_recruitment(mut self, clan_recruitment: bool) -> Self {
+        self.clan_recruitment = clan_recruitment;
+        self
+    }
+
+    pub fn with_costume_slots(mut self, costume_slots: bool) -> Self {
+        self.costume_slots = costume_slots;
+        self
+    }
+
+    pub fn with_titles(mut self, titles: bool) -> Self {
+        self.titles = titles;
+        self
+    }
+}
+
+impl Default for IroseClientProfile {
+    fn default() -> Self {
+        Self::new(IroseClientVersion::Irose112, &IROSE_112_TABLE)
+    }
+}
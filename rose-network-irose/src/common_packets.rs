@@ -1,19 +1,21 @@
 use bevy::prelude::Vec3;
 use modular_bitfield::prelude::*;
+use num_traits::FromPrimitive;
 use std::{convert::TryInto, num::NonZeroU16};
 
 use rose_data::{
-    AmmoIndex, ClanMemberPosition, EquipmentIndex, EquipmentItem, Item, ItemReference, ItemType,
-    StackableItem, StatusEffectId, StatusEffectType, VehiclePartIndex,
+    AmmoIndex, ClanMemberPosition, CostumeIndex, EquipmentIndex, EquipmentItem, Item,
+    ItemReference, ItemType, StackableItem, StatusEffectId, StatusEffectType, VehiclePartIndex,
 };
 use rose_data_irose::{
     decode_ammo_index, decode_equipment_index, decode_item_type, decode_vehicle_part_index,
-    encode_equipment_index, encode_item_type, encode_vehicle_part_index, SKILL_PAGE_SIZE,
+    encode_equipment_index, encode_item_type, encode_vehicle_part_index, IroseSkillPageType,
+    SKILL_PAGE_SIZE,
 };
 use rose_game_common::{
     components::{
-        ActiveStatusEffect, CharacterGender, ClanMark, Equipment, HealthPoints, HotbarSlot,
-        InventoryPageType, ItemSlot, Money, MoveMode, SkillSlot, Stamina,
+        ActiveStatusEffect, CharacterGender, ClanMark, ClanPermissions, Equipment, HealthPoints,
+        HotbarSlot, InventoryPageType, ItemSlot, Money, MoveMode, SkillSlot, Stamina,
     },
     data::Damage,
     messages::{
@@ -521,6 +523,39 @@ impl PacketWriteVehiclePartIndex for PacketWriter {
     }
 }
 
+pub trait PacketReadCostumeIndex {
+    fn read_costume_index_u8(&mut self) -> Result<CostumeIndex, PacketError>;
+}
+
+pub trait PacketWriteCostumeIndex {
+    fn write_costume_index_u8(&mut self, costume_index: CostumeIndex);
+}
+
+impl<'a> PacketReadCostumeIndex for PacketReader<'a> {
+    fn read_costume_index_u8(&mut self) -> Result<CostumeIndex, PacketError> {
+        match self.read_u8()? {
+            0 => Ok(CostumeIndex::Head),
+            1 => Ok(CostumeIndex::Body),
+            2 => Ok(CostumeIndex::Back),
+            3 => Ok(CostumeIndex::Hands),
+            4 => Ok(CostumeIndex::Feet),
+            _ => Err(PacketError::InvalidPacket),
+        }
+    }
+}
+
+impl PacketWriteCostumeIndex for PacketWriter {
+    fn write_costume_index_u8(&mut self, costume_index: CostumeIndex) {
+        self.write_u8(match costume_index {
+            CostumeIndex::Head => 0,
+            CostumeIndex::Body => 1,
+            CostumeIndex::Back => 2,
+            CostumeIndex::Hands => 3,
+            CostumeIndex::Feet => 4,
+        })
+    }
+}
+
 pub trait PacketReadItemSlot {
     fn read_item_slot_u8(&mut self) -> Result<ItemSlot, PacketError>;
     fn read_item_slot_u16(&mut self) -> Result<ItemSlot, PacketError>;
@@ -608,6 +643,7 @@ pub trait PacketWriteSkillSlot {
 fn decode_skill_slot(index: usize) -> Result<SkillSlot, PacketError> {
     let page = index / SKILL_PAGE_SIZE;
     let slot = index % SKILL_PAGE_SIZE;
+    IroseSkillPageType::from_usize(page).ok_or(PacketError::InvalidPacket)?;
     Ok(SkillSlot(page, slot))
 }
 
@@ -1302,3 +1338,39 @@ impl PacketWriteClanMark for PacketWriter {
         };
     }
 }
+
+pub trait PacketReadClanPermissions {
+    fn read_clan_permissions(&mut self) -> Result<ClanPermissions, PacketError>;
+}
+
+impl<'a> PacketReadClanPermissions for PacketReader<'a> {
+    fn read_clan_permissions(&mut self) -> Result<ClanPermissions, PacketError> {
+        // Each permission is a bitmask of the clan member positions which are allowed it
+        let mut permissions = ClanPermissions::default();
+        for (_, positions) in permissions.0.iter_mut() {
+            let mask = self.read_u8()?;
+            for (bit, (_, allowed)) in positions.iter_mut().enumerate() {
+                *allowed = mask & (1 << bit) != 0;
+            }
+        }
+        Ok(permissions)
+    }
+}
+
+pub trait PacketWriteClanPermissions {
+    fn write_clan_permissions(&mut self, permissions: &ClanPermissions);
+}
+
+impl PacketWriteClanPermissions for PacketWriter {
+    fn write_clan_permissions(&mut self, permissions: &ClanPermissions) {
+        for (_, positions) in permissions.0.iter() {
+            let mut mask = 0u8;
+            for (bit, (_, allowed)) in positions.iter().enumerate() {
+                if *allowed {
+                    mask |= 1 << bit;
+                }
+            }
+            self.write_u8(mask);
+        }
+    }
+}
@@ -7,27 +7,36 @@ use modular_bitfield::{
 };
 use num_derive::FromPrimitive;
 
-use rose_data::{AmmoIndex, EquipmentIndex, Item, MotionId, SkillId, VehiclePartIndex, WarpGateId};
+use rose_data::{
+    AmmoIndex, CostumeIndex, EquipmentIndex, Item, MotionId, SkillId, VehiclePartIndex, WarpGateId,
+};
 use rose_data_irose::{decode_ammo_index, encode_ammo_index};
 use rose_game_common::{
-    components::{BasicStatType, CharacterUniqueId, ClanMark, HotbarSlot, ItemSlot, SkillSlot},
+    components::{
+        BasicStatType, CharacterUniqueId, ClanMark, ClanPermissions, HotbarSlot, InventoryPageType,
+        ItemSlot, Money, SkillSlot,
+    },
     messages::{
-        client::NpcStoreBuyItem, ClientEntityId, PartyItemSharing, PartyRejectInviteReason,
-        PartyXpSharing,
+        client::{InventorySortType, NpcStoreBuyItem},
+        ClientEntityId, PartyItemSharing, PartyRejectInviteReason, PartyXpSharing,
     },
 };
 use rose_network_common::{Packet, PacketError, PacketReader, PacketWriter};
 
 use crate::common_packets::{
-    decode_item_slot, encode_item_slot, PacketReadClanMark, PacketReadEntityId,
-    PacketReadEquipmentIndex, PacketReadHotbarSlot, PacketReadItemSlot, PacketReadItems,
-    PacketReadPartyRules, PacketReadSkillSlot, PacketReadVehiclePartIndex, PacketWriteClanMark,
-    PacketWriteEntityId, PacketWriteEquipmentIndex, PacketWriteHotbarSlot, PacketWriteItemSlot,
-    PacketWriteItems, PacketWritePartyRules, PacketWriteSkillSlot, PacketWriteVehiclePartIndex,
+    decode_item_slot, encode_item_slot, PacketReadClanMark, PacketReadClanPermissions,
+    PacketReadCostumeIndex, PacketReadEntityId, PacketReadEquipmentIndex, PacketReadHotbarSlot,
+    PacketReadItemSlot, PacketReadItems, PacketReadPartyRules, PacketReadSkillSlot,
+    PacketReadVehiclePartIndex, PacketWriteClanMark, PacketWriteClanPermissions,
+    PacketWriteCostumeIndex, PacketWriteEntityId, PacketWriteEquipmentIndex, PacketWriteHotbarSlot,
+    PacketWriteItemSlot, PacketWriteItems, PacketWritePartyRules, PacketWriteSkillSlot,
+    PacketWriteVehiclePartIndex,
 };
 
 #[derive(FromPrimitive)]
 pub enum ClientPackets {
+    // Sent periodically by the client to keep the connection alive
+    Alive = 0x700,
     LogoutRequest = 0x707,
     ConnectRequest = 0x70b,
     ReturnToCharacterSelectRequest = 0x71C,
@@ -67,12 +76,34 @@ pub enum ClientPackets {
     PartyReply = 0x7d1,
     PartyUpdateRules = 0x7d7,
     ClanCommand = 0x7e0,
+    // Not an irose packet, only sent by patched clients which support toggle skills
+    ToggleSkill = 0x7f0,
+    // Not an irose packet, only sent by patched clients which support the clan vault
+    ClanVault = 0x7f1,
+    // Not an irose packet, only sent by patched clients which support clan permissions
+    ClanPermissions = 0x7f2,
+    // Not an irose packet, only sent by patched clients which support picking up the nearest drop
+    PickupNearestItemDrop = 0x7f3,
+    // Not an irose packet, only sent by patched clients which support inspecting characters
+    InspectCharacter = 0x7f4,
+    // Not an irose packet, only sent by patched clients which support character titles
+    SetActiveTitle = 0x7f5,
+    // Not an irose packet, only sent by patched clients which support character statistics
+    RequestCharacterStatistics = 0x7f6,
+    // Not an irose packet, only sent by patched clients which support sorting the inventory
+    InventoryOperation = 0x7f8,
+    // Not an irose packet, only sent by patched clients which support costume slots
+    ChangeCostume = 0x7f9,
+    // Not an irose packet, only sent by patched clients which support the clan recruitment board
+    ClanRecruitment = 0x7fa,
+    // Not an irose packet, only sent by patched clients which support auto loot
+    SetAutoLoot = 0x7fb,
 }
 
 #[derive(Debug)]
 pub struct PacketClientConnectRequest<'a> {
     pub login_token: u32,
-    pub password_md5: &'a str,
+    pub password: &'a str,
 }
 
 impl<'a> TryFrom<&'a Packet> for PacketClientConnectRequest<'a> {
@@ -85,10 +116,10 @@ impl<'a> TryFrom<&'a Packet> for PacketClientConnectRequest<'a> {
 
         let mut reader = PacketReader::from(packet);
         let login_token = reader.read_u32()?;
-        let password_md5 = reader.read_fixed_length_utf8(32)?;
+        let password = reader.read_fixed_length_utf8(32)?;
         Ok(PacketClientConnectRequest {
             login_token,
-            password_md5,
+            password,
         })
     }
 }
@@ -97,7 +128,7 @@ impl<'a> From<&'a PacketClientConnectRequest<'a>> for Packet {
     fn from(packet: &'a PacketClientConnectRequest<'a>) -> Self {
         let mut writer = PacketWriter::new(ClientPackets::ConnectRequest as u16);
         writer.write_u32(packet.login_token);
-        writer.write_fixed_length_utf8(packet.password_md5, 32);
+        writer.write_fixed_length_utf8(packet.password, 32);
         writer.into()
     }
 }
@@ -106,6 +137,8 @@ impl<'a> From<&'a PacketClientConnectRequest<'a>> for Packet {
 pub struct PacketClientJoinZone {
     pub weight_rate: u8,
     pub z: u16,
+    /// Not sent by the official client, used by patched clients to report their zone checksum
+    pub zone_checksum: Option<u32>,
 }
 
 impl TryFrom<&Packet> for PacketClientJoinZone {
@@ -119,7 +152,12 @@ impl TryFrom<&Packet> for PacketClientJoinZone {
         let mut reader = PacketReader::from(packet);
         let weight_rate = reader.read_u8()?;
         let z = reader.read_u16()?;
-        Ok(PacketClientJoinZone { weight_rate, z })
+        let zone_checksum = reader.read_u32().ok();
+        Ok(PacketClientJoinZone {
+            weight_rate,
+            z,
+            zone_checksum,
+        })
     }
 }
 
@@ -128,6 +166,9 @@ impl From<&PacketClientJoinZone> for Packet {
         let mut writer = PacketWriter::new(ClientPackets::JoinZone as u16);
         writer.write_u8(packet.weight_rate);
         writer.write_u16(packet.z);
+        if let Some(zone_checksum) = packet.zone_checksum {
+            writer.write_u32(zone_checksum);
+        }
         writer.into()
     }
 }
@@ -261,6 +302,7 @@ impl From<&PacketClientSetHotbarSlot> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketClientChangeEquipment {
     pub equipment_index: EquipmentIndex,
     pub item_slot: Option<ItemSlot>,
@@ -297,6 +339,7 @@ impl From<&PacketClientChangeEquipment> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketClientChangeVehiclePart {
     pub vehicle_part_index: VehiclePartIndex,
     pub item_slot: Option<ItemSlot>,
@@ -333,6 +376,7 @@ impl From<&PacketClientChangeVehiclePart> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketClientIncreaseBasicStat {
     pub basic_stat_type: BasicStatType,
 }
@@ -401,6 +445,28 @@ impl From<&PacketClientPickupItemDrop> for Packet {
     }
 }
 
+#[derive(Debug)]
+pub struct PacketClientPickupNearestItemDrop;
+
+impl TryFrom<&Packet> for PacketClientPickupNearestItemDrop {
+    type Error = PacketError;
+
+    fn try_from(packet: &Packet) -> Result<Self, Self::Error> {
+        if packet.command != ClientPackets::PickupNearestItemDrop as u16 {
+            return Err(PacketError::InvalidPacket);
+        }
+
+        Ok(PacketClientPickupNearestItemDrop)
+    }
+}
+
+impl From<&PacketClientPickupNearestItemDrop> for Packet {
+    fn from(_: &PacketClientPickupNearestItemDrop) -> Self {
+        PacketWriter::new(ClientPackets::PickupNearestItemDrop as u16).into()
+    }
+}
+
+#[derive(Debug)]
 pub enum PacketClientReviveRequest {
     CurrentZone,
     SaveZone,
@@ -436,6 +502,7 @@ impl From<&PacketClientReviveRequest> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketClientSetReviveZone;
 
 impl TryFrom<&Packet> for PacketClientSetReviveZone {
@@ -456,11 +523,13 @@ impl From<&PacketClientSetReviveZone> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub enum PacketClientQuestRequestType {
     DeleteQuest,
     DoTrigger,
 }
 
+#[derive(Debug)]
 pub struct PacketClientQuestRequest {
     pub request_type: PacketClientQuestRequestType,
     pub quest_slot: u8,
@@ -707,7 +776,7 @@ impl TryFrom<&Packet> for PacketClientUseItem {
 
         let mut reader = PacketReader::from(packet);
         let item_slot = reader.read_item_slot_u16()?;
-        let target_entity_id = reader.read_u16().ok().map(|x| ClientEntityId(x as usize));
+        let target_entity_id = reader.read_option_entity_id().unwrap_or(None);
 
         Ok(PacketClientUseItem {
             item_slot,
@@ -857,6 +926,227 @@ impl From<&PacketClientCastSkillTargetPosition> for Packet {
     }
 }
 
+#[derive(Debug)]
+pub struct PacketClientToggleSkill {
+    pub skill_slot: SkillSlot,
+    pub enabled: bool,
+}
+
+impl TryFrom<&Packet> for PacketClientToggleSkill {
+    type Error = PacketError;
+
+    fn try_from(packet: &Packet) -> Result<Self, Self::Error> {
+        if packet.command != ClientPackets::ToggleSkill as u16 {
+            return Err(PacketError::InvalidPacket);
+        }
+
+        let mut reader = PacketReader::from(packet);
+        let skill_slot = reader.read_skill_slot_u8()?;
+        let enabled = reader.read_u8()? != 0;
+
+        Ok(PacketClientToggleSkill {
+            skill_slot,
+            enabled,
+        })
+    }
+}
+
+impl From<&PacketClientToggleSkill> for Packet {
+    fn from(packet: &PacketClientToggleSkill) -> Self {
+        let mut writer = PacketWriter::new(ClientPackets::ToggleSkill as u16);
+        writer.write_skill_slot_u8(packet.skill_slot);
+        writer.write_u8(packet.enabled as u8);
+        writer.into()
+    }
+}
+
+#[derive(Debug)]
+pub enum PacketClientClanVault {
+    Open,
+    Deposit { item_slot: ItemSlot, item: Item },
+    Withdraw { vault_slot: usize, item: Item },
+    GetLog,
+    DonateMoney { money: Money },
+}
+
+impl TryFrom<&Packet> for PacketClientClanVault {
+    type Error = PacketError;
+
+    fn try_from(packet: &Packet) -> Result<Self, Self::Error> {
+        if packet.command != ClientPackets::ClanVault as u16 {
+            return Err(PacketError::InvalidPacket);
+        }
+
+        let mut reader = PacketReader::from(packet);
+        let command = reader.read_u8()?;
+        match command {
+            0 => Ok(Self::Open),
+            1 => {
+                let item_slot = reader.read_item_slot_u8()?;
+                let item = reader.read_item_full()?.ok_or(PacketError::InvalidPacket)?;
+                Ok(Self::Deposit { item_slot, item })
+            }
+            2 => {
+                let vault_slot = reader.read_u16()? as usize;
+                let item = reader.read_item_full()?.ok_or(PacketError::InvalidPacket)?;
+                Ok(Self::Withdraw { vault_slot, item })
+            }
+            3 => Ok(Self::GetLog),
+            4 => {
+                let money = Money(reader.read_i64()?);
+                Ok(Self::DonateMoney { money })
+            }
+            _ => Err(PacketError::InvalidPacket),
+        }
+    }
+}
+
+impl From<&PacketClientClanVault> for Packet {
+    fn from(packet: &PacketClientClanVault) -> Self {
+        let mut writer = PacketWriter::new(ClientPackets::ClanVault as u16);
+        match packet {
+            PacketClientClanVault::Open => {
+                writer.write_u8(0);
+            }
+            PacketClientClanVault::Deposit { item_slot, item } => {
+                writer.write_u8(1);
+                writer.write_item_slot_u8(*item_slot);
+                writer.write_item_full(Some(item));
+            }
+            PacketClientClanVault::Withdraw { vault_slot, item } => {
+                writer.write_u8(2);
+                writer.write_u16(*vault_slot as u16);
+                writer.write_item_full(Some(item));
+            }
+            PacketClientClanVault::GetLog => {
+                writer.write_u8(3);
+            }
+            PacketClientClanVault::DonateMoney { money } => {
+                writer.write_u8(4);
+                writer.write_i64(money.0);
+            }
+        }
+        writer.into()
+    }
+}
+
+#[derive(Debug)]
+pub enum PacketClientClanRecruitment {
+    SetNotice { notice: String },
+    Search { query: String },
+    Apply { clan_name: String, message: String },
+    GetApplications,
+    ApplicationReply { name: String, accepted: bool },
+}
+
+impl TryFrom<&Packet> for PacketClientClanRecruitment {
+    type Error = PacketError;
+
+    fn try_from(packet: &Packet) -> Result<Self, Self::Error> {
+        if packet.command != ClientPackets::ClanRecruitment as u16 {
+            return Err(PacketError::InvalidPacket);
+        }
+
+        let mut reader = PacketReader::from(packet);
+        let command = reader.read_u8()?;
+        match command {
+            0 => {
+                let notice = reader.read_null_terminated_utf8()?.to_string();
+                Ok(Self::SetNotice { notice })
+            }
+            1 => {
+                let query = reader.read_null_terminated_utf8()?.to_string();
+                Ok(Self::Search { query })
+            }
+            2 => {
+                let clan_name = reader.read_null_terminated_utf8()?.to_string();
+                let message = reader.read_null_terminated_utf8()?.to_string();
+                Ok(Self::Apply { clan_name, message })
+            }
+            3 => Ok(Self::GetApplications),
+            4 => {
+                let accepted = reader.read_u8()? != 0;
+                let name = reader.read_null_terminated_utf8()?.to_string();
+                Ok(Self::ApplicationReply { name, accepted })
+            }
+            _ => Err(PacketError::InvalidPacket),
+        }
+    }
+}
+
+impl From<&PacketClientClanRecruitment> for Packet {
+    fn from(packet: &PacketClientClanRecruitment) -> Self {
+        let mut writer = PacketWriter::new(ClientPackets::ClanRecruitment as u16);
+        match packet {
+            PacketClientClanRecruitment::SetNotice { notice } => {
+                writer.write_u8(0);
+                writer.write_null_terminated_utf8(notice);
+            }
+            PacketClientClanRecruitment::Search { query } => {
+                writer.write_u8(1);
+                writer.write_null_terminated_utf8(query);
+            }
+            PacketClientClanRecruitment::Apply { clan_name, message } => {
+                writer.write_u8(2);
+                writer.write_null_terminated_utf8(clan_name);
+                writer.write_null_terminated_utf8(message);
+            }
+            PacketClientClanRecruitment::GetApplications => {
+                writer.write_u8(3);
+            }
+            PacketClientClanRecruitment::ApplicationReply { name, accepted } => {
+                writer.write_u8(4);
+                writer.write_u8(*accepted as u8);
+                writer.write_null_terminated_utf8(name);
+            }
+        }
+        writer.into()
+    }
+}
+
+#[derive(Debug)]
+pub enum PacketClientClanPermissions {
+    Get,
+    Set { permissions: ClanPermissions },
+}
+
+impl TryFrom<&Packet> for PacketClientClanPermissions {
+    type Error = PacketError;
+
+    fn try_from(packet: &Packet) -> Result<Self, Self::Error> {
+        if packet.command != ClientPackets::ClanPermissions as u16 {
+            return Err(PacketError::InvalidPacket);
+        }
+
+        let mut reader = PacketReader::from(packet);
+        let command = reader.read_u8()?;
+        match command {
+            0 => Ok(Self::Get),
+            1 => {
+                let permissions = reader.read_clan_permissions()?;
+                Ok(Self::Set { permissions })
+            }
+            _ => Err(PacketError::InvalidPacket),
+        }
+    }
+}
+
+impl From<&PacketClientClanPermissions> for Packet {
+    fn from(packet: &PacketClientClanPermissions) -> Self {
+        let mut writer = PacketWriter::new(ClientPackets::ClanPermissions as u16);
+        match packet {
+            PacketClientClanPermissions::Get => {
+                writer.write_u8(0);
+            }
+            PacketClientClanPermissions::Set { permissions } => {
+                writer.write_u8(1);
+                writer.write_clan_permissions(permissions);
+            }
+        }
+        writer.into()
+    }
+}
+
 #[derive(Debug)]
 pub struct PacketClientNpcStoreTransactionBuyItem {
     pub tab: u8,
@@ -987,12 +1277,14 @@ impl From<&PacketClientChangeAmmo> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub enum PacketClientMoveToggleType {
     Run,
     Sit,
     Drive,
 }
 
+#[derive(Debug)]
 pub struct PacketClientMoveToggle {
     pub toggle_type: PacketClientMoveToggleType,
 }
@@ -1094,6 +1386,8 @@ pub enum PacketClientPartyRequest {
     Leave,
     ChangeOwner(ClientEntityId),
     Kick(CharacterUniqueId),
+    /// Not sent by the official client, used by patched clients to start a vote kick
+    VoteKick(CharacterUniqueId),
 }
 
 impl TryFrom<&Packet> for PacketClientPartyRequest {
@@ -1111,6 +1405,7 @@ impl TryFrom<&Packet> for PacketClientPartyRequest {
             2 => PacketClientPartyRequest::Leave,
             3 => PacketClientPartyRequest::ChangeOwner(ClientEntityId(reader.read_u16()? as usize)),
             0x81 => PacketClientPartyRequest::Kick(reader.read_u32()?),
+            0x82 => PacketClientPartyRequest::VoteKick(reader.read_u32()?),
             _ => return Err(PacketError::InvalidPacket),
         };
         Ok(request)
@@ -1144,6 +1439,10 @@ impl From<&PacketClientPartyRequest> for Packet {
                 writer.write_u8(0x81);
                 writer.write_u32(unique_id);
             }
+            PacketClientPartyRequest::VoteKick(unique_id) => {
+                writer.write_u8(0x82);
+                writer.write_u32(unique_id);
+            }
         }
         writer.into()
     }
@@ -1154,6 +1453,8 @@ pub enum PacketClientPartyReply {
     AcceptCreate(ClientEntityId),
     AcceptJoin(ClientEntityId),
     Reject(PartyRejectInviteReason, ClientEntityId),
+    /// Not sent by the official client, used by patched clients to vote on a vote kick
+    VoteKick(bool),
 }
 
 impl TryFrom<&Packet> for PacketClientPartyReply {
@@ -1176,6 +1477,8 @@ impl TryFrom<&Packet> for PacketClientPartyReply {
                 PartyRejectInviteReason::Reject,
                 ClientEntityId(reader.read_u16()? as usize),
             ),
+            0x82 => PacketClientPartyReply::VoteKick(true),
+            0x83 => PacketClientPartyReply::VoteKick(false),
             _ => return Err(PacketError::InvalidPacket),
         };
         Ok(reply)
@@ -1204,11 +1507,16 @@ impl From<&PacketClientPartyReply> for Packet {
                 writer.write_entity_id(entity_id);
                 writer.write_u16(0);
             }
+            PacketClientPartyReply::VoteKick(accept) => {
+                writer.write_u8(if accept { 0x82 } else { 0x83 });
+                writer.write_u32(0);
+            }
         }
         writer.into()
     }
 }
 
+#[derive(Debug)]
 pub struct PacketClientPartyUpdateRules {
     pub item_sharing: PartyItemSharing,
     pub xp_sharing: PartyXpSharing,
@@ -1239,6 +1547,7 @@ impl From<&PacketClientPartyUpdateRules> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketClientMoveCollision {
     pub position: Vec3,
 }
@@ -1271,6 +1580,7 @@ impl From<&PacketClientMoveCollision> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub enum PacketClientCraftItem {
     InsertGem {
         equipment_index: EquipmentIndex,
@@ -1419,6 +1729,7 @@ impl From<&PacketClientCraftItem> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketClientBankOpen {}
 
 impl TryFrom<&Packet> for PacketClientBankOpen {
@@ -1447,6 +1758,7 @@ impl From<&PacketClientBankOpen> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub enum PacketClientBankMoveItem {
     Deposit {
         item_slot: ItemSlot,
@@ -1572,3 +1884,245 @@ impl From<&PacketClientClanCommand> for Packet {
         writer.into()
     }
 }
+
+#[derive(Debug)]
+pub enum PacketClientInspectCharacter {
+    Inspect { target_entity_id: ClientEntityId },
+    SetAllowInspect { allow_inspect: bool },
+}
+
+impl TryFrom<&Packet> for PacketClientInspectCharacter {
+    type Error = PacketError;
+
+    fn try_from(packet: &Packet) -> Result<Self, Self::Error> {
+        if packet.command != ClientPackets::InspectCharacter as u16 {
+            return Err(PacketError::InvalidPacket);
+        }
+
+        let mut reader = PacketReader::from(packet);
+        match reader.read_u8()? {
+            0 => {
+                let target_entity_id = reader.read_entity_id()?;
+                Ok(PacketClientInspectCharacter::Inspect { target_entity_id })
+            }
+            1 => {
+                let allow_inspect = reader.read_u8()? != 0;
+                Ok(PacketClientInspectCharacter::SetAllowInspect { allow_inspect })
+            }
+            _ => Err(PacketError::InvalidPacket),
+        }
+    }
+}
+
+impl From<&PacketClientInspectCharacter> for Packet {
+    fn from(packet: &PacketClientInspectCharacter) -> Self {
+        let mut writer = PacketWriter::new(ClientPackets::InspectCharacter as u16);
+        match *packet {
+            PacketClientInspectCharacter::Inspect { target_entity_id } => {
+                writer.write_u8(0);
+                writer.write_entity_id(target_entity_id);
+            }
+            PacketClientInspectCharacter::SetAllowInspect { allow_inspect } => {
+                writer.write_u8(1);
+                writer.write_u8(allow_inspect as u8);
+            }
+        }
+        writer.into()
+    }
+}
+
+#[derive(Debug)]
+pub struct PacketClientSetActiveTitle {
+    pub title_id: Option<u16>,
+}
+
+impl TryFrom<&Packet> for PacketClientSetActiveTitle {
+    type Error = PacketError;
+
+    fn try_from(packet: &Packet) -> Result<Self, Self::Error> {
+        if packet.command != ClientPackets::SetActiveTitle as u16 {
+            return Err(PacketError::InvalidPacket);
+        }
+
+        let mut reader = PacketReader::from(packet);
+        let title_id = Some(reader.read_u16()?).filter(|id| *id != 0);
+        Ok(PacketClientSetActiveTitle { title_id })
+    }
+}
+
+impl From<&PacketClientSetActiveTitle> for Packet {
+    fn from(packet: &PacketClientSetActiveTitle) -> Self {
+        let mut writer = PacketWriter::new(ClientPackets::SetActiveTitle as u16);
+        writer.write_u16(packet.title_id.unwrap_or(0));
+        writer.into()
+    }
+}
+
+#[derive(Debug)]
+pub struct PacketClientRequestCharacterStatistics;
+
+impl TryFrom<&Packet> for PacketClientRequestCharacterStatistics {
+    type Error = PacketError;
+
+    fn try_from(packet: &Packet) -> Result<Self, Self::Error> {
+        if packet.command != ClientPackets::RequestCharacterStatistics as u16 {
+            return Err(PacketError::InvalidPacket);
+        }
+
+        Ok(PacketClientRequestCharacterStatistics)
+    }
+}
+
+impl From<&PacketClientRequestCharacterStatistics> for Packet {
+    fn from(_: &PacketClientRequestCharacterStatistics) -> Self {
+        PacketWriter::new(ClientPackets::RequestCharacterStatistics as u16).into()
+    }
+}
+
+#[derive(Debug)]
+pub struct PacketClientSetAutoLoot {
+    pub auto_loot: bool,
+}
+
+impl TryFrom<&Packet> for PacketClientSetAutoLoot {
+    type Error = PacketError;
+
+    fn try_from(packet: &Packet) -> Result<Self, Self::Error> {
+        if packet.command != ClientPackets::SetAutoLoot as u16 {
+            return Err(PacketError::InvalidPacket);
+        }
+
+        let mut reader = PacketReader::from(packet);
+        let auto_loot = reader.read_u8()? != 0;
+        Ok(PacketClientSetAutoLoot { auto_loot })
+    }
+}
+
+impl From<&PacketClientSetAutoLoot> for Packet {
+    fn from(packet: &PacketClientSetAutoLoot) -> Self {
+        let mut writer = PacketWriter::new(ClientPackets::SetAutoLoot as u16);
+        writer.write_u8(packet.auto_loot as u8);
+        writer.into()
+    }
+}
+
+fn decode_inventory_page_type(value: u8) -> Option<InventoryPageType> {
+    match value {
+        0 => Some(InventoryPageType::Equipment),
+        1 => Some(InventoryPageType::Consumables),
+        2 => Some(InventoryPageType::Materials),
+        3 => Some(InventoryPageType::Vehicles),
+        _ => None,
+    }
+}
+
+fn encode_inventory_page_type(page_type: InventoryPageType) -> u8 {
+    match page_type {
+        InventoryPageType::Equipment => 0,
+        InventoryPageType::Consumables => 1,
+        InventoryPageType::Materials => 2,
+        InventoryPageType::Vehicles => 3,
+    }
+}
+
+#[derive(Debug)]
+pub enum PacketClientInventoryOperation {
+    Sort {
+        page_type: InventoryPageType,
+        sort_type: InventorySortType,
+    },
+    BankDepositPage {
+        page_type: InventoryPageType,
+    },
+}
+
+impl TryFrom<&Packet> for PacketClientInventoryOperation {
+    type Error = PacketError;
+
+    fn try_from(packet: &Packet) -> Result<Self, Self::Error> {
+        if packet.command != ClientPackets::InventoryOperation as u16 {
+            return Err(PacketError::InvalidPacket);
+        }
+
+        let mut reader = PacketReader::from(packet);
+        let operation = reader.read_u8()?;
+        let page_type =
+            decode_inventory_page_type(reader.read_u8()?).ok_or(PacketError::InvalidPacket)?;
+        match operation {
+            0 => {
+                let sort_type = match reader.read_u8()? {
+                    0 => InventorySortType::ItemType,
+                    1 => InventorySortType::Value,
+                    _ => return Err(PacketError::InvalidPacket),
+                };
+                Ok(PacketClientInventoryOperation::Sort {
+                    page_type,
+                    sort_type,
+                })
+            }
+            1 => Ok(PacketClientInventoryOperation::BankDepositPage { page_type }),
+            _ => Err(PacketError::InvalidPacket),
+        }
+    }
+}
+
+impl From<&PacketClientInventoryOperation> for Packet {
+    fn from(packet: &PacketClientInventoryOperation) -> Self {
+        let mut writer = PacketWriter::new(ClientPackets::InventoryOperation as u16);
+        match *packet {
+            PacketClientInventoryOperation::Sort {
+                page_type,
+                sort_type,
+            } => {
+                writer.write_u8(0);
+                writer.write_u8(encode_inventory_page_type(page_type));
+                writer.write_u8(match sort_type {
+                    InventorySortType::ItemType => 0,
+                    InventorySortType::Value => 1,
+                });
+            }
+            PacketClientInventoryOperation::BankDepositPage { page_type } => {
+                writer.write_u8(1);
+                writer.write_u8(encode_inventory_page_type(page_type));
+            }
+        }
+        writer.into()
+    }
+}
+
+#[derive(Debug)]
+pub struct PacketClientChangeCostume {
+    pub costume_index: CostumeIndex,
+    pub item_slot: Option<ItemSlot>,
+}
+
+impl TryFrom<&Packet> for PacketClientChangeCostume {
+    type Error = PacketError;
+
+    fn try_from(packet: &Packet) -> Result<Self, Self::Error> {
+        if packet.command != ClientPackets::ChangeCostume as u16 {
+            return Err(PacketError::InvalidPacket);
+        }
+
+        let mut reader = PacketReader::from(packet);
+        let costume_index = reader.read_costume_index_u8()?;
+        let item_slot = reader.read_item_slot_u16().ok();
+        Ok(PacketClientChangeCostume {
+            costume_index,
+            item_slot,
+        })
+    }
+}
+
+impl From<&PacketClientChangeCostume> for Packet {
+    fn from(packet: &PacketClientChangeCostume) -> Self {
+        let mut writer = PacketWriter::new(ClientPackets::ChangeCostume as u16);
+        writer.write_costume_index_u8(packet.costume_index);
+        if let Some(item_slot) = packet.item_slot {
+            writer.write_item_slot_u16(item_slot);
+        } else {
+            writer.write_u16(0);
+        }
+        writer.into()
+    }
+}
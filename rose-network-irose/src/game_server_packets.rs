@@ -7,9 +7,9 @@ use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
 
 use rose_data::{
-    AbilityType, AmmoIndex, ClanMemberPosition, EquipmentIndex, EquipmentItem, Item, ItemReference,
-    ItemType, MotionId, NpcId, SkillId, SkillPageType, StackableItem, VehiclePartIndex, WorldTicks,
-    ZoneId,
+    AbilityType, AmmoIndex, ClanMemberPosition, CostumeIndex, EquipmentIndex, EquipmentItem, Item,
+    ItemReference, ItemType, MotionId, NpcId, SkillId, SkillPageType, StackableItem,
+    VehiclePartIndex, WorldTicks, ZoneId,
 };
 use rose_data_irose::{
     decode_ability_type, decode_ammo_index, encode_ability_type, encode_ammo_index,
@@ -17,17 +17,19 @@ use rose_data_irose::{
 };
 use rose_game_common::{
     components::{
-        ActiveQuest, BasicStatType, BasicStats, CharacterInfo, CharacterUniqueId, ClanLevel,
-        ClanMark, ClanPoints, ClanUniqueId, DroppedItem, Equipment, ExperiencePoints, HealthPoints,
-        Hotbar, HotbarSlot, Inventory, ItemSlot, Level, ManaPoints, Money, MoveMode, MoveSpeed,
-        Npc, QuestState, SkillList, SkillPage, SkillPoints, SkillSlot, Stamina, StatPoints, Team,
-        UnionMembership,
+        ActiveQuest, BasicStatType, BasicStats, CharacterInfo, CharacterStatistics,
+        CharacterUniqueId, ClanLevel, ClanMark, ClanPermissions, ClanPoints, ClanUniqueId,
+        DroppedItem, Equipment, ExperiencePoints, HealthPoints, Hotbar, HotbarSlot, Inventory,
+        ItemSlot, Level, ManaPoints, Money, MoveMode, MoveSpeed, Npc, QuestState, SkillList,
+        SkillPage, SkillPoints, SkillSlot, Stamina, StatPoints, Team, UnionMembership,
     },
     data::Damage,
     messages::{
         server::{
-            ActiveStatusEffects, CancelCastingSkillReason, CharacterClanMembership,
-            ClanCreateError, ClanMemberInfo, CraftInsertGemError, LearnSkillError,
+            ActiveStatusEffects, CancelCastingSkillReason, CharacterClanMembership, CharacterTitle,
+            ClanApplication, ClanApplicationError, ClanCreateError, ClanInviteError,
+            ClanMemberInfo, ClanRecruitmentListing, ClanVaultAction, ClanVaultError,
+            ClanVaultLogEntry, CraftInsertGemError, InspectCharacterError, LearnSkillError,
             LevelUpSkillError, NpcStoreTransactionError, PartyMemberInfo, PartyMemberInfoOnline,
             PersonalStoreTransactionStatus, PickupItemDropError, SpawnCommandState,
         },
@@ -38,15 +40,16 @@ use rose_network_common::{Packet, PacketError, PacketReader, PacketWriter};
 
 use crate::common_packets::{
     PacketEquipmentAmmoPart, PacketReadCharacterGender, PacketReadClanMark,
-    PacketReadClanMemberPosition, PacketReadDamage, PacketReadEntityId, PacketReadEquipmentIndex,
-    PacketReadHotbarSlot, PacketReadItemSlot, PacketReadItems, PacketReadMoveMode,
-    PacketReadPartyMemberInfo, PacketReadPartyRules, PacketReadSkillSlot,
-    PacketReadSpawnCommandState, PacketReadStatusEffects, PacketReadVehiclePartIndex,
-    PacketWriteCharacterGender, PacketWriteClanMark, PacketWriteClanMemberPosition,
-    PacketWriteDamage, PacketWriteEntityId, PacketWriteEquipmentIndex, PacketWriteHotbarSlot,
-    PacketWriteItemSlot, PacketWriteItems, PacketWriteMoveMode, PacketWritePartyMemberInfo,
-    PacketWritePartyRules, PacketWriteSkillSlot, PacketWriteSpawnCommandState,
-    PacketWriteStatusEffects, PacketWriteVehiclePartIndex,
+    PacketReadClanMemberPosition, PacketReadClanPermissions, PacketReadCostumeIndex,
+    PacketReadDamage, PacketReadEntityId, PacketReadEquipmentIndex, PacketReadHotbarSlot,
+    PacketReadItemSlot, PacketReadItems, PacketReadMoveMode, PacketReadPartyMemberInfo,
+    PacketReadPartyRules, PacketReadSkillSlot, PacketReadSpawnCommandState,
+    PacketReadStatusEffects, PacketReadVehiclePartIndex, PacketWriteCharacterGender,
+    PacketWriteClanMark, PacketWriteClanMemberPosition, PacketWriteClanPermissions,
+    PacketWriteCostumeIndex, PacketWriteDamage, PacketWriteEntityId, PacketWriteEquipmentIndex,
+    PacketWriteHotbarSlot, PacketWriteItemSlot, PacketWriteItems, PacketWriteMoveMode,
+    PacketWritePartyMemberInfo, PacketWritePartyRules, PacketWriteSkillSlot,
+    PacketWriteSpawnCommandState, PacketWriteStatusEffects, PacketWriteVehiclePartIndex,
 };
 
 #[derive(FromPrimitive)]
@@ -74,6 +77,7 @@ pub enum ServerPackets {
     LocalChat = 0x783,
     Whisper = 0x784,
     ShoutChat = 0x785,
+    SetEventStatus = 0x790,
     SpawnEntityNpc = 0x791,
     SpawnEntityMonster = 0x792,
     SpawnEntityCharacter = 0x793,
@@ -125,10 +129,27 @@ pub enum ServerPackets {
     PartyMemberUpdateInfo = 0x7d5,
     PartyUpdateRules = 0x7d7,
     ClanCommand = 0x7e0,
+    // Not an irose packet, only understood by patched clients which support toggle skills
+    ToggleSkill = 0x7f0,
+    // Not an irose packet, only understood by patched clients which support the clan vault
+    ClanVault = 0x7f1,
+    // Not an irose packet, only understood by patched clients which support clan permissions
+    ClanPermissions = 0x7f2,
+    // Not an irose packet, only understood by patched clients which support inspecting characters
+    InspectCharacter = 0x7f4,
+    // Not an irose packet, only understood by patched clients which support character titles
+    CharacterTitle = 0x7f5,
+    // Not an irose packet, only understood by patched clients which support character statistics
+    CharacterStatistics = 0x7f6,
+    // Not an irose packet, only understood by patched clients which support costume slots
+    UpdateCostume = 0x7f7,
+    // Not an irose packet, only understood by patched clients which support the clan
+    // recruitment board
+    ClanRecruitment = 0x7fa,
 }
 
 #[allow(dead_code)]
-#[derive(Clone, Copy, FromPrimitive)]
+#[derive(Clone, Copy, Debug, FromPrimitive)]
 pub enum ConnectResult {
     Ok = 0,
     Failed = 1,
@@ -137,6 +158,7 @@ pub enum ConnectResult {
     AlreadyLoggedIn = 4,
 }
 
+#[derive(Debug)]
 pub struct PacketConnectionReply {
     pub result: ConnectResult,
     pub packet_sequence_id: u32,
@@ -196,6 +218,7 @@ fn write_skill_page(writer: &mut PacketWriter, skill_page: Option<&SkillPage>) {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerSelectCharacter {
     pub character_info: CharacterInfo,
     pub position: Vec3,
@@ -465,6 +488,7 @@ impl From<&PacketServerSelectCharacter> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerCharacterInventory {
     pub equipment: Equipment,
     pub inventory: Inventory,
@@ -595,6 +619,7 @@ impl From<&PacketServerCharacterInventory> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerCharacterQuestData {
     pub quest_state: QuestState,
 }
@@ -688,6 +713,7 @@ impl TryFrom<&Packet> for PacketServerCharacterQuestData {
                 union_variables,
                 quest_switches: BitArray::new(quest_switches_u32),
                 active_quests,
+                ..Default::default()
             },
         })
     }
@@ -796,6 +822,7 @@ impl From<&PacketServerCharacterQuestData> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerAttackEntity {
     pub entity_id: ClientEntityId,
     pub target_entity_id: ClientEntityId,
@@ -845,6 +872,7 @@ impl From<&PacketServerAttackEntity> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerDamageEntity {
     pub attacker_entity_id: ClientEntityId,
     pub defender_entity_id: ClientEntityId,
@@ -887,6 +915,7 @@ impl From<&PacketServerDamageEntity> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerMoveEntity {
     pub entity_id: ClientEntityId,
     pub target_entity_id: Option<ClientEntityId>,
@@ -956,6 +985,7 @@ impl From<&PacketServerMoveEntity> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerJoinZone {
     pub entity_id: ClientEntityId,
     pub experience_points: ExperiencePoints,
@@ -967,6 +997,8 @@ pub struct PacketServerJoinZone {
     pub world_price_rate: i32,
     pub item_price_rate: i32,
     pub town_price_rate: i32,
+    /// Only understood by patched clients
+    pub zone_checksum: Option<u32>,
 }
 
 impl TryFrom<&Packet> for PacketServerJoinZone {
@@ -998,6 +1030,7 @@ impl TryFrom<&Packet> for PacketServerJoinZone {
 
         let world_ticks = WorldTicks(reader.read_u32()? as u64);
         let team = Team::new(reader.read_u32()?);
+        let zone_checksum = reader.read_u32().ok();
 
         Ok(PacketServerJoinZone {
             entity_id,
@@ -1010,6 +1043,7 @@ impl TryFrom<&Packet> for PacketServerJoinZone {
             world_price_rate,
             town_price_rate,
             item_price_rate: item_price_rate_0,
+            zone_checksum,
         })
     }
 }
@@ -1036,10 +1070,14 @@ impl From<&PacketServerJoinZone> for Packet {
 
         writer.write_u32(packet.world_ticks.0 as u32);
         writer.write_u32(packet.team.id);
+        if let Some(zone_checksum) = packet.zone_checksum {
+            writer.write_u32(zone_checksum);
+        }
         writer.into()
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerLocalChat<'a> {
     pub entity_id: ClientEntityId,
     pub text: &'a str,
@@ -1069,6 +1107,7 @@ impl<'a> From<&'a PacketServerLocalChat<'a>> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerShoutChat<'a> {
     pub name: &'a str,
     pub text: &'a str,
@@ -1098,6 +1137,7 @@ impl<'a> From<&'a PacketServerShoutChat<'a>> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerAnnounceChat<'a> {
     pub name: Option<&'a str>,
     pub text: &'a str,
@@ -1129,6 +1169,7 @@ impl<'a> From<&'a PacketServerAnnounceChat<'a>> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerWhisper<'a> {
     pub from: &'a str,
     pub text: &'a str,
@@ -1158,6 +1199,7 @@ impl<'a> From<&'a PacketServerWhisper<'a>> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerStopMoveEntity {
     pub entity_id: ClientEntityId,
     pub x: f32,
@@ -1193,6 +1235,7 @@ impl From<&PacketServerStopMoveEntity> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerTeleport {
     pub entity_id: ClientEntityId,
     pub zone_id: ZoneId,
@@ -1272,6 +1315,7 @@ impl From<&PacketServerSetHotbarSlot> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerSpawnEntityItemDrop {
     pub entity_id: ClientEntityId,
     pub dropped_item: DroppedItem,
@@ -1332,6 +1376,7 @@ impl From<&PacketServerSpawnEntityItemDrop> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerSpawnEntityNpc {
     pub entity_id: ClientEntityId,
     pub npc: Npc,
@@ -1342,6 +1387,7 @@ pub struct PacketServerSpawnEntityNpc {
     pub health: HealthPoints,
     pub move_mode: MoveMode,
     pub status_effects: ActiveStatusEffects,
+    pub event_status: i16,
 }
 
 impl TryFrom<&Packet> for PacketServerSpawnEntityNpc {
@@ -1366,7 +1412,7 @@ impl TryFrom<&Packet> for PacketServerSpawnEntityNpc {
         let npc_id = NpcId::new(reader.read_u16()?).ok_or(PacketError::InvalidPacket)?;
         let quest_index = reader.read_u16()?;
         let direction = reader.read_f32()?;
-        let _event_status = reader.read_u16()?;
+        let event_status = reader.read_i16()?;
         reader.read_status_effects_values(&mut status_effects)?;
         Ok(PacketServerSpawnEntityNpc {
             entity_id,
@@ -1378,6 +1424,7 @@ impl TryFrom<&Packet> for PacketServerSpawnEntityNpc {
             health,
             move_mode,
             status_effects,
+            event_status,
         })
     }
 }
@@ -1396,12 +1443,13 @@ impl From<&PacketServerSpawnEntityNpc> for Packet {
         writer.write_u16(packet.npc.id.get());
         writer.write_u16(packet.npc.quest_index);
         writer.write_f32(packet.direction);
-        writer.write_u16(0); // event status
+        writer.write_i16(packet.event_status);
         writer.write_status_effects_values(&packet.status_effects);
         writer.into()
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerSpawnEntityMonster {
     pub entity_id: ClientEntityId,
     pub npc: Npc,
@@ -1466,6 +1514,7 @@ impl From<&PacketServerSpawnEntityMonster> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerSpawnEntityCharacter {
     pub character_info: CharacterInfo,
     pub spawn_command_state: SpawnCommandState,
@@ -1481,8 +1530,13 @@ pub struct PacketServerSpawnEntityCharacter {
     pub team: Team,
     pub personal_store_info: Option<(i32, String)>,
     pub clan_membership: Option<CharacterClanMembership>,
+    pub title: Option<String>,
 }
 
+// Not an irose sub flag, a title follows the clan membership for patched clients which
+// support character titles
+const SPAWN_CHARACTER_SUB_FLAG_TITLE: u32 = 0x100;
+
 impl TryFrom<&Packet> for PacketServerSpawnEntityCharacter {
     type Error = PacketError;
 
@@ -1556,6 +1610,12 @@ impl TryFrom<&Packet> for PacketServerSpawnEntityCharacter {
             })
         }(&mut reader);
 
+        let title = if sub_flags & SPAWN_CHARACTER_SUB_FLAG_TITLE != 0 {
+            Some(reader.read_null_terminated_utf8()?.to_string())
+        } else {
+            None
+        };
+
         Ok(Self {
             entity_id,
             position: Vec3::new(position_x, position_y, position_z),
@@ -1586,6 +1646,7 @@ impl TryFrom<&Packet> for PacketServerSpawnEntityCharacter {
             passive_attack_speed,
             personal_store_info,
             clan_membership,
+            title,
         })
     }
 }
@@ -1639,6 +1700,9 @@ impl From<&PacketServerSpawnEntityCharacter> for Packet {
         if packet.personal_store_info.is_some() {
             sub_flags |= 0x2;
         }
+        if packet.title.is_some() {
+            sub_flags |= SPAWN_CHARACTER_SUB_FLAG_TITLE;
+        }
         writer.write_u32(sub_flags);
         writer.write_null_terminated_utf8(&packet.character_info.name);
 
@@ -1657,12 +1721,20 @@ impl From<&PacketServerSpawnEntityCharacter> for Packet {
             writer.write_u8(clan_membership.level.get() as u8);
             writer.write_clan_member_position_u8(&clan_membership.position);
             writer.write_null_terminated_utf8(&clan_membership.name);
+        } else if packet.title.is_some() {
+            // Clan unique id 0 marks the absent clan membership
+            writer.write_u32(0);
+        }
+
+        if let Some(title) = packet.title.as_ref() {
+            writer.write_null_terminated_utf8(title);
         }
 
         writer.into()
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerRemoveEntities {
     pub entity_ids: Vec<ClientEntityId>,
 }
@@ -1694,6 +1766,7 @@ impl From<&PacketServerRemoveEntities> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerUpdateInventory {
     pub items: Vec<(ItemSlot, Option<Item>)>,
     pub with_money: Option<Money>,
@@ -1750,6 +1823,7 @@ impl From<&PacketServerUpdateInventory> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerUpdateMoney {
     pub money: Money,
 }
@@ -1776,6 +1850,7 @@ impl From<&PacketServerUpdateMoney> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerRewardItems {
     pub items: Vec<(ItemSlot, Option<Item>)>,
 }
@@ -1812,6 +1887,7 @@ impl From<&PacketServerRewardItems> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerRewardMoney {
     pub money: Money,
 }
@@ -1838,6 +1914,7 @@ impl From<&PacketServerRewardMoney> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerUpdateAmmo {
     pub entity_id: ClientEntityId,
     pub ammo_index: AmmoIndex,
@@ -1897,6 +1974,7 @@ impl From<&PacketServerUpdateAmmo> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerUpdateEquipment {
     pub entity_id: ClientEntityId,
     pub equipment_index: EquipmentIndex,
@@ -1953,6 +2031,7 @@ impl From<&PacketServerUpdateEquipment> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerUpdateVehiclePart {
     pub entity_id: ClientEntityId,
     pub vehicle_part_index: VehiclePartIndex,
@@ -1996,6 +2075,7 @@ impl From<&PacketServerUpdateVehiclePart> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerUpdateItemLife {
     pub item_slot: ItemSlot,
     pub life: u16,
@@ -2026,6 +2106,7 @@ impl From<&PacketServerUpdateItemLife> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerUpdateLevel {
     pub entity_id: ClientEntityId,
     pub update_values: Option<(Level, ExperiencePoints, StatPoints, SkillPoints)>,
@@ -2072,6 +2153,7 @@ impl From<&PacketServerUpdateLevel> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerUpdateXpStamina {
     pub xp: u64,
     pub stamina: u32,
@@ -2109,6 +2191,7 @@ impl From<&PacketServerUpdateXpStamina> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerUpdateBasicStat {
     pub basic_stat_type: BasicStatType,
     pub value: i32,
@@ -2158,6 +2241,7 @@ impl From<&PacketServerUpdateBasicStat> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub enum PacketServerPickupItemDropResult {
     Item {
         drop_entity_id: ClientEntityId,
@@ -2261,6 +2345,7 @@ impl From<&PacketServerPickupItemDropResult> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerLogoutResult {
     pub result: Result<(), Duration>,
 }
@@ -2300,6 +2385,7 @@ impl From<&PacketServerLogoutResult> for Packet {
     }
 }
 
+#[derive(Debug)]
 #[allow(dead_code)]
 pub enum PacketServerQuestResultType {
     AddSuccess,
@@ -2310,6 +2396,7 @@ pub enum PacketServerQuestResultType {
     TriggerFailed,
 }
 
+#[derive(Debug)]
 pub struct PacketServerQuestResult {
     pub result: PacketServerQuestResultType,
     pub slot: u8,
@@ -2362,6 +2449,7 @@ impl From<&PacketServerQuestResult> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerUpdateAbilityValue {
     pub is_add: bool,
     pub ability_type: AbilityType,
@@ -2406,6 +2494,7 @@ impl From<&PacketServerUpdateAbilityValue> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub enum PacketServerLearnSkillResult {
     Success {
         skill_slot: SkillSlot,
@@ -2496,6 +2585,7 @@ impl From<&PacketServerLearnSkillResult> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub enum PacketServerLevelUpSkillResult {
     Success {
         skill_slot: SkillSlot,
@@ -2517,11 +2607,12 @@ impl TryFrom<&Packet> for PacketServerLevelUpSkillResult {
         }
 
         let mut reader = PacketReader::from(packet);
+        let result = reader.read_u8()?;
         let skill_slot = reader.read_skill_slot_u8()?;
         let skill_id = SkillId::new(reader.read_u16()?);
         let skill_points = SkillPoints::new(reader.read_u16()? as u32);
 
-        Ok(match reader.read_u8()? {
+        Ok(match result {
             0 => Self::Success {
                 skill_slot,
                 skill_id: skill_id.ok_or(PacketError::InvalidPacket)?,
@@ -2591,6 +2682,7 @@ impl From<&PacketServerLevelUpSkillResult> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerRunNpcDeathTrigger {
     pub npc_id: NpcId,
 }
@@ -2618,6 +2710,7 @@ impl From<&PacketServerRunNpcDeathTrigger> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerOpenPersonalStore<'a> {
     pub entity_id: ClientEntityId,
     pub skin: i32,
@@ -2654,6 +2747,7 @@ impl<'a> From<&'a PacketServerOpenPersonalStore<'a>> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerClosePersonalStore {
     pub entity_id: ClientEntityId,
 }
@@ -2681,6 +2775,7 @@ impl From<&PacketServerClosePersonalStore> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerPersonalStoreItemList {
     pub sell_items: Vec<(u8, Item, Money)>,
     pub buy_items: Vec<(u8, Item, Money)>,
@@ -2750,6 +2845,7 @@ impl From<&PacketServerPersonalStoreItemList> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerPersonalStoreTransactionUpdateMoneyAndInventory {
     pub items: Vec<(ItemSlot, Option<Item>)>,
     pub money: Money,
@@ -2792,6 +2888,7 @@ impl From<&PacketServerPersonalStoreTransactionUpdateMoneyAndInventory> for Pack
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerPersonalStoreTransactionResult {
     pub status: PersonalStoreTransactionStatus,
     pub store_entity_id: ClientEntityId,
@@ -2854,6 +2951,7 @@ impl From<&PacketServerPersonalStoreTransactionResult> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerUseItem {
     pub entity_id: ClientEntityId,
     pub item: ItemReference,
@@ -2893,6 +2991,7 @@ impl From<&PacketServerUseItem> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerCastSkillSelf {
     pub entity_id: ClientEntityId,
     pub skill_id: SkillId,
@@ -2932,6 +3031,7 @@ impl From<&PacketServerCastSkillSelf> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerCastSkillTargetEntity {
     pub entity_id: ClientEntityId,
     pub skill_id: SkillId,
@@ -2985,6 +3085,7 @@ impl From<&PacketServerCastSkillTargetEntity> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerCastSkillTargetPosition {
     pub entity_id: ClientEntityId,
     pub skill_id: SkillId,
@@ -3030,6 +3131,7 @@ impl From<&PacketServerCastSkillTargetPosition> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerStartCastingSkill {
     pub entity_id: ClientEntityId,
 }
@@ -3065,6 +3167,7 @@ pub struct SkillEffectData {
     caster_intelligence: B10,
 }
 
+#[derive(Debug)]
 pub struct PacketServerApplySkillEffect {
     pub entity_id: ClientEntityId,
     pub caster_entity_id: ClientEntityId,
@@ -3124,6 +3227,7 @@ impl From<&PacketServerApplySkillEffect> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerApplySkillDamage {
     pub entity_id: ClientEntityId,
     pub caster_entity_id: ClientEntityId,
@@ -3192,6 +3296,7 @@ impl From<&PacketServerApplySkillDamage> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerCancelCastingSkill {
     pub entity_id: ClientEntityId,
     pub reason: CancelCastingSkillReason,
@@ -3230,6 +3335,7 @@ impl From<&PacketServerCancelCastingSkill> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerFinishCastingSkill {
     pub entity_id: ClientEntityId,
     pub skill_id: SkillId,
@@ -3263,6 +3369,572 @@ impl From<&PacketServerFinishCastingSkill> for Packet {
     }
 }
 
+#[derive(Debug)]
+pub struct PacketServerToggleSkill {
+    pub entity_id: ClientEntityId,
+    pub skill_id: SkillId,
+    pub enabled: bool,
+}
+
+impl TryFrom<&Packet> for PacketServerToggleSkill {
+    type Error = PacketError;
+
+    fn try_from(packet: &Packet) -> Result<Self, PacketError> {
+        if packet.command != ServerPackets::ToggleSkill as u16 {
+            return Err(PacketError::InvalidPacket);
+        }
+
+        let mut reader = PacketReader::from(packet);
+        let entity_id = reader.read_entity_id()?;
+        let skill_id = SkillId::new(reader.read_u16()?).ok_or(PacketError::InvalidPacket)?;
+        let enabled = reader.read_u8()? != 0;
+
+        Ok(Self {
+            entity_id,
+            skill_id,
+            enabled,
+        })
+    }
+}
+
+impl From<&PacketServerToggleSkill> for Packet {
+    fn from(packet: &PacketServerToggleSkill) -> Self {
+        let mut writer = PacketWriter::new(ServerPackets::ToggleSkill as u16);
+        writer.write_entity_id(packet.entity_id);
+        writer.write_u16(packet.skill_id.get());
+        writer.write_u8(packet.enabled as u8);
+        writer.into()
+    }
+}
+
+#[derive(Debug)]
+pub enum PacketServerClanVault {
+    Items { items: Vec<(usize, Option<Item>)> },
+    Log { entries: Vec<ClanVaultLogEntry> },
+    Error { error: ClanVaultError },
+}
+
+impl TryFrom<&Packet> for PacketServerClanVault {
+    type Error = PacketError;
+
+    fn try_from(packet: &Packet) -> Result<Self, PacketError> {
+        if packet.command != ServerPackets::ClanVault as u16 {
+            return Err(PacketError::InvalidPacket);
+        }
+
+        let mut reader = PacketReader::from(packet);
+        let command = reader.read_u8()?;
+        match command {
+            0 => {
+                let num_items = reader.read_u16()? as usize;
+                let mut items = Vec::with_capacity(num_items);
+                for _ in 0..num_items {
+                    let vault_slot = reader.read_u16()? as usize;
+                    let item = reader.read_item_full()?;
+                    items.push((vault_slot, item));
+                }
+
+                Ok(Self::Items { items })
+            }
+            1 => {
+                let num_entries = reader.read_u16()? as usize;
+                let mut entries = Vec::with_capacity(num_entries);
+                for _ in 0..num_entries {
+                    let name = reader.read_null_terminated_utf8()?.to_string();
+                    let action = match reader.read_u8()? {
+                        0 => ClanVaultAction::Deposit,
+                        1 => ClanVaultAction::Withdraw,
+                        _ => return Err(PacketError::InvalidPacket),
+                    };
+                    let item = reader.read_item_full()?.ok_or(PacketError::InvalidPacket)?;
+                    let timestamp = reader.read_u64()?;
+                    entries.push(ClanVaultLogEntry {
+                        name,
+                        action,
+                        item,
+                        timestamp,
+                    });
+                }
+
+                Ok(Self::Log { entries })
+            }
+            2 => {
+                let error = match reader.read_u8()? {
+                    0 => ClanVaultError::Failed,
+                    1 => ClanVaultError::NoPermission,
+                    2 => ClanVaultError::VaultFull,
+                    3 => ClanVaultError::InventoryFull,
+                    _ => return Err(PacketError::InvalidPacket),
+                };
+
+                Ok(Self::Error { error })
+            }
+            _ => Err(PacketError::InvalidPacket),
+        }
+    }
+}
+
+impl From<&PacketServerClanVault> for Packet {
+    fn from(packet: &PacketServerClanVault) -> Self {
+        let mut writer = PacketWriter::new(ServerPackets::ClanVault as u16);
+        match packet {
+            PacketServerClanVault::Items { items } => {
+                writer.write_u8(0);
+                writer.write_u16(items.len() as u16);
+                for (vault_slot, item) in items.iter() {
+                    writer.write_u16(*vault_slot as u16);
+                    writer.write_item_full(item.as_ref());
+                }
+            }
+            PacketServerClanVault::Log { entries } => {
+                writer.write_u8(1);
+                writer.write_u16(entries.len() as u16);
+                for entry in entries.iter() {
+                    writer.write_null_terminated_utf8(&entry.name);
+                    writer.write_u8(match entry.action {
+                        ClanVaultAction::Deposit => 0,
+                        ClanVaultAction::Withdraw => 1,
+                    });
+                    writer.write_item_full(Some(&entry.item));
+                    writer.write_u64(entry.timestamp);
+                }
+            }
+            PacketServerClanVault::Error { error } => {
+                writer.write_u8(2);
+                writer.write_u8(match error {
+                    ClanVaultError::Failed => 0,
+                    ClanVaultError::NoPermission => 1,
+                    ClanVaultError::VaultFull => 2,
+                    ClanVaultError::InventoryFull => 3,
+                });
+            }
+        }
+        writer.into()
+    }
+}
+
+#[derive(Debug)]
+pub enum PacketServerClanRecruitment {
+    List {
+        listings: Vec<ClanRecruitmentListing>,
+    },
+    Applications {
+        applications: Vec<ClanApplication>,
+    },
+    ApplicationReceived {
+        application: ClanApplication,
+    },
+    ApplicationSent {
+        clan_name: String,
+    },
+    ApplicationRejected {
+        clan_name: String,
+    },
+    Error {
+        error: ClanApplicationError,
+    },
+}
+
+fn read_clan_application(reader: &mut PacketReader) -> Result<ClanApplication, PacketError> {
+    let level = Level::new(reader.read_u16()? as u32);
+    let job = reader.read_u16()?;
+    let name = reader.read_null_terminated_utf8()?.to_string();
+    let message = reader.read_null_terminated_utf8()?.to_string();
+    Ok(ClanApplication {
+        name,
+        level,
+        job,
+        message,
+    })
+}
+
+fn write_clan_application(writer: &mut PacketWriter, application: &ClanApplication) {
+    writer.write_u16(application.level.level as u16);
+    writer.write_u16(application.job);
+    writer.write_null_terminated_utf8(&application.name);
+    writer.write_null_terminated_utf8(&application.message);
+}
+
+impl TryFrom<&Packet> for PacketServerClanRecruitment {
+    type Error = PacketError;
+
+    fn try_from(packet: &Packet) -> Result<Self, PacketError> {
+        if packet.command != ServerPackets::ClanRecruitment as u16 {
+            return Err(PacketError::InvalidPacket);
+        }
+
+        let mut reader = PacketReader::from(packet);
+        let command = reader.read_u8()?;
+        match command {
+            0 => {
+                let num_listings = reader.read_u16()? as usize;
+                let mut listings = Vec::with_capacity(num_listings);
+                for _ in 0..num_listings {
+                    let level = ClanLevel::new(reader.read_u8()? as u32)
+                        .ok_or(PacketError::InvalidPacket)?;
+                    let member_count = reader.read_u16()? as usize;
+                    let name = reader.read_null_terminated_utf8()?.to_string();
+                    let notice = reader.read_null_terminated_utf8()?.to_string();
+                    listings.push(ClanRecruitmentListing {
+                        name,
+                        level,
+                        member_count,
+                        notice,
+                    });
+                }
+
+                Ok(Self::List { listings })
+            }
+            1 => {
+                let num_applications = reader.read_u16()? as usize;
+                let mut applications = Vec::with_capacity(num_applications);
+                for _ in 0..num_applications {
+                    applications.push(read_clan_application(&mut reader)?);
+                }
+
+                Ok(Self::Applications { applications })
+            }
+            2 => {
+                let application = read_clan_application(&mut reader)?;
+                Ok(Self::ApplicationReceived { application })
+            }
+            3 => {
+                let clan_name = reader.read_null_terminated_utf8()?.to_string();
+                Ok(Self::ApplicationSent { clan_name })
+            }
+            4 => {
+                let clan_name = reader.read_null_terminated_utf8()?.to_string();
+                Ok(Self::ApplicationRejected { clan_name })
+            }
+            5 => {
+                let error = match reader.read_u8()? {
+                    0 => ClanApplicationError::Failed,
+                    1 => ClanApplicationError::NoPermission,
+                    2 => ClanApplicationError::NotRecruiting,
+                    3 => ClanApplicationError::AlreadyInClan,
+                    4 => ClanApplicationError::AlreadyApplied,
+                    5 => ClanApplicationError::ClanFull,
+                    6 => ClanApplicationError::ApplicantOffline,
+                    7 => ClanApplicationError::TooLong,
+                    _ => return Err(PacketError::InvalidPacket),
+                };
+
+                Ok(Self::Error { error })
+            }
+            _ => Err(PacketError::InvalidPacket),
+        }
+    }
+}
+
+impl From<&PacketServerClanRecruitment> for Packet {
+    fn from(packet: &PacketServerClanRecruitment) -> Self {
+        let mut writer = PacketWriter::new(ServerPackets::ClanRecruitment as u16);
+        match packet {
+            PacketServerClanRecruitment::List { listings } => {
+                writer.write_u8(0);
+                writer.write_u16(listings.len() as u16);
+                for listing in listings.iter() {
+                    writer.write_u8(listing.level.get() as u8);
+                    writer.write_u16(listing.member_count as u16);
+                    writer.write_null_terminated_utf8(&listing.name);
+                    writer.write_null_terminated_utf8(&listing.notice);
+                }
+            }
+            PacketServerClanRecruitment::Applications { applications } => {
+                writer.write_u8(1);
+                writer.write_u16(applications.len() as u16);
+                for application in applications.iter() {
+                    write_clan_application(&mut writer, application);
+                }
+            }
+            PacketServerClanRecruitment::ApplicationReceived { application } => {
+                writer.write_u8(2);
+                write_clan_application(&mut writer, application);
+            }
+            PacketServerClanRecruitment::ApplicationSent { clan_name } => {
+                writer.write_u8(3);
+                writer.write_null_terminated_utf8(clan_name);
+            }
+            PacketServerClanRecruitment::ApplicationRejected { clan_name } => {
+                writer.write_u8(4);
+                writer.write_null_terminated_utf8(clan_name);
+            }
+            PacketServerClanRecruitment::Error { error } => {
+                writer.write_u8(5);
+                writer.write_u8(match error {
+                    ClanApplicationError::Failed => 0,
+                    ClanApplicationError::NoPermission => 1,
+                    ClanApplicationError::NotRecruiting => 2,
+                    ClanApplicationError::AlreadyInClan => 3,
+                    ClanApplicationError::AlreadyApplied => 4,
+                    ClanApplicationError::ClanFull => 5,
+                    ClanApplicationError::ApplicantOffline => 6,
+                    ClanApplicationError::TooLong => 7,
+                });
+            }
+        }
+        writer.into()
+    }
+}
+
+#[derive(Debug)]
+pub struct PacketServerClanPermissions {
+    pub permissions: ClanPermissions,
+}
+
+impl TryFrom<&Packet> for PacketServerClanPermissions {
+    type Error = PacketError;
+
+    fn try_from(packet: &Packet) -> Result<Self, PacketError> {
+        if packet.command != ServerPackets::ClanPermissions as u16 {
+            return Err(PacketError::InvalidPacket);
+        }
+
+        let mut reader = PacketReader::from(packet);
+        let permissions = reader.read_clan_permissions()?;
+        Ok(Self { permissions })
+    }
+}
+
+impl From<&PacketServerClanPermissions> for Packet {
+    fn from(packet: &PacketServerClanPermissions) -> Self {
+        let mut writer = PacketWriter::new(ServerPackets::ClanPermissions as u16);
+        writer.write_clan_permissions(&packet.permissions);
+        writer.into()
+    }
+}
+
+#[derive(Debug)]
+pub enum PacketServerInspectCharacter {
+    Inspect {
+        entity_id: ClientEntityId,
+        name: String,
+        level: Level,
+        job: u16,
+        equipment: Equipment,
+    },
+    Error {
+        error: InspectCharacterError,
+    },
+}
+
+const INSPECT_EQUIPMENT_INDICES: [EquipmentIndex; 11] = [
+    EquipmentIndex::Face,
+    EquipmentIndex::Head,
+    EquipmentIndex::Body,
+    EquipmentIndex::Back,
+    EquipmentIndex::Hands,
+    EquipmentIndex::Feet,
+    EquipmentIndex::Weapon,
+    EquipmentIndex::SubWeapon,
+    EquipmentIndex::Necklace,
+    EquipmentIndex::Ring,
+    EquipmentIndex::Earring,
+];
+
+impl TryFrom<&Packet> for PacketServerInspectCharacter {
+    type Error = PacketError;
+
+    fn try_from(packet: &Packet) -> Result<Self, PacketError> {
+        if packet.command != ServerPackets::InspectCharacter as u16 {
+            return Err(PacketError::InvalidPacket);
+        }
+
+        let mut reader = PacketReader::from(packet);
+        match reader.read_u8()? {
+            0 => {
+                let entity_id = reader.read_entity_id()?;
+                let level = Level::new(reader.read_u16()? as u32);
+                let job = reader.read_u16()?;
+                let mut equipment = Equipment::default();
+                for index in INSPECT_EQUIPMENT_INDICES {
+                    equipment.equipped_items[index] = reader.read_equipment_item_full()?;
+                }
+                let name = reader.read_null_terminated_utf8()?.to_string();
+
+                Ok(Self::Inspect {
+                    entity_id,
+                    name,
+                    level,
+                    job,
+                    equipment,
+                })
+            }
+            1 => {
+                let error = match reader.read_u8()? {
+                    0 => InspectCharacterError::NotFound,
+                    1 => InspectCharacterError::Private,
+                    2 => InspectCharacterError::TooFrequent,
+                    _ => return Err(PacketError::InvalidPacket),
+                };
+
+                Ok(Self::Error { error })
+            }
+            _ => Err(PacketError::InvalidPacket),
+        }
+    }
+}
+
+impl From<&PacketServerInspectCharacter> for Packet {
+    fn from(packet: &PacketServerInspectCharacter) -> Self {
+        let mut writer = PacketWriter::new(ServerPackets::InspectCharacter as u16);
+        match packet {
+            PacketServerInspectCharacter::Inspect {
+                entity_id,
+                name,
+                level,
+                job,
+                equipment,
+            } => {
+                writer.write_u8(0);
+                writer.write_entity_id(*entity_id);
+                writer.write_u16(level.level as u16);
+                writer.write_u16(*job);
+                for index in INSPECT_EQUIPMENT_INDICES {
+                    writer.write_equipment_item_full(equipment.get_equipment_item(index));
+                }
+                writer.write_null_terminated_utf8(name);
+            }
+            PacketServerInspectCharacter::Error { error } => {
+                writer.write_u8(1);
+                writer.write_u8(match error {
+                    InspectCharacterError::NotFound => 0,
+                    InspectCharacterError::Private => 1,
+                    InspectCharacterError::TooFrequent => 2,
+                });
+            }
+        }
+        writer.into()
+    }
+}
+
+#[derive(Debug)]
+pub enum PacketServerCharacterTitle {
+    List {
+        titles: Vec<CharacterTitle>,
+        active: Option<u16>,
+    },
+    Update {
+        entity_id: ClientEntityId,
+        title: Option<String>,
+    },
+}
+
+impl TryFrom<&Packet> for PacketServerCharacterTitle {
+    type Error = PacketError;
+
+    fn try_from(packet: &Packet) -> Result<Self, PacketError> {
+        if packet.command != ServerPackets::CharacterTitle as u16 {
+            return Err(PacketError::InvalidPacket);
+        }
+
+        let mut reader = PacketReader::from(packet);
+        match reader.read_u8()? {
+            0 => {
+                let active = Some(reader.read_u16()?).filter(|id| *id != 0);
+                let count = reader.read_u8()? as usize;
+                let mut titles = Vec::with_capacity(count);
+                for _ in 0..count {
+                    let id = reader.read_u16()?;
+                    let name = reader.read_null_terminated_utf8()?.to_string();
+                    titles.push(CharacterTitle { id, name });
+                }
+
+                Ok(Self::List { titles, active })
+            }
+            1 => {
+                let entity_id = reader.read_entity_id()?;
+                let title = Some(reader.read_null_terminated_utf8()?)
+                    .filter(|title| !title.is_empty())
+                    .map(|title| title.to_string());
+
+                Ok(Self::Update { entity_id, title })
+            }
+            _ => Err(PacketError::InvalidPacket),
+        }
+    }
+}
+
+impl From<&PacketServerCharacterTitle> for Packet {
+    fn from(packet: &PacketServerCharacterTitle) -> Self {
+        let mut writer = PacketWriter::new(ServerPackets::CharacterTitle as u16);
+        match packet {
+            PacketServerCharacterTitle::List { titles, active } => {
+                writer.write_u8(0);
+                writer.write_u16(active.unwrap_or(0));
+                writer.write_u8(titles.len() as u8);
+                for title in titles.iter() {
+                    writer.write_u16(title.id);
+                    writer.write_null_terminated_utf8(&title.name);
+                }
+            }
+            PacketServerCharacterTitle::Update { entity_id, title } => {
+                writer.write_u8(1);
+                writer.write_entity_id(*entity_id);
+                writer.write_null_terminated_utf8(title.as_deref().unwrap_or(""));
+            }
+        }
+        writer.into()
+    }
+}
+
+#[derive(Debug)]
+pub struct PacketServerCharacterStatistics {
+    pub statistics: CharacterStatistics,
+}
+
+impl TryFrom<&Packet> for PacketServerCharacterStatistics {
+    type Error = PacketError;
+
+    fn try_from(packet: &Packet) -> Result<Self, PacketError> {
+        if packet.command != ServerPackets::CharacterStatistics as u16 {
+            return Err(PacketError::InvalidPacket);
+        }
+
+        let mut reader = PacketReader::from(packet);
+        let mut statistics = CharacterStatistics {
+            monster_kills: reader.read_u32()?,
+            deaths: reader.read_u32()?,
+            money_earned: Money(reader.read_i64()?),
+            distance_walked: reader.read_u64()? as f64,
+            quests_completed: reader.read_u32()?,
+            ..Default::default()
+        };
+
+        let num_npc_kills = reader.read_u16()?;
+        for _ in 0..num_npc_kills {
+            let npc_id = NpcId::new(reader.read_u16()?).ok_or(PacketError::InvalidPacket)?;
+            let kills = reader.read_u32()?;
+            statistics.npc_kills.insert(npc_id, kills);
+        }
+
+        Ok(Self { statistics })
+    }
+}
+
+impl From<&PacketServerCharacterStatistics> for Packet {
+    fn from(packet: &PacketServerCharacterStatistics) -> Self {
+        let statistics = &packet.statistics;
+        let mut writer = PacketWriter::new(ServerPackets::CharacterStatistics as u16);
+        writer.write_u32(statistics.monster_kills);
+        writer.write_u32(statistics.deaths);
+        writer.write_i64(statistics.money_earned.0);
+        writer.write_u64(statistics.distance_walked as u64);
+        writer.write_u32(statistics.quests_completed);
+
+        // Sorted so the encoding does not depend on the map order
+        let mut npc_kills: Vec<_> = statistics.npc_kills.iter().collect();
+        npc_kills.sort_by_key(|(npc_id, _)| npc_id.get());
+        writer.write_u16(npc_kills.len() as u16);
+        for (npc_id, kills) in npc_kills {
+            writer.write_u16(npc_id.get());
+            writer.write_u32(*kills);
+        }
+        writer.into()
+    }
+}
+
+#[derive(Debug)]
 pub struct PacketServerUpdateSpeed {
     pub entity_id: ClientEntityId,
     pub run_speed: i32,
@@ -3302,6 +3974,7 @@ impl From<&PacketServerUpdateSpeed> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerUpdateStatusEffects {
     pub entity_id: ClientEntityId,
     pub status_effects: ActiveStatusEffects,
@@ -3349,6 +4022,7 @@ impl From<&PacketServerUpdateStatusEffects> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerNpcStoreTransactionError {
     pub error: NpcStoreTransactionError,
 }
@@ -3404,6 +4078,7 @@ pub enum PacketServerMoveToggleType {
     Sit,
 }
 
+#[derive(Debug)]
 pub struct PacketServerMoveToggle {
     pub entity_id: ClientEntityId,
     pub move_toggle_type: PacketServerMoveToggleType,
@@ -3454,6 +4129,7 @@ impl From<&PacketServerMoveToggle> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerUseEmote {
     pub entity_id: ClientEntityId,
     pub motion_id: MotionId,
@@ -3491,6 +4167,7 @@ impl From<&PacketServerUseEmote> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub enum PacketServerPartyRequest {
     Create(ClientEntityId),
     Invite(ClientEntityId),
@@ -3540,6 +4217,7 @@ impl From<&PacketServerPartyRequest> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub enum PacketServerPartyReply {
     AcceptCreate(ClientEntityId),
     AcceptInvite(ClientEntityId),
@@ -3548,6 +4226,9 @@ pub enum PacketServerPartyReply {
     ChangeOwner(ClientEntityId),
     MemberKicked(u32),
     MemberDisconnect(u32),
+    /// Vote kick replies are only understood by patched clients
+    VoteKickStarted(u32),
+    VoteKickFailed(u32),
 }
 
 impl TryFrom<&Packet> for PacketServerPartyReply {
@@ -3589,6 +4270,14 @@ impl TryFrom<&Packet> for PacketServerPartyReply {
                 let character_id = reader.read_u32()?;
                 PacketServerPartyReply::MemberDisconnect(character_id)
             }
+            0x82 => {
+                let character_id = reader.read_u32()?;
+                PacketServerPartyReply::VoteKickStarted(character_id)
+            }
+            0x83 => {
+                let character_id = reader.read_u32()?;
+                PacketServerPartyReply::VoteKickFailed(character_id)
+            }
             _ => return Err(PacketError::InvalidPacket),
         };
 
@@ -3635,11 +4324,20 @@ impl From<&PacketServerPartyReply> for Packet {
                 writer.write_u8(0x81);
                 writer.write_u32(character_id);
             }
+            PacketServerPartyReply::VoteKickStarted(character_id) => {
+                writer.write_u8(0x82);
+                writer.write_u32(character_id);
+            }
+            PacketServerPartyReply::VoteKickFailed(character_id) => {
+                writer.write_u8(0x83);
+                writer.write_u32(character_id);
+            }
         }
         writer.into()
     }
 }
 
+#[derive(Debug)]
 pub enum PacketServerPartyMembers {
     List {
         item_sharing: PartyItemSharing,
@@ -3679,10 +4377,16 @@ impl TryFrom<&Packet> for PacketServerPartyMembers {
                 members.push(reader.read_party_member_info()?);
             }
 
+            // The owner is always the first member in the packet
+            let owner_character_id = members
+                .first()
+                .ok_or(PacketError::InvalidPacket)?
+                .get_character_id();
+
             Ok(PacketServerPartyMembers::List {
                 item_sharing,
                 xp_sharing,
-                owner_character_id: members[0].get_character_id(),
+                owner_character_id,
                 members,
             })
         }
@@ -3729,6 +4433,7 @@ impl From<&PacketServerPartyMembers> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerPartyMemberUpdateInfo {
     pub member_info: PartyMemberInfoOnline,
 }
@@ -3755,6 +4460,7 @@ impl From<&PacketServerPartyMemberUpdateInfo> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerPartyMemberRewardItem {
     pub entity_id: ClientEntityId,
     pub item: Item,
@@ -3784,6 +4490,7 @@ impl From<&PacketServerPartyMemberRewardItem> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerChangeNpcId {
     pub entity_id: ClientEntityId,
     pub npc_id: NpcId,
@@ -3814,6 +4521,41 @@ impl From<&PacketServerChangeNpcId> for Packet {
     }
 }
 
+#[derive(Debug)]
+pub struct PacketServerSetEventStatus {
+    pub entity_id: ClientEntityId,
+    pub event_status: i16,
+}
+
+impl TryFrom<&Packet> for PacketServerSetEventStatus {
+    type Error = PacketError;
+
+    fn try_from(packet: &Packet) -> Result<Self, PacketError> {
+        if packet.command != ServerPackets::SetEventStatus as u16 {
+            return Err(PacketError::InvalidPacket);
+        }
+
+        let mut reader = PacketReader::from(packet);
+        let entity_id = reader.read_entity_id()?;
+        let event_status = reader.read_i16()?;
+
+        Ok(Self {
+            entity_id,
+            event_status,
+        })
+    }
+}
+
+impl From<&PacketServerSetEventStatus> for Packet {
+    fn from(packet: &PacketServerSetEventStatus) -> Self {
+        let mut writer = PacketWriter::new(ServerPackets::SetEventStatus as u16);
+        writer.write_entity_id(packet.entity_id);
+        writer.write_i16(packet.event_status);
+        writer.into()
+    }
+}
+
+#[derive(Debug)]
 pub struct PacketServerPartyUpdateRules {
     pub item_sharing: PartyItemSharing,
     pub xp_sharing: PartyXpSharing,
@@ -3845,6 +4587,7 @@ impl From<&PacketServerPartyUpdateRules> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerAdjustPosition {
     pub entity_id: ClientEntityId,
     pub position: Vec3,
@@ -3881,6 +4624,7 @@ impl From<&PacketServerAdjustPosition> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub enum PacketServerCraftItem {
     InsertGemFailed {
         error: CraftInsertGemError,
@@ -3953,6 +4697,7 @@ impl From<&PacketServerCraftItem> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub enum PacketServerBankOpen {
     Open,
     SetItems { items: Vec<(u8, Option<Item>)> },
@@ -4027,6 +4772,7 @@ impl From<&PacketServerBankOpen> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerBankTransaction {
     pub inventory_item_slot: ItemSlot,
     pub inventory_item: Option<Item>,
@@ -4074,6 +4820,7 @@ impl From<&PacketServerBankTransaction> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerRepairedItemUsingNpc {
     pub item_slot: ItemSlot,
     pub item: Item,
@@ -4159,6 +4906,61 @@ pub enum PacketServerClanCommand {
     ClanMemberList {
         members: Vec<ClanMemberInfo>,
     },
+    ClanInvite {
+        inviter_name: String,
+    },
+    ClanInviteRejected {
+        name: String,
+    },
+    ClanInviteError {
+        error: ClanInviteError,
+    },
+    ClanMemberJoined {
+        member: ClanMemberInfo,
+    },
+    ClanMemberKicked {
+        name: String,
+    },
+    ClanMemberQuit {
+        name: String,
+    },
+    ClanMemberPositionChanged {
+        name: String,
+        position: ClanMemberPosition,
+    },
+    ClanNotice {
+        notice: String,
+    },
+    CharacterLeaveClan {
+        client_entity_id: ClientEntityId,
+    },
+}
+
+fn read_clan_member_info(reader: &mut PacketReader) -> Result<ClanMemberInfo, PacketError> {
+    let position = reader.read_clan_member_position_u8()?;
+    let channel_id = NonZeroUsize::new(reader.read_u8()? as usize);
+    let contribution = ClanPoints(reader.read_u32()? as u64);
+    let level = Level::new(reader.read_u16()? as u32);
+    let job = reader.read_u16()?;
+    let name = reader.read_null_terminated_utf8()?.to_string();
+
+    Ok(ClanMemberInfo {
+        name,
+        position,
+        contribution,
+        channel_id,
+        level,
+        job,
+    })
+}
+
+fn write_clan_member_info(writer: &mut PacketWriter, member: &ClanMemberInfo) {
+    writer.write_clan_member_position_u8(&member.position);
+    writer.write_u8(member.channel_id.map_or(0, |value| value.get()) as u8);
+    writer.write_u32(member.contribution.0 as u32);
+    writer.write_u16(member.level.level as u16);
+    writer.write_u16(member.job);
+    writer.write_null_terminated_utf8(&member.name);
 }
 
 impl TryFrom<&Packet> for PacketServerClanCommand {
@@ -4212,9 +5014,21 @@ impl TryFrom<&Packet> for PacketServerClanCommand {
                     skills,
                 })
             }
+            0x02 => Ok(Self::ClanInvite {
+                inviter_name: reader.read_null_terminated_utf8()?.to_string(),
+            }),
+            0x04 => Ok(Self::ClanInviteRejected {
+                name: reader.read_null_terminated_utf8()?.to_string(),
+            }),
+            0x34 => Ok(Self::ClanNotice {
+                notice: reader.read_null_terminated_utf8()?.to_string(),
+            }),
             0x35 => {
                 let client_entity_id = reader.read_entity_id()?;
-                let id = ClanUniqueId::new(reader.read_u32()?).ok_or(PacketError::InvalidPacket)?;
+                let Some(id) = ClanUniqueId::new(reader.read_u32()?) else {
+                    // A zero clan id removes the character from their clan
+                    return Ok(Self::CharacterLeaveClan { client_entity_id });
+                };
                 let mark = reader.read_clan_mark_u32()?;
                 let level =
                     ClanLevel::new(reader.read_u8()? as u32).ok_or(PacketError::InvalidPacket)?;
@@ -4242,6 +5056,21 @@ impl TryFrom<&Packet> for PacketServerClanCommand {
             0x44 => Ok(Self::ClanCreateError {
                 error: ClanCreateError::UnmetCondition,
             }),
+            0x61 => Ok(Self::ClanMemberJoined {
+                member: read_clan_member_info(&mut reader)?,
+            }),
+            0x62 => Ok(Self::ClanInviteError {
+                error: ClanInviteError::Failed,
+            }),
+            0x63 => Ok(Self::ClanInviteError {
+                error: ClanInviteError::NoPermission,
+            }),
+            0x64 => Ok(Self::ClanInviteError {
+                error: ClanInviteError::AlreadyInClan,
+            }),
+            0x65 => Ok(Self::ClanInviteError {
+                error: ClanInviteError::ClanFull,
+            }),
             0x71 => {
                 let id = ClanUniqueId::new(reader.read_u32()?).ok_or(PacketError::InvalidPacket)?;
                 let mark = reader.read_clan_mark_u32()?;
@@ -4274,27 +5103,8 @@ impl TryFrom<&Packet> for PacketServerClanCommand {
                 })
             }
             0x72 => {
-                let try_read_clan_member =
-                    |reader: &mut PacketReader| -> Result<ClanMemberInfo, PacketError> {
-                        let position = reader.read_clan_member_position_u8()?;
-                        let channel_id = NonZeroUsize::new(reader.read_u8()? as usize);
-                        let contribution = ClanPoints(reader.read_u32()? as u64);
-                        let level = Level::new(reader.read_u16()? as u32);
-                        let job = reader.read_u16()?;
-                        let name = reader.read_null_terminated_utf8()?.to_string();
-
-                        Ok(ClanMemberInfo {
-                            name,
-                            position,
-                            contribution,
-                            channel_id,
-                            level,
-                            job,
-                        })
-                    };
-
                 let mut members = Vec::new();
-                while let Ok(member) = try_read_clan_member(&mut reader) {
+                while let Ok(member) = read_clan_member_info(&mut reader) {
                     members.push(member);
                 }
 
@@ -4314,6 +5124,17 @@ impl TryFrom<&Packet> for PacketServerClanCommand {
                     Ok(Self::ClanMemberDisconnected { name })
                 }
             }
+            0x75 => {
+                let position = reader.read_clan_member_position_u8()?;
+                let name = reader.read_null_terminated_utf8()?.to_string();
+                Ok(Self::ClanMemberPositionChanged { name, position })
+            }
+            0x81 => Ok(Self::ClanMemberKicked {
+                name: reader.read_null_terminated_utf8()?.to_string(),
+            }),
+            0x83 => Ok(Self::ClanMemberQuit {
+                name: reader.read_null_terminated_utf8()?.to_string(),
+            }),
             _ => Err(PacketError::InvalidPacket),
         }
     }
@@ -4424,12 +5245,7 @@ impl From<&PacketServerClanCommand> for Packet {
                 writer.write_u8(0x72);
 
                 for member in members.iter() {
-                    writer.write_clan_member_position_u8(&member.position);
-                    writer.write_u8(member.channel_id.map_or(0, |value| value.get()) as u8);
-                    writer.write_u32(member.contribution.0 as u32);
-                    writer.write_u16(member.level.level as u16);
-                    writer.write_u16(member.job);
-                    writer.write_null_terminated_utf8(&member.name);
+                    write_clan_member_info(&mut writer, member);
                 }
             }
             PacketServerClanCommand::ClanMemberConnected { name, channel_id } => {
@@ -4458,8 +5274,92 @@ impl From<&PacketServerClanCommand> for Packet {
                 ClanCreateError::NoPermission => writer.write_u8(0x43),
                 ClanCreateError::UnmetCondition => writer.write_u8(0x44),
             },
+            PacketServerClanCommand::ClanInvite { inviter_name } => {
+                writer.write_u8(0x02);
+                writer.write_null_terminated_utf8(inviter_name);
+            }
+            PacketServerClanCommand::ClanInviteRejected { name } => {
+                writer.write_u8(0x04);
+                writer.write_null_terminated_utf8(name);
+            }
+            PacketServerClanCommand::ClanInviteError { error } => match error {
+                ClanInviteError::Failed => writer.write_u8(0x62),
+                ClanInviteError::NoPermission => writer.write_u8(0x63),
+                ClanInviteError::AlreadyInClan => writer.write_u8(0x64),
+                ClanInviteError::ClanFull => writer.write_u8(0x65),
+            },
+            PacketServerClanCommand::ClanMemberJoined { member } => {
+                writer.write_u8(0x61);
+                write_clan_member_info(&mut writer, member);
+            }
+            PacketServerClanCommand::ClanMemberKicked { name } => {
+                writer.write_u8(0x81);
+                writer.write_null_terminated_utf8(name);
+            }
+            PacketServerClanCommand::ClanMemberQuit { name } => {
+                writer.write_u8(0x83);
+                writer.write_null_terminated_utf8(name);
+            }
+            PacketServerClanCommand::ClanMemberPositionChanged { name, position } => {
+                writer.write_u8(0x75);
+                writer.write_clan_member_position_u8(position);
+                writer.write_null_terminated_utf8(name);
+            }
+            PacketServerClanCommand::ClanNotice { notice } => {
+                writer.write_u8(0x34);
+                writer.write_null_terminated_utf8(notice);
+            }
+            PacketServerClanCommand::CharacterLeaveClan { client_entity_id } => {
+                writer.write_u8(0x35);
+                writer.write_entity_id(*client_entity_id);
+
+                // A zero clan id removes the character from their clan
+                writer.write_u32(0);
+                writer.write_u32(0); // mark
+                writer.write_u8(0); // level
+                writer.write_u8(0); // position
+                writer.write_null_terminated_utf8("");
+            }
         }
 
         writer.into()
     }
 }
+
+#[derive(Debug)]
+pub struct PacketServerUpdateCostume {
+    pub entity_id: ClientEntityId,
+    pub costume_index: CostumeIndex,
+    pub item: Option<EquipmentItem>,
+}
+
+impl TryFrom<&Packet> for PacketServerUpdateCostume {
+    type Error = PacketError;
+
+    fn try_from(packet: &Packet) -> Result<Self, PacketError> {
+        if packet.command != ServerPackets::UpdateCostume as u16 {
+            return Err(PacketError::InvalidPacket);
+        }
+
+        let mut reader = PacketReader::from(packet);
+        let entity_id = reader.read_entity_id()?;
+        let costume_index = reader.read_costume_index_u8()?;
+        let item = reader.read_equipment_item_part(costume_index.into())?;
+
+        Ok(Self {
+            entity_id,
+            costume_index,
+            item,
+        })
+    }
+}
+
+impl From<&PacketServerUpdateCostume> for Packet {
+    fn from(packet: &PacketServerUpdateCostume) -> Self {
+        let mut writer = PacketWriter::new(ServerPackets::UpdateCostume as u16);
+        writer.write_entity_id(packet.entity_id);
+        writer.write_costume_index_u8(packet.costume_index);
+        writer.write_equipment_item_part(packet.item.as_ref());
+        writer.into()
+    }
+}
@@ -1,6 +1,8 @@
 #![allow(dead_code)]
 
+mod client_profile;
 mod packet_codec;
+pub use client_profile::{IroseClientProfile, IroseClientVersion};
 pub use packet_codec::{ClientPacketCodec, ServerPacketCodec, IROSE_112_TABLE};
 
 pub mod common_packets;
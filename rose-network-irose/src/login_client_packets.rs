@@ -1,21 +1,61 @@
 use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
 use std::convert::TryFrom;
 
 use rose_network_common::{Packet, PacketError, PacketReader, PacketWriter};
 
 #[derive(FromPrimitive)]
 pub enum ClientPackets {
+    // Sent periodically by the client to keep the connection alive
+    Alive = 0x700,
     Connect = 0x703,
     ChannelList = 0x704,
     LoginRequest = 0x708,
     SelectServer = 0x70a,
 }
 
-pub struct PacketClientConnect;
+/// How the password of a login request is encoded, chosen by the client in its connect packet.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, FromPrimitive)]
+pub enum IrosePasswordEncoding {
+    /// The md5 hex digest of the password, as sent by stock clients
+    #[default]
+    Md5 = 0,
+    /// The seeded hash of `Password::Seeded`, using the packet sequence id of the connect reply
+    /// as the seed
+    Seeded = 1,
+}
+
+#[derive(Debug)]
+pub struct PacketClientConnect {
+    pub password_encoding: IrosePasswordEncoding,
+}
+
+impl TryFrom<&Packet> for PacketClientConnect {
+    type Error = PacketError;
+
+    fn try_from(packet: &Packet) -> Result<Self, Self::Error> {
+        if packet.command != ClientPackets::Connect as u16 {
+            return Err(PacketError::InvalidPacket);
+        }
+
+        // Stock clients send an empty connect packet
+        let password_encoding = if packet.data.is_empty() {
+            IrosePasswordEncoding::Md5
+        } else {
+            let mut reader = PacketReader::from(packet);
+            FromPrimitive::from_u8(reader.read_u8()?).ok_or(PacketError::InvalidPacket)?
+        };
+
+        Ok(PacketClientConnect { password_encoding })
+    }
+}
 
 impl From<&PacketClientConnect> for Packet {
-    fn from(_: &PacketClientConnect) -> Self {
-        let writer = PacketWriter::new(ClientPackets::Connect as u16);
+    fn from(packet: &PacketClientConnect) -> Self {
+        let mut writer = PacketWriter::new(ClientPackets::Connect as u16);
+        if packet.password_encoding != IrosePasswordEncoding::Md5 {
+            writer.write_u8(packet.password_encoding as u8);
+        }
         writer.into()
     }
 }
@@ -23,7 +63,7 @@ impl From<&PacketClientConnect> for Packet {
 #[derive(Debug)]
 pub struct PacketClientLoginRequest<'a> {
     pub username: &'a str,
-    pub password_md5: &'a str,
+    pub password: &'a str,
 }
 
 impl<'a> TryFrom<&'a Packet> for PacketClientLoginRequest<'a> {
@@ -35,20 +75,17 @@ impl<'a> TryFrom<&'a Packet> for PacketClientLoginRequest<'a> {
         }
 
         let mut reader = PacketReader::from(packet);
-        let password_md5 = reader.read_fixed_length_utf8(32)?;
+        let password = reader.read_fixed_length_utf8(32)?;
         let username = reader.read_null_terminated_utf8()?;
 
-        Ok(PacketClientLoginRequest {
-            username,
-            password_md5,
-        })
+        Ok(PacketClientLoginRequest { username, password })
     }
 }
 
 impl<'a> From<&'a PacketClientLoginRequest<'a>> for Packet {
     fn from(packet: &'a PacketClientLoginRequest<'a>) -> Self {
         let mut writer = PacketWriter::new(ClientPackets::LoginRequest as u16);
-        writer.write_fixed_length_utf8(packet.password_md5, 32);
+        writer.write_fixed_length_utf8(packet.password, 32);
         writer.write_null_terminated_utf8(packet.username);
         writer.into()
     }
@@ -98,7 +135,10 @@ impl TryFrom<&Packet> for PacketClientSelectServer {
 
         let mut reader = PacketReader::from(packet);
         let server_id = reader.read_u32()? as usize;
-        let channel_id = (reader.read_u8()? - 1) as usize;
+        let channel_id = reader
+            .read_u8()?
+            .checked_sub(1)
+            .ok_or(PacketError::InvalidPacket)? as usize;
 
         Ok(PacketClientSelectServer {
             server_id,
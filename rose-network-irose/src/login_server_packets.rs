@@ -11,7 +11,7 @@ pub enum ServerPackets {
 }
 
 #[allow(dead_code)]
-#[derive(Copy, Clone, FromPrimitive)]
+#[derive(Copy, Clone, Debug, FromPrimitive)]
 pub enum ConnectionResult {
     Connect = 1,
     Accepted = 2,
@@ -19,6 +19,7 @@ pub enum ConnectionResult {
     ServerDead = 4,
 }
 
+#[derive(Debug)]
 pub struct PacketConnectionReply {
     pub status: ConnectionResult,
     pub packet_sequence_id: u32,
@@ -81,6 +82,7 @@ pub enum LoginResult {
     OutsideRegion = 11,
 }
 
+#[derive(Debug)]
 pub struct PacketServerLoginReply {
     pub result: LoginResult,
     pub rights: u16,
@@ -144,6 +146,7 @@ impl From<&PacketServerLoginReply> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerChannelListItem<'a> {
     pub id: u8,
     pub low_age: u8,
@@ -152,6 +155,7 @@ pub struct PacketServerChannelListItem<'a> {
     pub name: &'a str,
 }
 
+#[derive(Debug)]
 pub struct PacketServerChannelList<'a> {
     pub server_id: usize,
     pub channels: Vec<PacketServerChannelListItem<'a>>,
@@ -171,12 +175,13 @@ impl<'a> TryFrom<&'a Packet> for PacketServerChannelList<'a> {
 
         let mut channels = Vec::with_capacity(num_channels);
         while let Ok(id) = reader.read_u8() {
+            let id = id.checked_sub(1).ok_or(PacketError::InvalidPacket)?;
             let low_age = reader.read_u8()?;
             let high_age = reader.read_u8()?;
             let percent_full = reader.read_u16()?;
             let name = reader.read_null_terminated_utf8()?;
             channels.push(PacketServerChannelListItem {
-                id: id - 1,
+                id,
                 low_age,
                 high_age,
                 percent_full,
@@ -210,7 +215,7 @@ impl<'a> From<&PacketServerChannelList<'a>> for Packet {
 }
 
 #[allow(dead_code)]
-#[derive(Copy, Clone, FromPrimitive)]
+#[derive(Copy, Clone, Debug, FromPrimitive)]
 pub enum SelectServerResult {
     Ok = 0,
     Failed = 1,
@@ -221,6 +226,7 @@ pub enum SelectServerResult {
     NeedCharge = 6,
 }
 
+#[derive(Debug)]
 pub struct PacketServerSelectServer<'a> {
     pub result: SelectServerResult,
     pub login_token: u32,
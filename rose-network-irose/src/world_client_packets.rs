@@ -8,6 +8,8 @@ use crate::common_packets::{PacketReadCharacterGender, PacketWriteCharacterGende
 
 #[derive(FromPrimitive)]
 pub enum ClientPackets {
+    // Sent periodically by the client to keep the connection alive
+    Alive = 0x700,
     ConnectRequest = 0x70b,
     CharacterListRequest = 0x712,
     CreateCharacter = 0x713,
@@ -19,7 +21,7 @@ pub enum ClientPackets {
 #[derive(Debug)]
 pub struct PacketClientConnectRequest<'a> {
     pub login_token: u32,
-    pub password_md5: &'a str,
+    pub password: &'a str,
 }
 
 impl<'a> TryFrom<&'a Packet> for PacketClientConnectRequest<'a> {
@@ -32,10 +34,10 @@ impl<'a> TryFrom<&'a Packet> for PacketClientConnectRequest<'a> {
 
         let mut reader = PacketReader::from(packet);
         let login_token = reader.read_u32()?;
-        let password_md5 = reader.read_fixed_length_utf8(32)?;
+        let password = reader.read_fixed_length_utf8(32)?;
         Ok(PacketClientConnectRequest {
             login_token,
-            password_md5,
+            password,
         })
     }
 }
@@ -44,7 +46,7 @@ impl<'a> From<&'a PacketClientConnectRequest<'a>> for Packet {
     fn from(packet: &'a PacketClientConnectRequest<'a>) -> Self {
         let mut writer = PacketWriter::new(ClientPackets::ConnectRequest as u16);
         writer.write_u32(packet.login_token);
-        writer.write_fixed_length_utf8(packet.password_md5, 32);
+        writer.write_fixed_length_utf8(packet.password, 32);
         writer.into()
     }
 }
@@ -191,7 +193,15 @@ impl<'a> From<&'a PacketClientSelectCharacter<'a>> for Packet {
 
 #[derive(Debug)]
 pub enum PacketClientClanCommand {
+    Invite { name: String },
+    InviteAccept { inviter_name: String },
+    InviteReject { inviter_name: String },
+    Promote { name: String },
+    Demote { name: String },
+    Quit,
     GetMemberList,
+    SetNotice { notice: String },
+    Kick { name: String },
     UpdateLevelAndJob { level: Level, job: u16 },
 }
 
@@ -205,7 +215,29 @@ impl TryFrom<&Packet> for PacketClientClanCommand {
 
         let mut reader = PacketReader::from(packet);
         match reader.read_u8()? {
+            2 => Ok(PacketClientClanCommand::Invite {
+                name: reader.read_null_terminated_utf8()?.to_string(),
+            }),
+            3 => Ok(PacketClientClanCommand::InviteAccept {
+                inviter_name: reader.read_null_terminated_utf8()?.to_string(),
+            }),
+            4 => Ok(PacketClientClanCommand::InviteReject {
+                inviter_name: reader.read_null_terminated_utf8()?.to_string(),
+            }),
+            5 => Ok(PacketClientClanCommand::Promote {
+                name: reader.read_null_terminated_utf8()?.to_string(),
+            }),
+            6 => Ok(PacketClientClanCommand::Demote {
+                name: reader.read_null_terminated_utf8()?.to_string(),
+            }),
+            7 => Ok(PacketClientClanCommand::Quit),
             8 => Ok(PacketClientClanCommand::GetMemberList),
+            9 => Ok(PacketClientClanCommand::SetNotice {
+                notice: reader.read_null_terminated_utf8()?.to_string(),
+            }),
+            11 => Ok(PacketClientClanCommand::Kick {
+                name: reader.read_null_terminated_utf8()?.to_string(),
+            }),
             15 => Ok(PacketClientClanCommand::UpdateLevelAndJob {
                 level: Level::new(reader.read_u16()? as u32),
                 job: reader.read_u16()?,
@@ -219,11 +251,42 @@ impl From<&PacketClientClanCommand> for Packet {
     fn from(packet: &PacketClientClanCommand) -> Self {
         let mut writer = PacketWriter::new(ClientPackets::ClanCommand as u16);
 
-        match *packet {
+        match packet {
+            PacketClientClanCommand::Invite { name } => {
+                writer.write_u8(2);
+                writer.write_null_terminated_utf8(name);
+            }
+            PacketClientClanCommand::InviteAccept { inviter_name } => {
+                writer.write_u8(3);
+                writer.write_null_terminated_utf8(inviter_name);
+            }
+            PacketClientClanCommand::InviteReject { inviter_name } => {
+                writer.write_u8(4);
+                writer.write_null_terminated_utf8(inviter_name);
+            }
+            PacketClientClanCommand::Promote { name } => {
+                writer.write_u8(5);
+                writer.write_null_terminated_utf8(name);
+            }
+            PacketClientClanCommand::Demote { name } => {
+                writer.write_u8(6);
+                writer.write_null_terminated_utf8(name);
+            }
+            PacketClientClanCommand::Quit => {
+                writer.write_u8(7);
+            }
             PacketClientClanCommand::GetMemberList => {
                 writer.write_u8(8);
             }
-            PacketClientClanCommand::UpdateLevelAndJob { level, job } => {
+            PacketClientClanCommand::SetNotice { notice } => {
+                writer.write_u8(9);
+                writer.write_null_terminated_utf8(notice);
+            }
+            PacketClientClanCommand::Kick { name } => {
+                writer.write_u8(11);
+                writer.write_null_terminated_utf8(name);
+            }
+            &PacketClientClanCommand::UpdateLevelAndJob { level, job } => {
                 writer.write_u8(15);
                 writer.write_u16(level.level as u16);
                 writer.write_u16(job);
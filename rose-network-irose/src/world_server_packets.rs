@@ -22,7 +22,7 @@ pub enum ServerPackets {
 }
 
 #[allow(dead_code)]
-#[derive(Clone, Copy, FromPrimitive)]
+#[derive(Clone, Copy, Debug, FromPrimitive)]
 pub enum ConnectResult {
     Ok = 0,
     Failed = 1,
@@ -31,6 +31,7 @@ pub enum ConnectResult {
     AlreadyLoggedIn = 4,
 }
 
+#[derive(Debug)]
 pub struct PacketConnectionReply {
     pub result: ConnectResult,
     pub packet_sequence_id: u32,
@@ -67,6 +68,7 @@ impl From<&PacketConnectionReply> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerCharacterList {
     pub characters: Vec<CharacterListItem>,
 }
@@ -215,6 +217,7 @@ pub enum CreateCharacterResult {
     Blocked = 5,
 }
 
+#[derive(Debug)]
 pub struct PacketServerCreateCharacterReply {
     pub result: CreateCharacterResult,
     pub is_platinum: bool,
@@ -247,6 +250,7 @@ impl From<&PacketServerCreateCharacterReply> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerDeleteCharacterReply<'a> {
     pub seconds_until_delete: Option<u32>,
     pub name: &'a str,
@@ -286,6 +290,7 @@ impl<'a> From<&'a PacketServerDeleteCharacterReply<'a>> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerMoveServer<'a> {
     pub login_token: u32,
     pub packet_codec_seed: u32,
@@ -326,6 +331,7 @@ impl<'a> From<&PacketServerMoveServer<'a>> for Packet {
     }
 }
 
+#[derive(Debug)]
 pub struct PacketServerReturnToCharacterSelect {}
 
 impl From<&PacketServerReturnToCharacterSelect> for Packet {
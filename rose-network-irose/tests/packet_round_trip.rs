@@ -0,0 +1,3992 @@
+use std::{
+    num::{NonZeroU16, NonZeroUsize},
+    time::Duration,
+};
+
+use bevy::math::{Vec2, Vec3};
+use bitvec::array::BitArray;
+use proptest::{prelude::*, strategy::LazyJust};
+
+use rose_data::{
+    AbilityType, AmmoIndex, ClanMemberPosition, CostumeIndex, EquipmentIndex, EquipmentItem, Item,
+    ItemReference, ItemType, MotionId, NpcId, SkillId, StackableItem, StatusEffectId,
+    StatusEffectType, VehiclePartIndex, WarpGateId, WorldTicks, ZoneId,
+};
+use rose_data_irose::SKILL_PAGE_SIZE;
+use rose_game_common::{
+    components::{
+        ActiveQuest, ActiveStatusEffect, BasicStatType, BasicStats, CharacterDeleteTime,
+        CharacterGender, CharacterInfo, CharacterStatistics, ClanLevel, ClanMark, ClanPermissions,
+        ClanPoints, ClanUniqueId, DroppedItem, Equipment, ExperiencePoints, HealthPoints, Hotbar,
+        HotbarSlot, Inventory, InventoryPageType, ItemSlot, Level, ManaPoints, Money, MoveMode,
+        MoveSpeed, Npc, QuestState, SkillList, SkillPage, SkillPoints, SkillSlot, Stamina,
+        StatPoints, Team, UnionMembership,
+    },
+    data::Damage,
+    messages::{
+        client::{InventorySortType, NpcStoreBuyItem},
+        server::{
+            ActiveStatusEffects, CancelCastingSkillReason, CharacterClanMembership,
+            CharacterListItem, CharacterTitle, ClanApplication, ClanApplicationError,
+            ClanCreateError, ClanInviteError, ClanMemberInfo, ClanRecruitmentListing,
+            ClanVaultAction, ClanVaultError, ClanVaultLogEntry, CraftInsertGemError,
+            InspectCharacterError, LearnSkillError, LevelUpSkillError, NpcStoreTransactionError,
+            PartyMemberInfo, PartyMemberInfoOffline, PartyMemberInfoOnline,
+            PersonalStoreTransactionStatus, PickupItemDropError, SpawnCommandState,
+        },
+        ClientEntityId, PartyItemSharing, PartyRejectInviteReason, PartyXpSharing,
+    },
+};
+use rose_network_common::Packet;
+use rose_network_irose::{
+    game_client_packets::{
+        self, PacketClientAttack, PacketClientChat, PacketClientClanRecruitment, PacketClientMove,
+    },
+    game_server_packets::{self, PacketServerClanRecruitment, PacketServerSetEventStatus},
+    login_client_packets, login_server_packets, world_client_packets, world_server_packets,
+};
+
+/// Parses `packet` as each of the listed packet types, and for every type which
+/// accepts it checks that encoding then decoding again produces identical bytes.
+macro_rules! assert_packets_round_trip {
+    ($packet:expr, [$($ty:path),* $(,)?]) => {
+        $(
+            if let Ok(decoded) = <$ty>::try_from($packet) {
+                let encoded = Packet::from(&decoded);
+                let redecoded = <$ty>::try_from(&encoded).unwrap_or_else(|error| {
+                    panic!(
+                        "{} failed to parse its own encoding {:?}: {}",
+                        stringify!($ty),
+                        encoded,
+                        error
+                    )
+                });
+                let reencoded = Packet::from(&redecoded);
+                prop_assert_eq!(encoded.command, reencoded.command, "{}", stringify!($ty));
+                prop_assert_eq!(&encoded.data[..], &reencoded.data[..], "{}", stringify!($ty));
+            }
+        )*
+    };
+}
+
+/// Encodes `$expected`, decodes it again as `$ty` and checks the decoded packet is identical.
+macro_rules! assert_typed_round_trip {
+    ($ty:ty, $expected:expr) => {{
+        let expected = &$expected;
+        let packet = Packet::from(expected);
+        let decoded = <$ty>::try_from(&packet).unwrap_or_else(|error| {
+            panic!(
+                "{} failed to parse its own encoding {:?}: {}",
+                stringify!($ty),
+                packet,
+                error
+            )
+        });
+        prop_assert_eq!(
+            format!("{:?}", decoded),
+            format!("{:?}", expected),
+            "{}",
+            stringify!($ty)
+        );
+    }};
+}
+
+fn arbitrary_packet(commands: Vec<u16>) -> impl Strategy<Value = Packet> {
+    (
+        prop::sample::select(commands),
+        prop::collection::vec(any::<u8>(), 0..1024),
+    )
+        .prop_map(|(command, data)| Packet {
+            command,
+            data: data.into(),
+        })
+}
+
+fn game_client_commands() -> Vec<u16> {
+    use game_client_packets::ClientPackets;
+    vec![
+        ClientPackets::LogoutRequest as u16,
+        ClientPackets::ConnectRequest as u16,
+        ClientPackets::ReturnToCharacterSelectRequest as u16,
+        ClientPackets::QuestRequest as u16,
+        ClientPackets::JoinZone as u16,
+        ClientPackets::ReviveRequest as u16,
+        ClientPackets::SetReviveZone as u16,
+        ClientPackets::MoveCollision as u16,
+        ClientPackets::Emote as u16,
+        ClientPackets::MoveToggle as u16,
+        ClientPackets::Chat as u16,
+        ClientPackets::StopMove as u16,
+        ClientPackets::Attack as u16,
+        ClientPackets::Move as u16,
+        ClientPackets::NpcStoreTransaction as u16,
+        ClientPackets::UseItem as u16,
+        ClientPackets::DropItemFromInventory as u16,
+        ClientPackets::ChangeEquipment as u16,
+        ClientPackets::PickupItemDrop as u16,
+        ClientPackets::WarpGateRequest as u16,
+        ClientPackets::IncreaseBasicStat as u16,
+        ClientPackets::SetHotbarSlot as u16,
+        ClientPackets::ChangeAmmo as u16,
+        ClientPackets::BankOpen as u16,
+        ClientPackets::BankMoveItem as u16,
+        ClientPackets::LevelUpSkill as u16,
+        ClientPackets::CastSkillSelf as u16,
+        ClientPackets::CastSkillTargetEntity as u16,
+        ClientPackets::CastSkillTargetPosition as u16,
+        ClientPackets::CraftItem as u16,
+        ClientPackets::ChangeVehiclePart as u16,
+        ClientPackets::PersonalStoreListItems as u16,
+        ClientPackets::PersonalStoreBuyItem as u16,
+        ClientPackets::RepairItemUsingItem as u16,
+        ClientPackets::RepairItemUsingNpc as u16,
+        ClientPackets::PartyRequest as u16,
+        ClientPackets::PartyReply as u16,
+        ClientPackets::PartyUpdateRules as u16,
+        ClientPackets::ClanCommand as u16,
+        ClientPackets::ToggleSkill as u16,
+        ClientPackets::ClanVault as u16,
+        ClientPackets::ClanPermissions as u16,
+        ClientPackets::PickupNearestItemDrop as u16,
+        ClientPackets::InspectCharacter as u16,
+        ClientPackets::SetActiveTitle as u16,
+        ClientPackets::RequestCharacterStatistics as u16,
+        ClientPackets::InventoryOperation as u16,
+        ClientPackets::ChangeCostume as u16,
+        ClientPackets::ClanRecruitment as u16,
+        ClientPackets::SetAutoLoot as u16,
+    ]
+}
+
+fn game_server_commands() -> Vec<u16> {
+    use game_server_packets::ServerPackets;
+    vec![
+        ServerPackets::AnnounceChat as u16,
+        ServerPackets::LogoutResult as u16,
+        ServerPackets::ConnectReply as u16,
+        ServerPackets::SelectCharacter as u16,
+        ServerPackets::CharacterInventory as u16,
+        ServerPackets::UpdateMoneyAndInventory as u16,
+        ServerPackets::UpdateInventory as u16,
+        ServerPackets::QuestData as u16,
+        ServerPackets::UpdateMoney as u16,
+        ServerPackets::RewardMoney as u16,
+        ServerPackets::RewardItems as u16,
+        ServerPackets::UpdateAbilityValueRewardAdd as u16,
+        ServerPackets::UpdateAbilityValueRewardSet as u16,
+        ServerPackets::QuestResult as u16,
+        ServerPackets::RunNpcDeathTrigger as u16,
+        ServerPackets::JoinZone as u16,
+        ServerPackets::AdjustPosition as u16,
+        ServerPackets::ChangeNpcId as u16,
+        ServerPackets::UseEmote as u16,
+        ServerPackets::MoveToggle as u16,
+        ServerPackets::LocalChat as u16,
+        ServerPackets::Whisper as u16,
+        ServerPackets::ShoutChat as u16,
+        ServerPackets::SetEventStatus as u16,
+        ServerPackets::SpawnEntityNpc as u16,
+        ServerPackets::SpawnEntityMonster as u16,
+        ServerPackets::SpawnEntityCharacter as u16,
+        ServerPackets::RemoveEntities as u16,
+        ServerPackets::StopMoveEntity as u16,
+        ServerPackets::MoveEntityWithMoveMode as u16,
+        ServerPackets::AttackEntity as u16,
+        ServerPackets::DamageEntity as u16,
+        ServerPackets::MoveEntity as u16,
+        ServerPackets::UpdateXpStamina as u16,
+        ServerPackets::UpdateLevel as u16,
+        ServerPackets::NpcStoreTransactionError as u16,
+        ServerPackets::UseItem as u16,
+        ServerPackets::UpdateEquipment as u16,
+        ServerPackets::SpawnEntityItemDrop as u16,
+        ServerPackets::PickupItemDropResult as u16,
+        ServerPackets::Teleport as u16,
+        ServerPackets::UpdateBasicStat as u16,
+        ServerPackets::SetHotbarSlot as u16,
+        ServerPackets::UpdateAmmo as u16,
+        ServerPackets::BankOpen as u16,
+        ServerPackets::BankTransaction as u16,
+        ServerPackets::LearnSkillResult as u16,
+        ServerPackets::LevelUpSkillResult as u16,
+        ServerPackets::CastSkillSelf as u16,
+        ServerPackets::CastSkillTargetEntity as u16,
+        ServerPackets::CastSkillTargetPosition as u16,
+        ServerPackets::ApplySkillEffect as u16,
+        ServerPackets::ApplySkillDamage as u16,
+        ServerPackets::UpdateStatusEffects as u16,
+        ServerPackets::UpdateSpeed as u16,
+        ServerPackets::FinishCastingSkill as u16,
+        ServerPackets::StartCastingSkill as u16,
+        ServerPackets::CraftItem as u16,
+        ServerPackets::CancelCastingSkill as u16,
+        ServerPackets::OpenPersonalStore as u16,
+        ServerPackets::ClosePersonalStore as u16,
+        ServerPackets::PersonalStoreItemList as u16,
+        ServerPackets::PersonalStoreTransactionResult as u16,
+        ServerPackets::PersonalStoreTransactionUpdateMoneyAndInventory as u16,
+        ServerPackets::UpdateVehiclePart as u16,
+        ServerPackets::RepairedItemUsingItem as u16,
+        ServerPackets::RepairedItemUsingNpc as u16,
+        ServerPackets::UpdateItemLife as u16,
+        ServerPackets::PartyRequest as u16,
+        ServerPackets::PartyReply as u16,
+        ServerPackets::PartyMembers as u16,
+        ServerPackets::PartyMemberRewardItem as u16,
+        ServerPackets::PartyMemberUpdateInfo as u16,
+        ServerPackets::PartyUpdateRules as u16,
+        ServerPackets::ClanCommand as u16,
+        ServerPackets::ToggleSkill as u16,
+        ServerPackets::ClanVault as u16,
+        ServerPackets::ClanPermissions as u16,
+        ServerPackets::InspectCharacter as u16,
+        ServerPackets::CharacterTitle as u16,
+        ServerPackets::CharacterStatistics as u16,
+        ServerPackets::UpdateCostume as u16,
+        ServerPackets::ClanRecruitment as u16,
+    ]
+}
+
+fn login_commands() -> Vec<u16> {
+    use login_client_packets::ClientPackets;
+    use login_server_packets::ServerPackets;
+    vec![
+        ClientPackets::Connect as u16,
+        ClientPackets::ChannelList as u16,
+        ClientPackets::LoginRequest as u16,
+        ClientPackets::SelectServer as u16,
+        ServerPackets::ChannelList as u16,
+        ServerPackets::LoginReply as u16,
+        ServerPackets::SelectServer as u16,
+        ServerPackets::NetworkStatus as u16,
+    ]
+}
+
+fn world_commands() -> Vec<u16> {
+    use world_client_packets::ClientPackets;
+    use world_server_packets::ServerPackets;
+    vec![
+        ClientPackets::ConnectRequest as u16,
+        ClientPackets::CharacterListRequest as u16,
+        ClientPackets::CreateCharacter as u16,
+        ClientPackets::DeleteCharacter as u16,
+        ClientPackets::SelectCharacter as u16,
+        ClientPackets::ClanCommand as u16,
+        ServerPackets::ConnectReply as u16,
+        ServerPackets::CharacterListReply as u16,
+        ServerPackets::CreateCharacterReply as u16,
+        ServerPackets::DeleteCharacterReply as u16,
+        ServerPackets::MoveServer as u16,
+        ServerPackets::ReturnToCharacterSelect as u16,
+    ]
+}
+
+fn client_entity_id() -> impl Strategy<Value = ClientEntityId> {
+    (1..=u16::MAX).prop_map(|id| ClientEntityId(id as usize))
+}
+
+fn packet_string() -> impl Strategy<Value = String> {
+    "[^\0]{0,64}"
+}
+
+fn client_clan_recruitment() -> impl Strategy<Value = PacketClientClanRecruitment> {
+    prop_oneof![
+        packet_string().prop_map(|notice| PacketClientClanRecruitment::SetNotice { notice }),
+        packet_string().prop_map(|query| PacketClientClanRecruitment::Search { query }),
+        (packet_string(), packet_string()).prop_map(|(clan_name, message)| {
+            PacketClientClanRecruitment::Apply { clan_name, message }
+        }),
+        Just(PacketClientClanRecruitment::GetApplications),
+        (packet_string(), any::<bool>()).prop_map(|(name, accepted)| {
+            PacketClientClanRecruitment::ApplicationReply { name, accepted }
+        }),
+    ]
+}
+
+fn clan_application() -> impl Strategy<Value = ClanApplication> {
+    (packet_string(), 1..=u16::MAX, any::<u16>(), packet_string()).prop_map(
+        |(name, level, job, message)| ClanApplication {
+            name,
+            level: Level::new(level as u32),
+            job,
+            message,
+        },
+    )
+}
+
+fn assert_clan_application_eq(
+    decoded: &ClanApplication,
+    expected: &ClanApplication,
+) -> Result<(), TestCaseError> {
+    prop_assert_eq!(&decoded.name, &expected.name);
+    prop_assert_eq!(decoded.level.level, expected.level.level);
+    prop_assert_eq!(decoded.job, expected.job);
+    prop_assert_eq!(&decoded.message, &expected.message);
+    Ok(())
+}
+
+const EQUIPMENT_INDICES: [EquipmentIndex; 11] = [
+    EquipmentIndex::Face,
+    EquipmentIndex::Head,
+    EquipmentIndex::Body,
+    EquipmentIndex::Back,
+    EquipmentIndex::Hands,
+    EquipmentIndex::Feet,
+    EquipmentIndex::Weapon,
+    EquipmentIndex::SubWeapon,
+    EquipmentIndex::Necklace,
+    EquipmentIndex::Ring,
+    EquipmentIndex::Earring,
+];
+
+/// The equipment which is sent to other characters, in the order it is encoded
+const VISIBLE_EQUIPMENT_INDICES: [EquipmentIndex; 8] = [
+    EquipmentIndex::Head,
+    EquipmentIndex::Body,
+    EquipmentIndex::Hands,
+    EquipmentIndex::Feet,
+    EquipmentIndex::Face,
+    EquipmentIndex::Back,
+    EquipmentIndex::Weapon,
+    EquipmentIndex::SubWeapon,
+];
+
+const AMMO_INDICES: [AmmoIndex; 3] = [AmmoIndex::Arrow, AmmoIndex::Bullet, AmmoIndex::Throw];
+
+const VEHICLE_PART_INDICES: [VehiclePartIndex; 4] = [
+    VehiclePartIndex::Body,
+    VehiclePartIndex::Engine,
+    VehiclePartIndex::Leg,
+    VehiclePartIndex::Arms,
+];
+
+const COSTUME_INDICES: [CostumeIndex; 5] = [
+    CostumeIndex::Head,
+    CostumeIndex::Body,
+    CostumeIndex::Back,
+    CostumeIndex::Hands,
+    CostumeIndex::Feet,
+];
+
+const INVENTORY_PAGE_TYPES: [InventoryPageType; 4] = [
+    InventoryPageType::Equipment,
+    InventoryPageType::Consumables,
+    InventoryPageType::Materials,
+    InventoryPageType::Vehicles,
+];
+
+const EQUIPMENT_ITEM_TYPES: [ItemType; 10] = [
+    ItemType::Face,
+    ItemType::Head,
+    ItemType::Body,
+    ItemType::Hands,
+    ItemType::Feet,
+    ItemType::Back,
+    ItemType::Jewellery,
+    ItemType::Weapon,
+    ItemType::SubWeapon,
+    ItemType::Vehicle,
+];
+
+const STACKABLE_ITEM_TYPES: [ItemType; 4] = [
+    ItemType::Consumable,
+    ItemType::Gem,
+    ItemType::Material,
+    ItemType::Quest,
+];
+
+const CLAN_MEMBER_POSITIONS: [ClanMemberPosition; 7] = [
+    ClanMemberPosition::Penalty,
+    ClanMemberPosition::Junior,
+    ClanMemberPosition::Senior,
+    ClanMemberPosition::Veteran,
+    ClanMemberPosition::Commander,
+    ClanMemberPosition::DeputyMaster,
+    ClanMemberPosition::Master,
+];
+
+/// Every status effect type which has a flag in the irose encoding, with the status effect id
+/// the decoder assigns it
+const STATUS_EFFECTS: [(StatusEffectType, u16); 29] = [
+    (StatusEffectType::IncreaseHp, 1),
+    (StatusEffectType::IncreaseMp, 4),
+    (StatusEffectType::Poisoned, 7),
+    (StatusEffectType::IncreaseMaxHp, 12),
+    (StatusEffectType::IncreaseMaxMp, 13),
+    (StatusEffectType::IncreaseMoveSpeed, 14),
+    (StatusEffectType::DecreaseMoveSpeed, 15),
+    (StatusEffectType::IncreaseAttackSpeed, 16),
+    (StatusEffectType::DecreaseAttackSpeed, 17),
+    (StatusEffectType::IncreaseAttackPower, 18),
+    (StatusEffectType::DecreaseAttackPower, 19),
+    (StatusEffectType::IncreaseDefence, 20),
+    (StatusEffectType::DecreaseDefence, 21),
+    (StatusEffectType::IncreaseResistance, 22),
+    (StatusEffectType::DecreaseResistance, 23),
+    (StatusEffectType::IncreaseHit, 24),
+    (StatusEffectType::DecreaseHit, 25),
+    (StatusEffectType::IncreaseCritical, 26),
+    (StatusEffectType::DecreaseCritical, 27),
+    (StatusEffectType::IncreaseAvoid, 28),
+    (StatusEffectType::DecreaseAvoid, 29),
+    (StatusEffectType::Dumb, 30),
+    (StatusEffectType::Sleep, 31),
+    (StatusEffectType::Fainting, 32),
+    (StatusEffectType::Disguise, 33),
+    (StatusEffectType::Transparent, 34),
+    (StatusEffectType::ShieldDamage, 35),
+    (StatusEffectType::AdditionalDamageRate, 36),
+    (StatusEffectType::DecreaseLifeTime, 43),
+];
+
+fn finite_f32() -> impl Strategy<Value = f32> {
+    any::<f32>().prop_filter("finite", |x| x.is_finite())
+}
+
+/// A position whose z is dropped by the encoding
+fn position_xy() -> impl Strategy<Value = Vec3> {
+    (finite_f32(), finite_f32()).prop_map(|(x, y)| Vec3::new(x, y, 0.0))
+}
+
+fn vec2() -> impl Strategy<Value = Vec2> {
+    (finite_f32(), finite_f32()).prop_map(|(x, y)| Vec2::new(x, y))
+}
+
+fn password() -> impl Strategy<Value = String> {
+    "[0-9a-f]{0,32}"
+}
+
+fn item_number() -> impl Strategy<Value = usize> {
+    1..=999usize
+}
+
+fn equipment_item_full() -> impl Strategy<Value = EquipmentItem> {
+    (
+        prop::sample::select(EQUIPMENT_ITEM_TYPES.to_vec()),
+        item_number(),
+        0..512u16,
+        0..128u8,
+        0..1024u16,
+        0..16u8,
+        any::<[bool; 3]>(),
+    )
+        .prop_map(
+            |(item_type, item_number, gem, durability, life, grade, flags)| {
+                let mut item =
+                    EquipmentItem::new(ItemReference::new(item_type, item_number), durability)
+                        .unwrap();
+                item.gem = gem;
+                item.life = life;
+                item.grade = grade;
+                item.is_crafted = flags[0];
+                item.has_socket = flags[1];
+                item.is_appraised = flags[2];
+                item
+            },
+        )
+}
+
+fn stackable_item_full() -> impl Strategy<Value = StackableItem> {
+    (
+        prop::sample::select(STACKABLE_ITEM_TYPES.to_vec()),
+        item_number(),
+        1..=u32::MAX,
+    )
+        .prop_map(|(item_type, item_number, quantity)| {
+            StackableItem::new(ItemReference::new(item_type, item_number), quantity).unwrap()
+        })
+}
+
+fn item_full() -> impl Strategy<Value = Item> {
+    prop_oneof![
+        equipment_item_full().prop_map(Item::Equipment),
+        stackable_item_full().prop_map(Item::Stackable),
+    ]
+}
+
+/// The fields of an equipment item which are kept by the 4 byte equipment part encoding, the
+/// item type is implied by the slot the part is written for
+fn equipment_item_part_fields() -> impl Strategy<Value = (usize, u16, bool, u8)> {
+    (item_number(), 0..512u16, any::<bool>(), 0..16u8)
+}
+
+fn equipment_item_from_part(
+    item_type: ItemType,
+    (item_number, gem, has_socket, grade): (usize, u16, bool, u8),
+) -> EquipmentItem {
+    let mut item = EquipmentItem::new(ItemReference::new(item_type, item_number), 0).unwrap();
+    item.gem = gem;
+    item.has_socket = has_socket;
+    item.grade = grade;
+    item
+}
+
+fn equipment_item_part(item_type: ItemType) -> impl Strategy<Value = EquipmentItem> {
+    equipment_item_part_fields().prop_map(move |fields| equipment_item_from_part(item_type, fields))
+}
+
+fn equipment_visible_part() -> impl Strategy<Value = Equipment> {
+    prop::collection::vec(
+        prop::option::of(equipment_item_part_fields()),
+        VISIBLE_EQUIPMENT_INDICES.len(),
+    )
+    .prop_map(|parts| {
+        let mut equipment = Equipment::default();
+        for (index, part) in VISIBLE_EQUIPMENT_INDICES.into_iter().zip(parts) {
+            equipment.equipped_items[index] =
+                part.map(|fields| equipment_item_from_part(index.into(), fields));
+        }
+        equipment
+    })
+}
+
+/// Equipment as encoded with a full item for every equipment slot
+fn equipment_full() -> impl Strategy<Value = Equipment> {
+    prop::collection::vec(
+        prop::option::of(equipment_item_full()),
+        EQUIPMENT_INDICES.len(),
+    )
+    .prop_map(|items| {
+        let mut equipment = Equipment::default();
+        for (index, item) in EQUIPMENT_INDICES.into_iter().zip(items) {
+            equipment.equipped_items[index] = item;
+        }
+        equipment
+    })
+}
+
+fn equipment_index() -> impl Strategy<Value = EquipmentIndex> {
+    prop::sample::select(EQUIPMENT_INDICES.to_vec())
+}
+
+fn ammo_index() -> impl Strategy<Value = AmmoIndex> {
+    prop::sample::select(AMMO_INDICES.to_vec())
+}
+
+fn vehicle_part_index() -> impl Strategy<Value = VehiclePartIndex> {
+    prop::sample::select(VEHICLE_PART_INDICES.to_vec())
+}
+
+fn costume_index() -> impl Strategy<Value = CostumeIndex> {
+    prop::sample::select(COSTUME_INDICES.to_vec())
+}
+
+fn inventory_page_type() -> impl Strategy<Value = InventoryPageType> {
+    prop::sample::select(INVENTORY_PAGE_TYPES.to_vec())
+}
+
+fn item_slot() -> impl Strategy<Value = ItemSlot> {
+    prop_oneof![
+        equipment_index().prop_map(ItemSlot::Equipment),
+        (inventory_page_type(), 0..30usize)
+            .prop_map(|(page_type, slot)| ItemSlot::Inventory(page_type, slot)),
+        ammo_index().prop_map(ItemSlot::Ammo),
+        vehicle_part_index().prop_map(ItemSlot::Vehicle),
+    ]
+}
+
+fn skill_slot() -> impl Strategy<Value = SkillSlot> {
+    (0..4usize, 0..SKILL_PAGE_SIZE).prop_map(|(page, slot)| SkillSlot(page, slot))
+}
+
+fn skill_id() -> impl Strategy<Value = SkillId> {
+    (1..=u16::MAX).prop_map(|id| SkillId::new(id).unwrap())
+}
+
+fn npc_id() -> impl Strategy<Value = NpcId> {
+    (1..=u16::MAX).prop_map(|id| NpcId::new(id).unwrap())
+}
+
+fn zone_id() -> impl Strategy<Value = ZoneId> {
+    (1..=u16::MAX).prop_map(|id| ZoneId::new(id).unwrap())
+}
+
+fn hotbar_slot() -> impl Strategy<Value = HotbarSlot> {
+    prop_oneof![
+        item_slot().prop_map(HotbarSlot::Inventory),
+        (0..2048u16).prop_map(HotbarSlot::Command),
+        skill_slot().prop_map(HotbarSlot::Skill),
+        (0..2048u16).prop_map(HotbarSlot::Emote),
+        (0..2048u16).prop_map(HotbarSlot::Dialog),
+        (0..2048u16).prop_map(HotbarSlot::ClanSkill),
+    ]
+}
+
+fn basic_stat_type() -> impl Strategy<Value = BasicStatType> {
+    prop::sample::select(vec![
+        BasicStatType::Strength,
+        BasicStatType::Dexterity,
+        BasicStatType::Intelligence,
+        BasicStatType::Concentration,
+        BasicStatType::Charm,
+        BasicStatType::Sense,
+    ])
+}
+
+fn character_gender() -> impl Strategy<Value = CharacterGender> {
+    prop::sample::select(vec![CharacterGender::Male, CharacterGender::Female])
+}
+
+fn move_mode() -> impl Strategy<Value = MoveMode> {
+    prop::sample::select(vec![MoveMode::Walk, MoveMode::Run, MoveMode::Drive])
+}
+
+fn party_item_sharing() -> impl Strategy<Value = PartyItemSharing> {
+    prop::sample::select(vec![
+        PartyItemSharing::EqualLootDistribution,
+        PartyItemSharing::AcquisitionOrder,
+    ])
+}
+
+fn party_xp_sharing() -> impl Strategy<Value = PartyXpSharing> {
+    prop::sample::select(vec![
+        PartyXpSharing::EqualShare,
+        PartyXpSharing::DistributedByLevel,
+    ])
+}
+
+fn party_reject_invite_reason() -> impl Strategy<Value = PartyRejectInviteReason> {
+    prop::sample::select(vec![
+        PartyRejectInviteReason::Busy,
+        PartyRejectInviteReason::Reject,
+    ])
+}
+
+fn clan_mark() -> impl Strategy<Value = ClanMark> {
+    prop_oneof![
+        (1..=u16::MAX, 1..=u16::MAX).prop_map(|(background, foreground)| ClanMark::Premade {
+            background: NonZeroU16::new(background).unwrap(),
+            foreground: NonZeroU16::new(foreground).unwrap(),
+        }),
+        any::<u16>().prop_map(|crc16| ClanMark::Custom { crc16 }),
+    ]
+}
+
+fn clan_member_position() -> impl Strategy<Value = ClanMemberPosition> {
+    prop::sample::select(CLAN_MEMBER_POSITIONS.to_vec())
+}
+
+fn clan_permissions() -> impl Strategy<Value = ClanPermissions> {
+    prop::collection::vec(any::<bool>(), 64).prop_map(|allowed| {
+        let mut allowed = allowed.into_iter();
+        let mut permissions = ClanPermissions::default();
+        for (_, positions) in permissions.0.iter_mut() {
+            for (_, position_allowed) in positions.iter_mut() {
+                *position_allowed = allowed.next().unwrap();
+            }
+        }
+        permissions
+    })
+}
+
+/// Status effects as decoded from their flags, values are only encoded for the few status
+/// effects which have one and only in the spawn packets
+fn status_effects(with_values: bool) -> impl Strategy<Value = ActiveStatusEffects> {
+    prop::collection::vec(prop::option::of(any::<u16>()), STATUS_EFFECTS.len()).prop_map(
+        move |values| {
+            let mut status_effects = ActiveStatusEffects::default();
+            for (&(status_effect_type, id), value) in STATUS_EFFECTS.iter().zip(values) {
+                let has_value = with_values
+                    && matches!(
+                        status_effect_type,
+                        StatusEffectType::IncreaseMaxHp
+                            | StatusEffectType::IncreaseMoveSpeed
+                            | StatusEffectType::DecreaseMoveSpeed
+                            | StatusEffectType::IncreaseAttackSpeed
+                            | StatusEffectType::DecreaseAttackSpeed
+                    );
+                status_effects[status_effect_type] = value.map(|value| ActiveStatusEffect {
+                    id: StatusEffectId::new(id).unwrap(),
+                    value: if has_value { value as i32 } else { 0 },
+                });
+            }
+            status_effects
+        },
+    )
+}
+
+fn damage() -> impl Strategy<Value = Damage> {
+    (0..2048u32, any::<bool>(), any::<bool>()).prop_map(|(amount, is_critical, apply_hit_stun)| {
+        Damage {
+            amount,
+            is_critical,
+            apply_hit_stun,
+        }
+    })
+}
+
+fn spawn_command_state() -> impl Strategy<Value = SpawnCommandState> {
+    prop_oneof![
+        prop::sample::select(vec![
+            SpawnCommandState::Stop,
+            SpawnCommandState::Die,
+            SpawnCommandState::CastSkillSelf,
+            SpawnCommandState::CastSkillTargetEntity,
+            SpawnCommandState::CastSkillTargetPosition,
+            SpawnCommandState::Sit,
+            SpawnCommandState::PersonalStore,
+        ]),
+        (position_xy(), prop::option::of(client_entity_id())).prop_map(
+            |(target_position, target_entity_id)| SpawnCommandState::Move {
+                target_position,
+                target_entity_id,
+            }
+        ),
+        (position_xy(), client_entity_id()).prop_map(|(target_position, target_entity_id)| {
+            SpawnCommandState::Attack {
+                target_entity_id,
+                target_position,
+            }
+        }),
+        (position_xy(), client_entity_id()).prop_map(|(target_position, target_entity_id)| {
+            SpawnCommandState::PickupItemDrop {
+                target_entity_id,
+                target_position,
+            }
+        }),
+        position_xy().prop_map(|target_position| SpawnCommandState::RunAway { target_position }),
+    ]
+}
+
+fn party_member_info_online() -> impl Strategy<Value = PartyMemberInfoOnline> {
+    (
+        any::<u32>(),
+        packet_string(),
+        client_entity_id(),
+        any::<u16>(),
+        status_effects(false),
+        any::<u16>(),
+        any::<u16>(),
+        any::<u8>(),
+        any::<u8>(),
+        any::<u16>(),
+    )
+        .prop_map(
+            |(
+                character_id,
+                name,
+                entity_id,
+                health_points,
+                status_effects,
+                max_health,
+                concentration,
+                health_recovery,
+                mana_recovery,
+                stamina,
+            )| PartyMemberInfoOnline {
+                character_id,
+                name,
+                entity_id,
+                health_points: HealthPoints::new(health_points as i32),
+                status_effects,
+                max_health: max_health as i32,
+                concentration: concentration as i32,
+                health_recovery: health_recovery as i32,
+                mana_recovery: mana_recovery as i32,
+                stamina: Stamina::new(stamina as u32),
+            },
+        )
+}
+
+fn inventory_items() -> impl Strategy<Value = Vec<(ItemSlot, Option<Item>)>> {
+    prop::collection::vec((item_slot(), prop::option::of(item_full())), 0..8)
+}
+
+fn client_join_zone() -> impl Strategy<Value = game_client_packets::PacketClientJoinZone> {
+    (any::<u8>(), any::<u16>(), prop::option::of(any::<u32>())).prop_map(
+        |(weight_rate, z, zone_checksum)| game_client_packets::PacketClientJoinZone {
+            weight_rate,
+            z,
+            zone_checksum,
+        },
+    )
+}
+
+fn client_set_hotbar_slot() -> impl Strategy<Value = game_client_packets::PacketClientSetHotbarSlot>
+{
+    (any::<u8>(), prop::option::of(hotbar_slot())).prop_map(|(slot_index, slot)| {
+        game_client_packets::PacketClientSetHotbarSlot {
+            slot_index: slot_index as usize,
+            slot,
+        }
+    })
+}
+
+fn client_change_equipment(
+) -> impl Strategy<Value = game_client_packets::PacketClientChangeEquipment> {
+    (equipment_index(), prop::option::of(item_slot())).prop_map(|(equipment_index, item_slot)| {
+        game_client_packets::PacketClientChangeEquipment {
+            equipment_index,
+            item_slot,
+        }
+    })
+}
+
+fn client_change_vehicle_part(
+) -> impl Strategy<Value = game_client_packets::PacketClientChangeVehiclePart> {
+    (vehicle_part_index(), prop::option::of(item_slot())).prop_map(
+        |(vehicle_part_index, item_slot)| game_client_packets::PacketClientChangeVehiclePart {
+            vehicle_part_index,
+            item_slot,
+        },
+    )
+}
+
+fn client_revive_request() -> impl Strategy<Value = game_client_packets::PacketClientReviveRequest>
+{
+    any::<bool>().prop_map(|save_zone| {
+        if save_zone {
+            game_client_packets::PacketClientReviveRequest::SaveZone
+        } else {
+            game_client_packets::PacketClientReviveRequest::CurrentZone
+        }
+    })
+}
+
+fn client_quest_request() -> impl Strategy<Value = game_client_packets::PacketClientQuestRequest> {
+    use game_client_packets::PacketClientQuestRequestType;
+    (any::<bool>(), any::<u8>(), any::<u32>()).prop_map(|(do_trigger, quest_slot, quest_id)| {
+        game_client_packets::PacketClientQuestRequest {
+            request_type: if do_trigger {
+                PacketClientQuestRequestType::DoTrigger
+            } else {
+                PacketClientQuestRequestType::DeleteQuest
+            },
+            quest_slot,
+            quest_id,
+        }
+    })
+}
+
+fn client_personal_store_buy_item(
+) -> impl Strategy<Value = game_client_packets::PacketClientPersonalStoreBuyItem> {
+    (client_entity_id(), any::<u8>(), item_full()).prop_map(
+        |(store_entity_id, store_slot_index, buy_item)| {
+            game_client_packets::PacketClientPersonalStoreBuyItem {
+                store_entity_id,
+                store_slot_index: store_slot_index as usize,
+                buy_item,
+            }
+        },
+    )
+}
+
+fn client_drop_item_from_inventory(
+) -> impl Strategy<Value = game_client_packets::PacketClientDropItemFromInventory> {
+    use game_client_packets::PacketClientDropItemFromInventory;
+    prop_oneof![
+        (item_slot(), any::<u32>()).prop_map(|(item_slot, quantity)| {
+            PacketClientDropItemFromInventory::Item(item_slot, quantity)
+        }),
+        any::<u32>().prop_map(PacketClientDropItemFromInventory::Money),
+    ]
+}
+
+fn client_clan_vault() -> impl Strategy<Value = game_client_packets::PacketClientClanVault> {
+    use game_client_packets::PacketClientClanVault;
+    prop_oneof![
+        LazyJust::new(|| PacketClientClanVault::Open),
+        (item_slot(), item_full())
+            .prop_map(|(item_slot, item)| PacketClientClanVault::Deposit { item_slot, item }),
+        (any::<u16>(), item_full()).prop_map(|(vault_slot, item)| {
+            PacketClientClanVault::Withdraw {
+                vault_slot: vault_slot as usize,
+                item,
+            }
+        }),
+        LazyJust::new(|| PacketClientClanVault::GetLog),
+        any::<i64>().prop_map(|money| PacketClientClanVault::DonateMoney {
+            money: Money(money)
+        }),
+    ]
+}
+
+fn client_clan_permissions(
+) -> impl Strategy<Value = game_client_packets::PacketClientClanPermissions> {
+    use game_client_packets::PacketClientClanPermissions;
+    prop_oneof![
+        LazyJust::new(|| PacketClientClanPermissions::Get),
+        clan_permissions().prop_map(|permissions| PacketClientClanPermissions::Set { permissions }),
+    ]
+}
+
+fn client_npc_store_transaction(
+) -> impl Strategy<Value = game_client_packets::PacketClientNpcStoreTransaction> {
+    (
+        client_entity_id(),
+        prop::collection::vec((any::<u8>(), any::<u8>(), any::<u16>()), 0..8),
+        prop::collection::vec((item_slot(), any::<u16>()), 0..8),
+    )
+        .prop_map(|(npc_entity_id, buy_items, sell_items)| {
+            game_client_packets::PacketClientNpcStoreTransaction {
+                npc_entity_id,
+                buy_items: buy_items
+                    .into_iter()
+                    .map(|(tab_index, item_index, quantity)| NpcStoreBuyItem {
+                        tab_index: tab_index as usize,
+                        item_index: item_index as usize,
+                        quantity: quantity as usize,
+                    })
+                    .collect(),
+                sell_items: sell_items
+                    .into_iter()
+                    .map(|(item_slot, quantity)| (item_slot, quantity as usize))
+                    .collect(),
+            }
+        })
+}
+
+fn client_change_ammo() -> impl Strategy<Value = game_client_packets::PacketClientChangeAmmo> {
+    (ammo_index(), prop::option::of(item_slot())).prop_map(|(ammo_index, item_slot)| {
+        game_client_packets::PacketClientChangeAmmo {
+            ammo_index,
+            item_slot,
+        }
+    })
+}
+
+fn client_move_toggle() -> impl Strategy<Value = game_client_packets::PacketClientMoveToggle> {
+    use game_client_packets::PacketClientMoveToggleType;
+    (0..3u8).prop_map(|toggle_type| game_client_packets::PacketClientMoveToggle {
+        toggle_type: match toggle_type {
+            0 => PacketClientMoveToggleType::Run,
+            1 => PacketClientMoveToggleType::Sit,
+            _ => PacketClientMoveToggleType::Drive,
+        },
+    })
+}
+
+fn client_party_request() -> impl Strategy<Value = game_client_packets::PacketClientPartyRequest> {
+    use game_client_packets::PacketClientPartyRequest;
+    prop_oneof![
+        client_entity_id().prop_map(PacketClientPartyRequest::Create),
+        client_entity_id().prop_map(PacketClientPartyRequest::Invite),
+        LazyJust::new(|| PacketClientPartyRequest::Leave),
+        client_entity_id().prop_map(PacketClientPartyRequest::ChangeOwner),
+        any::<u32>().prop_map(PacketClientPartyRequest::Kick),
+        any::<u32>().prop_map(PacketClientPartyRequest::VoteKick),
+    ]
+}
+
+fn client_party_reply() -> impl Strategy<Value = game_client_packets::PacketClientPartyReply> {
+    use game_client_packets::PacketClientPartyReply;
+    prop_oneof![
+        client_entity_id().prop_map(PacketClientPartyReply::AcceptCreate),
+        client_entity_id().prop_map(PacketClientPartyReply::AcceptJoin),
+        (party_reject_invite_reason(), client_entity_id())
+            .prop_map(|(reason, entity_id)| PacketClientPartyReply::Reject(reason, entity_id)),
+        any::<bool>().prop_map(PacketClientPartyReply::VoteKick),
+    ]
+}
+
+fn client_craft_item() -> impl Strategy<Value = game_client_packets::PacketClientCraftItem> {
+    use game_client_packets::PacketClientCraftItem;
+    prop_oneof![
+        (equipment_index(), item_slot()).prop_map(|(equipment_index, item_slot)| {
+            PacketClientCraftItem::InsertGem {
+                equipment_index,
+                item_slot,
+            }
+        }),
+        (skill_slot(), item_slot()).prop_map(|(skill_slot, item_slot)| {
+            PacketClientCraftItem::SkillDisassemble {
+                skill_slot,
+                item_slot,
+            }
+        }),
+        (client_entity_id(), item_slot()).prop_map(|(npc_entity_id, item_slot)| {
+            PacketClientCraftItem::NpcDisassemble {
+                npc_entity_id,
+                item_slot,
+            }
+        }),
+        (
+            skill_slot(),
+            item_slot(),
+            prop::array::uniform3(item_slot())
+        )
+            .prop_map(|(skill_slot, item_slot, ingredients)| {
+                PacketClientCraftItem::SkillUpgradeItem {
+                    skill_slot,
+                    item_slot,
+                    ingredients,
+                }
+            }),
+        (
+            client_entity_id(),
+            item_slot(),
+            prop::array::uniform3(item_slot())
+        )
+            .prop_map(|(npc_entity_id, item_slot, ingredients)| {
+                PacketClientCraftItem::NpcUpgradeItem {
+                    npc_entity_id,
+                    item_slot,
+                    ingredients,
+                }
+            }),
+    ]
+}
+
+fn client_bank_move_item() -> impl Strategy<Value = game_client_packets::PacketClientBankMoveItem> {
+    use game_client_packets::PacketClientBankMoveItem;
+    prop_oneof![
+        (item_slot(), item_full(), any::<bool>()).prop_map(|(item_slot, item, is_premium)| {
+            PacketClientBankMoveItem::Deposit {
+                item_slot,
+                item,
+                is_premium,
+            }
+        }),
+        (any::<u8>(), item_full(), any::<bool>()).prop_map(|(bank_slot, item, is_premium)| {
+            PacketClientBankMoveItem::Withdraw {
+                bank_slot: bank_slot as usize,
+                item,
+                is_premium,
+            }
+        }),
+    ]
+}
+
+fn client_clan_command() -> impl Strategy<Value = game_client_packets::PacketClientClanCommand> {
+    (packet_string(), packet_string(), clan_mark()).prop_map(|(name, description, mark)| {
+        game_client_packets::PacketClientClanCommand::Create {
+            name,
+            description,
+            mark,
+        }
+    })
+}
+
+fn client_inspect_character(
+) -> impl Strategy<Value = game_client_packets::PacketClientInspectCharacter> {
+    use game_client_packets::PacketClientInspectCharacter;
+    prop_oneof![
+        client_entity_id().prop_map(|target_entity_id| PacketClientInspectCharacter::Inspect {
+            target_entity_id
+        }),
+        any::<bool>().prop_map(|allow_inspect| {
+            PacketClientInspectCharacter::SetAllowInspect { allow_inspect }
+        }),
+    ]
+}
+
+fn client_inventory_operation(
+) -> impl Strategy<Value = game_client_packets::PacketClientInventoryOperation> {
+    use game_client_packets::PacketClientInventoryOperation;
+    prop_oneof![
+        (
+            inventory_page_type(),
+            prop::sample::select(vec![InventorySortType::ItemType, InventorySortType::Value]),
+        )
+            .prop_map(
+                |(page_type, sort_type)| PacketClientInventoryOperation::Sort {
+                    page_type,
+                    sort_type,
+                }
+            ),
+        inventory_page_type()
+            .prop_map(|page_type| PacketClientInventoryOperation::BankDepositPage { page_type }),
+    ]
+}
+
+fn client_change_costume() -> impl Strategy<Value = game_client_packets::PacketClientChangeCostume>
+{
+    (costume_index(), prop::option::of(item_slot())).prop_map(|(costume_index, item_slot)| {
+        game_client_packets::PacketClientChangeCostume {
+            costume_index,
+            item_slot,
+        }
+    })
+}
+
+fn server_select_character(
+) -> impl Strategy<Value = game_server_packets::PacketServerSelectCharacter> {
+    let character_info = (
+        packet_string(),
+        character_gender(),
+        any::<[u8; 5]>(),
+        any::<u16>(),
+        any::<[u16; 2]>(),
+        zone_id(),
+        any::<u32>(),
+    )
+        .prop_map(
+            |(
+                name,
+                gender,
+                [birth_stone, face, hair, rank, fame],
+                job,
+                [fame_b, fame_g],
+                revive_zone_id,
+                unique_id,
+            )| {
+                CharacterInfo {
+                    name,
+                    gender,
+                    race: 0,
+                    birth_stone,
+                    job,
+                    face,
+                    hair,
+                    rank,
+                    fame,
+                    fame_b,
+                    fame_g,
+                    revive_zone_id,
+                    revive_position: Vec3::new(0.0, 0.0, 0.0),
+                    unique_id,
+                }
+            },
+        );
+    let skill_list = prop::collection::vec(
+        prop::collection::vec(prop::option::of(skill_id()), SKILL_PAGE_SIZE),
+        4,
+    )
+    .prop_map(|pages| SkillList {
+        pages: pages
+            .into_iter()
+            .enumerate()
+            .map(|(page_type, skills)| SkillPage { page_type, skills })
+            .collect(),
+    });
+    let hotbar = prop::collection::vec(prop::option::of(hotbar_slot()), 32).prop_map(|slots| {
+        let mut hotbar = Hotbar::default();
+        for (slot, hotbar_slot) in hotbar.pages.iter_mut().flatten().zip(slots) {
+            *slot = hotbar_slot;
+        }
+        hotbar
+    });
+    let union_membership =
+        (prop::option::of(1..=u8::MAX), any::<[u16; 10]>()).prop_map(|(current_union, points)| {
+            UnionMembership {
+                current_union: current_union.and_then(|union| NonZeroUsize::new(union as usize)),
+                points: points.map(|points| points as u32),
+            }
+        });
+    (
+        character_info,
+        position_xy(),
+        zone_id(),
+        equipment_visible_part(),
+        any::<[u16; 6]>(),
+        any::<[u16; 5]>(),
+        any::<u32>(),
+        skill_list,
+        hotbar,
+        union_membership,
+        any::<u16>(),
+    )
+        .prop_map(
+            |(
+                character_info,
+                position,
+                zone_id,
+                equipment,
+                [strength, dexterity, intelligence, concentration, charm, sense],
+                [health_points, mana_points, level, stat_points, skill_points],
+                experience_points,
+                skill_list,
+                hotbar,
+                union_membership,
+                stamina,
+            )| game_server_packets::PacketServerSelectCharacter {
+                character_info,
+                position,
+                zone_id,
+                equipment,
+                basic_stats: BasicStats {
+                    strength: strength as i32,
+                    dexterity: dexterity as i32,
+                    intelligence: intelligence as i32,
+                    concentration: concentration as i32,
+                    charm: charm as i32,
+                    sense: sense as i32,
+                },
+                level: Level::new(level as u32),
+                experience_points: ExperiencePoints::new(experience_points as u64),
+                skill_list,
+                hotbar,
+                health_points: HealthPoints::new(health_points as i32),
+                mana_points: ManaPoints::new(mana_points as i32),
+                stat_points: StatPoints::new(stat_points as u32),
+                skill_points: SkillPoints::new(skill_points as u32),
+                union_membership,
+                stamina: Stamina::new(stamina as u32),
+            },
+        )
+}
+
+fn server_character_inventory(
+) -> impl Strategy<Value = game_server_packets::PacketServerCharacterInventory> {
+    (
+        equipment_full(),
+        prop::collection::vec(prop::option::of(stackable_item_full()), AMMO_INDICES.len()),
+        prop::collection::vec(
+            prop::option::of(equipment_item_full()),
+            VEHICLE_PART_INDICES.len(),
+        ),
+        any::<i64>(),
+        prop::collection::vec(prop::option::of(item_full()), 4 * 30),
+    )
+        .prop_map(|(mut equipment, ammo, vehicle, money, items)| {
+            for (index, item) in AMMO_INDICES.into_iter().zip(ammo) {
+                equipment.equipped_ammo[index] = item;
+            }
+            for (index, item) in VEHICLE_PART_INDICES.into_iter().zip(vehicle) {
+                equipment.equipped_vehicle[index] = item;
+            }
+
+            let mut inventory = Inventory {
+                money: Money(money),
+                ..Default::default()
+            };
+            let slots = inventory
+                .equipment
+                .slots
+                .iter_mut()
+                .chain(inventory.consumables.slots.iter_mut())
+                .chain(inventory.materials.slots.iter_mut())
+                .chain(inventory.vehicles.slots.iter_mut());
+            for (slot, item) in slots.zip(items) {
+                *slot = item;
+            }
+
+            game_server_packets::PacketServerCharacterInventory {
+                equipment,
+                inventory,
+            }
+        })
+}
+
+fn active_quest() -> impl Strategy<Value = ActiveQuest> {
+    (
+        1..=u16::MAX,
+        any::<u32>(),
+        any::<[u16; 10]>(),
+        any::<u32>(),
+        prop::collection::vec(prop::option::of(item_full()), 6),
+    )
+        .prop_map(|(quest_id, expire_time, variables, switches, items)| {
+            let mut active_quest = ActiveQuest {
+                quest_id: quest_id as usize,
+                expire_time: Some(expire_time)
+                    .filter(|expire_time| *expire_time != 0)
+                    .map(|expire_time| WorldTicks(expire_time as u64)),
+                variables,
+                switches: BitArray::new([switches]),
+                items: Default::default(),
+            };
+            for (slot, item) in active_quest.items.iter_mut().zip(items) {
+                *slot = item;
+            }
+            active_quest
+        })
+}
+
+fn server_character_quest_data(
+) -> impl Strategy<Value = game_server_packets::PacketServerCharacterQuestData> {
+    (
+        any::<[u16; 5]>(),
+        any::<[u16; 3]>(),
+        any::<[u16; 7]>(),
+        any::<[u16; 10]>(),
+        any::<[u32; 32]>(),
+        prop::collection::vec(prop::option::of(active_quest()), 10),
+    )
+        .prop_map(
+            |(
+                episode_variables,
+                job_variables,
+                planet_variables,
+                union_variables,
+                quest_switches,
+                quests,
+            )| {
+                let mut quest_state = QuestState {
+                    episode_variables,
+                    job_variables,
+                    planet_variables,
+                    union_variables,
+                    quest_switches: BitArray::new(quest_switches),
+                    ..Default::default()
+                };
+                for (slot, quest) in quest_state.active_quests.iter_mut().zip(quests) {
+                    *slot = quest;
+                }
+                game_server_packets::PacketServerCharacterQuestData { quest_state }
+            },
+        )
+}
+
+fn server_attack_entity() -> impl Strategy<Value = game_server_packets::PacketServerAttackEntity> {
+    (
+        client_entity_id(),
+        client_entity_id(),
+        any::<u16>(),
+        finite_f32(),
+        finite_f32(),
+        any::<u16>(),
+    )
+        .prop_map(|(entity_id, target_entity_id, distance, x, y, z)| {
+            game_server_packets::PacketServerAttackEntity {
+                entity_id,
+                target_entity_id,
+                distance,
+                x,
+                y,
+                z,
+            }
+        })
+}
+
+fn server_damage_entity() -> impl Strategy<Value = game_server_packets::PacketServerDamageEntity> {
+    (
+        client_entity_id(),
+        client_entity_id(),
+        damage(),
+        any::<bool>(),
+        any::<bool>(),
+    )
+        .prop_map(
+            |(attacker_entity_id, defender_entity_id, damage, is_killed, is_immediate)| {
+                game_server_packets::PacketServerDamageEntity {
+                    attacker_entity_id,
+                    defender_entity_id,
+                    damage,
+                    is_killed,
+                    is_immediate,
+                }
+            },
+        )
+}
+
+fn server_move_entity() -> impl Strategy<Value = game_server_packets::PacketServerMoveEntity> {
+    (
+        client_entity_id(),
+        prop::option::of(client_entity_id()),
+        any::<u16>(),
+        finite_f32(),
+        finite_f32(),
+        any::<u16>(),
+        prop::option::of(move_mode()),
+    )
+        .prop_map(
+            |(entity_id, target_entity_id, distance, x, y, z, move_mode)| {
+                game_server_packets::PacketServerMoveEntity {
+                    entity_id,
+                    target_entity_id,
+                    distance,
+                    x,
+                    y,
+                    z,
+                    move_mode,
+                }
+            },
+        )
+}
+
+fn server_join_zone() -> impl Strategy<Value = game_server_packets::PacketServerJoinZone> {
+    (
+        client_entity_id(),
+        any::<[u16; 4]>(),
+        any::<[u8; 2]>(),
+        any::<[u32; 3]>(),
+        prop::option::of(any::<u32>()),
+    )
+        .prop_map(
+            |(
+                entity_id,
+                [health_points, mana_points, craft_rate, world_price_rate],
+                [town_price_rate, item_price_rate],
+                [experience_points, world_ticks, team],
+                zone_checksum,
+            )| game_server_packets::PacketServerJoinZone {
+                entity_id,
+                experience_points: ExperiencePoints::new(experience_points as u64),
+                team: Team::new(team),
+                health_points: HealthPoints::new(health_points as i32),
+                mana_points: ManaPoints::new(mana_points as i32),
+                world_ticks: WorldTicks(world_ticks as u64),
+                craft_rate: craft_rate as i32,
+                world_price_rate: world_price_rate as i32,
+                item_price_rate: item_price_rate as i32,
+                town_price_rate: town_price_rate as i32,
+                zone_checksum,
+            },
+        )
+}
+
+fn server_stop_move_entity(
+) -> impl Strategy<Value = game_server_packets::PacketServerStopMoveEntity> {
+    (client_entity_id(), finite_f32(), finite_f32(), any::<u16>()).prop_map(
+        |(entity_id, x, y, z)| game_server_packets::PacketServerStopMoveEntity {
+            entity_id,
+            x,
+            y,
+            z,
+        },
+    )
+}
+
+fn server_teleport() -> impl Strategy<Value = game_server_packets::PacketServerTeleport> {
+    (
+        client_entity_id(),
+        zone_id(),
+        finite_f32(),
+        finite_f32(),
+        any::<u8>(),
+        any::<u8>(),
+    )
+        .prop_map(|(entity_id, zone_id, x, y, run_mode, ride_mode)| {
+            game_server_packets::PacketServerTeleport {
+                entity_id,
+                zone_id,
+                x,
+                y,
+                run_mode,
+                ride_mode,
+            }
+        })
+}
+
+fn server_set_hotbar_slot() -> impl Strategy<Value = game_server_packets::PacketServerSetHotbarSlot>
+{
+    (any::<u8>(), prop::option::of(hotbar_slot())).prop_map(|(slot_index, slot)| {
+        game_server_packets::PacketServerSetHotbarSlot {
+            slot_index: slot_index as usize,
+            slot,
+        }
+    })
+}
+
+fn server_spawn_entity_item_drop(
+) -> impl Strategy<Value = game_server_packets::PacketServerSpawnEntityItemDrop> {
+    (
+        client_entity_id(),
+        prop_oneof![
+            item_full().prop_map(DroppedItem::Item),
+            (1..=u32::MAX).prop_map(|money| DroppedItem::Money(Money(money as i64))),
+        ],
+        position_xy(),
+        prop::option::of(client_entity_id()),
+        any::<u16>(),
+    )
+        .prop_map(
+            |(entity_id, dropped_item, position, owner_entity_id, remaining_time)| {
+                game_server_packets::PacketServerSpawnEntityItemDrop {
+                    entity_id,
+                    dropped_item,
+                    position,
+                    owner_entity_id,
+                    remaining_time: Duration::from_millis(remaining_time as u64),
+                }
+            },
+        )
+}
+
+fn npc() -> impl Strategy<Value = Npc> {
+    (npc_id(), any::<u16>()).prop_map(|(id, quest_index)| Npc::new(id, quest_index))
+}
+
+fn server_spawn_entity_npc(
+) -> impl Strategy<Value = game_server_packets::PacketServerSpawnEntityNpc> {
+    (
+        client_entity_id(),
+        npc(),
+        finite_f32(),
+        position_xy(),
+        any::<u32>(),
+        spawn_command_state(),
+        any::<i32>(),
+        move_mode(),
+        status_effects(true),
+        any::<i16>(),
+    )
+        .prop_map(
+            |(
+                entity_id,
+                npc,
+                direction,
+                position,
+                team,
+                spawn_command_state,
+                health,
+                move_mode,
+                status_effects,
+                event_status,
+            )| game_server_packets::PacketServerSpawnEntityNpc {
+                entity_id,
+                npc,
+                direction,
+                position,
+                team: Team::new(team),
+                spawn_command_state,
+                health: HealthPoints::new(health),
+                move_mode,
+                status_effects,
+                event_status,
+            },
+        )
+}
+
+fn server_spawn_entity_monster(
+) -> impl Strategy<Value = game_server_packets::PacketServerSpawnEntityMonster> {
+    (
+        client_entity_id(),
+        npc(),
+        position_xy(),
+        any::<u32>(),
+        any::<i32>(),
+        spawn_command_state(),
+        move_mode(),
+        status_effects(true),
+    )
+        .prop_map(
+            |(
+                entity_id,
+                npc,
+                position,
+                team,
+                health,
+                spawn_command_state,
+                move_mode,
+                status_effects,
+            )| game_server_packets::PacketServerSpawnEntityMonster {
+                entity_id,
+                npc,
+                position,
+                team: Team::new(team),
+                health: HealthPoints::new(health),
+                spawn_command_state,
+                move_mode,
+                status_effects,
+            },
+        )
+}
+
+fn character_clan_membership() -> impl Strategy<Value = CharacterClanMembership> {
+    (
+        1..=u32::MAX,
+        clan_mark(),
+        1..=u8::MAX,
+        packet_string(),
+        clan_member_position(),
+    )
+        .prop_map(
+            |(clan_unique_id, mark, level, name, position)| CharacterClanMembership {
+                clan_unique_id: ClanUniqueId::new(clan_unique_id).unwrap(),
+                mark,
+                level: ClanLevel::new(level as u32).unwrap(),
+                name,
+                position,
+            },
+        )
+}
+
+/// The character info which is sent to other characters, everything else is decoded with a
+/// fixed value
+fn spawn_character_info() -> impl Strategy<Value = CharacterInfo> {
+    (
+        packet_string(),
+        character_gender(),
+        any::<u16>(),
+        any::<u8>(),
+        any::<u8>(),
+    )
+        .prop_map(|(name, gender, job, face, hair)| CharacterInfo {
+            name,
+            gender,
+            race: 0,
+            birth_stone: 0,
+            job,
+            face,
+            hair,
+            rank: 0,
+            fame: 0,
+            fame_b: 0,
+            fame_g: 0,
+            revive_zone_id: ZoneId::new(1).unwrap(),
+            revive_position: Vec3::new(0.0, 0.0, 0.0),
+            unique_id: 0,
+        })
+}
+
+fn server_spawn_entity_character(
+) -> impl Strategy<Value = game_server_packets::PacketServerSpawnEntityCharacter> {
+    let equipment = (
+        equipment_visible_part(),
+        prop::collection::vec(
+            prop::option::of((
+                prop::sample::select(STACKABLE_ITEM_TYPES.to_vec()),
+                item_number(),
+            )),
+            AMMO_INDICES.len(),
+        ),
+        prop::collection::vec(
+            prop::option::of(equipment_item_part(ItemType::Weapon)),
+            VEHICLE_PART_INDICES.len(),
+        ),
+    )
+        .prop_map(|(mut equipment, ammo, vehicle)| {
+            for (index, item) in AMMO_INDICES.into_iter().zip(ammo) {
+                equipment.equipped_ammo[index] = item.map(|(item_type, item_number)| {
+                    StackableItem::new(ItemReference::new(item_type, item_number), 999).unwrap()
+                });
+            }
+            for (index, item) in VEHICLE_PART_INDICES.into_iter().zip(vehicle) {
+                equipment.equipped_vehicle[index] = item;
+            }
+            equipment
+        });
+    let position =
+        (finite_f32(), finite_f32(), any::<u16>()).prop_map(|(x, y, z)| Vec3::new(x, y, z as f32));
+    (
+        (
+            spawn_character_info(),
+            spawn_command_state(),
+            client_entity_id(),
+            equipment,
+            any::<i32>(),
+            any::<u8>(),
+            move_mode(),
+            any::<[u16; 2]>(),
+        ),
+        (
+            position,
+            status_effects(true),
+            any::<u32>(),
+            prop::option::of((any::<u16>(), packet_string())),
+            prop::option::of(character_clan_membership()),
+            prop::option::of(packet_string()),
+        ),
+    )
+        .prop_map(
+            |(
+                (
+                    character_info,
+                    spawn_command_state,
+                    entity_id,
+                    equipment,
+                    health,
+                    level,
+                    move_mode,
+                    [move_speed, passive_attack_speed],
+                ),
+                (position, status_effects, team, personal_store_info, clan_membership, title),
+            )| game_server_packets::PacketServerSpawnEntityCharacter {
+                character_info,
+                spawn_command_state,
+                entity_id,
+                equipment,
+                health: HealthPoints::new(health),
+                level: Level::new(level as u32),
+                move_mode,
+                move_speed: MoveSpeed::new(move_speed as f32),
+                passive_attack_speed: passive_attack_speed as i32,
+                position,
+                status_effects,
+                team: Team::new(team),
+                personal_store_info: personal_store_info.map(|(skin, title)| (skin as i32, title)),
+                clan_membership,
+                title,
+            },
+        )
+}
+
+fn server_remove_entities() -> impl Strategy<Value = game_server_packets::PacketServerRemoveEntities>
+{
+    prop::collection::vec(client_entity_id(), 1..16)
+        .prop_map(|entity_ids| game_server_packets::PacketServerRemoveEntities { entity_ids })
+}
+
+fn server_update_inventory(
+) -> impl Strategy<Value = game_server_packets::PacketServerUpdateInventory> {
+    (inventory_items(), prop::option::of(any::<i64>())).prop_map(|(items, with_money)| {
+        game_server_packets::PacketServerUpdateInventory {
+            items,
+            with_money: with_money.map(Money),
+        }
+    })
+}
+
+fn server_update_ammo() -> impl Strategy<Value = game_server_packets::PacketServerUpdateAmmo> {
+    (
+        client_entity_id(),
+        ammo_index(),
+        prop::option::of(item_number()),
+    )
+        .prop_map(|(entity_id, ammo_index, item_number)| {
+            game_server_packets::PacketServerUpdateAmmo {
+                entity_id,
+                ammo_index,
+                item: item_number.map(|item_number| {
+                    StackableItem::new(ItemReference::material(item_number), 999).unwrap()
+                }),
+            }
+        })
+}
+
+fn server_update_equipment(
+) -> impl Strategy<Value = game_server_packets::PacketServerUpdateEquipment> {
+    (
+        client_entity_id(),
+        equipment_index(),
+        prop::option::of(equipment_item_part_fields()),
+        prop::option::of(any::<u16>()),
+    )
+        .prop_map(|(entity_id, equipment_index, item, run_speed)| {
+            game_server_packets::PacketServerUpdateEquipment {
+                entity_id,
+                equipment_index,
+                item: item.map(|fields| equipment_item_from_part(equipment_index.into(), fields)),
+                run_speed,
+            }
+        })
+}
+
+fn server_update_vehicle_part(
+) -> impl Strategy<Value = game_server_packets::PacketServerUpdateVehiclePart> {
+    (
+        client_entity_id(),
+        vehicle_part_index(),
+        prop::option::of(equipment_item_part(ItemType::Vehicle)),
+        prop::option::of(any::<u16>()),
+    )
+        .prop_map(|(entity_id, vehicle_part_index, item, run_speed)| {
+            game_server_packets::PacketServerUpdateVehiclePart {
+                entity_id,
+                vehicle_part_index,
+                item,
+                run_speed,
+            }
+        })
+}
+
+fn server_update_level() -> impl Strategy<Value = game_server_packets::PacketServerUpdateLevel> {
+    (
+        client_entity_id(),
+        prop::option::of((any::<u16>(), any::<u32>(), any::<u16>(), any::<u16>())),
+    )
+        .prop_map(
+            |(entity_id, update_values)| game_server_packets::PacketServerUpdateLevel {
+                entity_id,
+                update_values: update_values.map(
+                    |(level, experience_points, stat_points, skill_points)| {
+                        (
+                            Level::new(level as u32),
+                            ExperiencePoints::new(experience_points as u64),
+                            StatPoints::new(stat_points as u32),
+                            SkillPoints::new(skill_points as u32),
+                        )
+                    },
+                ),
+            },
+        )
+}
+
+fn server_update_xp_stamina(
+) -> impl Strategy<Value = game_server_packets::PacketServerUpdateXpStamina> {
+    (
+        any::<u32>(),
+        any::<u16>(),
+        prop::option::of(client_entity_id()),
+    )
+        .prop_map(|(xp, stamina, source_entity_id)| {
+            game_server_packets::PacketServerUpdateXpStamina {
+                xp: xp as u64,
+                stamina: stamina as u32,
+                source_entity_id,
+            }
+        })
+}
+
+fn server_update_basic_stat(
+) -> impl Strategy<Value = game_server_packets::PacketServerUpdateBasicStat> {
+    (basic_stat_type(), any::<u16>()).prop_map(|(basic_stat_type, value)| {
+        game_server_packets::PacketServerUpdateBasicStat {
+            basic_stat_type,
+            value: value as i32,
+        }
+    })
+}
+
+fn server_pickup_item_drop_result(
+) -> impl Strategy<Value = game_server_packets::PacketServerPickupItemDropResult> {
+    use game_server_packets::PacketServerPickupItemDropResult;
+    prop_oneof![
+        (client_entity_id(), item_slot(), item_full()).prop_map(
+            |(drop_entity_id, item_slot, item)| PacketServerPickupItemDropResult::Item {
+                drop_entity_id,
+                item_slot,
+                item,
+            }
+        ),
+        (client_entity_id(), 1..=u32::MAX).prop_map(|(drop_entity_id, money)| {
+            PacketServerPickupItemDropResult::Money {
+                drop_entity_id,
+                money: Money(money as i64),
+            }
+        }),
+        (
+            client_entity_id(),
+            prop::sample::select(vec![
+                PickupItemDropError::NotExist,
+                PickupItemDropError::NoPermission,
+                PickupItemDropError::InventoryFull,
+            ]),
+        )
+            .prop_map(|(drop_entity_id, error)| {
+                PacketServerPickupItemDropResult::Error {
+                    drop_entity_id,
+                    error,
+                }
+            }),
+    ]
+}
+
+fn server_logout_result() -> impl Strategy<Value = game_server_packets::PacketServerLogoutResult> {
+    prop::option::of(1..=u16::MAX).prop_map(|wait_secs| {
+        game_server_packets::PacketServerLogoutResult {
+            result: match wait_secs {
+                Some(wait_secs) => Err(Duration::from_secs(wait_secs as u64)),
+                None => Ok(()),
+            },
+        }
+    })
+}
+
+fn server_quest_result() -> impl Strategy<Value = game_server_packets::PacketServerQuestResult> {
+    use game_server_packets::PacketServerQuestResultType;
+    (0..6u8, any::<u8>(), any::<u32>()).prop_map(|(result, slot, quest_id)| {
+        game_server_packets::PacketServerQuestResult {
+            result: match result {
+                0 => PacketServerQuestResultType::AddSuccess,
+                1 => PacketServerQuestResultType::AddFailed,
+                2 => PacketServerQuestResultType::DeleteSuccess,
+                3 => PacketServerQuestResultType::DeleteFailed,
+                4 => PacketServerQuestResultType::TriggerSuccess,
+                _ => PacketServerQuestResultType::TriggerFailed,
+            },
+            slot,
+            quest_id,
+        }
+    })
+}
+
+fn server_update_ability_value(
+) -> impl Strategy<Value = game_server_packets::PacketServerUpdateAbilityValue> {
+    let ability_type = prop::sample::select(vec![
+        AbilityType::Gender,
+        AbilityType::Birthstone,
+        AbilityType::Job,
+        AbilityType::Union,
+        AbilityType::Rank,
+        AbilityType::Fame,
+        AbilityType::Face,
+        AbilityType::Hair,
+        AbilityType::Strength,
+        AbilityType::Dexterity,
+        AbilityType::Intelligence,
+        AbilityType::Concentration,
+        AbilityType::Charm,
+        AbilityType::Sense,
+        AbilityType::Health,
+        AbilityType::Mana,
+        AbilityType::Attack,
+        AbilityType::Defence,
+        AbilityType::Hit,
+        AbilityType::Resistance,
+        AbilityType::Avoid,
+        AbilityType::Speed,
+        AbilityType::AttackSpeed,
+        AbilityType::Weight,
+        AbilityType::Critical,
+        AbilityType::RecoverHealth,
+        AbilityType::RecoverMana,
+        AbilityType::SaveMana,
+        AbilityType::Experience,
+        AbilityType::Level,
+        AbilityType::BonusPoint,
+        AbilityType::PvpFlag,
+        AbilityType::TeamNumber,
+        AbilityType::HeadSize,
+        AbilityType::BodySize,
+        AbilityType::Skillpoint,
+        AbilityType::MaxHealth,
+        AbilityType::MaxMana,
+        AbilityType::Money,
+    ]);
+    (any::<bool>(), ability_type, any::<i32>()).prop_map(|(is_add, ability_type, value)| {
+        game_server_packets::PacketServerUpdateAbilityValue {
+            is_add,
+            ability_type,
+            value,
+        }
+    })
+}
+
+fn server_learn_skill_result(
+) -> impl Strategy<Value = game_server_packets::PacketServerLearnSkillResult> {
+    use game_server_packets::PacketServerLearnSkillResult;
+    prop_oneof![
+        (skill_slot(), prop::option::of(skill_id()), any::<u16>()).prop_map(
+            |(skill_slot, skill_id, updated_skill_points)| {
+                PacketServerLearnSkillResult::Success {
+                    skill_slot,
+                    skill_id,
+                    updated_skill_points: SkillPoints::new(updated_skill_points as u32),
+                }
+            }
+        ),
+        prop::sample::select(vec![
+            LearnSkillError::AlreadyLearnt,
+            LearnSkillError::JobRequirement,
+            LearnSkillError::SkillRequirement,
+            LearnSkillError::AbilityRequirement,
+            LearnSkillError::Full,
+            LearnSkillError::InvalidSkillId,
+            LearnSkillError::SkillPointRequirement,
+        ])
+        .prop_map(|error| PacketServerLearnSkillResult::Error { error }),
+    ]
+}
+
+fn server_level_up_skill_result(
+) -> impl Strategy<Value = game_server_packets::PacketServerLevelUpSkillResult> {
+    use game_server_packets::PacketServerLevelUpSkillResult;
+    prop_oneof![
+        (skill_slot(), skill_id(), any::<u16>()).prop_map(
+            |(skill_slot, skill_id, skill_points)| {
+                PacketServerLevelUpSkillResult::Success {
+                    skill_slot,
+                    skill_id,
+                    skill_points: SkillPoints::new(skill_points as u32),
+                }
+            }
+        ),
+        (
+            prop::sample::select(vec![
+                LevelUpSkillError::Failed,
+                LevelUpSkillError::SkillPointRequirement,
+                LevelUpSkillError::AbilityRequirement,
+                LevelUpSkillError::JobRequirement,
+                LevelUpSkillError::SkillRequirement,
+                LevelUpSkillError::MoneyRequirement,
+            ]),
+            any::<u16>(),
+        )
+            .prop_map(|(error, skill_points)| {
+                PacketServerLevelUpSkillResult::Error {
+                    error,
+                    skill_points: SkillPoints::new(skill_points as u32),
+                }
+            }),
+    ]
+}
+
+fn personal_store_items() -> impl Strategy<Value = Vec<(u8, Item, Money)>> {
+    prop::collection::vec(
+        (any::<u8>(), item_full(), any::<u32>())
+            .prop_map(|(slot_index, item, price)| (slot_index, item, Money(price as i64))),
+        0..8,
+    )
+}
+
+fn server_personal_store_item_list(
+) -> impl Strategy<Value = game_server_packets::PacketServerPersonalStoreItemList> {
+    (personal_store_items(), personal_store_items()).prop_map(|(sell_items, buy_items)| {
+        game_server_packets::PacketServerPersonalStoreItemList {
+            sell_items,
+            buy_items,
+        }
+    })
+}
+
+fn server_personal_store_transaction_update_money_and_inventory() -> impl Strategy<
+    Value = game_server_packets::PacketServerPersonalStoreTransactionUpdateMoneyAndInventory,
+> {
+    (inventory_items(), any::<i64>()).prop_map(|(items, money)| {
+        game_server_packets::PacketServerPersonalStoreTransactionUpdateMoneyAndInventory {
+            items,
+            money: Money(money),
+        }
+    })
+}
+
+fn server_personal_store_transaction_result(
+) -> impl Strategy<Value = game_server_packets::PacketServerPersonalStoreTransactionResult> {
+    (
+        prop::sample::select(vec![
+            PersonalStoreTransactionStatus::Cancelled,
+            PersonalStoreTransactionStatus::SoldOut,
+            PersonalStoreTransactionStatus::NoMoreNeed,
+            PersonalStoreTransactionStatus::BoughtFromStore,
+            PersonalStoreTransactionStatus::SoldToStore,
+        ]),
+        client_entity_id(),
+        prop::collection::vec((any::<u8>(), prop::option::of(item_full())), 0..8),
+    )
+        .prop_map(|(status, store_entity_id, update_store_items)| {
+            game_server_packets::PacketServerPersonalStoreTransactionResult {
+                status,
+                store_entity_id,
+                update_store_items: update_store_items
+                    .into_iter()
+                    .map(|(slot_index, item)| (slot_index as usize, item))
+                    .collect(),
+            }
+        })
+}
+
+fn server_use_item() -> impl Strategy<Value = game_server_packets::PacketServerUseItem> {
+    (
+        client_entity_id(),
+        any::<u16>(),
+        prop::option::of(item_slot()),
+    )
+        .prop_map(|(entity_id, item_number, inventory_slot)| {
+            game_server_packets::PacketServerUseItem {
+                entity_id,
+                item: ItemReference::consumable(item_number as usize),
+                inventory_slot,
+            }
+        })
+}
+
+fn cast_motion_id() -> impl Strategy<Value = Option<MotionId>> {
+    prop::option::of(any::<u8>().prop_map(|id| MotionId::new(id as u16)))
+}
+
+fn server_cast_skill_self() -> impl Strategy<Value = game_server_packets::PacketServerCastSkillSelf>
+{
+    (client_entity_id(), skill_id(), cast_motion_id()).prop_map(
+        |(entity_id, skill_id, cast_motion_id)| game_server_packets::PacketServerCastSkillSelf {
+            entity_id,
+            skill_id,
+            cast_motion_id,
+        },
+    )
+}
+
+fn server_cast_skill_target_entity(
+) -> impl Strategy<Value = game_server_packets::PacketServerCastSkillTargetEntity> {
+    (
+        client_entity_id(),
+        skill_id(),
+        client_entity_id(),
+        any::<u16>(),
+        vec2(),
+        cast_motion_id(),
+    )
+        .prop_map(
+            |(
+                entity_id,
+                skill_id,
+                target_entity_id,
+                target_distance,
+                target_position,
+                cast_motion_id,
+            )| {
+                game_server_packets::PacketServerCastSkillTargetEntity {
+                    entity_id,
+                    skill_id,
+                    target_entity_id,
+                    target_distance: target_distance as f32,
+                    target_position,
+                    cast_motion_id,
+                }
+            },
+        )
+}
+
+fn server_cast_skill_target_position(
+) -> impl Strategy<Value = game_server_packets::PacketServerCastSkillTargetPosition> {
+    (client_entity_id(), skill_id(), vec2(), cast_motion_id()).prop_map(
+        |(entity_id, skill_id, target_position, cast_motion_id)| {
+            game_server_packets::PacketServerCastSkillTargetPosition {
+                entity_id,
+                skill_id,
+                target_position,
+                cast_motion_id,
+            }
+        },
+    )
+}
+
+/// The skill fields shared by the apply skill effect and damage packets, the skill id and
+/// caster intelligence are packed into 12 and 10 bits
+fn skill_effect() -> impl Strategy<Value = (ClientEntityId, ClientEntityId, i32, SkillId, [bool; 2])>
+{
+    (
+        client_entity_id(),
+        client_entity_id(),
+        0..1024i32,
+        (1..4096u16).prop_map(|id| SkillId::new(id).unwrap()),
+        any::<[bool; 2]>(),
+    )
+}
+
+fn server_apply_skill_effect(
+) -> impl Strategy<Value = game_server_packets::PacketServerApplySkillEffect> {
+    skill_effect().prop_map(
+        |(entity_id, caster_entity_id, caster_intelligence, skill_id, effect_success)| {
+            game_server_packets::PacketServerApplySkillEffect {
+                entity_id,
+                caster_entity_id,
+                caster_intelligence,
+                skill_id,
+                effect_success,
+            }
+        },
+    )
+}
+
+fn server_apply_skill_damage(
+) -> impl Strategy<Value = game_server_packets::PacketServerApplySkillDamage> {
+    (skill_effect(), damage(), any::<bool>(), any::<bool>()).prop_map(
+        |(
+            (entity_id, caster_entity_id, caster_intelligence, skill_id, effect_success),
+            damage,
+            is_killed,
+            is_immediate,
+        )| {
+            game_server_packets::PacketServerApplySkillDamage {
+                entity_id,
+                caster_entity_id,
+                caster_intelligence,
+                skill_id,
+                effect_success,
+                damage,
+                is_killed,
+                is_immediate,
+            }
+        },
+    )
+}
+
+fn server_cancel_casting_skill(
+) -> impl Strategy<Value = game_server_packets::PacketServerCancelCastingSkill> {
+    (
+        client_entity_id(),
+        prop::sample::select(vec![
+            CancelCastingSkillReason::NeedAbility,
+            CancelCastingSkillReason::NeedTarget,
+            CancelCastingSkillReason::InvalidTarget,
+        ]),
+    )
+        .prop_map(
+            |(entity_id, reason)| game_server_packets::PacketServerCancelCastingSkill {
+                entity_id,
+                reason,
+            },
+        )
+}
+
+fn server_toggle_skill() -> impl Strategy<Value = game_server_packets::PacketServerToggleSkill> {
+    (client_entity_id(), skill_id(), any::<bool>()).prop_map(|(entity_id, skill_id, enabled)| {
+        game_server_packets::PacketServerToggleSkill {
+            entity_id,
+            skill_id,
+            enabled,
+        }
+    })
+}
+
+fn server_clan_vault() -> impl Strategy<Value = game_server_packets::PacketServerClanVault> {
+    use game_server_packets::PacketServerClanVault;
+    let log_entry = (
+        packet_string(),
+        prop::sample::select(vec![ClanVaultAction::Deposit, ClanVaultAction::Withdraw]),
+        item_full(),
+        any::<u64>(),
+    )
+        .prop_map(|(name, action, item, timestamp)| ClanVaultLogEntry {
+            name,
+            action,
+            item,
+            timestamp,
+        });
+    prop_oneof![
+        prop::collection::vec((any::<u16>(), prop::option::of(item_full())), 0..8).prop_map(
+            |items| PacketServerClanVault::Items {
+                items: items
+                    .into_iter()
+                    .map(|(vault_slot, item)| (vault_slot as usize, item))
+                    .collect(),
+            }
+        ),
+        prop::collection::vec(log_entry, 0..8)
+            .prop_map(|entries| PacketServerClanVault::Log { entries }),
+        prop::sample::select(vec![
+            ClanVaultError::Failed,
+            ClanVaultError::NoPermission,
+            ClanVaultError::VaultFull,
+            ClanVaultError::InventoryFull,
+        ])
+        .prop_map(|error| PacketServerClanVault::Error { error }),
+    ]
+}
+
+fn server_inspect_character(
+) -> impl Strategy<Value = game_server_packets::PacketServerInspectCharacter> {
+    use game_server_packets::PacketServerInspectCharacter;
+    prop_oneof![
+        (
+            client_entity_id(),
+            packet_string(),
+            any::<u16>(),
+            any::<u16>(),
+            equipment_full(),
+        )
+            .prop_map(|(entity_id, name, level, job, equipment)| {
+                PacketServerInspectCharacter::Inspect {
+                    entity_id,
+                    name,
+                    level: Level::new(level as u32),
+                    job,
+                    equipment,
+                }
+            }),
+        prop::sample::select(vec![
+            InspectCharacterError::NotFound,
+            InspectCharacterError::Private,
+            InspectCharacterError::TooFrequent,
+        ])
+        .prop_map(|error| PacketServerInspectCharacter::Error { error }),
+    ]
+}
+
+fn server_character_title() -> impl Strategy<Value = game_server_packets::PacketServerCharacterTitle>
+{
+    use game_server_packets::PacketServerCharacterTitle;
+    prop_oneof![
+        (
+            prop::collection::vec((any::<u16>(), packet_string()), 0..8),
+            prop::option::of(1..=u16::MAX),
+        )
+            .prop_map(|(titles, active)| PacketServerCharacterTitle::List {
+                titles: titles
+                    .into_iter()
+                    .map(|(id, name)| CharacterTitle { id, name })
+                    .collect(),
+                active,
+            }),
+        (client_entity_id(), prop::option::of("[^\0]{1,64}"),)
+            .prop_map(|(entity_id, title)| PacketServerCharacterTitle::Update { entity_id, title }),
+    ]
+}
+
+fn server_character_statistics(
+) -> impl Strategy<Value = game_server_packets::PacketServerCharacterStatistics> {
+    (
+        any::<[u32; 4]>(),
+        any::<i64>(),
+        prop::collection::hash_map(npc_id(), any::<u32>(), 0..8),
+    )
+        .prop_map(
+            |(
+                [monster_kills, deaths, distance_walked, quests_completed],
+                money_earned,
+                npc_kills,
+            )| {
+                game_server_packets::PacketServerCharacterStatistics {
+                    statistics: CharacterStatistics {
+                        monster_kills,
+                        npc_kills,
+                        deaths,
+                        money_earned: Money(money_earned),
+                        distance_walked: distance_walked as f64,
+                        quests_completed,
+                    },
+                }
+            },
+        )
+}
+
+fn server_update_status_effects(
+) -> impl Strategy<Value = game_server_packets::PacketServerUpdateStatusEffects> {
+    (
+        client_entity_id(),
+        status_effects(false),
+        prop::collection::vec(any::<i32>(), 0..8),
+    )
+        .prop_map(|(entity_id, status_effects, updated_values)| {
+            game_server_packets::PacketServerUpdateStatusEffects {
+                entity_id,
+                status_effects,
+                updated_values,
+            }
+        })
+}
+
+fn server_npc_store_transaction_error(
+) -> impl Strategy<Value = game_server_packets::PacketServerNpcStoreTransactionError> {
+    prop::sample::select(vec![
+        NpcStoreTransactionError::PriceDifference,
+        NpcStoreTransactionError::NpcNotFound,
+        NpcStoreTransactionError::NpcTooFarAway,
+        NpcStoreTransactionError::NotEnoughMoney,
+        NpcStoreTransactionError::NotSameUnion,
+        NpcStoreTransactionError::NotEnoughUnionPoints,
+    ])
+    .prop_map(|error| game_server_packets::PacketServerNpcStoreTransactionError { error })
+}
+
+fn server_move_toggle() -> impl Strategy<Value = game_server_packets::PacketServerMoveToggle> {
+    use game_server_packets::PacketServerMoveToggleType;
+    (client_entity_id(), 0..4u8, prop::option::of(any::<u16>())).prop_map(
+        |(entity_id, move_toggle_type, run_speed)| game_server_packets::PacketServerMoveToggle {
+            entity_id,
+            move_toggle_type: match move_toggle_type {
+                0 => PacketServerMoveToggleType::Walk,
+                1 => PacketServerMoveToggleType::Run,
+                2 => PacketServerMoveToggleType::Drive,
+                _ => PacketServerMoveToggleType::Sit,
+            },
+            run_speed: run_speed.map(|run_speed| run_speed as i32),
+        },
+    )
+}
+
+fn server_party_request() -> impl Strategy<Value = game_server_packets::PacketServerPartyRequest> {
+    use game_server_packets::PacketServerPartyRequest;
+    prop_oneof![
+        client_entity_id().prop_map(PacketServerPartyRequest::Create),
+        client_entity_id().prop_map(PacketServerPartyRequest::Invite),
+    ]
+}
+
+fn server_party_reply() -> impl Strategy<Value = game_server_packets::PacketServerPartyReply> {
+    use game_server_packets::PacketServerPartyReply;
+    prop_oneof![
+        client_entity_id().prop_map(PacketServerPartyReply::AcceptCreate),
+        client_entity_id().prop_map(PacketServerPartyReply::AcceptInvite),
+        (party_reject_invite_reason(), client_entity_id()).prop_map(|(reason, entity_id)| {
+            PacketServerPartyReply::RejectInvite(reason, entity_id)
+        }),
+        LazyJust::new(|| PacketServerPartyReply::Delete),
+        client_entity_id().prop_map(PacketServerPartyReply::ChangeOwner),
+        any::<u32>().prop_map(PacketServerPartyReply::MemberKicked),
+        any::<u32>().prop_map(PacketServerPartyReply::MemberDisconnect),
+        any::<u32>().prop_map(PacketServerPartyReply::VoteKickStarted),
+        any::<u32>().prop_map(PacketServerPartyReply::VoteKickFailed),
+    ]
+}
+
+fn server_party_members() -> impl Strategy<Value = game_server_packets::PacketServerPartyMembers> {
+    use game_server_packets::PacketServerPartyMembers;
+    let member = prop_oneof![
+        party_member_info_online().prop_map(PartyMemberInfo::Online),
+        packet_string().prop_map(|name| {
+            PartyMemberInfo::Offline(PartyMemberInfoOffline {
+                character_id: 0,
+                name,
+            })
+        }),
+    ];
+    prop_oneof![
+        (
+            party_item_sharing(),
+            party_xp_sharing(),
+            any::<u32>(),
+            prop::collection::vec(member, 1..8),
+        )
+            .prop_map(
+                |(item_sharing, xp_sharing, first_character_id, mut members)| {
+                    // Character ids are unique within a party and the owner is listed first
+                    for (index, member) in members.iter_mut().enumerate() {
+                        let character_id = first_character_id.wrapping_add(index as u32);
+                        match member {
+                            PartyMemberInfo::Online(info) => info.character_id = character_id,
+                            PartyMemberInfo::Offline(info) => info.character_id = character_id,
+                        }
+                    }
+                    PacketServerPartyMembers::List {
+                        item_sharing,
+                        xp_sharing,
+                        owner_character_id: first_character_id,
+                        members,
+                    }
+                }
+            ),
+        (any::<u32>(), any::<u32>()).prop_map(|(leaver_character_id, owner_character_id)| {
+            PacketServerPartyMembers::Leave {
+                leaver_character_id,
+                owner_character_id,
+            }
+        }),
+    ]
+}
+
+fn server_party_update_rules(
+) -> impl Strategy<Value = game_server_packets::PacketServerPartyUpdateRules> {
+    (party_item_sharing(), party_xp_sharing()).prop_map(|(item_sharing, xp_sharing)| {
+        game_server_packets::PacketServerPartyUpdateRules {
+            item_sharing,
+            xp_sharing,
+        }
+    })
+}
+
+fn server_craft_item() -> impl Strategy<Value = game_server_packets::PacketServerCraftItem> {
+    use game_server_packets::PacketServerCraftItem;
+    prop_oneof![
+        prop::sample::select(vec![
+            CraftInsertGemError::NoSocket,
+            CraftInsertGemError::SocketFull,
+        ])
+        .prop_map(|error| PacketServerCraftItem::InsertGemFailed { error }),
+        inventory_items().prop_map(|items| PacketServerCraftItem::InsertGemSuccess { items }),
+    ]
+}
+
+fn server_bank_open() -> impl Strategy<Value = game_server_packets::PacketServerBankOpen> {
+    use game_server_packets::PacketServerBankOpen;
+    let bank_items = || prop::collection::vec((any::<u8>(), prop::option::of(item_full())), 0..8);
+    prop_oneof![
+        LazyJust::new(|| PacketServerBankOpen::Open),
+        bank_items().prop_map(|items| PacketServerBankOpen::SetItems { items }),
+        bank_items().prop_map(|items| PacketServerBankOpen::UpdateItems { items }),
+    ]
+}
+
+fn server_bank_transaction(
+) -> impl Strategy<Value = game_server_packets::PacketServerBankTransaction> {
+    (
+        item_slot(),
+        prop::option::of(item_full()),
+        prop::option::of(any::<i64>()),
+        any::<u16>(),
+        prop::option::of(item_full()),
+    )
+        .prop_map(
+            |(inventory_item_slot, inventory_item, inventory_money, bank_slot, bank_item)| {
+                game_server_packets::PacketServerBankTransaction {
+                    inventory_item_slot,
+                    inventory_item,
+                    inventory_money: inventory_money.map(Money),
+                    bank_slot: bank_slot as usize,
+                    bank_item,
+                }
+            },
+        )
+}
+
+fn server_repaired_item_using_npc(
+) -> impl Strategy<Value = game_server_packets::PacketServerRepairedItemUsingNpc> {
+    (item_slot(), item_full(), any::<i64>()).prop_map(|(item_slot, item, updated_money)| {
+        game_server_packets::PacketServerRepairedItemUsingNpc {
+            item_slot,
+            item,
+            updated_money: Money(updated_money),
+        }
+    })
+}
+
+fn clan_member_info() -> impl Strategy<Value = ClanMemberInfo> {
+    (
+        packet_string(),
+        clan_member_position(),
+        any::<u32>(),
+        any::<u8>(),
+        any::<u16>(),
+        any::<u16>(),
+    )
+        .prop_map(
+            |(name, position, contribution, channel_id, level, job)| ClanMemberInfo {
+                name,
+                position,
+                contribution: ClanPoints(contribution as u64),
+                channel_id: NonZeroUsize::new(channel_id as usize),
+                level: Level::new(level as u32),
+                job,
+            },
+        )
+}
+
+fn clan_unique_id() -> impl Strategy<Value = ClanUniqueId> {
+    (1..=u32::MAX).prop_map(|id| ClanUniqueId::new(id).unwrap())
+}
+
+fn clan_level() -> impl Strategy<Value = ClanLevel> {
+    (1..=u8::MAX).prop_map(|level| ClanLevel::new(level as u32).unwrap())
+}
+
+fn clan_skills() -> impl Strategy<Value = Vec<SkillId>> {
+    prop::collection::vec(skill_id(), 0..=20)
+}
+
+fn server_clan_command() -> impl Strategy<Value = game_server_packets::PacketServerClanCommand> {
+    use game_server_packets::PacketServerClanCommand;
+    let clan_info = (
+        (
+            clan_unique_id(),
+            packet_string(),
+            packet_string(),
+            clan_mark(),
+        ),
+        (
+            clan_level(),
+            any::<u32>(),
+            any::<i64>(),
+            clan_member_position(),
+            any::<u32>(),
+            clan_skills(),
+        ),
+    )
+        .prop_map(
+            |(
+                (id, name, description, mark),
+                (level, points, money, position, contribution, skills),
+            )| PacketServerClanCommand::ClanInfo {
+                id,
+                name,
+                description,
+                mark,
+                level,
+                points: ClanPoints(points as u64),
+                money: Money(money),
+                position,
+                contribution: ClanPoints(contribution as u64),
+                skills,
+            },
+        );
+    let clan_update_info = (
+        clan_unique_id(),
+        clan_mark(),
+        clan_level(),
+        any::<u32>(),
+        any::<i64>(),
+        clan_skills(),
+    )
+        .prop_map(|(id, mark, level, points, money, skills)| {
+            PacketServerClanCommand::ClanUpdateInfo {
+                id,
+                mark,
+                level,
+                points: ClanPoints(points as u64),
+                money: Money(money),
+                skills,
+            }
+        });
+    let character_update_clan = (
+        client_entity_id(),
+        clan_unique_id(),
+        packet_string(),
+        clan_mark(),
+        clan_level(),
+        clan_member_position(),
+    )
+        .prop_map(|(client_entity_id, id, name, mark, level, position)| {
+            PacketServerClanCommand::CharacterUpdateClan {
+                client_entity_id,
+                id,
+                name,
+                mark,
+                level,
+                position,
+            }
+        });
+    let member_connection =
+        (packet_string(), prop::option::of(1..=u8::MAX)).prop_map(|(name, channel_id)| {
+            match channel_id {
+                Some(channel_id) => PacketServerClanCommand::ClanMemberConnected {
+                    name,
+                    channel_id: NonZeroUsize::new(channel_id as usize).unwrap(),
+                },
+                None => PacketServerClanCommand::ClanMemberDisconnected { name },
+            }
+        });
+    let errors = prop_oneof![
+        prop::sample::select(vec![
+            ClanCreateError::Failed,
+            ClanCreateError::NameExists,
+            ClanCreateError::NoPermission,
+            ClanCreateError::UnmetCondition,
+        ])
+        .prop_map(|error| PacketServerClanCommand::ClanCreateError { error }),
+        prop::sample::select(vec![
+            ClanInviteError::Failed,
+            ClanInviteError::NoPermission,
+            ClanInviteError::AlreadyInClan,
+            ClanInviteError::ClanFull,
+        ])
+        .prop_map(|error| PacketServerClanCommand::ClanInviteError { error }),
+    ];
+    let members = prop_oneof![
+        prop::collection::vec(clan_member_info(), 0..8)
+            .prop_map(|members| PacketServerClanCommand::ClanMemberList { members }),
+        clan_member_info().prop_map(|member| PacketServerClanCommand::ClanMemberJoined { member }),
+        (packet_string(), clan_member_position()).prop_map(|(name, position)| {
+            PacketServerClanCommand::ClanMemberPositionChanged { name, position }
+        }),
+        client_entity_id().prop_map(|client_entity_id| {
+            PacketServerClanCommand::CharacterLeaveClan { client_entity_id }
+        }),
+    ];
+    let names = (0..5u8, packet_string()).prop_map(|(command, name)| match command {
+        0 => PacketServerClanCommand::ClanInvite { inviter_name: name },
+        1 => PacketServerClanCommand::ClanInviteRejected { name },
+        2 => PacketServerClanCommand::ClanMemberKicked { name },
+        3 => PacketServerClanCommand::ClanMemberQuit { name },
+        _ => PacketServerClanCommand::ClanNotice { notice: name },
+    });
+    prop_oneof![
+        clan_info,
+        clan_update_info,
+        character_update_clan,
+        member_connection,
+        errors,
+        members,
+        names,
+    ]
+}
+
+fn server_update_costume() -> impl Strategy<Value = game_server_packets::PacketServerUpdateCostume>
+{
+    (
+        client_entity_id(),
+        costume_index(),
+        prop::option::of(equipment_item_part_fields()),
+    )
+        .prop_map(|(entity_id, costume_index, item)| {
+            game_server_packets::PacketServerUpdateCostume {
+                entity_id,
+                costume_index,
+                item: item.map(|fields| equipment_item_from_part(costume_index.into(), fields)),
+            }
+        })
+}
+
+fn login_server_login_reply() -> impl Strategy<Value = login_server_packets::PacketServerLoginReply>
+{
+    use login_server_packets::{LoginResult, PacketServerLoginReply};
+    prop_oneof![
+        (
+            any::<u16>(),
+            any::<u16>(),
+            prop::collection::vec((any::<u32>(), packet_string()), 0..8),
+        )
+            .prop_map(|(rights, pay_type, servers)| PacketServerLoginReply {
+                result: LoginResult::Ok,
+                rights,
+                pay_type,
+                servers,
+            }),
+        (
+            prop::sample::select(vec![
+                LoginResult::Failed,
+                LoginResult::UnknownAccount,
+                LoginResult::InvalidPassword,
+                LoginResult::AlreadyLoggedIn,
+                LoginResult::RefusedAccount,
+                LoginResult::NeedCharge,
+                LoginResult::NoRightToConnect,
+                LoginResult::TooManyUser,
+                LoginResult::NoRealName,
+                LoginResult::InvalidVersion,
+                LoginResult::OutsideRegion,
+            ]),
+            any::<u16>(),
+            any::<u16>(),
+        )
+            .prop_map(|(result, rights, pay_type)| PacketServerLoginReply {
+                result,
+                rights,
+                pay_type,
+                servers: Vec::new(),
+            }),
+    ]
+}
+
+fn login_select_server_result() -> impl Strategy<Value = login_server_packets::SelectServerResult> {
+    use login_server_packets::SelectServerResult;
+    prop::sample::select(vec![
+        SelectServerResult::Ok,
+        SelectServerResult::Failed,
+        SelectServerResult::Full,
+        SelectServerResult::InvalidChannel,
+        SelectServerResult::InactiveChannel,
+        SelectServerResult::InvalidAge,
+        SelectServerResult::NeedCharge,
+    ])
+}
+
+fn world_client_clan_command(
+) -> impl Strategy<Value = world_client_packets::PacketClientClanCommand> {
+    use world_client_packets::PacketClientClanCommand;
+    prop_oneof![
+        (0..7u8, packet_string()).prop_map(|(command, name)| match command {
+            0 => PacketClientClanCommand::Invite { name },
+            1 => PacketClientClanCommand::InviteAccept { inviter_name: name },
+            2 => PacketClientClanCommand::InviteReject { inviter_name: name },
+            3 => PacketClientClanCommand::Promote { name },
+            4 => PacketClientClanCommand::Demote { name },
+            5 => PacketClientClanCommand::SetNotice { notice: name },
+            _ => PacketClientClanCommand::Kick { name },
+        }),
+        LazyJust::new(|| PacketClientClanCommand::Quit),
+        LazyJust::new(|| PacketClientClanCommand::GetMemberList),
+        (any::<u16>(), any::<u16>()).prop_map(|(level, job)| {
+            PacketClientClanCommand::UpdateLevelAndJob {
+                level: Level::new(level as u32),
+                job,
+            }
+        }),
+    ]
+}
+
+/// The order equipment is listed in a character list item
+const CHARACTER_LIST_EQUIPMENT_INDICES: [EquipmentIndex; 8] = [
+    EquipmentIndex::Head,
+    EquipmentIndex::Body,
+    EquipmentIndex::Hands,
+    EquipmentIndex::Feet,
+    EquipmentIndex::Face,
+    EquipmentIndex::Back,
+    EquipmentIndex::SubWeapon,
+    EquipmentIndex::Weapon,
+];
+
+fn character_list_item() -> impl Strategy<Value = CharacterListItem> {
+    (
+        spawn_character_info(),
+        any::<u16>(),
+        prop::option::of(1..=u32::MAX / 2),
+        prop::collection::vec(
+            prop::option::of((item_number(), any::<u8>())),
+            CHARACTER_LIST_EQUIPMENT_INDICES.len(),
+        ),
+    )
+        .prop_map(|(info, level, delete_seconds, items)| {
+            let mut equipment = Equipment::default();
+            for (index, item) in CHARACTER_LIST_EQUIPMENT_INDICES.into_iter().zip(items) {
+                equipment.equipped_items[index] = item.map(|(item_number, grade)| {
+                    let mut item =
+                        EquipmentItem::new(ItemReference::new(index.into(), item_number), 0)
+                            .unwrap();
+                    item.grade = grade;
+                    item
+                });
+            }
+            CharacterListItem {
+                info,
+                level: Level::new(level as u32),
+                delete_time: delete_seconds.map(CharacterDeleteTime::from_seconds_remaining),
+                equipment,
+            }
+        })
+}
+
+#[test]
+fn game_connection_reply_results() {
+    use game_server_packets::{ConnectResult, PacketConnectionReply};
+    for result in [
+        ConnectResult::Ok,
+        ConnectResult::Failed,
+        ConnectResult::TimeOut,
+        ConnectResult::InvalidPassword,
+        ConnectResult::AlreadyLoggedIn,
+    ] {
+        let packet = Packet::from(&PacketConnectionReply {
+            result,
+            packet_sequence_id: 123,
+            pay_flags: 0xff,
+        });
+        let decoded = PacketConnectionReply::try_from(&packet).unwrap();
+        assert_eq!(decoded.result as u8, result as u8);
+        assert_eq!(decoded.packet_sequence_id, 123);
+        assert_eq!(decoded.pay_flags, 0xff);
+    }
+}
+
+#[test]
+fn world_connection_reply_results() {
+    use world_server_packets::{ConnectResult, PacketConnectionReply};
+    for result in [
+        ConnectResult::Ok,
+        ConnectResult::Failed,
+        ConnectResult::TimeOut,
+        ConnectResult::InvalidPassword,
+        ConnectResult::AlreadyLoggedIn,
+    ] {
+        let packet = Packet::from(&PacketConnectionReply {
+            result,
+            packet_sequence_id: 123,
+            pay_flags: 0xff,
+        });
+        let decoded = PacketConnectionReply::try_from(&packet).unwrap();
+        assert_eq!(decoded.result as u8, result as u8);
+        assert_eq!(decoded.packet_sequence_id, 123);
+        assert_eq!(decoded.pay_flags, 0xff);
+    }
+}
+
+#[test]
+fn server_clan_recruitment_errors() {
+    for error in [
+        ClanApplicationError::Failed,
+        ClanApplicationError::NoPermission,
+        ClanApplicationError::NotRecruiting,
+        ClanApplicationError::AlreadyInClan,
+        ClanApplicationError::AlreadyApplied,
+        ClanApplicationError::ClanFull,
+        ClanApplicationError::ApplicantOffline,
+        ClanApplicationError::TooLong,
+    ] {
+        let packet = Packet::from(&PacketServerClanRecruitment::Error {
+            error: error.clone(),
+        });
+        match PacketServerClanRecruitment::try_from(&packet).unwrap() {
+            PacketServerClanRecruitment::Error { error: decoded } => {
+                assert_eq!(format!("{:?}", decoded), format!("{:?}", error));
+            }
+            _ => panic!("Decoded wrong clan recruitment packet for {:?}", error),
+        }
+    }
+}
+
+#[test]
+fn game_client_unit_packets() -> Result<(), TestCaseError> {
+    use game_client_packets::{
+        PacketClientBankOpen, PacketClientPickupNearestItemDrop,
+        PacketClientRequestCharacterStatistics, PacketClientSetReviveZone,
+    };
+    assert_typed_round_trip!(PacketClientBankOpen, PacketClientBankOpen {});
+    assert_typed_round_trip!(
+        PacketClientPickupNearestItemDrop,
+        PacketClientPickupNearestItemDrop
+    );
+    assert_typed_round_trip!(
+        PacketClientRequestCharacterStatistics,
+        PacketClientRequestCharacterStatistics
+    );
+    assert_typed_round_trip!(PacketClientSetReviveZone, PacketClientSetReviveZone);
+    assert_typed_round_trip!(
+        world_client_packets::PacketClientCharacterList,
+        world_client_packets::PacketClientCharacterList {}
+    );
+    Ok(())
+}
+
+#[test]
+fn login_client_connect() -> Result<(), TestCaseError> {
+    use login_client_packets::{IrosePasswordEncoding, PacketClientConnect};
+    for password_encoding in [IrosePasswordEncoding::Md5, IrosePasswordEncoding::Seeded] {
+        assert_typed_round_trip!(
+            PacketClientConnect,
+            PacketClientConnect { password_encoding }
+        );
+    }
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn game_client_packets_round_trip(packet in arbitrary_packet(game_client_commands())) {
+        use game_client_packets::*;
+        assert_packets_round_trip!(&packet, [
+            PacketClientAttack,
+            PacketClientBankMoveItem,
+            PacketClientBankOpen,
+            PacketClientCastSkillSelf,
+            PacketClientCastSkillTargetEntity,
+            PacketClientCastSkillTargetPosition,
+            PacketClientChangeAmmo,
+            PacketClientChangeCostume,
+            PacketClientChangeEquipment,
+            PacketClientChangeVehiclePart,
+            PacketClientChat,
+            PacketClientClanCommand,
+            PacketClientClanPermissions,
+            PacketClientClanRecruitment,
+            PacketClientClanVault,
+            PacketClientConnectRequest,
+            PacketClientCraftItem,
+            PacketClientDropItemFromInventory,
+            PacketClientEmote,
+            PacketClientIncreaseBasicStat,
+            PacketClientInspectCharacter,
+            PacketClientInventoryOperation,
+            PacketClientJoinZone,
+            PacketClientLevelUpSkill,
+            PacketClientMove,
+            PacketClientMoveCollision,
+            PacketClientMoveToggle,
+            PacketClientNpcStoreTransaction,
+            PacketClientPartyReply,
+            PacketClientPartyRequest,
+            PacketClientPartyUpdateRules,
+            PacketClientPersonalStoreBuyItem,
+            PacketClientPersonalStoreListItems,
+            PacketClientPickupItemDrop,
+            PacketClientPickupNearestItemDrop,
+            PacketClientQuestRequest,
+            PacketClientRepairItemUsingItem,
+            PacketClientRepairItemUsingNpc,
+            PacketClientRequestCharacterStatistics,
+            PacketClientReviveRequest,
+            PacketClientSetActiveTitle,
+            PacketClientSetAutoLoot,
+            PacketClientSetHotbarSlot,
+            PacketClientSetReviveZone,
+            PacketClientToggleSkill,
+            PacketClientUseItem,
+            PacketClientWarpGateRequest,
+        ]);
+    }
+
+    #[test]
+    fn game_server_packets_round_trip(packet in arbitrary_packet(game_server_commands())) {
+        use game_server_packets::*;
+        assert_packets_round_trip!(&packet, [
+            PacketConnectionReply,
+            PacketServerAdjustPosition,
+            PacketServerAnnounceChat,
+            PacketServerApplySkillDamage,
+            PacketServerApplySkillEffect,
+            PacketServerAttackEntity,
+            PacketServerBankOpen,
+            PacketServerBankTransaction,
+            PacketServerCancelCastingSkill,
+            PacketServerCastSkillSelf,
+            PacketServerCastSkillTargetEntity,
+            PacketServerCastSkillTargetPosition,
+            PacketServerChangeNpcId,
+            PacketServerCharacterInventory,
+            PacketServerCharacterQuestData,
+            PacketServerCharacterStatistics,
+            PacketServerCharacterTitle,
+            PacketServerClanCommand,
+            PacketServerClanPermissions,
+            PacketServerClanRecruitment,
+            PacketServerClanVault,
+            PacketServerClosePersonalStore,
+            PacketServerCraftItem,
+            PacketServerDamageEntity,
+            PacketServerFinishCastingSkill,
+            PacketServerInspectCharacter,
+            PacketServerJoinZone,
+            PacketServerLearnSkillResult,
+            PacketServerLevelUpSkillResult,
+            PacketServerLocalChat,
+            PacketServerLogoutResult,
+            PacketServerMoveEntity,
+            PacketServerMoveToggle,
+            PacketServerNpcStoreTransactionError,
+            PacketServerOpenPersonalStore,
+            PacketServerPartyMemberRewardItem,
+            PacketServerPartyMemberUpdateInfo,
+            PacketServerPartyMembers,
+            PacketServerPartyReply,
+            PacketServerPartyRequest,
+            PacketServerPartyUpdateRules,
+            PacketServerPersonalStoreItemList,
+            PacketServerPersonalStoreTransactionResult,
+            PacketServerPersonalStoreTransactionUpdateMoneyAndInventory,
+            PacketServerPickupItemDropResult,
+            PacketServerQuestResult,
+            PacketServerRemoveEntities,
+            PacketServerRepairedItemUsingNpc,
+            PacketServerRewardItems,
+            PacketServerRewardMoney,
+            PacketServerRunNpcDeathTrigger,
+            PacketServerSelectCharacter,
+            PacketServerSetEventStatus,
+            PacketServerSetHotbarSlot,
+            PacketServerShoutChat,
+            PacketServerSpawnEntityCharacter,
+            PacketServerSpawnEntityItemDrop,
+            PacketServerSpawnEntityMonster,
+            PacketServerSpawnEntityNpc,
+            PacketServerStartCastingSkill,
+            PacketServerStopMoveEntity,
+            PacketServerTeleport,
+            PacketServerToggleSkill,
+            PacketServerUpdateAbilityValue,
+            PacketServerUpdateAmmo,
+            PacketServerUpdateBasicStat,
+            PacketServerUpdateCostume,
+            PacketServerUpdateEquipment,
+            PacketServerUpdateInventory,
+            PacketServerUpdateItemLife,
+            PacketServerUpdateLevel,
+            PacketServerUpdateMoney,
+            PacketServerUpdateSpeed,
+            PacketServerUpdateStatusEffects,
+            PacketServerUpdateVehiclePart,
+            PacketServerUpdateXpStamina,
+            PacketServerUseEmote,
+            PacketServerUseItem,
+            PacketServerWhisper,
+        ]);
+    }
+
+    #[test]
+    fn login_packets_round_trip(packet in arbitrary_packet(login_commands())) {
+        assert_packets_round_trip!(&packet, [
+            login_client_packets::PacketClientChannelList,
+            login_client_packets::PacketClientConnect,
+            login_client_packets::PacketClientLoginRequest,
+            login_client_packets::PacketClientSelectServer,
+            login_server_packets::PacketConnectionReply,
+            login_server_packets::PacketServerChannelList,
+            login_server_packets::PacketServerLoginReply,
+            login_server_packets::PacketServerSelectServer,
+        ]);
+    }
+
+    #[test]
+    fn world_packets_round_trip(packet in arbitrary_packet(world_commands())) {
+        assert_packets_round_trip!(&packet, [
+            world_client_packets::PacketClientCharacterList,
+            world_client_packets::PacketClientClanCommand,
+            world_client_packets::PacketClientConnectRequest,
+            world_client_packets::PacketClientCreateCharacter,
+            world_client_packets::PacketClientDeleteCharacter,
+            world_client_packets::PacketClientSelectCharacter,
+            world_server_packets::PacketConnectionReply,
+            world_server_packets::PacketServerCharacterList,
+            world_server_packets::PacketServerCreateCharacterReply,
+            world_server_packets::PacketServerDeleteCharacterReply,
+            world_server_packets::PacketServerMoveServer,
+        ]);
+    }
+
+    #[test]
+    fn client_move(
+        target_entity_id in prop::option::of(client_entity_id()),
+        x in any::<f32>().prop_filter("finite", |x| x.is_finite()),
+        y in any::<f32>().prop_filter("finite", |y| y.is_finite()),
+        z in any::<u16>(),
+    ) {
+        let packet = Packet::from(&PacketClientMove { target_entity_id, x, y, z });
+        let decoded = PacketClientMove::try_from(&packet).unwrap();
+        prop_assert_eq!(decoded.target_entity_id, target_entity_id);
+        prop_assert_eq!(decoded.x, x);
+        prop_assert_eq!(decoded.y, y);
+        prop_assert_eq!(decoded.z, z);
+    }
+
+    #[test]
+    fn client_attack(target_entity_id in client_entity_id()) {
+        let packet = Packet::from(&PacketClientAttack { target_entity_id });
+        let decoded = PacketClientAttack::try_from(&packet).unwrap();
+        prop_assert_eq!(decoded.target_entity_id, target_entity_id);
+    }
+
+    #[test]
+    fn client_chat(text in "[^\0]{0,256}") {
+        let packet = Packet::from(&PacketClientChat { text: &text });
+        let decoded = PacketClientChat::try_from(&packet).unwrap();
+        prop_assert_eq!(decoded.text, text.as_str());
+    }
+
+    #[test]
+    fn server_set_event_status(
+        entity_id in client_entity_id(),
+        event_status in any::<i16>(),
+    ) {
+        let packet = Packet::from(&PacketServerSetEventStatus {
+            entity_id,
+            event_status,
+        });
+        let decoded = PacketServerSetEventStatus::try_from(&packet).unwrap();
+        prop_assert_eq!(decoded.entity_id, entity_id);
+        prop_assert_eq!(decoded.event_status, event_status);
+    }
+
+    #[test]
+    fn client_clan_recruitment_round_trip(expected in client_clan_recruitment()) {
+        let packet = Packet::from(&expected);
+        let decoded = PacketClientClanRecruitment::try_from(&packet).unwrap();
+        prop_assert_eq!(format!("{:?}", decoded), format!("{:?}", expected));
+    }
+
+    #[test]
+    fn server_clan_recruitment_list(
+        listings in prop::collection::vec(
+            (packet_string(), 1..=u8::MAX, any::<u16>(), packet_string()),
+            0..8,
+        ),
+    ) {
+        let listings: Vec<ClanRecruitmentListing> = listings
+            .into_iter()
+            .map(|(name, level, member_count, notice)| ClanRecruitmentListing {
+                name,
+                level: ClanLevel::new(level as u32).unwrap(),
+                member_count: member_count as usize,
+                notice,
+            })
+            .collect();
+        let packet = Packet::from(&PacketServerClanRecruitment::List {
+            listings: listings.clone(),
+        });
+        let PacketServerClanRecruitment::List { listings: decoded } =
+            PacketServerClanRecruitment::try_from(&packet).unwrap()
+        else {
+            return Err(TestCaseError::fail("Decoded wrong clan recruitment packet"));
+        };
+        prop_assert_eq!(decoded.len(), listings.len());
+        for (decoded, expected) in decoded.iter().zip(listings.iter()) {
+            prop_assert_eq!(&decoded.name, &expected.name);
+            prop_assert_eq!(decoded.level.get(), expected.level.get());
+            prop_assert_eq!(decoded.member_count, expected.member_count);
+            prop_assert_eq!(&decoded.notice, &expected.notice);
+        }
+    }
+
+    #[test]
+    fn server_clan_recruitment_applications(
+        applications in prop::collection::vec(clan_application(), 0..8),
+    ) {
+        let packet = Packet::from(&PacketServerClanRecruitment::Applications {
+            applications: applications.clone(),
+        });
+        let PacketServerClanRecruitment::Applications { applications: decoded } =
+            PacketServerClanRecruitment::try_from(&packet).unwrap()
+        else {
+            return Err(TestCaseError::fail("Decoded wrong clan recruitment packet"));
+        };
+        prop_assert_eq!(decoded.len(), applications.len());
+        for (decoded, expected) in decoded.iter().zip(applications.iter()) {
+            assert_clan_application_eq(decoded, expected)?;
+        }
+    }
+
+    #[test]
+    fn server_clan_recruitment_application_received(application in clan_application()) {
+        let packet = Packet::from(&PacketServerClanRecruitment::ApplicationReceived {
+            application: application.clone(),
+        });
+        let PacketServerClanRecruitment::ApplicationReceived { application: decoded } =
+            PacketServerClanRecruitment::try_from(&packet).unwrap()
+        else {
+            return Err(TestCaseError::fail("Decoded wrong clan recruitment packet"));
+        };
+        assert_clan_application_eq(&decoded, &application)?;
+    }
+
+    #[test]
+    fn client_join_zone_round_trip(expected in client_join_zone()) {
+        assert_typed_round_trip!(game_client_packets::PacketClientJoinZone, expected);
+    }
+
+    #[test]
+    fn client_set_hotbar_slot_round_trip(expected in client_set_hotbar_slot()) {
+        assert_typed_round_trip!(game_client_packets::PacketClientSetHotbarSlot, expected);
+    }
+
+    #[test]
+    fn client_change_equipment_round_trip(expected in client_change_equipment()) {
+        assert_typed_round_trip!(game_client_packets::PacketClientChangeEquipment, expected);
+    }
+
+    #[test]
+    fn client_change_vehicle_part_round_trip(expected in client_change_vehicle_part()) {
+        assert_typed_round_trip!(game_client_packets::PacketClientChangeVehiclePart, expected);
+    }
+
+    #[test]
+    fn client_revive_request_round_trip(expected in client_revive_request()) {
+        assert_typed_round_trip!(game_client_packets::PacketClientReviveRequest, expected);
+    }
+
+    #[test]
+    fn client_quest_request_round_trip(expected in client_quest_request()) {
+        assert_typed_round_trip!(game_client_packets::PacketClientQuestRequest, expected);
+    }
+
+    #[test]
+    fn client_personal_store_buy_item_round_trip(expected in client_personal_store_buy_item()) {
+        assert_typed_round_trip!(game_client_packets::PacketClientPersonalStoreBuyItem, expected);
+    }
+
+    #[test]
+    fn client_drop_item_from_inventory_round_trip(expected in client_drop_item_from_inventory()) {
+        assert_typed_round_trip!(game_client_packets::PacketClientDropItemFromInventory, expected);
+    }
+
+    #[test]
+    fn client_clan_vault_round_trip(expected in client_clan_vault()) {
+        assert_typed_round_trip!(game_client_packets::PacketClientClanVault, expected);
+    }
+
+    #[test]
+    fn client_clan_permissions_round_trip(expected in client_clan_permissions()) {
+        assert_typed_round_trip!(game_client_packets::PacketClientClanPermissions, expected);
+    }
+
+    #[test]
+    fn client_npc_store_transaction_round_trip(expected in client_npc_store_transaction()) {
+        assert_typed_round_trip!(game_client_packets::PacketClientNpcStoreTransaction, expected);
+    }
+
+    #[test]
+    fn client_change_ammo_round_trip(expected in client_change_ammo()) {
+        assert_typed_round_trip!(game_client_packets::PacketClientChangeAmmo, expected);
+    }
+
+    #[test]
+    fn client_move_toggle_round_trip(expected in client_move_toggle()) {
+        assert_typed_round_trip!(game_client_packets::PacketClientMoveToggle, expected);
+    }
+
+    #[test]
+    fn client_party_request_round_trip(expected in client_party_request()) {
+        assert_typed_round_trip!(game_client_packets::PacketClientPartyRequest, expected);
+    }
+
+    #[test]
+    fn client_party_reply_round_trip(expected in client_party_reply()) {
+        assert_typed_round_trip!(game_client_packets::PacketClientPartyReply, expected);
+    }
+
+    #[test]
+    fn client_craft_item_round_trip(expected in client_craft_item()) {
+        assert_typed_round_trip!(game_client_packets::PacketClientCraftItem, expected);
+    }
+
+    #[test]
+    fn client_bank_move_item_round_trip(expected in client_bank_move_item()) {
+        assert_typed_round_trip!(game_client_packets::PacketClientBankMoveItem, expected);
+    }
+
+    #[test]
+    fn client_clan_command_round_trip(expected in client_clan_command()) {
+        assert_typed_round_trip!(game_client_packets::PacketClientClanCommand, expected);
+    }
+
+    #[test]
+    fn client_inspect_character_round_trip(expected in client_inspect_character()) {
+        assert_typed_round_trip!(game_client_packets::PacketClientInspectCharacter, expected);
+    }
+
+    #[test]
+    fn client_inventory_operation_round_trip(expected in client_inventory_operation()) {
+        assert_typed_round_trip!(game_client_packets::PacketClientInventoryOperation, expected);
+    }
+
+    #[test]
+    fn client_change_costume_round_trip(expected in client_change_costume()) {
+        assert_typed_round_trip!(game_client_packets::PacketClientChangeCostume, expected);
+    }
+
+    #[test]
+    fn server_select_character_round_trip(expected in server_select_character()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerSelectCharacter, expected);
+    }
+
+    #[test]
+    fn server_character_inventory_round_trip(expected in server_character_inventory()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerCharacterInventory, expected);
+    }
+
+    #[test]
+    fn server_character_quest_data_round_trip(expected in server_character_quest_data()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerCharacterQuestData, expected);
+    }
+
+    #[test]
+    fn server_attack_entity_round_trip(expected in server_attack_entity()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerAttackEntity, expected);
+    }
+
+    #[test]
+    fn server_damage_entity_round_trip(expected in server_damage_entity()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerDamageEntity, expected);
+    }
+
+    #[test]
+    fn server_move_entity_round_trip(expected in server_move_entity()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerMoveEntity, expected);
+    }
+
+    #[test]
+    fn server_join_zone_round_trip(expected in server_join_zone()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerJoinZone, expected);
+    }
+
+    #[test]
+    fn server_stop_move_entity_round_trip(expected in server_stop_move_entity()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerStopMoveEntity, expected);
+    }
+
+    #[test]
+    fn server_teleport_round_trip(expected in server_teleport()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerTeleport, expected);
+    }
+
+    #[test]
+    fn server_set_hotbar_slot_round_trip(expected in server_set_hotbar_slot()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerSetHotbarSlot, expected);
+    }
+
+    #[test]
+    fn server_spawn_entity_item_drop_round_trip(expected in server_spawn_entity_item_drop()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerSpawnEntityItemDrop, expected);
+    }
+
+    #[test]
+    fn server_spawn_entity_npc_round_trip(expected in server_spawn_entity_npc()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerSpawnEntityNpc, expected);
+    }
+
+    #[test]
+    fn server_spawn_entity_monster_round_trip(expected in server_spawn_entity_monster()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerSpawnEntityMonster, expected);
+    }
+
+    #[test]
+    fn server_spawn_entity_character_round_trip(expected in server_spawn_entity_character()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerSpawnEntityCharacter, expected);
+    }
+
+    #[test]
+    fn server_remove_entities_round_trip(expected in server_remove_entities()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerRemoveEntities, expected);
+    }
+
+    #[test]
+    fn server_update_inventory_round_trip(expected in server_update_inventory()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerUpdateInventory, expected);
+    }
+
+    #[test]
+    fn server_update_ammo_round_trip(expected in server_update_ammo()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerUpdateAmmo, expected);
+    }
+
+    #[test]
+    fn server_update_equipment_round_trip(expected in server_update_equipment()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerUpdateEquipment, expected);
+    }
+
+    #[test]
+    fn server_update_vehicle_part_round_trip(expected in server_update_vehicle_part()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerUpdateVehiclePart, expected);
+    }
+
+    #[test]
+    fn server_update_level_round_trip(expected in server_update_level()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerUpdateLevel, expected);
+    }
+
+    #[test]
+    fn server_update_xp_stamina_round_trip(expected in server_update_xp_stamina()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerUpdateXpStamina, expected);
+    }
+
+    #[test]
+    fn server_update_basic_stat_round_trip(expected in server_update_basic_stat()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerUpdateBasicStat, expected);
+    }
+
+    #[test]
+    fn server_pickup_item_drop_result_round_trip(expected in server_pickup_item_drop_result()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerPickupItemDropResult, expected);
+    }
+
+    #[test]
+    fn server_logout_result_round_trip(expected in server_logout_result()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerLogoutResult, expected);
+    }
+
+    #[test]
+    fn server_quest_result_round_trip(expected in server_quest_result()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerQuestResult, expected);
+    }
+
+    #[test]
+    fn server_update_ability_value_round_trip(expected in server_update_ability_value()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerUpdateAbilityValue, expected);
+    }
+
+    #[test]
+    fn server_learn_skill_result_round_trip(expected in server_learn_skill_result()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerLearnSkillResult, expected);
+    }
+
+    #[test]
+    fn server_level_up_skill_result_round_trip(expected in server_level_up_skill_result()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerLevelUpSkillResult, expected);
+    }
+
+    #[test]
+    fn server_personal_store_item_list_round_trip(expected in server_personal_store_item_list()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerPersonalStoreItemList, expected);
+    }
+
+    #[test]
+    fn server_personal_store_transaction_update_money_and_inventory_round_trip(expected in server_personal_store_transaction_update_money_and_inventory()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerPersonalStoreTransactionUpdateMoneyAndInventory, expected);
+    }
+
+    #[test]
+    fn server_personal_store_transaction_result_round_trip(expected in server_personal_store_transaction_result()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerPersonalStoreTransactionResult, expected);
+    }
+
+    #[test]
+    fn server_use_item_round_trip(expected in server_use_item()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerUseItem, expected);
+    }
+
+    #[test]
+    fn server_cast_skill_self_round_trip(expected in server_cast_skill_self()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerCastSkillSelf, expected);
+    }
+
+    #[test]
+    fn server_cast_skill_target_entity_round_trip(expected in server_cast_skill_target_entity()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerCastSkillTargetEntity, expected);
+    }
+
+    #[test]
+    fn server_cast_skill_target_position_round_trip(expected in server_cast_skill_target_position()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerCastSkillTargetPosition, expected);
+    }
+
+    #[test]
+    fn server_apply_skill_effect_round_trip(expected in server_apply_skill_effect()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerApplySkillEffect, expected);
+    }
+
+    #[test]
+    fn server_apply_skill_damage_round_trip(expected in server_apply_skill_damage()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerApplySkillDamage, expected);
+    }
+
+    #[test]
+    fn server_cancel_casting_skill_round_trip(expected in server_cancel_casting_skill()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerCancelCastingSkill, expected);
+    }
+
+    #[test]
+    fn server_toggle_skill_round_trip(expected in server_toggle_skill()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerToggleSkill, expected);
+    }
+
+    #[test]
+    fn server_clan_vault_round_trip(expected in server_clan_vault()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerClanVault, expected);
+    }
+
+    #[test]
+    fn server_inspect_character_round_trip(expected in server_inspect_character()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerInspectCharacter, expected);
+    }
+
+    #[test]
+    fn server_character_title_round_trip(expected in server_character_title()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerCharacterTitle, expected);
+    }
+
+    #[test]
+    fn server_update_status_effects_round_trip(expected in server_update_status_effects()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerUpdateStatusEffects, expected);
+    }
+
+    #[test]
+    fn server_npc_store_transaction_error_round_trip(expected in server_npc_store_transaction_error()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerNpcStoreTransactionError, expected);
+    }
+
+    #[test]
+    fn server_move_toggle_round_trip(expected in server_move_toggle()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerMoveToggle, expected);
+    }
+
+    #[test]
+    fn server_party_request_round_trip(expected in server_party_request()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerPartyRequest, expected);
+    }
+
+    #[test]
+    fn server_party_reply_round_trip(expected in server_party_reply()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerPartyReply, expected);
+    }
+
+    #[test]
+    fn server_party_members_round_trip(expected in server_party_members()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerPartyMembers, expected);
+    }
+
+    #[test]
+    fn server_party_update_rules_round_trip(expected in server_party_update_rules()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerPartyUpdateRules, expected);
+    }
+
+    #[test]
+    fn server_craft_item_round_trip(expected in server_craft_item()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerCraftItem, expected);
+    }
+
+    #[test]
+    fn server_bank_open_round_trip(expected in server_bank_open()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerBankOpen, expected);
+    }
+
+    #[test]
+    fn server_bank_transaction_round_trip(expected in server_bank_transaction()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerBankTransaction, expected);
+    }
+
+    #[test]
+    fn server_repaired_item_using_npc_round_trip(expected in server_repaired_item_using_npc()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerRepairedItemUsingNpc, expected);
+    }
+
+    #[test]
+    fn server_clan_command_round_trip(expected in server_clan_command()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerClanCommand, expected);
+    }
+
+    #[test]
+    fn server_update_costume_round_trip(expected in server_update_costume()) {
+        assert_typed_round_trip!(game_server_packets::PacketServerUpdateCostume, expected);
+    }
+
+    #[test]
+    fn login_server_login_reply_round_trip(expected in login_server_login_reply()) {
+        assert_typed_round_trip!(login_server_packets::PacketServerLoginReply, expected);
+    }
+
+    #[test]
+    fn world_client_clan_command_round_trip(expected in world_client_clan_command()) {
+        assert_typed_round_trip!(world_client_packets::PacketClientClanCommand, expected);
+    }
+
+    #[test]
+    fn client_connect_request_round_trip(login_token in any::<u32>(), password in password()) {
+        use game_client_packets::PacketClientConnectRequest;
+        assert_typed_round_trip!(
+            PacketClientConnectRequest,
+            PacketClientConnectRequest { login_token, password: &password }
+        );
+    }
+
+    #[test]
+    fn client_skill_slot_round_trip(
+        skill_slot in skill_slot(),
+        target_entity_id in client_entity_id(),
+        position in vec2(),
+        next_skill_idx in skill_id(),
+        enabled in any::<bool>(),
+    ) {
+        use game_client_packets::{
+            PacketClientCastSkillSelf, PacketClientCastSkillTargetEntity,
+            PacketClientCastSkillTargetPosition, PacketClientLevelUpSkill, PacketClientToggleSkill,
+        };
+        assert_typed_round_trip!(PacketClientCastSkillSelf, PacketClientCastSkillSelf { skill_slot });
+        assert_typed_round_trip!(
+            PacketClientCastSkillTargetEntity,
+            PacketClientCastSkillTargetEntity { target_entity_id, skill_slot }
+        );
+        assert_typed_round_trip!(
+            PacketClientCastSkillTargetPosition,
+            PacketClientCastSkillTargetPosition { skill_slot, position }
+        );
+        assert_typed_round_trip!(
+            PacketClientLevelUpSkill,
+            PacketClientLevelUpSkill { skill_slot, next_skill_idx }
+        );
+        assert_typed_round_trip!(PacketClientToggleSkill, PacketClientToggleSkill { skill_slot, enabled });
+    }
+
+    #[test]
+    fn client_target_entity_round_trip(
+        target_entity_id in client_entity_id(),
+        item_slot in item_slot(),
+        use_item_slot in item_slot(),
+        use_target_entity_id in prop::option::of(client_entity_id()),
+    ) {
+        use game_client_packets::{
+            PacketClientPersonalStoreListItems, PacketClientPickupItemDrop,
+            PacketClientRepairItemUsingItem, PacketClientRepairItemUsingNpc, PacketClientUseItem,
+        };
+        assert_typed_round_trip!(
+            PacketClientPersonalStoreListItems,
+            PacketClientPersonalStoreListItems { target_entity_id }
+        );
+        assert_typed_round_trip!(PacketClientPickupItemDrop, PacketClientPickupItemDrop { target_entity_id });
+        assert_typed_round_trip!(
+            PacketClientRepairItemUsingItem,
+            PacketClientRepairItemUsingItem { use_item_slot, item_slot }
+        );
+        assert_typed_round_trip!(
+            PacketClientRepairItemUsingNpc,
+            PacketClientRepairItemUsingNpc { npc_entity_id: target_entity_id, item_slot }
+        );
+        assert_typed_round_trip!(
+            PacketClientUseItem,
+            PacketClientUseItem { item_slot: use_item_slot, target_entity_id: use_target_entity_id }
+        );
+    }
+
+    #[test]
+    fn client_misc_round_trip(
+        motion_id in any::<u16>(),
+        is_stop in any::<bool>(),
+        basic_stat_type in basic_stat_type(),
+        (x, y, z) in (finite_f32(), finite_f32(), any::<i16>()),
+        (item_sharing, xp_sharing) in (party_item_sharing(), party_xp_sharing()),
+        title_id in prop::option::of(1..=u16::MAX),
+        auto_loot in any::<bool>(),
+        warp_gate_id in any::<u16>(),
+    ) {
+        use game_client_packets::{
+            PacketClientEmote, PacketClientIncreaseBasicStat, PacketClientMoveCollision,
+            PacketClientPartyUpdateRules, PacketClientSetActiveTitle, PacketClientSetAutoLoot,
+            PacketClientWarpGateRequest,
+        };
+        assert_typed_round_trip!(
+            PacketClientEmote,
+            PacketClientEmote { motion_id: MotionId::new(motion_id), is_stop }
+        );
+        assert_typed_round_trip!(
+            PacketClientIncreaseBasicStat,
+            PacketClientIncreaseBasicStat { basic_stat_type }
+        );
+        assert_typed_round_trip!(
+            PacketClientMoveCollision,
+            PacketClientMoveCollision { position: Vec3::new(x, y, z as f32) }
+        );
+        assert_typed_round_trip!(
+            PacketClientPartyUpdateRules,
+            PacketClientPartyUpdateRules { item_sharing, xp_sharing }
+        );
+        assert_typed_round_trip!(PacketClientSetActiveTitle, PacketClientSetActiveTitle { title_id });
+        assert_typed_round_trip!(PacketClientSetAutoLoot, PacketClientSetAutoLoot { auto_loot });
+        assert_typed_round_trip!(
+            PacketClientWarpGateRequest,
+            PacketClientWarpGateRequest { warp_gate_id: WarpGateId::new(warp_gate_id) }
+        );
+    }
+
+    #[test]
+    fn server_chat_round_trip(
+        entity_id in client_entity_id(),
+        name in packet_string(),
+        text in packet_string(),
+        announce_name in prop::option::of(packet_string()),
+    ) {
+        use game_server_packets::{
+            PacketServerAnnounceChat, PacketServerLocalChat, PacketServerShoutChat,
+            PacketServerWhisper,
+        };
+        assert_typed_round_trip!(PacketServerLocalChat, PacketServerLocalChat { entity_id, text: &text });
+        assert_typed_round_trip!(
+            PacketServerShoutChat,
+            PacketServerShoutChat { name: &name, text: &text }
+        );
+        assert_typed_round_trip!(
+            PacketServerAnnounceChat,
+            PacketServerAnnounceChat { name: announce_name.as_deref(), text: &text }
+        );
+        assert_typed_round_trip!(PacketServerWhisper, PacketServerWhisper { from: &name, text: &text });
+    }
+
+    #[test]
+    fn server_personal_store_round_trip(
+        entity_id in client_entity_id(),
+        skin in any::<u16>(),
+        title in packet_string(),
+    ) {
+        use game_server_packets::{PacketServerClosePersonalStore, PacketServerOpenPersonalStore};
+        assert_typed_round_trip!(
+            PacketServerOpenPersonalStore,
+            PacketServerOpenPersonalStore { entity_id, skin: skin as i32, title: &title }
+        );
+        assert_typed_round_trip!(
+            PacketServerClosePersonalStore,
+            PacketServerClosePersonalStore { entity_id }
+        );
+    }
+
+    #[test]
+    fn server_entity_round_trip(
+        entity_id in client_entity_id(),
+        skill_id in skill_id(),
+        npc_id in npc_id(),
+        motion_id in any::<u16>(),
+        is_stop in any::<bool>(),
+        (x, y, z) in (finite_f32(), finite_f32(), any::<i16>()),
+        [run_speed, passive_attack_speed] in any::<[u16; 2]>(),
+    ) {
+        use game_server_packets::{
+            PacketServerAdjustPosition, PacketServerChangeNpcId, PacketServerFinishCastingSkill,
+            PacketServerStartCastingSkill, PacketServerUpdateSpeed, PacketServerUseEmote,
+        };
+        assert_typed_round_trip!(
+            PacketServerStartCastingSkill,
+            PacketServerStartCastingSkill { entity_id }
+        );
+        assert_typed_round_trip!(
+            PacketServerFinishCastingSkill,
+            PacketServerFinishCastingSkill { entity_id, skill_id }
+        );
+        assert_typed_round_trip!(PacketServerChangeNpcId, PacketServerChangeNpcId { entity_id, npc_id });
+        assert_typed_round_trip!(
+            PacketServerUseEmote,
+            PacketServerUseEmote { entity_id, motion_id: MotionId::new(motion_id), is_stop }
+        );
+        assert_typed_round_trip!(
+            PacketServerAdjustPosition,
+            PacketServerAdjustPosition { entity_id, position: Vec3::new(x, y, z as f32) }
+        );
+        assert_typed_round_trip!(
+            PacketServerUpdateSpeed,
+            PacketServerUpdateSpeed {
+                entity_id,
+                run_speed: run_speed as i32,
+                passive_attack_speed: passive_attack_speed as i32,
+            }
+        );
+    }
+
+    #[test]
+    fn server_money_and_items_round_trip(
+        money in any::<i64>(),
+        items in prop::collection::vec((item_slot(), prop::option::of(item_full())), 0..8),
+        (item_slot, life) in (item_slot(), any::<u16>()),
+        (entity_id, item) in (client_entity_id(), item_full()),
+        npc_id in npc_id(),
+    ) {
+        use game_server_packets::{
+            PacketServerPartyMemberRewardItem, PacketServerRewardItems, PacketServerRewardMoney,
+            PacketServerRunNpcDeathTrigger, PacketServerUpdateItemLife, PacketServerUpdateMoney,
+        };
+        assert_typed_round_trip!(PacketServerUpdateMoney, PacketServerUpdateMoney { money: Money(money) });
+        assert_typed_round_trip!(PacketServerRewardMoney, PacketServerRewardMoney { money: Money(money) });
+        assert_typed_round_trip!(PacketServerRewardItems, PacketServerRewardItems { items });
+        assert_typed_round_trip!(PacketServerUpdateItemLife, PacketServerUpdateItemLife { item_slot, life });
+        assert_typed_round_trip!(
+            PacketServerPartyMemberRewardItem,
+            PacketServerPartyMemberRewardItem { entity_id, item }
+        );
+        assert_typed_round_trip!(
+            PacketServerRunNpcDeathTrigger,
+            PacketServerRunNpcDeathTrigger { npc_id }
+        );
+    }
+
+    #[test]
+    fn server_party_member_update_info_round_trip(member_info in party_member_info_online()) {
+        use game_server_packets::PacketServerPartyMemberUpdateInfo;
+        assert_typed_round_trip!(
+            PacketServerPartyMemberUpdateInfo,
+            PacketServerPartyMemberUpdateInfo { member_info }
+        );
+    }
+
+    #[test]
+    fn server_clan_permissions_round_trip(permissions in clan_permissions()) {
+        use game_server_packets::PacketServerClanPermissions;
+        assert_typed_round_trip!(
+            PacketServerClanPermissions,
+            PacketServerClanPermissions { permissions }
+        );
+    }
+
+    #[test]
+    fn server_clan_recruitment_application_replies(clan_name in packet_string(), sent in any::<bool>()) {
+        let expected = if sent {
+            PacketServerClanRecruitment::ApplicationSent { clan_name }
+        } else {
+            PacketServerClanRecruitment::ApplicationRejected { clan_name }
+        };
+        assert_typed_round_trip!(PacketServerClanRecruitment, expected);
+    }
+
+    #[test]
+    fn server_character_statistics_round_trip(expected in server_character_statistics()) {
+        use game_server_packets::PacketServerCharacterStatistics;
+        let packet = Packet::from(&expected);
+        let decoded = PacketServerCharacterStatistics::try_from(&packet).unwrap();
+        let (decoded, expected) = (&decoded.statistics, &expected.statistics);
+        prop_assert_eq!(decoded.monster_kills, expected.monster_kills);
+        prop_assert_eq!(&decoded.npc_kills, &expected.npc_kills);
+        prop_assert_eq!(decoded.deaths, expected.deaths);
+        prop_assert_eq!(decoded.money_earned, expected.money_earned);
+        prop_assert_eq!(decoded.distance_walked, expected.distance_walked);
+        prop_assert_eq!(decoded.quests_completed, expected.quests_completed);
+    }
+
+    #[test]
+    fn login_client_round_trip(
+        username in packet_string(),
+        password in password(),
+        server_id in any::<u32>(),
+        channel_id in 0..u8::MAX,
+    ) {
+        use login_client_packets::{
+            PacketClientChannelList, PacketClientLoginRequest, PacketClientSelectServer,
+        };
+        assert_typed_round_trip!(
+            PacketClientLoginRequest,
+            PacketClientLoginRequest { username: &username, password: &password }
+        );
+        assert_typed_round_trip!(
+            PacketClientChannelList,
+            PacketClientChannelList { server_id: server_id as usize }
+        );
+        assert_typed_round_trip!(
+            PacketClientSelectServer,
+            PacketClientSelectServer {
+                server_id: server_id as usize,
+                channel_id: channel_id as usize,
+            }
+        );
+    }
+
+    #[test]
+    fn login_server_round_trip(
+        status in prop::sample::select(vec![1u8, 2, 3, 4]),
+        packet_sequence_id in any::<u32>(),
+        login_reply in login_server_login_reply(),
+        server_id in any::<u32>(),
+        channels in prop::collection::vec(
+            (0..u8::MAX, any::<u8>(), any::<u8>(), any::<u16>(), packet_string()),
+            0..8,
+        ),
+        (result, login_token, packet_codec_seed) in
+            (login_select_server_result(), any::<u32>(), any::<u32>()),
+        (ip, port) in (packet_string(), any::<u16>()),
+    ) {
+        use login_server_packets::{
+            ConnectionResult, PacketConnectionReply, PacketServerChannelList,
+            PacketServerChannelListItem, PacketServerLoginReply, PacketServerSelectServer,
+        };
+        let status = match status {
+            1 => ConnectionResult::Connect,
+            2 => ConnectionResult::Accepted,
+            3 => ConnectionResult::Disconnect,
+            _ => ConnectionResult::ServerDead,
+        };
+        assert_typed_round_trip!(
+            PacketConnectionReply,
+            PacketConnectionReply { status, packet_sequence_id }
+        );
+        assert_typed_round_trip!(PacketServerLoginReply, login_reply);
+        assert_typed_round_trip!(
+            PacketServerChannelList,
+            PacketServerChannelList {
+                server_id: server_id as usize,
+                channels: channels
+                    .iter()
+                    .map(|(id, low_age, high_age, percent_full, name)| PacketServerChannelListItem {
+                        id: *id,
+                        low_age: *low_age,
+                        high_age: *high_age,
+                        percent_full: *percent_full,
+                        name,
+                    })
+                    .collect(),
+            }
+        );
+        assert_typed_round_trip!(
+            PacketServerSelectServer,
+            PacketServerSelectServer { result, login_token, packet_codec_seed, ip: &ip, port }
+        );
+    }
+
+    #[test]
+    fn world_client_round_trip(
+        login_token in any::<u32>(),
+        password in password(),
+        (gender, birth_stone, hair, face, start_point) in
+            (character_gender(), any::<u8>(), any::<u8>(), any::<u8>(), any::<u16>()),
+        (slot, is_delete) in (any::<u8>(), any::<bool>()),
+        name in packet_string(),
+    ) {
+        use world_client_packets::{
+            PacketClientConnectRequest, PacketClientCreateCharacter, PacketClientDeleteCharacter,
+            PacketClientSelectCharacter,
+        };
+        assert_typed_round_trip!(
+            PacketClientConnectRequest,
+            PacketClientConnectRequest { login_token, password: &password }
+        );
+        assert_typed_round_trip!(
+            PacketClientCreateCharacter,
+            PacketClientCreateCharacter { gender, birth_stone, hair, face, start_point, name: &name }
+        );
+        assert_typed_round_trip!(
+            PacketClientDeleteCharacter,
+            PacketClientDeleteCharacter { slot, is_delete, name: &name }
+        );
+        assert_typed_round_trip!(
+            PacketClientSelectCharacter,
+            PacketClientSelectCharacter { slot, name: &name }
+        );
+    }
+
+    #[test]
+    fn world_server_round_trip(
+        (result, is_platinum) in (
+            prop::sample::select(vec![0u8, 1, 2, 3, 4, 5]),
+            any::<bool>(),
+        ),
+        seconds_until_delete in prop::option::of(0..u32::MAX),
+        name in packet_string(),
+        (login_token, packet_codec_seed, ip, port) in
+            (any::<u32>(), any::<u32>(), packet_string(), any::<u16>()),
+    ) {
+        use world_server_packets::{
+            CreateCharacterResult, PacketServerCreateCharacterReply,
+            PacketServerDeleteCharacterReply, PacketServerMoveServer,
+        };
+        let result = match result {
+            0 => CreateCharacterResult::Ok,
+            1 => CreateCharacterResult::Failed,
+            2 => CreateCharacterResult::NameAlreadyExists,
+            3 => CreateCharacterResult::InvalidValue,
+            4 => CreateCharacterResult::NoMoreSlots,
+            _ => CreateCharacterResult::Blocked,
+        };
+        assert_typed_round_trip!(
+            PacketServerCreateCharacterReply,
+            PacketServerCreateCharacterReply { result, is_platinum }
+        );
+        assert_typed_round_trip!(
+            PacketServerDeleteCharacterReply,
+            PacketServerDeleteCharacterReply { seconds_until_delete, name: &name }
+        );
+        assert_typed_round_trip!(
+            PacketServerMoveServer,
+            PacketServerMoveServer { login_token, packet_codec_seed, ip: &ip, port }
+        );
+    }
+
+    #[test]
+    fn world_server_character_list_round_trip(
+        characters in prop::collection::vec(character_list_item(), 0..5),
+    ) {
+        use world_server_packets::PacketServerCharacterList;
+        let packet = Packet::from(&PacketServerCharacterList {
+            characters: characters.clone(),
+        });
+        let decoded = PacketServerCharacterList::try_from(&packet).unwrap();
+        prop_assert_eq!(decoded.characters.len(), characters.len());
+        for (decoded, expected) in decoded.characters.iter().zip(characters.iter()) {
+            prop_assert_eq!(format!("{:?}", decoded.info), format!("{:?}", expected.info));
+            prop_assert_eq!(decoded.level.level, expected.level.level);
+            prop_assert_eq!(
+                format!("{:?}", decoded.equipment),
+                format!("{:?}", expected.equipment)
+            );
+            // The delete time is sent as the seconds remaining, so allow for time passing
+            let remaining = |character: &CharacterListItem| {
+                character
+                    .delete_time
+                    .as_ref()
+                    .map(|delete_time| delete_time.get_time_until_delete().as_secs())
+            };
+            match (remaining(decoded), remaining(expected)) {
+                (Some(decoded), Some(expected)) => prop_assert!(decoded.abs_diff(expected) <= 2),
+                (decoded, expected) => prop_assert_eq!(decoded, expected),
+            }
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use std::time::{Duration, Instant};
+
+use bevy::{
+    ecs::system::SystemState,
+    math::Vec3,
+    prelude::{App, Commands, Res, ResMut, Startup},
+    time::TimeUpdateStrategy,
+    MinimalPlugins,
+};
+use rand::Rng;
+
+use rose_data::ZoneId;
+
+use crate::game::{
+    bots::{bot_level_range_for_zone, bot_spawn_random_build},
+    components::Position,
+    game_world::add_game_world,
+    resources::{BotList, BotListEntry, GameConfig, GameData},
+    storage::writer::disable_storage_writes,
+    systems::startup_zones_system,
+};
+
+const BENCHMARK_TICK: Duration = Duration::from_nanos(16_666_667);
+const BENCHMARK_BOT_SPREAD: f32 = 500.0;
+
+pub struct BenchmarkConfig {
+    pub bot_count: usize,
+    pub ticks: usize,
+    /// Zones to spread bots across, if empty then every zone with monster spawns is used
+    pub zones: Vec<ZoneId>,
+}
+
+pub struct BenchmarkReport {
+    pub bot_count: usize,
+    pub zone_count: usize,
+    pub tick_durations: Vec<Duration>,
+}
+
+impl BenchmarkReport {
+    pub fn total(&self) -> Duration {
+        self.tick_durations.iter().sum()
+    }
+
+    pub fn mean(&self) -> Duration {
+        if self.tick_durations.is_empty() {
+            return Duration::ZERO;
+        }
+
+        self.total() / self.tick_durations.len() as u32
+    }
+
+    /// Returns the tick duration at the given percentile, from 0.0 to 1.0
+    pub fn percentile(&self, percentile: f64) -> Duration {
+        if self.tick_durations.is_empty() {
+            return Duration::ZERO;
+        }
+
+        let mut sorted = self.tick_durations.clone();
+        sorted.sort();
+
+        let index = ((sorted.len() - 1) as f64 * percentile.clamp(0.0, 1.0)).round() as usize;
+        sorted[index]
+    }
+}
+
+/// Runs the game world without any network listeners, with bots providing the load, for a
+/// fixed number of ticks.
+///
+/// Storage writes are disabled for the rest of the process, so the benchmark cannot overwrite
+/// saved zone state or the navigation cache of the server it was started from.
+pub fn run_benchmark(
+    game_config: GameConfig,
+    game_data: GameData,
+    benchmark_config: &BenchmarkConfig,
+) -> BenchmarkReport {
+    disable_storage_writes();
+
+    let mut zones = benchmark_config.zones.clone();
+    if zones.is_empty() {
+        zones = game_data
+            .zones
+            .iter()
+            .filter(|zone_data| !zone_data.monster_spawns.is_empty())
+            .map(|zone_data| zone_data.id)
+            .collect();
+    }
+
+    // Keep the control channel open so control_server_system sees no disconnect
+    let (_control_tx, control_rx) = crossbeam_channel::unbounded();
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(BENCHMARK_TICK));
+    app.add_systems(Startup, startup_zones_system);
+    add_game_world(&mut app, control_rx, game_config, game_data);
+
+    // Run startup systems so zones are populated before adding bots
+    app.update();
+
+    let mut system_state: SystemState<(Commands, Res<GameData>, ResMut<BotList>)> =
+        SystemState::new(&mut app.world);
+    let (mut commands, game_data, mut bot_list) = system_state.get_mut(&mut app.world);
+    let mut rng = rand::thread_rng();
+    let mut bot_count = 0;
+
+    for (index, zone_id) in zones
+        .iter()
+        .cycle()
+        .take(benchmark_config.bot_count)
+        .enumerate()
+    {
+        let Some(zone_data) = game_data.zones.get_zone(*zone_id) else {
+            log::warn!("Skipping benchmark bot for invalid zone {}", zone_id.get());
+            continue;
+        };
+
+        // Start bots near a monster spawn so they quickly find something to fight
+        let origin = if zone_data.monster_spawns.is_empty() {
+            zone_data.start_position
+        } else {
+            zone_data.monster_spawns[index % zone_data.monster_spawns.len()].position
+        };
+        let position = origin
+            + Vec3::new(
+                rng.gen_range(-BENCHMARK_BOT_SPREAD..=BENCHMARK_BOT_SPREAD),
+                rng.gen_range(-BENCHMARK_BOT_SPREAD..=BENCHMARK_BOT_SPREAD),
+                0.0,
+            );
+
+        let level = rng.gen_range(bot_level_range_for_zone(&game_data, *zone_id)) as u32;
+        let bot_entity = bot_spawn_random_build(
+            &mut commands,
+            &game_data,
+            format!("Benchmark {}", index),
+            Position::new(position, *zone_id),
+            level,
+        );
+        bot_list.push(BotListEntry::new(bot_entity));
+        bot_count += 1;
+    }
+
+    system_state.apply(&mut app.world);
+
+    log::info!(
+        "Running benchmark with {} bots across {} zones for {} ticks",
+        bot_count,
+        zones.len(),
+        benchmark_config.ticks
+    );
+
+    let mut tick_durations = Vec::with_capacity(benchmark_config.ticks);
+    for _ in 0..benchmark_config.ticks {
+        let started_tick = Instant::now();
+        app.update();
+        tick_durations.push(started_tick.elapsed());
+    }
+
+    BenchmarkReport {
+        bot_count,
+        zone_count: zones.len(),
+        tick_durations,
+    }
+}
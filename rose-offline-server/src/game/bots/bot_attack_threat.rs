@@ -33,10 +33,14 @@ fn find_highest_damage_source(damage_sources: &DamageSources) -> Option<Entity>
             continue;
         }
 
+        let Some(damage_source_entity) = damage_source.attacker.entity() else {
+            continue;
+        };
+
         if highest_damage_source.map_or(true, |(total_damage, _)| {
             total_damage < damage_source.total_damage
         }) {
-            highest_damage_source = Some((damage_source.total_damage, damage_source.entity));
+            highest_damage_source = Some((damage_source.total_damage, damage_source_entity));
         }
     }
 
@@ -74,14 +78,18 @@ pub fn score_threat_is_not_target(
                 continue;
             }
 
+            let Some(damage_source_entity) = damage_source.attacker.entity() else {
+                continue;
+            };
+
             if highest_damage_source.map_or(true, |(total_damage, _)| {
                 total_damage < damage_source.total_damage
             }) {
-                highest_damage_source = Some((damage_source.total_damage, damage_source.entity));
+                highest_damage_source = Some((damage_source.total_damage, damage_source_entity));
             }
 
             if bot_combat_target.map_or(false, |bot_combat_target| {
-                bot_combat_target.entity == damage_source.entity
+                bot_combat_target.entity == damage_source_entity
             }) {
                 bot_combat_target_damage = damage_source.total_damage;
             }
@@ -138,11 +146,15 @@ pub fn action_attack_threat(
                         continue;
                     }
 
+                    let Some(damage_source_entity) = damage_source.attacker.entity() else {
+                        continue;
+                    };
+
                     if highest_damage_source.map_or(true, |(total_damage, _)| {
                         total_damage < damage_source.total_damage
                     }) {
                         highest_damage_source =
-                            Some((damage_source.total_damage, damage_source.entity));
+                            Some((damage_source.total_damage, damage_source_entity));
                     }
                 }
 
@@ -15,7 +15,7 @@ use crate::game::{
     GameData,
 };
 
-use super::{BotCombatTarget, BotQueryFilterAlive};
+use super::{bot_get_active_skill_page, BotCombatTarget, BotQueryFilterAlive};
 
 #[derive(Clone, Component, Debug, ScorerBuilder)]
 pub struct ShouldUseAttackSkill {
@@ -75,7 +75,7 @@ pub fn score_should_use_attack_skill(
             continue;
         }
 
-        let Some(active_skill_page) = skill_list.pages.get(1) else {
+        let Some(active_skill_page) = bot_get_active_skill_page(&game_data, skill_list) else {
             continue;
         };
 
@@ -86,7 +86,7 @@ pub fn score_should_use_attack_skill(
         for skill_id in active_skill_page.skills.iter().filter_map(|x| x.as_ref()) {
             if let Some(skill_data) = game_data.skills.get_skill(*skill_id) {
                 if skill_can_use(now, &game_data, &skill_caster, skill_data)
-                    && skill_can_target_entity(&skill_caster, &skill_target, skill_data)
+                    && skill_can_target_entity(&game_data, &skill_caster, &skill_target, skill_data)
                 {
                     score.set(scorer.score);
                     break;
@@ -123,7 +123,8 @@ pub fn action_use_attack_skill(
                     continue;
                 };
 
-                let Some(active_skill_page) = skill_list.pages.get(1) else {
+                let Some(active_skill_page) = bot_get_active_skill_page(&game_data, skill_list)
+                else {
                     *state = ActionState::Failure;
                     continue;
                 };
@@ -133,7 +134,12 @@ pub fn action_use_attack_skill(
                 for skill_id in active_skill_page.skills.iter().filter_map(|x| x.as_ref()) {
                     if let Some(skill_data) = game_data.skills.get_skill(*skill_id) {
                         if skill_can_use(now, &game_data, &skill_caster, skill_data)
-                            && skill_can_target_entity(&skill_caster, &skill_target, skill_data)
+                            && skill_can_target_entity(
+                                &game_data,
+                                &skill_caster,
+                                &skill_target,
+                                skill_data,
+                            )
                         {
                             commands.entity(entity).insert(
                                 NextCommand::with_cast_skill_target_entity(
@@ -16,7 +16,7 @@ use crate::game::{
     GameData,
 };
 
-use super::{BotQueryFilterAlive, BotQueryFilterAliveNoTarget};
+use super::{bot_get_active_skill_page, BotQueryFilterAlive, BotQueryFilterAliveNoTarget};
 
 #[derive(Clone, Component, Debug, ScorerBuilder)]
 pub struct ShouldUseBuffSkill {
@@ -64,7 +64,7 @@ pub fn score_should_use_buff_skill(
             continue;
         }
 
-        let Some(active_skill_page) = bot.skill_list.pages.get(1) else {
+        let Some(active_skill_page) = bot_get_active_skill_page(&game_data, bot.skill_list) else {
             continue;
         };
 
@@ -140,7 +140,8 @@ pub fn action_use_buff_skill(
                     continue;
                 };
 
-                let Some(active_skill_page) = bot.skill_list.pages.get(1) else {
+                let Some(active_skill_page) = bot_get_active_skill_page(&game_data, bot.skill_list)
+                else {
                     *state = ActionState::Failure;
                     continue;
                 };
@@ -1,16 +1,28 @@
-use bevy::prelude::Component;
+use bevy::{
+    prelude::{Commands, Component, Entity},
+    utils::HashSet,
+};
 use rand::seq::SliceRandom;
+use std::ops::RangeInclusive;
 
 use rose_data::{
     AbilityType, AmmoIndex, EquipmentIndex, EquipmentItem, ItemClass, ItemReference, ItemType,
-    JobId, SkillId, StackableItem,
+    JobId, SkillId, StackableItem, ZoneId,
 };
 use rose_game_common::components::{
     AbilityValues, BasicStatType, BasicStats, CharacterGender, StatPoints, StatusEffects,
+    StatusEffectsRegen,
 };
 
 use crate::game::{
-    bundles::{skill_list_try_learn_skill, skill_list_try_level_up_skill, SkillListBundle},
+    bots::bot_thinker,
+    bundles::{
+        skill_list_try_learn_skill, skill_list_try_level_up_skill, CharacterBundle, SkillListBundle,
+    },
+    components::{
+        ClanMembership, Command, Cooldowns, DamageSources, MotionData, MoveMode, MoveSpeed,
+        NextCommand, PartyMembership, PassiveRecoveryTime, Position, Team,
+    },
     storage::character::CharacterStorage,
     GameData,
 };
@@ -317,7 +329,7 @@ pub fn spend_skill_points(
         team: None,
         character_info: Some(&bot_data.info),
         experience_points: Some(&bot_data.experience_points),
-        inventory: Some(&bot_data.inventory),
+        inventory: Some(&mut bot_data.inventory),
         stamina: Some(&bot_data.stamina),
         stat_points: Some(&bot_data.stat_points),
         union_membership: Some(&bot_data.union_membership),
@@ -579,3 +591,121 @@ pub fn bot_create_with_build(
 
     bot_data
 }
+
+/// Calculate an ideal bot level range based on the monsters which spawn in a zone
+pub fn bot_level_range_for_zone(game_data: &GameData, zone_id: ZoneId) -> RangeInclusive<i32> {
+    let mut bot_level_range = 1..=170;
+    if let Some(zone_data) = game_data.zones.get_zone(zone_id) {
+        // Find all monsters which spawn in this zone
+        let mut monster_ids = HashSet::new();
+        for spawn in zone_data.monster_spawns.iter() {
+            for npc_id in spawn.basic_spawns.iter().map(|(npc_id, _)| *npc_id) {
+                monster_ids.insert(npc_id);
+            }
+
+            for npc_id in spawn.tactic_spawns.iter().map(|(npc_id, _)| *npc_id) {
+                monster_ids.insert(npc_id);
+            }
+        }
+
+        // Create a sorted list of the levels of the monsters in this zone
+        let mut monster_levels: Vec<_> = monster_ids
+            .iter()
+            .filter_map(|npc_id| game_data.npcs.get_npc(*npc_id))
+            .map(|npc_data| npc_data.level)
+            .collect();
+        monster_levels.sort();
+
+        // Calculate a bot level range based on the median monster level +/- 20%
+        if let Some(median_monster_level) = monster_levels.get((monster_levels.len() + 1) / 2) {
+            // Ensure delta_level is mininum of 2 to ensure at least some variation in bot levels
+            let delta_level = (*median_monster_level / 5).max(2);
+
+            bot_level_range = (median_monster_level - delta_level).max(1)
+                ..=(median_monster_level + delta_level).min(170);
+        }
+    }
+
+    bot_level_range
+}
+
+pub fn bot_spawn_random_build(
+    commands: &mut Commands,
+    game_data: &GameData,
+    name: String,
+    position: Position,
+    level: u32,
+) -> Entity {
+    let (bot_build, mut bot_data) = bot_create_random_build(game_data, name, level);
+
+    let status_effects = StatusEffects::new();
+    let status_effects_regen = StatusEffectsRegen::new();
+
+    let ability_values = game_data.ability_value_calculator.calculate(
+        &bot_data.info,
+        &bot_data.level,
+        &bot_data.equipment,
+        &bot_data.basic_stats,
+        &bot_data.skill_list,
+        &status_effects,
+    );
+
+    let move_mode = MoveMode::Run;
+    let move_speed = MoveSpeed::new(ability_values.get_move_speed(&move_mode));
+
+    let weapon_motion_type = game_data
+        .items
+        .get_equipped_weapon_item_data(&bot_data.equipment, EquipmentIndex::Weapon)
+        .map(|item_data| item_data.motion_type)
+        .unwrap_or(0) as usize;
+
+    let motion_data = MotionData::from_character(
+        game_data.motions.as_ref(),
+        weapon_motion_type,
+        bot_data.info.gender,
+    );
+
+    bot_data.position = position;
+    bot_data.health_points.hp = ability_values.get_max_health();
+    bot_data.mana_points.mp = ability_values.get_max_mana();
+
+    commands
+        .spawn((
+            bot_build,
+            bot_thinker(),
+            CharacterBundle {
+                ability_values,
+                basic_stats: bot_data.basic_stats,
+                bank: Default::default(),
+                cooldowns: Cooldowns::default(),
+                command: Command::default(),
+                damage_sources: DamageSources::default_character(),
+                equipment: bot_data.equipment,
+                experience_points: bot_data.experience_points,
+                health_points: bot_data.health_points,
+                hotbar: bot_data.hotbar,
+                info: bot_data.info,
+                inventory: bot_data.inventory,
+                level: bot_data.level,
+                mana_points: bot_data.mana_points,
+                motion_data,
+                move_mode,
+                move_speed,
+                next_command: NextCommand::default(),
+                party_membership: PartyMembership::default(),
+                passive_recovery_time: PassiveRecoveryTime::default(),
+                position: bot_data.position,
+                quest_state: bot_data.quest_state,
+                skill_list: bot_data.skill_list,
+                skill_points: bot_data.skill_points,
+                stamina: bot_data.stamina,
+                stat_points: bot_data.stat_points,
+                status_effects,
+                status_effects_regen,
+                team: Team::default_character(),
+                union_membership: bot_data.union_membership,
+                clan_membership: ClanMembership::default(),
+            },
+        ))
+        .id()
+}
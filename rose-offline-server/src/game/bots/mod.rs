@@ -17,7 +17,7 @@ mod create_bot;
 pub use create_bot::{
     bot_build_artisan, bot_build_bourgeois, bot_build_champion, bot_build_cleric, bot_build_knight,
     bot_build_mage, bot_build_raider, bot_build_scout, bot_create_random_build,
-    bot_create_with_build, BotBuild,
+    bot_create_with_build, bot_level_range_for_zone, bot_spawn_random_build, BotBuild,
 };
 
 use bot_accept_party_invite::{
@@ -63,7 +63,12 @@ use big_brain::{
 };
 use std::time::Duration;
 
-use crate::game::components::{ClientEntity, Dead};
+use rose_data::SkillPageKind;
+
+use crate::game::{
+    components::{ClientEntity, Dead, SkillList, SkillPage},
+    GameData,
+};
 
 const IDLE_DURATION: Duration = Duration::from_millis(250);
 
@@ -75,6 +80,16 @@ pub struct BotCombatTarget {
     entity: Entity,
 }
 
+fn bot_get_active_skill_page<'a>(
+    game_data: &GameData,
+    skill_list: &'a SkillList,
+) -> Option<&'a SkillPage> {
+    let page_type = game_data
+        .data_decoder
+        .encode_skill_page_kind(SkillPageKind::Active)?;
+    skill_list.get_page(page_type)
+}
+
 pub struct BotPlugin;
 
 impl Plugin for BotPlugin {
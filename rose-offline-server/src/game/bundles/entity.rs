@@ -17,7 +17,7 @@ use crate::game::{
         MoveMode, MoveSpeed, NextCommand, Npc, NpcAi, NpcStandingDirection, ObjectVariables, Owner,
         OwnerExpireTime, PartyMembership, PartyOwner, PassiveRecoveryTime, Position, QuestState,
         SkillList, SkillPoints, SpawnOrigin, Stamina, StatPoints, StatusEffects,
-        StatusEffectsRegen, Team, UnionMembership,
+        StatusEffectsRegen, Team, UnionMembership, ZoneChange,
     },
     messages::server::ServerMessage,
     resources::ClientEntityList,
@@ -28,7 +28,6 @@ pub const EVENT_OBJECT_VARIABLES_COUNT: usize = 20;
 pub const NPC_OBJECT_VARIABLES_COUNT: usize = 20;
 pub const MONSTER_OBJECT_VARIABLES_COUNT: usize = 5;
 pub const ITEM_DROP_ENTITY_EXPIRE_TIME: Duration = Duration::from_secs(120);
-pub const ITEM_DROP_OWNER_EXPIRE_TIME: Duration = Duration::from_secs(60);
 pub const ITEM_DROP_RADIUS: i32 = 200;
 
 #[derive(Bundle)]
@@ -121,9 +120,10 @@ impl MonsterBundle {
         summon_skill_level: Option<i32>,
     ) -> Option<Entity> {
         let npc_data = game_data.npcs.get_npc(npc_id)?;
-        let npc_ai = Some(npc_data.ai_file_index)
-            .filter(|ai_file_index| *ai_file_index != 0)
-            .map(|ai_file_index| NpcAi::new(ai_file_index as usize));
+        let npc_ai = game_data
+            .ai
+            .get_npc_ai_index(npc_id, npc_data.ai_file_index as usize)
+            .map(NpcAi::new);
 
         let status_effects = StatusEffects::new();
         let status_effects_regen = StatusEffectsRegen::new();
@@ -200,6 +200,44 @@ impl MonsterBundle {
 
         Some(entity)
     }
+
+    /// Spawns a pet next to its owner, whose life decreases over time
+    pub fn spawn_summon(
+        commands: &mut Commands,
+        client_entity_list: &mut ClientEntityList,
+        game_data: &GameData,
+        npc_id: NpcId,
+        owner_entity: Entity,
+        owner_position: &Position,
+        owner_team: &Team,
+        owner_level: &Level,
+        summon_level: i32,
+    ) -> Option<Entity> {
+        let entity = Self::spawn(
+            commands,
+            client_entity_list,
+            game_data,
+            npc_id,
+            owner_position.zone_id,
+            SpawnOrigin::Summoned(owner_entity, owner_position.position),
+            150,
+            owner_team.clone(),
+            Some((owner_entity, owner_level)),
+            Some(summon_level),
+        )?;
+
+        // Apply status effect to decrease summon's life over time
+        if let Some(status_effect_data) = game_data
+            .status_effects
+            .get_decrease_summon_life_status_effect()
+        {
+            let mut status_effects = StatusEffects::new();
+            status_effects.apply_summon_decrease_life_status_effect(status_effect_data);
+            commands.entity(entity).insert(status_effects);
+        }
+
+        Some(entity)
+    }
 }
 
 #[derive(Bundle)]
@@ -217,19 +255,12 @@ impl ItemDropBundle {
         client_entity_list: &mut ClientEntityList,
         item: DroppedItem,
         position: &Position,
-        owner_entity: Option<Entity>,
+        owner: Option<(Entity, Duration)>,
         party_owner_entity: Option<Entity>,
         time: &Time,
     ) -> Option<Entity> {
-        let mut rng = rand::thread_rng();
-
-        let drop_point = Vec3::new(
-            position.position.x + rng.gen_range(-ITEM_DROP_RADIUS..=ITEM_DROP_RADIUS) as f32,
-            position.position.y + rng.gen_range(-ITEM_DROP_RADIUS..=ITEM_DROP_RADIUS) as f32,
-            position.position.z,
-        );
-
-        let drop_position = Position::new(drop_point, position.zone_id);
+        // The drop is scattered around this position by item_drop_placement_system
+        let drop_position = position.clone();
 
         let mut entity_commands = commands.spawn(ItemDropBundle {
             drop: ItemDrop::with_dropped_item(item),
@@ -240,10 +271,10 @@ impl ItemDropBundle {
         });
         let entity = entity_commands.id();
 
-        if let Some(owner_entity) = owner_entity {
+        if let Some((owner_entity, owner_duration)) = owner {
             entity_commands.insert((
                 Owner::new(owner_entity),
-                OwnerExpireTime::new(time.last_update().unwrap() + ITEM_DROP_OWNER_EXPIRE_TIME),
+                OwnerExpireTime::new(time.last_update().unwrap() + owner_duration),
             ));
         }
 
@@ -332,6 +363,8 @@ pub fn client_entity_teleport_zone(
     ));
 
     if let Some(game_client) = game_client {
+        commands.entity(entity).insert(ZoneChange::default());
+
         game_client
             .server_message_tx
             .send(ServerMessage::Teleport {
@@ -1,5 +1,6 @@
 mod ability_values;
 mod entity;
+mod pvp;
 mod skill_list;
 mod skill_use;
 
@@ -9,14 +10,15 @@ pub use ability_values::{
 pub use entity::{
     client_entity_join_zone, client_entity_leave_zone, client_entity_teleport_zone,
     CharacterBundle, ItemDropBundle, MonsterBundle, NpcBundle, EVENT_OBJECT_VARIABLES_COUNT,
-    MONSTER_OBJECT_VARIABLES_COUNT, NPC_OBJECT_VARIABLES_COUNT,
+    ITEM_DROP_RADIUS, MONSTER_OBJECT_VARIABLES_COUNT, NPC_OBJECT_VARIABLES_COUNT,
 };
+pub use pvp::{pvp_check, PvpCheck, PvpDenied};
 pub use skill_list::{
     can_learn_skill, can_level_up_skill, skill_list_try_learn_skill, skill_list_try_level_up_skill,
     SkillListBundle,
 };
 pub use skill_use::{
     skill_can_target_entity, skill_can_target_position, skill_can_target_self, skill_can_use,
-    SkillCasterBundle, SkillCasterBundleItem, SkillTargetBundle, SkillTargetBundleItem,
-    GLOBAL_SKILL_COOLDOWN,
+    skill_check_combo, SkillCasterBundle, SkillCasterBundleItem, SkillTargetBundle,
+    SkillTargetBundleItem, GLOBAL_SKILL_COOLDOWN,
 };
@@ -0,0 +1,84 @@
+use bevy::math::Vec3Swizzles;
+
+use crate::game::{
+    components::{ClientEntity, Position, Team},
+    GameData,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PvpDenied {
+    Disabled,
+    OutsideSchedule,
+    SafeArea,
+}
+
+impl PvpDenied {
+    pub fn message(&self) -> &'static str {
+        match self {
+            PvpDenied::Disabled => "PvP is not allowed in this zone.",
+            PvpDenied::OutsideSchedule => "PvP is not allowed in this zone at this time.",
+            PvpDenied::SafeArea => "PvP is not allowed inside a safe area.",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PvpCheck {
+    /// The zone has no PvP rules, or the attack is not between two characters
+    NoRules,
+    Allowed,
+    Denied(PvpDenied),
+}
+
+impl PvpCheck {
+    /// Characters covered by zone PvP rules are enemies only while the rules allow it,
+    /// otherwise entities on different teams are enemies.
+    pub fn is_enemy(&self, attacker_team: &Team, target_team: &Team) -> bool {
+        match self {
+            PvpCheck::NoRules => attacker_team.id != target_team.id,
+            PvpCheck::Allowed => true,
+            PvpCheck::Denied(_) => false,
+        }
+    }
+}
+
+/// Checks the PvP rules of the zone for one character attacking another.
+pub fn pvp_check(
+    game_data: &GameData,
+    attacker_client_entity: &ClientEntity,
+    attacker_position: &Position,
+    target_client_entity: &ClientEntity,
+    target_position: &Position,
+) -> PvpCheck {
+    if !attacker_client_entity.is_character() || !target_client_entity.is_character() {
+        return PvpCheck::NoRules;
+    }
+
+    let Some(zone_data) = game_data.zones.get_zone(attacker_position.zone_id) else {
+        return PvpCheck::NoRules;
+    };
+    let Some(zone_pvp) = zone_data.pvp.as_ref() else {
+        return PvpCheck::NoRules;
+    };
+
+    if !zone_pvp.pvp {
+        return PvpCheck::Denied(PvpDenied::Disabled);
+    }
+
+    if !zone_pvp.schedule.is_empty()
+        && !zone_pvp
+            .schedule
+            .iter()
+            .any(|window| window.is_active_now())
+    {
+        return PvpCheck::Denied(PvpDenied::OutsideSchedule);
+    }
+
+    if zone_data.is_pvp_safe_area(attacker_position.position.xy())
+        || zone_data.is_pvp_safe_area(target_position.position.xy())
+    {
+        return PvpCheck::Denied(PvpDenied::SafeArea);
+    }
+
+    PvpCheck::Allowed
+}
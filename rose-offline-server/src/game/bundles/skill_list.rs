@@ -1,7 +1,7 @@
 use rose_data::{JobId, SkillData, SkillId};
 use rose_game_common::components::{
     AbilityValues, CharacterInfo, ExperiencePoints, HealthPoints, Inventory, Level, ManaPoints,
-    MoveSpeed, Stamina, StatPoints, Team, UnionMembership,
+    Money, MoveSpeed, Stamina, StatPoints, Team, UnionMembership,
 };
 
 use crate::game::{
@@ -22,7 +22,7 @@ pub struct SkillListBundle<'w> {
     pub team: Option<&'w Team>,
     pub character_info: Option<&'w CharacterInfo>,
     pub experience_points: Option<&'w ExperiencePoints>,
-    pub inventory: Option<&'w Inventory>,
+    pub inventory: Option<&'w mut Inventory>,
     pub stamina: Option<&'w Stamina>,
     pub stat_points: Option<&'w StatPoints>,
     pub union_membership: Option<&'w UnionMembership>,
@@ -39,6 +39,12 @@ fn check_skill_point_requirements(
     })
 }
 
+fn check_skill_money_requirements(skill_data: &SkillData, inventory: Option<&Inventory>) -> bool {
+    inventory.map_or(true, |inventory| {
+        inventory.money >= Money(skill_data.learn_money_cost as i64)
+    })
+}
+
 fn check_skill_job_requirements(
     game_data: &GameData,
     skill_data: &SkillData,
@@ -91,7 +97,7 @@ fn check_skill_ability_requirements(skill_data: &SkillData, skill_user: &SkillLi
             skill_user.team,
             skill_user.character_info,
             skill_user.experience_points,
-            skill_user.inventory,
+            skill_user.inventory.as_deref(),
             skill_user.skill_points.as_deref(),
             skill_user.stamina,
             skill_user.stat_points,
@@ -201,6 +207,10 @@ pub fn can_level_up_skill(
         return Err(LevelUpSkillError::SkillPointRequirement);
     }
 
+    if !check_skill_money_requirements(next_skill_data, skill_user.inventory.as_deref()) {
+        return Err(LevelUpSkillError::MoneyRequirement);
+    }
+
     if let Some(character_info) = skill_user.character_info {
         if !check_skill_job_requirements(game_data, next_skill_data, character_info) {
             return Err(LevelUpSkillError::JobRequirement);
@@ -230,6 +240,10 @@ fn try_level_up_skill(
     let next_skill_id = SkillId::new(current_skill_id.get() + 1).unwrap();
 
     let skill_point_cost = can_level_up_skill(game_data, skill_user, skill_slot)?;
+    let money_cost = game_data
+        .skills
+        .get_skill(next_skill_id)
+        .map_or(0, |skill_data| skill_data.learn_money_cost);
 
     let skill_list_slot = skill_user
         .skill_list
@@ -241,6 +255,21 @@ fn try_level_up_skill(
         skill_points.points -= skill_point_cost.points;
     }
 
+    if money_cost > 0 {
+        if let Some(inventory) = skill_user.inventory.as_deref_mut() {
+            inventory.money = inventory.money - Money(money_cost as i64);
+
+            if let Some(game_client) = skill_user.game_client {
+                game_client
+                    .server_message_tx
+                    .send(ServerMessage::UpdateMoney {
+                        money: inventory.money,
+                    })
+                    .ok();
+            }
+        }
+    }
+
     Ok(next_skill_id)
 }
 
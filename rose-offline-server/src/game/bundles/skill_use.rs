@@ -7,10 +7,11 @@ use rose_data::{
 };
 
 use crate::game::{
+    bundles::pvp_check,
     components::{
         AbilityValues, ClanMembership, ClientEntity, ClientEntityType, Cooldowns, Equipment,
-        ExperiencePoints, HealthPoints, Inventory, ManaPoints, MoveMode, PartyMembership, Stamina,
-        Team,
+        ExperiencePoints, HealthPoints, Inventory, ManaPoints, MoveMode, PartyMembership, Position,
+        Stamina, Team, Weight,
     },
     GameData,
 };
@@ -25,6 +26,7 @@ pub struct SkillCasterBundle<'w> {
     pub client_entity: &'w ClientEntity,
     pub health_points: &'w HealthPoints,
     pub move_mode: &'w MoveMode,
+    pub position: &'w Position,
     pub team: &'w Team,
 
     pub clan_membership: Option<&'w ClanMembership>,
@@ -35,6 +37,7 @@ pub struct SkillCasterBundle<'w> {
     pub mana_points: Option<&'w ManaPoints>,
     pub party_membership: Option<&'w PartyMembership>,
     pub stamina: Option<&'w Stamina>,
+    pub weight: Option<&'w Weight>,
 }
 
 #[derive(WorldQuery)]
@@ -43,6 +46,7 @@ pub struct SkillTargetBundle<'w> {
 
     pub client_entity: &'w ClientEntity,
     pub health_points: &'w HealthPoints,
+    pub position: &'w Position,
     pub team: &'w Team,
 
     pub clan_membership: Option<&'w ClanMembership>,
@@ -88,14 +92,33 @@ fn check_skill_cooldown(
     true
 }
 
+pub fn skill_check_combo(
+    skill_caster: &SkillCasterBundleItem,
+    now: Instant,
+    skill_data: &SkillData,
+) -> bool {
+    let Some(required_combo_skill_id) = skill_data.required_combo_skill_id else {
+        return true;
+    };
+
+    skill_caster
+        .cooldowns
+        .and_then(|cooldowns| cooldowns.combo)
+        .map_or(false, |(combo_skill_id, combo_skill_time)| {
+            combo_skill_id == required_combo_skill_id
+                && now < combo_skill_time + skill_data.combo_timeout
+        })
+}
+
 fn check_not_disabled(_skill_caster: &SkillCasterBundleItem) -> bool {
     // TODO: Check not muted / sleep / fainted / stunned
     true
 }
 
-fn check_weight(_skill_caster: &SkillCasterBundleItem) -> bool {
-    // TODO: Check weight not too heavy to use skills (110%)
-    true
+fn check_weight(skill_caster: &SkillCasterBundleItem) -> bool {
+    !skill_caster
+        .weight
+        .map_or(false, |weight| weight.is_max_overweight())
 }
 
 fn check_move_mode(skill_caster: &SkillCasterBundleItem, _skill_data: &SkillData) -> bool {
@@ -103,12 +126,24 @@ fn check_move_mode(skill_caster: &SkillCasterBundleItem, _skill_data: &SkillData
 }
 
 fn check_skill_target_filter(
+    game_data: &GameData,
     skill_caster: &SkillCasterBundleItem,
     skill_target: &SkillTargetBundleItem,
     skill_data: &SkillData,
 ) -> bool {
     let target_is_alive = skill_target.health_points.hp > 0;
     let target_is_caster = skill_caster.entity == skill_target.entity;
+    let target_is_enemy = || {
+        !target_is_caster
+            && pvp_check(
+                game_data,
+                skill_caster.client_entity,
+                skill_caster.position,
+                skill_target.client_entity,
+                skill_target.position,
+            )
+            .is_enemy(skill_caster.team, skill_target.team)
+    };
 
     match skill_data.target_filter {
         SkillTargetFilter::OnlySelf => target_is_alive && target_is_caster,
@@ -145,11 +180,11 @@ fn check_skill_target_filter(
         SkillTargetFilter::Enemy => {
             target_is_alive
                 && skill_target.team.id != Team::DEFAULT_NPC_TEAM_ID
-                && skill_caster.team.id != skill_target.team.id
+                && target_is_enemy()
         }
         SkillTargetFilter::EnemyCharacter => {
             target_is_alive
-                && skill_caster.team.id != skill_target.team.id
+                && target_is_enemy()
                 && matches!(
                     skill_target.client_entity.entity_type,
                     ClientEntityType::Character
@@ -180,7 +215,7 @@ fn check_skill_target_filter(
         }
         SkillTargetFilter::EnemyMonster => {
             target_is_alive
-                && skill_caster.team.id != skill_target.team.id
+                && target_is_enemy()
                 && matches!(
                     skill_target.client_entity.entity_type,
                     ClientEntityType::Monster
@@ -314,6 +349,10 @@ pub fn skill_can_use(
         return false;
     }
 
+    if !skill_check_combo(skill_caster, now, skill_data) {
+        return false;
+    }
+
     if !check_not_disabled(skill_caster) {
         return false;
     }
@@ -342,24 +381,31 @@ pub fn skill_can_use(
 }
 
 pub fn skill_can_target_entity(
+    game_data: &GameData,
     skill_caster: &SkillCasterBundleItem,
     skill_target: &SkillTargetBundleItem,
     skill_data: &SkillData,
 ) -> bool {
-    if !check_skill_target_filter(skill_caster, skill_target, skill_data) {
+    if !check_skill_target_filter(game_data, skill_caster, skill_target, skill_data) {
         return false;
     }
 
     true
 }
 
-pub fn skill_can_target_self(skill_caster: &SkillCasterBundleItem, skill_data: &SkillData) -> bool {
+pub fn skill_can_target_self(
+    game_data: &GameData,
+    skill_caster: &SkillCasterBundleItem,
+    skill_data: &SkillData,
+) -> bool {
     if !check_skill_target_filter(
+        game_data,
         skill_caster,
         &SkillTargetBundleItem {
             entity: skill_caster.entity,
             client_entity: skill_caster.client_entity,
             health_points: skill_caster.health_points,
+            position: skill_caster.position,
             clan_membership: skill_caster.clan_membership,
             party_membership: skill_caster.party_membership,
             team: skill_caster.team,
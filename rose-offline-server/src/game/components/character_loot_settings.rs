@@ -0,0 +1,8 @@
+use bevy::ecs::prelude::Component;
+use serde::{Deserialize, Serialize};
+
+#[derive(Component, Clone, Copy, Debug, Default, Deserialize, Serialize)]
+pub struct CharacterLootSettings {
+    /// Drops from our kills go straight into the inventory instead of onto the ground
+    pub auto_loot: bool,
+}
@@ -0,0 +1,16 @@
+use bevy::ecs::prelude::Component;
+use serde::{Deserialize, Serialize};
+
+#[derive(Component, Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct CharacterPrivacy {
+    /// Allow other characters to inspect our equipment
+    pub allow_inspect: bool,
+}
+
+impl Default for CharacterPrivacy {
+    fn default() -> Self {
+        Self {
+            allow_inspect: true,
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use bevy::ecs::prelude::Component;
+use serde::{Deserialize, Serialize};
+
+use rose_data::SkillId;
+
+use crate::game::components::SkillSlot;
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct SkillMasteryEntry {
+    pub skill_slot: SkillSlot,
+    /// The base skill the experience was earned with, a different skill in the slot starts over
+    #[serde(default)]
+    pub base_skill_id: Option<SkillId>,
+    pub xp: u32,
+}
+
+/// Skill experience earned by using skills, kept per skill slot so it
+/// carries over when the skill in that slot is levelled up.
+#[derive(Component, Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct CharacterSkillMastery {
+    pub skills: Vec<SkillMasteryEntry>,
+}
+
+impl CharacterSkillMastery {
+    pub fn get_xp(&self, skill_slot: SkillSlot, base_skill_id: SkillId) -> u32 {
+        self.skills
+            .iter()
+            .find(|entry| entry.skill_slot == skill_slot)
+            .filter(|entry| entry.base_skill_id == Some(base_skill_id))
+            .map_or(0, |entry| entry.xp)
+    }
+
+    /// Returns the new total skill experience for the slot
+    pub fn add_xp(&mut self, skill_slot: SkillSlot, base_skill_id: SkillId, xp: u32) -> u32 {
+        if let Some(entry) = self
+            .skills
+            .iter_mut()
+            .find(|entry| entry.skill_slot == skill_slot)
+        {
+            if entry.base_skill_id != Some(base_skill_id) {
+                entry.base_skill_id = Some(base_skill_id);
+                entry.xp = 0;
+            }
+
+            entry.xp = entry.xp.saturating_add(xp);
+            entry.xp
+        } else {
+            self.skills.push(SkillMasteryEntry {
+                skill_slot,
+                base_skill_id: Some(base_skill_id),
+                xp,
+            });
+            xp
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use bevy::ecs::prelude::Component;
+use serde::{Deserialize, Serialize};
+
+#[derive(Component, Clone, Debug, Default, Deserialize, Serialize)]
+pub struct CharacterTitles {
+    pub earned: Vec<u16>,
+    /// The earned title displayed to other characters
+    pub active: Option<u16>,
+}
+
+impl CharacterTitles {
+    pub fn has_title(&self, id: u16) -> bool {
+        self.earned.contains(&id)
+    }
+}
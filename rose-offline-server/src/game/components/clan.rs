@@ -1,7 +1,15 @@
 use bevy::prelude::{Component, Deref, DerefMut, Entity};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
-use rose_data::{ClanMemberPosition, SkillId};
-use rose_game_common::components::{ClanLevel, ClanMark, ClanPoints, ClanUniqueId, Level, Money};
+use rose_data::{ClanMemberPosition, Item, SkillId};
+use rose_game_common::{
+    components::{ClanLevel, ClanMark, ClanPermissions, ClanPoints, ClanUniqueId, Level, Money},
+    messages::server::{ClanApplication, ClanVaultLogEntry},
+};
+
+const CLAN_VAULT_MAX_SLOTS: usize = 120;
+const CLAN_VAULT_MAX_LOG_ENTRIES: usize = 200;
 
 #[derive(Component, Clone, Default, Deref, DerefMut)]
 pub struct ClanMembership(pub Option<Entity>);
@@ -16,17 +24,26 @@ impl ClanMembership {
     }
 }
 
+/// What a member has given to their clan.
+#[derive(Copy, Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ClanMemberStats {
+    pub donated_money: Money,
+    pub completed_quests: u32,
+}
+
 #[derive(Clone)]
 pub enum ClanMember {
     Online {
         entity: Entity,
         position: ClanMemberPosition,
         contribution: ClanPoints,
+        stats: ClanMemberStats,
     },
     Offline {
         name: String,
         position: ClanMemberPosition,
         contribution: ClanPoints,
+        stats: ClanMemberStats,
         level: Level,
         job: u16,
     },
@@ -46,6 +63,72 @@ impl ClanMember {
             ClanMember::Offline { contribution, .. } => *contribution,
         }
     }
+
+    pub fn stats(&self) -> ClanMemberStats {
+        match self {
+            ClanMember::Online { stats, .. } => *stats,
+            ClanMember::Offline { stats, .. } => *stats,
+        }
+    }
+}
+
+/// Items shared between all members of a clan, with a log of the most recent transactions.
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct ClanVault {
+    pub slots: Vec<Option<Item>>,
+    pub log: Vec<ClanVaultLogEntry>,
+}
+
+impl ClanVault {
+    pub fn try_add_item(&mut self, item: Item) -> Result<(usize, &Item), Item> {
+        // Stackable items first try to stack with an existing item
+        let mut index = match &item {
+            Item::Equipment(_) => None,
+            Item::Stackable(stackable) => self.slots.iter().position(|slot| {
+                slot.as_ref().map_or(false, |slot_item| {
+                    slot_item.can_stack_with(stackable).is_ok()
+                })
+            }),
+        };
+
+        if index.is_none() {
+            index = self.slots.iter().position(|slot| slot.is_none());
+        }
+
+        if index.is_none() && self.slots.len() < CLAN_VAULT_MAX_SLOTS {
+            index = Some(self.slots.len());
+            self.slots.push(None);
+        }
+
+        let Some(index) = index else {
+            return Err(item);
+        };
+
+        if self.slots[index].is_none() {
+            self.slots[index] = Some(item);
+        } else {
+            self.slots[index]
+                .as_mut()
+                .unwrap()
+                .try_stack_with_item(item)
+                .expect("how did we get here");
+        }
+
+        Ok((index, self.slots[index].as_ref().unwrap()))
+    }
+
+    pub fn add_log_entry(&mut self, entry: ClanVaultLogEntry) {
+        if self.log.len() >= CLAN_VAULT_MAX_LOG_ENTRIES {
+            self.log.remove(0);
+        }
+        self.log.push(entry);
+    }
+}
+
+/// A character invited to join a clan, which expires if they do not reply in time.
+pub struct ClanPendingInvite {
+    pub entity: Entity,
+    pub expire_time: Duration,
 }
 
 #[derive(Component)]
@@ -53,15 +136,34 @@ pub struct Clan {
     pub unique_id: ClanUniqueId,
     pub name: String,
     pub description: String,
+    pub notice: String,
     pub money: Money,
     pub points: ClanPoints,
     pub level: ClanLevel,
     pub members: Vec<ClanMember>,
     pub mark: ClanMark,
     pub skills: Vec<SkillId>,
+    pub vault: ClanVault,
+    pub permissions: ClanPermissions,
+    /// Notice shown on the recruitment board, the clan is not listed when empty.
+    pub recruitment: String,
+    /// Characters which have applied to join the clan from the recruitment board.
+    pub applications: Vec<ClanApplication>,
+    /// Characters which have been invited to join the clan but not yet replied.
+    pub pending_invites: Vec<ClanPendingInvite>,
 }
 
 impl Clan {
+    pub fn find_online_member_index(&self, entity: Entity) -> Option<usize> {
+        self.members.iter().position(|member| match member {
+            ClanMember::Online {
+                entity: member_entity,
+                ..
+            } => *member_entity == entity,
+            _ => false,
+        })
+    }
+
     pub fn find_online_member(&self, entity: Entity) -> Option<&ClanMember> {
         self.members.iter().find(|member| match member {
             ClanMember::Online {
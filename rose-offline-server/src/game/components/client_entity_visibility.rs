@@ -1,10 +1,20 @@
 use bevy::prelude::{Component, Deref, DerefMut};
+use std::{collections::HashMap, time::Duration};
 
-use crate::game::resources::ClientEntitySet;
+use crate::game::{messages::server::ServerMessage, resources::ClientEntitySet};
+
+/// The most recent movement update sent to an observer for a visible entity.
+pub struct ThrottledMovement {
+    pub last_sent: Duration,
+    pub pending: Option<ServerMessage>,
+}
 
 #[derive(Component, Default, Deref, DerefMut)]
 pub struct ClientEntityVisibility {
+    #[deref]
     pub entities: ClientEntitySet,
+    /// Keyed by ClientEntityId index
+    pub movement_updates: HashMap<usize, ThrottledMovement>,
 }
 
 impl ClientEntityVisibility {
@@ -39,6 +39,10 @@ pub enum CommandData {
     PickupItemDrop {
         target: Entity,
     },
+    /// Gathering from an event object, interrupted by any other command
+    Gather {
+        target: Entity,
+    },
     PersonalStore,
     CastSkill {
         skill_id: SkillId,
@@ -57,6 +61,8 @@ pub enum CommandData {
     /// Transition away from Sit
     Standing,
 
+    /// Character emotes with is_stop set are held until another command, otherwise they end with
+    /// the motion
     Emote {
         motion_id: MotionId,
         is_stop: bool,
@@ -186,6 +192,10 @@ impl Command {
         Self::new(CommandData::PickupItemDrop { target }, Some(duration))
     }
 
+    pub fn with_gather(target: Entity, duration: Duration) -> Self {
+        Self::new(CommandData::Gather { target }, Some(duration))
+    }
+
     pub fn with_sit() -> Self {
         Self::new(CommandData::Sit, None)
     }
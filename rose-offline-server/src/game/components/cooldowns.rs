@@ -11,4 +11,10 @@ pub struct Cooldowns {
     pub skill: HashMap<SkillId, Instant>,
     pub skill_global: Option<Instant>,
     pub skill_group: [Option<Instant>; MAX_SKILL_COOLDOWN_GROUPS],
+    /// Base skill id of the last skill used, and when it was used
+    pub combo: Option<(SkillId, Instant)>,
+    /// When each consumable item cooldown group can next be used
+    pub item_group: HashMap<usize, Instant>,
+    /// When we can next inspect another character
+    pub inspect: Option<Instant>,
 }
@@ -2,8 +2,24 @@ use std::time::Instant;
 
 use bevy::ecs::prelude::{Component, Entity};
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DamageAttacker {
+    Entity(Entity),
+    /// Damage with no living attacker, such as event objects or status effects
+    Environment,
+}
+
+impl DamageAttacker {
+    pub fn entity(&self) -> Option<Entity> {
+        match *self {
+            DamageAttacker::Entity(entity) => Some(entity),
+            DamageAttacker::Environment => None,
+        }
+    }
+}
+
 pub struct DamageSource {
-    pub entity: Entity,
+    pub attacker: DamageAttacker,
     pub total_damage: usize,
     pub first_damage_time: Instant,
     pub last_damage_time: Instant,
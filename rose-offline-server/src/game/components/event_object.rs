@@ -3,10 +3,16 @@ use bevy::ecs::prelude::Component;
 #[derive(Component)]
 pub struct EventObject {
     pub event_id: u16,
+    pub map_chunk_x: i32,
+    pub map_chunk_y: i32,
 }
 
 impl EventObject {
-    pub fn new(event_id: u16) -> Self {
-        Self { event_id }
+    pub fn new(event_id: u16, map_chunk_x: i32, map_chunk_y: i32) -> Self {
+        Self {
+            event_id,
+            map_chunk_x,
+            map_chunk_y,
+        }
     }
 }
@@ -0,0 +1,31 @@
+use bevy::ecs::prelude::Component;
+use std::time::Duration;
+
+/// The maximum distance a character can gather from
+pub const GATHER_NODE_DISTANCE: f32 = 300.0;
+
+#[derive(Component)]
+pub struct GatherNode {
+    /// Index into the gather node database
+    pub node_index: usize,
+
+    /// Number of gathers left before the node is depleted
+    pub remaining_gathers: u32,
+
+    /// How long the node has been depleted for
+    pub time_since_depleted: Duration,
+}
+
+impl GatherNode {
+    pub fn new(node_index: usize, max_gathers: u32) -> Self {
+        Self {
+            node_index,
+            remaining_gathers: max_gathers,
+            time_since_depleted: Duration::ZERO,
+        }
+    }
+
+    pub fn is_depleted(&self) -> bool {
+        self.remaining_gathers == 0
+    }
+}
@@ -0,0 +1,16 @@
+use std::time::Duration;
+
+use bevy::prelude::Component;
+
+/// Added to a character whose game client disconnected, keeping the character in the world
+/// until the client resumes the session or the reconnect grace period expires.
+#[derive(Component)]
+pub struct LinkDead {
+    pub disconnected_at: Duration,
+}
+
+impl LinkDead {
+    pub fn new(disconnected_at: Duration) -> Self {
+        Self { disconnected_at }
+    }
+}
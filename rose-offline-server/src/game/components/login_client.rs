@@ -1,25 +1,29 @@
+use std::net::IpAddr;
+
 use bevy::ecs::prelude::Component;
 use crossbeam_channel::Receiver;
-use tokio::sync::mpsc::UnboundedSender;
 
-use rose_game_common::messages::{client::ClientMessage, server::ServerMessage};
+use crate::game::messages::{client::ClientMessage, server_queue::ServerMessageSender};
 
 #[derive(Component)]
 pub struct LoginClient {
     pub client_message_rx: Receiver<ClientMessage>,
-    pub server_message_tx: UnboundedSender<ServerMessage>,
+    pub server_message_tx: ServerMessageSender,
     pub login_token: u32,
+    pub ip_address: Option<IpAddr>,
 }
 
 impl LoginClient {
     pub fn new(
         client_message_rx: Receiver<ClientMessage>,
-        server_message_tx: UnboundedSender<ServerMessage>,
+        server_message_tx: ServerMessageSender,
+        ip_address: Option<IpAddr>,
     ) -> Self {
         Self {
             client_message_rx,
             server_message_tx,
             login_token: 0u32,
+            ip_address,
         }
     }
 }
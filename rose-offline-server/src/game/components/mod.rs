@@ -1,6 +1,10 @@
 mod account;
 mod bank;
 mod character_list;
+mod character_loot_settings;
+mod character_privacy;
+mod character_skill_mastery;
+mod character_titles;
 mod clan;
 mod client_entity;
 mod client_entity_sector;
@@ -13,9 +17,13 @@ mod driving_time;
 mod entity_expire_time;
 mod event_object;
 mod game_client;
+mod gather_node;
+mod link_dead;
 mod login_client;
 mod monster_spawn_point;
 mod motion_data;
+mod move_path;
+mod new_character;
 mod next_command;
 mod npc_ai;
 mod npc_standing_direction;
@@ -30,44 +38,55 @@ mod personal_store;
 mod position;
 mod server_info;
 mod spawn_origin;
+mod spectator;
+mod toggle_skill;
 mod weight;
 mod world_client;
+mod zone_change;
 
 pub use rose_game_common::components::{
     AbilityValues, ActiveQuest, ActiveStatusEffect, ActiveStatusEffectRegen, BasicStatType,
-    BasicStats, CharacterDeleteTime, CharacterGender, CharacterInfo, CharacterUniqueId,
-    DamageCategory, DamageType, DroppedItem, Equipment, EquipmentItemDatabase,
+    BasicStats, CharacterDeleteTime, CharacterGender, CharacterInfo, CharacterStatistics,
+    CharacterUniqueId, DamageCategory, DamageType, DroppedItem, Equipment, EquipmentItemDatabase,
     EquipmentItemReference, ExperiencePoints, HealthPoints, Hotbar, HotbarSlot, Inventory,
     InventoryPage, InventoryPageType, ItemDrop, ItemSlot, Level, ManaPoints, Money, MoveMode,
     MoveSpeed, Npc, QuestState, SkillList, SkillPage, SkillPoints, SkillSlot, Stamina, StatPoints,
-    StatusEffects, StatusEffectsRegen, Team, UnionMembership, MAX_STAMINA,
+    StatusEffects, StatusEffectsRegen, Team, UnionMembership, INVENTORY_PAGE_SIZE, MAX_STAMINA,
 };
 
 pub use account::Account;
 pub use bank::Bank;
 pub use character_list::CharacterList;
-pub use clan::{Clan, ClanMember, ClanMembership};
+pub use character_loot_settings::CharacterLootSettings;
+pub use character_privacy::CharacterPrivacy;
+pub use character_skill_mastery::{CharacterSkillMastery, SkillMasteryEntry};
+pub use character_titles::CharacterTitles;
+pub use clan::{Clan, ClanMember, ClanMemberStats, ClanMembership, ClanPendingInvite, ClanVault};
 pub use client_entity::{ClientEntity, ClientEntityId, ClientEntityType};
 pub use client_entity_sector::ClientEntitySector;
-pub use client_entity_visibility::ClientEntityVisibility;
+pub use client_entity_visibility::{ClientEntityVisibility, ThrottledMovement};
 pub use command::{Command, CommandCastSkillTarget, CommandData};
 pub use cooldowns::Cooldowns;
-pub use damage_sources::{DamageSource, DamageSources};
+pub use damage_sources::{DamageAttacker, DamageSource, DamageSources};
 pub use dead::Dead;
 pub use driving_time::DrivingTime;
 pub use entity_expire_time::EntityExpireTime;
 pub use event_object::EventObject;
 pub use game_client::GameClient;
+pub use gather_node::{GatherNode, GATHER_NODE_DISTANCE};
+pub use link_dead::LinkDead;
 pub use login_client::LoginClient;
 pub use monster_spawn_point::MonsterSpawnPoint;
 pub use motion_data::{MotionData, MotionDataCharacter, MotionDataNpc};
+pub use move_path::MovePath;
+pub use new_character::NewCharacter;
 pub use next_command::NextCommand;
 pub use npc_ai::NpcAi;
 pub use npc_standing_direction::NpcStandingDirection;
 pub use object_variables::ObjectVariables;
 pub use owner::Owner;
 pub use owner_expire_time::OwnerExpireTime;
-pub use party::{Party, PartyMember};
+pub use party::{Party, PartyMember, PartyVoteKick};
 pub use party_membership::PartyMembership;
 pub use party_owner::PartyOwner;
 pub use passive_recovery_time::PassiveRecoveryTime;
@@ -75,5 +94,8 @@ pub use personal_store::{PersonalStore, PERSONAL_STORE_ITEM_SLOTS};
 pub use position::Position;
 pub use server_info::ServerInfo;
 pub use spawn_origin::SpawnOrigin;
-pub use weight::Weight;
+pub use spectator::Spectator;
+pub use toggle_skill::ActiveToggleSkill;
+pub use weight::{OverweightLevel, Weight};
 pub use world_client::WorldClient;
+pub use zone_change::{ZoneChange, ZoneChangeState};
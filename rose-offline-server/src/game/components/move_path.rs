@@ -0,0 +1,19 @@
+use std::collections::VecDeque;
+
+use bevy::{ecs::prelude::Component, math::Vec3};
+
+#[derive(Component, Default)]
+pub struct MovePath {
+    /// The destination which the waypoints were calculated for
+    pub destination: Option<Vec3>,
+    pub waypoints: VecDeque<Vec3>,
+}
+
+impl MovePath {
+    pub fn new(destination: Vec3, waypoints: VecDeque<Vec3>) -> Self {
+        Self {
+            destination: Some(destination),
+            waypoints,
+        }
+    }
+}
@@ -0,0 +1,6 @@
+use bevy::prelude::Component;
+
+/// Added to a character which has never joined a zone, removed once its starter status effects
+/// and tutorial triggers have been applied.
+#[derive(Component)]
+pub struct NewCharacter;
@@ -55,6 +55,13 @@ impl NextCommand {
         }
     }
 
+    pub fn with_gather(target: Entity) -> Self {
+        Self {
+            command: Some(CommandData::Gather { target }),
+            has_sent_server_message: false,
+        }
+    }
+
     pub fn with_sitting() -> Self {
         Self {
             command: Some(CommandData::Sitting),
@@ -11,4 +11,9 @@ impl ObjectVariables {
             variables: vec![0; count],
         }
     }
+
+    /// The first variable is the event status which is sent to clients
+    pub fn event_status(&self) -> i16 {
+        self.variables.first().copied().unwrap_or(0) as i16
+    }
 }
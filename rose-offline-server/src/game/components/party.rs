@@ -1,13 +1,15 @@
 use arrayvec::ArrayVec;
 use bevy::ecs::prelude::{Component, Entity};
 use enum_map::{enum_map, EnumMap};
+use std::time::Duration;
 
+use rose_data::ItemType;
 use rose_game_common::{
     components::InventoryPageType,
     messages::{PartyItemSharing, PartyXpSharing},
 };
 
-use crate::game::components::CharacterUniqueId;
+use crate::game::components::{CharacterUniqueId, Money};
 
 #[derive(Clone)]
 pub enum PartyMember {
@@ -24,6 +26,13 @@ impl PartyMember {
     }
 }
 
+/// A vote by the party members to kick another member.
+pub struct PartyVoteKick {
+    pub character_id: CharacterUniqueId,
+    pub started: Duration,
+    pub votes: ArrayVec<(CharacterUniqueId, bool), 5>,
+}
+
 #[derive(Component)]
 pub struct Party {
     pub owner: Entity,
@@ -34,6 +43,9 @@ pub struct Party {
     pub level: i32,
     pub acquire_item_order: EnumMap<InventoryPageType, usize>,
     pub acquire_money_order: usize,
+    /// Character id of the owner and when they disconnected, while waiting for them to reconnect
+    pub owner_disconnected: Option<(CharacterUniqueId, Duration)>,
+    pub vote_kick: Option<PartyVoteKick>,
 }
 
 impl Party {
@@ -55,6 +67,69 @@ impl Party {
                 _ => 0,
             },
             acquire_money_order: 0,
+            owner_disconnected: None,
+            vote_kick: None,
         }
     }
+
+    /// Chooses the next online member after the previous acquirer of `item_type` items, for
+    /// parties which take turns acquiring items.
+    pub fn next_item_acquirer(
+        &mut self,
+        item_type: ItemType,
+        is_eligible: impl Fn(Entity) -> bool,
+    ) -> Option<Entity> {
+        next_acquirer(
+            &self.members,
+            &mut self.acquire_item_order[item_type.into()],
+            is_eligible,
+        )
+    }
+
+    /// Chooses the next online member after the previous acquirer of money, for parties which
+    /// take turns acquiring items.
+    pub fn next_money_acquirer(&mut self, is_eligible: impl Fn(Entity) -> bool) -> Option<Entity> {
+        next_acquirer(&self.members, &mut self.acquire_money_order, is_eligible)
+    }
+
+    /// Splits money between the online members, for parties which share loot equally.
+    pub fn split_money(
+        &self,
+        money: Money,
+        is_eligible: impl Fn(Entity) -> bool,
+    ) -> Vec<(Entity, Money)> {
+        let recipients: Vec<Entity> = self
+            .members
+            .iter()
+            .filter_map(PartyMember::get_entity)
+            .filter(|entity| is_eligible(*entity))
+            .collect();
+        if recipients.is_empty() {
+            return Vec::new();
+        }
+
+        let money_per_member = Money(money.0 / recipients.len() as i64 + 1);
+        recipients
+            .into_iter()
+            .map(|entity| (entity, money_per_member))
+            .collect()
+    }
+}
+
+fn next_acquirer(
+    members: &[PartyMember],
+    acquire_order: &mut usize,
+    is_eligible: impl Fn(Entity) -> bool,
+) -> Option<Entity> {
+    for offset in 1..=members.len() {
+        let index = (*acquire_order + offset) % members.len();
+        if let PartyMember::Online(entity) = members[index] {
+            if is_eligible(entity) {
+                *acquire_order = index;
+                return Some(entity);
+            }
+        }
+    }
+
+    None
 }
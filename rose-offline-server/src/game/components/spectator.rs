@@ -0,0 +1,5 @@
+use bevy::prelude::Component;
+
+/// Hides a GM from other clients whilst they observe the zone.
+#[derive(Component)]
+pub struct Spectator;
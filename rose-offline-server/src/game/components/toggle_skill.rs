@@ -0,0 +1,24 @@
+use std::time::Duration;
+
+use bevy::prelude::{Component, Entity};
+
+use rose_data::SkillId;
+
+/// A toggle skill which is switched on, keeping its status effects active on the affected
+/// entities for as long as the caster can pay the mana drain.
+#[derive(Component)]
+pub struct ActiveToggleSkill {
+    pub skill_id: SkillId,
+    pub affected_entities: Vec<Entity>,
+    pub mana_drain_timer: Duration,
+}
+
+impl ActiveToggleSkill {
+    pub fn new(skill_id: SkillId, affected_entities: Vec<Entity>) -> Self {
+        Self {
+            skill_id,
+            affected_entities,
+            mana_drain_timer: Duration::ZERO,
+        }
+    }
+}
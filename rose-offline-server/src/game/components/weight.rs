@@ -1,12 +1,27 @@
 use bevy::ecs::prelude::Component;
 
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum OverweightLevel {
+    #[default]
+    None,
+    /// Can only walk
+    Overweight,
+    /// Can not attack or use skills, and can not pick up any more items
+    MaxOverweight,
+}
+
 #[derive(Component)]
 pub struct Weight {
     pub weight: u32,
+    pub overweight: OverweightLevel,
 }
 
 impl Weight {
-    pub fn new(weight: u32) -> Self {
-        Self { weight }
+    pub fn new(weight: u32, overweight: OverweightLevel) -> Self {
+        Self { weight, overweight }
+    }
+
+    pub fn is_max_overweight(&self) -> bool {
+        matches!(self.overweight, OverweightLevel::MaxOverweight)
     }
 }
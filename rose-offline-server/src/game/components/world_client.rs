@@ -1,13 +1,12 @@
 use bevy::ecs::prelude::{Component, Entity};
 use crossbeam_channel::Receiver;
-use tokio::sync::mpsc::UnboundedSender;
 
-use crate::game::messages::{client::ClientMessage, server::ServerMessage};
+use crate::game::messages::{client::ClientMessage, server_queue::ServerMessageSender};
 
 #[derive(Component)]
 pub struct WorldClient {
     pub client_message_rx: Receiver<ClientMessage>,
-    pub server_message_tx: UnboundedSender<ServerMessage>,
+    pub server_message_tx: ServerMessageSender,
     pub login_token: u32,
     pub selected_game_server: Option<Entity>,
     pub game_client_entity: Option<Entity>,
@@ -16,7 +15,7 @@ pub struct WorldClient {
 impl WorldClient {
     pub fn new(
         client_message_rx: Receiver<ClientMessage>,
-        server_message_tx: UnboundedSender<ServerMessage>,
+        server_message_tx: ServerMessageSender,
     ) -> Self {
         Self {
             client_message_rx,
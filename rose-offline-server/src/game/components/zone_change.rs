@@ -0,0 +1,34 @@
+use std::{collections::VecDeque, time::Duration};
+
+use bevy::ecs::prelude::Component;
+
+use crate::game::messages::client::ClientMessage;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ZoneChangeState {
+    /// Waiting for the client to load the zone and send JoinZoneRequest
+    Loading,
+    /// Joined the zone, replaying any messages deferred whilst loading
+    Joined,
+    /// Disconnected before joining the zone, any further messages are discarded until the
+    /// connection closes
+    Disconnecting,
+}
+
+/// Added when a character leaves a zone, tracking them until they have joined the next zone.
+#[derive(Component)]
+pub struct ZoneChange {
+    pub state: ZoneChangeState,
+    pub loading_duration: Duration,
+    pub deferred_messages: VecDeque<ClientMessage>,
+}
+
+impl Default for ZoneChange {
+    fn default() -> Self {
+        Self {
+            state: ZoneChangeState::Loading,
+            loading_duration: Duration::ZERO,
+            deferred_messages: VecDeque::new(),
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use bevy::{ecs::prelude::Entity, prelude::Event};
+
+use crate::game::components::{DroppedItem, Position};
+
+/// A monster drop for a character with auto loot enabled, given to them or their party instead
+/// of spawning on the ground.
+#[derive(Event)]
+pub struct AutoLootEvent {
+    pub entity: Entity,
+    pub party: Option<Entity>,
+    pub item: DroppedItem,
+    pub position: Position,
+}
@@ -2,7 +2,7 @@ use bevy::prelude::{Entity, Event};
 
 use rose_data::Item;
 
-use crate::game::components::ItemSlot;
+use crate::game::components::{InventoryPageType, ItemSlot};
 
 #[derive(Event)]
 pub enum BankEvent {
@@ -21,4 +21,8 @@ pub enum BankEvent {
         item: Item,
         is_premium: bool,
     },
+    DepositInventoryPage {
+        entity: Entity,
+        page_type: InventoryPageType,
+    },
 }
@@ -1,9 +1,9 @@
 use bevy::prelude::{Entity, Event};
 
-use rose_data::SkillId;
-use rose_game_common::components::{ClanLevel, ClanMark, ClanPoints, Money};
+use rose_data::{Item, SkillId};
+use rose_game_common::components::{ClanLevel, ClanMark, ClanPermissions, ClanPoints, Money};
 
-use crate::game::components::Level;
+use crate::game::components::{ItemSlot, Level};
 
 #[derive(Event)]
 pub enum ClanEvent {
@@ -23,6 +23,34 @@ pub enum ClanEvent {
     GetMemberList {
         entity: Entity,
     },
+    Invite {
+        inviter: Entity,
+        name: String,
+    },
+    InviteReply {
+        invitee: Entity,
+        inviter_name: String,
+        accepted: bool,
+    },
+    Kick {
+        requester: Entity,
+        name: String,
+    },
+    Promote {
+        requester: Entity,
+        name: String,
+    },
+    Demote {
+        requester: Entity,
+        name: String,
+    },
+    SetNotice {
+        requester: Entity,
+        notice: String,
+    },
+    Quit {
+        entity: Entity,
+    },
     AddLevel {
         clan_entity: Entity,
         level: i32,
@@ -42,11 +70,18 @@ pub enum ClanEvent {
     AddPoints {
         clan_entity: Entity,
         points: i64,
+        /// The member who earned the points, they are added to their contribution
+        contributor: Option<Entity>,
     },
     SetPoints {
         clan_entity: Entity,
         points: ClanPoints,
     },
+    /// A member completed a clan quest
+    QuestCompleted {
+        clan_entity: Entity,
+        entity: Entity,
+    },
     AddSkill {
         clan_entity: Entity,
         skill_id: SkillId,
@@ -55,4 +90,52 @@ pub enum ClanEvent {
         clan_entity: Entity,
         skill_id: SkillId,
     },
+    VaultOpen {
+        entity: Entity,
+    },
+    VaultDeposit {
+        entity: Entity,
+        item_slot: ItemSlot,
+        item: Item,
+    },
+    VaultWithdraw {
+        entity: Entity,
+        vault_slot: usize,
+        item: Item,
+    },
+    VaultGetLog {
+        entity: Entity,
+    },
+    DonateMoney {
+        entity: Entity,
+        money: Money,
+    },
+    GetPermissions {
+        entity: Entity,
+    },
+    SetPermissions {
+        requester: Entity,
+        permissions: ClanPermissions,
+    },
+    SetRecruitment {
+        requester: Entity,
+        notice: String,
+    },
+    SearchRecruitment {
+        entity: Entity,
+        query: String,
+    },
+    Apply {
+        entity: Entity,
+        clan_name: String,
+        message: String,
+    },
+    GetApplications {
+        entity: Entity,
+    },
+    ApplicationReply {
+        requester: Entity,
+        name: String,
+        accepted: bool,
+    },
 }
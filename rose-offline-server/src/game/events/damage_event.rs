@@ -27,4 +27,9 @@ pub enum DamageEvent {
         attacker: Entity,
         defender: Entity,
     },
+    // For damage with no living attacker, which gives no XP or drops on its own
+    Environment {
+        defender: Entity,
+        damage: Damage,
+    },
 }
@@ -1,6 +1,6 @@
 use bevy::prelude::{Entity, Event};
 
-use rose_data::{AmmoIndex, EquipmentIndex, VehiclePartIndex};
+use rose_data::{AmmoIndex, CostumeIndex, EquipmentIndex, VehiclePartIndex};
 use rose_game_common::components::ItemSlot;
 
 #[derive(Event, Copy, Clone, Debug)]
@@ -20,4 +20,9 @@ pub enum EquipmentEvent {
         vehicle_part_index: VehiclePartIndex,
         item_slot: Option<ItemSlot>,
     },
+    ChangeCostume {
+        entity: Entity,
+        costume_index: CostumeIndex,
+        item_slot: Option<ItemSlot>,
+    },
 }
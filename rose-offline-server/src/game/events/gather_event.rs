@@ -0,0 +1,7 @@
+use bevy::prelude::{Entity, Event};
+
+#[derive(Event)]
+pub struct GatherEvent {
+    pub gather_entity: Entity,
+    pub node_entity: Entity,
+}
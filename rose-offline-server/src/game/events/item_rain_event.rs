@@ -0,0 +1,9 @@
+use bevy::prelude::Event;
+
+use rose_data::ZoneId;
+
+#[derive(Event)]
+pub struct ItemRainEvent {
+    pub zone_id: ZoneId,
+    pub drop_count: usize,
+}
@@ -1,9 +1,12 @@
+mod auto_loot_event;
 mod bank_event;
 mod chat_command_event;
 mod clan_event;
 mod damage_event;
 mod equipment_event;
+mod gather_event;
 mod item_life_event;
+mod item_rain_event;
 mod npc_store_event;
 mod party_event;
 mod personal_store_event;
@@ -14,15 +17,20 @@ mod reward_item_event;
 mod reward_xp_event;
 mod save_event;
 mod skill_event;
+mod title_event;
+mod toggle_skill_event;
 mod use_ammo_event;
 mod use_item_event;
 
+pub use auto_loot_event::AutoLootEvent;
 pub use bank_event::BankEvent;
 pub use chat_command_event::ChatCommandEvent;
 pub use clan_event::ClanEvent;
 pub use damage_event::DamageEvent;
 pub use equipment_event::EquipmentEvent;
+pub use gather_event::GatherEvent;
 pub use item_life_event::ItemLifeEvent;
+pub use item_rain_event::ItemRainEvent;
 pub use npc_store_event::NpcStoreEvent;
 pub use party_event::{PartyEvent, PartyMemberEvent};
 pub use personal_store_event::PersonalStoreEvent;
@@ -33,5 +41,7 @@ pub use reward_item_event::RewardItemEvent;
 pub use reward_xp_event::RewardXpEvent;
 pub use save_event::SaveEvent;
 pub use skill_event::{SkillEvent, SkillEventTarget};
+pub use title_event::TitleEvent;
+pub use toggle_skill_event::ToggleSkillEvent;
 pub use use_ammo_event::UseAmmoEvent;
 pub use use_item_event::UseItemEvent;
@@ -45,6 +45,14 @@ pub enum PartyEvent {
         owner_entity: Entity,
         kick_character_id: CharacterUniqueId,
     },
+    VoteKick {
+        voter_entity: Entity,
+        kick_character_id: CharacterUniqueId,
+    },
+    VoteKickReply {
+        voter_entity: Entity,
+        accept: bool,
+    },
     UpdateRules {
         owner_entity: Entity,
         item_sharing: PartyItemSharing,
@@ -0,0 +1,17 @@
+use bevy::prelude::{Entity, Event};
+
+use rose_data::QuestTriggerHash;
+
+#[derive(Event)]
+pub enum TitleEvent {
+    QuestTriggerSuccess {
+        entity: Entity,
+        trigger_hash: QuestTriggerHash,
+    },
+    /// A statistic used by title requirements has changed
+    StatisticsChanged { entity: Entity },
+    SetActive {
+        entity: Entity,
+        title_id: Option<u16>,
+    },
+}
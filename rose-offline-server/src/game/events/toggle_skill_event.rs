@@ -0,0 +1,9 @@
+use bevy::prelude::{Entity, Event};
+
+use rose_data::SkillId;
+
+#[derive(Event)]
+pub enum ToggleSkillEvent {
+    Enable { entity: Entity, skill_id: SkillId },
+    Disable { entity: Entity },
+}
@@ -1,22 +1,65 @@
+use std::net::IpAddr;
+
 use bevy::ecs::prelude::Entity;
 use crossbeam_channel::Receiver;
-use tokio::sync::mpsc::UnboundedSender;
 use tokio::sync::oneshot;
 
-use crate::game::messages::{client::ClientMessage, server::ServerMessage};
+use rose_data::ZoneId;
+use rose_game_common::data::Password;
+
+use crate::game::{
+    components::ClientEntityId,
+    messages::{client::ClientMessage, server_queue::ServerMessageSender, watch::ClientWatch},
+    resources::GameConfig,
+};
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub enum ClientType {
     Login,
     World,
     Game,
 }
 
+/// The features supported by the clients of a protocol, so one game world can serve clients
+/// of different game versions.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ProtocolCapabilities {
+    pub clans: bool,
+    /// Patched clients which understand the clan recruitment board packets
+    pub clan_recruitment: bool,
+    pub costume_slots: bool,
+    /// Patched clients which can display character titles
+    pub titles: bool,
+}
+
+#[derive(Clone, Debug)]
+pub enum InspectEntityTarget {
+    CharacterName(String),
+    ClientEntity {
+        zone_id: ZoneId,
+        entity_id: ClientEntityId,
+    },
+}
+
+/// A change to the server news, as with the news chat commands.
+#[derive(Clone, Debug)]
+pub enum ServerNewsEdit {
+    /// Sets the message of the day, or clears it if None
+    Motd(Option<String>),
+    AddAnnouncement(String),
+    RemoveAnnouncement(usize),
+    ClearAnnouncements,
+    AnnouncementInterval(u64),
+}
+
 pub enum ControlMessage {
     AddClient {
         client_type: ClientType,
+        capabilities: ProtocolCapabilities,
+        ip_address: Option<IpAddr>,
         client_message_rx: Receiver<ClientMessage>,
-        server_message_tx: UnboundedSender<ServerMessage>,
+        server_message_tx: ServerMessageSender,
+        watch: ClientWatch,
         response_tx: oneshot::Sender<Entity>,
     },
     RemoveClient {
@@ -36,9 +79,54 @@ pub enum ControlMessage {
         ip: String,
         port: u16,
         packet_codec_seed: u32,
+        max_players: u32,
         response_tx: oneshot::Sender<Entity>,
     },
     RemoveServer {
         entity: Entity,
     },
+    ReloadConfig {
+        game_config: Box<GameConfig>,
+    },
+    /// Responds with a JSON snapshot of the entity's components, or None if it was not found
+    InspectEntity {
+        target: InspectEntityTarget,
+        response_tx: oneshot::Sender<Option<serde_json::Value>>,
+    },
+    /// Saves the character with the given name, or every character if None, responding with the
+    /// number of characters saved
+    SaveCharacters {
+        name: Option<String>,
+        response_tx: oneshot::Sender<usize>,
+    },
+    /// Starts or stops logging the messages of the character with the given name to the watch
+    /// log target, responding with false if the character is not in game
+    WatchCharacter {
+        name: String,
+        enabled: bool,
+        response_tx: oneshot::Sender<bool>,
+    },
+    /// Applies the edit to the server news, if any, responding with the resulting news or an
+    /// error message if the edit was invalid
+    EditServerNews {
+        edit: Option<ServerNewsEdit>,
+        response_tx: oneshot::Sender<Result<serde_json::Value, String>>,
+    },
+    /// Responds with the accounts and ip addresses which are locked out after failed logins
+    GetLoginLockouts {
+        response_tx: oneshot::Sender<serde_json::Value>,
+    },
+    /// Responds with the entity counts of all zones together, and of the zones with the most
+    /// entities
+    GetZoneCensus {
+        num_top_zones: usize,
+        response_tx: oneshot::Sender<serde_json::Value>,
+    },
+    /// Sets the password of an account and clears its login lockout, responding with an error
+    /// message if the account could not be updated
+    ResetPassword {
+        name: String,
+        password: Password,
+        response_tx: oneshot::Sender<Result<(), String>>,
+    },
 }
@@ -1,4 +1,6 @@
 pub mod control;
+pub mod server_queue;
+pub mod watch;
 
 pub use rose_game_common::messages::client;
 pub use rose_game_common::messages::server;
@@ -0,0 +1,205 @@
+use serde::Serialize;
+use std::sync::{
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    Arc,
+};
+use thiserror::Error;
+use tokio::sync::mpsc::{self, error::TrySendError};
+
+use crate::game::messages::server::ServerMessage;
+
+/// Maximum number of messages which can be waiting to be sent to a client
+pub const SERVER_MESSAGE_QUEUE_CAPACITY: usize = 2048;
+
+/// Once this many messages are waiting, cosmetic messages are dropped so the
+/// remaining space is kept for messages the client can not do without
+const SERVER_MESSAGE_QUEUE_COSMETIC_LIMIT: usize = SERVER_MESSAGE_QUEUE_CAPACITY * 3 / 4;
+
+#[derive(Debug, Error)]
+pub enum ServerMessageSendError {
+    #[error("client has disconnected")]
+    Closed,
+    #[error("cosmetic message dropped for slow client")]
+    Dropped,
+    #[error("client message queue is full")]
+    Overflow,
+}
+
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct ServerMessageQueueStats {
+    pub depth: usize,
+    pub peak_depth: usize,
+    pub dropped: usize,
+}
+
+#[derive(Default)]
+struct ServerMessageQueueState {
+    depth: AtomicUsize,
+    peak_depth: AtomicUsize,
+    dropped: AtomicUsize,
+    overflowed: AtomicBool,
+}
+
+/// Messages which the client can safely miss when it is not keeping up
+fn is_cosmetic_message(message: &ServerMessage) -> bool {
+    matches!(
+        message,
+        ServerMessage::LocalChat { .. }
+            | ServerMessage::ShoutChat { .. }
+            | ServerMessage::UseEmote { .. }
+            | ServerMessage::PartyMemberUpdateInfo { .. }
+    )
+}
+
+/// Bounded queue of messages from the game to a client connection.
+///
+/// When a client falls behind, cosmetic messages are dropped first. If the
+/// queue fills up entirely the client is disconnected rather than losing a
+/// message it needs, such as a teleport or damage.
+pub fn server_message_queue() -> (ServerMessageSender, ServerMessageReceiver) {
+    let (tx, rx) = mpsc::channel(SERVER_MESSAGE_QUEUE_CAPACITY);
+    let state = Arc::new(ServerMessageQueueState::default());
+    (
+        ServerMessageSender {
+            tx,
+            state: state.clone(),
+        },
+        ServerMessageReceiver { rx, state },
+    )
+}
+
+#[derive(Clone)]
+pub struct ServerMessageSender {
+    tx: mpsc::Sender<ServerMessage>,
+    state: Arc<ServerMessageQueueState>,
+}
+
+impl ServerMessageSender {
+    pub fn send(&self, message: ServerMessage) -> Result<(), ServerMessageSendError> {
+        if self.state.overflowed.load(Ordering::Relaxed) {
+            return Err(ServerMessageSendError::Overflow);
+        }
+
+        if is_cosmetic_message(&message)
+            && self.state.depth.load(Ordering::Relaxed) >= SERVER_MESSAGE_QUEUE_COSMETIC_LIMIT
+        {
+            self.state.dropped.fetch_add(1, Ordering::Relaxed);
+            return Err(ServerMessageSendError::Dropped);
+        }
+
+        // Increment depth before sending so the receiver can never take it below zero
+        let depth = self.state.depth.fetch_add(1, Ordering::Relaxed) + 1;
+        match self.tx.try_send(message) {
+            Ok(_) => {
+                self.state.peak_depth.fetch_max(depth, Ordering::Relaxed);
+                Ok(())
+            }
+            Err(TrySendError::Closed(_)) => {
+                self.state.depth.fetch_sub(1, Ordering::Relaxed);
+                Err(ServerMessageSendError::Closed)
+            }
+            Err(TrySendError::Full(_)) => {
+                self.state.depth.fetch_sub(1, Ordering::Relaxed);
+                if !self.state.overflowed.swap(true, Ordering::Relaxed) {
+                    log::warn!(
+                        "Disconnecting client which has {} server messages waiting to be sent",
+                        SERVER_MESSAGE_QUEUE_CAPACITY
+                    );
+                }
+                Err(ServerMessageSendError::Overflow)
+            }
+        }
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.tx.is_closed()
+    }
+
+    pub fn stats(&self) -> ServerMessageQueueStats {
+        ServerMessageQueueStats {
+            depth: self.state.depth.load(Ordering::Relaxed),
+            peak_depth: self.state.peak_depth.load(Ordering::Relaxed),
+            dropped: self.state.dropped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+pub struct ServerMessageReceiver {
+    rx: mpsc::Receiver<ServerMessage>,
+    state: Arc<ServerMessageQueueState>,
+}
+
+impl ServerMessageReceiver {
+    /// Returns None once the sender has gone, or the queue has overflowed and
+    /// the client should be disconnected.
+    pub async fn recv(&mut self) -> Option<ServerMessage> {
+        if self.state.overflowed.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        let message = self.rx.recv().await?;
+        self.state.depth.fetch_sub(1, Ordering::Relaxed);
+        Some(message)
+    }
+
+    /// Returns the next message without waiting, or None if the queue is empty.
+    pub fn try_recv(&mut self) -> Option<ServerMessage> {
+        if self.state.overflowed.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        let message = self.rx.try_recv().ok()?;
+        self.state.depth.fetch_sub(1, Ordering::Relaxed);
+        Some(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::components::{ClientEntityId, Money};
+
+    fn chat_message() -> ServerMessage {
+        ServerMessage::LocalChat {
+            entity_id: ClientEntityId(1),
+            text: "hello".to_string(),
+        }
+    }
+
+    fn money_message() -> ServerMessage {
+        ServerMessage::UpdateMoney { money: Money(1) }
+    }
+
+    #[test]
+    fn cosmetic_messages_dropped_before_queue_is_full() {
+        let (tx, _rx) = server_message_queue();
+        for _ in 0..SERVER_MESSAGE_QUEUE_COSMETIC_LIMIT {
+            assert!(tx.send(money_message()).is_ok());
+        }
+
+        assert!(matches!(
+            tx.send(chat_message()),
+            Err(ServerMessageSendError::Dropped)
+        ));
+        assert!(tx.send(money_message()).is_ok());
+
+        let stats = tx.stats();
+        assert_eq!(stats.depth, SERVER_MESSAGE_QUEUE_COSMETIC_LIMIT + 1);
+        assert_eq!(stats.dropped, 1);
+    }
+
+    #[test]
+    fn full_queue_disconnects_client() {
+        let (tx, _rx) = server_message_queue();
+        for _ in 0..SERVER_MESSAGE_QUEUE_CAPACITY {
+            assert!(tx.send(money_message()).is_ok());
+        }
+
+        assert!(matches!(
+            tx.send(money_message()),
+            Err(ServerMessageSendError::Overflow)
+        ));
+        assert!(tx.state.overflowed.load(Ordering::Relaxed));
+        assert_eq!(tx.stats().peak_depth, SERVER_MESSAGE_QUEUE_CAPACITY);
+    }
+}
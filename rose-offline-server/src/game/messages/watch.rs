@@ -0,0 +1,63 @@
+use std::{
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use crate::game::messages::{client::ClientMessage, server::ServerMessage};
+
+const MAX_SUMMARY_LENGTH: usize = 256;
+
+/// Shared by a client connection and its entity, when enabled all messages to
+/// and from the client are logged with the "watch" target.
+#[derive(Clone, Default)]
+pub struct ClientWatch {
+    enabled: Arc<AtomicBool>,
+    label: Arc<Mutex<String>>,
+}
+
+fn summarise(message: &dyn Debug) -> String {
+    let mut summary = format!("{:?}", message);
+    if summary.len() > MAX_SUMMARY_LENGTH {
+        let mut end = MAX_SUMMARY_LENGTH;
+        while !summary.is_char_boundary(end) {
+            end -= 1;
+        }
+        summary.truncate(end);
+        summary.push_str("...");
+    }
+    summary
+}
+
+impl ClientWatch {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn start(&self, label: String) {
+        *self.label.lock().unwrap() = label;
+        self.enabled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn stop(&self) {
+        self.enabled.store(false, Ordering::Relaxed);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    pub fn log_recv(&self, message: &ClientMessage) {
+        if self.is_enabled() {
+            log::info!(target: "watch", "[{}] RECV {}", self.label.lock().unwrap(), summarise(message));
+        }
+    }
+
+    pub fn log_send(&self, message: &ServerMessage) {
+        if self.is_enabled() {
+            log::info!(target: "watch", "[{}] SEND {}", self.label.lock().unwrap(), summarise(message));
+        }
+    }
+}
@@ -1,3 +1,4 @@
+mod benchmark;
 mod bots;
 mod bundles;
 mod events;
@@ -5,9 +6,16 @@ mod game_world;
 mod resources;
 mod systems;
 
+#[cfg(test)]
+mod simulation;
+
 pub mod components;
 pub mod messages;
 pub mod storage;
 
+pub use benchmark::{run_benchmark, BenchmarkConfig, BenchmarkReport};
 pub use game_world::GameWorld;
-pub use resources::{GameConfig, GameData};
+pub use resources::{
+    GameChannelConfig, GameConfig, GameData, IroseConfig, LoggingConfig, LoggingFileRotation,
+    WorldServerConfig,
+};
@@ -10,6 +10,10 @@ use crate::game::components::{ClientEntity, ClientEntityId, ClientEntitySector,
 
 const MAX_CLIENT_ENTITY_ID: usize = 4096;
 
+/// Number of ticks after an entity has left a zone before its id can be given to another entity,
+/// so clients do not apply packets which were meant for the previous entity to the new one
+pub const CLIENT_ENTITY_ID_RECYCLE_TICKS: u64 = 300;
+
 pub type ClientEntitySet = BitArr!(for MAX_CLIENT_ENTITY_ID);
 
 #[derive(Clone, Default)]
@@ -69,6 +73,12 @@ pub struct ClientEntityZone {
     // The list of entities leaving the zone, this is so we can process any
     // visibility changes before freeing the entity id
     leaving_entities: Vec<ClientEntityId>,
+
+    // The tick from which each freed entity id can be allocated again
+    recycle_ticks: Vec<u64>,
+
+    // Number of times the zone leavers have been processed, which is once per tick
+    tick: u64,
 }
 
 impl ClientEntityZone {
@@ -88,6 +98,8 @@ impl ClientEntityZone {
             ],
             entities: vec![None; MAX_CLIENT_ENTITY_ID],
             leaving_entities: Vec::new(),
+            recycle_ticks: vec![0; MAX_CLIENT_ENTITY_ID],
+            tick: 0,
         }
     }
 
@@ -166,13 +178,15 @@ impl ClientEntityZone {
     ) -> Option<(ClientEntity, ClientEntitySector)> {
         let sector = self.calculate_sector(position.xy());
 
-        // Allocate an entity id, skipping over invalid entity id
+        // Allocate an entity id, skipping over invalid entity id and recently freed ids
+        let tick = self.tick;
+        let recycle_ticks = &self.recycle_ticks;
         let (free_index, free_slot) = self
             .entities
             .iter_mut()
             .enumerate()
             .skip(1)
-            .find(|(_, slot)| slot.is_none())?;
+            .find(|(index, slot)| slot.is_none() && recycle_ticks[*index] <= tick)?;
         let client_entity_id = ClientEntityId(free_index);
         let client_entity = ClientEntity::new(entity_type, client_entity_id, self.zone_id);
         let client_entity_sector = ClientEntitySector::new(sector);
@@ -233,9 +247,12 @@ impl ClientEntityZone {
     }
 
     pub fn process_zone_leavers(&mut self) {
+        self.tick += 1;
+
         // Free the entity id
         for id in self.leaving_entities.iter() {
             self.entities[id.0] = None;
+            self.recycle_ticks[id.0] = self.tick + CLIENT_ENTITY_ID_RECYCLE_TICKS;
         }
 
         self.leaving_entities.clear();
@@ -0,0 +1,30 @@
+use bevy::{ecs::prelude::Entity, prelude::Resource};
+use std::time::Duration;
+
+use rose_data::QuestTriggerHash;
+
+/// A trigger started by QsdReward::TriggerAfterDelay, it is run with the
+/// object which was selected when the reward was applied.
+///
+/// The character which started the trigger may have left the game by the time it runs,
+/// in which case only the conditions and rewards for the selected object are applied.
+pub struct DelayedQuestTrigger {
+    pub source_entity: Option<Entity>,
+    pub trigger_hash: QuestTriggerHash,
+    pub selected_event_object: Option<Entity>,
+    pub selected_npc: Option<Entity>,
+    pub run_at: Duration,
+}
+
+/// Triggers on event objects are saved with the zone storage, so they still run
+/// after a restart.
+#[derive(Default, Resource)]
+pub struct DelayedQuestTriggers {
+    pub pending: Vec<DelayedQuestTrigger>,
+}
+
+impl DelayedQuestTriggers {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
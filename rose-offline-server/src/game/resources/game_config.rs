@@ -1,16 +1,766 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
 use bevy::prelude::Resource;
+use chrono::{DateTime, Datelike, Local};
+use serde::Deserialize;
+
+use rose_data::{Item, ItemReference, NpcId, ScheduleWindow, StatusEffectId, ZoneId};
+use rose_game_common::data::AbilityValueCalculatorOptions;
+
+/// Sets an event object variable while the local time is within the scheduled window.
+#[derive(Clone, Debug, Deserialize)]
+pub struct EventObjectSchedule {
+    pub zone_id: ZoneId,
+    pub event_id: u16,
+    pub map_chunk_x: i32,
+    pub map_chunk_y: i32,
+    #[serde(default)]
+    pub variable_id: usize,
+    pub active_value: i32,
+    #[serde(default)]
+    pub inactive_value: i32,
+    #[serde(flatten)]
+    pub window: ScheduleWindow,
+}
+
+/// A zone which can be owned by a clan, taxing npc store purchases.
+#[derive(Clone, Debug, Deserialize)]
+pub struct TerritoryConfig {
+    pub zone_id: ZoneId,
+    /// Percentage of npc store purchases charged on top of the price and paid to the owning clan
+    #[serde(default)]
+    pub tax_rate: i64,
+    /// When ownership can be contested, if ever
+    #[serde(default)]
+    pub siege: Option<ScheduleWindow>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LoggingFileRotation {
+    Minutely,
+    Hourly,
+    #[default]
+    Daily,
+    Never,
+}
+
+fn default_log_file_prefix() -> String {
+    String::from("rose-offline.log")
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct LoggingFileConfig {
+    pub directory: PathBuf,
+    #[serde(default = "default_log_file_prefix")]
+    pub prefix: String,
+    #[serde(default)]
+    pub rotation: LoggingFileRotation,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct LoggingConfig {
+    /// Default level for all log targets
+    pub level: String,
+    /// Per module or log target level overrides, e.g. quest: debug
+    pub filters: BTreeMap<String, String>,
+    pub json: bool,
+    pub file: Option<LoggingFileConfig>,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: String::from("trace"),
+            filters: ["mio", "npc_ai", "packets", "quest"]
+                .into_iter()
+                .map(|target| (String::from(target), String::from("off")))
+                .collect(),
+            json: false,
+            file: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct LoginLockoutConfig {
+    /// Failed logins for an account or ip address before it is locked out, 0 to disable
+    pub max_failures: u32,
+    /// Time an account or ip address is locked out for, in seconds
+    pub lockout_secs: u64,
+}
+
+impl Default for LoginLockoutConfig {
+    fn default() -> Self {
+        Self {
+            max_failures: 5,
+            lockout_secs: 300,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct MovementUpdateConfig {
+    /// Observers within this distance receive every movement update
+    pub full_rate_distance: f32,
+    /// Minimum time between movement updates for observers further away, in milliseconds
+    pub throttled_interval_ms: u64,
+}
+
+impl Default for MovementUpdateConfig {
+    fn default() -> Self {
+        Self {
+            full_rate_distance: 2500.0,
+            throttled_interval_ms: 500,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct IroseConfig {
+    /// Client build to support, currently only "112"
+    pub client_version: String,
+    /// Optional path to a 256 byte packet codec table, for client builds with a custom table
+    pub packet_table: Option<PathBuf>,
+    /// Notify clients of clan recruitment applications, only for patched clients which support
+    /// the clan recruitment board
+    pub enable_clan_recruitment: bool,
+    /// Send costume slots to clients, only for patched clients which support them
+    pub enable_costume_slots: bool,
+    /// Send character titles to clients, only for patched clients which support them
+    pub enable_titles: bool,
+    /// Language index of the game data strings sent to clients, 0 korean, 1 english,
+    /// 2 japanese, 3 traditional chinese, 4 simplified chinese
+    pub language: usize,
+}
+
+impl Default for IroseConfig {
+    fn default() -> Self {
+        Self {
+            client_version: String::from("112"),
+            packet_table: None,
+            enable_clan_recruitment: false,
+            enable_costume_slots: false,
+            enable_titles: false,
+            language: 1,
+        }
+    }
+}
+
+fn default_channel_max_players() -> u32 {
+    500
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct GameChannelConfig {
+    pub name: String,
+    pub port: u16,
+    /// Number of connected characters at which the channel is reported as full
+    #[serde(default = "default_channel_max_players")]
+    pub max_players: u32,
+}
+
+impl GameChannelConfig {
+    pub fn new(name: String, port: u16) -> Self {
+        Self {
+            name,
+            port,
+            max_players: default_channel_max_players(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct WorldServerConfig {
+    pub name: String,
+    pub port: u16,
+    pub channels: Vec<GameChannelConfig>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct ItemDropConfig {
+    /// Time only the killer, or their party, can pickup a monster drop, in seconds
+    pub owner_duration_secs: u64,
+    /// Allow the killer's party to pickup drops while they are owned
+    pub party_shared: bool,
+    /// Zones where anyone can pickup any drop
+    pub free_for_all_zones: Vec<ZoneId>,
+    /// Maximum distance of the drop picked up when a client asks to pickup the nearest drop
+    pub pickup_nearest_distance: f32,
+    /// Allow characters to enable auto loot, skipping the ground drop for their kills
+    pub enable_auto_loot: bool,
+}
+
+impl Default for ItemDropConfig {
+    fn default() -> Self {
+        Self {
+            owner_duration_secs: 60,
+            party_shared: true,
+            free_for_all_zones: Vec::new(),
+            pickup_nearest_distance: 1000.0,
+            enable_auto_loot: false,
+        }
+    }
+}
+
+fn default_item_rain_quantity() -> u32 {
+    1
+}
+
+/// An item which can be dropped by an item rain.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ItemRainItem {
+    pub item: ItemReference,
+    #[serde(default = "default_item_rain_quantity")]
+    pub quantity: u32,
+    pub weight: u32,
+}
+
+/// Starts an item rain in a zone each time the local time enters the scheduled window.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ItemRainSchedule {
+    pub zone_id: ZoneId,
+    #[serde(flatten)]
+    pub window: ScheduleWindow,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct ItemRainConfig {
+    /// Items dropped by an item rain, chosen by weight
+    pub items: Vec<ItemRainItem>,
+    /// Number of items dropped by an item rain, unless the /itemrain chat command gives a count
+    pub drop_count: usize,
+    /// An item rain will not drop more items once a zone has this many item drops
+    pub max_zone_item_drops: usize,
+    pub schedules: Vec<ItemRainSchedule>,
+}
+
+impl Default for ItemRainConfig {
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            drop_count: 50,
+            max_zone_item_drops: 500,
+            schedules: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct NewsConfig {
+    /// Lines sent to a character when they first join a zone after logging in
+    pub motd: Vec<String>,
+    /// Announcements sent to every client in turn
+    pub announcements: Vec<String>,
+    /// Time between announcements, in seconds, 0 to disable
+    pub announcement_interval_secs: u64,
+}
+
+impl Default for NewsConfig {
+    fn default() -> Self {
+        Self {
+            motd: Vec::new(),
+            announcements: Vec::new(),
+            announcement_interval_secs: 600,
+        }
+    }
+}
+
+/// Which attacker is credited with killing a monster.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KillCreditPolicy {
+    /// XP is shared by damage dealt, the drop is owned by the killing blow
+    #[default]
+    Shared,
+    FirstHit,
+    MostDamage,
+    LastHit,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct KillCreditConfig {
+    pub default: KillCreditPolicy,
+    /// Per npc overrides of the default policy
+    pub npcs: HashMap<NpcId, KillCreditPolicy>,
+}
+
+impl KillCreditConfig {
+    pub fn get_policy(&self, npc_id: NpcId) -> KillCreditPolicy {
+        self.npcs.get(&npc_id).copied().unwrap_or(self.default)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct NewbieStatusEffect {
+    pub id: StatusEffectId,
+    pub value: i32,
+    pub duration_secs: u64,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct NewbieProtectionConfig {
+    /// Characters below this level take reduced damage from other characters, 0 to disable
+    pub max_level: u32,
+    /// Percentage of damage from other characters taken by protected characters
+    pub pvp_damage_percent: u32,
+    /// Status effects applied when a new character first joins a zone
+    pub status_effects: Vec<NewbieStatusEffect>,
+    /// Run the FILE_TUTORIAL.STB quest triggers when a new character first joins a zone
+    pub enable_tutorial_triggers: bool,
+}
+
+impl Default for NewbieProtectionConfig {
+    fn default() -> Self {
+        Self {
+            max_level: 0,
+            pvp_damage_percent: 50,
+            status_effects: Vec::new(),
+            enable_tutorial_triggers: false,
+        }
+    }
+}
+
+impl NewbieProtectionConfig {
+    pub fn is_protected(&self, level: u32) -> bool {
+        level < self.max_level
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct OverweightConfig {
+    /// Characters carrying at least this percentage of their max weight can only walk, 0 to
+    /// disable
+    pub walk_percent: u32,
+    /// Characters carrying at least this percentage of their max weight can not attack or use
+    /// skills, and can not pick up items which would take them over it, 0 to disable
+    pub max_percent: u32,
+    /// Status effect shown on characters while they are overweight
+    pub status_effect_id: Option<StatusEffectId>,
+}
+
+impl Default for OverweightConfig {
+    fn default() -> Self {
+        Self {
+            walk_percent: 100,
+            max_percent: 110,
+            status_effect_id: None,
+        }
+    }
+}
+
+impl OverweightConfig {
+    /// The weight above which items can no longer be picked up, None when there is no limit
+    pub fn max_weight_limit(&self, max_weight: i32) -> Option<u32> {
+        if self.max_percent == 0 {
+            None
+        } else {
+            Some(max_weight.max(0) as u32 * self.max_percent / 100)
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct ExperienceConfig {
+    /// Party members further than this from a killed monster do not share its XP
+    pub party_share_distance: f32,
+    /// Characters this many levels above a monster receive grey_xp_percent of its XP, in place of
+    /// the usual level difference scaling, 0 to disable
+    pub grey_level_difference: i32,
+    pub grey_xp_percent: i32,
+}
+
+impl Default for ExperienceConfig {
+    fn default() -> Self {
+        Self {
+            party_share_distance: 5000.0,
+            grey_level_difference: 20,
+            grey_xp_percent: 10,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct PartyConfig {
+    /// Time a disconnected party owner has to reconnect before ownership is transferred, in seconds
+    pub owner_reconnect_grace_secs: u64,
+    /// Allow party members to vote to kick another member
+    pub enable_vote_kick: bool,
+    /// Time before an undecided vote kick fails, in seconds
+    pub vote_kick_timeout_secs: u64,
+}
+
+impl Default for PartyConfig {
+    fn default() -> Self {
+        Self {
+            owner_reconnect_grace_secs: 60,
+            enable_vote_kick: true,
+            vote_kick_timeout_secs: 30,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuestResetPeriod {
+    Daily,
+    Weekly,
+}
+
+/// A quest which can only be completed once per reset period.
+#[derive(Clone, Debug, Deserialize)]
+pub struct RepeatableQuestConfig {
+    pub quest_id: usize,
+    pub reset: QuestResetPeriod,
+    /// Quest switches set by the quest's triggers, which are cleared when the quest resets
+    #[serde(default)]
+    pub switches: Vec<usize>,
+}
 
-#[derive(Resource)]
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct QuestResetConfig {
+    /// Local hour at which repeatable quests reset
+    pub hour: u32,
+    /// Day weekly quests reset, 0 = Monday
+    pub weekday: u32,
+    pub quests: Vec<RepeatableQuestConfig>,
+}
+
+impl QuestResetConfig {
+    pub fn get_quest(&self, quest_id: usize) -> Option<&RepeatableQuestConfig> {
+        self.quests.iter().find(|quest| quest.quest_id == quest_id)
+    }
+
+    pub fn get_reset_period(&self, quest_id: usize) -> Option<QuestResetPeriod> {
+        self.get_quest(quest_id).map(|quest| quest.reset)
+    }
+
+    /// The timestamp of the most recent reset of the period
+    pub fn get_last_reset(&self, period: QuestResetPeriod, now: DateTime<Local>) -> i64 {
+        let (days_since_reset, period_days) = match period {
+            QuestResetPeriod::Daily => (0, 1),
+            QuestResetPeriod::Weekly => (
+                (now.weekday().num_days_from_monday() + 7 - self.weekday % 7) % 7,
+                7,
+            ),
+        };
+
+        let reset_date = now.date_naive() - chrono::Duration::days(days_since_reset as i64);
+        let reset_time = reset_date
+            .and_hms_opt(self.hour.min(23), 0, 0)
+            .and_then(|date_time| date_time.and_local_timezone(Local).earliest())
+            .map_or(now.timestamp(), |date_time| date_time.timestamp());
+
+        if reset_time > now.timestamp() {
+            // Today's reset has not happened yet
+            reset_time - chrono::Duration::days(period_days).num_seconds()
+        } else {
+            reset_time
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct SkillMasteryConfig {
+    /// Using skills earns skill experience towards mastery levels
+    pub enabled: bool,
+    /// Skill experience earned each time a skill is used
+    pub xp_per_use: u32,
+    /// Total skill experience required for each mastery level
+    pub level_xp: Vec<u32>,
+    /// Percentage of mana cost removed per mastery level
+    pub mana_cost_reduction_percent: u32,
+    /// Percentage of cooldown removed per mastery level, unpatched clients still wait for the
+    /// full cooldown before allowing the skill to be used again
+    pub cooldown_reduction_percent: u32,
+}
+
+impl Default for SkillMasteryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            xp_per_use: 1,
+            level_xp: vec![50, 200, 500, 1000, 2000],
+            mana_cost_reduction_percent: 2,
+            cooldown_reduction_percent: 0,
+        }
+    }
+}
+
+impl SkillMasteryConfig {
+    pub fn get_level(&self, xp: u32) -> u32 {
+        self.level_xp
+            .iter()
+            .take_while(|&&level_xp| xp >= level_xp)
+            .count() as u32
+    }
+
+    /// Percentage of mana cost removed at the mastery level
+    pub fn mana_cost_reduction(&self, level: u32) -> u32 {
+        (level * self.mana_cost_reduction_percent).min(100)
+    }
+
+    /// Percentage of cooldown removed at the mastery level
+    pub fn cooldown_reduction(&self, level: u32) -> u32 {
+        (level * self.cooldown_reduction_percent).min(100)
+    }
+}
+
+fn default_capture_max_health_percent() -> u32 {
+    20
+}
+
+/// A consumable item which captures a weakened monster into a summon item.
+#[derive(Clone, Debug, Deserialize)]
+pub struct CaptureItemConfig {
+    /// Consumable item number of the capture item
+    pub item: usize,
+    /// Consumable item number of the summon item created by capturing a monster
+    pub summon_item: usize,
+    /// The monsters which can be captured
+    pub npcs: Vec<NpcId>,
+    /// Monsters must be at or below this percentage of their max health to be captured
+    #[serde(default = "default_capture_max_health_percent")]
+    pub max_health_percent: u32,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct TamingConfig {
+    pub capture_items: Vec<CaptureItemConfig>,
+}
+
+impl TamingConfig {
+    pub fn get_capture_item(&self, item_number: usize) -> Option<&CaptureItemConfig> {
+        self.capture_items
+            .iter()
+            .find(|capture_item| capture_item.item == item_number)
+    }
+}
+
+/// How a title is earned.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TitleRequirement {
+    /// Kill this many monsters, optionally only of a single npc
+    MonsterKills {
+        count: u32,
+        #[serde(default)]
+        npc: Option<NpcId>,
+    },
+    QuestsCompleted {
+        count: u32,
+    },
+    Deaths {
+        count: u32,
+    },
+    MoneyEarned {
+        money: i64,
+    },
+    DistanceWalked {
+        distance: f64,
+    },
+    /// Succeed at the named quest trigger, such as one run by an event npc
+    QuestTrigger {
+        trigger: String,
+    },
+}
+
+/// Ability values added while the title is active.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct TitleBonus {
+    pub attack_power: i32,
+    pub attack_speed: i32,
+    pub avoid: i32,
+    pub critical: i32,
+    pub defence: i32,
+    pub hit: i32,
+    pub max_health: i32,
+    pub max_mana: i32,
+    pub resistance: i32,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct TitleConfig {
+    pub id: u16,
+    pub name: String,
+    pub requirement: TitleRequirement,
+    #[serde(default)]
+    pub bonus: TitleBonus,
+}
+
+/// What to do when an account logs in whilst it already has an active session.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicateLoginPolicy {
+    /// Refuse the new login with an already logged in error
+    #[default]
+    Reject,
+    /// Save and disconnect the existing session, then allow the new login
+    Takeover,
+}
+
+/// What to do when a patched client reports a zone checksum which differs from the server.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ZoneChecksumEnforcement {
+    #[default]
+    Log,
+    /// Log and tell the player their map data is modified
+    Warn,
+    /// Log and disconnect the client
+    Kick,
+}
+
+#[derive(Clone, Resource, Deserialize)]
+#[serde(default)]
 pub struct GameConfig {
+    /// Level and stat limits, and coefficients of the ability value formulas
+    pub ability_values: AbilityValueCalculatorOptions,
+    /// Append the position, hp, mp and experience of changed characters to their checkpoint
+    /// file at this interval, 0 to disable
+    pub checkpoint_interval_secs: u64,
+    /// Invites to join a clan which have not been replied to within this time are discarded
+    pub clan_invite_timeout_secs: u64,
+    pub duplicate_login: DuplicateLoginPolicy,
     pub enable_npc_spawns: bool,
     pub enable_monster_spawns: bool,
+    /// Pause npc ai, monster spawns and status effects in zones without any characters
+    pub enable_zone_sleeping: bool,
+    pub event_object_schedules: Vec<EventObjectSchedule>,
+    pub experience: ExperienceConfig,
+    /// Remove characters whose game client connection closed without the server being told after
+    /// this time, 0 to disable
+    pub ghost_client_grace_secs: u64,
+    /// Disconnect game clients which have not sent a packet within this time, so half-open
+    /// connections do not hold on to their character and login token, 0 to disable
+    pub idle_timeout_secs: u64,
+    pub territories: Vec<TerritoryConfig>,
+    /// Minimum time between /where, /time or /who chat commands from the same character, in
+    /// milliseconds
+    pub info_command_cooldown_millis: u64,
+    pub irose: IroseConfig,
+    pub item_drops: ItemDropConfig,
+    pub item_rain: ItemRainConfig,
+    pub kill_credit: KillCreditConfig,
+    pub logging: LoggingConfig,
+    pub login_lockout: LoginLockoutConfig,
+    pub movement_updates: MovementUpdateConfig,
+    pub newbie_protection: NewbieProtectionConfig,
+    pub news: NewsConfig,
+    pub overweight: OverweightConfig,
+    pub party: PartyConfig,
+    pub quest_resets: QuestResetConfig,
+    /// Keep the character of a disconnected game client in the world for this time, allowing the
+    /// client to resume the session with its login token
+    pub reconnect_grace_secs: u64,
+    /// Minimum time between /bug or /report chat commands from the same character, in seconds
+    pub report_cooldown_secs: u64,
+    pub skill_mastery: SkillMasteryConfig,
+    pub taming: TamingConfig,
+    pub titles: Vec<TitleConfig>,
+    /// Items which cannot be sold in a personal store or deposited in a clan vault
+    pub untradeable_items: Vec<ItemReference>,
+    /// World servers and their game channels, when empty a single world server and channel
+    /// listen on the --world-port and --game-port
+    pub world_servers: Vec<WorldServerConfig>,
+    /// Disconnect clients which have not joined their new zone within this time
+    pub zone_change_timeout_secs: u64,
+    pub zone_checksum_enforcement: ZoneChecksumEnforcement,
 }
 
-impl GameConfig {
-    pub fn default() -> Self {
+impl Default for GameConfig {
+    fn default() -> Self {
         Self {
+            ability_values: AbilityValueCalculatorOptions::default(),
+            checkpoint_interval_secs: 30,
+            clan_invite_timeout_secs: 60,
+            duplicate_login: DuplicateLoginPolicy::default(),
             enable_monster_spawns: true,
             enable_npc_spawns: true,
+            enable_zone_sleeping: true,
+            event_object_schedules: Vec::new(),
+            experience: ExperienceConfig::default(),
+            ghost_client_grace_secs: 60,
+            idle_timeout_secs: 300,
+            territories: Vec::new(),
+            info_command_cooldown_millis: 1000,
+            irose: IroseConfig::default(),
+            item_drops: ItemDropConfig::default(),
+            item_rain: ItemRainConfig::default(),
+            kill_credit: KillCreditConfig::default(),
+            logging: LoggingConfig::default(),
+            login_lockout: LoginLockoutConfig::default(),
+            movement_updates: MovementUpdateConfig::default(),
+            newbie_protection: NewbieProtectionConfig::default(),
+            news: NewsConfig::default(),
+            overweight: OverweightConfig::default(),
+            party: PartyConfig::default(),
+            quest_resets: QuestResetConfig::default(),
+            reconnect_grace_secs: 30,
+            report_cooldown_secs: 60,
+            skill_mastery: SkillMasteryConfig::default(),
+            taming: TamingConfig::default(),
+            titles: Vec::new(),
+            untradeable_items: Vec::new(),
+            world_servers: Vec::new(),
+            zone_change_timeout_secs: 120,
+            zone_checksum_enforcement: ZoneChecksumEnforcement::default(),
         }
     }
 }
+
+impl GameConfig {
+    /// Applies a reloaded config, the settings which are only used at startup keep their
+    /// current values.
+    pub fn reload(&mut self, game_config: GameConfig) {
+        let previous = std::mem::replace(self, game_config);
+        self.ability_values = previous.ability_values;
+        self.enable_npc_spawns = previous.enable_npc_spawns;
+        self.enable_zone_sleeping = previous.enable_zone_sleeping;
+        self.idle_timeout_secs = previous.idle_timeout_secs;
+        self.irose = previous.irose;
+        self.logging = previous.logging;
+        self.territories = previous.territories;
+        self.world_servers = previous.world_servers;
+    }
+
+    pub fn get_title(&self, id: u16) -> Option<&TitleConfig> {
+        self.titles.iter().find(|title| title.id == id)
+    }
+
+    pub fn is_untradeable_item(&self, item: &Item) -> bool {
+        self.untradeable_items.contains(&item.get_item_reference())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, anyhow::Error> {
+        let str = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read file {}", path.to_string_lossy()))?;
+        let config: Self = serde_yaml::from_str(&str).with_context(|| {
+            format!(
+                "Failed to deserialise GameConfig from file {}",
+                path.to_string_lossy()
+            )
+        })?;
+        Ok(config)
+    }
+}
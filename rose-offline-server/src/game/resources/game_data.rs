@@ -2,9 +2,9 @@ use bevy::prelude::Resource;
 use std::sync::Arc;
 
 use rose_data::{
-    AiDatabase, CharacterMotionDatabase, DataDecoder, ItemDatabase, JobClassDatabase, NpcDatabase,
-    QuestDatabase, SkillDatabase, StatusEffectDatabase, StringDatabase, WarpGateDatabase,
-    ZoneDatabase,
+    AiDatabase, CharacterMotionDatabase, DataDecoder, EffectDatabase, GatherDatabase, ItemDatabase,
+    JobClassDatabase, NpcDatabase, QuestDatabase, SkillDatabase, StatusEffectDatabase,
+    StringDatabase, TeleportAnchorDatabase, WarpGateDatabase, ZoneDatabase,
 };
 use rose_game_common::data::{AbilityValueCalculator, DropTable};
 
@@ -17,6 +17,8 @@ pub struct GameData {
     pub data_decoder: Box<dyn DataDecoder + Send + Sync>,
     pub drop_table: Box<dyn DropTable + Send + Sync>,
     pub ai: Arc<AiDatabase>,
+    pub effects: Arc<EffectDatabase>,
+    pub gather_nodes: Arc<GatherDatabase>,
     pub items: Arc<ItemDatabase>,
     pub job_class: Arc<JobClassDatabase>,
     pub motions: Arc<CharacterMotionDatabase>,
@@ -25,6 +27,7 @@ pub struct GameData {
     pub skills: Arc<SkillDatabase>,
     pub status_effects: Arc<StatusEffectDatabase>,
     pub string_database: Arc<StringDatabase>,
+    pub teleport_anchors: Arc<TeleportAnchorDatabase>,
     pub warp_gates: Arc<WarpGateDatabase>,
     pub zones: Arc<ZoneDatabase>,
 }
@@ -0,0 +1,131 @@
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    time::{Duration, Instant},
+};
+
+use bevy::prelude::Resource;
+
+struct FailedLogins {
+    count: u32,
+    last_failure: Instant,
+}
+
+impl FailedLogins {
+    fn is_expired(&self, now: Instant, lockout_duration: Duration) -> bool {
+        now.duration_since(self.last_failure) >= lockout_duration
+    }
+}
+
+/// Recent failed login attempts per account and per ip address, failures are forgotten once
+/// the lockout duration has passed since the last one.
+#[derive(Default, Resource)]
+pub struct LoginAttempts {
+    accounts: HashMap<String, FailedLogins>,
+    ip_addresses: HashMap<IpAddr, FailedLogins>,
+}
+
+impl LoginAttempts {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn is_locked(
+        &self,
+        username: &str,
+        ip_address: Option<IpAddr>,
+        max_failures: u32,
+        lockout_duration: Duration,
+        now: Instant,
+    ) -> bool {
+        let is_locked = |failed_logins: &FailedLogins| {
+            failed_logins.count >= max_failures && !failed_logins.is_expired(now, lockout_duration)
+        };
+
+        max_failures > 0
+            && (self.accounts.get(username).map_or(false, is_locked)
+                || ip_address
+                    .and_then(|ip_address| self.ip_addresses.get(&ip_address))
+                    .map_or(false, is_locked))
+    }
+
+    /// Returns the number of recent failures for the account and for the ip address. Failures
+    /// for accounts which do not exist only count towards the ip address.
+    pub fn add_failure(
+        &mut self,
+        username: Option<&str>,
+        ip_address: Option<IpAddr>,
+        lockout_duration: Duration,
+        now: Instant,
+    ) -> (u32, u32) {
+        self.accounts
+            .retain(|_, failed_logins| !failed_logins.is_expired(now, lockout_duration));
+        self.ip_addresses
+            .retain(|_, failed_logins| !failed_logins.is_expired(now, lockout_duration));
+
+        let add_failure = |failed_logins: &mut FailedLogins| {
+            failed_logins.count += 1;
+            failed_logins.last_failure = now;
+            failed_logins.count
+        };
+        let new_failed_logins = || FailedLogins {
+            count: 0,
+            last_failure: now,
+        };
+
+        let account_failures = username.map_or(0, |username| {
+            add_failure(
+                self.accounts
+                    .entry(username.to_string())
+                    .or_insert_with(new_failed_logins),
+            )
+        });
+        let ip_address_failures = ip_address.map_or(0, |ip_address| {
+            add_failure(
+                self.ip_addresses
+                    .entry(ip_address)
+                    .or_insert_with(new_failed_logins),
+            )
+        });
+
+        (account_failures, ip_address_failures)
+    }
+
+    pub fn clear_account(&mut self, username: &str) {
+        self.accounts.remove(username);
+    }
+
+    /// Returns the locked accounts and ip addresses, with the time until each is unlocked.
+    pub fn get_lockouts(
+        &self,
+        max_failures: u32,
+        lockout_duration: Duration,
+        now: Instant,
+    ) -> (Vec<(String, Duration)>, Vec<(IpAddr, Duration)>) {
+        let remaining_time = |failed_logins: &FailedLogins| {
+            if max_failures == 0
+                || failed_logins.count < max_failures
+                || failed_logins.is_expired(now, lockout_duration)
+            {
+                None
+            } else {
+                Some(lockout_duration - now.duration_since(failed_logins.last_failure))
+            }
+        };
+
+        (
+            self.accounts
+                .iter()
+                .filter_map(|(username, failed_logins)| {
+                    Some((username.clone(), remaining_time(failed_logins)?))
+                })
+                .collect(),
+            self.ip_addresses
+                .iter()
+                .filter_map(|(ip_address, failed_logins)| {
+                    Some((*ip_address, remaining_time(failed_logins)?))
+                })
+                .collect(),
+        )
+    }
+}
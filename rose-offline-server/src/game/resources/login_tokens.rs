@@ -9,6 +9,8 @@ pub struct LoginToken {
     pub login_client: Option<Entity>,
     pub world_client: Option<Entity>,
     pub game_client: Option<Entity>,
+    /// Connection entity of a game client which resumed the session of a link dead character
+    pub game_client_connection: Option<Entity>,
 }
 
 #[derive(Default, Resource)]
@@ -41,6 +43,7 @@ impl LoginTokens {
             login_client: Some(login_client),
             world_client: None,
             game_client: None,
+            game_client_connection: None,
         });
         token
     }
@@ -49,6 +52,31 @@ impl LoginTokens {
         self.tokens.iter().find(|token| token.username == username)
     }
 
+    pub fn remove_username_tokens(&mut self, username: &str) -> Vec<LoginToken> {
+        let mut removed = Vec::new();
+        while let Some(index) = self
+            .tokens
+            .iter()
+            .position(|token| token.username == username)
+        {
+            removed.push(self.tokens.remove(index));
+        }
+        removed
+    }
+
+    /// Find another session of the same account as the given token which has a character in
+    /// game, on any game server
+    pub fn find_other_game_session(&self, token_id: u32) -> Option<&LoginToken> {
+        let username = &self
+            .tokens
+            .iter()
+            .find(|token| token.token == token_id)?
+            .username;
+        self.tokens.iter().find(|token| {
+            token.token != token_id && &token.username == username && token.game_client.is_some()
+        })
+    }
+
     pub fn get_token_mut(&mut self, token_id: u32) -> Option<&mut LoginToken> {
         self.tokens.iter_mut().find(|token| token.token == token_id)
     }
@@ -1,23 +1,46 @@
 mod bot_list;
 mod client_entity_list;
 mod control_channel;
+mod delayed_quest_triggers;
 mod game_config;
 mod game_data;
+mod login_attempts;
 mod login_tokens;
+mod navigation_grids;
 mod server_list;
 mod server_messages;
+mod server_news;
+mod territory_list;
 mod world_rates;
 mod world_time;
+mod zone_activity;
+mod zone_census;
 mod zone_list;
 
 pub use bot_list::{BotList, BotListEntry};
-pub use client_entity_list::{ClientEntityList, ClientEntitySet, ClientEntityZone};
+pub use client_entity_list::{
+    ClientEntityList, ClientEntitySet, ClientEntityZone, CLIENT_ENTITY_ID_RECYCLE_TICKS,
+};
 pub use control_channel::ControlChannel;
-pub use game_config::GameConfig;
+pub use delayed_quest_triggers::{DelayedQuestTrigger, DelayedQuestTriggers};
+pub use game_config::{
+    CaptureItemConfig, DuplicateLoginPolicy, ExperienceConfig, GameChannelConfig, GameConfig,
+    IroseConfig, ItemDropConfig, ItemRainConfig, ItemRainItem, ItemRainSchedule, KillCreditConfig,
+    KillCreditPolicy, LoggingConfig, LoggingFileRotation, LoginLockoutConfig, MovementUpdateConfig,
+    NewbieProtectionConfig, NewbieStatusEffect, NewsConfig, OverweightConfig, PartyConfig,
+    QuestResetConfig, QuestResetPeriod, RepeatableQuestConfig, SkillMasteryConfig, TamingConfig,
+    TitleBonus, TitleConfig, TitleRequirement, WorldServerConfig, ZoneChecksumEnforcement,
+};
 pub use game_data::GameData;
+pub use login_attempts::LoginAttempts;
 pub use login_tokens::{LoginToken, LoginTokens};
+pub use navigation_grids::{NavigationGrid, NavigationGrids, NavigationPath};
 pub use server_list::{GameServer, ServerList, WorldServer};
 pub use server_messages::ServerMessages;
+pub use server_news::ServerNews;
+pub use territory_list::{Territory, TerritoryList};
 pub use world_rates::WorldRates;
 pub use world_time::WorldTime;
+pub use zone_activity::ZoneActivity;
+pub use zone_census::{ZoneCensus, ZoneCensusCounts};
 pub use zone_list::ZoneList;
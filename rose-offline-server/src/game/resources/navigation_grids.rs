@@ -0,0 +1,522 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hasher,
+    io::{Read, Write},
+    path::PathBuf,
+    sync::Arc,
+};
+
+use bevy::{
+    math::{Vec2, Vec3, Vec3Swizzles},
+    prelude::Resource,
+};
+use bitvec::prelude::*;
+use crossbeam_channel::Receiver;
+use log::{debug, warn};
+
+use rose_data::{ZoneData, ZoneDatabase, ZoneId};
+
+use crate::game::storage::{writer::is_storage_write_enabled, NAVIGATION_CACHE_DIR};
+
+const NAVIGATION_CELL_SIZE: f32 = 250.0;
+const NAVIGATION_CACHE_MAGIC: &[u8; 4] = b"NAV3";
+const MAX_WALKABLE_STEP_HEIGHT: f32 = 150.0;
+const MAX_PATH_NODES: usize = 4000;
+const PATHFINDING_NODES_PER_TICK: usize = 20000;
+
+pub enum NavigationPath {
+    /// There is a direct path, or no navigation data, so just move straight to destination
+    Direct,
+
+    /// Move through each waypoint in order, the final waypoint is the destination
+    Waypoints(VecDeque<Vec3>),
+
+    /// The pathfinding budget for this tick has been used, try again next tick
+    Deferred,
+}
+
+/// 64 bit FNV-1a, so the cache key does not change between runs of the server.
+struct NavigationCacheHasher(u64);
+
+impl Default for NavigationCacheHasher {
+    fn default() -> Self {
+        Self(0xcbf29ce484222325)
+    }
+}
+
+impl Hasher for NavigationCacheHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ *byte as u64).wrapping_mul(0x100000001b3);
+        }
+    }
+}
+
+struct NavigationGridKey {
+    width: u32,
+    height: u32,
+    origin: Vec2,
+    /// Hash of the heightmap and collision object contents the grid was generated from
+    contents_hash: u64,
+}
+
+impl NavigationGridKey {
+    fn new(zone_data: &ZoneData) -> Option<Self> {
+        let (min, max) = zone_data.heightmap.bounds()?;
+
+        let mut hasher = NavigationCacheHasher::default();
+        zone_data.heightmap.hash_contents(&mut hasher);
+        zone_data.collision.hash_contents(&mut hasher);
+
+        Some(Self {
+            width: ((max.x - min.x) / NAVIGATION_CELL_SIZE).ceil() as u32,
+            height: ((max.y - min.y) / NAVIGATION_CELL_SIZE).ceil() as u32,
+            origin: min,
+            contents_hash: hasher.finish(),
+        })
+    }
+
+    fn write(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        writer.write_all(NAVIGATION_CACHE_MAGIC)?;
+        writer.write_all(&self.width.to_le_bytes())?;
+        writer.write_all(&self.height.to_le_bytes())?;
+        writer.write_all(&self.origin.x.to_le_bytes())?;
+        writer.write_all(&self.origin.y.to_le_bytes())?;
+        writer.write_all(&self.contents_hash.to_le_bytes())?;
+        Ok(())
+    }
+
+    fn matches(&self, reader: &mut impl Read) -> std::io::Result<bool> {
+        let mut expected = Vec::new();
+        self.write(&mut expected)?;
+
+        let mut header = vec![0u8; expected.len()];
+        reader.read_exact(&mut header)?;
+        Ok(header == expected)
+    }
+}
+
+pub struct NavigationGrid {
+    origin: Vec2,
+    width: u32,
+    height: u32,
+    walkable: BitVec<u8, Lsb0>,
+}
+
+fn get_cache_path(zone_id: ZoneId) -> PathBuf {
+    NAVIGATION_CACHE_DIR.join(format!("{}.nav", zone_id.get()))
+}
+
+impl NavigationGrid {
+    pub fn generate(zone_data: &ZoneData) -> Option<Self> {
+        let (min, max) = zone_data.heightmap.bounds()?;
+        let width = ((max.x - min.x) / NAVIGATION_CELL_SIZE).ceil() as u32;
+        let height = ((max.y - min.y) / NAVIGATION_CELL_SIZE).ceil() as u32;
+        let mut grid = Self {
+            origin: min,
+            width,
+            height,
+            walkable: bitvec![u8, Lsb0; 0; (width * height) as usize],
+        };
+
+        let cell_heights: Vec<Option<f32>> = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                zone_data
+                    .heightmap
+                    .get_height(grid.get_cell_center(x as i32, y as i32))
+            })
+            .collect();
+
+        for y in 0..height as i32 {
+            for x in 0..width as i32 {
+                let Some(cell_height) = cell_heights[(y as u32 * width + x as u32) as usize] else {
+                    continue;
+                };
+
+                let center = grid.get_cell_center(x, y);
+                if zone_data.is_position_blocked(Vec3::new(center.x, center.y, cell_height)) {
+                    continue;
+                }
+
+                // Cells which are too steep compared to their neighbours are unwalkable
+                let is_steep = [(-1, 0), (1, 0), (0, -1), (0, 1)].iter().any(|(dx, dy)| {
+                    grid.get_cell_index(x + dx, y + dy)
+                        .and_then(|index| cell_heights[index])
+                        .map_or(false, |neighbour_height| {
+                            (neighbour_height - cell_height).abs() > MAX_WALKABLE_STEP_HEIGHT
+                        })
+                });
+                if !is_steep {
+                    grid.walkable
+                        .set((y as u32 * width + x as u32) as usize, true);
+                }
+            }
+        }
+
+        Some(grid)
+    }
+
+    fn load_cache(zone_id: ZoneId, key: &NavigationGridKey) -> Option<Self> {
+        let mut file = std::fs::File::open(get_cache_path(zone_id)).ok()?;
+        if !key.matches(&mut file).ok()? {
+            return None;
+        }
+
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes).ok()?;
+
+        let mut walkable = BitVec::from_vec(bytes);
+        if walkable.len() < (key.width * key.height) as usize {
+            return None;
+        }
+        walkable.truncate((key.width * key.height) as usize);
+
+        Some(Self {
+            origin: key.origin,
+            width: key.width,
+            height: key.height,
+            walkable,
+        })
+    }
+
+    fn save_cache(&self, zone_id: ZoneId, key: &NavigationGridKey) -> Result<(), anyhow::Error> {
+        let path = get_cache_path(zone_id);
+        std::fs::create_dir_all(path.parent().unwrap())?;
+
+        let mut file = std::fs::File::create(&path)?;
+        key.write(&mut file)?;
+        file.write_all(self.walkable.as_raw_slice())?;
+        Ok(())
+    }
+
+    fn get_cell_center(&self, x: i32, y: i32) -> Vec2 {
+        self.origin + (Vec2::new(x as f32, y as f32) + 0.5) * NAVIGATION_CELL_SIZE
+    }
+
+    fn get_cell(&self, position: Vec2) -> (i32, i32) {
+        let cell = ((position - self.origin) / NAVIGATION_CELL_SIZE).floor();
+        (cell.x as i32, cell.y as i32)
+    }
+
+    fn get_cell_index(&self, x: i32, y: i32) -> Option<usize> {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            None
+        } else {
+            Some((y as u32 * self.width + x as u32) as usize)
+        }
+    }
+
+    pub fn is_walkable(&self, position: Vec2) -> bool {
+        let (x, y) = self.get_cell(position);
+        self.is_cell_walkable(x, y)
+    }
+
+    fn is_cell_walkable(&self, x: i32, y: i32) -> bool {
+        self.get_cell_index(x, y)
+            .map_or(false, |index| self.walkable[index])
+    }
+
+    /// The center of every walkable cell
+    pub fn walkable_positions(&self) -> Vec<Vec2> {
+        self.walkable
+            .iter_ones()
+            .map(|index| {
+                self.get_cell_center(
+                    (index as u32 % self.width) as i32,
+                    (index as u32 / self.width) as i32,
+                )
+            })
+            .collect()
+    }
+
+    pub fn is_line_walkable(&self, from: Vec2, to: Vec2) -> bool {
+        let num_samples = (from.distance(to) / (NAVIGATION_CELL_SIZE * 0.5)).ceil() as i32;
+        (1..=num_samples).all(|i| self.is_walkable(from.lerp(to, i as f32 / num_samples as f32)))
+    }
+
+    /// A* search across the grid, returns the list of cells from start to goal and the number
+    /// of nodes which were expanded.
+    fn find_cell_path(
+        &self,
+        start: (i32, i32),
+        goal: (i32, i32),
+        max_nodes: usize,
+    ) -> (Option<Vec<(i32, i32)>>, usize) {
+        const NEIGHBOURS: [(i32, i32, u32); 8] = [
+            (-1, 0, 10),
+            (1, 0, 10),
+            (0, -1, 10),
+            (0, 1, 10),
+            (-1, -1, 14),
+            (1, -1, 14),
+            (-1, 1, 14),
+            (1, 1, 14),
+        ];
+        let heuristic = |(x, y): (i32, i32)| {
+            let dx = (x - goal.0).unsigned_abs();
+            let dy = (y - goal.1).unsigned_abs();
+            10 * u32::max(dx, dy) + 4 * u32::min(dx, dy)
+        };
+
+        let mut open = BinaryHeap::new();
+        let mut came_from: HashMap<(i32, i32), (i32, i32)> = HashMap::new();
+        let mut cost: HashMap<(i32, i32), u32> = HashMap::new();
+        let mut nodes_expanded = 0;
+
+        cost.insert(start, 0);
+        open.push(Reverse((heuristic(start), start)));
+
+        while let Some(Reverse((_, current))) = open.pop() {
+            if current == goal {
+                let mut path = vec![current];
+                let mut node = current;
+                while let Some(&previous) = came_from.get(&node) {
+                    path.push(previous);
+                    node = previous;
+                }
+                path.reverse();
+                return (Some(path), nodes_expanded);
+            }
+
+            nodes_expanded += 1;
+            if nodes_expanded >= max_nodes {
+                break;
+            }
+
+            let current_cost = cost[&current];
+            for &(dx, dy, step_cost) in NEIGHBOURS.iter() {
+                let next = (current.0 + dx, current.1 + dy);
+                if !self.is_cell_walkable(next.0, next.1) {
+                    continue;
+                }
+
+                // Do not allow cutting corners diagonally
+                if dx != 0
+                    && dy != 0
+                    && (!self.is_cell_walkable(current.0 + dx, current.1)
+                        || !self.is_cell_walkable(current.0, current.1 + dy))
+                {
+                    continue;
+                }
+
+                let next_cost = current_cost + step_cost;
+                if cost
+                    .get(&next)
+                    .map_or(true, |&existing| next_cost < existing)
+                {
+                    cost.insert(next, next_cost);
+                    came_from.insert(next, current);
+                    open.push(Reverse((next_cost + heuristic(next), next)));
+                }
+            }
+        }
+
+        (None, nodes_expanded)
+    }
+
+    /// Remove any waypoints which can be skipped by walking in a straight line
+    fn smooth_path(&self, from: Vec2, points: Vec<Vec2>) -> Vec<Vec2> {
+        let mut smoothed = Vec::new();
+        let mut current = from;
+        let mut index = 0;
+
+        while index < points.len() {
+            let mut furthest = index;
+            for candidate in (index + 1..points.len()).rev() {
+                if self.is_line_walkable(current, points[candidate]) {
+                    furthest = candidate;
+                    break;
+                }
+            }
+
+            current = points[furthest];
+            smoothed.push(current);
+            index = furthest + 1;
+        }
+
+        smoothed
+    }
+}
+
+/// Generates the grids which were not cached on a background thread, so the server does not
+/// wait for them at startup. Each grid is saved to the cache once it has been generated, unless
+/// storage writes are disabled.
+fn spawn_grid_generator(
+    zone_database: Arc<ZoneDatabase>,
+    zones: Vec<(ZoneId, NavigationGridKey)>,
+) -> Receiver<(ZoneId, NavigationGrid)> {
+    let (tx, rx) = crossbeam_channel::unbounded();
+
+    std::thread::Builder::new()
+        .name("navigation-grids".to_string())
+        .spawn(move || {
+            for (zone_id, key) in zones {
+                let Some(grid) = zone_database
+                    .get_zone(zone_id)
+                    .and_then(NavigationGrid::generate)
+                else {
+                    continue;
+                };
+
+                if is_storage_write_enabled() {
+                    if let Err(error) = grid.save_cache(zone_id, &key) {
+                        warn!(
+                            "Failed to save navigation cache for zone {}: {}",
+                            zone_id.get(),
+                            error
+                        );
+                    }
+                }
+
+                if tx.send((zone_id, grid)).is_err() {
+                    break;
+                }
+            }
+        })
+        .expect("Failed to spawn navigation grid thread");
+
+    rx
+}
+
+#[derive(Resource)]
+pub struct NavigationGrids {
+    grids: HashMap<ZoneId, NavigationGrid>,
+    generated_grids_rx: Option<Receiver<(ZoneId, NavigationGrid)>>,
+    nodes_remaining: usize,
+}
+
+impl NavigationGrids {
+    /// Loads every cached grid whose zone heightmap and collision are unchanged, the remaining
+    /// grids are generated in the background and zones move directly until theirs is ready.
+    pub fn new(zone_database: Arc<ZoneDatabase>) -> Self {
+        let mut grids = HashMap::new();
+        let mut uncached_zones = Vec::new();
+
+        for zone_data in zone_database.iter() {
+            let Some(key) = NavigationGridKey::new(zone_data) else {
+                continue;
+            };
+
+            if let Some(grid) = NavigationGrid::load_cache(zone_data.id, &key) {
+                debug!(
+                    "Loaded navigation grid for zone {} size ({}, {})",
+                    zone_data.id.get(),
+                    grid.width,
+                    grid.height
+                );
+                grids.insert(zone_data.id, grid);
+            } else {
+                uncached_zones.push((zone_data.id, key));
+            }
+        }
+
+        let generated_grids_rx = if uncached_zones.is_empty() {
+            None
+        } else {
+            debug!(
+                "Generating {} navigation grids in the background",
+                uncached_zones.len()
+            );
+            Some(spawn_grid_generator(zone_database, uncached_zones))
+        };
+
+        Self {
+            grids,
+            generated_grids_rx,
+            nodes_remaining: PATHFINDING_NODES_PER_TICK,
+        }
+    }
+
+    /// Adds any grids which have finished generating in the background
+    pub fn receive_generated_grids(&mut self) {
+        let Some(generated_grids_rx) = self.generated_grids_rx.as_ref() else {
+            return;
+        };
+
+        for (zone_id, grid) in generated_grids_rx.try_iter() {
+            debug!(
+                "Generated navigation grid for zone {} size ({}, {})",
+                zone_id.get(),
+                grid.width,
+                grid.height
+            );
+            self.grids.insert(zone_id, grid);
+        }
+    }
+
+    pub fn reset_budget(&mut self) {
+        self.nodes_remaining = PATHFINDING_NODES_PER_TICK;
+    }
+
+    pub fn get_grid(&self, zone_id: ZoneId) -> Option<&NavigationGrid> {
+        self.grids.get(&zone_id)
+    }
+
+    pub fn find_path(
+        &mut self,
+        zone_data: &ZoneData,
+        from: Vec3,
+        destination: Vec3,
+    ) -> NavigationPath {
+        let Some(grid) = self.grids.get(&zone_data.id) else {
+            return NavigationPath::Direct;
+        };
+
+        if grid.is_line_walkable(from.xy(), destination.xy()) {
+            return NavigationPath::Direct;
+        }
+
+        if self.nodes_remaining == 0 {
+            return NavigationPath::Deferred;
+        }
+
+        let start = grid.get_cell(from.xy());
+        let goal = grid.get_cell(destination.xy());
+        if !grid.is_cell_walkable(goal.0, goal.1) {
+            // Cannot path to an unwalkable cell, so leave the movement as it is
+            return NavigationPath::Direct;
+        }
+
+        let (cells, nodes_expanded) = grid.find_cell_path(
+            start,
+            goal,
+            usize::min(MAX_PATH_NODES, self.nodes_remaining),
+        );
+        self.nodes_remaining = self.nodes_remaining.saturating_sub(nodes_expanded);
+
+        let Some(cells) = cells else {
+            return NavigationPath::Direct;
+        };
+
+        // Replace the final cell with the exact destination
+        let mut points: Vec<Vec2> = cells
+            .iter()
+            .skip(1)
+            .map(|&(x, y)| grid.get_cell_center(x, y))
+            .collect();
+        points.pop();
+        points.push(destination.xy());
+
+        NavigationPath::Waypoints(
+            grid.smooth_path(from.xy(), points)
+                .into_iter()
+                .map(|point| {
+                    Vec3::new(
+                        point.x,
+                        point.y,
+                        zone_data
+                            .heightmap
+                            .get_height(point)
+                            .unwrap_or(destination.z),
+                    )
+                })
+                .collect(),
+        )
+    }
+}
@@ -6,6 +6,7 @@ pub struct GameServer {
     pub ip: String,
     pub port: u16,
     pub packet_codec_seed: u32,
+    pub max_players: u32,
 }
 
 pub struct WorldServer {
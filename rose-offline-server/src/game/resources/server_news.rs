@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+use bevy::prelude::Resource;
+
+use crate::game::resources::NewsConfig;
+
+/// The message of the day and rotating announcements, initialised from the game config and
+/// editable at runtime with the news chat commands and admin API requests.
+#[derive(Resource)]
+pub struct ServerNews {
+    pub motd: Vec<String>,
+    pub announcements: Vec<String>,
+    pub announcement_interval: Duration,
+    next_announcement_index: usize,
+    last_announcement_time: Duration,
+}
+
+impl ServerNews {
+    pub fn new(news_config: &NewsConfig) -> Self {
+        Self {
+            motd: news_config.motd.clone(),
+            announcements: news_config.announcements.clone(),
+            announcement_interval: Duration::from_secs(news_config.announcement_interval_secs),
+            next_announcement_index: 0,
+            last_announcement_time: Duration::ZERO,
+        }
+    }
+
+    /// Replaces the news with a reloaded config, keeping the position in the rotation.
+    pub fn reload(&mut self, news_config: &NewsConfig) {
+        self.motd = news_config.motd.clone();
+        self.announcements = news_config.announcements.clone();
+        self.announcement_interval = Duration::from_secs(news_config.announcement_interval_secs);
+    }
+
+    /// Returns the next announcement in the rotation if the interval has passed since the last.
+    pub fn get_next_announcement(&mut self, now: Duration) -> Option<&str> {
+        if self.announcement_interval.is_zero()
+            || self.announcements.is_empty()
+            || now - self.last_announcement_time < self.announcement_interval
+        {
+            return None;
+        }
+        self.last_announcement_time = now;
+
+        let index = self.next_announcement_index % self.announcements.len();
+        self.next_announcement_index = index + 1;
+        Some(&self.announcements[index])
+    }
+}
@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+
+use bevy::prelude::{Entity, Resource};
+use log::warn;
+
+use rose_data::{ScheduleWindow, ZoneId};
+
+use crate::game::{resources::GameConfig, storage::territory::TerritoryStorage};
+
+pub struct Territory {
+    pub zone_id: ZoneId,
+    pub tax_rate: i64,
+    pub siege: Option<ScheduleWindow>,
+    pub owner_clan: Option<String>,
+    pub siege_active: bool,
+    pub siege_points: HashMap<Entity, u32>,
+}
+
+impl Territory {
+    pub fn save(&self) {
+        let storage = TerritoryStorage {
+            owner_clan: self.owner_clan.clone(),
+        };
+
+        if let Err(error) = storage.save(self.zone_id) {
+            warn!(
+                "Failed to save territory storage for zone {}: {:?}",
+                self.zone_id.get(),
+                error
+            );
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct TerritoryList {
+    territories: HashMap<ZoneId, Territory>,
+}
+
+impl TerritoryList {
+    pub fn new(game_config: &GameConfig) -> Self {
+        let mut territories = HashMap::new();
+
+        for territory_config in game_config.territories.iter() {
+            let zone_id = territory_config.zone_id;
+            let owner_clan = if TerritoryStorage::exists(zone_id) {
+                TerritoryStorage::try_load(zone_id)
+                    .map_err(|error| {
+                        warn!(
+                            "Failed to load territory storage for zone {}: {:?}",
+                            zone_id.get(),
+                            error
+                        )
+                    })
+                    .ok()
+                    .and_then(|storage| storage.owner_clan)
+            } else {
+                None
+            };
+
+            territories.insert(
+                zone_id,
+                Territory {
+                    zone_id,
+                    tax_rate: territory_config.tax_rate.clamp(0, 100),
+                    siege: territory_config.siege.clone(),
+                    owner_clan,
+                    siege_active: false,
+                    siege_points: HashMap::new(),
+                },
+            );
+        }
+
+        Self { territories }
+    }
+
+    pub fn get(&self, zone_id: ZoneId) -> Option<&Territory> {
+        self.territories.get(&zone_id)
+    }
+
+    pub fn get_mut(&mut self, zone_id: ZoneId) -> Option<&mut Territory> {
+        self.territories.get_mut(&zone_id)
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Territory> {
+        self.territories.values_mut()
+    }
+}
@@ -0,0 +1,70 @@
+use bevy::prelude::Resource;
+use std::{collections::HashMap, time::Duration};
+
+use rose_data::{ZoneDatabase, ZoneId};
+
+#[derive(Default)]
+struct ZoneActivityState {
+    num_characters: usize,
+    sleeping_duration: Duration,
+    delta: Option<Duration>,
+}
+
+/// Tracks which zones have characters in them, so zones without any can skip npc ai,
+/// monster spawns and status effects.
+///
+/// When a zone wakes up the time it spent asleep is added to its next delta, so timers
+/// catch up instead of resuming where they were paused.
+#[derive(Resource)]
+pub struct ZoneActivity {
+    pub sleeping_enabled: bool,
+    zones: HashMap<ZoneId, ZoneActivityState>,
+    time_delta: Duration,
+}
+
+impl ZoneActivity {
+    pub fn new(zone_database: &ZoneDatabase, sleeping_enabled: bool) -> Self {
+        Self {
+            sleeping_enabled,
+            zones: zone_database
+                .iter()
+                .map(|zone| (zone.id, ZoneActivityState::default()))
+                .collect(),
+            time_delta: Duration::ZERO,
+        }
+    }
+
+    pub fn update(&mut self, num_characters: &HashMap<ZoneId, usize>, time_delta: Duration) {
+        self.time_delta = time_delta;
+
+        for (zone_id, zone) in self.zones.iter_mut() {
+            zone.num_characters = num_characters.get(zone_id).copied().unwrap_or(0);
+
+            if self.sleeping_enabled && zone.num_characters == 0 {
+                zone.sleeping_duration += time_delta;
+                zone.delta = None;
+            } else {
+                zone.delta = Some(time_delta + zone.sleeping_duration);
+                zone.sleeping_duration = Duration::ZERO;
+            }
+        }
+    }
+
+    pub fn get_num_characters(&self, zone_id: ZoneId) -> usize {
+        self.zones
+            .get(&zone_id)
+            .map_or(0, |zone| zone.num_characters)
+    }
+
+    pub fn is_active(&self, zone_id: ZoneId) -> bool {
+        self.get_delta(zone_id).is_some()
+    }
+
+    /// Returns the time to advance a zone by this tick, or None if the zone is asleep.
+    pub fn get_delta(&self, zone_id: ZoneId) -> Option<Duration> {
+        match self.zones.get(&zone_id) {
+            Some(zone) => zone.delta,
+            None => Some(self.time_delta),
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use bevy::prelude::Resource;
+use serde::Serialize;
+use std::collections::HashMap;
+
+use rose_data::ZoneId;
+
+#[derive(Clone, Copy, Default, Serialize)]
+pub struct ZoneCensusCounts {
+    pub players: usize,
+    pub bots: usize,
+    pub monsters: usize,
+    pub npcs: usize,
+    pub item_drops: usize,
+}
+
+impl ZoneCensusCounts {
+    pub fn total(&self) -> usize {
+        self.players + self.bots + self.monsters + self.npcs + self.item_drops
+    }
+}
+
+/// Counts of the entities in each zone, updated every tick by zone_census_system.
+#[derive(Resource, Default)]
+pub struct ZoneCensus {
+    zones: HashMap<ZoneId, ZoneCensusCounts>,
+}
+
+impl ZoneCensus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, zones: HashMap<ZoneId, ZoneCensusCounts>) {
+        self.zones = zones;
+    }
+
+    pub fn get(&self, zone_id: ZoneId) -> ZoneCensusCounts {
+        self.zones.get(&zone_id).copied().unwrap_or_default()
+    }
+
+    /// The counts of all zones added together
+    pub fn total(&self) -> ZoneCensusCounts {
+        self.zones
+            .values()
+            .fold(ZoneCensusCounts::default(), |mut total, counts| {
+                total.players += counts.players;
+                total.bots += counts.bots;
+                total.monsters += counts.monsters;
+                total.npcs += counts.npcs;
+                total.item_drops += counts.item_drops;
+                total
+            })
+    }
+
+    /// The zones with the most entities, largest first
+    pub fn top_zones(&self, count: usize) -> Vec<(ZoneId, ZoneCensusCounts)> {
+        let mut zones: Vec<_> = self
+            .zones
+            .iter()
+            .map(|(zone_id, counts)| (*zone_id, *counts))
+            .collect();
+        zones.sort_by(|(a_id, a), (b_id, b)| {
+            b.total()
+                .cmp(&a.total())
+                .then_with(|| a_id.get().cmp(&b_id.get()))
+        });
+        zones.truncate(count);
+        zones
+    }
+}
@@ -0,0 +1,444 @@
+use arrayvec::ArrayVec;
+use bevy::math::{Vec2, Vec3};
+use enum_map::enum_map;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use rose_data::{
+    AbilityType, AiDatabase, CharacterMotionDatabase, EffectDatabase, GatherDatabase, ItemDatabase,
+    JobClassDatabase, MotionId, NpcData, NpcDatabase, NpcId, QuestDatabase, QuestTriggerHash,
+    SkillActionMode, SkillCooldown, SkillData, SkillDatabase, SkillId, SkillTargetFilter,
+    SkillType, StatusEffectDatabase, StatusEffectId, StringDatabase, TeleportAnchorDatabase,
+    WarpGateDatabase, ZoneCollision, ZoneData, ZoneDatabase, ZoneHeightmap, ZoneId,
+};
+use rose_data_irose::{
+    encode_ability_type, encode_clan_member_position, encode_item_class,
+    encode_skill_target_filter, encode_skill_type, get_data_decoder,
+};
+use rose_game_common::{
+    components::{BasicStats, CharacterGender, CharacterInfo, DroppedItem},
+    data::{AbilityValueCalculatorOptions, DropTable},
+};
+use rose_game_irose::data::get_ability_value_calculator;
+
+use crate::game::{
+    components::{
+        CharacterLootSettings, CharacterPrivacy, CharacterSkillMastery, CharacterStatistics,
+        CharacterTitles, Equipment, ExperiencePoints, HealthPoints, Hotbar, Inventory, Level,
+        ManaPoints, Position, QuestState, SkillList, SkillPoints, Stamina, StatPoints,
+        UnionMembership,
+    },
+    storage::character::{CharacterCreator, CharacterCreatorError, CharacterStorage},
+    GameData,
+};
+
+pub const SIMULATION_SECTOR_SIZE: u32 = 5000;
+pub const SIMULATION_NUM_SECTORS: u32 = 8;
+
+pub fn simulation_zone_id() -> ZoneId {
+    ZoneId::new(1).unwrap()
+}
+
+/// A flat zone with no heightmap or collision objects.
+pub fn simulation_zone(id: ZoneId) -> ZoneData {
+    let centre = (SIMULATION_SECTOR_SIZE * SIMULATION_NUM_SECTORS) as f32 / 2.0;
+
+    ZoneData {
+        id,
+        name: "Simulation",
+        description: "",
+        sector_size: SIMULATION_SECTOR_SIZE,
+        grid_per_patch: 4.0,
+        grid_size: 250.0,
+        event_objects: Vec::new(),
+        monster_spawns: Vec::new(),
+        npcs: Vec::new(),
+        sectors_base_position: Vec2::ZERO,
+        num_sectors_x: SIMULATION_NUM_SECTORS,
+        num_sectors_y: SIMULATION_NUM_SECTORS,
+        start_position: Vec3::new(centre, centre, 0.0),
+        revive_positions: vec![Vec3::new(centre, centre, 0.0)],
+        event_positions: HashMap::new(),
+        day_cycle: 0,
+        morning_time: 0,
+        day_time: 0,
+        evening_time: 0,
+        night_time: 0,
+        skybox_id: None,
+        heightmap: ZoneHeightmap::new(16000.0, 250.0),
+        collision: ZoneCollision::new(1000.0),
+        checksum: 0,
+        pvp: None,
+    }
+}
+
+/// A monster with no AI program, motions or drop table entries.
+pub fn simulation_npc(id: NpcId, level: i32, health_points: i32) -> NpcData {
+    NpcData {
+        id,
+        name: "Simulation Monster",
+        walk_speed: 200,
+        run_speed: 400,
+        scale: 1.0,
+        right_hand_part_index: 0,
+        left_hand_part_index: 0,
+        level,
+        health_points,
+        attack: 10,
+        hit: 10,
+        defence: 10,
+        resistance: 10,
+        avoid: 10,
+        attack_speed: 100,
+        is_attack_magic_damage: false,
+        // A non-zero index gives the monster an NpcAi so it is rewarded and despawned on death
+        ai_file_index: 1,
+        reward_xp: 10,
+        drop_table_index: 0,
+        drop_money_rate: 0,
+        drop_item_rate: 0,
+        npc_minimap_icon_index: 0,
+        summon_point_requirement: 0,
+        store_tabs: [None; 4],
+        store_union_number: None,
+        is_untargetable: false,
+        attack_range: 150,
+        npc_type_index: None,
+        hit_sound_material_type: 0,
+        face_icon_index: 0,
+        summon_monster_type: 0,
+        normal_effect_sound_id: None,
+        attack_sound_id: None,
+        hitted_sound_id: None,
+        hand_hit_effect_id: None,
+        die_effect_file_id: None,
+        die_sound_id: None,
+        npc_quest_type: 0,
+        glow_colour: (0.0, 0.0, 0.0),
+        create_effect_index: 0,
+        create_sound_id: None,
+        death_quest_trigger_name: String::new(),
+        npc_height: 0,
+        motion_data: Vec::new(),
+    }
+}
+
+/// A self targeted skill on the basic skill page which does nothing but cost mana.
+pub fn simulation_skill(id: SkillId, base_skill_id: Option<SkillId>, mana_cost: i32) -> SkillData {
+    let mut use_ability = ArrayVec::new();
+    use_ability.push((AbilityType::Mana, mana_cost));
+
+    SkillData {
+        id,
+        name: "Simulation Skill",
+        description: "",
+        base_skill_id,
+        level: 1,
+        learn_point_cost: 0,
+        learn_money_cost: 0,
+        skill_type: SkillType::BasicAction,
+        page: 0,
+        icon_number: 0,
+        use_ability,
+        required_ability: ArrayVec::new(),
+        required_job_class: None,
+        required_planet: None,
+        required_skills: ArrayVec::new(),
+        required_combo_skill_id: None,
+        combo_timeout: Duration::ZERO,
+        required_union: ArrayVec::new(),
+        required_equipment_class: ArrayVec::new(),
+        action_mode: SkillActionMode::Stop,
+        action_motion_id: None,
+        action_motion_speed: 1.0,
+        add_ability: [None, None],
+        basic_command: None,
+        bullet_effect_id: None,
+        bullet_link_dummy_bone_id: 0,
+        bullet_fire_sound_id: None,
+        cast_range: 0,
+        casting_motion_id: None,
+        casting_motion_speed: 1.0,
+        casting_repeat_motion_id: None,
+        casting_repeat_motion_count: 0,
+        casting_effects: [None, None, None, None],
+        cooldown: SkillCooldown::Skill {
+            duration: Duration::ZERO,
+        },
+        damage_type: 0,
+        harm: 0,
+        hit_effect_file_id: None,
+        hit_link_dummy_bone_id: None,
+        hit_sound_id: None,
+        hit_dummy_effect_file_id: [None, None],
+        hit_dummy_sound_id: [None, None],
+        item_make_number: 0,
+        power: 0,
+        scope: 0,
+        status_effects: [None, None],
+        status_effect_duration: Duration::ZERO,
+        success_ratio: 0,
+        summon_npc_id: None,
+        target_filter: SkillTargetFilter::OnlySelf,
+        toggle_mana_drain: 0,
+        warp_zone_id: None,
+        warp_zone_x: 0.0,
+        warp_zone_y: 0.0,
+    }
+}
+
+struct SimulationCharacterCreator {
+    start_position: Position,
+}
+
+impl CharacterCreator for SimulationCharacterCreator {
+    fn create(
+        &self,
+        name: String,
+        gender: CharacterGender,
+        birth_stone: u8,
+        face: u8,
+        hair: u8,
+    ) -> Result<CharacterStorage, CharacterCreatorError> {
+        let unique_id = QuestTriggerHash::from(name.as_str()).hash;
+
+        Ok(CharacterStorage {
+            info: CharacterInfo {
+                name,
+                unique_id,
+                gender,
+                race: 0,
+                birth_stone,
+                job: 0,
+                face,
+                hair,
+                revive_zone_id: self.start_position.zone_id,
+                revive_position: self.start_position.position,
+                fame: 0,
+                fame_b: 0,
+                fame_g: 0,
+                rank: 0,
+            },
+            basic_stats: self.get_basic_stats(gender)?,
+            inventory: Inventory::default(),
+            equipment: Equipment::default(),
+            level: Level::new(1),
+            experience_points: ExperiencePoints::default(),
+            position: self.start_position.clone(),
+            skill_list: self.create_skill_list(),
+            hotbar: Hotbar::default(),
+            delete_time: None,
+            health_points: HealthPoints::new(0),
+            mana_points: ManaPoints::new(0),
+            skill_points: SkillPoints::default(),
+            stat_points: StatPoints::default(),
+            quest_state: QuestState::default(),
+            union_membership: UnionMembership::default(),
+            stamina: Stamina::default(),
+            privacy: CharacterPrivacy::default(),
+            titles: CharacterTitles::default(),
+            statistics: CharacterStatistics::default(),
+            loot_settings: CharacterLootSettings::default(),
+            skill_mastery: CharacterSkillMastery::default(),
+            is_new_character: false,
+        })
+    }
+
+    fn get_basic_stats(
+        &self,
+        _gender: CharacterGender,
+    ) -> Result<BasicStats, CharacterCreatorError> {
+        Ok(BasicStats {
+            strength: 15,
+            dexterity: 15,
+            intelligence: 15,
+            concentration: 15,
+            charm: 10,
+            sense: 10,
+        })
+    }
+
+    fn create_skill_list(&self) -> SkillList {
+        SkillList::default()
+    }
+}
+
+struct SimulationDropTable {
+    drops: HashMap<NpcId, DroppedItem>,
+}
+
+impl DropTable for SimulationDropTable {
+    fn get_drop(
+        &self,
+        _world_drop_item_rate: i32,
+        _world_drop_money_rate: i32,
+        npc_id: NpcId,
+        _zone_id: ZoneId,
+        _level_difference: i32,
+        _character_drop_rate: i32,
+        _character_charm: i32,
+    ) -> Option<DroppedItem> {
+        self.drops.get(&npc_id).cloned()
+    }
+}
+
+/// Synthetic game data for the simulation world, built without a VFS.
+///
+/// Drops are deterministic: a monster with an entry in `drops` always drops it.
+pub(super) struct SimulationGameData {
+    pub npcs: Vec<NpcData>,
+    pub skills: Vec<SkillData>,
+    pub zones: Vec<ZoneData>,
+    pub drops: HashMap<NpcId, DroppedItem>,
+}
+
+impl Default for SimulationGameData {
+    fn default() -> Self {
+        Self {
+            npcs: Vec::new(),
+            skills: Vec::new(),
+            zones: vec![simulation_zone(simulation_zone_id())],
+            drops: HashMap::new(),
+        }
+    }
+}
+
+impl SimulationGameData {
+    pub fn build(self) -> GameData {
+        let string_database = Arc::new(StringDatabase {
+            language: 1,
+            encode_ability_type,
+            encode_clan_member_position,
+            encode_item_class,
+            encode_skill_target_filter,
+            encode_skill_type,
+            ability: Default::default(),
+            clan: Default::default(),
+            client_strings: Default::default(),
+            conversation: Default::default(),
+            item: Default::default(),
+            item_prefix: Default::default(),
+            item_class: Default::default(),
+            job: Default::default(),
+            job_class: Default::default(),
+            npc: Default::default(),
+            npc_store_tabs: Default::default(),
+            planet: Default::default(),
+            quest: Default::default(),
+            skill: Default::default(),
+            skill_target: Default::default(),
+            skill_type: Default::default(),
+            status_effect: Default::default(),
+            union: Default::default(),
+            zone: Default::default(),
+        });
+
+        let mut npcs = Vec::new();
+        for npc in self.npcs {
+            let index = npc.id.get() as usize;
+            if npcs.len() <= index {
+                npcs.resize_with(index + 1, || None);
+            }
+            npcs[index] = Some(npc);
+        }
+
+        let mut skills = Vec::new();
+        for skill in self.skills {
+            let index = skill.id.get() as usize;
+            if skills.len() <= index {
+                skills.resize_with(index + 1, || None);
+            }
+            skills[index] = Some(skill);
+        }
+
+        let mut zones = Vec::new();
+        for zone in self.zones {
+            let index = zone.id.get() as usize;
+            if zones.len() <= index {
+                zones.resize_with(index + 1, || None);
+            }
+            zones[index] = Some(zone);
+        }
+
+        let start_position = zones
+            .iter()
+            .flatten()
+            .next()
+            .map(|zone: &ZoneData| Position::new(zone.start_position, zone.id))
+            .expect("Simulation requires at least one zone");
+
+        let item_database = Arc::new(ItemDatabase::new(
+            string_database.clone(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+        ));
+        let npc_database = Arc::new(NpcDatabase::new(
+            string_database.clone(),
+            npcs,
+            HashMap::new(),
+            HashMap::new(),
+            enum_map! { _ => MotionId::new(0) },
+        ));
+        let skill_database = Arc::new(SkillDatabase::new(string_database.clone(), skills));
+
+        GameData {
+            character_creator: Box::new(SimulationCharacterCreator { start_position }),
+            ability_value_calculator: get_ability_value_calculator(
+                item_database.clone(),
+                skill_database.clone(),
+                npc_database.clone(),
+                AbilityValueCalculatorOptions::default(),
+            ),
+            data_decoder: get_data_decoder(),
+            drop_table: Box::new(SimulationDropTable { drops: self.drops }),
+            ai: Arc::new(AiDatabase::new(
+                HashMap::new(),
+                HashMap::new(),
+                HashMap::new(),
+            )),
+            effects: Arc::new(EffectDatabase::new(Vec::new(), Vec::new(), Vec::new())),
+            gather_nodes: Arc::new(GatherDatabase::default()),
+            items: item_database,
+            job_class: Arc::new(JobClassDatabase::new(string_database.clone(), Vec::new())),
+            motions: Arc::new(CharacterMotionDatabase::new(
+                1,
+                Vec::new(),
+                Vec::new(),
+                enum_map! { _ => MotionId::new(0) },
+                enum_map! { _ => 0 },
+                Vec::new(),
+            )),
+            npcs: npc_database,
+            quests: Arc::new(QuestDatabase {
+                _string_database: string_database.clone(),
+                quests: Vec::new(),
+                strings: HashMap::new(),
+                triggers: HashMap::new(),
+                triggers_by_hash: HashMap::new(),
+                tutorial_triggers: Vec::new(),
+            }),
+            skills: skill_database,
+            status_effects: Arc::new(StatusEffectDatabase::new(
+                string_database.clone(),
+                HashMap::new(),
+                StatusEffectId::new(1).unwrap(),
+            )),
+            teleport_anchors: Arc::new(TeleportAnchorDatabase::default()),
+            warp_gates: Arc::new(WarpGateDatabase::new(HashMap::new())),
+            zones: Arc::new(ZoneDatabase::new(string_database.clone(), zones)),
+            string_database,
+        }
+    }
+}
@@ -0,0 +1,342 @@
+//! A deterministic game world for testing game systems without a VFS or network.
+
+use bevy::{
+    ecs::{event::Events, system::SystemState, world::World},
+    math::Vec3,
+    prelude::{App, Commands, Component, Entity, Event, Mut, Res, ResMut, Resource},
+    time::TimeUpdateStrategy,
+    MinimalPlugins,
+};
+use crossbeam_channel::Sender;
+use rose_data::{NpcData, NpcId, SkillData, ZoneData, ZoneId};
+use rose_game_common::components::{
+    CharacterGender, DroppedItem, StatusEffects, StatusEffectsRegen,
+};
+use std::time::Duration;
+
+use crate::game::{
+    bundles::{client_entity_join_zone, CharacterBundle, MonsterBundle},
+    components::{
+        ClanMembership, ClientEntityType, Command, Cooldowns, DamageSources, GameClient,
+        MotionData, MoveMode, MoveSpeed, NextCommand, PartyMembership, PassiveRecoveryTime,
+        Position, SpawnOrigin, Team, ZoneChange,
+    },
+    game_world::add_game_world,
+    messages::{
+        client::ClientMessage,
+        control::{ControlMessage, ProtocolCapabilities},
+        server::ServerMessage,
+        server_queue::{server_message_queue, ServerMessageReceiver},
+        watch::ClientWatch,
+    },
+    resources::{ClientEntityList, GameConfig, GameData},
+};
+
+mod game_data;
+mod tests;
+
+use game_data::SimulationGameData;
+pub use game_data::{simulation_npc, simulation_skill, simulation_zone, simulation_zone_id};
+
+pub const SIMULATION_TICK: Duration = Duration::from_nanos(16_666_667);
+
+pub struct SimulationWorld {
+    pub app: App,
+    _control_tx: Sender<ControlMessage>,
+}
+
+/// Configures the game data and game config of a [`SimulationWorld`].
+pub struct SimulationBuilder {
+    game_data: SimulationGameData,
+    game_config: GameConfig,
+}
+
+impl Default for SimulationBuilder {
+    fn default() -> Self {
+        Self {
+            game_data: SimulationGameData::default(),
+            game_config: GameConfig {
+                enable_npc_spawns: false,
+                enable_monster_spawns: false,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl SimulationBuilder {
+    pub fn with_npc(mut self, npc: NpcData) -> Self {
+        self.game_data.npcs.push(npc);
+        self
+    }
+
+    pub fn with_skill(mut self, skill: SkillData) -> Self {
+        self.game_data.skills.push(skill);
+        self
+    }
+
+    /// Replaces the default flat zone with the same id.
+    pub fn with_zone(mut self, zone: ZoneData) -> Self {
+        self.game_data
+            .zones
+            .retain(|existing| existing.id != zone.id);
+        self.game_data.zones.push(zone);
+        self
+    }
+
+    pub fn with_drop(mut self, npc_id: NpcId, drop: DroppedItem) -> Self {
+        self.game_data.drops.insert(npc_id, drop);
+        self
+    }
+
+    pub fn with_game_config(mut self, configure: impl FnOnce(&mut GameConfig)) -> Self {
+        configure(&mut self.game_config);
+        self
+    }
+
+    pub fn build(self) -> SimulationWorld {
+        let (control_tx, control_rx) = crossbeam_channel::unbounded();
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(SIMULATION_TICK));
+        add_game_world(
+            &mut app,
+            control_rx,
+            self.game_config,
+            self.game_data.build(),
+        );
+
+        let mut simulation = SimulationWorld {
+            app,
+            _control_tx: control_tx,
+        };
+
+        // The first update initialises time, so damage timestamps are valid from here on
+        simulation.tick();
+        simulation
+    }
+}
+
+impl SimulationWorld {
+    pub fn builder() -> SimulationBuilder {
+        SimulationBuilder::default()
+    }
+
+    pub fn tick(&mut self) {
+        self.app.update();
+    }
+
+    pub fn advance(&mut self, ticks: usize) {
+        for _ in 0..ticks {
+            self.tick();
+        }
+    }
+
+    pub fn advance_duration(&mut self, duration: Duration) {
+        let ticks = (duration.as_secs_f64() / SIMULATION_TICK.as_secs_f64()).ceil();
+        self.advance(ticks as usize);
+    }
+
+    pub fn world(&self) -> &World {
+        &self.app.world
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.app.world
+    }
+
+    pub fn resource<R: Resource>(&self) -> &R {
+        self.app.world.resource::<R>()
+    }
+
+    pub fn get<C: Component>(&self, entity: Entity) -> Option<&C> {
+        self.app.world.get::<C>(entity)
+    }
+
+    pub fn get_mut<C: Component>(&mut self, entity: Entity) -> Option<Mut<C>> {
+        self.app.world.get_mut::<C>(entity)
+    }
+
+    pub fn exists(&self, entity: Entity) -> bool {
+        self.app.world.get_entity(entity).is_some()
+    }
+
+    pub fn send_event<E: Event>(&mut self, event: E) {
+        self.app.world.resource_mut::<Events<E>>().send(event);
+    }
+
+    /// Spawns a level 1 character which has joined the zone, without a game client.
+    pub fn spawn_character(&mut self, name: &str, zone_id: ZoneId, position: Vec3) -> Entity {
+        let mut system_state: SystemState<(Commands, ResMut<ClientEntityList>, Res<GameData>)> =
+            SystemState::new(&mut self.app.world);
+        let (mut commands, mut client_entity_list, game_data) =
+            system_state.get_mut(&mut self.app.world);
+
+        let character = create_character_bundle(&game_data, name, zone_id, position);
+        let character_position = character.position.clone();
+        let entity = commands.spawn(character).id();
+
+        client_entity_join_zone(
+            &mut commands,
+            &mut client_entity_list,
+            entity,
+            ClientEntityType::Character,
+            &character_position,
+        )
+        .expect("Failed to join simulation character into zone");
+
+        system_state.apply(&mut self.app.world);
+        entity
+    }
+
+    /// Spawns a level 1 character with a game client which is still loading the zone, the
+    /// client must send `JoinZoneRequest` before the character joins the zone.
+    pub fn spawn_joining_character(
+        &mut self,
+        name: &str,
+        zone_id: ZoneId,
+        position: Vec3,
+    ) -> SimulationClient {
+        let (client_message_tx, client_message_rx) = crossbeam_channel::unbounded();
+        let (server_message_tx, server_message_rx) = server_message_queue();
+
+        let character = create_character_bundle(
+            self.app.world.resource::<GameData>(),
+            name,
+            zone_id,
+            position,
+        );
+        let entity = self
+            .app
+            .world
+            .spawn((
+                character,
+                GameClient::new(
+                    client_message_rx,
+                    server_message_tx,
+                    ClientWatch::new(),
+                    ProtocolCapabilities::default(),
+                ),
+                ZoneChange::default(),
+            ))
+            .id();
+
+        SimulationClient {
+            entity,
+            client_message_tx,
+            server_message_rx,
+        }
+    }
+
+    /// Spawns a monster at `position`, on the default monster team.
+    pub fn spawn_monster(&mut self, npc_id: NpcId, zone_id: ZoneId, position: Vec3) -> Entity {
+        let mut system_state: SystemState<(Commands, ResMut<ClientEntityList>, Res<GameData>)> =
+            SystemState::new(&mut self.app.world);
+        let (mut commands, mut client_entity_list, game_data) =
+            system_state.get_mut(&mut self.app.world);
+
+        // A spawn range of 1 keeps the monster within one unit of the requested position
+        let entity = MonsterBundle::spawn(
+            &mut commands,
+            &mut client_entity_list,
+            &game_data,
+            npc_id,
+            zone_id,
+            SpawnOrigin::Quest(Entity::PLACEHOLDER, position),
+            1,
+            Team::default_monster(),
+            None,
+            None,
+        )
+        .expect("Failed to spawn simulation monster");
+
+        system_state.apply(&mut self.app.world);
+        entity
+    }
+}
+
+/// The network side of a simulated game client.
+pub struct SimulationClient {
+    pub entity: Entity,
+    pub client_message_tx: Sender<ClientMessage>,
+    pub server_message_rx: ServerMessageReceiver,
+}
+
+impl SimulationClient {
+    pub fn send(&self, message: ClientMessage) {
+        self.client_message_tx
+            .send(message)
+            .expect("Simulation client message channel closed");
+    }
+
+    /// Returns every message the server has sent since the last call.
+    pub fn receive_all(&mut self) -> Vec<ServerMessage> {
+        std::iter::from_fn(|| self.server_message_rx.try_recv()).collect()
+    }
+}
+
+fn create_character_bundle(
+    game_data: &GameData,
+    name: &str,
+    zone_id: ZoneId,
+    position: Vec3,
+) -> CharacterBundle {
+    let mut character = game_data
+        .character_creator
+        .create(name.to_string(), CharacterGender::Male, 1, 1, 1)
+        .expect("Failed to create simulation character");
+
+    let status_effects = StatusEffects::new();
+    let status_effects_regen = StatusEffectsRegen::new();
+    let ability_values = game_data.ability_value_calculator.calculate(
+        &character.info,
+        &character.level,
+        &character.equipment,
+        &character.basic_stats,
+        &character.skill_list,
+        &status_effects,
+    );
+    let motion_data =
+        MotionData::from_character(game_data.motions.as_ref(), 0, character.info.gender);
+    let move_mode = MoveMode::Run;
+    let move_speed = MoveSpeed::new(ability_values.get_move_speed(&move_mode));
+
+    character.position = Position::new(position, zone_id);
+    character.health_points.hp = ability_values.get_max_health();
+    character.mana_points.mp = ability_values.get_max_mana();
+
+    CharacterBundle {
+        ability_values,
+        basic_stats: character.basic_stats,
+        bank: Default::default(),
+        cooldowns: Cooldowns::default(),
+        command: Command::default(),
+        damage_sources: DamageSources::default_character(),
+        equipment: character.equipment,
+        experience_points: character.experience_points,
+        health_points: character.health_points,
+        hotbar: character.hotbar,
+        info: character.info,
+        inventory: character.inventory,
+        level: character.level,
+        mana_points: character.mana_points,
+        motion_data,
+        move_mode,
+        move_speed,
+        next_command: NextCommand::default(),
+        party_membership: PartyMembership::default(),
+        passive_recovery_time: PassiveRecoveryTime::default(),
+        position: character.position,
+        quest_state: character.quest_state,
+        skill_list: character.skill_list,
+        skill_points: character.skill_points,
+        stamina: character.stamina,
+        stat_points: character.stat_points,
+        status_effects,
+        status_effects_regen,
+        team: Team::default_character(),
+        union_membership: character.union_membership,
+        clan_membership: ClanMembership::default(),
+    }
+}
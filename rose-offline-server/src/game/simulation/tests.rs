@@ -0,0 +1,668 @@
+use bevy::{ecs::entity::Entity, math::Vec3, time::Time};
+use std::{num::NonZeroU16, time::Duration};
+
+use rose_data::{
+    ClanMemberPosition, Item, ItemReference, NpcId, ScheduleWindow, SkillId, StackableItem,
+    ZoneData, ZonePvpData, ZoneSafeArea,
+};
+use rose_game_common::{
+    components::{ClanLevel, ClanMark, ClanPermissions, ClanPoints, ClanUniqueId, DroppedItem},
+    data::Damage,
+};
+
+use crate::game::{
+    bundles::{pvp_check, PvpCheck, PvpDenied},
+    components::{
+        CharacterSkillMastery, Clan, ClanMember, ClanMemberStats, ClanMembership, ClanVault,
+        ClientEntity, ClientEntityType, Dead, ExperiencePoints, HealthPoints, Inventory, ItemDrop,
+        ManaPoints, Money, MoveMode, Position, SkillList, ZoneChange, ZoneChangeState,
+    },
+    events::{ClanEvent, DamageEvent, SkillEvent, SkillEventTarget},
+    messages::{client::ClientMessage, server::ServerMessage},
+    resources::{GameConfig, GameData, SkillMasteryConfig, CLIENT_ENTITY_ID_RECYCLE_TICKS},
+    simulation::{
+        simulation_npc, simulation_skill, simulation_zone, simulation_zone_id, SimulationWorld,
+    },
+};
+
+fn simulation_npc_id() -> NpcId {
+    NpcId::new(1).unwrap()
+}
+
+fn kill_damage() -> Damage {
+    Damage {
+        amount: 10000,
+        is_critical: false,
+        apply_hit_stun: false,
+    }
+}
+
+#[test]
+fn character_joins_zone() {
+    let mut simulation = SimulationWorld::builder().build();
+    let character = simulation.spawn_character("Tester", simulation_zone_id(), Vec3::ZERO);
+    simulation.tick();
+
+    let client_entity = simulation
+        .get::<ClientEntity>(character)
+        .expect("Character did not join zone");
+    assert!(matches!(
+        client_entity.entity_type,
+        ClientEntityType::Character
+    ));
+}
+
+#[test]
+fn damage_reduces_monster_health() {
+    let mut simulation = SimulationWorld::builder()
+        .with_npc(simulation_npc(simulation_npc_id(), 1, 100))
+        .build();
+    let character = simulation.spawn_character("Tester", simulation_zone_id(), Vec3::ZERO);
+    let monster = simulation.spawn_monster(simulation_npc_id(), simulation_zone_id(), Vec3::ZERO);
+    simulation.tick();
+
+    simulation.send_event(DamageEvent::Attack {
+        attacker: character,
+        defender: monster,
+        damage: Damage {
+            amount: 30,
+            is_critical: false,
+            apply_hit_stun: false,
+        },
+    });
+    simulation.tick();
+
+    assert_eq!(simulation.get::<HealthPoints>(monster).unwrap().hp, 70);
+    assert!(simulation.get::<Dead>(monster).is_none());
+}
+
+#[test]
+fn killing_monster_rewards_xp_and_despawns() {
+    let mut simulation = SimulationWorld::builder()
+        .with_npc(simulation_npc(simulation_npc_id(), 1, 100))
+        .build();
+    let character = simulation.spawn_character("Tester", simulation_zone_id(), Vec3::ZERO);
+    let monster = simulation.spawn_monster(simulation_npc_id(), simulation_zone_id(), Vec3::ZERO);
+    simulation.tick();
+
+    simulation.send_event(DamageEvent::Attack {
+        attacker: character,
+        defender: monster,
+        damage: kill_damage(),
+    });
+    simulation.tick();
+
+    assert_eq!(simulation.get::<HealthPoints>(monster).unwrap().hp, 0);
+    assert!(simulation.get::<Dead>(monster).is_some());
+
+    simulation.advance_duration(Duration::from_secs(2));
+    assert!(!simulation.exists(monster));
+    assert!(simulation.get::<ExperiencePoints>(character).unwrap().xp > 0);
+}
+
+#[test]
+fn killing_monster_drops_item() {
+    let mut simulation = SimulationWorld::builder()
+        .with_npc(simulation_npc(simulation_npc_id(), 1, 100))
+        .with_drop(simulation_npc_id(), DroppedItem::Money(Money(100)))
+        .build();
+    let character = simulation.spawn_character("Tester", simulation_zone_id(), Vec3::ZERO);
+    let monster = simulation.spawn_monster(simulation_npc_id(), simulation_zone_id(), Vec3::ZERO);
+    simulation.tick();
+
+    simulation.send_event(DamageEvent::Attack {
+        attacker: character,
+        defender: monster,
+        damage: kill_damage(),
+    });
+    simulation.advance(2);
+
+    let drops: Vec<_> = simulation
+        .world_mut()
+        .query::<&ItemDrop>()
+        .iter(simulation.world())
+        .filter_map(|item_drop| item_drop.item.clone())
+        .collect();
+    assert_eq!(drops, vec![DroppedItem::Money(Money(100))]);
+}
+
+#[test]
+fn environment_kill_credits_last_attacker() {
+    let mut simulation = SimulationWorld::builder()
+        .with_npc(simulation_npc(simulation_npc_id(), 1, 100))
+        .with_drop(simulation_npc_id(), DroppedItem::Money(Money(100)))
+        .build();
+    let character = simulation.spawn_character("Tester", simulation_zone_id(), Vec3::ZERO);
+    let monster = simulation.spawn_monster(simulation_npc_id(), simulation_zone_id(), Vec3::ZERO);
+    simulation.tick();
+
+    simulation.send_event(DamageEvent::Attack {
+        attacker: character,
+        defender: monster,
+        damage: Damage {
+            amount: 30,
+            is_critical: false,
+            apply_hit_stun: false,
+        },
+    });
+    simulation.tick();
+
+    simulation.send_event(DamageEvent::Environment {
+        defender: monster,
+        damage: kill_damage(),
+    });
+    simulation.advance(2);
+
+    let drops = simulation
+        .world_mut()
+        .query::<&ItemDrop>()
+        .iter(simulation.world())
+        .count();
+    assert_eq!(drops, 1);
+    assert!(simulation.get::<ExperiencePoints>(character).unwrap().xp > 0);
+}
+
+#[test]
+fn environment_kill_without_attackers_rewards_nothing() {
+    let mut simulation = SimulationWorld::builder()
+        .with_npc(simulation_npc(simulation_npc_id(), 1, 100))
+        .with_drop(simulation_npc_id(), DroppedItem::Money(Money(100)))
+        .build();
+    let character = simulation.spawn_character("Tester", simulation_zone_id(), Vec3::ZERO);
+    let monster = simulation.spawn_monster(simulation_npc_id(), simulation_zone_id(), Vec3::ZERO);
+    simulation.tick();
+
+    simulation.send_event(DamageEvent::Environment {
+        defender: monster,
+        damage: kill_damage(),
+    });
+    simulation.advance(2);
+
+    assert!(simulation.get::<Dead>(monster).is_some() || !simulation.exists(monster));
+    let drops = simulation
+        .world_mut()
+        .query::<&ItemDrop>()
+        .iter(simulation.world())
+        .count();
+    assert_eq!(drops, 0);
+    assert_eq!(simulation.get::<ExperiencePoints>(character).unwrap().xp, 0);
+}
+
+#[test]
+fn client_entity_id_is_not_reissued_within_recycle_window() {
+    let mut simulation = SimulationWorld::builder()
+        .with_npc(simulation_npc(simulation_npc_id(), 1, 100))
+        .build();
+    let character = simulation.spawn_character("Tester", simulation_zone_id(), Vec3::ZERO);
+    let monster = simulation.spawn_monster(simulation_npc_id(), simulation_zone_id(), Vec3::ZERO);
+    simulation.tick();
+    let monster_id = simulation.get::<ClientEntity>(monster).unwrap().id;
+
+    simulation.send_event(DamageEvent::Attack {
+        attacker: character,
+        defender: monster,
+        damage: kill_damage(),
+    });
+    simulation.advance_duration(Duration::from_secs(2));
+    assert!(!simulation.exists(monster));
+
+    let replacement =
+        simulation.spawn_monster(simulation_npc_id(), simulation_zone_id(), Vec3::ZERO);
+    simulation.tick();
+    assert_ne!(
+        simulation.get::<ClientEntity>(replacement).unwrap().id,
+        monster_id
+    );
+
+    simulation.advance(CLIENT_ENTITY_ID_RECYCLE_TICKS as usize);
+    let recycled = simulation.spawn_monster(simulation_npc_id(), simulation_zone_id(), Vec3::ZERO);
+    simulation.tick();
+    assert_eq!(
+        simulation.get::<ClientEntity>(recycled).unwrap().id,
+        monster_id
+    );
+}
+
+#[test]
+fn identical_money_drops_are_merged() {
+    let mut simulation = SimulationWorld::builder()
+        .with_npc(simulation_npc(simulation_npc_id(), 1, 100))
+        .with_drop(simulation_npc_id(), DroppedItem::Money(Money(100)))
+        .build();
+    let character = simulation.spawn_character("Tester", simulation_zone_id(), Vec3::ZERO);
+    let monsters = [
+        simulation.spawn_monster(simulation_npc_id(), simulation_zone_id(), Vec3::ZERO),
+        simulation.spawn_monster(simulation_npc_id(), simulation_zone_id(), Vec3::ZERO),
+    ];
+    simulation.tick();
+
+    for monster in monsters {
+        simulation.send_event(DamageEvent::Attack {
+            attacker: character,
+            defender: monster,
+            damage: kill_damage(),
+        });
+    }
+    simulation.advance(2);
+
+    let drops: Vec<_> = simulation
+        .world_mut()
+        .query::<&ItemDrop>()
+        .iter(simulation.world())
+        .filter_map(|item_drop| item_drop.item.clone())
+        .collect();
+    assert_eq!(drops, vec![DroppedItem::Money(Money(200))]);
+}
+
+fn enable_skill_mastery(game_config: &mut GameConfig) {
+    game_config.skill_mastery = SkillMasteryConfig {
+        enabled: true,
+        xp_per_use: 1,
+        level_xp: vec![2],
+        mana_cost_reduction_percent: 50,
+        cooldown_reduction_percent: 0,
+    };
+}
+
+/// Spawns a character which has learnt skill 1 and is tracking skill mastery.
+fn spawn_skill_mastery_character(simulation: &mut SimulationWorld) -> Entity {
+    let character = simulation.spawn_character("Tester", simulation_zone_id(), Vec3::ZERO);
+    simulation
+        .world_mut()
+        .entity_mut(character)
+        .insert(CharacterSkillMastery::default());
+    learn_skill(simulation, character, SkillId::new(1).unwrap());
+    simulation.tick();
+
+    character
+}
+
+fn learn_skill(simulation: &mut SimulationWorld, character: Entity, skill_id: SkillId) {
+    let skills = simulation.resource::<GameData>().skills.clone();
+    let skill_data = skills.get_skill(skill_id).unwrap();
+    let mut skill_list = simulation.get_mut::<SkillList>(character).unwrap();
+    if let Some(previous_skill) = skills.iter().find(|skill| skill.id != skill_id) {
+        skill_list.remove_skill(previous_skill);
+    }
+    skill_list.add_skill(skill_data).unwrap();
+}
+
+fn use_skill(simulation: &mut SimulationWorld, character: Entity, skill_id: SkillId) -> i32 {
+    let mana_before = simulation.get::<ManaPoints>(character).unwrap().mp;
+    let now = simulation.resource::<Time>().last_update().unwrap();
+    simulation.send_event(SkillEvent::new(
+        character,
+        now,
+        skill_id,
+        SkillEventTarget::Entity(character),
+        None,
+    ));
+    simulation.tick();
+    mana_before - simulation.get::<ManaPoints>(character).unwrap().mp
+}
+
+#[test]
+fn skill_mastery_level_reduces_mana_cost() {
+    let mut simulation = SimulationWorld::builder()
+        .with_skill(simulation_skill(SkillId::new(1).unwrap(), None, 10))
+        .with_skill(simulation_skill(SkillId::new(2).unwrap(), None, 10))
+        .with_game_config(enable_skill_mastery)
+        .build();
+    let character = spawn_skill_mastery_character(&mut simulation);
+    let skill_id = SkillId::new(1).unwrap();
+
+    assert_eq!(use_skill(&mut simulation, character, skill_id), 10);
+    assert_eq!(use_skill(&mut simulation, character, skill_id), 5);
+
+    let skill_mastery = simulation.get::<CharacterSkillMastery>(character).unwrap();
+    assert_eq!(skill_mastery.skills.len(), 1);
+    assert_eq!(skill_mastery.skills[0].xp, 2);
+}
+
+#[test]
+fn skill_mastery_is_lost_when_slot_has_a_different_skill() {
+    let mut simulation = SimulationWorld::builder()
+        .with_skill(simulation_skill(SkillId::new(1).unwrap(), None, 10))
+        .with_skill(simulation_skill(SkillId::new(2).unwrap(), None, 10))
+        .with_game_config(enable_skill_mastery)
+        .build();
+    let character = spawn_skill_mastery_character(&mut simulation);
+
+    use_skill(&mut simulation, character, SkillId::new(1).unwrap());
+    use_skill(&mut simulation, character, SkillId::new(1).unwrap());
+    learn_skill(&mut simulation, character, SkillId::new(2).unwrap());
+
+    assert_eq!(
+        use_skill(&mut simulation, character, SkillId::new(2).unwrap()),
+        10
+    );
+    let skill_mastery = simulation.get::<CharacterSkillMastery>(character).unwrap();
+    assert_eq!(skill_mastery.skills.len(), 1);
+    assert_eq!(skill_mastery.skills[0].xp, 1);
+}
+
+fn pvp_zone(schedule: Vec<ScheduleWindow>) -> ZoneData {
+    let mut zone = simulation_zone(simulation_zone_id());
+    zone.pvp = Some(ZonePvpData {
+        zone: simulation_zone_id(),
+        pvp: true,
+        schedule,
+        spawn_safe_radius: 1000.0,
+        safe_areas: vec![ZoneSafeArea {
+            x: 10000.0,
+            y: 10000.0,
+            radius: 500.0,
+        }],
+    });
+    zone
+}
+
+fn check_pvp(simulation: &SimulationWorld, attacker: Entity, target: Entity) -> PvpCheck {
+    pvp_check(
+        simulation.resource::<GameData>(),
+        simulation.get::<ClientEntity>(attacker).unwrap(),
+        simulation.get::<Position>(attacker).unwrap(),
+        simulation.get::<ClientEntity>(target).unwrap(),
+        simulation.get::<Position>(target).unwrap(),
+    )
+}
+
+#[test]
+fn pvp_allowed_outside_safe_areas() {
+    let mut simulation = SimulationWorld::builder()
+        .with_zone(pvp_zone(Vec::new()))
+        .build();
+    let zone_id = simulation_zone_id();
+    let attacker =
+        simulation.spawn_character("Attacker", zone_id, Vec3::new(25000.0, 20000.0, 0.0));
+    let target = simulation.spawn_character("Target", zone_id, Vec3::new(25100.0, 20000.0, 0.0));
+    simulation.tick();
+
+    assert_eq!(check_pvp(&simulation, attacker, target), PvpCheck::Allowed);
+}
+
+#[test]
+fn pvp_denied_inside_safe_areas() {
+    let mut simulation = SimulationWorld::builder()
+        .with_zone(pvp_zone(Vec::new()))
+        .build();
+    let zone_id = simulation_zone_id();
+    let outside = simulation.spawn_character("Outside", zone_id, Vec3::new(25000.0, 20000.0, 0.0));
+    let near_spawn = simulation.spawn_character("Spawn", zone_id, Vec3::new(20500.0, 20000.0, 0.0));
+    let safe_area = simulation.spawn_character("Safe", zone_id, Vec3::new(10000.0, 10400.0, 0.0));
+    simulation.tick();
+
+    let denied = PvpCheck::Denied(PvpDenied::SafeArea);
+    assert_eq!(check_pvp(&simulation, outside, near_spawn), denied);
+    assert_eq!(check_pvp(&simulation, near_spawn, outside), denied);
+    assert_eq!(check_pvp(&simulation, outside, safe_area), denied);
+    assert_eq!(check_pvp(&simulation, safe_area, outside), denied);
+}
+
+#[test]
+fn pvp_follows_zone_schedule() {
+    for (schedule, expected) in [
+        // Always active
+        (
+            vec![ScheduleWindow {
+                weekdays: Vec::new(),
+                start_hour: 0,
+                end_hour: 24,
+            }],
+            PvpCheck::Allowed,
+        ),
+        // A window which starts and ends at the same hour is never active
+        (
+            vec![ScheduleWindow {
+                weekdays: Vec::new(),
+                start_hour: 12,
+                end_hour: 12,
+            }],
+            PvpCheck::Denied(PvpDenied::OutsideSchedule),
+        ),
+    ] {
+        let mut simulation = SimulationWorld::builder()
+            .with_zone(pvp_zone(schedule))
+            .build();
+        let zone_id = simulation_zone_id();
+        let attacker =
+            simulation.spawn_character("Attacker", zone_id, Vec3::new(25000.0, 20000.0, 0.0));
+        let target =
+            simulation.spawn_character("Target", zone_id, Vec3::new(25100.0, 20000.0, 0.0));
+        simulation.tick();
+
+        assert_eq!(check_pvp(&simulation, attacker, target), expected);
+    }
+}
+
+#[test]
+fn pvp_has_no_rules_without_zone_pvp_data() {
+    let mut simulation = SimulationWorld::builder().build();
+    let zone_id = simulation_zone_id();
+    let attacker =
+        simulation.spawn_character("Attacker", zone_id, Vec3::new(25000.0, 20000.0, 0.0));
+    let target = simulation.spawn_character("Target", zone_id, Vec3::new(25100.0, 20000.0, 0.0));
+    simulation.tick();
+
+    assert_eq!(check_pvp(&simulation, attacker, target), PvpCheck::NoRules);
+}
+
+/// Spawns a clan with an officer as its master, and a character which is not in any clan.
+fn spawn_clan(simulation: &mut SimulationWorld, clan_name: &str) -> (Entity, Entity, Entity) {
+    let officer = simulation.spawn_character("Officer", simulation_zone_id(), Vec3::ZERO);
+    let applicant = simulation.spawn_character("Applicant", simulation_zone_id(), Vec3::ZERO);
+
+    let clan_entity = simulation
+        .world_mut()
+        .spawn(Clan {
+            unique_id: ClanUniqueId::new(1).unwrap(),
+            name: clan_name.to_string(),
+            description: String::default(),
+            notice: String::default(),
+            money: Money(0),
+            points: ClanPoints(0),
+            level: ClanLevel::new(1).unwrap(),
+            members: vec![ClanMember::Online {
+                entity: officer,
+                position: ClanMemberPosition::Master,
+                contribution: ClanPoints(0),
+                stats: ClanMemberStats::default(),
+            }],
+            mark: ClanMark::Premade {
+                background: NonZeroU16::new(1).unwrap(),
+                foreground: NonZeroU16::new(1).unwrap(),
+            },
+            skills: Vec::new(),
+            vault: ClanVault::default(),
+            permissions: ClanPermissions::default(),
+            recruitment: "Recruiting".to_string(),
+            applications: Vec::new(),
+            pending_invites: Vec::new(),
+        })
+        .id();
+    simulation
+        .world_mut()
+        .entity_mut(officer)
+        .insert(ClanMembership::new(clan_entity));
+    simulation.tick();
+
+    (officer, applicant, clan_entity)
+}
+
+fn clan_apply(simulation: &mut SimulationWorld, applicant: Entity, clan_name: &str, message: &str) {
+    simulation.send_event(ClanEvent::Apply {
+        entity: applicant,
+        clan_name: clan_name.to_string(),
+        message: message.to_string(),
+    });
+    simulation.tick();
+}
+
+#[test]
+fn clan_application_accepted_joins_clan() {
+    let mut simulation = SimulationWorld::builder().build();
+    let (officer, applicant, clan_entity) = spawn_clan(&mut simulation, "Accepting");
+    clan_apply(&mut simulation, applicant, "Accepting", "Hello");
+    assert_eq!(
+        simulation
+            .get::<Clan>(clan_entity)
+            .unwrap()
+            .applications
+            .len(),
+        1
+    );
+
+    simulation.send_event(ClanEvent::ApplicationReply {
+        requester: officer,
+        name: "Applicant".to_string(),
+        accepted: true,
+    });
+    simulation.tick();
+
+    let clan = simulation.get::<Clan>(clan_entity).unwrap();
+    assert!(clan.applications.is_empty());
+    assert!(clan.find_online_member(applicant).is_some());
+    assert_eq!(
+        simulation.get::<ClanMembership>(applicant).unwrap().clan(),
+        Some(clan_entity)
+    );
+}
+
+#[test]
+fn clan_application_rejected_does_not_join_clan() {
+    let mut simulation = SimulationWorld::builder().build();
+    let (officer, applicant, clan_entity) = spawn_clan(&mut simulation, "Rejecting");
+    clan_apply(&mut simulation, applicant, "Rejecting", "Hello");
+
+    simulation.send_event(ClanEvent::ApplicationReply {
+        requester: officer,
+        name: "Applicant".to_string(),
+        accepted: false,
+    });
+    simulation.tick();
+
+    let clan = simulation.get::<Clan>(clan_entity).unwrap();
+    assert!(clan.applications.is_empty());
+    assert!(clan.find_online_member(applicant).is_none());
+    assert!(simulation
+        .get::<ClanMembership>(applicant)
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn clan_application_message_too_long_is_rejected() {
+    let mut simulation = SimulationWorld::builder().build();
+    let (_, applicant, clan_entity) = spawn_clan(&mut simulation, "Strict");
+    clan_apply(&mut simulation, applicant, "Strict", &"a".repeat(256));
+
+    assert!(simulation
+        .get::<Clan>(clan_entity)
+        .unwrap()
+        .applications
+        .is_empty());
+}
+
+#[test]
+fn clan_vault_rejects_untradeable_items() {
+    let item = Item::from(StackableItem::new(ItemReference::material(1), 1).unwrap());
+    let mut simulation = SimulationWorld::builder()
+        .with_game_config(|game_config| {
+            game_config.untradeable_items = vec![item.get_item_reference()];
+        })
+        .build();
+    let (officer, _, clan_entity) = spawn_clan(&mut simulation, "Vault");
+    let item_slot = simulation
+        .get_mut::<Inventory>(officer)
+        .unwrap()
+        .try_add_item(item.clone())
+        .map(|(item_slot, _)| item_slot)
+        .unwrap();
+
+    simulation.send_event(ClanEvent::VaultDeposit {
+        entity: officer,
+        item_slot,
+        item: item.clone(),
+    });
+    simulation.tick();
+
+    assert!(simulation
+        .get::<Clan>(clan_entity)
+        .unwrap()
+        .vault
+        .slots
+        .iter()
+        .all(Option::is_none));
+    assert_eq!(
+        simulation
+            .get::<Inventory>(officer)
+            .unwrap()
+            .get_item(item_slot),
+        Some(&item)
+    );
+}
+
+#[test]
+fn zone_change_defers_and_replays_messages() {
+    let mut simulation = SimulationWorld::builder().build();
+    let client = simulation.spawn_joining_character("Joiner", simulation_zone_id(), Vec3::ZERO);
+
+    // Messages which are valid in any zone are kept until the zone is joined
+    client.send(ClientMessage::RunToggle);
+    simulation.tick();
+    let zone_change = simulation.get::<ZoneChange>(client.entity).unwrap();
+    assert_eq!(zone_change.state, ZoneChangeState::Loading);
+    assert_eq!(zone_change.deferred_messages.len(), 1);
+    assert_eq!(
+        *simulation.get::<MoveMode>(client.entity).unwrap(),
+        MoveMode::Run
+    );
+
+    client.send(ClientMessage::JoinZoneRequest {
+        zone_checksum: None,
+    });
+    simulation.tick();
+    assert_eq!(
+        simulation.get::<ZoneChange>(client.entity).unwrap().state,
+        ZoneChangeState::Joined
+    );
+
+    // The deferred message is replayed once joined, after which the zone change is complete
+    simulation.advance(2);
+    assert!(simulation.get::<ClientEntity>(client.entity).is_some());
+    assert!(simulation.get::<ZoneChange>(client.entity).is_none());
+    assert_eq!(
+        *simulation.get::<MoveMode>(client.entity).unwrap(),
+        MoveMode::Walk
+    );
+}
+
+#[test]
+fn zone_change_timeout_disconnects_client() {
+    let mut simulation = SimulationWorld::builder()
+        .with_game_config(|game_config| game_config.zone_change_timeout_secs = 1)
+        .build();
+    let mut client = simulation.spawn_joining_character("Joiner", simulation_zone_id(), Vec3::ZERO);
+
+    simulation.advance_duration(Duration::from_secs(2));
+    assert!(client
+        .receive_all()
+        .iter()
+        .any(|message| matches!(message, ServerMessage::Disconnect)));
+    assert_eq!(
+        simulation.get::<ZoneChange>(client.entity).unwrap().state,
+        ZoneChangeState::Disconnecting
+    );
+
+    // Messages sent whilst disconnecting are discarded, so the character never joins the zone
+    client.send(ClientMessage::JoinZoneRequest {
+        zone_checksum: None,
+    });
+    simulation.advance(2);
+    assert!(simulation.get::<ClientEntity>(client.entity).is_none());
+    assert_eq!(
+        simulation.get::<ZoneChange>(client.entity).unwrap().state,
+        ZoneChangeState::Disconnecting
+    );
+    assert!(client.receive_all().is_empty());
+}
@@ -6,7 +6,10 @@ use thiserror::Error;
 
 use rose_game_common::data::Password;
 
-use crate::game::storage::ACCOUNT_STORAGE_DIR;
+use crate::game::storage::{
+    encryption::{account_storage_key, is_encrypted},
+    ACCOUNT_STORAGE_DIR,
+};
 
 #[derive(Error, Debug)]
 pub enum AccountStorageError {
@@ -28,17 +31,21 @@ fn get_account_path(name: &str) -> PathBuf {
     ACCOUNT_STORAGE_DIR.join(format!("{}.json", name))
 }
 
-fn hash_password(password: &Password) -> String {
+fn hash_password(password: &Password) -> Result<String, AccountStorageError> {
+    // A seeded password can only be checked against an existing hash
+    let password_md5 = password
+        .to_md5()
+        .ok_or(AccountStorageError::InvalidPassword)?;
     let mut hasher = Sha256::new();
-    hasher.update(password.to_md5());
-    hex::encode(hasher.finalize())
+    hasher.update(password_md5);
+    Ok(hex::encode(hasher.finalize()))
 }
 
 impl AccountStorage {
     pub fn create(name: &str, password: &Password) -> Result<Self, anyhow::Error> {
         let account = Self {
             name: String::from(name),
-            password_md5_sha256: hash_password(password),
+            password_md5_sha256: hash_password(password)?,
             character_names: Vec::new(),
         };
         account.save_impl(false)?;
@@ -46,17 +53,34 @@ impl AccountStorage {
     }
 
     pub fn try_load(name: &str, password: &Password) -> Result<Self, anyhow::Error> {
+        let account = Self::try_load_without_password(name)?;
+        account.check_password(password)?;
+        Ok(account)
+    }
+
+    /// Loads the account without checking its password, for admin requests.
+    pub fn try_load_without_password(name: &str) -> Result<Self, anyhow::Error> {
         let path = get_account_path(name);
         if path.exists() {
-            let str = std::fs::read_to_string(&path)
+            let mut data = std::fs::read(&path)
                 .with_context(|| format!("Failed to read file {}", path.to_string_lossy()))?;
-            let account: Self = serde_json::from_str(&str).with_context(|| {
+            if is_encrypted(&data) {
+                let key = account_storage_key().with_context(|| {
+                    format!(
+                        "Account file {} is encrypted but no storage key was given",
+                        path.to_string_lossy()
+                    )
+                })?;
+                data = key.decrypt(&data).with_context(|| {
+                    format!("Failed to decrypt file {}", path.to_string_lossy())
+                })?;
+            }
+            let account: Self = serde_json::from_slice(&data).with_context(|| {
                 format!(
                     "Failed to deserialise AccountStorage from file {}",
                     path.to_string_lossy()
                 )
             })?;
-            account.check_password(password)?;
             Ok(account)
         } else {
             Err(AccountStorageError::NotFound.into())
@@ -64,13 +88,25 @@ impl AccountStorage {
     }
 
     pub fn check_password(&self, password: &Password) -> Result<(), anyhow::Error> {
-        if self.password_md5_sha256 == hash_password(password) {
+        let valid = match password {
+            Password::Seeded { seed, hash } => {
+                hash.eq_ignore_ascii_case(&Password::seeded_hash(*seed, &self.password_md5_sha256))
+            }
+            _ => hash_password(password)? == self.password_md5_sha256,
+        };
+
+        if valid {
             Ok(())
         } else {
             Err(AccountStorageError::InvalidPassword.into())
         }
     }
 
+    pub fn set_password(&mut self, password: &Password) -> Result<(), anyhow::Error> {
+        self.password_md5_sha256 = hash_password(password)?;
+        Ok(())
+    }
+
     pub fn save(&self) -> Result<(), anyhow::Error> {
         self.save_impl(true)
     }
@@ -93,13 +129,23 @@ impl AccountStorage {
             )
         })?;
 
+        let data = match account_storage_key() {
+            Some(key) => key.encrypt(json.as_bytes()).with_context(|| {
+                format!(
+                    "Failed to encrypt data whilst saving account {}",
+                    &self.name
+                )
+            })?,
+            None => json.into_bytes(),
+        };
+
         let mut file = tempfile::NamedTempFile::new().with_context(|| {
             format!(
                 "Failed to create temporary file whilst saving account {}",
                 &self.name
             )
         })?;
-        file.write_all(json.as_bytes()).with_context(|| {
+        file.write_all(&data).with_context(|| {
             format!(
                 "Failed to write data to temporary file whilst saving account {}",
                 &self.name
@@ -125,3 +171,34 @@ impl AccountStorage {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeded_password_is_checked_against_stored_hash() {
+        let account = AccountStorage {
+            name: String::from("Tester"),
+            password_md5_sha256: hash_password(&Password::Plaintext(String::from("secret")))
+                .unwrap(),
+            character_names: Vec::new(),
+        };
+        let hash = Password::seeded_hash(0x1234, &account.password_md5_sha256);
+
+        assert!(account
+            .check_password(&Password::Seeded {
+                seed: 0x1234,
+                hash: hash.to_uppercase(),
+            })
+            .is_ok());
+        assert!(account
+            .check_password(&Password::Seeded { seed: 0x4321, hash })
+            .is_err());
+        assert!(hash_password(&Password::Seeded {
+            seed: 0x1234,
+            hash: String::new(),
+        })
+        .is_err());
+    }
+}
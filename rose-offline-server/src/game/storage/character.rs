@@ -1,16 +1,24 @@
 use anyhow::Context;
+use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
-use std::{io::Write, path::PathBuf};
+use std::{
+    fs::OpenOptions,
+    io::{ErrorKind, Write},
+    path::PathBuf,
+    sync::{Mutex, MutexGuard, PoisonError},
+    time::SystemTime,
+};
 
 use rose_game_common::components::CharacterGender;
 
 use crate::game::{
     components::{
-        BasicStats, CharacterDeleteTime, CharacterInfo, Equipment, ExperiencePoints, HealthPoints,
-        Hotbar, Inventory, Level, ManaPoints, Position, QuestState, SkillList, SkillPoints,
-        Stamina, StatPoints, UnionMembership,
+        BasicStats, CharacterDeleteTime, CharacterInfo, CharacterLootSettings, CharacterPrivacy,
+        CharacterSkillMastery, CharacterStatistics, CharacterTitles, Equipment, ExperiencePoints,
+        HealthPoints, Hotbar, Inventory, Level, ManaPoints, Position, QuestState, SkillList,
+        SkillPoints, Stamina, StatPoints, UnionMembership,
     },
-    storage::CHARACTER_STORAGE_DIR,
+    storage::{writer::queue_storage_write, CHARACTER_STORAGE_DIR},
 };
 
 #[derive(Deserialize, Serialize)]
@@ -32,12 +40,166 @@ pub struct CharacterStorage {
     pub quest_state: QuestState,
     pub union_membership: UnionMembership,
     pub stamina: Stamina,
+    #[serde(default)]
+    pub privacy: CharacterPrivacy,
+    #[serde(default)]
+    pub titles: CharacterTitles,
+    #[serde(default)]
+    pub statistics: CharacterStatistics,
+    #[serde(default)]
+    pub loot_settings: CharacterLootSettings,
+    #[serde(default)]
+    pub skill_mastery: CharacterSkillMastery,
+    /// Has not yet joined a zone
+    #[serde(default)]
+    pub is_new_character: bool,
+}
+
+lazy_static! {
+    // Held whilst a character file is being written, so a journal applied on the storage writer
+    // thread cannot interleave its load and save with a save from the game thread
+    static ref CHARACTER_FILE_LOCK: Mutex<()> = Mutex::new(());
 }
 
+// Once the checkpoint file grows past this size it is rewritten with only the latest checkpoint
+const MAX_CHECKPOINT_FILE_SIZE: u64 = 16 * 1024;
+
 fn get_character_path(name: &str) -> PathBuf {
     CHARACTER_STORAGE_DIR.join(format!("{}.json", name))
 }
 
+fn get_character_checkpoint_path(name: &str) -> PathBuf {
+    CHARACTER_STORAGE_DIR.join(format!("{}.checkpoints", name))
+}
+
+/// Frequently changing character state which is appended to a checkpoint file between full
+/// saves, so less progress is lost if the server stops without saving.
+///
+/// The latest checkpoint is merged into the character when it is loaded, and the checkpoint
+/// file is removed by the next full save. Points are stored together with what they are spent
+/// on, so a restored checkpoint can never give back points which were already spent.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct CharacterCheckpoint {
+    pub position: Position,
+    pub health_points: HealthPoints,
+    pub mana_points: ManaPoints,
+    pub level: Level,
+    pub experience_points: ExperiencePoints,
+    #[serde(default)]
+    pub basic_stats: Option<BasicStats>,
+    #[serde(default)]
+    pub stat_points: Option<StatPoints>,
+    #[serde(default)]
+    pub skill_list: Option<SkillList>,
+    #[serde(default)]
+    pub skill_points: Option<SkillPoints>,
+    #[serde(default)]
+    pub skill_mastery: Option<CharacterSkillMastery>,
+}
+
+impl PartialEq for CharacterCheckpoint {
+    fn eq(&self, other: &Self) -> bool {
+        self.position.position == other.position.position
+            && self.position.zone_id == other.position.zone_id
+            && self.health_points.hp == other.health_points.hp
+            && self.mana_points.mp == other.mana_points.mp
+            && self.level.level == other.level.level
+            && self.experience_points.xp == other.experience_points.xp
+            && self.basic_stats == other.basic_stats
+            && self.stat_points.map(|stat_points| stat_points.points)
+                == other.stat_points.map(|stat_points| stat_points.points)
+            && self.skill_list == other.skill_list
+            && self.skill_points.map(|skill_points| skill_points.points)
+                == other.skill_points.map(|skill_points| skill_points.points)
+            && self.skill_mastery == other.skill_mastery
+    }
+}
+
+impl CharacterCheckpoint {
+    /// Queues the checkpoint to be appended on the storage writer thread.
+    ///
+    /// A checkpoint taken before the character was last fully saved holds older state than the
+    /// character file, so it is dropped instead of being written.
+    pub fn queue_append(self, name: String) {
+        let created = SystemTime::now();
+        queue_storage_write(format!("checkpoint character {}", name), move || {
+            let _lock = CharacterStorage::lock_files();
+            if CharacterStorage::modified_time(&name).map_or(false, |modified| modified >= created)
+            {
+                return Ok(());
+            }
+
+            self.append(&name)
+        });
+    }
+
+    fn append(&self, name: &str) -> Result<(), anyhow::Error> {
+        let path = get_character_checkpoint_path(name);
+        let mut json = serde_json::to_string(self).with_context(|| {
+            format!(
+                "Failed to serialise CharacterCheckpoint whilst checkpointing character {}",
+                name
+            )
+        })?;
+        json.push('\n');
+
+        let file_size = std::fs::metadata(&path).map_or(0, |metadata| metadata.len());
+        if file_size > MAX_CHECKPOINT_FILE_SIZE {
+            // Only the latest checkpoint is ever used, so replace the file rather than grow it
+            let mut file = tempfile::NamedTempFile::new().with_context(|| {
+                format!(
+                    "Failed to create temporary file whilst checkpointing character {}",
+                    name
+                )
+            })?;
+            file.write_all(json.as_bytes()).with_context(|| {
+                format!(
+                    "Failed to write data to temporary file whilst checkpointing character {}",
+                    name
+                )
+            })?;
+            file.persist(&path).with_context(|| {
+                format!(
+                    "Failed to persist temporary checkpoint file to path {}",
+                    path.to_string_lossy()
+                )
+            })?;
+            return Ok(());
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open file {}", path.to_string_lossy()))?;
+        file.write_all(json.as_bytes())
+            .with_context(|| format!("Failed to write file {}", path.to_string_lossy()))?;
+        Ok(())
+    }
+
+    /// Returns the latest checkpoint, ignoring any record left incomplete by a crash.
+    fn load_latest(name: &str) -> Option<Self> {
+        let str = std::fs::read_to_string(get_character_checkpoint_path(name)).ok()?;
+        str.lines()
+            .rev()
+            .find_map(|line| serde_json::from_str::<CharacterCheckpoint>(line).ok())
+    }
+
+    fn remove(name: &str) -> Result<(), anyhow::Error> {
+        let path = get_character_checkpoint_path(name);
+        match std::fs::remove_file(&path) {
+            Ok(_) => Ok(()),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(()),
+            Err(error) => Err(error).with_context(|| {
+                format!(
+                    "Failed to remove checkpoint file {}",
+                    path.to_string_lossy()
+                )
+            }),
+        }
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Copy, Clone, Debug)]
 pub enum CharacterCreatorError {
@@ -60,6 +222,8 @@ pub trait CharacterCreator {
 
     fn get_basic_stats(&self, gender: CharacterGender)
         -> Result<BasicStats, CharacterCreatorError>;
+
+    fn create_skill_list(&self) -> SkillList;
 }
 
 impl CharacterStorage {
@@ -71,17 +235,66 @@ impl CharacterStorage {
         let path = get_character_path(name);
         let str = std::fs::read_to_string(&path)
             .with_context(|| format!("Failed to read file {}", path.to_string_lossy()))?;
-        let character: CharacterStorage = serde_json::from_str(&str).with_context(|| {
+        let mut character: CharacterStorage = serde_json::from_str(&str).with_context(|| {
             format!(
                 "Failed to deserialise CharacterStorage from file {}",
                 path.to_string_lossy()
             )
         })?;
+
+        if let Some(checkpoint) = CharacterCheckpoint::load_latest(name) {
+            character.position = checkpoint.position;
+            character.health_points = checkpoint.health_points;
+            character.mana_points = checkpoint.mana_points;
+            character.level = checkpoint.level;
+            character.experience_points = checkpoint.experience_points;
+
+            if let (Some(basic_stats), Some(stat_points)) =
+                (checkpoint.basic_stats, checkpoint.stat_points)
+            {
+                character.basic_stats = basic_stats;
+                character.stat_points = stat_points;
+            }
+
+            if let (Some(skill_list), Some(skill_points), Some(skill_mastery)) = (
+                checkpoint.skill_list,
+                checkpoint.skill_points,
+                checkpoint.skill_mastery,
+            ) {
+                character.skill_list = skill_list;
+                character.skill_points = skill_points;
+                character.skill_mastery = skill_mastery;
+            }
+        }
+
         Ok(character)
     }
 
     pub fn save(&self) -> Result<(), anyhow::Error> {
-        self.save_character_impl(true)
+        let _lock = Self::lock_files();
+        self.save_locked()
+    }
+
+    /// Locks character files against writes from other threads until the guard is dropped.
+    pub(super) fn lock_files() -> MutexGuard<'static, ()> {
+        CHARACTER_FILE_LOCK
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Saves the character, the caller must already hold the lock from `lock_files`.
+    pub(super) fn save_locked(&self) -> Result<(), anyhow::Error> {
+        self.save_character_impl(true)?;
+
+        // The full save includes everything from the checkpoints
+        CharacterCheckpoint::remove(&self.info.name)
+    }
+
+    /// Returns when the character file was last written
+    pub(super) fn modified_time(name: &str) -> Option<SystemTime> {
+        std::fs::metadata(get_character_path(name))
+            .and_then(|metadata| metadata.modified())
+            .ok()
     }
 
     fn save_character_impl(&self, allow_overwrite: bool) -> Result<(), anyhow::Error> {
@@ -142,6 +355,6 @@ impl CharacterStorage {
         if path.exists() {
             std::fs::remove_file(path)?;
         }
-        Ok(())
+        CharacterCheckpoint::remove(name)
     }
 }
@@ -4,15 +4,23 @@ use anyhow::Context;
 use serde::{Deserialize, Serialize};
 
 use rose_data::{ClanMemberPosition, SkillId};
-use rose_game_common::components::{ClanLevel, ClanMark, ClanPoints, Money};
+use rose_game_common::{
+    components::{ClanLevel, ClanMark, ClanPermissions, ClanPoints, Money},
+    messages::server::ClanApplication,
+};
 
-use crate::game::storage::CLAN_STORAGE_DIR;
+use crate::game::{
+    components::{ClanMemberStats, ClanVault},
+    storage::CLAN_STORAGE_DIR,
+};
 
 #[derive(Deserialize, Serialize)]
 pub struct ClanStorageMember {
     pub name: String,
     pub position: ClanMemberPosition,
     pub contribution: ClanPoints,
+    #[serde(default)]
+    pub stats: ClanMemberStats,
 }
 
 impl ClanStorageMember {
@@ -21,6 +29,7 @@ impl ClanStorageMember {
             name,
             position,
             contribution: ClanPoints(0),
+            stats: ClanMemberStats::default(),
         }
     }
 }
@@ -29,12 +38,22 @@ impl ClanStorageMember {
 pub struct ClanStorage {
     pub name: String,
     pub description: String,
+    #[serde(default)]
+    pub notice: String,
     pub mark: ClanMark,
     pub money: Money,
     pub points: ClanPoints,
     pub level: ClanLevel,
     pub members: Vec<ClanStorageMember>,
     pub skills: Vec<SkillId>,
+    #[serde(default)]
+    pub vault: ClanVault,
+    #[serde(default)]
+    pub permissions: ClanPermissions,
+    #[serde(default)]
+    pub recruitment: String,
+    #[serde(default)]
+    pub applications: Vec<ClanApplication>,
 }
 
 fn get_clan_path(name: &str) -> PathBuf {
@@ -46,12 +65,17 @@ impl ClanStorage {
         Self {
             name,
             description,
+            notice: String::default(),
             mark,
             money: Money(0),
             points: ClanPoints(0),
             level: ClanLevel::new(1).unwrap(),
             members: Vec::default(),
             skills: Vec::default(),
+            vault: ClanVault::default(),
+            permissions: ClanPermissions::default(),
+            recruitment: String::default(),
+            applications: Vec::default(),
         }
     }
 
@@ -0,0 +1,147 @@
+use aes_gcm::{
+    aead::{Aead, KeyInit},
+    Aes256Gcm, Nonce,
+};
+use anyhow::{anyhow, Context};
+use rand::Rng;
+use std::{io::Write, path::Path, sync::OnceLock};
+
+use crate::game::storage::ACCOUNT_STORAGE_DIR;
+
+const ENCRYPTED_FILE_MAGIC: &[u8] = b"ROSEENC1";
+const NONCE_SIZE: usize = 12;
+
+static ACCOUNT_STORAGE_KEY: OnceLock<StorageKey> = OnceLock::new();
+
+/// An AES-256-GCM key used to encrypt storage files at rest.
+pub struct StorageKey {
+    cipher: Aes256Gcm,
+}
+
+impl StorageKey {
+    /// Loads a hex encoded key from path.
+    pub fn load(path: &Path) -> Result<Self, anyhow::Error> {
+        let str = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read key file {}", path.to_string_lossy()))?;
+        let key = hex::decode(str.trim())
+            .with_context(|| format!("Failed to decode key file {}", path.to_string_lossy()))?;
+        Self::from_key(&key, path)
+    }
+
+    /// Generates a new key and writes it to path, which must not already exist. The file is
+    /// only readable by the current user.
+    pub fn create(path: &Path) -> Result<Self, anyhow::Error> {
+        let key = rand::thread_rng().gen::<[u8; 32]>().to_vec();
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        options
+            .open(path)
+            .and_then(|mut file| file.write_all(hex::encode(&key).as_bytes()))
+            .with_context(|| format!("Failed to write key file {}", path.to_string_lossy()))?;
+        Self::from_key(&key, path)
+    }
+
+    fn from_key(key: &[u8], path: &Path) -> Result<Self, anyhow::Error> {
+        let cipher = Aes256Gcm::new_from_slice(key).map_err(|_| {
+            anyhow!(
+                "Invalid key in key file {}, expected 32 bytes",
+                path.to_string_lossy()
+            )
+        })?;
+        Ok(Self { cipher })
+    }
+
+    pub fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+        let nonce = rand::thread_rng().gen::<[u8; NONCE_SIZE]>();
+        let ciphertext = self
+            .cipher
+            .encrypt(Nonce::from_slice(&nonce), data)
+            .map_err(|_| anyhow!("Failed to encrypt data"))?;
+
+        let mut encrypted =
+            Vec::with_capacity(ENCRYPTED_FILE_MAGIC.len() + NONCE_SIZE + ciphertext.len());
+        encrypted.extend_from_slice(ENCRYPTED_FILE_MAGIC);
+        encrypted.extend_from_slice(&nonce);
+        encrypted.extend_from_slice(&ciphertext);
+        Ok(encrypted)
+    }
+
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+        let data = data
+            .strip_prefix(ENCRYPTED_FILE_MAGIC)
+            .filter(|data| data.len() >= NONCE_SIZE)
+            .ok_or_else(|| anyhow!("Data is not encrypted"))?;
+        let (nonce, ciphertext) = data.split_at(NONCE_SIZE);
+        self.cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| anyhow!("Failed to decrypt data, is the storage key correct?"))
+    }
+}
+
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(ENCRYPTED_FILE_MAGIC)
+}
+
+/// Sets the key used to encrypt account storage, must be called before any accounts are loaded.
+pub fn set_account_storage_key(key: StorageKey) {
+    if ACCOUNT_STORAGE_KEY.set(key).is_err() {
+        panic!("Account storage key has already been set");
+    }
+}
+
+pub fn account_storage_key() -> Option<&'static StorageKey> {
+    ACCOUNT_STORAGE_KEY.get()
+}
+
+/// Encrypts every account storage file which is not already encrypted, returns how many files
+/// were encrypted.
+pub fn encrypt_account_storage(key: &StorageKey) -> Result<usize, anyhow::Error> {
+    if !ACCOUNT_STORAGE_DIR.exists() {
+        return Ok(0);
+    }
+
+    let mut num_encrypted = 0;
+    for entry in std::fs::read_dir(ACCOUNT_STORAGE_DIR.as_path()).with_context(|| {
+        format!(
+            "Failed to read account storage directory {}",
+            ACCOUNT_STORAGE_DIR.to_string_lossy()
+        )
+    })? {
+        let path = entry?.path();
+        if path
+            .extension()
+            .map_or(true, |extension| extension != "json")
+        {
+            continue;
+        }
+
+        let data = std::fs::read(&path)
+            .with_context(|| format!("Failed to read file {}", path.to_string_lossy()))?;
+        if is_encrypted(&data) {
+            continue;
+        }
+
+        let encrypted = key.encrypt(&data)?;
+        let mut file = tempfile::NamedTempFile::new_in(ACCOUNT_STORAGE_DIR.as_path())
+            .context("Failed to create temporary file whilst encrypting account storage")?;
+        file.write_all(&encrypted).with_context(|| {
+            format!(
+                "Failed to write encrypted data for {}",
+                path.to_string_lossy()
+            )
+        })?;
+        file.persist(&path).with_context(|| {
+            format!(
+                "Failed to persist encrypted account file to path {}",
+                path.to_string_lossy()
+            )
+        })?;
+        num_encrypted += 1;
+    }
+
+    Ok(num_encrypted)
+}
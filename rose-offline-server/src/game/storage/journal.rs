@@ -0,0 +1,195 @@
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context;
+use log::{error, info};
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    components::Inventory,
+    storage::{character::CharacterStorage, writer::queue_storage_write, JOURNAL_STORAGE_DIR},
+};
+
+static JOURNAL_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Deserialize, Serialize)]
+pub struct JournalStorageEntry {
+    pub character_name: String,
+    pub inventory: Inventory,
+}
+
+/// A write-ahead journal for item / money transfers between multiple characters.
+///
+/// The journal is persisted before any character file is updated, so if the server
+/// stops part way through a commit it is replayed at next startup.
+#[derive(Default, Deserialize, Serialize)]
+pub struct JournalStorage {
+    /// When the transaction happened, any character saved after this already includes it
+    #[serde(default)]
+    pub created: Option<SystemTime>,
+    pub entries: Vec<JournalStorageEntry>,
+}
+
+impl JournalStorage {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn add_inventory(&mut self, character_name: &str, inventory: &Inventory) {
+        self.entries.push(JournalStorageEntry {
+            character_name: character_name.to_string(),
+            inventory: inventory.clone(),
+        });
+    }
+
+    /// Queues the journal to be written and applied on the storage writer thread.
+    pub fn commit(mut self) {
+        self.created = Some(SystemTime::now());
+        queue_storage_write("commit journal", move || self.commit_now());
+    }
+
+    fn commit_now(&self) -> Result<(), anyhow::Error> {
+        let path = self.write()?;
+        self.apply()?;
+        Self::remove_committed(&path)
+    }
+
+    fn remove_committed(path: &Path) -> Result<(), anyhow::Error> {
+        // Empty the journal before removing it, so if the remove fails it is discarded rather
+        // than replayed over newer saves
+        OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .open(path)
+            .and_then(|file| file.sync_all())
+            .with_context(|| {
+                format!(
+                    "Failed to truncate committed journal file {}",
+                    path.to_string_lossy()
+                )
+            })?;
+        std::fs::remove_file(path).with_context(|| {
+            format!(
+                "Failed to remove committed journal file {}",
+                path.to_string_lossy()
+            )
+        })?;
+        Ok(())
+    }
+
+    fn write(&self) -> Result<PathBuf, anyhow::Error> {
+        std::fs::create_dir_all(JOURNAL_STORAGE_DIR.as_path()).with_context(|| {
+            format!(
+                "Failed to create journal storage directory {}",
+                JOURNAL_STORAGE_DIR.to_string_lossy()
+            )
+        })?;
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_nanos());
+        let sequence = JOURNAL_SEQUENCE.fetch_add(1, Ordering::Relaxed);
+        let path = JOURNAL_STORAGE_DIR.join(format!("{}-{}.json", timestamp, sequence));
+
+        let json =
+            serde_json::to_string_pretty(self).context("Failed to serialise JournalStorage")?;
+        let mut file = tempfile::NamedTempFile::new()
+            .context("Failed to create temporary file whilst writing journal")?;
+        file.write_all(json.as_bytes())
+            .context("Failed to write data to temporary file whilst writing journal")?;
+        file.as_file()
+            .sync_all()
+            .context("Failed to sync temporary journal file")?;
+        file.persist_noclobber(&path).with_context(|| {
+            format!(
+                "Failed to persist temporary journal file to path {}",
+                path.to_string_lossy()
+            )
+        })?;
+
+        Ok(path)
+    }
+
+    fn apply(&self) -> Result<(), anyhow::Error> {
+        let _lock = CharacterStorage::lock_files();
+
+        for entry in self.entries.iter() {
+            if !CharacterStorage::exists(&entry.character_name) {
+                continue;
+            }
+
+            // Replaying an entry which was already applied, or has since been saved over, must
+            // not revert the character to the inventory in the journal
+            if let (Some(created), Some(modified)) = (
+                self.created,
+                CharacterStorage::modified_time(&entry.character_name),
+            ) {
+                if modified > created {
+                    continue;
+                }
+            }
+
+            let mut character = CharacterStorage::try_load(&entry.character_name)?;
+            character.inventory = entry.inventory.clone();
+            character.save_locked()?;
+        }
+
+        Ok(())
+    }
+
+    /// Replay any journals which were not fully committed.
+    pub fn replay_pending() {
+        let Ok(read_dir) = JOURNAL_STORAGE_DIR.read_dir() else {
+            return;
+        };
+
+        let mut paths: Vec<PathBuf> = read_dir.flatten().map(|entry| entry.path()).collect();
+        paths.sort();
+
+        for path in paths {
+            let journal = std::fs::read_to_string(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|str| {
+                    if str.is_empty() {
+                        Ok(None)
+                    } else {
+                        serde_json::from_str::<JournalStorage>(&str)
+                            .map(Some)
+                            .map_err(anyhow::Error::from)
+                    }
+                });
+
+            match journal {
+                // Journal was committed but could not be removed
+                Ok(None) => {}
+                Ok(Some(journal)) => match journal.apply() {
+                    Ok(_) => info!("Replayed journal {}", path.to_string_lossy()),
+                    Err(error) => {
+                        // Keep the journal so it can be retried on next startup
+                        error!(
+                            "Failed to replay journal {} with error {:?}",
+                            path.to_string_lossy(),
+                            error
+                        );
+                        continue;
+                    }
+                },
+                Err(error) => {
+                    // Journal was never fully written, so nothing was applied
+                    error!(
+                        "Discarding invalid journal {} with error {:?}",
+                        path.to_string_lossy(),
+                        error
+                    );
+                }
+            }
+
+            std::fs::remove_file(&path).ok();
+        }
+    }
+}
@@ -5,16 +5,32 @@ use lazy_static::lazy_static;
 
 lazy_static! {
     pub static ref LOCAL_STORAGE_DIR: PathBuf = {
-        let project = ProjectDirs::from("", "", "rose-offline").unwrap();
-        PathBuf::from(project.data_local_dir())
+        // Tests must never read or write the real server storage
+        if cfg!(test) {
+            std::env::temp_dir().join(format!("rose-offline-test-{}", std::process::id()))
+        } else {
+            let project = ProjectDirs::from("", "", "rose-offline").unwrap();
+            PathBuf::from(project.data_local_dir())
+        }
     };
     pub static ref ACCOUNT_STORAGE_DIR: PathBuf = LOCAL_STORAGE_DIR.join("accounts");
     pub static ref BANK_STORAGE_DIR: PathBuf = LOCAL_STORAGE_DIR.join("bank");
     pub static ref CHARACTER_STORAGE_DIR: PathBuf = LOCAL_STORAGE_DIR.join("characters");
     pub static ref CLAN_STORAGE_DIR: PathBuf = LOCAL_STORAGE_DIR.join("clan");
+    pub static ref JOURNAL_STORAGE_DIR: PathBuf = LOCAL_STORAGE_DIR.join("journal");
+    pub static ref NAVIGATION_CACHE_DIR: PathBuf = LOCAL_STORAGE_DIR.join("navigation");
+    pub static ref REPORT_STORAGE_DIR: PathBuf = LOCAL_STORAGE_DIR.join("reports");
+    pub static ref TERRITORY_STORAGE_DIR: PathBuf = LOCAL_STORAGE_DIR.join("territory");
+    pub static ref ZONE_STORAGE_DIR: PathBuf = LOCAL_STORAGE_DIR.join("zones");
 }
 
 pub mod account;
 pub mod bank;
 pub mod character;
 pub mod clan;
+pub mod encryption;
+pub mod journal;
+pub mod report;
+pub mod territory;
+pub mod writer;
+pub mod zone;
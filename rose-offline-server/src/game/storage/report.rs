@@ -0,0 +1,124 @@
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{components::Position, storage::REPORT_STORAGE_DIR};
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportKind {
+    Bug,
+    Player,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ReportEntitySummary {
+    pub entity_type: String,
+    pub name: String,
+    pub position: Position,
+    pub level: u32,
+    pub health: i32,
+    pub command: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ReportStorage {
+    pub kind: ReportKind,
+    /// Seconds since the unix epoch
+    pub timestamp: u64,
+    pub reporter: String,
+    pub position: Position,
+    pub text: String,
+    /// The reported player, or the reporter's target for bug reports
+    pub target: Option<ReportEntitySummary>,
+    pub command: String,
+    pub recent_chat_commands: Vec<String>,
+}
+
+fn get_report_path(timestamp: u64, reporter: &str, kind: ReportKind) -> PathBuf {
+    let kind = match kind {
+        ReportKind::Bug => "bug",
+        ReportKind::Player => "player",
+    };
+    REPORT_STORAGE_DIR.join(format!("{}_{}_{}.json", timestamp, reporter, kind))
+}
+
+fn get_report_timestamp(path: &Path) -> Option<u64> {
+    let file_name = path.file_name()?.to_str()?;
+    file_name.split('_').next()?.parse().ok()
+}
+
+impl ReportStorage {
+    /// Loads up to count of the most recent reports, newest first.
+    pub fn try_load_recent(count: usize) -> Result<Vec<Self>, anyhow::Error> {
+        let Ok(read_dir) = REPORT_STORAGE_DIR.read_dir() else {
+            // No reports have been saved yet
+            return Ok(Vec::new());
+        };
+
+        let mut paths: Vec<(u64, PathBuf)> = read_dir
+            .flatten()
+            .map(|entry| entry.path())
+            .filter_map(|path| Some((get_report_timestamp(&path)?, path)))
+            .collect();
+        paths.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+        let mut reports = Vec::new();
+        for (_, path) in paths.into_iter().take(count) {
+            let str = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read file {}", path.to_string_lossy()))?;
+            let report: Self = serde_json::from_str(&str).with_context(|| {
+                format!(
+                    "Failed to deserialise ReportStorage from file {}",
+                    path.to_string_lossy()
+                )
+            })?;
+            reports.push(report);
+        }
+
+        Ok(reports)
+    }
+
+    pub fn save(&self) -> Result<(), anyhow::Error> {
+        let path = get_report_path(self.timestamp, &self.reporter, self.kind);
+        let storage_dir = path.parent().unwrap();
+
+        std::fs::create_dir_all(storage_dir).with_context(|| {
+            format!(
+                "Failed to create report storage directory {}",
+                storage_dir.to_string_lossy()
+            )
+        })?;
+
+        let json = serde_json::to_string_pretty(self).with_context(|| {
+            format!(
+                "Failed to serialise ReportStorage whilst saving report from {}",
+                &self.reporter
+            )
+        })?;
+        let mut file = tempfile::NamedTempFile::new().with_context(|| {
+            format!(
+                "Failed to create temporary file whilst saving report from {}",
+                &self.reporter
+            )
+        })?;
+        file.write_all(json.as_bytes()).with_context(|| {
+            format!(
+                "Failed to write data to temporary file whilst saving report from {}",
+                &self.reporter
+            )
+        })?;
+        file.persist_noclobber(&path).with_context(|| {
+            format!(
+                "Failed to persist_noclobber temporary report file to path {}",
+                path.to_string_lossy()
+            )
+        })?;
+
+        Ok(())
+    }
+}
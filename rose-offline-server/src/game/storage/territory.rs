@@ -0,0 +1,75 @@
+use std::{io::Write, path::PathBuf};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use rose_data::ZoneId;
+
+use crate::game::storage::TERRITORY_STORAGE_DIR;
+
+#[derive(Default, Deserialize, Serialize)]
+pub struct TerritoryStorage {
+    pub owner_clan: Option<String>,
+}
+
+fn get_territory_path(zone_id: ZoneId) -> PathBuf {
+    TERRITORY_STORAGE_DIR.join(format!("{}.json", zone_id.get()))
+}
+
+impl TerritoryStorage {
+    pub fn exists(zone_id: ZoneId) -> bool {
+        get_territory_path(zone_id).exists()
+    }
+
+    pub fn try_load(zone_id: ZoneId) -> Result<Self, anyhow::Error> {
+        let path = get_territory_path(zone_id);
+        let str = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read file {}", path.to_string_lossy()))?;
+        let territory: Self = serde_json::from_str(&str).with_context(|| {
+            format!(
+                "Failed to deserialise TerritoryStorage from file {}",
+                path.to_string_lossy()
+            )
+        })?;
+        Ok(territory)
+    }
+
+    pub fn save(&self, zone_id: ZoneId) -> Result<(), anyhow::Error> {
+        let path = get_territory_path(zone_id);
+        let storage_dir = path.parent().unwrap();
+
+        std::fs::create_dir_all(storage_dir).with_context(|| {
+            format!(
+                "Failed to create territory storage directory {}",
+                storage_dir.to_string_lossy()
+            )
+        })?;
+
+        let json = serde_json::to_string_pretty(self).with_context(|| {
+            format!(
+                "Failed to serialise TerritoryStorage whilst saving territory {}",
+                zone_id.get()
+            )
+        })?;
+        let mut file = tempfile::NamedTempFile::new().with_context(|| {
+            format!(
+                "Failed to create temporary file whilst saving territory {}",
+                zone_id.get()
+            )
+        })?;
+        file.write_all(json.as_bytes()).with_context(|| {
+            format!(
+                "Failed to write data to temporary file whilst saving territory {}",
+                zone_id.get()
+            )
+        })?;
+        file.persist(&path).with_context(|| {
+            format!(
+                "Failed to persist temporary territory file to path {}",
+                path.to_string_lossy()
+            )
+        })?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,135 @@
+use crossbeam_channel::Sender;
+use lazy_static::lazy_static;
+use log::error;
+use std::{sync::Mutex, thread::JoinHandle};
+
+type StorageWrite = Box<dyn FnOnce() -> Result<(), anyhow::Error> + Send>;
+
+struct StorageWriter {
+    tx: Sender<(String, StorageWrite)>,
+    thread: JoinHandle<()>,
+}
+
+enum StorageWriterState {
+    NotStarted,
+    Running(StorageWriter),
+    /// Writes are performed immediately by the caller once the writer has shut down
+    Stopped,
+    /// Writes are discarded, for running the game world without persistent storage
+    Disabled,
+}
+
+lazy_static! {
+    static ref STORAGE_WRITER: Mutex<StorageWriterState> =
+        Mutex::new(StorageWriterState::NotStarted);
+}
+
+fn perform_storage_write(description: &str, write: StorageWrite) {
+    if let Err(error) = write() {
+        error!("Failed to {} with error {:?}", description, error);
+    }
+}
+
+fn start_storage_writer() -> StorageWriter {
+    let (tx, rx) = crossbeam_channel::unbounded::<(String, StorageWrite)>();
+    let thread = std::thread::Builder::new()
+        .name("storage-writer".to_string())
+        .spawn(move || {
+            for (description, write) in rx {
+                perform_storage_write(&description, write);
+            }
+        })
+        .expect("Failed to spawn storage writer thread");
+    StorageWriter { tx, thread }
+}
+
+/// Queues a write to be performed on the storage writer thread, so slow disk I/O does not
+/// stall the game tick.
+///
+/// Writes are performed one at a time in the order they were queued, so callers which must
+/// persist one file before another can rely on queue order.
+pub fn queue_storage_write(
+    description: impl Into<String>,
+    write: impl FnOnce() -> Result<(), anyhow::Error> + Send + 'static,
+) {
+    let description = description.into();
+    let mut storage_writer = STORAGE_WRITER.lock().unwrap();
+    if let StorageWriterState::NotStarted = *storage_writer {
+        *storage_writer = StorageWriterState::Running(start_storage_writer());
+    }
+
+    match &*storage_writer {
+        StorageWriterState::Running(writer) => {
+            writer.tx.send((description, Box::new(write))).ok();
+        }
+        StorageWriterState::Disabled => {}
+        _ => {
+            drop(storage_writer);
+            perform_storage_write(&description, Box::new(write));
+        }
+    }
+}
+
+/// Discards every write queued from now on, any writes which were already queued are still
+/// performed.
+pub fn disable_storage_writes() {
+    shutdown_storage_writer();
+    *STORAGE_WRITER.lock().unwrap() = StorageWriterState::Disabled;
+}
+
+pub fn is_storage_write_enabled() -> bool {
+    !matches!(
+        *STORAGE_WRITER.lock().unwrap(),
+        StorageWriterState::Disabled
+    )
+}
+
+/// Waits for every queued write to be performed and stops the storage writer thread, must be
+/// called before the process exits or queued writes are lost.
+pub fn shutdown_storage_writer() {
+    // The lock is held until the queue is drained, so later writes cannot overtake queued ones
+    let mut storage_writer = STORAGE_WRITER.lock().unwrap();
+    if let StorageWriterState::Disabled = *storage_writer {
+        return;
+    }
+    let state = std::mem::replace(&mut *storage_writer, StorageWriterState::Stopped);
+
+    if let StorageWriterState::Running(StorageWriter { tx, thread }) = state {
+        // Dropping the sender ends the writer thread once it has drained the queue
+        drop(tx);
+        if thread.join().is_err() {
+            error!("Storage writer thread panicked before all writes were performed");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::game::storage::LOCAL_STORAGE_DIR;
+
+    #[test]
+    fn shutdown_performs_queued_writes() {
+        let storage_dir = LOCAL_STORAGE_DIR.join("writer");
+        std::fs::create_dir_all(&storage_dir).unwrap();
+
+        let paths: Vec<_> = (0..5)
+            .map(|i| storage_dir.join(format!("{}.txt", i)))
+            .collect();
+        for path in paths.iter().cloned() {
+            queue_storage_write("write test file", move || {
+                // Slow enough that the writes are still queued when shutdown is requested
+                std::thread::sleep(Duration::from_millis(20));
+                std::fs::write(path, "test")?;
+                Ok(())
+            });
+        }
+        shutdown_storage_writer();
+
+        for path in paths.iter() {
+            assert!(path.exists());
+        }
+    }
+}
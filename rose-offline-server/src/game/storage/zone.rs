@@ -0,0 +1,96 @@
+use std::{io::Write, path::PathBuf};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use rose_data::ZoneId;
+
+use crate::game::storage::ZONE_STORAGE_DIR;
+
+#[derive(Deserialize, Serialize)]
+pub struct ZoneStorageEventObject {
+    pub event_id: u16,
+    pub map_chunk_x: i32,
+    pub map_chunk_y: i32,
+    pub variables: Vec<i32>,
+}
+
+/// A delayed quest trigger pending on an event object, it is run once the remaining
+/// time has passed after the server starts.
+#[derive(Deserialize, Serialize)]
+pub struct ZoneStorageDelayedTrigger {
+    pub event_id: u16,
+    pub map_chunk_x: i32,
+    pub map_chunk_y: i32,
+    pub trigger_hash: u32,
+    pub remaining_ms: u64,
+}
+
+#[derive(Default, Deserialize, Serialize)]
+pub struct ZoneStorage {
+    pub event_objects: Vec<ZoneStorageEventObject>,
+    #[serde(default)]
+    pub delayed_triggers: Vec<ZoneStorageDelayedTrigger>,
+}
+
+fn get_zone_path(zone_id: ZoneId) -> PathBuf {
+    ZONE_STORAGE_DIR.join(format!("{}.json", zone_id.get()))
+}
+
+impl ZoneStorage {
+    pub fn exists(zone_id: ZoneId) -> bool {
+        get_zone_path(zone_id).exists()
+    }
+
+    pub fn try_load(zone_id: ZoneId) -> Result<Self, anyhow::Error> {
+        let path = get_zone_path(zone_id);
+        let str = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read file {}", path.to_string_lossy()))?;
+        let zone: Self = serde_json::from_str(&str).with_context(|| {
+            format!(
+                "Failed to deserialise ZoneStorage from file {}",
+                path.to_string_lossy()
+            )
+        })?;
+        Ok(zone)
+    }
+
+    pub fn save(&self, zone_id: ZoneId) -> Result<(), anyhow::Error> {
+        let path = get_zone_path(zone_id);
+        let storage_dir = path.parent().unwrap();
+
+        std::fs::create_dir_all(storage_dir).with_context(|| {
+            format!(
+                "Failed to create zone storage directory {}",
+                storage_dir.to_string_lossy()
+            )
+        })?;
+
+        let json = serde_json::to_string_pretty(self).with_context(|| {
+            format!(
+                "Failed to serialise ZoneStorage whilst saving zone {}",
+                zone_id.get()
+            )
+        })?;
+        let mut file = tempfile::NamedTempFile::new().with_context(|| {
+            format!(
+                "Failed to create temporary file whilst saving zone {}",
+                zone_id.get()
+            )
+        })?;
+        file.write_all(json.as_bytes()).with_context(|| {
+            format!(
+                "Failed to write data to temporary file whilst saving zone {}",
+                zone_id.get()
+            )
+        })?;
+        file.persist(&path).with_context(|| {
+            format!(
+                "Failed to persist temporary zone file to path {}",
+                path.to_string_lossy()
+            )
+        })?;
+
+        Ok(())
+    }
+}
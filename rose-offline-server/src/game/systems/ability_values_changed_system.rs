@@ -1,22 +1,40 @@
 use bevy::{
-    ecs::{prelude::Changed, query::WorldQuery, system::Query},
+    ecs::{
+        prelude::Changed,
+        query::WorldQuery,
+        system::{Query, ResMut},
+    },
     prelude::Or,
 };
 
-use crate::game::components::{AbilityValues, HealthPoints, ManaPoints, MoveMode, MoveSpeed};
+use rose_game_common::messages::server::ServerMessage;
+
+use crate::game::{
+    components::{
+        AbilityValues, ClientEntity, HealthPoints, ManaPoints, MoveMode, MoveSpeed,
+        OverweightLevel, Weight,
+    },
+    resources::ServerMessages,
+};
 
 #[derive(WorldQuery)]
 #[world_query(mutable)]
 pub struct AbilityValuesChangedQuery<'w> {
     ability_values: &'w mut AbilityValues,
+    client_entity: Option<&'w ClientEntity>,
     health_points: &'w mut HealthPoints,
     mana_points: Option<&'w mut ManaPoints>,
     move_mode: &'w MoveMode,
     move_speed: &'w mut MoveSpeed,
+    weight: Option<&'w Weight>,
 }
 
 pub fn ability_values_changed_system(
-    mut query: Query<AbilityValuesChangedQuery, Or<(Changed<AbilityValues>, Changed<MoveMode>)>>,
+    mut query: Query<
+        AbilityValuesChangedQuery,
+        Or<(Changed<AbilityValues>, Changed<MoveMode>, Changed<Weight>)>,
+    >,
+    mut server_messages: ResMut<ServerMessages>,
 ) {
     for mut object in query.iter_mut() {
         // Update is_driving so vehicle stats are used correctly
@@ -36,10 +54,34 @@ pub fn ability_values_changed_system(
             }
         }
 
-        // Update move speed
-        let updated_move_speed = object.ability_values.get_move_speed(object.move_mode);
-        if (object.move_speed.speed - updated_move_speed).abs() > f32::EPSILON {
+        // Update move speed, overweight characters can only walk
+        let is_overweight = object
+            .weight
+            .map_or(false, |weight| weight.overweight != OverweightLevel::None);
+        let updated_move_speed = if is_overweight && matches!(object.move_mode, MoveMode::Run) {
+            object.ability_values.get_walk_speed()
+        } else {
+            object.ability_values.get_move_speed(object.move_mode)
+        };
+        let speed_changed = (object.move_speed.speed - updated_move_speed).abs() > f32::EPSILON;
+        if speed_changed {
             object.move_speed.speed = updated_move_speed;
         }
+
+        // The client calculates its own run speed, so it must be told when it is limited to
+        // walking by weight or it will predict movement faster than the server
+        if let (Some(client_entity), Some(_)) = (object.client_entity, object.weight) {
+            if matches!(object.move_mode, MoveMode::Run) && (speed_changed || is_overweight) {
+                let passive_attack_speed = object.ability_values.get_passive_attack_speed();
+                server_messages.send_entity_message(
+                    client_entity,
+                    ServerMessage::UpdateSpeed {
+                        entity_id: client_entity.id,
+                        run_speed: updated_move_speed as i32,
+                        passive_attack_speed,
+                    },
+                );
+            }
+        }
     }
 }
@@ -6,8 +6,10 @@ use bevy::ecs::{
 
 use crate::game::{
     components::{
-        AbilityValues, BasicStats, CharacterInfo, Equipment, Level, SkillList, StatusEffects,
+        AbilityValues, BasicStats, CharacterInfo, CharacterTitles, Equipment, Level, SkillList,
+        StatusEffects,
     },
+    resources::{GameConfig, TitleBonus},
     GameData,
 };
 
@@ -21,6 +23,19 @@ pub struct AbilityValuesCharacterQuery<'w> {
     level: &'w Level,
     skill_list: &'w SkillList,
     status_effects: &'w StatusEffects,
+    titles: Option<&'w CharacterTitles>,
+}
+
+fn apply_title_bonus(ability_values: &mut AbilityValues, bonus: &TitleBonus) {
+    ability_values.attack_power += bonus.attack_power;
+    ability_values.attack_speed += bonus.attack_speed;
+    ability_values.avoid += bonus.avoid;
+    ability_values.critical += bonus.critical;
+    ability_values.defence += bonus.defence;
+    ability_values.hit += bonus.hit;
+    ability_values.max_health += bonus.max_health;
+    ability_values.max_mana += bonus.max_mana;
+    ability_values.resistance += bonus.resistance;
 }
 
 pub fn ability_values_update_character_system(
@@ -28,6 +43,7 @@ pub fn ability_values_update_character_system(
         AbilityValuesCharacterQuery,
         Or<(
             Changed<CharacterInfo>,
+            Changed<CharacterTitles>,
             Changed<Level>,
             Changed<Equipment>,
             Changed<BasicStats>,
@@ -35,6 +51,7 @@ pub fn ability_values_update_character_system(
             Changed<StatusEffects>,
         )>,
     >,
+    game_config: Res<GameConfig>,
     game_data: Res<GameData>,
 ) {
     for mut character in query.iter_mut() {
@@ -46,5 +63,13 @@ pub fn ability_values_update_character_system(
             character.skill_list,
             character.status_effects,
         );
+
+        if let Some(title) = character
+            .titles
+            .and_then(|titles| titles.active)
+            .and_then(|id| game_config.get_title(id))
+        {
+            apply_title_bonus(&mut character.ability_values, &title.bonus);
+        }
     }
 }
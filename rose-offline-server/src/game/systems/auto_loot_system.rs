@@ -0,0 +1,156 @@
+use std::time::Duration;
+
+use bevy::{
+    ecs::prelude::{Commands, Entity, EventReader, EventWriter, Query, Res, ResMut},
+    time::Time,
+};
+
+use rose_data::{ItemClass, ItemType};
+use rose_game_common::messages::PartyItemSharing;
+
+use crate::game::{
+    bundles::ItemDropBundle,
+    components::{CharacterStatistics, DroppedItem, GameClient, Inventory, Money, Party, Position},
+    events::{AutoLootEvent, TitleEvent, UseItemEvent},
+    messages::server::ServerMessage,
+    resources::{ClientEntityList, GameConfig},
+    GameData,
+};
+
+pub fn auto_loot_system(
+    mut commands: Commands,
+    mut auto_loot_events: EventReader<AutoLootEvent>,
+    mut query_inventory: Query<(
+        &mut Inventory,
+        Option<&GameClient>,
+        Option<&mut CharacterStatistics>,
+    )>,
+    query_position: Query<&Position>,
+    mut query_party: Query<&mut Party>,
+    mut use_item_events: EventWriter<UseItemEvent>,
+    mut title_events: EventWriter<TitleEvent>,
+    mut client_entity_list: ResMut<ClientEntityList>,
+    game_config: Res<GameConfig>,
+    game_data: Res<GameData>,
+    time: Res<Time>,
+) {
+    let owner_duration = Duration::from_secs(game_config.item_drops.owner_duration_secs);
+
+    for event in auto_loot_events.iter() {
+        let mut party = event
+            .party
+            .filter(|_| game_config.item_drops.party_shared)
+            .and_then(|party_entity| query_party.get_mut(party_entity).ok());
+        // Only the online party members in the same zone as the drop share it
+        let is_recipient = |entity: Entity| {
+            query_position.get(entity).map_or(false, |member_position| {
+                member_position.zone_id == event.position.zone_id
+            })
+        };
+
+        let unlooted_item = match &event.item {
+            DroppedItem::Item(item) => {
+                let recipient = match party.as_mut() {
+                    Some(party)
+                        if matches!(party.item_sharing, PartyItemSharing::AcquisitionOrder) =>
+                    {
+                        party
+                            .next_item_acquirer(item.get_item_type(), is_recipient)
+                            .unwrap_or(event.entity)
+                    }
+                    _ => event.entity,
+                };
+
+                if matches!(item.get_item_type(), ItemType::Consumable)
+                    && game_data
+                        .items
+                        .get_consumable_item(item.get_item_number())
+                        .map_or(false, |item_data| {
+                            matches!(item_data.item_data.class, ItemClass::AutomaticConsumption)
+                        })
+                {
+                    use_item_events.send(UseItemEvent::from_item(recipient, item.clone()));
+                    None
+                } else if let Ok((mut inventory, game_client, _)) =
+                    query_inventory.get_mut(recipient)
+                {
+                    match inventory.try_add_item(item.clone()) {
+                        Ok((slot, item)) => {
+                            if let Some(game_client) = game_client {
+                                game_client
+                                    .server_message_tx
+                                    .send(ServerMessage::RewardItems {
+                                        items: vec![(slot, Some(item.clone()))],
+                                    })
+                                    .ok();
+                            }
+                            None
+                        }
+                        Err(item) => Some(DroppedItem::Item(item)),
+                    }
+                } else {
+                    Some(event.item.clone())
+                }
+            }
+            &DroppedItem::Money(money) => {
+                // Money no party member can receive goes to the killer
+                let shares = party
+                    .as_mut()
+                    .map(|party| match party.item_sharing {
+                        PartyItemSharing::EqualLootDistribution => {
+                            party.split_money(money, is_recipient)
+                        }
+                        PartyItemSharing::AcquisitionOrder => party
+                            .next_money_acquirer(is_recipient)
+                            .map(|entity| vec![(entity, money)])
+                            .unwrap_or_default(),
+                    })
+                    .filter(|shares| !shares.is_empty())
+                    .unwrap_or_else(|| vec![(event.entity, money)]);
+
+                let mut unlooted_money = Money(0);
+                for (entity, share) in shares {
+                    let Ok((mut inventory, game_client, statistics)) =
+                        query_inventory.get_mut(entity)
+                    else {
+                        continue;
+                    };
+
+                    if inventory.try_add_money(share).is_err() {
+                        unlooted_money = unlooted_money + share;
+                        continue;
+                    }
+
+                    if let Some(mut statistics) = statistics {
+                        statistics.add_money_earned(share);
+                        title_events.send(TitleEvent::StatisticsChanged { entity });
+                    }
+
+                    if let Some(game_client) = game_client {
+                        game_client
+                            .server_message_tx
+                            .send(ServerMessage::RewardMoney {
+                                money: inventory.money,
+                            })
+                            .ok();
+                    }
+                }
+
+                Some(DroppedItem::Money(unlooted_money)).filter(|_| unlooted_money.0 > 0)
+            }
+        };
+
+        // Anything which does not fit is dropped on the ground as usual
+        if let Some(unlooted_item) = unlooted_item {
+            ItemDropBundle::spawn(
+                &mut commands,
+                &mut client_entity_list,
+                unlooted_item,
+                &event.position,
+                Some((event.entity, owner_duration)),
+                event.party,
+                &time,
+            );
+        }
+    }
+}
@@ -4,7 +4,7 @@ use rose_data::ItemSlotBehaviour;
 use rose_game_common::messages::server::ServerMessage;
 
 use crate::game::{
-    components::{Bank, GameClient, Inventory},
+    components::{Bank, GameClient, Inventory, ItemSlot, INVENTORY_PAGE_SIZE},
     events::BankEvent,
 };
 
@@ -125,6 +125,51 @@ pub fn bank_system(
                     }
                 }
             }
+            BankEvent::DepositInventoryPage { entity, page_type } => {
+                let Ok((game_client, mut bank, mut inventory)) = query_entity.get_mut(entity) else {
+                    continue;
+                };
+
+                let mut updated_inventory_items = Vec::new();
+                let mut updated_bank_items = Vec::new();
+                for index in 0..INVENTORY_PAGE_SIZE {
+                    let item_slot = ItemSlot::Inventory(page_type, index);
+                    let Some(inventory_slot) = inventory.get_item_slot_mut(item_slot) else {
+                        continue;
+                    };
+                    let Some(deposit_item) = inventory_slot.take() else {
+                        continue;
+                    };
+
+                    match bank.try_add_item(deposit_item) {
+                        Ok((bank_slot, bank_item)) => {
+                            updated_inventory_items.push((item_slot, None));
+                            updated_bank_items.push((bank_slot as u8, Some(bank_item.clone())));
+                        }
+                        Err(deposit_item) => {
+                            // The bank is full, stop depositing
+                            *inventory_slot = Some(deposit_item);
+                            break;
+                        }
+                    }
+                }
+
+                if !updated_inventory_items.is_empty() {
+                    game_client
+                        .server_message_tx
+                        .send(ServerMessage::UpdateInventory {
+                            items: updated_inventory_items,
+                            money: None,
+                        })
+                        .ok();
+                    game_client
+                        .server_message_tx
+                        .send(ServerMessage::BankUpdateItems {
+                            items: updated_bank_items,
+                        })
+                        .ok();
+                }
+            }
         }
     }
 }
@@ -0,0 +1,80 @@
+use std::{collections::HashMap, time::Duration};
+
+use bevy::{
+    ecs::{
+        prelude::{Entity, Local, Query, Res, With},
+        query::WorldQuery,
+    },
+    time::Time,
+};
+
+use crate::game::{
+    components::{
+        Account, BasicStats, CharacterInfo, CharacterSkillMastery, ExperiencePoints, HealthPoints,
+        Level, ManaPoints, Position, SkillList, SkillPoints, StatPoints,
+    },
+    resources::GameConfig,
+    storage::character::CharacterCheckpoint,
+};
+
+#[derive(WorldQuery)]
+pub struct CheckpointQuery<'w> {
+    entity: Entity,
+    character_info: &'w CharacterInfo,
+    position: &'w Position,
+    health_points: &'w HealthPoints,
+    mana_points: &'w ManaPoints,
+    level: &'w Level,
+    experience_points: &'w ExperiencePoints,
+    basic_stats: &'w BasicStats,
+    stat_points: &'w StatPoints,
+    skill_list: &'w SkillList,
+    skill_points: &'w SkillPoints,
+    skill_mastery: &'w CharacterSkillMastery,
+}
+
+pub fn character_checkpoint_system(
+    query: Query<CheckpointQuery, With<Account>>,
+    game_config: Res<GameConfig>,
+    time: Res<Time>,
+    mut last_checkpoint_time: Local<Duration>,
+    mut last_checkpoints: Local<HashMap<Entity, CharacterCheckpoint>>,
+) {
+    if game_config.checkpoint_interval_secs == 0 {
+        return;
+    }
+
+    let now = time.elapsed();
+    if now - *last_checkpoint_time < Duration::from_secs(game_config.checkpoint_interval_secs) {
+        return;
+    }
+    *last_checkpoint_time = now;
+
+    last_checkpoints.retain(|entity, _| query.contains(*entity));
+
+    for character in query.iter() {
+        let checkpoint = CharacterCheckpoint {
+            position: character.position.clone(),
+            health_points: *character.health_points,
+            mana_points: *character.mana_points,
+            level: *character.level,
+            experience_points: *character.experience_points,
+            basic_stats: Some(character.basic_stats.clone()),
+            stat_points: Some(*character.stat_points),
+            skill_list: Some(character.skill_list.clone()),
+            skill_points: Some(*character.skill_points),
+            skill_mastery: Some(character.skill_mastery.clone()),
+        };
+
+        // Only write characters which have changed since their last checkpoint
+        if last_checkpoints.get(&character.entity) == Some(&checkpoint) {
+            continue;
+        }
+
+        // The file is written on the storage writer thread, so the tick does not wait on disk I/O
+        checkpoint
+            .clone()
+            .queue_append(character.character_info.name.clone());
+        last_checkpoints.insert(character.entity, checkpoint);
+    }
+}
@@ -1,25 +1,27 @@
 use std::{
+    collections::{HashMap, VecDeque},
     f32::consts::PI,
     num::{ParseFloatError, ParseIntError},
+    time::{Duration, SystemTime},
 };
 
 use bevy::{
     ecs::{
-        prelude::{Commands, Entity, EventReader, EventWriter, Query, Res, ResMut},
+        prelude::{Commands, Entity, EventReader, EventWriter, Local, Query, Res, ResMut, Without},
         query::WorldQuery,
         system::SystemParam,
     },
     math::{UVec2, Vec3, Vec3Swizzles},
     time::Time,
-    utils::HashSet,
 };
 use clap::{Arg, PossibleValue};
 use lazy_static::lazy_static;
+use log::{info, warn};
 use rand::Rng;
 
 use rose_data::{
-    AbilityType, EquipmentIndex, EquipmentItem, Item, ItemReference, ItemType, NpcId, SkillId,
-    StackableItem, ZoneId,
+    AbilityType, EquipmentItem, Item, ItemReference, ItemType, MotionId, NpcId, SkillId, SkillType,
+    StackableItem, ZoneData, ZoneId, MAX_STACKABLE_ITEM_QUANTITY, WORLD_MONTH_PER_YEAR,
 };
 use rose_game_common::{
     components::{BasicStatType, ClanLevel, ClanPoints, DroppedItem, ExperiencePoints, SkillSlot},
@@ -29,39 +31,71 @@ use rose_game_common::{
 use crate::game::{
     bots::{
         bot_build_artisan, bot_build_bourgeois, bot_build_champion, bot_build_cleric,
-        bot_build_knight, bot_build_mage, bot_build_raider, bot_build_scout,
-        bot_create_random_build, bot_create_with_build, bot_snowball_fight, bot_thinker,
+        bot_build_knight, bot_build_mage, bot_build_raider, bot_build_scout, bot_create_with_build,
+        bot_level_range_for_zone, bot_snowball_fight, bot_spawn_random_build,
     },
     bundles::{
         ability_values_add_value, ability_values_set_value, client_entity_teleport_zone,
-        CharacterBundle, ItemDropBundle, MonsterBundle,
+        ItemDropBundle, MonsterBundle,
     },
     components::{
-        AbilityValues, BasicStats, CharacterInfo, ClanMembership, ClientEntity, ClientEntitySector,
-        ClientEntityType, Command, Cooldowns, DamageSources, EquipmentItemDatabase, GameClient,
-        HealthPoints, Inventory, Level, ManaPoints, Money, MotionData, MoveMode, MoveSpeed,
-        NextCommand, PartyMembership, PassiveRecoveryTime, PersonalStore, Position, SkillList,
-        SkillPoints, SpawnOrigin, Stamina, StatPoints, StatusEffects, StatusEffectsRegen, Team,
-        UnionMembership, PERSONAL_STORE_ITEM_SLOTS,
+        AbilityValues, BasicStats, CharacterInfo, CharacterLootSettings, CharacterStatistics,
+        ClanMembership, ClientEntity, ClientEntityId, ClientEntitySector, ClientEntityType,
+        Command, CommandData, EquipmentItemDatabase, GameClient, GatherNode, HealthPoints,
+        Inventory, Level, ManaPoints, Money, MoveSpeed, NextCommand, Npc, PersonalStore, Position,
+        SkillList, SkillPoints, SpawnOrigin, Spectator, Stamina, StatPoints, StatusEffects, Team,
+        UnionMembership, GATHER_NODE_DISTANCE, PERSONAL_STORE_ITEM_SLOTS,
+    },
+    events::{
+        ChatCommandEvent, ClanEvent, DamageEvent, ItemRainEvent, RewardItemEvent, RewardXpEvent,
+        SaveEvent,
     },
-    events::{ChatCommandEvent, ClanEvent, DamageEvent, RewardItemEvent, RewardXpEvent},
     messages::server::ServerMessage,
-    resources::{BotList, BotListEntry, ClientEntityList, ServerMessages},
+    resources::{
+        BotList, BotListEntry, ClientEntityList, GameConfig, ServerMessages, ServerNews, WorldTime,
+        ZoneCensus, ZoneCensusCounts,
+    },
+    storage::report::{ReportEntitySummary, ReportKind, ReportStorage},
     GameData,
 };
 
+// Number of chat commands included in bug and player reports
+const RECENT_CHAT_COMMANDS_LENGTH: usize = 10;
+
+// Distance within which /who lists other characters
+const WHO_NEARBY_DISTANCE: f32 = 5000.0;
+
+// Maximum number of nearby characters listed by /who
+const WHO_MAX_NEARBY_CHARACTERS: usize = 20;
+
+#[derive(SystemParam)]
+pub struct ChatCommandEvents<'w> {
+    clan_events: EventWriter<'w, ClanEvent>,
+    reward_xp_events: EventWriter<'w, RewardXpEvent>,
+    damage_events: EventWriter<'w, DamageEvent>,
+    reward_item_events: EventWriter<'w, RewardItemEvent>,
+    item_rain_events: EventWriter<'w, ItemRainEvent>,
+    save_events: EventWriter<'w, SaveEvent>,
+}
+
 #[derive(SystemParam)]
 pub struct ChatCommandParams<'w, 's> {
     commands: Commands<'w, 's>,
     bot_list: ResMut<'w, BotList>,
     client_entity_list: ResMut<'w, ClientEntityList>,
+    game_config: Res<'w, GameConfig>,
     game_data: Res<'w, GameData>,
-    clan_events: EventWriter<'w, ClanEvent>,
-    reward_xp_events: EventWriter<'w, RewardXpEvent>,
-    damage_events: EventWriter<'w, DamageEvent>,
-    reward_item_events: EventWriter<'w, RewardItemEvent>,
+    events: ChatCommandEvents<'w>,
     server_messages: ResMut<'w, ServerMessages>,
+    server_news: ResMut<'w, ServerNews>,
     time: Res<'w, Time>,
+    world_time: Res<'w, WorldTime>,
+    zone_census: Res<'w, ZoneCensus>,
+    query_gather_node: Query<'w, 's, (Entity, &'static GatherNode, &'static Position)>,
+    // Keyed by character name, so reconnecting does not reset the report cooldown
+    last_report_times: Local<'s, HashMap<String, Duration>>,
+    last_info_command_times: Local<'s, HashMap<Entity, Duration>>,
+    recent_chat_commands: Local<'s, HashMap<Entity, VecDeque<String>>>,
 }
 
 #[derive(WorldQuery)]
@@ -71,6 +105,7 @@ pub struct ChatCommandUserQuery<'w> {
     ability_values: &'w AbilityValues,
     client_entity: &'w ClientEntity,
     client_entity_sector: &'w ClientEntitySector,
+    command: &'w Command,
     game_client: &'w GameClient,
     level: &'w mut Level,
     position: &'w Position,
@@ -86,6 +121,24 @@ pub struct ChatCommandUserQuery<'w> {
     stat_points: &'w mut StatPoints,
     union_membership: &'w mut UnionMembership,
     clan_membership: &'w ClanMembership,
+    spectator: Option<&'w Spectator>,
+    statistics: Option<&'w CharacterStatistics>,
+    loot_settings: Option<&'w CharacterLootSettings>,
+}
+
+#[derive(WorldQuery)]
+pub struct ChatCommandInspectQuery<'w> {
+    ability_values: &'w AbilityValues,
+    client_entity: &'w ClientEntity,
+    command: &'w Command,
+    health_points: &'w HealthPoints,
+    level: &'w Level,
+    move_speed: &'w MoveSpeed,
+    position: &'w Position,
+    status_effects: &'w StatusEffects,
+    team: &'w Team,
+    character_info: Option<&'w CharacterInfo>,
+    npc: Option<&'w Npc>,
 }
 
 lazy_static! {
@@ -93,7 +146,29 @@ lazy_static! {
         clap::Command::new("Chat Commands")
             .subcommand(clap::Command::new("help"))
             .subcommand(clap::Command::new("where"))
+            .subcommand(clap::Command::new("time"))
+            .subcommand(clap::Command::new("who"))
+            .subcommand(clap::Command::new("dance"))
+            .subcommand(clap::Command::new("sit"))
+            .subcommand(clap::Command::new("gather"))
+            .subcommand(clap::Command::new("wave"))
+            .subcommand(clap::Command::new("spectate"))
+            .subcommand(clap::Command::new("inspect").arg(Arg::new("entity_id").required(true)))
+            .subcommand(clap::Command::new("watch").arg(Arg::new("name").required(true)))
+            .subcommand(clap::Command::new("save").arg(Arg::new("name").required(false)))
             .subcommand(clap::Command::new("ability_values"))
+            .subcommand(clap::Command::new("stats"))
+            .subcommand(clap::Command::new("autoloot"))
+            .subcommand(clap::Command::new("census").arg(Arg::new("zones").required(false)))
+            .subcommand(
+                clap::Command::new("bug")
+                    .arg(Arg::new("text").required(true).multiple_values(true)),
+            )
+            .subcommand(
+                clap::Command::new("report")
+                    .arg(Arg::new("name").required(true))
+                    .arg(Arg::new("reason").required(true).multiple_values(true)),
+            )
             .subcommand(
                 clap::Command::new("damage")
                     .arg(Arg::new("amount").required(true))
@@ -107,7 +182,8 @@ lazy_static! {
                     .arg(Arg::new("quantity").required(false))
                     .arg(Arg::new("socket").required(false))
                     .arg(Arg::new("gem").required(false))
-                    .arg(Arg::new("grade").required(false)),
+                    .arg(Arg::new("grade").required(false))
+                    .arg(Arg::new("durability").required(false)),
             )
             .subcommand(
                 clap::Command::new("item")
@@ -116,7 +192,13 @@ lazy_static! {
                     .arg(Arg::new("quantity").required(false))
                     .arg(Arg::new("socket").required(false))
                     .arg(Arg::new("gem").required(false))
-                    .arg(Arg::new("grade").required(false)),
+                    .arg(Arg::new("grade").required(false))
+                    .arg(Arg::new("durability").required(false)),
+            )
+            .subcommand(
+                clap::Command::new("itemrain")
+                    .arg(Arg::new("count").required(false))
+                    .arg(Arg::new("zone").required(false)),
             )
             .subcommand(
                 clap::Command::new("mm")
@@ -124,6 +206,12 @@ lazy_static! {
                     .arg(Arg::new("x"))
                     .arg(Arg::new("y")),
             )
+            .subcommand(
+                clap::Command::new("goto")
+                    .arg(Arg::new("target").required(true))
+                    .arg(Arg::new("x"))
+                    .arg(Arg::new("y")),
+            )
             .subcommand(
                 clap::Command::new("mon")
                     .arg(Arg::new("id").required(true))
@@ -214,6 +302,17 @@ lazy_static! {
                             .arg(Arg::new("value").required(true)),
                     ),
             )
+            .subcommand(
+                clap::Command::new("news")
+                    .subcommand(clap::Command::new("list"))
+                    .subcommand(clap::Command::new("motd").arg(Arg::new("text").required(false)))
+                    .subcommand(clap::Command::new("add").arg(Arg::new("text").required(true)))
+                    .subcommand(clap::Command::new("remove").arg(Arg::new("index").required(true)))
+                    .subcommand(clap::Command::new("clear"))
+                    .subcommand(
+                        clap::Command::new("interval").arg(Arg::new("secs").required(true)),
+                    ),
+            )
     };
 }
 
@@ -290,92 +389,65 @@ impl From<ParseFloatError> for ChatCommandError {
     }
 }
 
-fn create_bot_entity(
-    chat_command_params: &mut ChatCommandParams,
-    name: String,
-    position: Position,
-    level: u32,
-) -> Option<Entity> {
-    let (bot_build, mut bot_data) =
-        bot_create_random_build(&chat_command_params.game_data, name, level);
+fn format_census_counts(name: &str, counts: &ZoneCensusCounts) -> String {
+    format!(
+        "{}: {} players, {} bots, {} monsters, {} npcs, {} item drops",
+        name, counts.players, counts.bots, counts.monsters, counts.npcs, counts.item_drops
+    )
+}
 
-    let status_effects = StatusEffects::new();
-    let status_effects_regen = StatusEffectsRegen::new();
+/// Limits how often a character can use the informational /where, /time and /who commands
+fn check_info_command_cooldown(
+    chat_command_params: &mut ChatCommandParams,
+    entity: Entity,
+) -> Result<(), ChatCommandError> {
+    let now = chat_command_params.time.elapsed();
+    let cooldown =
+        Duration::from_millis(chat_command_params.game_config.info_command_cooldown_millis);
+    if let Some(last_time) = chat_command_params.last_info_command_times.get(&entity) {
+        if now - *last_time < cooldown {
+            return Err(ChatCommandError::WithMessage(String::from(
+                "Please wait before using this command again",
+            )));
+        }
+    }
 
-    let ability_values = chat_command_params
-        .game_data
-        .ability_value_calculator
-        .calculate(
-            &bot_data.info,
-            &bot_data.level,
-            &bot_data.equipment,
-            &bot_data.basic_stats,
-            &bot_data.skill_list,
-            &status_effects,
-        );
+    chat_command_params
+        .last_info_command_times
+        .insert(entity, now);
+    Ok(())
+}
 
-    let move_mode = MoveMode::Run;
-    let move_speed = MoveSpeed::new(ability_values.get_move_speed(&move_mode));
+fn get_zone_time_of_day(game_data: &GameData, zone_id: ZoneId, world_time: u32) -> &'static str {
+    let Some(zone_data) = game_data
+        .zones
+        .get_zone(zone_id)
+        .filter(|zone_data| zone_data.day_cycle != 0)
+    else {
+        return "unknown";
+    };
 
-    let weapon_motion_type = chat_command_params
-        .game_data
-        .items
-        .get_equipped_weapon_item_data(&bot_data.equipment, EquipmentIndex::Weapon)
-        .map(|item_data| item_data.motion_type)
-        .unwrap_or(0) as usize;
-
-    let motion_data = MotionData::from_character(
-        chat_command_params.game_data.motions.as_ref(),
-        weapon_motion_type,
-        bot_data.info.gender,
-    );
+    let zone_time = world_time % zone_data.day_cycle;
+    if zone_time >= zone_data.night_time || zone_time < zone_data.morning_time {
+        "night"
+    } else if zone_time >= zone_data.evening_time {
+        "evening"
+    } else if zone_time >= zone_data.day_time {
+        "day"
+    } else {
+        "morning"
+    }
+}
 
-    bot_data.position = position;
-    bot_data.health_points.hp = ability_values.get_max_health();
-    bot_data.mana_points.mp = ability_values.get_max_mana();
-
-    let entity = chat_command_params
-        .commands
-        .spawn((
-            bot_build,
-            bot_thinker(),
-            CharacterBundle {
-                ability_values,
-                basic_stats: bot_data.basic_stats,
-                bank: Default::default(),
-                cooldowns: Cooldowns::default(),
-                command: Command::default(),
-                damage_sources: DamageSources::default_character(),
-                equipment: bot_data.equipment,
-                experience_points: bot_data.experience_points,
-                health_points: bot_data.health_points,
-                hotbar: bot_data.hotbar,
-                info: bot_data.info,
-                inventory: bot_data.inventory,
-                level: bot_data.level,
-                mana_points: bot_data.mana_points,
-                motion_data,
-                move_mode,
-                move_speed,
-                next_command: NextCommand::default(),
-                party_membership: PartyMembership::default(),
-                passive_recovery_time: PassiveRecoveryTime::default(),
-                position: bot_data.position,
-                quest_state: bot_data.quest_state,
-                skill_list: bot_data.skill_list,
-                skill_points: bot_data.skill_points,
-                stamina: bot_data.stamina,
-                stat_points: bot_data.stat_points,
-                status_effects,
-                status_effects_regen,
-                team: Team::default_character(),
-                union_membership: bot_data.union_membership,
-                clan_membership: ClanMembership::default(),
-            },
-        ))
-        .id();
-
-    Some(entity)
+/// Finds the motion of the emote skill with a name matching the chat command
+fn find_emote_motion(game_data: &GameData, name: &str) -> Option<MotionId> {
+    game_data
+        .skills
+        .iter()
+        .filter(|skill_data| matches!(skill_data.skill_type, SkillType::Emote))
+        .find(|skill_data| skill_data.name.trim().eq_ignore_ascii_case(name))
+        .and_then(|skill_data| skill_data.action_motion_id)
+        .filter(|&motion_id| game_data.motions.is_emote_motion(motion_id))
 }
 
 fn create_random_bot_entities(
@@ -388,38 +460,7 @@ fn create_random_bot_entities(
     let spawn_radius = f32::max(num_bots as f32 * spacing, 100.0);
     let mut bot_entities = Vec::new();
 
-    // Try to calculate an ideal bot level range based on the current zone
-    let mut bot_level_range = 1..=170;
-    if let Some(zone_data) = chat_command_params.game_data.zones.get_zone(origin.zone_id) {
-        // Find all monsters which spawn in this zone
-        let mut monster_ids = HashSet::new();
-        for spawn in zone_data.monster_spawns.iter() {
-            for npc_id in spawn.basic_spawns.iter().map(|(npc_id, _)| *npc_id) {
-                monster_ids.insert(npc_id);
-            }
-
-            for npc_id in spawn.tactic_spawns.iter().map(|(npc_id, _)| *npc_id) {
-                monster_ids.insert(npc_id);
-            }
-        }
-
-        // Create a sorted list of the levels of the monsters in this zone
-        let mut monster_levels: Vec<_> = monster_ids
-            .iter()
-            .filter_map(|npc_id| chat_command_params.game_data.npcs.get_npc(*npc_id))
-            .map(|npc_data| npc_data.level)
-            .collect();
-        monster_levels.sort();
-
-        // Calculate a bot level range based on the median monster level +/- 20%
-        if let Some(median_monster_level) = monster_levels.get((monster_levels.len() + 1) / 2) {
-            // Ensure delta_level is mininum of 2 to ensure at least some variation in bot levels
-            let delta_level = (*median_monster_level / 5).max(2);
-
-            bot_level_range = (median_monster_level - delta_level).max(1)
-                ..=(median_monster_level + delta_level).min(170);
-        }
-    }
+    let bot_level_range = bot_level_range_for_zone(&chat_command_params.game_data, origin.zone_id);
 
     for i in 0..num_bots {
         let angle = (i as f32 * (2.0 * PI)) / num_bots as f32;
@@ -427,17 +468,17 @@ fn create_random_bot_entities(
         bot_position.position.x += spawn_radius * angle.cos();
         bot_position.position.y += spawn_radius * angle.sin();
 
-        if let Some(bot_entity) = create_bot_entity(
-            chat_command_params,
+        let bot_entity = bot_spawn_random_build(
+            &mut chat_command_params.commands,
+            &chat_command_params.game_data,
             format!("Friend {}", chat_command_params.bot_list.len()),
             bot_position,
             rng.gen_range::<i32, _>(bot_level_range.clone()) as u32,
-        ) {
-            chat_command_params
-                .bot_list
-                .push(BotListEntry::new(bot_entity));
-            bot_entities.push(bot_entity);
-        }
+        );
+        chat_command_params
+            .bot_list
+            .push(BotListEntry::new(bot_entity));
+        bot_entities.push(bot_entity);
     }
 
     bot_entities
@@ -460,6 +501,8 @@ fn handle_chat_command(
             send_chat_commands_help(chat_command_user.game_client);
         }
         ("where", _) => {
+            check_info_command_cooldown(chat_command_params, chat_command_user.entity)?;
+
             let sector = chat_command_params
                 .client_entity_list
                 .get_zone(chat_command_user.position.zone_id)
@@ -474,7 +517,12 @@ fn handle_chat_command(
                 .send(ServerMessage::Whisper {
                     from: String::from("SERVER"),
                     text: format!(
-                        "zone: {} position: ({}, {}, {}) sector: ({}, {})",
+                        "zone: {} ({}) position: ({}, {}, {}) sector: ({}, {})",
+                        chat_command_params
+                            .game_data
+                            .zones
+                            .get_zone(chat_command_user.position.zone_id)
+                            .map_or("unknown", |zone_data| zone_data.name),
                         chat_command_user.position.zone_id.get(),
                         chat_command_user.position.position.x,
                         chat_command_user.position.position.y,
@@ -485,6 +533,140 @@ fn handle_chat_command(
                 })
                 .ok();
         }
+        ("time", _) => {
+            check_info_command_cooldown(chat_command_params, chat_command_user.entity)?;
+
+            let ticks = chat_command_params.world_time.ticks;
+            let time_of_day = get_zone_time_of_day(
+                &chat_command_params.game_data,
+                chat_command_user.position.zone_id,
+                ticks.get_world_time(),
+            );
+
+            send_multiline_whisper(
+                chat_command_user.game_client,
+                &format!(
+                    "Year {} Month {} Day {}, {} (world tick {})",
+                    ticks.get_world_year() + 1,
+                    ticks.get_world_month() % WORLD_MONTH_PER_YEAR as u32 + 1,
+                    ticks.get_world_day(),
+                    time_of_day,
+                    ticks.0,
+                ),
+            );
+        }
+        (name @ ("dance" | "wave"), _) => {
+            let motion_id = find_emote_motion(&chat_command_params.game_data, name)
+                .ok_or_else(|| ChatCommandError::WithMessage(format!("No emote for {}", name)))?;
+
+            chat_command_params
+                .commands
+                .entity(chat_command_user.entity)
+                .insert(NextCommand::with_emote(motion_id, false));
+        }
+        ("sit", _) => {
+            let next_command = if matches!(chat_command_user.command.command, CommandData::Sit) {
+                NextCommand::with_standing()
+            } else {
+                NextCommand::with_sitting()
+            };
+
+            chat_command_params
+                .commands
+                .entity(chat_command_user.entity)
+                .insert(next_command);
+        }
+        ("gather", _) => {
+            let user_position = chat_command_user.position;
+            let Some((node_entity, gather_node, _)) = chat_command_params
+                .query_gather_node
+                .iter()
+                .filter(|(_, _, position)| position.zone_id == user_position.zone_id)
+                .map(|(entity, gather_node, position)| {
+                    (
+                        entity,
+                        gather_node,
+                        position.position.xy().distance(user_position.position.xy()),
+                    )
+                })
+                .filter(|(_, _, distance)| *distance <= GATHER_NODE_DISTANCE)
+                .min_by(|(_, _, a), (_, _, b)| a.total_cmp(b))
+            else {
+                return Err(ChatCommandError::WithMessage(String::from(
+                    "There is nothing to gather nearby",
+                )));
+            };
+
+            if gather_node.is_depleted() {
+                let name = chat_command_params
+                    .game_data
+                    .gather_nodes
+                    .get_node(gather_node.node_index)
+                    .map_or("node", |gather_node_data| gather_node_data.name.as_str());
+                return Err(ChatCommandError::WithMessage(format!(
+                    "The {} has been depleted, try again later",
+                    name
+                )));
+            }
+
+            chat_command_params
+                .commands
+                .entity(chat_command_user.entity)
+                .insert(NextCommand::with_gather(node_entity));
+        }
+        ("spectate", _) => {
+            if chat_command_user.spectator.is_some() {
+                chat_command_params
+                    .commands
+                    .entity(chat_command_user.entity)
+                    .remove::<Spectator>();
+                send_multiline_whisper(chat_command_user.game_client, "Spectator mode disabled");
+            } else {
+                chat_command_params
+                    .commands
+                    .entity(chat_command_user.entity)
+                    .insert(Spectator);
+                send_multiline_whisper(chat_command_user.game_client, "Spectator mode enabled");
+            }
+        }
+        ("itemrain", arg_matches) => {
+            let item_rain_config = &chat_command_params.game_config.item_rain;
+            if item_rain_config.items.is_empty() {
+                return Err(ChatCommandError::WithMessage(
+                    "There are no item rain items configured".to_string(),
+                ));
+            }
+
+            let drop_count = arg_matches
+                .value_of("count")
+                .map(|str| str.parse::<usize>())
+                .transpose()?
+                .unwrap_or(item_rain_config.drop_count);
+            let zone_id = arg_matches
+                .value_of("zone")
+                .map(|str| str.parse::<ZoneId>())
+                .transpose()?
+                .unwrap_or(chat_command_user.position.zone_id);
+            if chat_command_params
+                .game_data
+                .zones
+                .get_zone(zone_id)
+                .is_none()
+            {
+                return Err(ChatCommandError::WithMessage(format!(
+                    "Invalid zone id {}",
+                    zone_id.get()
+                )));
+            }
+
+            chat_command_params
+                .events
+                .item_rain_events
+                .send(ItemRainEvent {
+                    zone_id,
+                    drop_count,
+                });
+        }
         ("mm", arg_matches) => {
             let zone_id = arg_matches.value_of("zone").unwrap().parse::<ZoneId>()?;
             let (x, y) = if let (Some(x), Some(y)) =
@@ -521,8 +703,89 @@ fn handle_chat_command(
                 &format!("{:?}", chat_command_user.ability_values),
             );
         }
+        ("stats", _) => {
+            let statistics = chat_command_user.statistics.cloned().unwrap_or_default();
+            let mut npc_kills: Vec<_> = statistics.npc_kills.iter().collect();
+            npc_kills.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+            let mut lines = vec![
+                format!("Monsters killed: {}", statistics.monster_kills),
+                format!("Deaths: {}", statistics.deaths),
+                format!("Zuly earned: {}", statistics.money_earned.0),
+                format!("Distance walked: {:.0}", statistics.distance_walked),
+                format!("Quests completed: {}", statistics.quests_completed),
+            ];
+            for (npc_id, kills) in npc_kills.into_iter().take(5) {
+                let name = chat_command_params
+                    .game_data
+                    .npcs
+                    .get_npc(*npc_id)
+                    .map_or("Unknown", |npc_data| npc_data.name);
+                lines.push(format!("{}: {}", name, kills));
+            }
+            send_multiline_whisper(chat_command_user.game_client, &lines.join("\n"));
+        }
+        ("census", arg_matches) => {
+            let num_top_zones = arg_matches
+                .value_of("zones")
+                .map(|str| str.parse::<usize>())
+                .transpose()?
+                .unwrap_or(5);
+            let zone_census = &chat_command_params.zone_census;
+            let zone_id = chat_command_user.position.zone_id;
+
+            let mut lines = vec![
+                format_census_counts("All zones", &zone_census.total()),
+                format_census_counts(
+                    chat_command_params
+                        .game_data
+                        .zones
+                        .get_zone(zone_id)
+                        .map_or("Current zone", |zone_data| zone_data.name),
+                    &zone_census.get(zone_id),
+                ),
+            ];
+            for (zone_id, counts) in zone_census.top_zones(num_top_zones) {
+                let name = chat_command_params
+                    .game_data
+                    .zones
+                    .get_zone(zone_id)
+                    .map_or("Unknown", |zone_data| zone_data.name);
+                lines.push(format_census_counts(
+                    &format!("{} ({})", name, zone_id.get()),
+                    &counts,
+                ));
+            }
+            send_multiline_whisper(chat_command_user.game_client, &lines.join("\n"));
+        }
+        ("autoloot", _) => {
+            if !chat_command_params.game_config.item_drops.enable_auto_loot {
+                return Err(ChatCommandError::WithMessage(
+                    "Auto loot is disabled on this server".to_string(),
+                ));
+            }
+
+            let auto_loot = !chat_command_user
+                .loot_settings
+                .map_or(false, |loot_settings| loot_settings.auto_loot);
+            chat_command_params
+                .commands
+                .entity(chat_command_user.entity)
+                .insert(CharacterLootSettings { auto_loot });
+            send_multiline_whisper(
+                chat_command_user.game_client,
+                if auto_loot {
+                    "Auto loot enabled"
+                } else {
+                    "Auto loot disabled"
+                },
+            );
+        }
         ("level", arg_matches) => {
-            let target_level = arg_matches.value_of("level").unwrap().parse::<u32>()?;
+            let target_level = chat_command_params
+                .game_config
+                .ability_values
+                .clamp_level(arg_matches.value_of("level").unwrap().parse::<u32>()?);
             let current_level = chat_command_user.level.level;
             let mut required_xp = 0;
 
@@ -534,6 +797,7 @@ fn handle_chat_command(
             }
 
             chat_command_params
+                .events
                 .reward_xp_events
                 .send(RewardXpEvent::new(
                     chat_command_user.entity,
@@ -570,10 +834,12 @@ fn handle_chat_command(
                     )));
                 }
             };
-            let level = arg_matches
-                .value_of("level")
-                .and_then(|str| str.parse::<u32>().ok())
-                .unwrap_or(chat_command_user.level.level);
+            let level = chat_command_params.game_config.ability_values.clamp_level(
+                arg_matches
+                    .value_of("level")
+                    .and_then(|str| str.parse::<u32>().ok())
+                    .unwrap_or(chat_command_user.level.level),
+            );
 
             let bot_data = bot_create_with_build(
                 &chat_command_params.game_data,
@@ -956,6 +1222,7 @@ fn handle_chat_command(
                 ) {
                     if chat_command_user.entity != defender {
                         chat_command_params
+                            .events
                             .damage_events
                             .send(DamageEvent::Immediate {
                                 attacker: chat_command_user.entity,
@@ -1013,25 +1280,34 @@ fn handle_chat_command(
 
             let quantity = arg_matches
                 .value_of("quantity")
-                .and_then(|str| str.parse::<u32>().ok())
+                .map(|str| str.parse::<u32>())
+                .transpose()?
                 .unwrap_or(1);
 
             let has_socket = arg_matches
                 .value_of("socket")
-                .and_then(|str| str.parse::<u8>().ok())
+                .map(|str| str.parse::<u8>())
+                .transpose()?
                 .unwrap_or(0)
                 != 0;
 
             let gem = arg_matches
                 .value_of("gem")
-                .and_then(|str| str.parse::<u16>().ok())
+                .map(|str| str.parse::<u16>())
+                .transpose()?
                 .unwrap_or(0);
 
             let grade = arg_matches
                 .value_of("grade")
-                .and_then(|str| str.parse::<u8>().ok())
+                .map(|str| str.parse::<u8>())
+                .transpose()?
                 .unwrap_or(0);
 
+            let durability = arg_matches
+                .value_of("durability")
+                .map(|str| str.parse::<u8>())
+                .transpose()?;
+
             let item_reference = ItemReference::new(item_type, item_number);
             let item_data = chat_command_params
                 .game_data
@@ -1041,17 +1317,68 @@ fn handle_chat_command(
                     ChatCommandError::WithMessage(format!("Invalid item {:?}", item_reference))
                 })?;
 
-            let mut item = Item::from_item_data(item_data, quantity)
-                .ok_or(ChatCommandError::InvalidArguments)?;
+            let item = if item_type.is_stackable_item() {
+                if quantity == 0 || quantity > MAX_STACKABLE_ITEM_QUANTITY {
+                    return Err(ChatCommandError::WithMessage(format!(
+                        "Invalid quantity {}, must be between 1 and {}",
+                        quantity, MAX_STACKABLE_ITEM_QUANTITY
+                    )));
+                }
+
+                Item::Stackable(
+                    StackableItem::from_item_data(item_data, quantity)
+                        .ok_or(ChatCommandError::InvalidArguments)?,
+                )
+            } else {
+                if quantity != 1 {
+                    return Err(ChatCommandError::WithMessage(format!(
+                        "Invalid quantity {}, equipment items can not be stacked",
+                        quantity
+                    )));
+                }
+
+                if gem != 0 {
+                    if !has_socket {
+                        return Err(ChatCommandError::WithMessage(String::from(
+                            "A gem can only be set on an item with a socket",
+                        )));
+                    }
 
-            match &mut item {
-                Item::Equipment(equipment_item) => {
-                    equipment_item.has_socket = has_socket;
-                    equipment_item.gem = gem;
-                    equipment_item.grade = grade;
+                    if chat_command_params
+                        .game_data
+                        .items
+                        .get_gem_item(gem as usize)
+                        .is_none()
+                    {
+                        return Err(ChatCommandError::WithMessage(format!(
+                            "Invalid gem {}",
+                            gem
+                        )));
+                    }
+                }
+
+                if chat_command_params
+                    .game_data
+                    .items
+                    .get_item_grade(grade)
+                    .is_none()
+                {
+                    return Err(ChatCommandError::WithMessage(format!(
+                        "Invalid grade {}",
+                        grade
+                    )));
                 }
-                Item::Stackable(_) => {}
-            }
+
+                let mut equipment_item = EquipmentItem::from_item_data(item_data)
+                    .ok_or(ChatCommandError::InvalidArguments)?;
+                equipment_item.has_socket = has_socket;
+                equipment_item.gem = gem;
+                equipment_item.grade = grade;
+                if let Some(durability) = durability {
+                    equipment_item.durability = durability;
+                }
+                Item::Equipment(equipment_item)
+            };
 
             if is_drop {
                 ItemDropBundle::spawn(
@@ -1065,6 +1392,7 @@ fn handle_chat_command(
                 );
             } else {
                 chat_command_params
+                    .events
                     .reward_item_events
                     .send(RewardItemEvent::new(chat_command_user.entity, item, true));
             }
@@ -1077,17 +1405,23 @@ fn handle_chat_command(
                 if let Some(clan_entity) = chat_command_user.clan_membership.clan() {
                     match cmd {
                         "add" => {
-                            chat_command_params.clan_events.send(ClanEvent::AddLevel {
-                                clan_entity,
-                                level: value,
-                            });
+                            chat_command_params
+                                .events
+                                .clan_events
+                                .send(ClanEvent::AddLevel {
+                                    clan_entity,
+                                    level: value,
+                                });
                         }
                         "set" => {
-                            chat_command_params.clan_events.send(ClanEvent::SetLevel {
-                                clan_entity,
-                                level: ClanLevel::new(value as u32)
-                                    .ok_or(ChatCommandError::InvalidArguments)?,
-                            });
+                            chat_command_params
+                                .events
+                                .clan_events
+                                .send(ClanEvent::SetLevel {
+                                    clan_entity,
+                                    level: ClanLevel::new(value as u32)
+                                        .ok_or(ChatCommandError::InvalidArguments)?,
+                                });
                         }
                         _ => return Err(ChatCommandError::InvalidArguments),
                     }
@@ -1099,16 +1433,23 @@ fn handle_chat_command(
                 if let Some(clan_entity) = chat_command_user.clan_membership.clan() {
                     match cmd {
                         "add" => {
-                            chat_command_params.clan_events.send(ClanEvent::AddPoints {
-                                clan_entity,
-                                points: value,
-                            });
+                            chat_command_params
+                                .events
+                                .clan_events
+                                .send(ClanEvent::AddPoints {
+                                    clan_entity,
+                                    points: value,
+                                    contributor: None,
+                                });
                         }
                         "set" => {
-                            chat_command_params.clan_events.send(ClanEvent::SetPoints {
-                                clan_entity,
-                                points: ClanPoints(value as u64),
-                            });
+                            chat_command_params
+                                .events
+                                .clan_events
+                                .send(ClanEvent::SetPoints {
+                                    clan_entity,
+                                    points: ClanPoints(value as u64),
+                                });
                         }
                         _ => return Err(ChatCommandError::InvalidArguments),
                     }
@@ -1120,16 +1461,22 @@ fn handle_chat_command(
                 if let Some(clan_entity) = chat_command_user.clan_membership.clan() {
                     match cmd {
                         "add" => {
-                            chat_command_params.clan_events.send(ClanEvent::AddMoney {
-                                clan_entity,
-                                money: value,
-                            });
+                            chat_command_params
+                                .events
+                                .clan_events
+                                .send(ClanEvent::AddMoney {
+                                    clan_entity,
+                                    money: value,
+                                });
                         }
                         "set" => {
-                            chat_command_params.clan_events.send(ClanEvent::SetMoney {
-                                clan_entity,
-                                money: Money(value),
-                            });
+                            chat_command_params
+                                .events
+                                .clan_events
+                                .send(ClanEvent::SetMoney {
+                                    clan_entity,
+                                    money: Money(value),
+                                });
                         }
                         _ => return Err(ChatCommandError::InvalidArguments),
                     }
@@ -1141,14 +1488,18 @@ fn handle_chat_command(
                 if let Some(clan_entity) = chat_command_user.clan_membership.clan() {
                     match cmd {
                         "add" => {
-                            chat_command_params.clan_events.send(ClanEvent::AddSkill {
-                                clan_entity,
-                                skill_id: SkillId::new(value)
-                                    .ok_or(ChatCommandError::InvalidArguments)?,
-                            });
+                            chat_command_params
+                                .events
+                                .clan_events
+                                .send(ClanEvent::AddSkill {
+                                    clan_entity,
+                                    skill_id: SkillId::new(value)
+                                        .ok_or(ChatCommandError::InvalidArguments)?,
+                                });
                         }
                         "remove" => {
                             chat_command_params
+                                .events
                                 .clan_events
                                 .send(ClanEvent::RemoveSkill {
                                     clan_entity,
@@ -1161,53 +1512,585 @@ fn handle_chat_command(
                 }
             }
         }
+        ("news", arg_matches) => {
+            let server_news = &mut chat_command_params.server_news;
+
+            match arg_matches
+                .subcommand()
+                .ok_or(ChatCommandError::InvalidArguments)?
+            {
+                ("list", _) => {
+                    let mut text = format!(
+                        "motd: {}\ninterval: {}s",
+                        server_news.motd.join(" | "),
+                        server_news.announcement_interval.as_secs()
+                    );
+                    for (index, announcement) in server_news.announcements.iter().enumerate() {
+                        text.push_str(&format!("\n{}: {}", index, announcement));
+                    }
+                    send_multiline_whisper(chat_command_user.game_client, &text);
+                }
+                ("motd", sub_matches) => {
+                    // Without any text the message of the day is cleared
+                    server_news.motd = sub_matches
+                        .value_of("text")
+                        .map(|text| vec![text.to_string()])
+                        .unwrap_or_default();
+                }
+                ("add", sub_matches) => {
+                    let text = sub_matches.value_of("text").unwrap();
+                    server_news.announcements.push(text.to_string());
+                }
+                ("remove", sub_matches) => {
+                    let index = sub_matches.value_of("index").unwrap().parse::<usize>()?;
+                    if index >= server_news.announcements.len() {
+                        return Err(ChatCommandError::WithMessage(format!(
+                            "Invalid announcement index {}",
+                            index
+                        )));
+                    }
+                    server_news.announcements.remove(index);
+                }
+                ("clear", _) => {
+                    server_news.announcements.clear();
+                }
+                ("interval", sub_matches) => {
+                    let secs = sub_matches.value_of("secs").unwrap().parse::<u64>()?;
+                    server_news.announcement_interval = Duration::from_secs(secs);
+                }
+                _ => return Err(ChatCommandError::InvalidArguments),
+            }
+        }
         _ => return Err(ChatCommandError::InvalidCommand),
     }
 
     Ok(())
 }
 
+fn handle_inspect_chat_command(
+    chat_command_params: &ChatCommandParams,
+    user_query: &Query<ChatCommandUserQuery>,
+    inspect_query: &Query<ChatCommandInspectQuery, Without<GameClient>>,
+    user: &ChatCommandUserQueryReadOnlyItem,
+    arg_matches: &clap::ArgMatches,
+) -> Result<(), ChatCommandError> {
+    let entity_id = arg_matches
+        .value_of("entity_id")
+        .unwrap()
+        .parse::<usize>()?;
+
+    let (target_entity, _, _) = chat_command_params
+        .client_entity_list
+        .get_zone(user.position.zone_id)
+        .and_then(|client_entity_zone| client_entity_zone.get_entity(ClientEntityId(entity_id)))
+        .ok_or_else(|| ChatCommandError::WithMessage(format!("Invalid entity id {}", entity_id)))?;
+
+    let text = if let Ok(target) = user_query.get(*target_entity) {
+        format!(
+            "character: {}\nzone: {} position: ({}, {}, {})\nlevel: {} hp: {}/{} mp: {}/{}\n{:?}",
+            target.character_info.name,
+            target.position.zone_id.get(),
+            target.position.position.x,
+            target.position.position.y,
+            target.position.position.z,
+            target.level.level,
+            target.health_points.hp,
+            target.ability_values.get_max_health(),
+            target.mana_points.mp,
+            target.ability_values.get_max_mana(),
+            target.ability_values,
+        )
+    } else if let Ok(target) = inspect_query.get(*target_entity) {
+        let name = if let Some(character_info) = target.character_info {
+            character_info.name.clone()
+        } else if let Some(npc) = target.npc {
+            chat_command_params
+                .game_data
+                .npcs
+                .get_npc(npc.id)
+                .map_or_else(
+                    || format!("npc {}", npc.id.get()),
+                    |npc_data| format!("{} (npc {})", npc_data.name, npc.id.get()),
+                )
+        } else {
+            String::from("unknown")
+        };
+
+        format!(
+            "{:?}: {}\nzone: {} position: ({}, {}, {})\nlevel: {} hp: {}/{} team: {} speed: {}\ncommand: {:?}\nstatus effects: {:?}\n{:?}",
+            target.client_entity.entity_type,
+            name,
+            target.position.zone_id.get(),
+            target.position.position.x,
+            target.position.position.y,
+            target.position.position.z,
+            target.level.level,
+            target.health_points.hp,
+            target.ability_values.get_max_health(),
+            target.team.id,
+            target.move_speed.speed,
+            target.command.command,
+            target
+                .status_effects
+                .active
+                .values()
+                .filter_map(|active| active.as_ref().map(|active| active.id))
+                .collect::<Vec<_>>(),
+            target.ability_values,
+        )
+    } else {
+        return Err(ChatCommandError::WithMessage(format!(
+            "Unable to inspect entity id {}",
+            entity_id
+        )));
+    };
+
+    send_multiline_whisper(user.game_client, &text);
+    Ok(())
+}
+
+fn handle_who_chat_command(
+    chat_command_params: &mut ChatCommandParams,
+    user_query: &Query<ChatCommandUserQuery>,
+    user: &ChatCommandUserQueryReadOnlyItem,
+) -> Result<(), ChatCommandError> {
+    check_info_command_cooldown(chat_command_params, user.entity)?;
+
+    let mut online_count = 0;
+    let mut zone_count = 0;
+    let mut nearby = Vec::new();
+    for target in user_query.iter() {
+        online_count += 1;
+
+        if target.position.zone_id != user.position.zone_id {
+            continue;
+        }
+        zone_count += 1;
+
+        if target.entity != user.entity
+            && target.spectator.is_none()
+            && target
+                .position
+                .position
+                .xy()
+                .distance(user.position.position.xy())
+                < WHO_NEARBY_DISTANCE
+        {
+            nearby.push((target.character_info.name.as_str(), target.level.level));
+        }
+    }
+    nearby.sort();
+
+    let mut text = format!("online: {} in zone: {}", online_count, zone_count);
+    if nearby.is_empty() {
+        text.push_str("\nnobody nearby");
+    } else {
+        text.push_str(&format!("\nnearby ({}):", nearby.len()));
+        for (name, level) in nearby.iter().take(WHO_MAX_NEARBY_CHARACTERS) {
+            text.push_str(&format!("\n- {} (level {})", name, level));
+        }
+        if nearby.len() > WHO_MAX_NEARBY_CHARACTERS {
+            text.push_str(&format!(
+                "\n... and {} more",
+                nearby.len() - WHO_MAX_NEARBY_CHARACTERS
+            ));
+        }
+    }
+
+    send_multiline_whisper(user.game_client, &text);
+    Ok(())
+}
+
+fn find_zone_by_id_or_name<'a>(game_data: &'a GameData, zone: &str) -> Option<&'a ZoneData> {
+    if let Ok(zone_id) = zone.parse::<ZoneId>() {
+        game_data.zones.get_zone(zone_id)
+    } else {
+        game_data.zones.find_zone_by_name(zone)
+    }
+}
+
+/// Teleport to a teleport anchor, a character, or a zone by id or name
+fn handle_goto_chat_command(
+    chat_command_params: &mut ChatCommandParams,
+    user_query: &Query<ChatCommandUserQuery>,
+    user: &ChatCommandUserQueryReadOnlyItem,
+    arg_matches: &clap::ArgMatches,
+) -> Result<(), ChatCommandError> {
+    let target = arg_matches.value_of("target").unwrap();
+    let position = if let (Some(x), Some(y)) =
+        (arg_matches.value_of("x"), arg_matches.value_of("y"))
+    {
+        let zone_data = find_zone_by_id_or_name(&chat_command_params.game_data, target)
+            .ok_or_else(|| ChatCommandError::WithMessage(format!("Unknown zone {}", target)))?;
+        Position::new(
+            Vec3::new(x.parse::<f32>()? * 1000.0, y.parse::<f32>()? * 1000.0, 0.0),
+            zone_data.id,
+        )
+    } else if let Some(anchor) = chat_command_params
+        .game_data
+        .teleport_anchors
+        .find_anchor(target)
+    {
+        Position::new(Vec3::new(anchor.x, anchor.y, 0.0), anchor.zone)
+    } else if let Some(character) = user_query
+        .iter()
+        .find(|character| character.character_info.name.eq_ignore_ascii_case(target))
+    {
+        character.position.clone()
+    } else if let Some(zone_data) = find_zone_by_id_or_name(&chat_command_params.game_data, target)
+    {
+        Position::new(zone_data.start_position, zone_data.id)
+    } else {
+        return Err(ChatCommandError::WithMessage(format!(
+            "No teleport anchor, character or zone named {}",
+            target
+        )));
+    };
+
+    if chat_command_params
+        .client_entity_list
+        .get_zone(position.zone_id)
+        .is_none()
+    {
+        return Err(ChatCommandError::WithMessage(format!(
+            "Invalid zone id {}",
+            position.zone_id.get()
+        )));
+    }
+
+    client_entity_teleport_zone(
+        &mut chat_command_params.commands,
+        &mut chat_command_params.client_entity_list,
+        user.entity,
+        user.client_entity,
+        user.client_entity_sector,
+        user.position,
+        position,
+        Some(user.game_client),
+    );
+    Ok(())
+}
+
+fn handle_watch_chat_command(
+    user_query: &Query<ChatCommandUserQuery>,
+    user: &ChatCommandUserQueryReadOnlyItem,
+    arg_matches: &clap::ArgMatches,
+) -> Result<(), ChatCommandError> {
+    let name = arg_matches.value_of("name").unwrap();
+    let target = user_query
+        .iter()
+        .find(|target| target.character_info.name == name)
+        .ok_or_else(|| {
+            ChatCommandError::WithMessage(format!("Unable to find character {}", name))
+        })?;
+
+    if target.game_client.watch.is_enabled() {
+        target.game_client.watch.stop();
+        send_multiline_whisper(user.game_client, &format!("Stopped watching {}", name));
+    } else {
+        target.game_client.watch.start(name.to_string());
+        send_multiline_whisper(
+            user.game_client,
+            &format!("Watching {}, messages are logged with target watch", name),
+        );
+    }
+
+    Ok(())
+}
+
+fn handle_save_chat_command(
+    chat_command_params: &mut ChatCommandParams,
+    user_query: &Query<ChatCommandUserQuery>,
+    user: &ChatCommandUserQueryReadOnlyItem,
+    arg_matches: &clap::ArgMatches,
+) -> Result<(), ChatCommandError> {
+    let target_entity = match arg_matches.value_of("name") {
+        Some(name) => {
+            user_query
+                .iter()
+                .find(|target| target.character_info.name == name)
+                .ok_or_else(|| {
+                    ChatCommandError::WithMessage(format!("Unable to find character {}", name))
+                })?
+                .entity
+        }
+        None => user.entity,
+    };
+
+    chat_command_params
+        .events
+        .save_events
+        .send(SaveEvent::Character {
+            entity: target_entity,
+            remove_after_save: false,
+        });
+    Ok(())
+}
+
+fn get_report_entity_summary(
+    chat_command_params: &ChatCommandParams,
+    user_query: &Query<ChatCommandUserQuery>,
+    inspect_query: &Query<ChatCommandInspectQuery, Without<GameClient>>,
+    entity: Entity,
+) -> Option<ReportEntitySummary> {
+    if let Ok(target) = user_query.get(entity) {
+        Some(ReportEntitySummary {
+            entity_type: format!("{:?}", target.client_entity.entity_type),
+            name: target.character_info.name.clone(),
+            position: target.position.clone(),
+            level: target.level.level,
+            health: target.health_points.hp,
+            command: format!("{:?}", target.command.command),
+        })
+    } else if let Ok(target) = inspect_query.get(entity) {
+        let name = if let Some(character_info) = target.character_info {
+            character_info.name.clone()
+        } else if let Some(npc) = target.npc {
+            chat_command_params
+                .game_data
+                .npcs
+                .get_npc(npc.id)
+                .map_or_else(
+                    || format!("npc {}", npc.id.get()),
+                    |npc_data| format!("{} (npc {})", npc_data.name, npc.id.get()),
+                )
+        } else {
+            String::from("unknown")
+        };
+
+        Some(ReportEntitySummary {
+            entity_type: format!("{:?}", target.client_entity.entity_type),
+            name,
+            position: target.position.clone(),
+            level: target.level.level,
+            health: target.health_points.hp,
+            command: format!("{:?}", target.command.command),
+        })
+    } else {
+        None
+    }
+}
+
+fn handle_report_chat_command(
+    chat_command_params: &mut ChatCommandParams,
+    user_query: &Query<ChatCommandUserQuery>,
+    inspect_query: &Query<ChatCommandInspectQuery, Without<GameClient>>,
+    user: &ChatCommandUserQueryReadOnlyItem,
+    kind: ReportKind,
+    arg_matches: &clap::ArgMatches,
+) -> Result<(), ChatCommandError> {
+    let now = chat_command_params.time.elapsed();
+    let cooldown = Duration::from_secs(chat_command_params.game_config.report_cooldown_secs);
+    if let Some(last_report_time) = chat_command_params
+        .last_report_times
+        .get(&user.character_info.name)
+    {
+        let elapsed = now - *last_report_time;
+        if elapsed < cooldown {
+            return Err(ChatCommandError::WithMessage(format!(
+                "Please wait {} seconds before sending another report",
+                (cooldown - elapsed).as_secs() + 1
+            )));
+        }
+    }
+
+    let (text_arg, target_entity) = match kind {
+        ReportKind::Bug => ("text", user.command.target_entity()),
+        ReportKind::Player => {
+            let name = arg_matches.value_of("name").unwrap();
+            let target = user_query
+                .iter()
+                .find(|target| target.character_info.name == name)
+                .ok_or_else(|| {
+                    ChatCommandError::WithMessage(format!("Unable to find character {}", name))
+                })?;
+            ("reason", Some(target.entity))
+        }
+    };
+
+    let report = ReportStorage {
+        kind,
+        timestamp: SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs()),
+        reporter: user.character_info.name.clone(),
+        position: user.position.clone(),
+        text: arg_matches
+            .values_of(text_arg)
+            .unwrap()
+            .collect::<Vec<_>>()
+            .join(" "),
+        target: target_entity.and_then(|entity| {
+            get_report_entity_summary(chat_command_params, user_query, inspect_query, entity)
+        }),
+        command: format!("{:?}", user.command.command),
+        recent_chat_commands: chat_command_params
+            .recent_chat_commands
+            .get(&user.entity)
+            .map(|commands| commands.iter().cloned().collect())
+            .unwrap_or_default(),
+    };
+
+    if let Err(error) = report.save() {
+        warn!(
+            "Failed to save {:?} report from {}: {:?}",
+            kind, report.reporter, error
+        );
+        return Err(ChatCommandError::WithMessage(String::from(
+            "Failed to save report",
+        )));
+    }
+    info!("Saved {:?} report from {}", kind, report.reporter);
+
+    chat_command_params
+        .last_report_times
+        .insert(report.reporter, now);
+    send_multiline_whisper(user.game_client, "Thank you, your report has been sent");
+    Ok(())
+}
+
+fn handle_readonly_chat_command(
+    chat_command_params: &mut ChatCommandParams,
+    user_query: &Query<ChatCommandUserQuery>,
+    inspect_query: &Query<ChatCommandInspectQuery, Without<GameClient>>,
+    user_entity: Entity,
+    command_text: &str,
+) -> Result<(), ChatCommandError> {
+    let mut args = shellwords::split(command_text)?;
+    args.insert(0, String::new()); // Clap expects arg[0] to be like executable name
+    let command_matches = CHAT_COMMANDS.clone().try_get_matches_from(args)?;
+    let user = user_query
+        .get(user_entity)
+        .map_err(|_| ChatCommandError::InvalidCommand)?;
+
+    match command_matches
+        .subcommand()
+        .ok_or(ChatCommandError::InvalidCommand)?
+    {
+        ("inspect", arg_matches) => handle_inspect_chat_command(
+            chat_command_params,
+            user_query,
+            inspect_query,
+            &user,
+            arg_matches,
+        ),
+        ("watch", arg_matches) => handle_watch_chat_command(user_query, &user, arg_matches),
+        ("save", arg_matches) => {
+            handle_save_chat_command(chat_command_params, user_query, &user, arg_matches)
+        }
+        ("who", _) => handle_who_chat_command(chat_command_params, user_query, &user),
+        ("goto", arg_matches) => {
+            handle_goto_chat_command(chat_command_params, user_query, &user, arg_matches)
+        }
+        ("bug", arg_matches) => handle_report_chat_command(
+            chat_command_params,
+            user_query,
+            inspect_query,
+            &user,
+            ReportKind::Bug,
+            arg_matches,
+        ),
+        ("report", arg_matches) => handle_report_chat_command(
+            chat_command_params,
+            user_query,
+            inspect_query,
+            &user,
+            ReportKind::Player,
+            arg_matches,
+        ),
+        _ => Err(ChatCommandError::InvalidCommand),
+    }
+}
+
+/// Commands which read other players, so must be handled without holding a mutable user
+fn is_readonly_chat_command(command_text: &str) -> bool {
+    matches!(
+        command_text.split_whitespace().next(),
+        Some("bug")
+            | Some("goto")
+            | Some("inspect")
+            | Some("report")
+            | Some("save")
+            | Some("watch")
+            | Some("who")
+    )
+}
+
 pub fn chat_commands_system(
     mut chat_command_params: ChatCommandParams,
     mut user_query: Query<ChatCommandUserQuery>,
+    inspect_query: Query<ChatCommandInspectQuery, Without<GameClient>>,
     mut chat_command_events: EventReader<ChatCommandEvent>,
 ) {
+    if !chat_command_events.is_empty() {
+        chat_command_params
+            .recent_chat_commands
+            .retain(|entity, _| user_query.contains(*entity));
+        chat_command_params
+            .last_info_command_times
+            .retain(|entity, _| user_query.contains(*entity));
+    }
+
     for &ChatCommandEvent {
         entity,
         ref command,
     } in chat_command_events.iter()
     {
-        if let Ok(mut chat_command_user) = user_query.get_mut(entity) {
-            match handle_chat_command(
+        let result = if is_readonly_chat_command(&command[1..]) {
+            handle_readonly_chat_command(
+                &mut chat_command_params,
+                &user_query,
+                &inspect_query,
+                entity,
+                &command[1..],
+            )
+        } else if let Ok(mut chat_command_user) = user_query.get_mut(entity) {
+            handle_chat_command(
                 &mut chat_command_params,
                 &mut chat_command_user,
                 &command[1..],
-            ) {
-                Ok(_) => {
-                    send_multiline_whisper(
-                        chat_command_user.game_client,
-                        &format!("Success: {}", command),
-                    );
-                }
-                Err(error) => {
-                    send_multiline_whisper(
-                        chat_command_user.game_client,
-                        &format!("Failed: {}", command),
-                    );
+            )
+        } else {
+            continue;
+        };
+
+        let recent_chat_commands = chat_command_params
+            .recent_chat_commands
+            .entry(entity)
+            .or_default();
+        if recent_chat_commands.len() == RECENT_CHAT_COMMANDS_LENGTH {
+            recent_chat_commands.pop_front();
+        }
+        recent_chat_commands.push_back(command.clone());
 
-                    match error {
-                        ChatCommandError::InvalidCommand => {
-                            send_multiline_whisper(chat_command_user.game_client, "Invalid command")
-                        }
-                        ChatCommandError::InvalidArguments => send_multiline_whisper(
-                            chat_command_user.game_client,
-                            "Invalid argument",
-                        ),
-                        ChatCommandError::WithMessage(message) => {
-                            send_multiline_whisper(chat_command_user.game_client, &message)
-                        }
-                    };
-                }
+        let Ok(chat_command_user) = user_query.get(entity) else {
+            continue;
+        };
+
+        match result {
+            Ok(_) => {
+                send_multiline_whisper(
+                    chat_command_user.game_client,
+                    &format!("Success: {}", command),
+                );
+            }
+            Err(error) => {
+                send_multiline_whisper(
+                    chat_command_user.game_client,
+                    &format!("Failed: {}", command),
+                );
+
+                match error {
+                    ChatCommandError::InvalidCommand => {
+                        send_multiline_whisper(chat_command_user.game_client, "Invalid command")
+                    }
+                    ChatCommandError::InvalidArguments => {
+                        send_multiline_whisper(chat_command_user.game_client, "Invalid argument")
+                    }
+                    ChatCommandError::WithMessage(message) => {
+                        send_multiline_whisper(chat_command_user.game_client, &message)
+                    }
+                };
             }
         }
     }
@@ -1,29 +1,42 @@
-use std::num::{NonZeroU32, NonZeroUsize};
+use std::{
+    num::{NonZeroU32, NonZeroUsize},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 use bevy::{
     ecs::query::WorldQuery,
-    prelude::{Changed, Commands, Entity, EventReader, Query, ResMut},
+    prelude::{Added, Changed, Commands, Entity, EventReader, Or, Query, Res, ResMut, With},
+    time::Time,
 };
+use log::warn;
 
-use rose_data::{ClanMemberPosition, QuestTriggerHash};
+use rose_data::{ClanMemberPosition, Item, ItemSlotBehaviour, QuestTriggerHash};
 use rose_game_common::{
-    components::{ClanLevel, ClanPoints, ClanUniqueId},
-    messages::server::{ClanCreateError, ClanMemberInfo, ServerMessage},
+    components::{ClanLevel, ClanPermission, ClanPoints, ClanUniqueId},
+    messages::server::{
+        ClanApplication, ClanApplicationError, ClanCreateError, ClanInviteError, ClanMemberInfo,
+        ClanRecruitmentListing, ClanVaultAction, ClanVaultError, ClanVaultLogEntry, ServerMessage,
+    },
 };
 
 use crate::game::{
     components::{
-        CharacterInfo, Clan, ClanMember, ClanMembership, ClientEntity, GameClient, Inventory,
-        Level, Money,
+        CharacterInfo, Clan, ClanMember, ClanMemberStats, ClanMembership, ClanPendingInvite,
+        ClientEntity, GameClient, Inventory, ItemSlot, Level, Money,
     },
     events::ClanEvent,
-    resources::ServerMessages,
-    storage::clan::{ClanStorage, ClanStorageMember},
+    resources::{GameConfig, ServerMessages},
+    storage::{
+        clan::{ClanStorage, ClanStorageMember},
+        journal::JournalStorage,
+        writer::queue_storage_write,
+    },
 };
 
 #[derive(WorldQuery)]
 #[world_query(mutable)]
 pub struct CreatorQuery<'w> {
+    entity: Entity,
     client_entity: &'w ClientEntity,
     character_info: &'w CharacterInfo,
     level: &'w Level,
@@ -38,10 +51,29 @@ pub struct MemberQuery<'w> {
     character_info: &'w CharacterInfo,
     clan_membership: &'w ClanMembership,
     level: &'w Level,
+    client_entity: Option<&'w ClientEntity>,
     game_client: Option<&'w GameClient>,
 }
 
-fn send_update_clan_info(clan: &Clan, query_member: &Query<MemberQuery>) {
+// Maximum number of members for each clan level
+const CLAN_MEMBER_LIMITS: [usize; 7] = [15, 20, 25, 30, 36, 43, 50];
+
+// Maximum number of applications a clan can have waiting for a reply
+const CLAN_MAX_APPLICATIONS: usize = 50;
+
+// Maximum number of clans returned by a recruitment board search
+const CLAN_RECRUITMENT_MAX_RESULTS: usize = 50;
+
+// Maximum length in bytes of a recruitment notice or application message, so that a full page of
+// search results still fits within one packet
+const CLAN_RECRUITMENT_MAX_LENGTH: usize = 255;
+
+fn clan_member_limit(level: ClanLevel) -> usize {
+    let index = (level.get() as usize).clamp(1, CLAN_MEMBER_LIMITS.len()) - 1;
+    CLAN_MEMBER_LIMITS[index]
+}
+
+fn send_clan_message(clan: &Clan, query_member: &Query<MemberQuery>, message: ServerMessage) {
     for clan_member in clan.members.iter() {
         let &ClanMember::Online {
             entity: clan_member_entity,
@@ -55,29 +87,606 @@ fn send_update_clan_info(clan: &Clan, query_member: &Query<MemberQuery>) {
             if let Some(online_member_game_client) = online_member.game_client {
                 online_member_game_client
                     .server_message_tx
-                    .send(ServerMessage::ClanUpdateInfo {
-                        id: clan.unique_id,
-                        mark: clan.mark,
-                        level: clan.level,
-                        points: clan.points,
-                        money: clan.money,
-                        skills: clan.skills.clone(),
-                    })
+                    .send(message.clone())
                     .ok();
             }
         }
     }
 }
 
+fn send_update_clan_info(clan: &Clan, query_member: &Query<MemberQuery>) {
+    send_clan_message(
+        clan,
+        query_member,
+        ServerMessage::ClanUpdateInfo {
+            id: clan.unique_id,
+            mark: clan.mark,
+            level: clan.level,
+            points: clan.points,
+            money: clan.money,
+            skills: clan.skills.clone(),
+        },
+    );
+}
+
+fn send_member_message(query_member: &Query<MemberQuery>, entity: Entity, message: ServerMessage) {
+    if let Some(game_client) = query_member
+        .get(entity)
+        .ok()
+        .and_then(|member| member.game_client)
+    {
+        game_client.server_message_tx.send(message).ok();
+    }
+}
+
+fn get_member_name<'a>(
+    member: &'a ClanMember,
+    query_member: &'a Query<MemberQuery>,
+) -> Option<&'a str> {
+    match member {
+        &ClanMember::Online { entity, .. } => query_member
+            .get(entity)
+            .ok()
+            .map(|member| member.character_info.name.as_str()),
+        ClanMember::Offline { name, .. } => Some(name.as_str()),
+    }
+}
+
+fn find_member_index(clan: &Clan, query_member: &Query<MemberQuery>, name: &str) -> Option<usize> {
+    clan.members
+        .iter()
+        .position(|member| get_member_name(member, query_member) == Some(name))
+}
+
+fn save_clan(clan: &Clan, query_member: &Query<MemberQuery>) {
+    let members: Option<Vec<ClanStorageMember>> = clan
+        .members
+        .iter()
+        .map(|member| {
+            Some(ClanStorageMember {
+                name: get_member_name(member, query_member)?.to_string(),
+                position: member.position(),
+                contribution: member.contribution(),
+                stats: member.stats(),
+            })
+        })
+        .collect();
+    let Some(members) = members else {
+        // Do not lose members from storage because an online member could not be found
+        warn!(
+            "Skipped saving clan {} with an unknown online member",
+            clan.name
+        );
+        return;
+    };
+
+    let clan_storage = ClanStorage {
+        name: clan.name.clone(),
+        description: clan.description.clone(),
+        notice: clan.notice.clone(),
+        mark: clan.mark,
+        money: clan.money,
+        points: clan.points,
+        level: clan.level,
+        members,
+        skills: clan.skills.clone(),
+        vault: clan.vault.clone(),
+        permissions: clan.permissions.clone(),
+        recruitment: clan.recruitment.clone(),
+        applications: clan.applications.clone(),
+    };
+    // Queued behind any journal commits, so vault transactions are persisted in order
+    queue_storage_write(format!("save clan {}", clan.name), move || {
+        clan_storage.save()
+    });
+}
+
+/// Removes a member from a clan, clearing the clan from their character if they are online.
+fn remove_clan_member(
+    commands: &mut Commands,
+    server_messages: &mut ServerMessages,
+    clan: &mut Clan,
+    query_member: &Query<MemberQuery>,
+    member_index: usize,
+) {
+    if let ClanMember::Online { entity, .. } = clan.members.remove(member_index) {
+        commands.entity(entity).insert(ClanMembership::default());
+
+        if let Some(client_entity) = query_member
+            .get(entity)
+            .ok()
+            .and_then(|member| member.client_entity)
+        {
+            server_messages.send_entity_message(
+                client_entity,
+                ServerMessage::CharacterLeaveClan {
+                    client_entity_id: client_entity.id,
+                },
+            );
+        }
+    }
+}
+
+fn handle_clan_invite(
+    clan: &mut Clan,
+    query_member: &Query<MemberQuery>,
+    inviter_entity: Entity,
+    name: &str,
+    expire_time: Duration,
+) -> Result<(), ClanInviteError> {
+    let inviter_position = clan
+        .find_online_member(inviter_entity)
+        .ok_or(ClanInviteError::Failed)?
+        .position();
+    if !clan
+        .permissions
+        .is_allowed(inviter_position, ClanPermission::Invite)
+    {
+        return Err(ClanInviteError::NoPermission);
+    }
+
+    if clan.members.len() >= clan_member_limit(clan.level) {
+        return Err(ClanInviteError::ClanFull);
+    }
+
+    let invitee = query_member
+        .iter()
+        .find(|member| member.character_info.name == name)
+        .ok_or(ClanInviteError::Failed)?;
+    if invitee.clan_membership.is_some() {
+        return Err(ClanInviteError::AlreadyInClan);
+    }
+
+    // The invitee would never be able to accept without a client which supports clans
+    if invitee
+        .game_client
+        .map_or(false, |game_client| !game_client.capabilities.clans)
+    {
+        return Err(ClanInviteError::Failed);
+    }
+
+    // Inviting again restarts the timeout of an existing invite
+    clan.pending_invites
+        .retain(|invite| invite.entity != invitee.entity);
+    clan.pending_invites.push(ClanPendingInvite {
+        entity: invitee.entity,
+        expire_time,
+    });
+
+    let inviter = query_member
+        .get(inviter_entity)
+        .map_err(|_| ClanInviteError::Failed)?;
+    if let Some(game_client) = invitee.game_client {
+        game_client
+            .server_message_tx
+            .send(ServerMessage::ClanInvite {
+                inviter_name: inviter.character_info.name.clone(),
+            })
+            .ok();
+    }
+
+    Ok(())
+}
+
+fn handle_clan_invite_accept(
+    commands: &mut Commands,
+    server_messages: &mut ServerMessages,
+    clan_entity: Entity,
+    clan: &mut Clan,
+    query_member: &Query<MemberQuery>,
+    invitee_entity: Entity,
+) -> Result<(), ClanInviteError> {
+    let invitee = query_member
+        .get(invitee_entity)
+        .map_err(|_| ClanInviteError::Failed)?;
+    if invitee.clan_membership.is_some() {
+        return Err(ClanInviteError::AlreadyInClan);
+    }
+
+    if clan.members.len() >= clan_member_limit(clan.level) {
+        return Err(ClanInviteError::ClanFull);
+    }
+
+    let position = ClanMemberPosition::Junior;
+    send_clan_message(
+        clan,
+        query_member,
+        ServerMessage::ClanMemberJoined {
+            member: ClanMemberInfo {
+                name: invitee.character_info.name.clone(),
+                position,
+                contribution: ClanPoints(0),
+                channel_id: NonZeroUsize::new(1),
+                level: *invitee.level,
+                job: invitee.character_info.job,
+            },
+        },
+    );
+
+    clan.members.push(ClanMember::Online {
+        entity: invitee_entity,
+        position,
+        contribution: ClanPoints(0),
+        stats: ClanMemberStats::default(),
+    });
+    commands
+        .entity(invitee_entity)
+        .insert(ClanMembership::new(clan_entity));
+
+    if let Some(client_entity) = invitee.client_entity {
+        server_messages.send_entity_message(
+            client_entity,
+            ServerMessage::CharacterUpdateClan {
+                client_entity_id: client_entity.id,
+                id: clan.unique_id,
+                name: clan.name.clone(),
+                mark: clan.mark,
+                level: clan.level,
+                position,
+            },
+        );
+    }
+
+    save_clan(clan, query_member);
+    Ok(())
+}
+
+/// Changes the position of a clan member, the requester must outrank both the member's current
+/// and new position.
+fn handle_clan_change_position(
+    server_messages: &mut ServerMessages,
+    clan: &mut Clan,
+    query_member: &Query<MemberQuery>,
+    requester_entity: Entity,
+    name: &str,
+    promote: bool,
+) -> Option<()> {
+    let requester_position = clan.find_online_member(requester_entity)?.position();
+    let member_index = find_member_index(clan, query_member, name)?;
+    let current_position = clan.members[member_index].position();
+    let new_position = if promote {
+        current_position.promoted()?
+    } else {
+        current_position.demoted()?
+    };
+
+    if current_position >= requester_position || new_position >= requester_position {
+        return None;
+    }
+
+    match &mut clan.members[member_index] {
+        ClanMember::Online { position, .. } | ClanMember::Offline { position, .. } => {
+            *position = new_position;
+        }
+    }
+
+    if let &ClanMember::Online { entity, .. } = &clan.members[member_index] {
+        if let Some(client_entity) = query_member
+            .get(entity)
+            .ok()
+            .and_then(|member| member.client_entity)
+        {
+            server_messages.send_entity_message(
+                client_entity,
+                ServerMessage::CharacterUpdateClan {
+                    client_entity_id: client_entity.id,
+                    id: clan.unique_id,
+                    name: clan.name.clone(),
+                    mark: clan.mark,
+                    level: clan.level,
+                    position: new_position,
+                },
+            );
+        }
+    }
+
+    send_clan_message(
+        clan,
+        query_member,
+        ServerMessage::ClanMemberPositionChanged {
+            name: name.to_string(),
+            position: new_position,
+        },
+    );
+    save_clan(clan, query_member);
+    Some(())
+}
+
+fn check_vault_permission(
+    clan: &Clan,
+    entity: Entity,
+    permission: ClanPermission,
+) -> Result<(), ClanVaultError> {
+    let position = clan
+        .find_online_member(entity)
+        .ok_or(ClanVaultError::Failed)?
+        .position();
+    if !clan.permissions.is_allowed(position, permission) {
+        return Err(ClanVaultError::NoPermission);
+    }
+    Ok(())
+}
+
+fn check_application_permission(
+    clan: &Clan,
+    entity: Entity,
+    permission: ClanPermission,
+) -> Result<(), ClanApplicationError> {
+    let position = clan
+        .find_online_member(entity)
+        .ok_or(ClanApplicationError::Failed)?
+        .position();
+    if !clan.permissions.is_allowed(position, permission) {
+        return Err(ClanApplicationError::NoPermission);
+    }
+    Ok(())
+}
+
+fn handle_clan_apply(
+    clan: &mut Clan,
+    query_member: &Query<MemberQuery>,
+    applicant: &MemberQueryItem,
+    message: &str,
+) -> Result<(), ClanApplicationError> {
+    if message.len() > CLAN_RECRUITMENT_MAX_LENGTH {
+        return Err(ClanApplicationError::TooLong);
+    }
+
+    if clan.recruitment.is_empty() {
+        return Err(ClanApplicationError::NotRecruiting);
+    }
+
+    if clan
+        .applications
+        .iter()
+        .any(|application| application.name == applicant.character_info.name)
+    {
+        return Err(ClanApplicationError::AlreadyApplied);
+    }
+
+    if clan.members.len() >= clan_member_limit(clan.level) {
+        return Err(ClanApplicationError::ClanFull);
+    }
+
+    if clan.applications.len() >= CLAN_MAX_APPLICATIONS {
+        return Err(ClanApplicationError::Failed);
+    }
+
+    let application = ClanApplication {
+        name: applicant.character_info.name.clone(),
+        level: *applicant.level,
+        job: applicant.character_info.job,
+        message: message.to_string(),
+    };
+    clan.applications.push(application.clone());
+
+    // Notify the online members who are able to reply to the application
+    for clan_member in clan.members.iter() {
+        if let &ClanMember::Online {
+            entity, position, ..
+        } = clan_member
+        {
+            if clan
+                .permissions
+                .is_allowed(position, ClanPermission::Invite)
+            {
+                send_member_message(
+                    query_member,
+                    entity,
+                    ServerMessage::ClanApplicationReceived {
+                        application: application.clone(),
+                    },
+                );
+            }
+        }
+    }
+
+    save_clan(clan, query_member);
+    Ok(())
+}
+
+/// Saves both sides of a vault transaction, the side which lost the item is saved first so that
+/// a failure part way through can never duplicate an item.
+fn save_clan_vault_transaction(
+    clan: &Clan,
+    query_member: &Query<MemberQuery>,
+    member: &CreatorQueryItem,
+    action: ClanVaultAction,
+) {
+    let save_inventory = || {
+        let mut journal = JournalStorage::new();
+        journal.add_inventory(&member.character_info.name, &member.inventory);
+        journal.commit();
+    };
+
+    match action {
+        ClanVaultAction::Deposit => {
+            save_inventory();
+            save_clan(clan, query_member);
+        }
+        ClanVaultAction::Withdraw => {
+            save_clan(clan, query_member);
+            save_inventory();
+        }
+    }
+}
+
+fn add_clan_vault_log_entry(clan: &mut Clan, name: &str, action: ClanVaultAction, item: Item) {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    clan.vault.add_log_entry(ClanVaultLogEntry {
+        name: name.to_string(),
+        action,
+        item,
+        timestamp,
+    });
+}
+
+// Vault transactions are only ever handled here one event at a time and are validated against
+// the current vault contents, so members using the vault at the same time cannot take the same item
+fn handle_clan_vault_deposit(
+    clan: &mut Clan,
+    query_member: &Query<MemberQuery>,
+    game_config: &GameConfig,
+    depositor: &mut CreatorQueryItem,
+    item_slot: ItemSlot,
+    item: &Item,
+) -> Result<(), ClanVaultError> {
+    check_vault_permission(clan, depositor.entity, ClanPermission::VaultDeposit)?;
+
+    if game_config.is_untradeable_item(item) {
+        return Err(ClanVaultError::Failed);
+    }
+
+    let inventory_slot = depositor
+        .inventory
+        .get_item_slot_mut(item_slot)
+        .ok_or(ClanVaultError::Failed)?;
+    if !inventory_slot
+        .as_ref()
+        .map_or(false, |inventory_item| inventory_item.is_same_item(item))
+    {
+        return Err(ClanVaultError::Failed);
+    }
+
+    let deposit_item = inventory_slot
+        .try_take_quantity(item.get_quantity())
+        .ok_or(ClanVaultError::Failed)?;
+    let vault_slot = match clan.vault.try_add_item(deposit_item.clone()) {
+        Ok((vault_slot, _)) => vault_slot,
+        Err(deposit_item) => {
+            inventory_slot
+                .try_stack_with_item(deposit_item)
+                .expect("bad things happened");
+            return Err(ClanVaultError::VaultFull);
+        }
+    };
+
+    add_clan_vault_log_entry(
+        clan,
+        &depositor.character_info.name,
+        ClanVaultAction::Deposit,
+        deposit_item,
+    );
+    save_clan_vault_transaction(clan, query_member, depositor, ClanVaultAction::Deposit);
+
+    if let Some(game_client) = depositor.game_client {
+        game_client
+            .server_message_tx
+            .send(ServerMessage::UpdateInventory {
+                items: vec![(item_slot, depositor.inventory.get_item(item_slot).cloned())],
+                money: None,
+            })
+            .ok();
+    }
+
+    send_clan_message(
+        clan,
+        query_member,
+        ServerMessage::ClanVaultItems {
+            items: vec![(vault_slot, clan.vault.slots[vault_slot].clone())],
+        },
+    );
+    Ok(())
+}
+
+fn handle_clan_vault_withdraw(
+    clan: &mut Clan,
+    query_member: &Query<MemberQuery>,
+    withdrawer: &mut CreatorQueryItem,
+    vault_slot_index: usize,
+    item: &Item,
+) -> Result<(), ClanVaultError> {
+    check_vault_permission(clan, withdrawer.entity, ClanPermission::VaultWithdraw)?;
+
+    let vault_slot = clan
+        .vault
+        .slots
+        .get_mut(vault_slot_index)
+        .ok_or(ClanVaultError::Failed)?;
+    if !vault_slot
+        .as_ref()
+        .map_or(false, |vault_item| vault_item.is_same_item(item))
+    {
+        return Err(ClanVaultError::Failed);
+    }
+
+    let withdraw_item = vault_slot
+        .try_take_quantity(item.get_quantity())
+        .ok_or(ClanVaultError::Failed)?;
+    let inventory_item_slot = match withdrawer.inventory.try_add_item(withdraw_item.clone()) {
+        Ok((inventory_item_slot, _)) => inventory_item_slot,
+        Err(withdraw_item) => {
+            vault_slot
+                .try_stack_with_item(withdraw_item)
+                .expect("bad things happened");
+            return Err(ClanVaultError::InventoryFull);
+        }
+    };
+
+    add_clan_vault_log_entry(
+        clan,
+        &withdrawer.character_info.name,
+        ClanVaultAction::Withdraw,
+        withdraw_item,
+    );
+    save_clan_vault_transaction(clan, query_member, withdrawer, ClanVaultAction::Withdraw);
+
+    if let Some(game_client) = withdrawer.game_client {
+        game_client
+            .server_message_tx
+            .send(ServerMessage::UpdateInventory {
+                items: vec![(
+                    inventory_item_slot,
+                    withdrawer.inventory.get_item(inventory_item_slot).cloned(),
+                )],
+                money: None,
+            })
+            .ok();
+    }
+
+    send_clan_message(
+        clan,
+        query_member,
+        ServerMessage::ClanVaultItems {
+            items: vec![(vault_slot_index, clan.vault.slots[vault_slot_index].clone())],
+        },
+    );
+    Ok(())
+}
+
 pub fn clan_system(
     mut commands: Commands,
     mut clan_events: EventReader<ClanEvent>,
     query_member_connected: Query<MemberQuery, Changed<ClanMembership>>,
+    query_member_join_zone: Query<
+        MemberQuery,
+        (
+            With<ClientEntity>,
+            Or<(Changed<ClanMembership>, Added<ClientEntity>)>,
+        ),
+    >,
     query_member: Query<MemberQuery>,
     mut query_creator: Query<CreatorQuery>,
     mut query_clans: Query<&mut Clan>,
     mut server_messages: ResMut<ServerMessages>,
+    game_config: Res<GameConfig>,
+    time: Res<Time>,
 ) {
+    let now = time.elapsed();
+    let invite_timeout = Duration::from_secs(game_config.clan_invite_timeout_secs);
+
+    // Discard invites which were not replied to in time, or whose invitee has logged out
+    for mut clan in query_clans.iter_mut() {
+        let is_expired = |invite: &ClanPendingInvite| {
+            now >= invite.expire_time || !query_member.contains(invite.entity)
+        };
+        if clan.pending_invites.iter().any(is_expired) {
+            clan.pending_invites.retain(|invite| !is_expired(invite));
+        }
+    }
+
     for event in clan_events.iter() {
         match event {
             ClanEvent::Create {
@@ -165,18 +774,25 @@ pub fn clan_system(
                     entity: *creator_entity,
                     position: ClanMemberPosition::Master,
                     contribution: ClanPoints(0),
+                    stats: ClanMemberStats::default(),
                 }];
                 let clan_entity = commands
                     .spawn(Clan {
                         unique_id,
                         name: clan_storage.name.clone(),
                         description: clan_storage.description,
+                        notice: clan_storage.notice,
                         mark: clan_storage.mark,
                         money: clan_storage.money,
                         points: clan_storage.points,
                         level: clan_storage.level,
                         skills: clan_storage.skills,
+                        vault: clan_storage.vault,
+                        permissions: clan_storage.permissions,
+                        recruitment: clan_storage.recruitment,
+                        applications: clan_storage.applications,
                         members,
+                        pending_invites: Vec::new(),
                     })
                     .id();
 
@@ -210,6 +826,7 @@ pub fn clan_system(
                         let &mut ClanMember::Online {
                             position,
                             contribution,
+                            stats,
                             ..
                         } = clan_member
                         else {
@@ -219,6 +836,7 @@ pub fn clan_system(
                             name: name.clone(),
                             position,
                             contribution,
+                            stats,
                             level,
                             job,
                         };
@@ -261,6 +879,7 @@ pub fn clan_system(
                                     entity: member_entity,
                                     position,
                                     contribution,
+                                    ..
                                 } => {
                                     if let Ok(member) = query_member.get(member_entity) {
                                         members.push(ClanMemberInfo {
@@ -279,6 +898,7 @@ pub fn clan_system(
                                     contribution,
                                     level,
                                     job,
+                                    ..
                                 } => {
                                     members.push(ClanMemberInfo {
                                         name: name.clone(),
@@ -301,6 +921,239 @@ pub fn clan_system(
                     }
                 }
             }
+            &ClanEvent::Invite {
+                inviter: inviter_entity,
+                ref name,
+            } => {
+                let Some(clan_entity) = query_member
+                    .get(inviter_entity)
+                    .ok()
+                    .and_then(|inviter| inviter.clan_membership.clan())
+                else {
+                    continue;
+                };
+                let Ok(mut clan) = query_clans.get_mut(clan_entity) else {
+                    continue;
+                };
+
+                if let Err(error) = handle_clan_invite(
+                    &mut clan,
+                    &query_member,
+                    inviter_entity,
+                    name,
+                    now + invite_timeout,
+                ) {
+                    send_member_message(
+                        &query_member,
+                        inviter_entity,
+                        ServerMessage::ClanInviteError { error },
+                    );
+                }
+            }
+            &ClanEvent::InviteReply {
+                invitee: invitee_entity,
+                ref inviter_name,
+                accepted,
+            } => {
+                let Some(inviter) = query_member
+                    .iter()
+                    .find(|member| member.character_info.name == *inviter_name)
+                else {
+                    continue;
+                };
+                let inviter_entity = inviter.entity;
+                let Some(clan_entity) = inviter.clan_membership.clan() else {
+                    continue;
+                };
+                let Ok(mut clan) = query_clans.get_mut(clan_entity) else {
+                    continue;
+                };
+
+                // Ensure we actually received an invite and are not trying to hack our way into a clan
+                let Some(invite_index) = clan
+                    .pending_invites
+                    .iter()
+                    .position(|invite| invite.entity == invitee_entity)
+                else {
+                    continue;
+                };
+                clan.pending_invites.remove(invite_index);
+
+                if !accepted {
+                    if let Ok(invitee) = query_member.get(invitee_entity) {
+                        send_member_message(
+                            &query_member,
+                            inviter_entity,
+                            ServerMessage::ClanInviteRejected {
+                                name: invitee.character_info.name.clone(),
+                            },
+                        );
+                    }
+                    continue;
+                }
+
+                if let Err(error) = handle_clan_invite_accept(
+                    &mut commands,
+                    &mut server_messages,
+                    clan_entity,
+                    &mut clan,
+                    &query_member,
+                    invitee_entity,
+                ) {
+                    send_member_message(
+                        &query_member,
+                        inviter_entity,
+                        ServerMessage::ClanInviteError { error },
+                    );
+                }
+            }
+            &ClanEvent::Kick {
+                requester: requester_entity,
+                ref name,
+            } => {
+                let Some(clan_entity) = query_member
+                    .get(requester_entity)
+                    .ok()
+                    .and_then(|requester| requester.clan_membership.clan())
+                else {
+                    continue;
+                };
+                let Ok(mut clan) = query_clans.get_mut(clan_entity) else {
+                    continue;
+                };
+                let Some(requester_position) = clan
+                    .find_online_member(requester_entity)
+                    .map(|member| member.position())
+                else {
+                    continue;
+                };
+                let Some(member_index) = find_member_index(&clan, &query_member, name) else {
+                    continue;
+                };
+
+                if !clan
+                    .permissions
+                    .is_allowed(requester_position, ClanPermission::Kick)
+                    || clan.members[member_index].position() >= requester_position
+                {
+                    continue;
+                }
+
+                // Send before removing so the kicked member is also told
+                send_clan_message(
+                    &clan,
+                    &query_member,
+                    ServerMessage::ClanMemberKicked { name: name.clone() },
+                );
+                remove_clan_member(
+                    &mut commands,
+                    &mut server_messages,
+                    &mut clan,
+                    &query_member,
+                    member_index,
+                );
+                save_clan(&clan, &query_member);
+            }
+            &ClanEvent::Promote {
+                requester: requester_entity,
+                ref name,
+            }
+            | &ClanEvent::Demote {
+                requester: requester_entity,
+                ref name,
+            } => {
+                let Some(clan_entity) = query_member
+                    .get(requester_entity)
+                    .ok()
+                    .and_then(|requester| requester.clan_membership.clan())
+                else {
+                    continue;
+                };
+                let Ok(mut clan) = query_clans.get_mut(clan_entity) else {
+                    continue;
+                };
+
+                handle_clan_change_position(
+                    &mut server_messages,
+                    &mut clan,
+                    &query_member,
+                    requester_entity,
+                    name,
+                    matches!(event, ClanEvent::Promote { .. }),
+                );
+            }
+            &ClanEvent::SetNotice {
+                requester: requester_entity,
+                ref notice,
+            } => {
+                let Some(clan_entity) = query_member
+                    .get(requester_entity)
+                    .ok()
+                    .and_then(|requester| requester.clan_membership.clan())
+                else {
+                    continue;
+                };
+                let Ok(mut clan) = query_clans.get_mut(clan_entity) else {
+                    continue;
+                };
+                if !clan
+                    .find_online_member(requester_entity)
+                    .map_or(false, |member| {
+                        clan.permissions
+                            .is_allowed(member.position(), ClanPermission::SetNotice)
+                    })
+                {
+                    continue;
+                }
+
+                clan.notice = notice.clone();
+                send_clan_message(
+                    &clan,
+                    &query_member,
+                    ServerMessage::ClanNotice {
+                        notice: notice.clone(),
+                    },
+                );
+                save_clan(&clan, &query_member);
+            }
+            &ClanEvent::Quit { entity } => {
+                let Ok(member) = query_member.get(entity) else {
+                    continue;
+                };
+                let Some(clan_entity) = member.clan_membership.clan() else {
+                    continue;
+                };
+                let Ok(mut clan) = query_clans.get_mut(clan_entity) else {
+                    continue;
+                };
+                let Some(member_index) = clan.find_online_member_index(entity) else {
+                    continue;
+                };
+
+                // The clan master cannot leave their own clan
+                if matches!(
+                    clan.members[member_index].position(),
+                    ClanMemberPosition::Master
+                ) {
+                    continue;
+                }
+
+                send_clan_message(
+                    &clan,
+                    &query_member,
+                    ServerMessage::ClanMemberQuit {
+                        name: member.character_info.name.clone(),
+                    },
+                );
+                remove_clan_member(
+                    &mut commands,
+                    &mut server_messages,
+                    &mut clan,
+                    &query_member,
+                    member_index,
+                );
+                save_clan(&clan, &query_member);
+            }
             &ClanEvent::AddLevel { clan_entity, level } => {
                 if let Ok(mut clan) = query_clans.get_mut(clan_entity) {
                     if let Some(level) = clan
@@ -338,12 +1191,22 @@ pub fn clan_system(
             &ClanEvent::AddPoints {
                 clan_entity,
                 points,
+                contributor,
             } => {
                 if let Ok(mut clan) = query_clans.get_mut(clan_entity) {
-                    if let Some(points) = clan.points.0.checked_add_signed(points) {
-                        clan.points = ClanPoints(points);
+                    if let Some(new_points) = clan.points.0.checked_add_signed(points) {
+                        clan.points = ClanPoints(new_points);
                         send_update_clan_info(&clan, &query_member);
                     }
+
+                    // Clan points earned by a member count towards their contribution
+                    if let Some(ClanMember::Online { contribution, .. }) = contributor
+                        .filter(|_| points > 0)
+                        .and_then(|contributor| clan.find_online_member_mut(contributor))
+                    {
+                        contribution.0 = contribution.0.saturating_add(points as u64);
+                        save_clan(&clan, &query_member);
+                    }
                 }
             }
             &ClanEvent::SetPoints {
@@ -355,6 +1218,19 @@ pub fn clan_system(
                     send_update_clan_info(&clan, &query_member);
                 }
             }
+            &ClanEvent::QuestCompleted {
+                clan_entity,
+                entity,
+            } => {
+                if let Ok(mut clan) = query_clans.get_mut(clan_entity) {
+                    if let Some(ClanMember::Online { stats, .. }) =
+                        clan.find_online_member_mut(entity)
+                    {
+                        stats.completed_quests += 1;
+                        save_clan(&clan, &query_member);
+                    }
+                }
+            }
             &ClanEvent::AddSkill {
                 clan_entity,
                 skill_id,
@@ -377,6 +1253,424 @@ pub fn clan_system(
                     }
                 }
             }
+            &ClanEvent::VaultOpen { entity } | &ClanEvent::VaultGetLog { entity } => {
+                let Ok(member) = query_member.get(entity) else {
+                    continue;
+                };
+                let Some(clan) = member
+                    .clan_membership
+                    .and_then(|clan_entity| query_clans.get(clan_entity).ok())
+                else {
+                    continue;
+                };
+
+                let message = if matches!(event, ClanEvent::VaultOpen { .. }) {
+                    // Any member who can deposit or withdraw is able to see the vault
+                    check_vault_permission(clan, entity, ClanPermission::VaultDeposit)
+                        .or_else(|_| {
+                            check_vault_permission(clan, entity, ClanPermission::VaultWithdraw)
+                        })
+                        .map(|_| ServerMessage::ClanVaultItems {
+                            items: clan
+                                .vault
+                                .slots
+                                .iter()
+                                .enumerate()
+                                .filter(|(_, item)| item.is_some())
+                                .map(|(index, item)| (index, item.clone()))
+                                .collect(),
+                        })
+                } else {
+                    check_vault_permission(clan, entity, ClanPermission::VaultViewLog).map(|_| {
+                        ServerMessage::ClanVaultLog {
+                            entries: clan.vault.log.clone(),
+                        }
+                    })
+                };
+
+                send_member_message(
+                    &query_member,
+                    entity,
+                    message.unwrap_or_else(|error| ServerMessage::ClanVaultError { error }),
+                );
+            }
+            &ClanEvent::VaultDeposit {
+                entity,
+                item_slot,
+                ref item,
+            } => {
+                let Ok(mut depositor) = query_creator.get_mut(entity) else {
+                    continue;
+                };
+                let Some(mut clan) = depositor
+                    .clan_membership
+                    .and_then(|clan_entity| query_clans.get_mut(clan_entity).ok())
+                else {
+                    continue;
+                };
+
+                if let Err(error) = handle_clan_vault_deposit(
+                    &mut clan,
+                    &query_member,
+                    &game_config,
+                    &mut depositor,
+                    item_slot,
+                    item,
+                ) {
+                    send_member_message(
+                        &query_member,
+                        entity,
+                        ServerMessage::ClanVaultError { error },
+                    );
+                }
+            }
+            &ClanEvent::VaultWithdraw {
+                entity,
+                vault_slot,
+                ref item,
+            } => {
+                let Ok(mut withdrawer) = query_creator.get_mut(entity) else {
+                    continue;
+                };
+                let Some(mut clan) = withdrawer
+                    .clan_membership
+                    .and_then(|clan_entity| query_clans.get_mut(clan_entity).ok())
+                else {
+                    continue;
+                };
+
+                if let Err(error) = handle_clan_vault_withdraw(
+                    &mut clan,
+                    &query_member,
+                    &mut withdrawer,
+                    vault_slot,
+                    item,
+                ) {
+                    send_member_message(
+                        &query_member,
+                        entity,
+                        ServerMessage::ClanVaultError { error },
+                    );
+                }
+            }
+            &ClanEvent::DonateMoney { entity, money } => {
+                let Ok(mut donor) = query_creator.get_mut(entity) else {
+                    continue;
+                };
+                let Some(mut clan) = donor
+                    .clan_membership
+                    .and_then(|clan_entity| query_clans.get_mut(clan_entity).ok())
+                else {
+                    continue;
+                };
+                if money.0 <= 0 || clan.find_online_member(entity).is_none() {
+                    continue;
+                }
+
+                let Ok(money) = donor.inventory.try_take_money(money) else {
+                    continue;
+                };
+                let Some(clan_money) = clan.money.0.checked_add(money.0) else {
+                    donor.inventory.try_add_money(money).ok();
+                    continue;
+                };
+                clan.money = Money(clan_money);
+
+                if let Some(ClanMember::Online { stats, .. }) = clan.find_online_member_mut(entity)
+                {
+                    stats.donated_money.0 = stats.donated_money.0.saturating_add(money.0);
+                }
+
+                let mut journal = JournalStorage::new();
+                journal.add_inventory(&donor.character_info.name, &donor.inventory);
+                journal.commit();
+                save_clan(&clan, &query_member);
+
+                if let Some(game_client) = donor.game_client {
+                    game_client
+                        .server_message_tx
+                        .send(ServerMessage::UpdateMoney {
+                            money: donor.inventory.money,
+                        })
+                        .ok();
+                }
+                send_update_clan_info(&clan, &query_member);
+            }
+            &ClanEvent::GetPermissions { entity } => {
+                let Some(clan) = query_member
+                    .get(entity)
+                    .ok()
+                    .and_then(|member| member.clan_membership.clan())
+                    .and_then(|clan_entity| query_clans.get(clan_entity).ok())
+                else {
+                    continue;
+                };
+
+                send_member_message(
+                    &query_member,
+                    entity,
+                    ServerMessage::ClanPermissions {
+                        permissions: clan.permissions.clone(),
+                    },
+                );
+            }
+            &ClanEvent::SetPermissions {
+                requester: requester_entity,
+                ref permissions,
+            } => {
+                let Some(clan_entity) = query_member
+                    .get(requester_entity)
+                    .ok()
+                    .and_then(|requester| requester.clan_membership.clan())
+                else {
+                    continue;
+                };
+                let Ok(mut clan) = query_clans.get_mut(clan_entity) else {
+                    continue;
+                };
+
+                // Only the clan master can change the permissions of each position
+                if !clan
+                    .find_online_member(requester_entity)
+                    .map_or(false, |member| {
+                        matches!(member.position(), ClanMemberPosition::Master)
+                    })
+                {
+                    continue;
+                }
+
+                clan.permissions = permissions.clone();
+                send_clan_message(
+                    &clan,
+                    &query_member,
+                    ServerMessage::ClanPermissions {
+                        permissions: permissions.clone(),
+                    },
+                );
+                save_clan(&clan, &query_member);
+            }
+            &ClanEvent::SetRecruitment {
+                requester: requester_entity,
+                ref notice,
+            } => {
+                let Some(clan_entity) = query_member
+                    .get(requester_entity)
+                    .ok()
+                    .and_then(|requester| requester.clan_membership.clan())
+                else {
+                    continue;
+                };
+                let Ok(mut clan) = query_clans.get_mut(clan_entity) else {
+                    continue;
+                };
+
+                if let Err(error) =
+                    check_application_permission(&clan, requester_entity, ClanPermission::SetNotice)
+                {
+                    send_member_message(
+                        &query_member,
+                        requester_entity,
+                        ServerMessage::ClanApplicationError { error },
+                    );
+                    continue;
+                }
+
+                if notice.len() > CLAN_RECRUITMENT_MAX_LENGTH {
+                    send_member_message(
+                        &query_member,
+                        requester_entity,
+                        ServerMessage::ClanApplicationError {
+                            error: ClanApplicationError::TooLong,
+                        },
+                    );
+                    continue;
+                }
+
+                clan.recruitment = notice.clone();
+                save_clan(&clan, &query_member);
+            }
+            &ClanEvent::SearchRecruitment { entity, ref query } => {
+                let query = query.to_lowercase();
+                let listings = query_clans
+                    .iter()
+                    .filter(|clan| !clan.recruitment.is_empty())
+                    .filter(|clan| {
+                        clan.name.to_lowercase().contains(&query)
+                            || clan.recruitment.to_lowercase().contains(&query)
+                    })
+                    .take(CLAN_RECRUITMENT_MAX_RESULTS)
+                    .map(|clan| ClanRecruitmentListing {
+                        name: clan.name.clone(),
+                        level: clan.level,
+                        member_count: clan.members.len(),
+                        notice: clan.recruitment.clone(),
+                    })
+                    .collect();
+
+                send_member_message(
+                    &query_member,
+                    entity,
+                    ServerMessage::ClanRecruitmentList { listings },
+                );
+            }
+            &ClanEvent::Apply {
+                entity,
+                ref clan_name,
+                ref message,
+            } => {
+                let Ok(applicant) = query_member.get(entity) else {
+                    continue;
+                };
+
+                let result = if applicant.clan_membership.is_some() {
+                    Err(ClanApplicationError::AlreadyInClan)
+                } else if let Some(mut clan) =
+                    query_clans.iter_mut().find(|clan| clan.name == *clan_name)
+                {
+                    handle_clan_apply(&mut clan, &query_member, &applicant, message)
+                } else {
+                    Err(ClanApplicationError::Failed)
+                };
+
+                send_member_message(
+                    &query_member,
+                    entity,
+                    match result {
+                        Ok(_) => ServerMessage::ClanApplicationSent {
+                            clan_name: clan_name.clone(),
+                        },
+                        Err(error) => ServerMessage::ClanApplicationError { error },
+                    },
+                );
+            }
+            &ClanEvent::GetApplications { entity } => {
+                let Some(clan) = query_member
+                    .get(entity)
+                    .ok()
+                    .and_then(|member| member.clan_membership.clan())
+                    .and_then(|clan_entity| query_clans.get(clan_entity).ok())
+                else {
+                    continue;
+                };
+
+                send_member_message(
+                    &query_member,
+                    entity,
+                    match check_application_permission(&clan, entity, ClanPermission::Invite) {
+                        Ok(_) => ServerMessage::ClanApplications {
+                            applications: clan.applications.clone(),
+                        },
+                        Err(error) => ServerMessage::ClanApplicationError { error },
+                    },
+                );
+            }
+            &ClanEvent::ApplicationReply {
+                requester: requester_entity,
+                ref name,
+                accepted,
+            } => {
+                let Some(clan_entity) = query_member
+                    .get(requester_entity)
+                    .ok()
+                    .and_then(|requester| requester.clan_membership.clan())
+                else {
+                    continue;
+                };
+                let Ok(mut clan) = query_clans.get_mut(clan_entity) else {
+                    continue;
+                };
+
+                if let Err(error) =
+                    check_application_permission(&clan, requester_entity, ClanPermission::Invite)
+                {
+                    send_member_message(
+                        &query_member,
+                        requester_entity,
+                        ServerMessage::ClanApplicationError { error },
+                    );
+                    continue;
+                }
+
+                let Some(application_index) = clan
+                    .applications
+                    .iter()
+                    .position(|application| application.name == *name)
+                else {
+                    continue;
+                };
+                let applicant = query_member
+                    .iter()
+                    .find(|member| member.character_info.name == *name);
+
+                if !accepted {
+                    clan.applications.remove(application_index);
+                    if let Some(applicant) = applicant {
+                        send_member_message(
+                            &query_member,
+                            applicant.entity,
+                            ServerMessage::ClanApplicationRejected {
+                                clan_name: clan.name.clone(),
+                            },
+                        );
+                    }
+                    save_clan(&clan, &query_member);
+                    continue;
+                }
+
+                // Joining a clan requires the applicant to be online, so keep the application
+                // until they are
+                let Some(applicant) = applicant else {
+                    send_member_message(
+                        &query_member,
+                        requester_entity,
+                        ServerMessage::ClanApplicationError {
+                            error: ClanApplicationError::ApplicantOffline,
+                        },
+                    );
+                    continue;
+                };
+
+                clan.applications.remove(application_index);
+                if let Err(error) = handle_clan_invite_accept(
+                    &mut commands,
+                    &mut server_messages,
+                    clan_entity,
+                    &mut clan,
+                    &query_member,
+                    applicant.entity,
+                ) {
+                    save_clan(&clan, &query_member);
+                    send_member_message(
+                        &query_member,
+                        requester_entity,
+                        ServerMessage::ClanApplicationError {
+                            error: match error {
+                                ClanInviteError::Failed => ClanApplicationError::Failed,
+                                ClanInviteError::NoPermission => ClanApplicationError::NoPermission,
+                                ClanInviteError::AlreadyInClan => {
+                                    ClanApplicationError::AlreadyInClan
+                                }
+                                ClanInviteError::ClanFull => ClanApplicationError::ClanFull,
+                            },
+                        },
+                    );
+                    continue;
+                }
+
+                // The applicant has joined a clan, so their other applications are no longer needed
+                for mut other_clan in query_clans.iter_mut() {
+                    if other_clan
+                        .applications
+                        .iter()
+                        .any(|application| application.name == *name)
+                    {
+                        other_clan
+                            .applications
+                            .retain(|application| application.name != *name);
+                        save_clan(&other_clan, &query_member);
+                    }
+                }
+            }
         }
     }
 
@@ -388,31 +1682,8 @@ pub fn clan_system(
             continue;
         };
 
-        let Some(&ClanMember::Online {
-            position: connected_member_position,
-            contribution: connected_member_contribution,
-            ..
-        }) = clan.find_online_member(connected_member.entity)
-        else {
+        if clan.find_online_member(connected_member.entity).is_none() {
             continue;
-        };
-
-        if let Some(game_client) = connected_member.game_client.as_ref() {
-            game_client
-                .server_message_tx
-                .send(ServerMessage::ClanInfo {
-                    id: clan.unique_id,
-                    name: clan.name.clone(),
-                    description: clan.description.clone(),
-                    mark: clan.mark,
-                    level: clan.level,
-                    points: clan.points,
-                    money: clan.money,
-                    skills: clan.skills.clone(),
-                    position: connected_member_position,
-                    contribution: connected_member_contribution,
-                })
-                .ok();
         }
 
         // Send message to other clan members that we have connected
@@ -442,4 +1713,52 @@ pub fn clan_system(
             }
         }
     }
+
+    // Send clan info whenever a member joins a zone or their clan changes
+    for member in query_member_join_zone.iter() {
+        let Some(clan) = member
+            .clan_membership
+            .and_then(|clan_entity| query_clans.get(clan_entity).ok())
+        else {
+            continue;
+        };
+
+        let Some(&ClanMember::Online {
+            position,
+            contribution,
+            ..
+        }) = clan.find_online_member(member.entity)
+        else {
+            continue;
+        };
+
+        let Some(game_client) = member.game_client else {
+            continue;
+        };
+
+        game_client
+            .server_message_tx
+            .send(ServerMessage::ClanInfo {
+                id: clan.unique_id,
+                name: clan.name.clone(),
+                description: clan.description.clone(),
+                mark: clan.mark,
+                level: clan.level,
+                points: clan.points,
+                money: clan.money,
+                skills: clan.skills.clone(),
+                position,
+                contribution,
+            })
+            .ok();
+
+        if !clan.notice.is_empty() {
+            game_client
+                .server_message_tx
+                .send(ServerMessage::ClanNotice {
+                    notice: clan.notice.clone(),
+                })
+                .ok();
+        }
+    }
 }
@@ -1,7 +1,8 @@
 use bevy::{
     ecs::{
-        prelude::{Query, Res, ResMut},
+        prelude::{Entity, Query, Res, ResMut, With},
         query::WorldQuery,
+        system::SystemParam,
     },
     time::Time,
 };
@@ -11,14 +12,14 @@ use rose_game_common::messages::server::CharacterClanMembership;
 
 use crate::game::{
     components::{
-        AbilityValues, CharacterInfo, Clan, ClanMembership, ClientEntity, ClientEntityId,
-        ClientEntitySector, ClientEntityType, ClientEntityVisibility, Command,
+        AbilityValues, CharacterInfo, CharacterTitles, Clan, ClanMembership, ClientEntity,
+        ClientEntityId, ClientEntitySector, ClientEntityType, ClientEntityVisibility, Command,
         CommandCastSkillTarget, CommandData, EntityExpireTime, Equipment, GameClient, HealthPoints,
-        ItemDrop, Level, MoveMode, MoveSpeed, Npc, NpcStandingDirection, Owner, PersonalStore,
-        Position, StatusEffects, Team,
+        ItemDrop, Level, MoveMode, MoveSpeed, Npc, NpcStandingDirection, ObjectVariables, Owner,
+        PersonalStore, Position, Spectator, StatusEffects, Team,
     },
     messages::server::{ServerMessage, SpawnCommandState, SpawnEntityCharacter},
-    resources::ClientEntityList,
+    resources::{ClientEntityList, GameConfig},
 };
 
 #[derive(WorldQuery)]
@@ -29,6 +30,7 @@ pub struct GameClientQuery<'w> {
     client_entity_visibility: &'w mut ClientEntityVisibility,
     game_client: &'w GameClient,
     position: &'w Position,
+    spectator: Option<&'w Spectator>,
 }
 
 #[derive(WorldQuery)]
@@ -47,6 +49,7 @@ pub struct CharacterQuery<'w> {
     team: &'w Team,
     personal_store: Option<&'w PersonalStore>,
     clan_membership: &'w ClanMembership,
+    titles: Option<&'w CharacterTitles>,
 }
 
 #[derive(WorldQuery)]
@@ -78,6 +81,7 @@ pub struct NpcQuery<'w> {
     command: &'w Command,
     move_mode: &'w MoveMode,
     status_effects: &'w StatusEffects,
+    object_variables: &'w ObjectVariables,
 }
 
 #[derive(WorldQuery)]
@@ -89,7 +93,9 @@ pub struct TargetQuery<'w> {
 fn spawn_command_state(command: &Command, query_target: &Query<TargetQuery>) -> SpawnCommandState {
     match command.command {
         CommandData::Die { .. } => SpawnCommandState::Die,
-        CommandData::Stop { .. } | CommandData::Standing => SpawnCommandState::Stop,
+        CommandData::Stop { .. } | CommandData::Standing | CommandData::Gather { .. } => {
+            SpawnCommandState::Stop
+        }
         CommandData::Move {
             destination,
             target: target_entity,
@@ -150,15 +156,103 @@ fn spawn_command_state(command: &Command, query_target: &Query<TargetQuery>) ->
     }
 }
 
+#[derive(SystemParam)]
+pub struct SpawnMessageQueries<'w, 's> {
+    characters_query: Query<'w, 's, CharacterQuery<'static>>,
+    monsters_query: Query<'w, 's, MonsterQuery<'static>>,
+    npcs_query: Query<'w, 's, NpcQuery<'static>>,
+    clan_query: Query<'w, 's, &'static Clan>,
+    query_target: Query<'w, 's, TargetQuery<'static>>,
+    game_config: Res<'w, GameConfig>,
+}
+
+fn build_spawn_message(
+    queries: &SpawnMessageQueries,
+    spawn_entity: Entity,
+    spawn_client_entity: &ClientEntity,
+) -> Option<ServerMessage> {
+    match spawn_client_entity.entity_type {
+        ClientEntityType::Character => {
+            let character = queries.characters_query.get(spawn_entity).ok()?;
+            Some(ServerMessage::SpawnEntityCharacter {
+                data: Box::new(SpawnEntityCharacter {
+                    entity_id: spawn_client_entity.id,
+                    character_info: character.character_info.clone(),
+                    position: character.position.position,
+                    health: *character.health_points,
+                    team: character.team.clone(),
+                    equipment: character.equipment.clone(),
+                    level: *character.level,
+                    move_mode: *character.move_mode,
+                    move_speed: *character.move_speed,
+                    passive_attack_speed: character.ability_values.passive_attack_speed,
+                    status_effects: character.status_effects.active.clone(),
+                    spawn_command_state: spawn_command_state(
+                        character.command,
+                        &queries.query_target,
+                    ),
+                    personal_store_info: character
+                        .personal_store
+                        .map(|personal_store| (personal_store.skin, personal_store.title.clone())),
+                    clan_membership: character.clan_membership.and_then(|clan_entity| {
+                        let clan = queries.clan_query.get(clan_entity).ok()?;
+                        Some(CharacterClanMembership {
+                            clan_unique_id: clan.unique_id,
+                            mark: clan.mark,
+                            level: clan.level,
+                            name: clan.name.clone(),
+                            position: clan
+                                .find_online_member(spawn_entity)
+                                .map_or(ClanMemberPosition::Junior, |member| member.position()),
+                        })
+                    }),
+                    title: character
+                        .titles
+                        .and_then(|titles| titles.active)
+                        .and_then(|id| queries.game_config.get_title(id))
+                        .map(|title| title.name.clone()),
+                }),
+            })
+        }
+        ClientEntityType::Monster => {
+            let monster = queries.monsters_query.get(spawn_entity).ok()?;
+            Some(ServerMessage::SpawnEntityMonster {
+                entity_id: spawn_client_entity.id,
+                npc: monster.npc.clone(),
+                position: monster.position.position,
+                team: monster.team.clone(),
+                health: *monster.health,
+                spawn_command_state: spawn_command_state(monster.command, &queries.query_target),
+                move_mode: *monster.move_mode,
+                status_effects: monster.status_effects.active.clone(),
+            })
+        }
+        ClientEntityType::Npc => {
+            let npc = queries.npcs_query.get(spawn_entity).ok()?;
+            Some(ServerMessage::SpawnEntityNpc {
+                entity_id: spawn_client_entity.id,
+                npc: npc.npc.clone(),
+                direction: npc.direction.direction,
+                position: npc.position.position,
+                team: npc.team.clone(),
+                health: *npc.health,
+                spawn_command_state: spawn_command_state(npc.command, &queries.query_target),
+                move_mode: *npc.move_mode,
+                status_effects: npc.status_effects.active.clone(),
+                event_status: npc.object_variables.event_status(),
+            })
+        }
+        // Item drops are sent with their remaining time by the visibility system
+        ClientEntityType::ItemDrop => None,
+    }
+}
+
 pub fn client_entity_visibility_system(
     mut game_clients_query: Query<GameClientQuery>,
     entity_id_query: Query<&ClientEntity>,
-    characters_query: Query<CharacterQuery>,
     item_drop_query: Query<ItemDropQuery>,
-    monsters_query: Query<MonsterQuery>,
-    npcs_query: Query<NpcQuery>,
-    clan_query: Query<&Clan>,
-    query_target: Query<TargetQuery>,
+    spawn_message_queries: SpawnMessageQueries,
+    spectator_query: Query<&ClientEntity, With<Spectator>>,
     mut client_entity_list: ResMut<ClientEntityList>,
     time: Res<Time>,
 ) {
@@ -166,11 +260,20 @@ pub fn client_entity_visibility_system(
     for mut game_client in game_clients_query.iter_mut() {
         if let Some(client_entity_zone) = client_entity_list.get_zone(game_client.position.zone_id)
         {
-            let sector_visible_entities = client_entity_zone
+            let mut sector_visible_entities = *client_entity_zone
                 .get_sector_visible_entities(game_client.client_entity_sector.sector);
 
+            // Spectators are only visible to other spectators
+            if game_client.spectator.is_none() {
+                for spectator_client_entity in spectator_query.iter() {
+                    if spectator_client_entity.zone_id == game_client.position.zone_id {
+                        sector_visible_entities.set(spectator_client_entity.id.0, false);
+                    }
+                }
+            }
+
             let mut visibility_difference =
-                game_client.client_entity_visibility.entities ^ *sector_visible_entities;
+                game_client.client_entity_visibility.entities ^ sector_visible_entities;
 
             // Ignore self
             visibility_difference.set(game_client.client_entity.id.0, false);
@@ -184,130 +287,37 @@ pub fn client_entity_visibility_system(
                 } else if let Some((spawn_entity, spawn_client_entity, _)) =
                     client_entity_zone.get_entity(ClientEntityId(index))
                 {
-                    match spawn_client_entity.entity_type {
-                        ClientEntityType::Character => {
-                            if let Ok(character) = characters_query.get(*spawn_entity) {
-                                game_client
-                                    .game_client
-                                    .server_message_tx
-                                    .send(ServerMessage::SpawnEntityCharacter {
-                                        data: Box::new(SpawnEntityCharacter {
-                                            entity_id: spawn_client_entity.id,
-                                            character_info: character.character_info.clone(),
-                                            position: character.position.position,
-                                            health: *character.health_points,
-                                            team: character.team.clone(),
-                                            equipment: character.equipment.clone(),
-                                            level: *character.level,
-                                            move_mode: *character.move_mode,
-                                            move_speed: *character.move_speed,
-                                            passive_attack_speed: character
-                                                .ability_values
-                                                .passive_attack_speed,
-                                            status_effects: character.status_effects.active.clone(),
-                                            spawn_command_state: spawn_command_state(
-                                                character.command,
-                                                &query_target,
-                                            ),
-                                            personal_store_info: character.personal_store.map(
-                                                |personal_store| {
-                                                    (
-                                                        personal_store.skin,
-                                                        personal_store.title.clone(),
-                                                    )
-                                                },
-                                            ),
-                                            clan_membership: character.clan_membership.and_then(
-                                                |clan_entity| {
-                                                    if let Ok(clan) = clan_query.get(clan_entity) {
-                                                        Some(CharacterClanMembership {
-                                                            clan_unique_id: clan.unique_id,
-                                                            mark: clan.mark,
-                                                            level: clan.level,
-                                                            name: clan.name.clone(),
-                                                            position: clan
-                                                                .find_online_member(*spawn_entity)
-                                                                .map_or(
-                                                                    ClanMemberPosition::Junior,
-                                                                    |member| member.position(),
-                                                                ),
-                                                        })
-                                                    } else {
-                                                        None
-                                                    }
-                                                },
-                                            ),
-                                        }),
-                                    })
-                                    .ok();
-                            }
-                        }
-                        ClientEntityType::ItemDrop => {
-                            if let Ok(item_drop) = item_drop_query.get(*spawn_entity) {
-                                if let Some(dropped_item) = item_drop.item_drop.item.clone() {
-                                    let owner_entity_id = item_drop
-                                        .owner
-                                        .and_then(|owner| entity_id_query.get(owner.entity).ok())
-                                        .map(|owner_client_entity| owner_client_entity.id);
+                    if matches!(spawn_client_entity.entity_type, ClientEntityType::ItemDrop) {
+                        if let Ok(item_drop) = item_drop_query.get(*spawn_entity) {
+                            if let Some(dropped_item) = item_drop.item_drop.item.clone() {
+                                let owner_entity_id = item_drop
+                                    .owner
+                                    .and_then(|owner| entity_id_query.get(owner.entity).ok())
+                                    .map(|owner_client_entity| owner_client_entity.id);
 
-                                    game_client
-                                        .game_client
-                                        .server_message_tx
-                                        .send(ServerMessage::SpawnEntityItemDrop {
-                                            entity_id: spawn_client_entity.id,
-                                            dropped_item,
-                                            position: item_drop.position.position,
-                                            remaining_time: item_drop.expire_time.when
-                                                - time.last_update().unwrap(),
-                                            owner_entity_id,
-                                        })
-                                        .ok();
-                                }
-                            }
-                        }
-                        ClientEntityType::Monster => {
-                            if let Ok(monster) = monsters_query.get(*spawn_entity) {
-                                game_client
-                                    .game_client
-                                    .server_message_tx
-                                    .send(ServerMessage::SpawnEntityMonster {
-                                        entity_id: spawn_client_entity.id,
-                                        npc: monster.npc.clone(),
-                                        position: monster.position.position,
-                                        team: monster.team.clone(),
-                                        health: *monster.health,
-                                        spawn_command_state: spawn_command_state(
-                                            monster.command,
-                                            &query_target,
-                                        ),
-                                        move_mode: *monster.move_mode,
-                                        status_effects: monster.status_effects.active.clone(),
-                                    })
-                                    .ok();
-                            }
-                        }
-                        ClientEntityType::Npc => {
-                            if let Ok(npc) = npcs_query.get(*spawn_entity) {
                                 game_client
                                     .game_client
                                     .server_message_tx
-                                    .send(ServerMessage::SpawnEntityNpc {
+                                    .send(ServerMessage::SpawnEntityItemDrop {
                                         entity_id: spawn_client_entity.id,
-                                        npc: npc.npc.clone(),
-                                        direction: npc.direction.direction,
-                                        position: npc.position.position,
-                                        team: npc.team.clone(),
-                                        health: *npc.health,
-                                        spawn_command_state: spawn_command_state(
-                                            npc.command,
-                                            &query_target,
-                                        ),
-                                        move_mode: *npc.move_mode,
-                                        status_effects: npc.status_effects.active.clone(),
+                                        dropped_item,
+                                        position: item_drop.position.position,
+                                        remaining_time: item_drop.expire_time.when
+                                            - time.last_update().unwrap(),
+                                        owner_entity_id,
                                     })
                                     .ok();
                             }
                         }
+                        continue;
+                    }
+
+                    if let Some(message) = build_spawn_message(
+                        &spawn_message_queries,
+                        *spawn_entity,
+                        spawn_client_entity,
+                    ) {
+                        game_client.game_client.server_message_tx.send(message).ok();
                     }
                 }
             }
@@ -323,7 +333,7 @@ pub fn client_entity_visibility_system(
             }
 
             // Update visibility
-            game_client.client_entity_visibility.entities = *sector_visible_entities;
+            game_client.client_entity_visibility.entities = sector_visible_entities;
         }
     }
 
@@ -2,40 +2,54 @@ use std::time::{Duration, Instant};
 
 use bevy::{
     ecs::{
-        prelude::{Commands, Entity, EventWriter, Query, Res, ResMut},
+        prelude::{Commands, Entity, EventWriter, Local, Query, Res, ResMut},
         query::WorldQuery,
+        system::SystemParam,
     },
     math::{Vec3, Vec3Swizzles},
     time::Time,
 };
 
 use rose_data::{
-    AmmoIndex, EquipmentIndex, ItemClass, SkillActionMode, SkillId, SkillType, VehiclePartIndex,
+    AmmoIndex, AnimationEventFlags, EquipmentIndex, ItemClass, SkillActionMode, SkillId, SkillType,
+    VehiclePartIndex, ZoneData,
 };
 use rose_game_common::components::{CharacterGender, CharacterInfo};
 
 use crate::game::{
     bundles::{
-        skill_can_target_entity, skill_can_target_position, skill_can_target_self, skill_can_use,
-        SkillCasterBundle, SkillTargetBundle,
+        pvp_check, skill_can_target_entity, skill_can_target_position, skill_can_target_self,
+        skill_can_use, skill_check_combo, PvpCheck, SkillCasterBundle, SkillTargetBundle,
     },
     components::{
         AbilityValues, ClientEntity, ClientEntitySector, ClientEntityType, Command,
-        CommandCastSkillTarget, CommandData, Equipment, GameClient, HealthPoints, ItemDrop,
-        MotionData, MoveMode, MoveSpeed, NextCommand, Npc, Owner, PartyOwner, PersonalStore,
-        Position, Team,
+        CommandCastSkillTarget, CommandData, Equipment, GameClient, GatherNode, HealthPoints,
+        ItemDrop, MotionData, MoveMode, MovePath, MoveSpeed, NextCommand, Npc, Owner, PartyOwner,
+        PersonalStore, Position, Team, Weight, GATHER_NODE_DISTANCE,
     },
     events::{
-        DamageEvent, ItemLifeEvent, PickupItemEvent, SkillEvent, SkillEventTarget, UseAmmoEvent,
+        DamageEvent, GatherEvent, ItemLifeEvent, PickupItemEvent, SkillEvent, SkillEventTarget,
+        UseAmmoEvent,
     },
-    messages::server::ServerMessage,
-    resources::{GameData, ServerMessages},
+    messages::server::{CancelCastingSkillReason, ServerMessage},
+    resources::{GameData, NavigationGrids, NavigationPath, ServerMessages},
 };
 
 const NPC_MOVE_TO_DISTANCE: f32 = 250.0;
 const CHARACTER_MOVE_TO_DISTANCE: f32 = 1000.0;
 const DROPPED_ITEM_MOVE_TO_DISTANCE: f32 = 150.0;
 const DROPPED_ITEM_PICKUP_DISTANCE: f32 = 200.0;
+const LINE_OF_SIGHT_HEIGHT: f32 = 150.0;
+const MAX_ATTACK_HEIGHT_DIFFERENCE: f32 = 1000.0;
+const MOVE_PATH_RECALCULATE_DISTANCE: f32 = 500.0;
+const MOVE_PATH_WAYPOINT_REACHED_DISTANCE: f32 = 10.0;
+
+// The frames where the client shows attack damage
+const ATTACK_HIT_EVENT_FLAGS: AnimationEventFlags = AnimationEventFlags::EFFECT_WEAPON_ATTACK_HIT
+    .union(AnimationEventFlags::EFFECT_WEAPON_FIRE_BULLET)
+    .union(AnimationEventFlags::EFFECT_SKILL_DUMMY_HIT_0)
+    .union(AnimationEventFlags::EFFECT_SKILL_DUMMY_HIT_1)
+    .union(AnimationEventFlags::EFFECT_SKILL_FIRE_DUMMY_BULLET);
 
 #[derive(WorldQuery)]
 #[world_query(mutable)]
@@ -55,8 +69,20 @@ pub struct QueryCommandEntity<'w> {
     character_info: Option<&'w CharacterInfo>,
     equipment: Option<&'w Equipment>,
     game_client: Option<&'w GameClient>,
+    move_path: Option<&'w mut MovePath>,
     npc: Option<&'w Npc>,
     personal_store: Option<&'w PersonalStore>,
+    weight: Option<&'w Weight>,
+}
+
+#[derive(SystemParam)]
+pub struct CommandSystemEvents<'w> {
+    damage_events: EventWriter<'w, DamageEvent>,
+    skill_events: EventWriter<'w, SkillEvent>,
+    pickup_item_events: EventWriter<'w, PickupItemEvent>,
+    item_life_events: EventWriter<'w, ItemLifeEvent>,
+    use_ammo_events: EventWriter<'w, UseAmmoEvent>,
+    gather_events: EventWriter<'w, GatherEvent>,
 }
 
 #[derive(WorldQuery)]
@@ -115,15 +141,29 @@ fn is_valid_move_target(target: &CommandMoveTargetQueryItem, position: &Position
 }
 
 fn is_valid_attack_target(
+    game_data: &GameData,
     target: &CommandAttackTargetQueryItem,
+    client_entity: &ClientEntity,
     position: &Position,
     team: &Team,
 ) -> bool {
-    if target.team.id == team.id || target.team.id == Team::DEFAULT_NPC_TEAM_ID {
+    if target.position.zone_id != position.zone_id {
         return false;
     }
 
-    if target.position.zone_id != position.zone_id {
+    if target.client_entity.id == client_entity.id || target.team.id == Team::DEFAULT_NPC_TEAM_ID {
+        return false;
+    }
+
+    if !pvp_check(
+        game_data,
+        client_entity,
+        position,
+        target.client_entity,
+        target.position,
+    )
+    .is_enemy(team, target.team)
+    {
         return false;
     }
 
@@ -134,6 +174,36 @@ fn is_valid_attack_target(
     true
 }
 
+/// Tells a player when they can not attack another character because of the zone PvP rules
+fn send_pvp_denied_message(
+    game_data: &GameData,
+    game_client: Option<&GameClient>,
+    client_entity: &ClientEntity,
+    position: &Position,
+    target_client_entity: &ClientEntity,
+    target_position: &Position,
+) {
+    let Some(game_client) = game_client else {
+        return;
+    };
+
+    if let PvpCheck::Denied(reason) = pvp_check(
+        game_data,
+        client_entity,
+        position,
+        target_client_entity,
+        target_position,
+    ) {
+        game_client
+            .server_message_tx
+            .send(ServerMessage::Whisper {
+                from: String::from("SERVER"),
+                text: String::from(reason.message()),
+            })
+            .ok();
+    }
+}
+
 fn is_valid_pickup_target(target: &CommandPickupItemTargetQueryItem, position: &Position) -> bool {
     if target.position.zone_id != position.zone_id {
         return false;
@@ -150,6 +220,93 @@ fn is_valid_pickup_target(target: &CommandPickupItemTargetQueryItem, position: &
     true
 }
 
+/// Returns the position an npc should currently move towards to reach destination, and
+/// whether that is a new waypoint which must be sent to clients.
+fn get_npc_move_waypoint(
+    commands: &mut Commands,
+    navigation_grids: &mut NavigationGrids,
+    zone_data: Option<&ZoneData>,
+    entity: Entity,
+    position: &Position,
+    destination: Vec3,
+    move_path: Option<&mut MovePath>,
+) -> (Vec3, bool) {
+    let Some(zone_data) = zone_data else {
+        return (destination, false);
+    };
+
+    let mut new_move_path = None;
+    let move_path = match move_path {
+        Some(move_path) => move_path,
+        None => new_move_path.insert(MovePath::default()),
+    };
+
+    let mut waypoint_changed = false;
+    if move_path.destination.map_or(true, |path_destination| {
+        path_destination.xy().distance(destination.xy()) > MOVE_PATH_RECALCULATE_DISTANCE
+    }) {
+        match navigation_grids.find_path(zone_data, position.position, destination) {
+            NavigationPath::Direct => {
+                *move_path = MovePath::new(destination, Default::default());
+            }
+            NavigationPath::Waypoints(waypoints) => {
+                *move_path = MovePath::new(destination, waypoints);
+                waypoint_changed = true;
+            }
+            NavigationPath::Deferred => {}
+        }
+    }
+
+    while let Some(waypoint) = move_path.waypoints.front() {
+        if waypoint.xy().distance(position.position.xy()) > MOVE_PATH_WAYPOINT_REACHED_DISTANCE {
+            break;
+        }
+
+        move_path.waypoints.pop_front();
+        waypoint_changed = true;
+    }
+
+    let waypoint = move_path.waypoints.front().cloned();
+
+    if let Some(new_move_path) = new_move_path {
+        commands.entity(entity).insert(new_move_path);
+    }
+
+    match waypoint {
+        Some(waypoint) => (waypoint, waypoint_changed),
+        None => (destination, waypoint_changed),
+    }
+}
+
+fn is_in_line_of_sight(game_data: &GameData, position: &Position, target_position: Vec3) -> bool {
+    let Some(zone_data) = game_data.zones.get_zone(position.zone_id) else {
+        return true;
+    };
+    let heightmap = &zone_data.heightmap;
+
+    // Positions may be below the terrain, e.g. skill target positions have no z
+    let get_ground_position = |position: Vec3| {
+        Vec3::new(
+            position.x,
+            position.y,
+            heightmap
+                .get_height(position.xy())
+                .map_or(position.z, |height| f32::max(height, position.z)),
+        )
+    };
+    let from = get_ground_position(position.position);
+    let to = get_ground_position(target_position);
+
+    if (from.z - to.z).abs() > MAX_ATTACK_HEIGHT_DIFFERENCE {
+        return false;
+    }
+
+    heightmap.has_line_of_sight(
+        from + Vec3::new(0.0, 0.0, LINE_OF_SIGHT_HEIGHT),
+        to + Vec3::new(0.0, 0.0, LINE_OF_SIGHT_HEIGHT),
+    )
+}
+
 fn can_cast_skill(
     now: Instant,
     game_data: &GameData,
@@ -177,7 +334,7 @@ fn can_cast_skill(
                 return false;
             };
 
-            if !skill_can_target_entity(&skill_caster, &skill_target, skill_data) {
+            if !skill_can_target_entity(game_data, &skill_caster, &skill_target, skill_data) {
                 return false;
             }
         }
@@ -194,7 +351,7 @@ fn can_cast_skill(
                     | SkillType::SelfStateDuration
                     | SkillType::SummonPet
                     | SkillType::SelfDamage
-            ) && !skill_can_target_self(&skill_caster, skill_data)
+            ) && !skill_can_target_self(game_data, &skill_caster, skill_data)
             {
                 return false;
             }
@@ -204,6 +361,10 @@ fn can_cast_skill(
     true
 }
 
+fn get_attack_speed(ability_values: &AbilityValues) -> f32 {
+    i32::max(ability_values.get_attack_speed(), 30) as f32 / 100.0
+}
+
 pub fn command_system(
     mut commands: Commands,
     mut query_command_entity: Query<QueryCommandEntity>,
@@ -213,19 +374,33 @@ pub fn command_system(
     query_position: Query<(&ClientEntity, &Position)>,
     query_skill_target: Query<SkillTargetBundle>,
     query_skill_caster: Query<SkillCasterBundle>,
+    query_gather_node: Query<(&GatherNode, &Position)>,
     game_data: Res<GameData>,
+    mut navigation_grids: ResMut<NavigationGrids>,
     time: Res<Time>,
-    mut damage_events: EventWriter<DamageEvent>,
-    mut skill_events: EventWriter<SkillEvent>,
-    mut pickup_item_event: EventWriter<PickupItemEvent>,
-    mut item_life_event: EventWriter<ItemLifeEvent>,
-    mut use_ammo_event: EventWriter<UseAmmoEvent>,
+    mut events: CommandSystemEvents,
     mut server_messages: ResMut<ServerMessages>,
+    mut pending_attack_damage: Local<Vec<(Instant, DamageEvent)>>,
 ) {
     let Some(now) = time.last_update() else {
         return;
     };
 
+    // Apply attack damage once the attack motion has reached its hit frame
+    let mut i = 0;
+    while i != pending_attack_damage.len() {
+        if pending_attack_damage[i].0 > now {
+            i += 1;
+            continue;
+        }
+
+        let (_, damage_event) = pending_attack_damage.remove(i);
+        events.damage_events.send(damage_event);
+    }
+
+    navigation_grids.receive_generated_grids();
+    navigation_grids.reset_budget();
+
     for mut command_entity in query_command_entity.iter_mut() {
         if command_entity.command.is_dead() {
             // Ignore all requested commands whilst dead.
@@ -245,6 +420,7 @@ pub fn command_system(
                 CommandData::PersonalStore => {}
                 CommandData::PickupItemDrop { .. } => {}
                 CommandData::Emote { .. } => {}
+                CommandData::Gather { .. } => {}
                 CommandData::Move {
                     destination,
                     target,
@@ -264,6 +440,12 @@ pub fn command_system(
                         }
                     }
 
+                    // A new move command requires a new path
+                    if let Some(move_path) = command_entity.move_path.as_mut() {
+                        move_path.destination = None;
+                        move_path.waypoints.clear();
+                    }
+
                     let distance = command_entity
                         .position
                         .position
@@ -291,7 +473,9 @@ pub fn command_system(
                             .ok()
                             .filter(|target| {
                                 is_valid_attack_target(
+                                    &game_data,
                                     target,
+                                    command_entity.client_entity,
                                     command_entity.position,
                                     command_entity.team,
                                 )
@@ -315,6 +499,16 @@ pub fn command_system(
                             },
                         );
                     } else {
+                        if let Ok(target) = query_attack_target.get(target_entity) {
+                            send_pvp_denied_message(
+                                &game_data,
+                                command_entity.game_client,
+                                command_entity.client_entity,
+                                command_entity.position,
+                                target.client_entity,
+                                target.position,
+                            );
+                        }
                         *command_entity.next_command = NextCommand::with_stop(true);
                     }
                 }
@@ -387,16 +581,12 @@ pub fn command_system(
         command_entity.command.duration += time.delta();
 
         let required_duration = match &mut command_entity.command.command {
-            CommandData::Attack { .. } => {
-                let attack_speed =
-                    i32::max(command_entity.ability_values.get_attack_speed(), 30) as f32 / 100.0;
-                command_entity
-                    .command
-                    .required_duration
-                    .map(|duration| duration.div_f32(attack_speed))
-            }
-            CommandData::Emote { .. } => {
-                // Any command can interrupt an emote
+            CommandData::Attack { .. } => command_entity
+                .command
+                .required_duration
+                .map(|duration| duration.div_f32(get_attack_speed(command_entity.ability_values))),
+            CommandData::Emote { .. } | CommandData::Gather { .. } => {
+                // Any command can interrupt an emote or gather
                 if command_entity.next_command.command.is_some() {
                     None
                 } else {
@@ -416,6 +606,24 @@ pub fn command_system(
             continue;
         }
 
+        if let CommandData::Gather { target } = command_entity.command.command {
+            // Only a gather which was not interrupted will reward the player
+            if command_entity
+                .command
+                .required_duration
+                .map_or(false, |required_duration| {
+                    command_entity.command.duration >= required_duration
+                })
+            {
+                events.gather_events.send(GatherEvent {
+                    gather_entity: command_entity.entity,
+                    node_entity: target,
+                });
+            }
+
+            *command_entity.command = Command::default();
+        }
+
         match command_entity.command.command {
             CommandData::Die { .. } => {
                 // We can't perform NextCommand if we are dead!
@@ -432,7 +640,17 @@ pub fn command_system(
             // If we have completed current command, and there is no next command, then clear current.
             // This does not apply for some commands which must be manually completed, such as Sit
             // where you need to stand after.
-            if command_motion_completed && !command_entity.command.command.is_manual_complete() {
+            // Character emotes with is_stop are also held, npc emotes always end so the AI can
+            // return to idle.
+            let is_held_emote = command_entity.npc.is_none()
+                && matches!(
+                    command_entity.command.command,
+                    CommandData::Emote { is_stop: true, .. }
+                );
+            if command_motion_completed
+                && !command_entity.command.command.is_manual_complete()
+                && !is_held_emote
+            {
                 *command_entity.command = Command::default();
             }
 
@@ -445,7 +663,7 @@ pub fn command_system(
             let duration = command_entity
                 .motion_data
                 .get_sit_standing()
-                .map(|motion_data| motion_data.duration)
+                .map(|motion_data| motion_data.duration())
                 .unwrap_or_else(|| Duration::from_secs(0));
 
             *command_entity.command = Command::with_standing(duration);
@@ -560,6 +778,40 @@ pub fn command_system(
                     .distance(destination.xy());
                 if distance < 0.1 {
                     *command_entity.command = Command::with_stop();
+                } else if command_entity.npc.is_some() && command_entity.game_client.is_none() {
+                    // Npcs follow a path around any obstacles towards their destination
+                    let (waypoint, waypoint_changed) = get_npc_move_waypoint(
+                        &mut commands,
+                        &mut navigation_grids,
+                        game_data.zones.get_zone(command_entity.position.zone_id),
+                        command_entity.entity,
+                        command_entity.position,
+                        *destination,
+                        command_entity.move_path.as_deref_mut(),
+                    );
+
+                    if waypoint_changed {
+                        server_messages.send_entity_message(
+                            command_entity.client_entity,
+                            ServerMessage::MoveEntity {
+                                entity_id: command_entity.client_entity.id,
+                                target_entity_id: None,
+                                distance: command_entity
+                                    .position
+                                    .position
+                                    .xy()
+                                    .distance(waypoint.xy())
+                                    as u16,
+                                x: waypoint.x,
+                                y: waypoint.y,
+                                z: waypoint.z as u16,
+                                move_mode: *command_move_mode,
+                            },
+                        );
+                    }
+
+                    *command_entity.command =
+                        Command::with_move(waypoint, *target, *command_move_mode);
                 } else {
                     *command_entity.command =
                         Command::with_move(*destination, *target, *command_move_mode);
@@ -575,7 +827,7 @@ pub fn command_system(
                         is_valid_pickup_target(&target, command_entity.position)
                     })
                 {
-                    pickup_item_event.send(PickupItemEvent {
+                    events.pickup_item_events.send(PickupItemEvent {
                         pickup_entity: command_entity.entity,
                         item_entity: target_entity,
                     });
@@ -584,7 +836,7 @@ pub fn command_system(
                     let motion_duration = command_entity
                         .motion_data
                         .get_pickup_item_drop()
-                        .map_or_else(|| Duration::from_secs(1), |motion| motion.duration);
+                        .map_or_else(|| Duration::from_secs(1), |motion| motion.duration());
 
                     *command_entity.command =
                         Command::with_pickup_item_drop(target_entity, motion_duration);
@@ -594,6 +846,52 @@ pub fn command_system(
 
                 *command_entity.next_command = NextCommand::default();
             }
+            &mut CommandData::Gather {
+                target: target_entity,
+            } => {
+                let Some(gather_node_data) = query_gather_node
+                    .get(target_entity)
+                    .ok()
+                    .filter(|(gather_node, position)| {
+                        !gather_node.is_depleted()
+                            && position.zone_id == command_entity.position.zone_id
+                            && position
+                                .position
+                                .xy()
+                                .distance(command_entity.position.position.xy())
+                                <= GATHER_NODE_DISTANCE
+                    })
+                    .and_then(|(gather_node, _)| {
+                        game_data.gather_nodes.get_node(gather_node.node_index)
+                    })
+                else {
+                    command_stop(
+                        &mut command_entity.command,
+                        command_entity.client_entity,
+                        command_entity.position,
+                        Some(&mut server_messages),
+                    );
+                    *command_entity.next_command = NextCommand::default();
+                    continue;
+                };
+
+                if let Some(motion_id) = gather_node_data.motion_id {
+                    server_messages.send_entity_message(
+                        command_entity.client_entity,
+                        ServerMessage::UseEmote {
+                            entity_id: command_entity.client_entity.id,
+                            motion_id,
+                            is_stop: false,
+                        },
+                    );
+                }
+
+                *command_entity.command = Command::with_gather(
+                    target_entity,
+                    Duration::from_millis(gather_node_data.gather_millis),
+                );
+                *command_entity.next_command = NextCommand::default();
+            }
             &mut CommandData::Attack {
                 target: target_entity,
             } => {
@@ -601,10 +899,26 @@ pub fn command_system(
                     .get(target_entity)
                     .ok()
                     .filter(|target| {
-                        is_valid_attack_target(target, command_entity.position, command_entity.team)
+                        is_valid_attack_target(
+                            &game_data,
+                            target,
+                            command_entity.client_entity,
+                            command_entity.position,
+                            command_entity.team,
+                        )
                     })
                 else {
                     // Cannot attack target, cancel command.
+                    if let Ok(target) = query_attack_target.get(target_entity) {
+                        send_pvp_denied_message(
+                            &game_data,
+                            command_entity.game_client,
+                            command_entity.client_entity,
+                            command_entity.position,
+                            target.client_entity,
+                            target.position,
+                        );
+                    }
                     command_stop(
                         &mut command_entity.command,
                         command_entity.client_entity,
@@ -631,15 +945,40 @@ pub fn command_system(
                     continue;
                 }
 
-                let mut cancel_attack = false;
+                if !is_in_line_of_sight(
+                    &game_data,
+                    command_entity.position,
+                    target.position.position,
+                ) {
+                    // Target is obstructed by terrain, cancel attack
+                    command_stop(
+                        &mut command_entity.command,
+                        command_entity.client_entity,
+                        command_entity.position,
+                        Some(&mut server_messages),
+                    );
+                    *command_entity.next_command = NextCommand::default();
+                    continue;
+                }
+
+                // Characters at their max overweight can not attack
+                let mut cancel_attack = command_entity
+                    .weight
+                    .map_or(false, |weight| weight.is_max_overweight());
 
-                let (attack_duration, hit_count) =
+                let (attack_duration, attack_hit_time, hit_count) =
                     if let Some(attack_motion) = command_entity.motion_data.get_attack() {
-                        (attack_motion.duration, attack_motion.total_attack_frames)
+                        (
+                            attack_motion.duration(),
+                            attack_motion
+                                .find_frame_event(ATTACK_HIT_EVENT_FLAGS)
+                                .unwrap_or(Duration::ZERO),
+                            attack_motion.total_attack_frames(),
+                        )
                     } else {
                         // No attack animation, cancel attack
                         cancel_attack = true;
-                        (Duration::ZERO, 0)
+                        (Duration::ZERO, Duration::ZERO, 0)
                     };
 
                 if matches!(command_entity.move_mode, MoveMode::Drive) {
@@ -689,7 +1028,7 @@ pub fn command_system(
                                             ammo_item.quantity >= hit_count as u32
                                         })
                                     {
-                                        use_ammo_event.send(UseAmmoEvent {
+                                        events.use_ammo_events.send(UseAmmoEvent {
                                             entity: command_entity.entity,
                                             ammo_index,
                                             quantity: hit_count,
@@ -718,24 +1057,49 @@ pub fn command_system(
 
                 if matches!(command_entity.move_mode, MoveMode::Drive) {
                     // Decrease vehicle engine item life on attack
-                    item_life_event.send(ItemLifeEvent::DecreaseVehicleEngineLife {
-                        entity: command_entity.entity,
-                        amount: None,
-                    });
+                    events
+                        .item_life_events
+                        .send(ItemLifeEvent::DecreaseVehicleEngineLife {
+                            entity: command_entity.entity,
+                            amount: None,
+                        });
                 }
 
                 // Decrease weapon item life on attack
                 if command_entity.character_info.is_some() {
-                    item_life_event.send(ItemLifeEvent::DecreaseWeaponLife {
-                        entity: command_entity.entity,
-                    });
+                    events
+                        .item_life_events
+                        .send(ItemLifeEvent::DecreaseWeaponLife {
+                            entity: command_entity.entity,
+                        });
                 }
 
+                // The attack motion plays faster with attack speed, when attacking repeatedly
+                // carry over the time past the end of the previous attack so the attack rate
+                // matches the client animation rather than the server tick rate
+                let attack_speed = get_attack_speed(command_entity.ability_values);
+                let carry_over_duration = if command_entity.command.is_attack_target(target_entity)
+                {
+                    command_entity
+                        .command
+                        .required_duration
+                        .map_or(Duration::ZERO, |required_duration| {
+                            command_entity
+                                .command
+                                .duration
+                                .saturating_sub(required_duration.div_f32(attack_speed))
+                        })
+                        .min(time.delta())
+                } else {
+                    Duration::ZERO
+                };
+
                 // In range, set current command to attack
                 *command_entity.command = Command::with_attack(target_entity, attack_duration);
+                command_entity.command.duration = carry_over_duration;
 
-                // Send damage event to damage system
-                damage_events.send(DamageEvent::Attack {
+                // Send damage event to damage system when the attack motion hits
+                let damage_event = DamageEvent::Attack {
                     attacker: command_entity.entity,
                     defender: target_entity,
                     damage: game_data.ability_value_calculator.calculate_damage(
@@ -743,7 +1107,15 @@ pub fn command_system(
                         target.ability_values,
                         hit_count as i32,
                     ),
-                });
+                };
+                let attack_hit_time = attack_hit_time
+                    .div_f32(attack_speed)
+                    .saturating_sub(carry_over_duration);
+                if attack_hit_time.is_zero() {
+                    events.damage_events.send(damage_event);
+                } else {
+                    pending_attack_damage.push((now + attack_hit_time, damage_event));
+                }
             }
             &mut CommandData::CastSkill {
                 skill_id,
@@ -761,6 +1133,40 @@ pub fn command_system(
                     &query_skill_caster,
                     &query_skill_target,
                 ) {
+                    // Let the client know when a combo skill was used out of order, irose has
+                    // no combo reason so it is reported as an unmet skill requirement
+                    if let (Ok(skill_caster), Some(skill_data)) = (
+                        query_skill_caster.get(command_entity.entity),
+                        game_data.skills.get_skill(skill_id),
+                    ) {
+                        if skill_caster.client_entity.is_character()
+                            && !skill_check_combo(&skill_caster, now, skill_data)
+                        {
+                            server_messages.send_entity_message(
+                                command_entity.client_entity,
+                                ServerMessage::CancelCastingSkill {
+                                    entity_id: command_entity.client_entity.id,
+                                    reason: CancelCastingSkillReason::NeedAbility,
+                                },
+                            );
+                        }
+                    }
+
+                    if let Some(CommandCastSkillTarget::Entity(target_entity)) = skill_target {
+                        if let Ok((target_client_entity, target_position)) =
+                            query_position.get(target_entity)
+                        {
+                            send_pvp_denied_message(
+                                &game_data,
+                                command_entity.game_client,
+                                command_entity.client_entity,
+                                command_entity.position,
+                                target_client_entity,
+                                target_position,
+                            );
+                        }
+                    }
+
                     // Cannot use skill, cancel command.
                     command_stop(
                         &mut command_entity.command,
@@ -811,6 +1217,20 @@ pub fn command_system(
                     continue;
                 }
 
+                if target_position.map_or(false, |target_position| {
+                    !is_in_line_of_sight(&game_data, command_entity.position, target_position)
+                }) {
+                    // Target is obstructed by terrain, cancel command.
+                    command_stop(
+                        &mut command_entity.command,
+                        command_entity.client_entity,
+                        command_entity.position,
+                        Some(&mut server_messages),
+                    );
+                    *command_entity.next_command = NextCommand::default();
+                    continue;
+                }
+
                 let casting_duration = cast_motion_id
                     .or(skill_data.casting_motion_id)
                     .and_then(|motion_id| {
@@ -824,27 +1244,54 @@ pub fn command_system(
                             )
                         }
                     })
-                    .map(|motion_data| motion_data.duration)
+                    .map(|motion_data| motion_data.duration())
                     .unwrap_or_else(|| Duration::from_secs(0))
                     .mul_f32(skill_data.casting_motion_speed);
 
-                let action_duration = action_motion_id
-                    .or(skill_data.action_motion_id)
-                    .and_then(|motion_id| {
-                        if let Some(npc) = command_entity.npc {
-                            game_data.npcs.get_npc_motion(npc.id, motion_id)
-                        } else {
-                            game_data.motions.find_first_character_motion(
-                                motion_id,
-                                weapon_motion_type,
-                                weapon_motion_gender,
-                            )
-                        }
-                    })
-                    .map(|motion_data| motion_data.duration)
+                let action_motion =
+                    action_motion_id
+                        .or(skill_data.action_motion_id)
+                        .and_then(|motion_id| {
+                            if let Some(npc) = command_entity.npc {
+                                game_data.npcs.get_npc_motion(npc.id, motion_id)
+                            } else {
+                                game_data.motions.find_first_character_motion(
+                                    motion_id,
+                                    weapon_motion_type,
+                                    weapon_motion_gender,
+                                )
+                            }
+                        });
+                let action_duration = action_motion
+                    .map(|motion_data| motion_data.duration())
                     .unwrap_or_else(|| Duration::from_secs(0))
                     .mul_f32(skill_data.action_motion_speed);
 
+                // The client applies the skill effect at the frame flagged in the action motion
+                let skill_effect_time = casting_duration
+                    + action_motion
+                        .and_then(|motion_data| {
+                            motion_data
+                                .find_frame_event(AnimationEventFlags::APPLY_PENDING_SKILL_EFFECT)
+                        })
+                        .unwrap_or_else(|| Duration::from_secs(0))
+                        .mul_f32(skill_data.action_motion_speed)
+                    + skill_data
+                        .bullet_effect_id
+                        .and_then(|effect_id| game_data.effects.get_effect(effect_id))
+                        .zip(target_position)
+                        .map_or(Duration::ZERO, |(effect_data, target_position)| {
+                            // Bullet skills apply their effect when the bullet reaches the target
+                            game_data.effects.get_bullet_travel_time(
+                                effect_data,
+                                command_entity
+                                    .position
+                                    .position
+                                    .xy()
+                                    .distance(target_position.xy()),
+                            )
+                        });
+
                 // For skills which target an entity, we must send a message indicating start of skill
                 if target_entity.is_some() {
                     server_messages.send_entity_message(
@@ -855,10 +1302,10 @@ pub fn command_system(
                     );
                 }
 
-                // Send skill event for effect to be applied after casting motion
-                skill_events.send(SkillEvent::new(
+                // Send skill event for effect to be applied at the action motion hit frame
+                events.skill_events.send(SkillEvent::new(
                     command_entity.entity,
-                    time.last_update().unwrap() + casting_duration,
+                    time.last_update().unwrap() + skill_effect_time,
                     skill_id,
                     match skill_target {
                         None => SkillEventTarget::Entity(command_entity.entity),
@@ -894,6 +1341,7 @@ pub fn command_system(
                         }
                         CommandData::Die { .. }
                         | CommandData::Emote { .. }
+                        | CommandData::Gather { .. }
                         | CommandData::PickupItemDrop { .. }
                         | CommandData::PersonalStore
                         | CommandData::Sit
@@ -932,7 +1380,7 @@ pub fn command_system(
                 let duration = command_entity
                     .motion_data
                     .get_sit_sitting()
-                    .map(|motion_data| motion_data.duration)
+                    .map(|motion_data| motion_data.duration())
                     .unwrap_or_else(|| Duration::from_secs(0));
 
                 *command_entity.command = Command::with_sitting(duration);
@@ -975,7 +1423,7 @@ pub fn command_system(
                 );
 
                 let duration = motion_data
-                    .map(|motion_data| motion_data.duration)
+                    .map(|motion_data| motion_data.duration())
                     .unwrap_or_else(|| Duration::from_secs(0));
 
                 *command_entity.command = Command::with_emote(motion_id, is_stop, duration);
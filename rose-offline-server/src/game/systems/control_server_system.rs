@@ -1,36 +1,214 @@
-use bevy::ecs::prelude::{Commands, EventWriter, Res, ResMut};
+use std::time::{Duration, Instant};
+
+use bevy::{
+    ecs::{
+        prelude::{Commands, Entity, EventWriter, Query, Res, ResMut, With},
+        query::WorldQuery,
+    },
+    time::Time,
+};
+use serde_json::json;
+
+use rose_game_common::data::Password;
 
 use crate::game::{
-    components::{GameClient, LoginClient, ServerInfo, WorldClient},
+    components::{
+        AbilityValues, Account, CharacterInfo, Command, GameClient, HealthPoints, Inventory,
+        InventoryPage, LinkDead, LoginClient, NextCommand, Position, ServerInfo, StatusEffects,
+        WorldClient,
+    },
     events::SaveEvent,
-    messages::control::{ClientType, ControlMessage},
-    resources::{ControlChannel, GameServer, LoginTokens, ServerList, WorldServer},
+    messages::control::{ClientType, ControlMessage, InspectEntityTarget, ServerNewsEdit},
+    resources::{
+        ClientEntityList, ControlChannel, GameConfig, GameData, GameServer, LoginAttempts,
+        LoginTokens, ServerList, ServerNews, WorldServer, ZoneCensus,
+    },
+    storage::account::AccountStorage,
 };
 
+#[derive(WorldQuery)]
+pub struct InspectEntityQuery<'w> {
+    entity: Entity,
+    position: &'w Position,
+    command: &'w Command,
+    ability_values: &'w AbilityValues,
+    health_points: &'w HealthPoints,
+    status_effects: &'w StatusEffects,
+    character_info: Option<&'w CharacterInfo>,
+    inventory: Option<&'w Inventory>,
+    game_client: Option<&'w GameClient>,
+}
+
+fn count_page_items(page: &InventoryPage) -> usize {
+    page.slots.iter().filter(|slot| slot.is_some()).count()
+}
+
+fn inspect_entity(
+    query_inspect: &Query<InspectEntityQuery>,
+    client_entity_list: &ClientEntityList,
+    time: &Time,
+    target: &InspectEntityTarget,
+) -> Option<serde_json::Value> {
+    let inspect = match target {
+        InspectEntityTarget::CharacterName(name) => query_inspect.iter().find(|inspect| {
+            inspect
+                .character_info
+                .map_or(false, |character_info| &character_info.name == name)
+        })?,
+        InspectEntityTarget::ClientEntity { zone_id, entity_id } => {
+            let (entity, _, _) = client_entity_list
+                .get_zone(*zone_id)?
+                .get_entity(*entity_id)?;
+            query_inspect.get(*entity).ok()?
+        }
+    };
+
+    let now = time.last_update().unwrap();
+    let remaining_times = inspect.status_effects.remaining_times(now);
+    let status_effects: Vec<_> = inspect
+        .status_effects
+        .active
+        .iter()
+        .filter_map(|(status_effect_type, active)| {
+            let active = active.as_ref()?;
+            Some(json!({
+                "type": format!("{:?}", status_effect_type),
+                "id": active.id.get(),
+                "value": active.value,
+                "remaining_secs": remaining_times[status_effect_type]
+                    .map(|remaining_time| remaining_time.as_secs_f32()),
+            }))
+        })
+        .collect();
+
+    let ability_values = inspect.ability_values;
+    Some(json!({
+        "entity": format!("{:?}", inspect.entity),
+        "name": inspect.character_info.map(|character_info| character_info.name.clone()),
+        "position": inspect.position,
+        "command": inspect.command,
+        "health": inspect.health_points.hp,
+        "ability_values": {
+            "level": ability_values.get_level(),
+            "max_health": ability_values.get_max_health(),
+            "max_mana": ability_values.get_max_mana(),
+            "attack_power": ability_values.get_attack_power(),
+            "defence": ability_values.get_defence(),
+            "run_speed": ability_values.get_run_speed(),
+        },
+        "status_effects": status_effects,
+        "inventory": inspect.inventory.map(|inventory| json!({
+            "money": inventory.money.0,
+            "equipment": count_page_items(&inventory.equipment),
+            "consumables": count_page_items(&inventory.consumables),
+            "materials": count_page_items(&inventory.materials),
+            "vehicles": count_page_items(&inventory.vehicles),
+        })),
+        "server_message_queue": inspect
+            .game_client
+            .map(|game_client| game_client.server_message_tx.stats()),
+    }))
+}
+
+fn edit_server_news(
+    server_news: &mut ServerNews,
+    edit: Option<ServerNewsEdit>,
+) -> Result<serde_json::Value, String> {
+    match edit {
+        None => {}
+        Some(ServerNewsEdit::Motd(text)) => {
+            server_news.motd = text.into_iter().collect();
+        }
+        Some(ServerNewsEdit::AddAnnouncement(text)) => {
+            server_news.announcements.push(text);
+        }
+        Some(ServerNewsEdit::RemoveAnnouncement(index)) => {
+            if index >= server_news.announcements.len() {
+                return Err(format!("invalid announcement index {}", index));
+            }
+            server_news.announcements.remove(index);
+        }
+        Some(ServerNewsEdit::ClearAnnouncements) => {
+            server_news.announcements.clear();
+        }
+        Some(ServerNewsEdit::AnnouncementInterval(secs)) => {
+            server_news.announcement_interval = Duration::from_secs(secs);
+        }
+    }
+
+    Ok(json!({
+        "motd": server_news.motd,
+        "announcements": server_news.announcements,
+        "announcement_interval_secs": server_news.announcement_interval.as_secs(),
+    }))
+}
+
+fn reset_account_password(
+    query_accounts: &mut Query<&mut Account>,
+    name: &str,
+    password: &Password,
+) -> Result<(), anyhow::Error> {
+    let mut account_storage = AccountStorage::try_load_without_password(name)?;
+    account_storage.set_password(password)?;
+    account_storage.save()?;
+
+    // A logged in account would otherwise save its old password over the new one
+    for mut account in query_accounts.iter_mut() {
+        if account.name == name {
+            account.password_md5_sha256 = account_storage.password_md5_sha256.clone();
+        }
+    }
+
+    Ok(())
+}
+
 pub fn control_server_system(
     mut commands: Commands,
+    query_inspect: Query<InspectEntityQuery>,
+    query_characters: Query<(Entity, &CharacterInfo), With<Account>>,
+    mut query_accounts: Query<&mut Account>,
+    query_game_client: Query<(), With<GameClient>>,
     channel: Res<ControlChannel>,
+    client_entity_list: Res<ClientEntityList>,
+    mut game_config: ResMut<GameConfig>,
+    game_data: Res<GameData>,
+    mut login_attempts: ResMut<LoginAttempts>,
     mut login_tokens: ResMut<LoginTokens>,
     mut server_list: ResMut<ServerList>,
+    mut server_news: ResMut<ServerNews>,
+    time: Res<Time>,
+    zone_census: Res<ZoneCensus>,
     mut save_events: EventWriter<SaveEvent>,
 ) {
     while let Ok(message) = channel.control_rx.try_recv() {
         match message {
             ControlMessage::AddClient {
                 client_type,
+                capabilities,
+                ip_address,
                 client_message_rx,
                 server_message_tx,
+                watch,
                 response_tx,
             } => {
                 let entity = match client_type {
                     ClientType::Login => commands
-                        .spawn(LoginClient::new(client_message_rx, server_message_tx))
+                        .spawn(LoginClient::new(
+                            client_message_rx,
+                            server_message_tx,
+                            ip_address,
+                        ))
                         .id(),
                     ClientType::World => commands
                         .spawn(WorldClient::new(client_message_rx, server_message_tx))
                         .id(),
                     ClientType::Game => commands
-                        .spawn(GameClient::new(client_message_rx, server_message_tx))
+                        .spawn(GameClient::new(
+                            client_message_rx,
+                            server_message_tx,
+                            watch,
+                            capabilities,
+                        ))
                         .id(),
                 };
                 response_tx.send(entity).unwrap();
@@ -63,23 +241,29 @@ pub fn control_server_system(
                     commands.entity(entity).despawn();
                 }
                 ClientType::Game => {
-                    for (index, login_token) in login_tokens.tokens.iter_mut().enumerate() {
-                        if login_token.game_client == Some(entity) {
-                            login_token.game_client = None;
-                        }
+                    // A resumed session keeps using the entity of the original character
+                    let mut character_entity = entity;
+                    for login_token in login_tokens.tokens.iter_mut() {
+                        if login_token.game_client_connection == Some(entity) {
+                            login_token.game_client_connection = None;
 
-                        if login_token.game_client.is_none() && login_token.world_client.is_none() {
-                            login_tokens.tokens.remove(index);
-                            break;
+                            if let Some(game_client) = login_token.game_client {
+                                character_entity = game_client;
+                            }
                         }
                     }
 
-                    // Let the save system handle despawning the entity
-                    save_events.send(SaveEvent::Character {
-                        entity,
-                        remove_after_save: true,
-                    });
-                    commands.entity(entity).remove::<GameClient>();
+                    // A character whose session was taken over has already been removed
+                    if !query_game_client.contains(character_entity) {
+                        continue;
+                    }
+
+                    // The login token is kept so the client can resume the session, the link
+                    // dead system handles saving and despawning the entity
+                    commands
+                        .entity(character_entity)
+                        .remove::<GameClient>()
+                        .insert((LinkDead::new(time.elapsed()), NextCommand::with_stop(true)));
                 }
             },
             ControlMessage::AddWorldServer {
@@ -113,6 +297,7 @@ pub fn control_server_system(
                 ip,
                 port,
                 packet_codec_seed,
+                max_players,
                 response_tx,
             } => {
                 let entity = commands
@@ -134,12 +319,147 @@ pub fn control_server_system(
                     ip,
                     port,
                     packet_codec_seed,
+                    max_players,
                 });
                 response_tx.send(entity).unwrap();
             }
             ControlMessage::RemoveServer { entity } => {
                 commands.entity(entity).despawn();
             }
+            ControlMessage::ReloadConfig {
+                game_config: reloaded_config,
+            } => {
+                server_news.reload(&reloaded_config.news);
+                game_config.reload(*reloaded_config);
+                log::info!("Reloaded server config");
+            }
+            ControlMessage::InspectEntity {
+                target,
+                response_tx,
+            } => {
+                response_tx
+                    .send(inspect_entity(
+                        &query_inspect,
+                        &client_entity_list,
+                        &time,
+                        &target,
+                    ))
+                    .ok();
+            }
+            ControlMessage::SaveCharacters { name, response_tx } => {
+                let mut num_saved = 0;
+                for (entity, character_info) in query_characters.iter() {
+                    if name
+                        .as_ref()
+                        .map_or(true, |name| &character_info.name == name)
+                    {
+                        save_events.send(SaveEvent::Character {
+                            entity,
+                            remove_after_save: false,
+                        });
+                        num_saved += 1;
+                    }
+                }
+                response_tx.send(num_saved).ok();
+            }
+            ControlMessage::WatchCharacter {
+                name,
+                enabled,
+                response_tx,
+            } => {
+                let watch = query_inspect.iter().find_map(|inspect| {
+                    if inspect.character_info?.name == name {
+                        Some(&inspect.game_client?.watch)
+                    } else {
+                        None
+                    }
+                });
+                if let Some(watch) = watch {
+                    if enabled {
+                        watch.start(name);
+                    } else {
+                        watch.stop();
+                    }
+                }
+                response_tx.send(watch.is_some()).ok();
+            }
+            ControlMessage::EditServerNews { edit, response_tx } => {
+                response_tx
+                    .send(edit_server_news(&mut server_news, edit))
+                    .ok();
+            }
+            ControlMessage::ResetPassword {
+                name,
+                password,
+                response_tx,
+            } => {
+                let result = reset_account_password(&mut query_accounts, &name, &password);
+                match &result {
+                    Ok(_) => {
+                        login_attempts.clear_account(&name);
+                        log::info!(
+                            target: "audit",
+                            "Reset password of account {} from admin api",
+                            &name
+                        );
+                    }
+                    Err(error) => log::warn!(
+                        "Failed to reset password of account {} with error {:?}",
+                        &name,
+                        error
+                    ),
+                }
+                response_tx
+                    .send(result.map_err(|error| error.to_string()))
+                    .ok();
+            }
+            ControlMessage::GetLoginLockouts { response_tx } => {
+                let (accounts, ip_addresses) = login_attempts.get_lockouts(
+                    game_config.login_lockout.max_failures,
+                    Duration::from_secs(game_config.login_lockout.lockout_secs),
+                    Instant::now(),
+                );
+                response_tx
+                    .send(json!({
+                        "accounts": accounts
+                            .iter()
+                            .map(|(username, remaining_time)| json!({
+                                "username": username,
+                                "remaining_secs": remaining_time.as_secs(),
+                            }))
+                            .collect::<Vec<_>>(),
+                        "ip_addresses": ip_addresses
+                            .iter()
+                            .map(|(ip_address, remaining_time)| json!({
+                                "ip_address": ip_address.to_string(),
+                                "remaining_secs": remaining_time.as_secs(),
+                            }))
+                            .collect::<Vec<_>>(),
+                    }))
+                    .ok();
+            }
+            ControlMessage::GetZoneCensus {
+                num_top_zones,
+                response_tx,
+            } => {
+                response_tx
+                    .send(json!({
+                        "total": zone_census.total(),
+                        "zones": zone_census
+                            .top_zones(num_top_zones)
+                            .iter()
+                            .map(|(zone_id, counts)| json!({
+                                "zone_id": zone_id.get(),
+                                "name": game_data
+                                    .zones
+                                    .get_zone(*zone_id)
+                                    .map(|zone_data| zone_data.name),
+                                "counts": counts,
+                            }))
+                            .collect::<Vec<_>>(),
+                    }))
+                    .ok();
+            }
         }
     }
 }
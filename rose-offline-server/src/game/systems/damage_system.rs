@@ -9,12 +9,12 @@ use rose_game_common::data::Damage;
 
 use crate::game::{
     components::{
-        ClientEntity, ClientEntityType, Command, DamageSource, DamageSources, Dead, HealthPoints,
-        MotionData, NpcAi,
+        CharacterStatistics, ClientEntity, ClientEntityType, Command, CommandData, DamageAttacker,
+        DamageSource, DamageSources, Dead, HealthPoints, Level, MotionData, NextCommand, NpcAi,
     },
-    events::{DamageEvent, ItemLifeEvent},
+    events::{DamageEvent, ItemLifeEvent, TitleEvent},
     messages::server::ServerMessage,
-    resources::ServerMessages,
+    resources::{GameConfig, ServerMessages},
 };
 
 pub fn damage_system(
@@ -26,24 +26,40 @@ pub fn damage_system(
         Option<&mut DamageSources>,
         Option<&mut NpcAi>,
         Option<&MotionData>,
+        Option<&mut CharacterStatistics>,
+        Option<&Level>,
+        Option<&Command>,
+        Option<&mut NextCommand>,
     )>,
     mut damage_events: EventReader<DamageEvent>,
     mut item_life_events: EventWriter<ItemLifeEvent>,
+    mut title_events: EventWriter<TitleEvent>,
     mut server_messages: ResMut<ServerMessages>,
+    game_config: Res<GameConfig>,
     time: Res<Time>,
 ) {
     for damage_event in damage_events.iter() {
-        let (attacker_entity, defender_entity, damage, from_skill) = match *damage_event {
+        let (attacker, defender_entity, mut damage, from_skill) = match *damage_event {
             DamageEvent::Attack {
                 attacker: attacker_entity,
                 defender: defender_entity,
                 damage,
-            } => (attacker_entity, defender_entity, damage, None),
+            } => (
+                DamageAttacker::Entity(attacker_entity),
+                defender_entity,
+                damage,
+                None,
+            ),
             DamageEvent::Immediate {
                 attacker: attacker_entity,
                 defender: defender_entity,
                 damage,
-            } => (attacker_entity, defender_entity, damage, None),
+            } => (
+                DamageAttacker::Entity(attacker_entity),
+                defender_entity,
+                damage,
+                None,
+            ),
             DamageEvent::Skill {
                 attacker: attacker_entity,
                 defender: defender_entity,
@@ -51,7 +67,7 @@ pub fn damage_system(
                 skill_id,
                 attacker_intelligence,
             } => (
-                attacker_entity,
+                DamageAttacker::Entity(attacker_entity),
                 defender_entity,
                 damage,
                 Some((skill_id, attacker_intelligence)),
@@ -60,7 +76,7 @@ pub fn damage_system(
                 attacker: attacker_entity,
                 defender: defender_entity,
             } => (
-                attacker_entity,
+                DamageAttacker::Entity(attacker_entity),
                 defender_entity,
                 Damage {
                     amount: 0,
@@ -69,15 +85,27 @@ pub fn damage_system(
                 },
                 None,
             ),
+            DamageEvent::Environment {
+                defender: defender_entity,
+                damage,
+            } => (DamageAttacker::Environment, defender_entity, damage, None),
         };
 
-        let attacker_entity_id = attacker_query
-            .get(attacker_entity)
-            .map(|client_entity| Some(client_entity.id))
-            .unwrap_or(None);
-
-        if let Ok((client_entity, mut health_points, damage_sources, npc_ai, motion_data)) =
-            defender_query.get_mut(defender_entity)
+        let attacker_client_entity = attacker
+            .entity()
+            .and_then(|attacker_entity| attacker_query.get(attacker_entity).ok());
+
+        if let Ok((
+            client_entity,
+            mut health_points,
+            damage_sources,
+            npc_ai,
+            motion_data,
+            statistics,
+            level,
+            command,
+            next_command,
+        )) = defender_query.get_mut(defender_entity)
         {
             if damage.apply_hit_stun {
                 // TODO: Apply hit stun by setting next command to HitStun ?
@@ -88,9 +116,33 @@ pub fn damage_system(
                 continue;
             }
 
+            // Low level characters take reduced damage from other characters
+            if attacker_client_entity.map_or(false, |attacker_client_entity| {
+                matches!(
+                    attacker_client_entity.entity_type,
+                    ClientEntityType::Character
+                )
+            }) && matches!(client_entity.entity_type, ClientEntityType::Character)
+                && level.map_or(false, |level| {
+                    game_config.newbie_protection.is_protected(level.level)
+                })
+            {
+                damage.amount = damage
+                    .amount
+                    .saturating_mul(game_config.newbie_protection.pvp_damage_percent)
+                    / 100;
+            }
+
             health_points.hp = i32::max(health_points.hp - damage.amount as i32, 0);
 
             if !matches!(damage_event, DamageEvent::Tagged { .. }) {
+                // Environment damage is shown to clients as self inflicted
+                let attacker_entity_id = match attacker {
+                    DamageAttacker::Entity(_) => attacker_client_entity
+                        .map(|attacker_client_entity| attacker_client_entity.id),
+                    DamageAttacker::Environment => Some(client_entity.id),
+                };
+
                 if let Some(attacker_entity_id) = attacker_entity_id {
                     server_messages.send_entity_message(
                         client_entity,
@@ -117,7 +169,7 @@ pub fn damage_system(
                 if let Some(source) = damage_sources
                     .damage_sources
                     .iter_mut()
-                    .find(|source| source.entity == attacker_entity)
+                    .find(|source| source.attacker == attacker)
                 {
                     source.last_damage_time = time.last_update().unwrap();
                     source.total_damage += damage.amount as usize;
@@ -146,7 +198,7 @@ pub fn damage_system(
                     }
 
                     damage_sources.damage_sources.push(DamageSource {
-                        entity: attacker_entity,
+                        attacker,
                         total_damage: damage.amount as usize,
                         first_damage_time: time.last_update().unwrap(),
                         last_damage_time: time.last_update().unwrap(),
@@ -154,19 +206,35 @@ pub fn damage_system(
                 }
             }
 
-            if let Some(mut npc_ai) = npc_ai {
+            if let (Some(mut npc_ai), Some(attacker_entity)) = (npc_ai, attacker.entity()) {
                 npc_ai.pending_damage.push((attacker_entity, damage));
             }
 
+            if damage.amount > 0 && health_points.hp > 0 {
+                // Taking damage interrupts gathering
+                if let (Some(command), Some(mut next_command)) = (command, next_command) {
+                    if matches!(command.command, CommandData::Gather { .. }) {
+                        *next_command = NextCommand::with_stop(true);
+                    }
+                }
+            }
+
             if health_points.hp == 0 {
+                if let Some(mut statistics) = statistics {
+                    statistics.deaths += 1;
+                    title_events.send(TitleEvent::StatisticsChanged {
+                        entity: defender_entity,
+                    });
+                }
+
                 commands.entity(defender_entity).insert((
                     Dead,
                     Command::with_die(
-                        Some(attacker_entity),
+                        attacker.entity(),
                         Some(damage),
                         motion_data
                             .and_then(|motion_data| motion_data.get_die())
-                            .map(|die_motion| die_motion.duration)
+                            .map(|die_motion| die_motion.duration())
                             .or_else(|| Some(Duration::from_secs(1))),
                     ),
                 ));
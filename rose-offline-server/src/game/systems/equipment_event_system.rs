@@ -4,8 +4,8 @@ use bevy::{
 };
 
 use rose_data::{
-    BaseItemData, EquipmentIndex, Item, ItemType, JobId, StackError, StackableSlotBehaviour,
-    VehiclePartIndex,
+    BaseItemData, CostumeIndex, EquipmentIndex, Item, ItemType, JobId, StackError,
+    StackableSlotBehaviour, VehiclePartIndex,
 };
 use rose_game_common::messages::server::ServerMessage;
 
@@ -262,6 +262,51 @@ pub fn equipment_event_system(
                     );
                 }
             }
+            EquipmentEvent::ChangeCostume {
+                entity,
+                costume_index,
+                item_slot,
+            } => {
+                let Ok(mut entity) = query.get_mut(entity) else {
+                    continue;
+                };
+                if !entity.command.can_equip_items() {
+                    continue;
+                }
+
+                let updated_inventory_items = if let Some(item_slot) = item_slot {
+                    equip_costume_from_inventory(&game_data, &mut entity, costume_index, item_slot)
+                        .ok()
+                } else {
+                    unequip_costume_to_inventory(
+                        &mut entity.equipment,
+                        &mut entity.inventory,
+                        costume_index,
+                    )
+                    .ok()
+                };
+
+                if let Some(updated_inventory_items) = updated_inventory_items {
+                    if let Some(game_client) = entity.game_client {
+                        game_client
+                            .server_message_tx
+                            .send(ServerMessage::UpdateInventory {
+                                items: updated_inventory_items,
+                                money: None,
+                            })
+                            .ok();
+                    }
+
+                    server_messages.send_entity_message(
+                        entity.client_entity,
+                        ServerMessage::UpdateCostume {
+                            entity_id: entity.client_entity.id,
+                            costume_index,
+                            item: entity.equipment.get_costume_item(costume_index).cloned(),
+                        },
+                    );
+                }
+            }
         }
     }
 }
@@ -425,6 +470,46 @@ fn equip_vehicle_from_inventory(
     Ok(updated_inventory_items)
 }
 
+fn equip_costume_from_inventory(
+    game_data: &GameData,
+    entity: &mut EquipmentEventEntityItem,
+    costume_index: CostumeIndex,
+    item_slot: ItemSlot,
+) -> Result<Vec<(ItemSlot, Option<Item>)>, EquipItemError> {
+    let equipment_item = entity
+        .inventory
+        .get_equipment_item(item_slot)
+        .ok_or(EquipItemError::InvalidItem)?;
+
+    let item_data = game_data
+        .items
+        .get_base_item(equipment_item.item)
+        .ok_or(EquipItemError::InvalidItemData)?;
+
+    if equipment_item.item.item_type != ItemType::from(costume_index) {
+        return Err(EquipItemError::InvalidEquipmentIndex);
+    }
+
+    if !check_equipment_job_class(game_data, item_data, entity)
+        || !check_equipment_union_membership(item_data, entity)
+        || !check_equipment_ability_requirement(item_data, entity)
+    {
+        return Err(EquipItemError::FailedRequirements);
+    }
+
+    // Costumes have no ItemSlot of their own, so only the inventory slot is updated
+    let inventory_slot = entity.inventory.get_item_slot_mut(item_slot).unwrap();
+    let costume_slot = entity.equipment.get_costume_slot_mut(costume_index);
+    let equipment_item = match inventory_slot.take() {
+        Some(Item::Equipment(equipment_item)) => equipment_item,
+        _ => unreachable!(),
+    };
+    *inventory_slot = costume_slot.take().map(Item::Equipment);
+    *costume_slot = Some(equipment_item);
+
+    Ok(vec![(item_slot, inventory_slot.clone())])
+}
+
 enum UnequipError {
     NoItem,
     InventoryFull,
@@ -472,6 +557,24 @@ fn unequip_vehicle_to_inventory(
     }
 }
 
+fn unequip_costume_to_inventory(
+    equipment: &mut Equipment,
+    inventory: &mut Inventory,
+    costume_index: CostumeIndex,
+) -> Result<Vec<(ItemSlot, Option<Item>)>, UnequipError> {
+    let costume_slot = equipment.get_costume_slot_mut(costume_index);
+    let costume_item = costume_slot.take().ok_or(UnequipError::NoItem)?;
+
+    match inventory.try_add_equipment_item(costume_item) {
+        Ok((item_slot, item)) => Ok(vec![(item_slot, Some(item.clone()))]),
+        Err(costume_item) => {
+            // Failed to add to inventory, return item to costume slot
+            *costume_slot = Some(costume_item);
+            Err(UnequipError::InventoryFull)
+        }
+    }
+}
+
 fn check_equipment_job_class(
     game_data: &GameData,
     item_data: &BaseItemData,
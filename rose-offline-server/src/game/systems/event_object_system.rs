@@ -0,0 +1,138 @@
+use std::collections::HashSet;
+
+use bevy::{
+    ecs::prelude::{Changed, DetectChanges, Local, Query, Ref, Res, ResMut, Without},
+    time::Time,
+};
+
+use rose_data::ZoneId;
+use rose_game_common::messages::server::ServerMessage;
+
+use crate::game::{
+    components::{ClientEntity, EventObject, ObjectVariables, Position},
+    resources::{DelayedQuestTriggers, GameConfig, ServerMessages, ZoneList},
+    storage::{
+        writer::queue_storage_write,
+        zone::{ZoneStorage, ZoneStorageDelayedTrigger, ZoneStorageEventObject},
+    },
+};
+
+pub fn event_object_system(
+    mut query: Query<(&EventObject, &Position, &mut ObjectVariables), Without<ClientEntity>>,
+    client_entity_query: Query<(&ClientEntity, Ref<ObjectVariables>), Changed<ObjectVariables>>,
+    delayed_quest_triggers: Res<DelayedQuestTriggers>,
+    game_config: Res<GameConfig>,
+    time: Res<Time>,
+    zone_list: Res<ZoneList>,
+    mut server_messages: ResMut<ServerMessages>,
+    mut delayed_trigger_zones: Local<HashSet<ZoneId>>,
+) {
+    // Apply scheduled event object state
+    if !game_config.event_object_schedules.is_empty() {
+        for schedule in game_config.event_object_schedules.iter() {
+            let value = if schedule.window.is_active_now() {
+                schedule.active_value
+            } else {
+                schedule.inactive_value
+            };
+
+            if let Some(entity) = zone_list.find_event_object(
+                schedule.zone_id,
+                schedule.event_id,
+                schedule.map_chunk_x,
+                schedule.map_chunk_y,
+            ) {
+                if let Ok((_, _, mut object_variables)) = query.get_mut(entity) {
+                    if object_variables.variables.get(schedule.variable_id) != Some(&value) {
+                        if let Some(variable) =
+                            object_variables.variables.get_mut(schedule.variable_id)
+                        {
+                            *variable = value;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Clients address event status by object index, so it can only be sent for npcs which have
+    // been spawned to them, the initial status is sent with the spawn
+    for (client_entity, object_variables) in client_entity_query.iter() {
+        if !client_entity.is_npc() || object_variables.is_added() {
+            continue;
+        }
+
+        server_messages.send_entity_message(
+            client_entity,
+            ServerMessage::SetEventStatus {
+                entity_id: client_entity.id,
+                event_status: object_variables.event_status(),
+            },
+        );
+    }
+
+    // Collect which zones need their event object state saved
+    let mut changed_zones = HashSet::new();
+    for (_, position, object_variables) in query.iter_mut() {
+        if object_variables.is_changed() && !object_variables.is_added() {
+            changed_zones.insert(position.zone_id);
+        }
+    }
+
+    // Save zones whose delayed quest triggers have been added or run, including the
+    // zones which no longer have any
+    if delayed_quest_triggers.is_changed() {
+        let trigger_zones: HashSet<ZoneId> = delayed_quest_triggers
+            .pending
+            .iter()
+            .filter_map(|delayed_trigger| delayed_trigger.selected_event_object)
+            .filter_map(|entity| query.get(entity).ok())
+            .map(|(_, position, _)| position.zone_id)
+            .collect();
+        changed_zones.extend(delayed_trigger_zones.iter().copied());
+        changed_zones.extend(trigger_zones.iter().copied());
+        *delayed_trigger_zones = trigger_zones;
+    }
+
+    let now = time.elapsed();
+
+    for zone_id in changed_zones {
+        let zone_storage = ZoneStorage {
+            event_objects: query
+                .iter()
+                .filter(|(_, position, object_variables)| {
+                    position.zone_id == zone_id
+                        && object_variables.variables.iter().any(|value| *value != 0)
+                })
+                .map(
+                    |(event_object, _, object_variables)| ZoneStorageEventObject {
+                        event_id: event_object.event_id,
+                        map_chunk_x: event_object.map_chunk_x,
+                        map_chunk_y: event_object.map_chunk_y,
+                        variables: object_variables.variables.clone(),
+                    },
+                )
+                .collect(),
+            delayed_triggers: delayed_quest_triggers
+                .pending
+                .iter()
+                .filter_map(|delayed_trigger| {
+                    let (event_object, position, _) =
+                        query.get(delayed_trigger.selected_event_object?).ok()?;
+                    let remaining = delayed_trigger.run_at.saturating_sub(now);
+                    (position.zone_id == zone_id).then(|| ZoneStorageDelayedTrigger {
+                        event_id: event_object.event_id,
+                        map_chunk_x: event_object.map_chunk_x,
+                        map_chunk_y: event_object.map_chunk_y,
+                        trigger_hash: delayed_trigger.trigger_hash.hash,
+                        remaining_ms: remaining.as_millis() as u64,
+                    })
+                })
+                .collect(),
+        };
+
+        queue_storage_write(format!("save zone {}", zone_id.get()), move || {
+            zone_storage.save(zone_id)
+        });
+    }
+}
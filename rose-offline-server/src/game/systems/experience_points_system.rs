@@ -8,7 +8,7 @@ use crate::game::{
     },
     events::{QuestTriggerEvent, RewardXpEvent},
     messages::server::ServerMessage,
-    resources::{ServerMessages, WorldRates},
+    resources::{GameConfig, ServerMessages, WorldRates},
     GameData,
 };
 
@@ -33,6 +33,7 @@ pub fn experience_points_system(
         &StatusEffects,
     )>,
     source_entity_query: Query<&ClientEntity>,
+    game_config: Res<GameConfig>,
     game_data: Res<GameData>,
     world_rates: Res<WorldRates>,
     mut quest_trigger_events: EventWriter<QuestTriggerEvent>,
@@ -68,7 +69,6 @@ pub fn experience_points_system(
                 }
             }
 
-            // TODO: Apply level cap
             // TODO: Penalty xp?
 
             let level_before = level.level;
@@ -76,6 +76,12 @@ pub fn experience_points_system(
                 let need_xp = game_data
                     .ability_value_calculator
                     .calculate_levelup_require_xp(level.level);
+                if game_config.ability_values.is_max_level(level.level) {
+                    // Experience stops accumulating at the level cap
+                    experience_points.xp = experience_points.xp.min(need_xp);
+                    break;
+                }
+
                 if experience_points.xp < need_xp {
                     break;
                 }
@@ -1,6 +1,8 @@
+use std::{cmp::Reverse, time::Duration};
+
 use bevy::{
     ecs::{
-        prelude::{Commands, Entity, EventWriter, Query, Res, ResMut, Without},
+        prelude::{Commands, Entity, EventWriter, Query, Res, ResMut, With, Without},
         query::WorldQuery,
         system::SystemParam,
     },
@@ -9,10 +11,16 @@ use bevy::{
 };
 use log::warn;
 
-use rose_data::{EquipmentIndex, Item, ItemClass, ItemSlotBehaviour, ItemType};
+use rose_data::{
+    EquipmentIndex, Item, ItemClass, ItemSlotBehaviour, ItemType, SkillId, SkillPageKind,
+};
 use rose_game_common::{
+    components::HOTBAR_PAGE_SIZE,
     data::Password,
-    messages::server::{CharacterData, CharacterDataItems, CraftInsertGemError},
+    messages::{
+        client::InventorySortType,
+        server::{CharacterData, CharacterDataItems, CraftInsertGemError, InspectCharacterError},
+    },
 };
 
 use crate::game::{
@@ -21,30 +29,219 @@ use crate::game::{
         skill_list_try_level_up_skill, CharacterBundle, ItemDropBundle, SkillListBundle,
     },
     components::{
-        AbilityValues, Account, Bank, BasicStatType, BasicStats, CharacterInfo, Clan, ClanMember,
+        AbilityValues, Account, Bank, BasicStatType, BasicStats, CharacterInfo,
+        CharacterLootSettings, CharacterPrivacy, CharacterStatistics, Clan, ClanMember,
         ClanMembership, ClientEntity, ClientEntitySector, ClientEntityType, ClientEntityVisibility,
         Command, CommandData, Cooldowns, DamageSources, Dead, DrivingTime, DroppedItem, Equipment,
-        EquipmentItemDatabase, ExperiencePoints, GameClient, HealthPoints, Hotbar, Inventory,
-        ItemSlot, Level, ManaPoints, Money, MotionData, MoveMode, MoveSpeed, NextCommand, Party,
-        PartyMember, PartyMembership, PassiveRecoveryTime, Position, QuestState, SkillList,
-        SkillPoints, StatPoints, StatusEffects, StatusEffectsRegen, Team, WorldClient,
+        EquipmentItemDatabase, ExperiencePoints, GameClient, HealthPoints, Hotbar, HotbarSlot,
+        Inventory, ItemDrop, ItemSlot, Level, LinkDead, ManaPoints, Money, MotionData, MoveMode,
+        MoveSpeed, NewCharacter, NextCommand, Owner, Party, PartyMember, PartyMembership,
+        PartyOwner, PassiveRecoveryTime, Position, QuestState, SkillList, SkillPoints, SkillSlot,
+        Spectator, Stamina, StatPoints, StatusEffects, StatusEffectsRegen, Team, UnionMembership,
+        WorldClient, ZoneChange, ZoneChangeState, INVENTORY_PAGE_SIZE,
     },
     events::{
         BankEvent, ChatCommandEvent, ClanEvent, EquipmentEvent, ItemLifeEvent, NpcStoreEvent,
         PartyEvent, PartyMemberEvent, PersonalStoreEvent, QuestTriggerEvent, ReviveEvent,
-        RevivePosition, UseItemEvent,
+        RevivePosition, SaveEvent, TitleEvent, ToggleSkillEvent, UseItemEvent,
     },
     messages::{
         client::ClientMessage,
         server::{ConnectionRequestError, ServerMessage},
     },
-    resources::{ClientEntityList, GameData, LoginTokens, ServerMessages, WorldRates, WorldTime},
+    resources::{
+        ClientEntityList, GameConfig, GameData, LoginToken, LoginTokens, ServerMessages,
+        WorldRates, WorldTime, ZoneChecksumEnforcement,
+    },
     storage::{account::AccountStorage, bank::BankStorage, character::CharacterStorage},
+    systems::can_pickup_item_drop,
 };
 
+// Allow some height above terrain for bridges, buildings etc
+const MAX_MOVE_HEIGHT_ABOVE_TERRAIN: f32 = 2000.0;
+
+const INSPECT_CHARACTER_COOLDOWN: Duration = Duration::from_secs(2);
+
+const MAX_ZONE_CHANGE_DEFERRED_MESSAGES: usize = 64;
+
+fn clamp_move_destination(game_data: &GameData, position: &Position, destination: Vec3) -> Vec3 {
+    let Some(zone_data) = game_data.zones.get_zone(position.zone_id) else {
+        return destination;
+    };
+
+    let destination = zone_data
+        .heightmap
+        .clamp_height(destination, MAX_MOVE_HEIGHT_ABOVE_TERRAIN);
+
+    // Do not allow moving through zone objects
+    zone_data.trace_movement(position.position, destination)
+}
+
+/// Returns false for messages which reference entities or positions in the previous zone, these
+/// are dropped rather than deferred until the new zone is joined.
+fn is_zone_change_deferrable(message: &ClientMessage) -> bool {
+    !matches!(
+        message,
+        ClientMessage::Move { .. }
+            | ClientMessage::MoveCollision { .. }
+            | ClientMessage::Attack { .. }
+            | ClientMessage::PickupItemDrop { .. }
+            | ClientMessage::PickupNearestItemDrop
+            | ClientMessage::CastSkillSelf { .. }
+            | ClientMessage::CastSkillTargetEntity { .. }
+            | ClientMessage::CastSkillTargetPosition { .. }
+            | ClientMessage::InspectCharacter { .. }
+            | ClientMessage::NpcStoreTransaction { .. }
+            | ClientMessage::PersonalStoreListItems { .. }
+            | ClientMessage::PersonalStoreBuyItem { .. }
+            | ClientMessage::UseEmote { .. }
+            | ClientMessage::WarpGateRequest { .. }
+    )
+}
+
+fn get_castable_skill(
+    game_data: &GameData,
+    skill_list: &SkillList,
+    clan_membership: &ClanMembership,
+    skill_slot: SkillSlot,
+) -> Option<SkillId> {
+    let skill_id = skill_list.get_skill(skill_slot)?;
+
+    match game_data
+        .data_decoder
+        .decode_skill_page_kind(skill_slot.0)?
+    {
+        // Passive skills are always applied and cannot be cast
+        SkillPageKind::Passive => None,
+        SkillPageKind::Clan if clan_membership.clan().is_none() => None,
+        _ => Some(skill_id),
+    }
+}
+
+#[derive(WorldQuery)]
+pub struct LinkDeadCharacterQuery<'w> {
+    client_entity: Option<&'w ClientEntity>,
+    client_entity_sector: Option<&'w ClientEntitySector>,
+    character_info: &'w CharacterInfo,
+    position: &'w Position,
+    basic_stats: &'w BasicStats,
+    level: &'w Level,
+    equipment: &'w Equipment,
+    experience_points: &'w ExperiencePoints,
+    skill_list: &'w SkillList,
+    hotbar: &'w Hotbar,
+    health_points: &'w HealthPoints,
+    mana_points: &'w ManaPoints,
+    stat_points: &'w StatPoints,
+    skill_points: &'w SkillPoints,
+    union_membership: &'w UnionMembership,
+    stamina: &'w Stamina,
+    inventory: &'w Inventory,
+    quest_state: &'w QuestState,
+    _link_dead: &'w LinkDead,
+}
+
+fn handle_game_resume_request(
+    commands: &mut Commands,
+    client_entity_list: &mut ClientEntityList,
+    login_token: &mut LoginToken,
+    entity: Entity,
+    game_client: &GameClient,
+    password: &Password,
+    character_entity: Entity,
+    query_link_dead: &Query<LinkDeadCharacterQuery>,
+) -> Result<
+    (
+        u32,
+        Box<CharacterData>,
+        Box<CharacterDataItems>,
+        Box<QuestState>,
+    ),
+    ConnectionRequestError,
+> {
+    let character = query_link_dead
+        .get(character_entity)
+        .map_err(|_| ConnectionRequestError::InvalidToken)?;
+
+    // Verify account password
+    AccountStorage::try_load(&login_token.username, password).map_err(|error| {
+        log::error!(
+            "Failed to load account {} with error {:?}",
+            &login_token.username,
+            error
+        );
+        ConnectionRequestError::InvalidPassword
+    })?;
+
+    log::info!(
+        "Resuming session of link dead character {}",
+        &character.character_info.name
+    );
+
+    // The character keeps its entity, messages from the new connection are moved onto it
+    login_token.game_client_connection = Some(entity);
+    commands.entity(entity).despawn();
+
+    // Rejoin the zone so the client receives the entities around the character again
+    if let (Some(client_entity), Some(client_entity_sector)) =
+        (character.client_entity, character.client_entity_sector)
+    {
+        client_entity_leave_zone(
+            commands,
+            client_entity_list,
+            character_entity,
+            client_entity,
+            client_entity_sector,
+            character.position,
+        );
+    }
+
+    commands
+        .entity(character_entity)
+        .remove::<LinkDead>()
+        .insert((
+            GameClient {
+                client_message_rx: game_client.client_message_rx.clone(),
+                server_message_tx: game_client.server_message_tx.clone(),
+                login_token: login_token.token,
+                world_client_entity: login_token.world_client,
+                watch: game_client.watch.clone(),
+                capabilities: game_client.capabilities,
+            },
+            ZoneChange::default(),
+        ));
+
+    Ok((
+        123,
+        Box::new(CharacterData {
+            character_info: character.character_info.clone(),
+            position: character.position.position,
+            zone_id: character.position.zone_id,
+            basic_stats: character.basic_stats.clone(),
+            level: *character.level,
+            equipment: character.equipment.clone(),
+            experience_points: *character.experience_points,
+            skill_list: character.skill_list.clone(),
+            hotbar: character.hotbar.clone(),
+            health_points: *character.health_points,
+            mana_points: *character.mana_points,
+            stat_points: *character.stat_points,
+            skill_points: *character.skill_points,
+            union_membership: character.union_membership.clone(),
+            stamina: *character.stamina,
+        }),
+        Box::new(CharacterDataItems {
+            inventory: character.inventory.clone(),
+            equipment: character.equipment.clone(),
+        }),
+        Box::new(character.quest_state.clone()),
+    ))
+}
+
 fn handle_game_connection_request(
     commands: &mut Commands,
     game_data: &GameData,
+    client_entity_list: &mut ClientEntityList,
     login_tokens: &mut LoginTokens,
     entity: Entity,
     game_client: &mut GameClient,
@@ -52,6 +249,8 @@ fn handle_game_connection_request(
     password: &Password,
     query_world_client: &mut Query<&mut WorldClient>,
     query_clans: &mut Query<(Entity, &mut Clan)>,
+    query_link_dead: &Query<LinkDeadCharacterQuery>,
+    query_accounts: &Query<&Account, With<CharacterInfo>>,
 ) -> Result<
     (
         u32,
@@ -61,14 +260,52 @@ fn handle_game_connection_request(
     ),
     ConnectionRequestError,
 > {
+    // An account can only have one character in game at a time across all game servers
+    if let Some(other_session) = login_tokens.find_other_game_session(token_id) {
+        log::warn!(
+            "Rejected game connection for account {} which already has a character in game",
+            &other_session.username
+        );
+        return Err(ConnectionRequestError::AlreadyLoggedIn);
+    }
+
     // Verify token
     let login_token = login_tokens
         .get_token_mut(token_id)
         .ok_or(ConnectionRequestError::InvalidToken)?;
+    if let Some(character_entity) = login_token
+        .game_client
+        .filter(|game_client| query_link_dead.contains(*game_client))
+    {
+        return handle_game_resume_request(
+            commands,
+            client_entity_list,
+            login_token,
+            entity,
+            game_client,
+            password,
+            character_entity,
+            query_link_dead,
+        );
+    }
+
     if login_token.world_client.is_none() || login_token.game_client.is_some() {
         return Err(ConnectionRequestError::InvalidToken);
     }
 
+    // A character of the account may still be in game after its session ended, e.g. whilst
+    // waiting to be saved and removed
+    if query_accounts
+        .iter()
+        .any(|account| account.name == login_token.username)
+    {
+        log::warn!(
+            "Rejected game connection for account {} which already has a character in game",
+            &login_token.username
+        );
+        return Err(ConnectionRequestError::AlreadyLoggedIn);
+    }
+
     let mut world_client =
         if let Ok(world_client) = query_world_client.get_mut(login_token.world_client.unwrap()) {
             world_client
@@ -108,7 +345,7 @@ fn handle_game_connection_request(
     };
 
     // Try load character
-    let character =
+    let mut character =
         CharacterStorage::try_load(&login_token.selected_character).map_err(|error| {
             log::error!(
                 "Failed to load character {} with error {:?}",
@@ -118,6 +355,25 @@ fn handle_game_connection_request(
             ConnectionRequestError::Failed
         })?;
 
+    // Characters saved before a skill page existed cannot learn skills on that page
+    character
+        .skill_list
+        .add_missing_pages(&game_data.character_creator.create_skill_list());
+
+    // Remove any hotbar slots referencing items or skills the character no longer has
+    let removed_hotbar_slots = character.hotbar.remove_invalid_slots(
+        &character.equipment,
+        &character.inventory,
+        &character.skill_list,
+    );
+    if !removed_hotbar_slots.is_empty() {
+        log::warn!(
+            "Removed invalid hotbar slots {:?} from character {}",
+            removed_hotbar_slots,
+            &character.info.name
+        );
+    }
+
     // Try find clan membership
     let mut clan_membership = ClanMembership(None);
     for (clan_entity, mut clan) in query_clans.iter_mut() {
@@ -125,6 +381,7 @@ fn handle_game_connection_request(
             let &mut ClanMember::Offline {
                 position,
                 contribution,
+                stats,
                 ..
             } = clan_member
             else {
@@ -135,6 +392,7 @@ fn handle_game_connection_request(
                 entity,
                 position,
                 contribution,
+                stats,
             };
             clan_membership = ClanMembership::new(clan_entity);
             break;
@@ -229,8 +487,18 @@ fn handle_game_connection_request(
             union_membership: character.union_membership.clone(),
             clan_membership,
         },
+        character.privacy,
+        character.titles.clone(),
+        character.statistics.clone(),
+        character.loot_settings,
+        character.skill_mastery.clone(),
+        ZoneChange::default(),
     ));
 
+    if character.is_new_character {
+        commands.entity(entity).insert(NewCharacter);
+    }
+
     Ok((
         123,
         Box::new(CharacterData {
@@ -263,6 +531,9 @@ pub fn game_server_authentication_system(
     mut query: Query<(Entity, &mut GameClient), Without<CharacterInfo>>,
     mut query_world_client: Query<&mut WorldClient>,
     mut query_clans: Query<(Entity, &mut Clan)>,
+    query_link_dead: Query<LinkDeadCharacterQuery>,
+    query_accounts: Query<&Account, With<CharacterInfo>>,
+    mut client_entity_list: ResMut<ClientEntityList>,
     mut login_tokens: ResMut<LoginTokens>,
     game_data: Res<GameData>,
 ) {
@@ -276,6 +547,7 @@ pub fn game_server_authentication_system(
                     match handle_game_connection_request(
                         &mut commands,
                         game_data.as_ref(),
+                        client_entity_list.as_mut(),
                         login_tokens.as_mut(),
                         entity,
                         game_client.as_mut(),
@@ -283,6 +555,8 @@ pub fn game_server_authentication_system(
                         &password,
                         &mut query_world_client,
                         &mut query_clans,
+                        &query_link_dead,
+                        &query_accounts,
                     ) {
                         Ok((
                             packet_sequence_id,
@@ -329,41 +603,136 @@ pub fn game_server_authentication_system(
     });
 }
 
+/// Disconnects a client which has not joined its zone, keeping it in the disconnecting state so
+/// it can not join the zone or replay deferred messages before the connection closes.
+fn disconnect_joining_client(commands: &mut Commands, entity: Entity, game_client: &GameClient) {
+    game_client
+        .server_message_tx
+        .send(ServerMessage::Disconnect)
+        .ok();
+    commands.entity(entity).insert(ZoneChange {
+        state: ZoneChangeState::Disconnecting,
+        ..Default::default()
+    });
+}
+
 pub fn game_server_join_system(
     mut commands: Commands,
-    query: Query<
+    mut query: Query<
         (
             Entity,
             &GameClient,
             &CharacterInfo,
+            &Equipment,
             &ExperiencePoints,
             &Team,
             &HealthPoints,
             &ManaPoints,
             &Position,
+            Option<&mut ZoneChange>,
         ),
         Without<ClientEntity>,
     >,
     mut client_entity_list: ResMut<ClientEntityList>,
+    game_config: Res<GameConfig>,
+    game_data: Res<GameData>,
+    time: Res<Time>,
     world_rates: Res<WorldRates>,
     world_time: Res<WorldTime>,
     mut party_query: Query<(Entity, &mut Party)>,
     mut party_member_events: EventWriter<PartyMemberEvent>,
+    mut save_events: EventWriter<SaveEvent>,
 ) {
-    query.for_each(
+    let zone_change_timeout = Duration::from_secs(game_config.zone_change_timeout_secs);
+
+    query.for_each_mut(
         |(
             entity,
             game_client,
             character_info,
+            equipment,
             experience_points,
             team,
             health_points,
             mana_points,
             position,
+            mut zone_change,
         )| {
+            if let Some(zone_change) = zone_change.as_mut() {
+                if zone_change.state == ZoneChangeState::Disconnecting {
+                    // Discard any messages until the connection closes
+                    while game_client.client_message_rx.try_recv().is_ok() {}
+                    return;
+                }
+
+                zone_change.loading_duration += time.delta();
+
+                if zone_change.loading_duration > zone_change_timeout {
+                    warn!(
+                        "Disconnecting character {} which did not join zone {} within {:?}",
+                        character_info.name,
+                        position.zone_id.get(),
+                        zone_change_timeout
+                    );
+                    disconnect_joining_client(&mut commands, entity, game_client);
+                    return;
+                }
+            }
+
             if let Ok(message) = game_client.client_message_rx.try_recv() {
                 match message {
-                    ClientMessage::JoinZoneRequest => {
+                    ClientMessage::JoinZoneRequest { zone_checksum } => {
+                        let server_zone_checksum = game_data
+                            .zones
+                            .get_zone(position.zone_id)
+                            .map(|zone_data| zone_data.checksum);
+
+                        // Only patched clients report a checksum, so there is nothing to verify
+                        // for the official client
+                        if zone_checksum.is_some()
+                            && server_zone_checksum.is_some()
+                            && zone_checksum != server_zone_checksum
+                        {
+                            match game_config.zone_checksum_enforcement {
+                                ZoneChecksumEnforcement::Log => {
+                                    log::info!(
+                                        "Character {} has modified data for zone {}",
+                                        character_info.name,
+                                        position.zone_id.get()
+                                    );
+                                }
+                                ZoneChecksumEnforcement::Warn => {
+                                    warn!(
+                                        "Character {} has modified data for zone {}",
+                                        character_info.name,
+                                        position.zone_id.get()
+                                    );
+                                    game_client
+                                        .server_message_tx
+                                        .send(ServerMessage::Whisper {
+                                            from: String::from("SERVER"),
+                                            text: String::from(
+                                                "Your map data differs from the server, please repair your client",
+                                            ),
+                                        })
+                                        .ok();
+                                }
+                                ZoneChecksumEnforcement::Kick => {
+                                    warn!(
+                                        "Disconnecting character {} with modified data for zone {}",
+                                        character_info.name,
+                                        position.zone_id.get()
+                                    );
+                                    disconnect_joining_client(
+                                        &mut commands,
+                                        entity,
+                                        game_client,
+                                    );
+                                    return;
+                                }
+                            }
+                        }
+
                         if let Ok(entity_id) = client_entity_join_zone(
                             &mut commands,
                             &mut client_entity_list,
@@ -371,6 +740,14 @@ pub fn game_server_join_system(
                             ClientEntityType::Character,
                             position,
                         ) {
+                            if let Some(zone_change) = zone_change.as_mut() {
+                                if zone_change.deferred_messages.is_empty() {
+                                    commands.entity(entity).remove::<ZoneChange>();
+                                } else {
+                                    zone_change.state = ZoneChangeState::Joined;
+                                }
+                            }
+
                             // See if we are in a party as an offline member
                             let mut party_membership = PartyMembership::default();
                             for (party_entity, mut party) in party_query.iter_mut() {
@@ -403,6 +780,11 @@ pub fn game_server_join_system(
                                 .insert(ClientEntityVisibility::new())
                                 .insert(PassiveRecoveryTime::default());
 
+                            save_events.send(SaveEvent::Character {
+                                entity,
+                                remove_after_save: false,
+                            });
+
                             game_client
                                 .server_message_tx
                                 .send(ServerMessage::JoinZone {
@@ -416,11 +798,47 @@ pub fn game_server_join_system(
                                     world_price_rate: world_rates.world_price_rate,
                                     item_price_rate: world_rates.item_price_rate,
                                     town_price_rate: world_rates.town_price_rate,
+                                    zone_checksum: zone_checksum.and(server_zone_checksum),
                                 })
                                 .ok();
+
+                            // Costumes are not part of the character data sent before joining
+                            for (costume_index, item) in equipment.equipped_costumes.iter() {
+                                if item.is_some() && game_client.capabilities.costume_slots {
+                                    game_client
+                                        .server_message_tx
+                                        .send(ServerMessage::UpdateCostume {
+                                            entity_id,
+                                            costume_index,
+                                            item: item.clone(),
+                                        })
+                                        .ok();
+                                }
+                            }
+                        }
+                    }
+                    message => {
+                        let Some(zone_change) = zone_change.as_mut() else {
+                            warn!("Received unexpected client message {:?}", message);
+                            return;
+                        };
+
+                        // Messages referencing the previous zone are no longer valid
+                        if !is_zone_change_deferrable(&message) {
+                            return;
+                        }
+
+                        if zone_change.deferred_messages.len() >= MAX_ZONE_CHANGE_DEFERRED_MESSAGES
+                        {
+                            warn!(
+                                "Disconnecting character {} for sending too many messages whilst changing zone",
+                                character_info.name
+                            );
+                            disconnect_joining_client(&mut commands, entity, game_client);
+                        } else {
+                            zone_change.deferred_messages.push_back(message);
                         }
                     }
-                    _ => warn!("Received unexpected client message {:?}", message),
                 }
             }
         },
@@ -438,6 +856,7 @@ pub struct GameClientQuery<'w> {
     ability_values: &'w AbilityValues,
     command: &'w Command,
     dead: Option<&'w Dead>,
+    spectator: Option<&'w Spectator>,
     level: &'w Level,
     move_speed: &'w MoveSpeed,
     team: &'w Team,
@@ -446,11 +865,16 @@ pub struct GameClientQuery<'w> {
     stat_points: &'w mut StatPoints,
     skill_points: &'w mut SkillPoints,
     skill_list: &'w mut SkillList,
+    clan_membership: &'w ClanMembership,
+    party_membership: Option<&'w PartyMembership>,
     hotbar: &'w mut Hotbar,
     equipment: &'w mut Equipment,
     inventory: &'w mut Inventory,
     quest_state: &'w mut QuestState,
     move_mode: &'w mut MoveMode,
+    cooldowns: &'w mut Cooldowns,
+    statistics: Option<&'w CharacterStatistics>,
+    zone_change: Option<&'w mut ZoneChange>,
 }
 
 #[derive(SystemParam)]
@@ -465,6 +889,9 @@ pub struct GameEvents<'w> {
     personal_store_events: EventWriter<'w, PersonalStoreEvent>,
     quest_trigger_events: EventWriter<'w, QuestTriggerEvent>,
     revive_events: EventWriter<'w, ReviveEvent>,
+    save_events: EventWriter<'w, SaveEvent>,
+    title_events: EventWriter<'w, TitleEvent>,
+    toggle_skill_events: EventWriter<'w, ToggleSkillEvent>,
     use_item_events: EventWriter<'w, UseItemEvent>,
 }
 
@@ -473,15 +900,37 @@ pub fn game_server_main_system(
     mut events: GameEvents,
     mut game_client_query: Query<GameClientQuery>,
     world_client_query: Query<&WorldClient>,
+    item_drop_query: Query<(Option<&Owner>, Option<&PartyOwner>), With<ItemDrop>>,
+    privacy_query: Query<&CharacterPrivacy>,
     mut client_entity_list: ResMut<ClientEntityList>,
     mut server_messages: ResMut<ServerMessages>,
+    game_config: Res<GameConfig>,
     game_data: Res<GameData>,
     time: Res<Time>,
 ) {
+    let mut pending_inspects = Vec::new();
+
     for mut game_client in game_client_query.iter_mut() {
         let mut entity_commands = commands.entity(game_client.entity);
 
-        if let Ok(message) = game_client.game_client.client_message_rx.try_recv() {
+        // Replay messages deferred whilst changing zone before receiving any new messages
+        let message = if let Some(zone_change) = game_client
+            .zone_change
+            .as_mut()
+            .filter(|zone_change| zone_change.state == ZoneChangeState::Joined)
+        {
+            let message = zone_change.deferred_messages.pop_front();
+            if zone_change.deferred_messages.is_empty() {
+                entity_commands.remove::<ZoneChange>();
+            }
+            message
+        } else {
+            game_client.game_client.client_message_rx.try_recv().ok()
+        };
+
+        if let Some(message) = message {
+            game_client.game_client.watch.log_recv(&message);
+
             match message {
                 ClientMessage::Chat { text } => {
                     if text.chars().next().map_or(false, |c| c == '/') {
@@ -514,7 +963,16 @@ pub fn game_server_main_system(
                         }
                     }
 
-                    let destination = Vec3::new(x, y, z as f32);
+                    // Spectators can fly freely through the zone
+                    let destination = if game_client.spectator.is_some() {
+                        Vec3::new(x, y, z as f32)
+                    } else {
+                        clamp_move_destination(
+                            &game_data,
+                            game_client.position,
+                            Vec3::new(x, y, z as f32),
+                        )
+                    };
                     entity_commands.insert(NextCommand::with_move(
                         destination,
                         move_target_entity,
@@ -532,7 +990,19 @@ pub fn game_server_main_system(
                     }
                 }
                 ClientMessage::SetHotbarSlot { slot_index, slot } => {
-                    if game_client
+                    if slot.as_ref().map_or(false, |slot| {
+                        !Hotbar::is_valid_slot(
+                            slot,
+                            &game_client.equipment,
+                            &game_client.inventory,
+                            &game_client.skill_list,
+                        )
+                    }) {
+                        warn!(
+                            "Ignoring invalid hotbar slot {:?} for character {}",
+                            slot, game_client.character_info.name
+                        );
+                    } else if game_client
                         .hotbar
                         .set_slot(slot_index, slot.clone())
                         .is_some()
@@ -578,6 +1048,24 @@ pub fn game_server_main_system(
                         item_slot,
                     });
                 }
+                ClientMessage::ChangeCostume {
+                    costume_index,
+                    item_slot,
+                } => {
+                    // Costume slots are only enabled for patched clients which support them
+                    if game_client.game_client.capabilities.costume_slots {
+                        events.equipment_events.send(EquipmentEvent::ChangeCostume {
+                            entity: game_client.entity,
+                            costume_index,
+                            item_slot,
+                        });
+                    } else {
+                        warn!(
+                            "Character {:?} tried to change costume without costume slots enabled",
+                            game_client.entity
+                        );
+                    }
+                }
                 ClientMessage::IncreaseBasicStat { basic_stat_type } => {
                     if let Some(cost) = game_data
                         .ability_value_calculator
@@ -586,7 +1074,7 @@ pub fn game_server_main_system(
                             basic_stat_type,
                         )
                     {
-                        if cost < game_client.stat_points.points {
+                        if cost <= game_client.stat_points.points {
                             let value = match basic_stat_type {
                                 BasicStatType::Strength => &mut game_client.basic_stats.strength,
                                 BasicStatType::Dexterity => &mut game_client.basic_stats.dexterity,
@@ -624,6 +1112,46 @@ pub fn game_server_main_system(
                         entity_commands.insert(NextCommand::with_stop(true));
                     }
                 }
+                ClientMessage::PickupNearestItemDrop => {
+                    let pickup_party = game_client
+                        .party_membership
+                        .and_then(|party_membership| party_membership.party);
+                    let nearest_item_drop = client_entity_list
+                        .get_zone(game_client.position.zone_id)
+                        .and_then(|zone| {
+                            zone.iter_entity_type_within_distance(
+                                game_client.position.position.xy(),
+                                game_config.item_drops.pickup_nearest_distance,
+                                &[ClientEntityType::ItemDrop],
+                            )
+                            .filter(|(item_drop_entity, _)| {
+                                item_drop_query.get(*item_drop_entity).map_or(
+                                    false,
+                                    |(owner, party_owner)| {
+                                        can_pickup_item_drop(
+                                            &game_config.item_drops,
+                                            game_client.position.zone_id,
+                                            owner.map(|owner| owner.entity),
+                                            party_owner.map(|party_owner| party_owner.entity),
+                                            game_client.entity,
+                                            pickup_party,
+                                        )
+                                    },
+                                )
+                            })
+                            .min_by(|(_, a), (_, b)| {
+                                let position = game_client.position.position.xy();
+                                position
+                                    .distance_squared(a.xy())
+                                    .total_cmp(&position.distance_squared(b.xy()))
+                            })
+                        });
+
+                    if let Some((item_drop_entity, _)) = nearest_item_drop {
+                        entity_commands
+                            .insert(NextCommand::with_pickup_item_drop(item_drop_entity));
+                    }
+                }
                 ClientMessage::Logout | ClientMessage::ReturnToCharacterSelect => {
                     if let ClientMessage::ReturnToCharacterSelect = message {
                         // Send ReturnToCharacterSelect via world_client
@@ -643,6 +1171,12 @@ pub fn game_server_main_system(
                         .send(ServerMessage::LogoutSuccess)
                         .ok();
 
+                    // Save now rather than when the link dead reconnect grace expires
+                    events.save_events.send(SaveEvent::Character {
+                        entity: game_client.entity,
+                        remove_after_save: false,
+                    });
+
                     client_entity_leave_zone(
                         &mut commands,
                         &mut client_entity_list,
@@ -765,7 +1299,7 @@ pub fn game_server_main_system(
                             team: Some(game_client.team),
                             character_info: Some(&game_client.character_info),
                             experience_points: None,
-                            inventory: Some(&game_client.inventory),
+                            inventory: Some(&mut game_client.inventory),
                             stamina: None,
                             stat_points: None,
                             union_membership: None,
@@ -777,7 +1311,12 @@ pub fn game_server_main_system(
                     .ok();
                 }
                 ClientMessage::CastSkillSelf { skill_slot } => {
-                    if let Some(skill) = game_client.skill_list.get_skill(skill_slot) {
+                    if let Some(skill) = get_castable_skill(
+                        &game_data,
+                        &game_client.skill_list,
+                        game_client.clan_membership,
+                        skill_slot,
+                    ) {
                         entity_commands
                             .insert(NextCommand::with_cast_skill_target_self(skill, None));
                     }
@@ -786,7 +1325,12 @@ pub fn game_server_main_system(
                     skill_slot,
                     target_entity_id,
                 } => {
-                    if let Some(skill) = game_client.skill_list.get_skill(skill_slot) {
+                    if let Some(skill) = get_castable_skill(
+                        &game_data,
+                        &game_client.skill_list,
+                        game_client.clan_membership,
+                        skill_slot,
+                    ) {
                         if let Some((target_entity, _, _)) = client_entity_list
                             .get_zone(game_client.position.zone_id)
                             .and_then(|zone| zone.get_entity(target_entity_id))
@@ -803,12 +1347,37 @@ pub fn game_server_main_system(
                     skill_slot,
                     position,
                 } => {
-                    if let Some(skill) = game_client.skill_list.get_skill(skill_slot) {
+                    if let Some(skill) = get_castable_skill(
+                        &game_data,
+                        &game_client.skill_list,
+                        game_client.clan_membership,
+                        skill_slot,
+                    ) {
                         entity_commands.insert(NextCommand::with_cast_skill_target_position(
                             skill, position,
                         ));
                     }
                 }
+                ClientMessage::ToggleSkill {
+                    skill_slot,
+                    enabled,
+                } => {
+                    if !enabled {
+                        events.toggle_skill_events.send(ToggleSkillEvent::Disable {
+                            entity: game_client.entity,
+                        });
+                    } else if let Some(skill_id) = get_castable_skill(
+                        &game_data,
+                        &game_client.skill_list,
+                        game_client.clan_membership,
+                        skill_slot,
+                    ) {
+                        events.toggle_skill_events.send(ToggleSkillEvent::Enable {
+                            entity: game_client.entity,
+                            skill_id,
+                        });
+                    }
+                }
                 ClientMessage::NpcStoreTransaction {
                     npc_entity_id,
                     buy_items,
@@ -959,7 +1528,15 @@ pub fn game_server_main_system(
                     }
                 }
                 ClientMessage::UseEmote { motion_id, is_stop } => {
-                    entity_commands.insert(NextCommand::with_emote(motion_id, is_stop));
+                    if game_data.motions.is_emote_motion(motion_id) {
+                        entity_commands.insert(NextCommand::with_emote(motion_id, is_stop));
+                    } else {
+                        warn!(
+                            "Ignoring invalid emote motion {} for character {}",
+                            motion_id.get(),
+                            game_client.character_info.name
+                        );
+                    }
                 }
                 ClientMessage::WarpGateRequest { warp_gate_id } => {
                     if let Some(warp_gate) = game_data.warp_gates.get_warp_gate(warp_gate_id) {
@@ -1017,6 +1594,18 @@ pub fn game_server_main_system(
                         kick_character_id: character_id,
                     });
                 }
+                ClientMessage::PartyVoteKick { character_id } => {
+                    events.party_events.send(PartyEvent::VoteKick {
+                        voter_entity: game_client.entity,
+                        kick_character_id: character_id,
+                    });
+                }
+                ClientMessage::PartyVoteKickReply { accept } => {
+                    events.party_events.send(PartyEvent::VoteKickReply {
+                        voter_entity: game_client.entity,
+                        accept,
+                    });
+                }
                 ClientMessage::PartyAcceptCreateInvite { owner_entity_id }
                 | ClientMessage::PartyAcceptJoinInvite { owner_entity_id } => {
                     if let Some(&(owner_entity, _, _)) = client_entity_list
@@ -1056,6 +1645,11 @@ pub fn game_server_main_system(
                 }
                 ClientMessage::MoveCollision { position } => {
                     // TODO: Sanity check position
+                    let position = if game_client.spectator.is_some() {
+                        position
+                    } else {
+                        clamp_move_destination(&game_data, game_client.position, position)
+                    };
                     entity_commands
                         .insert(NextCommand::with_move(position, None, None))
                         .insert(Position::new(position, game_client.position.zone_id));
@@ -1179,6 +1773,83 @@ pub fn game_server_main_system(
                         is_premium,
                     });
                 }
+                ClientMessage::BankDepositInventoryPage { page_type } => {
+                    events.bank_events.send(BankEvent::DepositInventoryPage {
+                        entity: game_client.entity,
+                        page_type,
+                    });
+                }
+                ClientMessage::SortInventory {
+                    page_type,
+                    sort_type,
+                } => {
+                    let moved_items = match sort_type {
+                        InventorySortType::ItemType => {
+                            game_client.inventory.sort_page_by_key(page_type, |item| {
+                                (item.get_item_type() as usize, item.get_item_number())
+                            })
+                        }
+                        InventorySortType::Value => {
+                            game_client.inventory.sort_page_by_key(page_type, |item| {
+                                let base_price = game_data
+                                    .items
+                                    .get_base_item(item.get_item_reference())
+                                    .map_or(0, |item_data| item_data.base_price);
+                                (
+                                    Reverse(base_price),
+                                    item.get_item_type() as usize,
+                                    item.get_item_number(),
+                                )
+                            })
+                        }
+                    };
+
+                    game_client
+                        .game_client
+                        .server_message_tx
+                        .send(ServerMessage::UpdateInventory {
+                            items: (0..INVENTORY_PAGE_SIZE)
+                                .map(|index| {
+                                    let item_slot = ItemSlot::Inventory(page_type, index);
+                                    (
+                                        item_slot,
+                                        game_client.inventory.get_item(item_slot).cloned(),
+                                    )
+                                })
+                                .collect(),
+                            money: None,
+                        })
+                        .ok();
+
+                    // Keep hotbar slots pointing at the items they referenced before sorting
+                    for (page_index, page) in game_client.hotbar.pages.iter_mut().enumerate() {
+                        for (index, hotbar_slot) in page.iter_mut().enumerate() {
+                            let Some(HotbarSlot::Inventory(item_slot)) = hotbar_slot.as_mut()
+                            else {
+                                continue;
+                            };
+                            let Some(&(_, new_item_slot)) = moved_items
+                                .iter()
+                                .find(|(old_item_slot, _)| old_item_slot == item_slot)
+                            else {
+                                continue;
+                            };
+                            if new_item_slot == *item_slot {
+                                continue;
+                            }
+
+                            *item_slot = new_item_slot;
+                            game_client
+                                .game_client
+                                .server_message_tx
+                                .send(ServerMessage::SetHotbarSlot {
+                                    slot_index: page_index * HOTBAR_PAGE_SIZE + index,
+                                    slot: hotbar_slot.clone(),
+                                })
+                                .ok();
+                        }
+                    }
+                }
                 ClientMessage::RepairItemUsingNpc {
                     npc_entity_id,
                     item_slot,
@@ -1233,8 +1904,162 @@ pub fn game_server_main_system(
                         mark,
                     });
                 }
+                ClientMessage::ClanVaultOpen => {
+                    events.clan_events.send(ClanEvent::VaultOpen {
+                        entity: game_client.entity,
+                    });
+                }
+                ClientMessage::ClanVaultDeposit { item_slot, item } => {
+                    events.clan_events.send(ClanEvent::VaultDeposit {
+                        entity: game_client.entity,
+                        item_slot,
+                        item,
+                    });
+                }
+                ClientMessage::ClanVaultWithdraw { vault_slot, item } => {
+                    events.clan_events.send(ClanEvent::VaultWithdraw {
+                        entity: game_client.entity,
+                        vault_slot,
+                        item,
+                    });
+                }
+                ClientMessage::ClanVaultGetLog => {
+                    events.clan_events.send(ClanEvent::VaultGetLog {
+                        entity: game_client.entity,
+                    });
+                }
+                ClientMessage::ClanDonateMoney { money } => {
+                    events.clan_events.send(ClanEvent::DonateMoney {
+                        entity: game_client.entity,
+                        money,
+                    });
+                }
+                ClientMessage::ClanGetPermissions => {
+                    events.clan_events.send(ClanEvent::GetPermissions {
+                        entity: game_client.entity,
+                    });
+                }
+                ClientMessage::ClanSetPermissions { permissions } => {
+                    events.clan_events.send(ClanEvent::SetPermissions {
+                        requester: game_client.entity,
+                        permissions,
+                    });
+                }
+                ClientMessage::ClanSetRecruitment { notice } => {
+                    events.clan_events.send(ClanEvent::SetRecruitment {
+                        requester: game_client.entity,
+                        notice,
+                    });
+                }
+                ClientMessage::ClanSearchRecruitment { query } => {
+                    events.clan_events.send(ClanEvent::SearchRecruitment {
+                        entity: game_client.entity,
+                        query,
+                    });
+                }
+                ClientMessage::ClanApply { clan_name, message } => {
+                    events.clan_events.send(ClanEvent::Apply {
+                        entity: game_client.entity,
+                        clan_name,
+                        message,
+                    });
+                }
+                ClientMessage::ClanGetApplications => {
+                    events.clan_events.send(ClanEvent::GetApplications {
+                        entity: game_client.entity,
+                    });
+                }
+                ClientMessage::ClanApplicationReply { name, accepted } => {
+                    events.clan_events.send(ClanEvent::ApplicationReply {
+                        requester: game_client.entity,
+                        name,
+                        accepted,
+                    });
+                }
+                ClientMessage::InspectCharacter { target_entity_id } => {
+                    let now = time.last_update().unwrap();
+                    if game_client
+                        .cooldowns
+                        .inspect
+                        .map_or(false, |inspect_cooldown| now < inspect_cooldown)
+                    {
+                        game_client
+                            .game_client
+                            .server_message_tx
+                            .send(ServerMessage::InspectCharacterError {
+                                error: InspectCharacterError::TooFrequent,
+                            })
+                            .ok();
+                    } else if let Some((target_entity, _, _)) = client_entity_list
+                        .get_zone(game_client.position.zone_id)
+                        .and_then(|zone| zone.get_entity(target_entity_id))
+                    {
+                        game_client.cooldowns.inspect = Some(now + INSPECT_CHARACTER_COOLDOWN);
+                        pending_inspects.push((game_client.entity, *target_entity));
+                    } else {
+                        game_client
+                            .game_client
+                            .server_message_tx
+                            .send(ServerMessage::InspectCharacterError {
+                                error: InspectCharacterError::NotFound,
+                            })
+                            .ok();
+                    }
+                }
+                ClientMessage::SetAllowInspect { allow_inspect } => {
+                    entity_commands.insert(CharacterPrivacy { allow_inspect });
+                }
+                ClientMessage::RequestCharacterStatistics => {
+                    game_client
+                        .game_client
+                        .server_message_tx
+                        .send(ServerMessage::CharacterStatistics {
+                            statistics: game_client.statistics.cloned().unwrap_or_default(),
+                        })
+                        .ok();
+                }
+                ClientMessage::SetAutoLoot { auto_loot } => {
+                    entity_commands.insert(CharacterLootSettings { auto_loot });
+                }
+                ClientMessage::SetActiveTitle { title_id } => {
+                    events.title_events.send(TitleEvent::SetActive {
+                        entity: game_client.entity,
+                        title_id,
+                    });
+                }
                 _ => warn!("[GS] Received unimplemented client message {:?}", message),
             }
         }
     }
+
+    // The target is read once every client has been iterated mutably above
+    for (requester_entity, target_entity) in pending_inspects {
+        let Ok(requester) = game_client_query.get(requester_entity) else {
+            continue;
+        };
+
+        let message = match game_client_query.get(target_entity) {
+            Ok(target)
+                if privacy_query
+                    .get(target_entity)
+                    .map_or(true, |privacy| privacy.allow_inspect) =>
+            {
+                ServerMessage::InspectCharacter {
+                    entity_id: target.client_entity.id,
+                    name: target.character_info.name.clone(),
+                    level: *target.level,
+                    job: target.character_info.job,
+                    equipment: target.equipment.clone(),
+                }
+            }
+            Ok(_) => ServerMessage::InspectCharacterError {
+                error: InspectCharacterError::Private,
+            },
+            Err(_) => ServerMessage::InspectCharacterError {
+                error: InspectCharacterError::NotFound,
+            },
+        };
+
+        requester.game_client.server_message_tx.send(message).ok();
+    }
 }
@@ -0,0 +1,81 @@
+use std::time::Duration;
+
+use bevy::{
+    ecs::prelude::{EventReader, EventWriter, Query, Res},
+    time::Time,
+};
+use log::warn;
+use rand::seq::SliceRandom;
+
+use rose_data::Item;
+
+use crate::game::{
+    components::GatherNode,
+    events::{GatherEvent, RewardItemEvent},
+    resources::GameData,
+};
+
+pub fn gather_system(
+    mut query_gather_node: Query<&mut GatherNode>,
+    mut gather_events: EventReader<GatherEvent>,
+    mut reward_item_events: EventWriter<RewardItemEvent>,
+    game_data: Res<GameData>,
+    time: Res<Time>,
+) {
+    let mut rng = rand::thread_rng();
+
+    for gather_event in gather_events.iter() {
+        let Ok(mut gather_node) = query_gather_node.get_mut(gather_event.node_entity) else {
+            continue;
+        };
+
+        // Someone else may have depleted the node whilst we were gathering
+        if gather_node.is_depleted() {
+            continue;
+        }
+
+        let Some(gather_node_data) = game_data.gather_nodes.get_node(gather_node.node_index) else {
+            continue;
+        };
+
+        gather_node.remaining_gathers -= 1;
+
+        let Ok(gather_item) = gather_node_data
+            .items
+            .choose_weighted(&mut rng, |gather_item| gather_item.weight)
+        else {
+            continue;
+        };
+
+        let Some(item) = game_data
+            .items
+            .get_base_item(gather_item.item)
+            .and_then(|item_data| Item::from_item_data(item_data, gather_item.quantity))
+        else {
+            warn!(
+                "Invalid item {:?} in gather node {}",
+                gather_item.item, gather_node_data.name
+            );
+            continue;
+        };
+
+        reward_item_events.send(RewardItemEvent::new(gather_event.gather_entity, item, true));
+    }
+
+    // Respawn depleted nodes
+    for mut gather_node in query_gather_node.iter_mut() {
+        if !gather_node.is_depleted() {
+            continue;
+        }
+
+        let Some(gather_node_data) = game_data.gather_nodes.get_node(gather_node.node_index) else {
+            continue;
+        };
+
+        gather_node.time_since_depleted += time.delta();
+        if gather_node.time_since_depleted >= Duration::from_secs(gather_node_data.respawn_secs) {
+            gather_node.remaining_gathers = gather_node_data.max_gathers;
+            gather_node.time_since_depleted = Duration::ZERO;
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use std::{collections::HashMap, time::Duration};
+
+use bevy::{
+    ecs::prelude::{Entity, EventWriter, Local, Query, Res, ResMut},
+    time::Time,
+};
+use log::warn;
+
+use crate::game::{
+    components::{CharacterInfo, ClientEntity, GameClient, Position},
+    events::SaveEvent,
+    resources::{GameConfig, LoginTokens},
+};
+
+/// Removes characters which are still in a zone after their game client connection has closed,
+/// such as when the control message removing the client was lost.
+pub fn ghost_client_system(
+    query: Query<(
+        Entity,
+        &GameClient,
+        &ClientEntity,
+        &Position,
+        Option<&CharacterInfo>,
+    )>,
+    game_config: Res<GameConfig>,
+    mut login_tokens: ResMut<LoginTokens>,
+    mut save_events: EventWriter<SaveEvent>,
+    mut closed_since: Local<HashMap<Entity, Duration>>,
+    time: Res<Time>,
+) {
+    if game_config.ghost_client_grace_secs == 0 {
+        return;
+    }
+
+    let ghost_client_grace = Duration::from_secs(game_config.ghost_client_grace_secs);
+    let now = time.elapsed();
+
+    closed_since.retain(|entity, _| {
+        query.get(*entity).map_or(false, |(_, game_client, ..)| {
+            game_client.server_message_tx.is_closed()
+        })
+    });
+
+    for (entity, game_client, client_entity, position, character_info) in query.iter() {
+        if !game_client.server_message_tx.is_closed() {
+            continue;
+        }
+
+        let closed_at = *closed_since.entry(entity).or_insert(now);
+        if now - closed_at < ghost_client_grace {
+            continue;
+        }
+
+        warn!(
+            "Removing ghost character {} ({:?}, client entity {:?}) in zone {} at {:?}, its connection closed {:?} ago",
+            character_info.map_or("unknown", |character_info| character_info.name.as_str()),
+            entity,
+            client_entity.id,
+            position.zone_id.get(),
+            position.position,
+            now - closed_at
+        );
+
+        for (index, login_token) in login_tokens.tokens.iter_mut().enumerate() {
+            if login_token.game_client == Some(entity) {
+                login_token.game_client = None;
+                login_token.game_client_connection = None;
+
+                if login_token.world_client.is_none() {
+                    login_tokens.tokens.remove(index);
+                }
+                break;
+            }
+        }
+
+        // Let the save system handle despawning the entity
+        save_events.send(SaveEvent::Character {
+            entity,
+            remove_after_save: true,
+        });
+        closed_since.remove(&entity);
+    }
+}
@@ -0,0 +1,46 @@
+use bevy::ecs::prelude::{Changed, Or, Query};
+
+use crate::game::{
+    components::{Equipment, GameClient, Hotbar, Inventory, SkillList},
+    messages::server::ServerMessage,
+};
+
+pub fn hotbar_system(
+    mut query: Query<
+        (
+            &mut Hotbar,
+            &Equipment,
+            &Inventory,
+            &SkillList,
+            Option<&GameClient>,
+        ),
+        Or<(Changed<Equipment>, Changed<Inventory>, Changed<SkillList>)>,
+    >,
+) {
+    query.for_each_mut(
+        |(mut hotbar, equipment, inventory, skill_list, game_client)| {
+            // Avoid triggering change detection when there is nothing to remove
+            if !hotbar
+                .pages
+                .iter()
+                .flatten()
+                .flatten()
+                .any(|slot| !Hotbar::is_valid_slot(slot, equipment, inventory, skill_list))
+            {
+                return;
+            }
+
+            for slot_index in hotbar.remove_invalid_slots(equipment, inventory, skill_list) {
+                if let Some(game_client) = game_client {
+                    game_client
+                        .server_message_tx
+                        .send(ServerMessage::SetHotbarSlot {
+                            slot_index,
+                            slot: None,
+                        })
+                        .ok();
+                }
+            }
+        },
+    );
+}
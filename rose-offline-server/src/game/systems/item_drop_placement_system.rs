@@ -0,0 +1,168 @@
+use bevy::{
+    ecs::{
+        prelude::{Added, Commands, Entity, Query, Res, ResMut},
+        query::WorldQuery,
+    },
+    math::{Vec2, Vec3, Vec3Swizzles},
+};
+use rand::Rng;
+use std::f32::consts::TAU;
+
+use crate::game::{
+    bundles::{client_entity_leave_zone, ITEM_DROP_RADIUS},
+    components::{
+        ClientEntity, ClientEntitySector, ClientEntityType, ItemDrop, Owner, PartyOwner, Position,
+    },
+    resources::{ClientEntityList, ClientEntityZone, GameData},
+};
+
+/// Minimum distance between item drops when there is space for it within the drop radius
+const ITEM_DROP_SPACING: f32 = 60.0;
+
+/// Number of random positions to try before giving up on finding space for an item drop
+const ITEM_DROP_PLACEMENT_ATTEMPTS: usize = 8;
+
+#[derive(WorldQuery)]
+#[world_query(mutable)]
+pub struct ItemDropPlacementQuery<'w> {
+    client_entity: &'w ClientEntity,
+    client_entity_sector: &'w mut ClientEntitySector,
+    item_drop: &'w mut ItemDrop,
+    position: &'w mut Position,
+    owner: Option<&'w Owner>,
+    party_owner: Option<&'w PartyOwner>,
+}
+
+fn is_same_owner(a: &ItemDropPlacementQueryItem, b: &ItemDropPlacementQueryItem) -> bool {
+    a.owner.map(|owner| owner.entity) == b.owner.map(|owner| owner.entity)
+        && a.party_owner.map(|party_owner| party_owner.entity)
+            == b.party_owner.map(|party_owner| party_owner.entity)
+}
+
+fn find_free_drop_position(zone: &ClientEntityZone, entity: Entity, origin: Vec3) -> Vec2 {
+    let mut rng = rand::thread_rng();
+    let mut drop_position = origin.xy();
+
+    for _ in 0..ITEM_DROP_PLACEMENT_ATTEMPTS {
+        // Uniformly distributed within the drop radius
+        let distance = ITEM_DROP_RADIUS as f32 * rng.gen::<f32>().sqrt();
+        let angle = rng.gen::<f32>() * TAU;
+        drop_position = origin.xy() + Vec2::new(angle.cos(), angle.sin()) * distance;
+
+        if !zone
+            .iter_entity_type_within_distance(
+                drop_position,
+                ITEM_DROP_SPACING,
+                &[ClientEntityType::ItemDrop],
+            )
+            .any(|(other_entity, _)| other_entity != entity)
+        {
+            break;
+        }
+    }
+
+    drop_position
+}
+
+/// Places newly spawned item drops before they are sent to clients.
+///
+/// Identical stackable drops with the same owner within the drop radius are merged into one,
+/// otherwise the drop is scattered within the drop radius away from other drops and placed on
+/// the terrain.
+pub fn item_drop_placement_system(
+    mut commands: Commands,
+    query_new_item_drops: Query<Entity, Added<ItemDrop>>,
+    mut query_item_drops: Query<ItemDropPlacementQuery>,
+    mut client_entity_list: ResMut<ClientEntityList>,
+    game_data: Res<GameData>,
+) {
+    let mut merged_entities = Vec::new();
+
+    for new_entity in query_new_item_drops.iter() {
+        if merged_entities.contains(&new_entity) {
+            continue;
+        }
+
+        let Ok(new_item_drop) = query_item_drops.get(new_entity) else {
+            continue;
+        };
+        let origin = new_item_drop.position.clone();
+        let Some(zone) = client_entity_list.get_zone(origin.zone_id) else {
+            continue;
+        };
+
+        // Merge with an existing drop of the same stackable item, taking its place
+        let merge_candidates: Vec<Entity> = zone
+            .iter_entity_type_within_distance(
+                origin.position.xy(),
+                ITEM_DROP_RADIUS as f32,
+                &[ClientEntityType::ItemDrop],
+            )
+            .map(|(entity, _)| entity)
+            .filter(|entity| *entity != new_entity)
+            .collect();
+
+        let mut merged_position = None;
+        for existing_entity in merge_candidates {
+            let Ok([mut new_item_drop, mut existing_item_drop]) =
+                query_item_drops.get_many_mut([new_entity, existing_entity])
+            else {
+                continue;
+            };
+
+            if !is_same_owner(&new_item_drop, &existing_item_drop) {
+                continue;
+            }
+
+            let Some(new_item) = new_item_drop.item_drop.item.as_mut() else {
+                break;
+            };
+            let Some(existing_item) = existing_item_drop.item_drop.item.take() else {
+                continue;
+            };
+
+            if let Err(existing_item) = new_item.try_stack_with(existing_item) {
+                existing_item_drop.item_drop.item = Some(existing_item);
+                continue;
+            }
+
+            client_entity_leave_zone(
+                &mut commands,
+                &mut client_entity_list,
+                existing_entity,
+                existing_item_drop.client_entity,
+                &existing_item_drop.client_entity_sector,
+                &existing_item_drop.position,
+            );
+            commands.entity(existing_entity).despawn();
+            merged_entities.push(existing_entity);
+
+            merged_position = Some(existing_item_drop.position.position);
+            break;
+        }
+
+        let Some(zone) = client_entity_list.get_zone_mut(origin.zone_id) else {
+            continue;
+        };
+        let drop_position = merged_position.unwrap_or_else(|| {
+            let drop_position = find_free_drop_position(zone, new_entity, origin.position);
+            let drop_height = game_data
+                .zones
+                .get_zone(origin.zone_id)
+                .and_then(|zone_data| zone_data.heightmap.get_height(drop_position))
+                .unwrap_or(origin.position.z);
+            drop_position.extend(drop_height)
+        });
+
+        let Ok(mut new_item_drop) = query_item_drops.get_mut(new_entity) else {
+            continue;
+        };
+        zone.update_position(
+            new_entity,
+            new_item_drop.client_entity,
+            &mut new_item_drop.client_entity_sector,
+            drop_position,
+        );
+        new_item_drop.position.position = drop_position;
+    }
+}
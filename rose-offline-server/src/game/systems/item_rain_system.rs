@@ -0,0 +1,125 @@
+use bevy::{
+    ecs::prelude::{Commands, EventReader, Local, Res, ResMut},
+    time::Time,
+};
+use log::warn;
+use rand::seq::SliceRandom;
+
+use rose_data::Item;
+use rose_game_common::messages::server::ServerMessage;
+
+use crate::game::{
+    bundles::ItemDropBundle,
+    components::{DroppedItem, Position},
+    events::ItemRainEvent,
+    resources::{
+        ClientEntityList, GameConfig, GameData, NavigationGrids, ServerMessages, ZoneCensus,
+    },
+};
+
+pub fn item_rain_system(
+    mut commands: Commands,
+    mut item_rain_events: EventReader<ItemRainEvent>,
+    mut client_entity_list: ResMut<ClientEntityList>,
+    mut server_messages: ResMut<ServerMessages>,
+    mut schedules_active: Local<Vec<bool>>,
+    game_config: Res<GameConfig>,
+    game_data: Res<GameData>,
+    navigation_grids: Res<NavigationGrids>,
+    zone_census: Res<ZoneCensus>,
+    time: Res<Time>,
+) {
+    let item_rain_config = &game_config.item_rain;
+
+    // Start scheduled item rains when their window begins
+    let mut item_rains = Vec::new();
+    schedules_active.resize(item_rain_config.schedules.len(), false);
+    for (schedule, active) in item_rain_config
+        .schedules
+        .iter()
+        .zip(schedules_active.iter_mut())
+    {
+        let is_active = schedule.window.is_active_now();
+        if is_active && !*active {
+            item_rains.push((schedule.zone_id, item_rain_config.drop_count));
+        }
+        *active = is_active;
+    }
+
+    item_rains.extend(
+        item_rain_events
+            .iter()
+            .map(|item_rain_event| (item_rain_event.zone_id, item_rain_event.drop_count)),
+    );
+
+    let mut rng = rand::thread_rng();
+
+    for (zone_id, drop_count) in item_rains {
+        let (Some(zone_data), Some(navigation_grid)) = (
+            game_data.zones.get_zone(zone_id),
+            navigation_grids.get_grid(zone_id),
+        ) else {
+            continue;
+        };
+
+        let drop_count = drop_count.min(
+            item_rain_config
+                .max_zone_item_drops
+                .saturating_sub(zone_census.get(zone_id).item_drops),
+        );
+        let walkable_positions = navigation_grid.walkable_positions();
+        if drop_count == 0 || walkable_positions.is_empty() {
+            continue;
+        }
+
+        let mut num_dropped = 0;
+        for _ in 0..drop_count {
+            let Ok(rain_item) = item_rain_config
+                .items
+                .choose_weighted(&mut rng, |rain_item| rain_item.weight)
+            else {
+                break;
+            };
+
+            let Some(item) = game_data
+                .items
+                .get_base_item(rain_item.item)
+                .and_then(|item_data| Item::from_item_data(item_data, rain_item.quantity))
+            else {
+                warn!("Invalid item {:?} in item rain config", rain_item.item);
+                continue;
+            };
+
+            let Some(drop_position) = walkable_positions.choose(&mut rng) else {
+                break;
+            };
+            let Some(drop_height) = zone_data.heightmap.get_height(*drop_position) else {
+                continue;
+            };
+
+            if ItemDropBundle::spawn(
+                &mut commands,
+                &mut client_entity_list,
+                DroppedItem::Item(item),
+                &Position::new(drop_position.extend(drop_height), zone_id),
+                None,
+                None,
+                &time,
+            )
+            .is_some()
+            {
+                num_dropped += 1;
+            }
+        }
+
+        if num_dropped > 0 {
+            server_messages.send_zone_message(
+                zone_id,
+                ServerMessage::AnnounceChat {
+                    name: None,
+                    text: format!("It is raining items in {}!", zone_data.name),
+                },
+            );
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use bevy::{
+    ecs::prelude::{Commands, Entity, EventWriter, Query, Res, ResMut},
+    time::Time,
+};
+use log::info;
+
+use crate::game::{
+    components::{CharacterInfo, ClientEntity, LinkDead},
+    events::SaveEvent,
+    resources::{GameConfig, LoginTokens},
+};
+
+pub fn link_dead_system(
+    mut commands: Commands,
+    query: Query<(
+        Entity,
+        &LinkDead,
+        Option<&CharacterInfo>,
+        Option<&ClientEntity>,
+    )>,
+    game_config: Res<GameConfig>,
+    mut login_tokens: ResMut<LoginTokens>,
+    mut save_events: EventWriter<SaveEvent>,
+    time: Res<Time>,
+) {
+    let reconnect_grace = Duration::from_secs(game_config.reconnect_grace_secs);
+    let now = time.elapsed();
+
+    for (entity, link_dead, character_info, client_entity) in query.iter() {
+        // Characters which are not in a zone, such as after logging out, cannot be resumed
+        if client_entity.is_some() && now - link_dead.disconnected_at < reconnect_grace {
+            continue;
+        }
+
+        if let Some(character_info) = character_info {
+            if client_entity.is_some() {
+                info!(
+                    "Removing character {} which did not reconnect within {:?}",
+                    character_info.name, reconnect_grace
+                );
+            }
+        }
+
+        for (index, login_token) in login_tokens.tokens.iter_mut().enumerate() {
+            if login_token.game_client == Some(entity) {
+                login_token.game_client = None;
+                login_token.game_client_connection = None;
+
+                if login_token.world_client.is_none() {
+                    login_tokens.tokens.remove(index);
+                }
+                break;
+            }
+        }
+
+        // Let the save system handle despawning the entity
+        save_events.send(SaveEvent::Character {
+            entity,
+            remove_after_save: true,
+        });
+        commands.entity(entity).remove::<LinkDead>();
+    }
+}
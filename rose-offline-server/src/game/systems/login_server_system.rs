@@ -1,20 +1,92 @@
-use bevy::ecs::prelude::{Commands, Entity, Query, Res, ResMut, Without};
+use std::time::{Duration, Instant};
+
+use bevy::ecs::prelude::{Commands, Entity, EventWriter, Query, Res, ResMut, Without};
 use log::warn;
 
 use crate::game::{
-    components::{Account, LoginClient},
+    components::{Account, GameClient, LinkDead, LoginClient, WorldClient},
+    events::SaveEvent,
     messages::client::ClientMessage,
-    messages::server::{ChannelListError, JoinServerError, LoginError, ServerMessage},
-    resources::{LoginTokens, ServerList},
+    messages::server::{
+        ChannelListError, ChannelListItem, JoinServerError, LoginError, ServerMessage,
+    },
+    resources::{DuplicateLoginPolicy, GameConfig, LoginAttempts, LoginTokens, ServerList},
     storage::account::{AccountStorage, AccountStorageError},
 };
 
+/// Disconnects the existing sessions of an account, saving and removing their character.
+fn takeover_sessions(
+    commands: &mut Commands,
+    login_tokens: &mut LoginTokens,
+    query_login_client: &Query<&LoginClient>,
+    query_world_client: &Query<&WorldClient>,
+    query_game_client: &Query<&GameClient>,
+    save_events: &mut EventWriter<SaveEvent>,
+    username: &str,
+) {
+    for login_token in login_tokens.remove_username_tokens(username) {
+        if let Some(login_client) = login_token
+            .login_client
+            .and_then(|entity| query_login_client.get(entity).ok())
+        {
+            login_client
+                .server_message_tx
+                .send(ServerMessage::Disconnect)
+                .ok();
+        }
+
+        if let Some(world_client) = login_token
+            .world_client
+            .and_then(|entity| query_world_client.get(entity).ok())
+        {
+            world_client
+                .server_message_tx
+                .send(ServerMessage::Disconnect)
+                .ok();
+        }
+
+        if let Some(character_entity) = login_token.game_client {
+            if let Ok(game_client) = query_game_client.get(character_entity) {
+                game_client
+                    .server_message_tx
+                    .send(ServerMessage::Whisper {
+                        from: String::from("SERVER"),
+                        text: String::from("Your account has logged in from another location"),
+                    })
+                    .ok();
+                game_client
+                    .server_message_tx
+                    .send(ServerMessage::Disconnect)
+                    .ok();
+            }
+
+            // The character is removed now rather than waiting for a link dead reconnect
+            commands
+                .entity(character_entity)
+                .remove::<(GameClient, LinkDead)>();
+            save_events.send(SaveEvent::Character {
+                entity: character_entity,
+                remove_after_save: true,
+            });
+        }
+    }
+}
+
 pub fn login_server_authentication_system(
     mut commands: Commands,
     query: Query<(Entity, &LoginClient), Without<Account>>,
-    login_tokens: Res<LoginTokens>,
+    query_login_client: Query<&LoginClient>,
+    query_world_client: Query<&WorldClient>,
+    query_game_client: Query<&GameClient>,
+    mut login_attempts: ResMut<LoginAttempts>,
+    mut login_tokens: ResMut<LoginTokens>,
+    mut save_events: EventWriter<SaveEvent>,
     server_list: Res<ServerList>,
+    game_config: Res<GameConfig>,
 ) {
+    let max_failures = game_config.login_lockout.max_failures;
+    let lockout_duration = Duration::from_secs(game_config.login_lockout.lockout_secs);
+
     query.for_each(|(entity, login_client)| {
         if let Ok(message) = login_client.client_message_rx.try_recv() {
             match message {
@@ -22,13 +94,27 @@ pub fn login_server_authentication_system(
                     login_client
                         .server_message_tx
                         .send(ServerMessage::ConnectionRequestSuccess {
-                            packet_sequence_id: 123,
+                            // Also the seed of seeded passwords, so must differ per connection
+                            packet_sequence_id: rand::random(),
                         })
                         .ok();
                 }
                 ClientMessage::LoginRequest { username, password } => {
-                    let login_result = if login_tokens.find_username_token(&username).is_some() {
-                        Err(LoginError::AlreadyLoggedIn)
+                    let now = Instant::now();
+                    let login_result = if login_attempts.is_locked(
+                        &username,
+                        login_client.ip_address,
+                        max_failures,
+                        lockout_duration,
+                        now,
+                    ) {
+                        log::info!(
+                            target: "audit",
+                            "Refused login to locked account {} from {:?}",
+                            &username,
+                            login_client.ip_address
+                        );
+                        Err(LoginError::TooManyAttempts)
                     } else {
                         match AccountStorage::try_load(&username, &password) {
                             Ok(account) => Ok(account),
@@ -64,6 +150,73 @@ pub fn login_server_authentication_system(
                         }
                     };
 
+                    // Only check for an existing session once the password has been verified
+                    let login_result = login_result.and_then(|account| {
+                        if login_tokens.find_username_token(&username).is_none() {
+                            return Ok(account);
+                        }
+
+                        match game_config.duplicate_login {
+                            DuplicateLoginPolicy::Reject => Err(LoginError::AlreadyLoggedIn),
+                            DuplicateLoginPolicy::Takeover => {
+                                log::info!(
+                                    target: "audit",
+                                    "Login to account {} from {:?} took over its existing session",
+                                    &username,
+                                    login_client.ip_address
+                                );
+                                takeover_sessions(
+                                    &mut commands,
+                                    &mut login_tokens,
+                                    &query_login_client,
+                                    &query_world_client,
+                                    &query_game_client,
+                                    &mut save_events,
+                                    &username,
+                                );
+                                Ok(account)
+                            }
+                        }
+                    });
+
+                    match login_result {
+                        Ok(_) => login_attempts.clear_account(&username),
+                        Err(LoginError::InvalidPassword | LoginError::InvalidAccount) => {
+                            // Failures for missing accounts still count towards the ip address,
+                            // so accounts can not be enumerated without being locked out
+                            let account_exists =
+                                matches!(login_result, Err(LoginError::InvalidPassword));
+                            let (account_failures, ip_address_failures) = login_attempts
+                                .add_failure(
+                                    account_exists.then_some(username.as_str()),
+                                    login_client.ip_address,
+                                    lockout_duration,
+                                    now,
+                                );
+                            log::info!(
+                                target: "audit",
+                                "Failed login to account {} from {:?}",
+                                &username,
+                                login_client.ip_address
+                            );
+
+                            if max_failures > 0
+                                && (account_failures == max_failures
+                                    || ip_address_failures == max_failures)
+                            {
+                                warn!(
+                                    target: "audit",
+                                    "Locked out logins to account {} from {:?} for {:?} after {} failures",
+                                    &username,
+                                    login_client.ip_address,
+                                    lockout_duration,
+                                    account_failures.max(ip_address_failures)
+                                );
+                            }
+                        }
+                        Err(_) => {}
+                    }
+
                     let response = match login_result {
                         Ok(account) => {
                             commands.entity(entity).insert(Account::from(account));
@@ -104,7 +257,22 @@ pub fn login_server_system(
                         |world_server| {
                             let mut channels = Vec::new();
                             for (id, channel) in world_server.channels.iter().enumerate() {
-                                channels.push((id as u8, channel.name.clone()));
+                                let players = login_tokens
+                                    .tokens
+                                    .iter()
+                                    .filter(|login_token| {
+                                        login_token.selected_game_server == channel.entity
+                                            && login_token.game_client.is_some()
+                                    })
+                                    .count() as u32;
+
+                                channels.push(ChannelListItem {
+                                    id: id as u8,
+                                    name: channel.name.clone(),
+                                    percent_full: (players * 100 / channel.max_players.max(1))
+                                        .min(100)
+                                        as u16,
+                                });
                             }
                             ServerMessage::ChannelList {
                                 server_id,
@@ -1,7 +1,9 @@
 mod ability_values_changed_system;
 mod ability_values_update_character_system;
 mod ability_values_update_npc_system;
+mod auto_loot_system;
 mod bank_system;
+mod character_checkpoint_system;
 mod chat_commands_system;
 mod clan_system;
 mod client_entity_visibility_system;
@@ -10,27 +12,41 @@ mod control_server_system;
 mod damage_system;
 mod driving_time_system;
 mod equipment_event_system;
+mod event_object_system;
 mod experience_points_system;
 mod expire_time_system;
 mod game_server_system;
+mod gather_system;
+mod ghost_client_system;
+mod hotbar_system;
+mod item_drop_placement_system;
 mod item_life_system;
+mod item_rain_system;
+mod link_dead_system;
 mod login_server_system;
 mod monster_spawn_system;
+mod newbie_system;
 mod npc_ai_system;
 mod npc_store_system;
 mod party_system;
 mod passive_recovery_system;
 mod personal_store_system;
 mod pickup_item_system;
+mod quest_reset_system;
 mod quest_system;
 mod revive_event_system;
 mod reward_item_system;
 mod save_system;
 mod server_messages_system;
+mod server_news_system;
 mod skill_effect_system;
 mod startup_clans_system;
+mod startup_journal_system;
 mod startup_zones_system;
 mod status_effect_system;
+mod territory_system;
+mod title_system;
+mod toggle_skill_system;
 mod update_motion_data_system;
 mod update_position_system;
 mod use_ammo_system;
@@ -38,11 +54,15 @@ mod use_item_system;
 mod weight_system;
 mod world_server_system;
 mod world_time_system;
+mod zone_activity_system;
+mod zone_census_system;
 
 pub use ability_values_changed_system::ability_values_changed_system;
 pub use ability_values_update_character_system::ability_values_update_character_system;
 pub use ability_values_update_npc_system::ability_values_update_npc_system;
+pub use auto_loot_system::auto_loot_system;
 pub use bank_system::bank_system;
+pub use character_checkpoint_system::character_checkpoint_system;
 pub use chat_commands_system::chat_commands_system;
 pub use clan_system::clan_system;
 pub use client_entity_visibility_system::client_entity_visibility_system;
@@ -51,32 +71,46 @@ pub use control_server_system::control_server_system;
 pub use damage_system::damage_system;
 pub use driving_time_system::driving_time_system;
 pub use equipment_event_system::equipment_event_system;
+pub use event_object_system::event_object_system;
 pub use experience_points_system::experience_points_system;
 pub use expire_time_system::expire_time_system;
 pub use game_server_system::{
     game_server_authentication_system, game_server_join_system, game_server_main_system,
 };
+pub use gather_system::gather_system;
+pub use ghost_client_system::ghost_client_system;
+pub use hotbar_system::hotbar_system;
+pub use item_drop_placement_system::item_drop_placement_system;
 pub use item_life_system::item_life_system;
+pub use item_rain_system::item_rain_system;
+pub use link_dead_system::link_dead_system;
 pub use login_server_system::{login_server_authentication_system, login_server_system};
 pub use monster_spawn_system::monster_spawn_system;
+pub use newbie_system::newbie_system;
 pub use npc_ai_system::npc_ai_system;
 pub use npc_store_system::npc_store_system;
 pub use party_system::{
-    party_member_event_system, party_member_update_info_system, party_system,
+    party_member_event_system, party_member_update_info_system, party_system, party_timeout_system,
     party_update_average_level_system,
 };
 pub use passive_recovery_system::passive_recovery_system;
 pub use personal_store_system::personal_store_system;
-pub use pickup_item_system::pickup_item_system;
+pub use pickup_item_system::{can_pickup_item_drop, pickup_item_system};
+pub use quest_reset_system::quest_reset_system;
 pub use quest_system::quest_system;
 pub use revive_event_system::revive_event_system;
 pub use reward_item_system::reward_item_system;
 pub use save_system::save_system;
 pub use server_messages_system::server_messages_system;
+pub use server_news_system::server_news_system;
 pub use skill_effect_system::skill_effect_system;
 pub use startup_clans_system::startup_clans_system;
+pub use startup_journal_system::startup_journal_system;
 pub use startup_zones_system::startup_zones_system;
 pub use status_effect_system::status_effect_system;
+pub use territory_system::territory_system;
+pub use title_system::title_system;
+pub use toggle_skill_system::toggle_skill_system;
 pub use update_motion_data_system::{
     update_character_motion_data_system, update_npc_motion_data_system,
 };
@@ -86,3 +120,5 @@ pub use use_item_system::use_item_system;
 pub use weight_system::weight_system;
 pub use world_server_system::{world_server_authentication_system, world_server_system};
 pub use world_time_system::world_time_system;
+pub use zone_activity_system::zone_activity_system;
+pub use zone_census_system::zone_census_system;
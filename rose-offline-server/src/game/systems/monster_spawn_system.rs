@@ -1,32 +1,39 @@
-use bevy::{
-    ecs::prelude::{Commands, Entity, Query, Res, ResMut},
-    time::Time,
-};
+use bevy::ecs::prelude::{Commands, Entity, Query, Res, ResMut};
 
 use rose_data::NpcId;
 
 use crate::game::{
     bundles::MonsterBundle,
     components::{MonsterSpawnPoint, Position, SpawnOrigin, Team},
-    resources::{ClientEntityList, GameData, ZoneList},
+    resources::{ClientEntityList, GameConfig, GameData, ZoneActivity, ZoneList},
 };
 
 pub fn monster_spawn_system(
     mut commands: Commands,
     mut query: Query<(Entity, &mut MonsterSpawnPoint, &Position)>,
-    time: Res<Time>,
     mut client_entity_list: ResMut<ClientEntityList>,
+    game_config: Res<GameConfig>,
     game_data: Res<GameData>,
+    zone_activity: Res<ZoneActivity>,
     zone_list: Res<ZoneList>,
 ) {
+    // Checked here rather than at startup so it can be changed by reloading the config
+    if !game_config.enable_monster_spawns {
+        return;
+    }
+
     query.for_each_mut(
         |(spawn_point_entity, mut spawn_point, spawn_point_position)| {
             if !zone_list.get_monster_spawns_enabled(spawn_point_position.zone_id) {
                 return;
             }
 
+            let Some(delta) = zone_activity.get_delta(spawn_point_position.zone_id) else {
+                return;
+            };
+
             let spawn_point = &mut *spawn_point;
-            spawn_point.time_since_last_check += time.delta();
+            spawn_point.time_since_last_check += delta;
             if spawn_point.time_since_last_check < spawn_point.interval {
                 return;
             }
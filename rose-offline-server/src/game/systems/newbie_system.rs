@@ -0,0 +1,67 @@
+use std::time::Duration;
+
+use bevy::{
+    ecs::prelude::{Added, Commands, Entity, EventWriter, Query, Res, ResMut, With},
+    time::Time,
+};
+
+use crate::game::{
+    components::{ClientEntity, NewCharacter, StatusEffects},
+    events::QuestTriggerEvent,
+    messages::server::ServerMessage,
+    resources::{GameConfig, ServerMessages},
+    GameData,
+};
+
+pub fn newbie_system(
+    mut commands: Commands,
+    mut query: Query<
+        (Entity, &ClientEntity, &mut StatusEffects),
+        (With<NewCharacter>, Added<ClientEntity>),
+    >,
+    mut quest_trigger_events: EventWriter<QuestTriggerEvent>,
+    mut server_messages: ResMut<ServerMessages>,
+    game_config: Res<GameConfig>,
+    game_data: Res<GameData>,
+    time: Res<Time>,
+) {
+    let now = time.last_update().unwrap();
+
+    for (entity, client_entity, mut status_effects) in query.iter_mut() {
+        let mut applied = false;
+        for newbie_status_effect in game_config.newbie_protection.status_effects.iter() {
+            if let Some(status_effect_data) = game_data
+                .status_effects
+                .get_status_effect(newbie_status_effect.id)
+            {
+                applied |= status_effects.apply_status_effect(
+                    status_effect_data,
+                    now + Duration::from_secs(newbie_status_effect.duration_secs),
+                    newbie_status_effect.value,
+                );
+            }
+        }
+
+        if applied {
+            server_messages.send_entity_message(
+                client_entity,
+                ServerMessage::UpdateStatusEffects {
+                    entity_id: client_entity.id,
+                    status_effects: status_effects.active.clone(),
+                    updated_values: Vec::new(),
+                },
+            );
+        }
+
+        if game_config.newbie_protection.enable_tutorial_triggers {
+            for &trigger_hash in game_data.quests.tutorial_triggers.iter() {
+                quest_trigger_events.send(QuestTriggerEvent {
+                    trigger_entity: entity,
+                    trigger_hash,
+                });
+            }
+        }
+
+        commands.entity(entity).remove::<NewCharacter>();
+    }
+}
@@ -14,31 +14,39 @@ use std::{
     marker::PhantomData,
     num::NonZeroU8,
     ops::{Range, RangeInclusive},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
-use rose_data::{ClanMemberPosition, Item, MotionId, NpcId, SkillId, ZoneId};
+use rose_data::{
+    AiAction, AiProgram, AiTrigger, ClanMemberPosition, Item, MotionId, NpcId, QuestTriggerHash,
+    SkillId, ZoneId,
+};
 use rose_file_readers::{
-    AipAbilityType, AipAction, AipAttackNearbyStat, AipCondition, AipConditionFindNearbyEntities,
+    AipAbilityType, AipAttackNearbyStat, AipCondition, AipConditionFindNearbyEntities,
     AipConditionMonthDayTime, AipConditionWeekDayTime, AipDamageType, AipDistance,
-    AipDistanceOrigin, AipEvent, AipHaveStatusTarget, AipHaveStatusType, AipItemBase1000,
-    AipMessageType, AipMonsterSpawnState, AipMotionId, AipMoveMode, AipMoveOrigin, AipNearbyAlly,
-    AipNpcId, AipOperatorType, AipResultOperator, AipSkillId, AipSkillTarget, AipSpawnNpcOrigin,
-    AipTrigger, AipVariableType, AipZoneId,
+    AipDistanceOrigin, AipHaveStatusTarget, AipHaveStatusType, AipItemBase1000, AipMessageType,
+    AipMonsterSpawnState, AipMoveMode, AipMoveOrigin, AipNearbyAlly, AipNpcId, AipOperatorType,
+    AipResultOperator, AipSkillTarget, AipSpawnNpcOrigin, AipVariableType,
 };
 use rose_game_common::{data::Damage, messages::PartyXpSharing};
 
 use crate::game::{
     bundles::{client_entity_leave_zone, ItemDropBundle, MonsterBundle},
     components::{
-        AbilityValues, Clan, ClanMembership, ClientEntity, ClientEntitySector, ClientEntityType,
-        Command, CommandData, DamageSources, DroppedItem, GameClient, HealthPoints, Level,
-        MonsterSpawnPoint, MoveMode, NextCommand, Npc, NpcAi, ObjectVariables, Owner, Party,
-        PartyMember, PartyMembership, Position, SpawnOrigin, StatusEffects, Team,
+        AbilityValues, CharacterLootSettings, CharacterStatistics, Clan, ClanMembership,
+        ClientEntity, ClientEntitySector, ClientEntityType, Command, CommandData, DamageSources,
+        Dead, DroppedItem, GameClient, HealthPoints, Level, MonsterSpawnPoint, MoveMode,
+        NextCommand, Npc, NpcAi, ObjectVariables, Owner, Party, PartyMember, PartyMembership,
+        Position, SpawnOrigin, StatusEffects, Team,
+    },
+    events::{
+        AutoLootEvent, DamageEvent, QuestTriggerEvent, RewardItemEvent, RewardXpEvent, TitleEvent,
     },
-    events::{DamageEvent, QuestTriggerEvent, RewardItemEvent, RewardXpEvent},
     messages::server::ServerMessage,
-    resources::{ClientEntityList, ServerMessages, WorldRates, WorldTime, ZoneList},
+    resources::{
+        ClientEntityList, GameConfig, KillCreditPolicy, ServerMessages, WorldRates, WorldTime,
+        ZoneActivity, ZoneList,
+    },
     GameData,
 };
 
@@ -84,6 +92,8 @@ pub struct KillerQuery<'w> {
     position: &'w Position,
     owner: Option<&'w Owner>,
     game_client: Option<&'w GameClient>,
+    dead: Option<&'w Dead>,
+    loot_settings: Option<&'w CharacterLootSettings>,
 }
 
 #[derive(WorldQuery)]
@@ -109,6 +119,7 @@ pub struct AiSystemParameters<'w, 's> {
     object_variable_query: Query<'w, 's, &'static mut ObjectVariables>,
     owner_query: Query<'w, 's, (&'static Position, &'static Command)>,
     clan_query: Query<'w, 's, &'static Clan>,
+    auto_loot_events: EventWriter<'w, AutoLootEvent>,
     damage_events: EventWriter<'w, DamageEvent>,
     quest_trigger_events: EventWriter<'w, QuestTriggerEvent>,
     reward_item_events: EventWriter<'w, RewardItemEvent>,
@@ -117,9 +128,11 @@ pub struct AiSystemParameters<'w, 's> {
 
 #[derive(SystemParam)]
 pub struct AiSystemResources<'w, 's> {
+    game_config: Res<'w, GameConfig>,
     game_data: Res<'w, GameData>,
     time: Res<'w, Time>,
     world_time: Res<'w, WorldTime>,
+    zone_activity: Res<'w, ZoneActivity>,
 
     #[system_param(ignore)]
     _secret: PhantomData<&'s ()>,
@@ -687,10 +700,10 @@ fn ai_condition_is_target_clan_master(
 fn npc_ai_check_conditions(
     ai_system_parameters: &mut AiSystemParameters,
     ai_system_resources: &AiSystemResources,
-    ai_program_event: &AipEvent,
+    conditions: &[AipCondition],
     ai_parameters: &mut AiParameters,
 ) -> bool {
-    for condition in ai_program_event.conditions.iter() {
+    for condition in conditions.iter() {
         let result = match *condition {
             AipCondition::FindNearbyEntities(AipConditionFindNearbyEntities {
                 distance,
@@ -1041,10 +1054,8 @@ fn ai_action_attack_nearby_entity_by_stat(
 fn ai_action_quest_trigger(
     ai_system_parameters: &mut AiSystemParameters,
     ai_parameters: &mut AiParameters,
-    trigger_name: &str,
+    trigger_hash: QuestTriggerHash,
 ) {
-    let trigger_hash = trigger_name.into();
-
     if matches!(
         ai_parameters.source.client_entity.entity_type,
         ClientEntityType::Monster
@@ -1152,7 +1163,7 @@ fn ai_action_spawn_npc(
     ai_system_parameters: &mut AiSystemParameters,
     ai_system_resources: &AiSystemResources,
     ai_parameters: &mut AiParameters,
-    npc_id: AipNpcId,
+    npc_id: NpcId,
     distance: AipDistance,
     origin: AipSpawnNpcOrigin,
     is_owner: bool,
@@ -1176,18 +1187,13 @@ fn ai_action_spawn_npc(
                 }),
         };
 
-    let npc_id = NpcId::new(npc_id as u16);
-    if npc_id.is_none() {
-        return;
-    }
-
     if let Some(spawn_position) = spawn_position {
         // TODO: If ai_parameters.is_dead { spawn after 3 seconds }
         if let Some(spawn_entity) = MonsterBundle::spawn(
             &mut ai_system_parameters.commands,
             &mut ai_system_parameters.client_entity_list,
             &ai_system_resources.game_data,
-            npc_id.unwrap(),
+            npc_id,
             ai_parameters.source.position.zone_id,
             SpawnOrigin::Summoned(ai_parameters.source.entity, spawn_position),
             distance,
@@ -1208,31 +1214,27 @@ fn ai_action_spawn_npc(
 fn ai_action_transform_npc(
     ai_system_parameters: &mut AiSystemParameters,
     ai_parameters: &mut AiParameters,
-    npc_id: AipNpcId,
+    npc_id: NpcId,
 ) {
-    if let Some(npc_id) = NpcId::new(npc_id as u16) {
-        ai_system_parameters
-            .commands
-            .entity(ai_parameters.source.entity)
-            .insert(Npc::new(npc_id, 0));
+    ai_system_parameters
+        .commands
+        .entity(ai_parameters.source.entity)
+        .insert(Npc::new(npc_id, 0));
 
-        ai_system_parameters.server_messages.send_entity_message(
-            ai_parameters.source.client_entity,
-            ServerMessage::ChangeNpcId {
-                entity_id: ai_parameters.source.client_entity.id,
-                npc_id,
-            },
-        );
-    }
+    ai_system_parameters.server_messages.send_entity_message(
+        ai_parameters.source.client_entity,
+        ServerMessage::ChangeNpcId {
+            entity_id: ai_parameters.source.client_entity.id,
+            npc_id,
+        },
+    );
 }
 
 fn ai_action_use_emote(
     ai_system_parameters: &mut AiSystemParameters,
     ai_parameters: &mut AiParameters,
-    motion_id: AipMotionId,
+    motion_id: MotionId,
 ) {
-    let motion_id = MotionId::new(motion_id as u16);
-
     ai_system_parameters
         .commands
         .entity(ai_parameters.source.entity)
@@ -1243,8 +1245,9 @@ fn ai_action_use_skill(
     ai_system_parameters: &mut AiSystemParameters,
     ai_parameters: &mut AiParameters,
     target: AipSkillTarget,
-    skill_id: AipSkillId,
-    motion_id: AipMotionId,
+    skill_id: SkillId,
+    cast_motion_id: MotionId,
+    action_motion_id: MotionId,
 ) {
     let target_entity = match target {
         AipSkillTarget::FindChar => ai_parameters.find_char.map(|(entity, _)| entity),
@@ -1252,11 +1255,8 @@ fn ai_action_use_skill(
         AipSkillTarget::This => Some(ai_parameters.source.entity),
         AipSkillTarget::NearChar => ai_parameters.near_char.map(|(entity, _)| entity),
     };
-    let skill_id = SkillId::new(skill_id as u16);
-    let cast_motion_id = MotionId::new(motion_id as u16);
-    let action_motion_id = MotionId::new(motion_id as u16 + 1);
 
-    if let (Some(skill_id), Some(target_entity)) = (skill_id, target_entity) {
+    if let Some(target_entity) = target_entity {
         let next_command = if target_entity != ai_parameters.source.entity {
             NextCommand::with_npc_cast_skill_target(
                 skill_id,
@@ -1278,12 +1278,10 @@ fn ai_action_use_skill(
 fn ai_action_set_monster_spawn_state(
     ai_system_parameters: &mut AiSystemParameters,
     ai_parameters: &mut AiParameters,
-    zone_id: Option<AipZoneId>,
+    zone_id: Option<ZoneId>,
     state: AipMonsterSpawnState,
 ) {
-    let zone_id = zone_id
-        .and_then(|zone_id| ZoneId::new(zone_id as u16))
-        .unwrap_or(ai_parameters.source.position.zone_id);
+    let zone_id = zone_id.unwrap_or(ai_parameters.source.position.zone_id);
 
     let enabled = match state {
         AipMonsterSpawnState::Disabled => false,
@@ -1360,7 +1358,7 @@ fn ai_action_message(
     ai_system_resources: &AiSystemResources,
     ai_parameters: &mut AiParameters,
     message_type: AipMessageType,
-    string_id: usize,
+    message: &str,
 ) {
     let npc_name = ai_system_resources
         .game_data
@@ -1368,36 +1366,34 @@ fn ai_action_message(
         .get_npc(ai_parameters.source.npc.id)
         .map(|npc_data| npc_data.name.to_string());
 
-    if let Some(message) = ai_system_resources.game_data.ai.get_ai_string(string_id) {
-        match message_type {
-            AipMessageType::Say => ai_system_parameters.server_messages.send_entity_message(
-                ai_parameters.source.client_entity,
-                ServerMessage::LocalChat {
-                    entity_id: ai_parameters.source.client_entity.id,
-                    text: message.to_string(),
-                },
-            ),
-            AipMessageType::Shout => {
-                if let Some(npc_name) = npc_name {
-                    ai_system_parameters.server_messages.send_entity_message(
-                        ai_parameters.source.client_entity,
-                        ServerMessage::ShoutChat {
-                            name: npc_name,
-                            text: message.to_string(),
-                        },
-                    )
-                }
+    match message_type {
+        AipMessageType::Say => ai_system_parameters.server_messages.send_entity_message(
+            ai_parameters.source.client_entity,
+            ServerMessage::LocalChat {
+                entity_id: ai_parameters.source.client_entity.id,
+                text: message.to_string(),
+            },
+        ),
+        AipMessageType::Shout => {
+            if let Some(npc_name) = npc_name {
+                ai_system_parameters.server_messages.send_entity_message(
+                    ai_parameters.source.client_entity,
+                    ServerMessage::ShoutChat {
+                        name: npc_name,
+                        text: message.to_string(),
+                    },
+                )
             }
-            AipMessageType::Announce => {
-                if let Some(npc_name) = npc_name {
-                    ai_system_parameters.server_messages.send_entity_message(
-                        ai_parameters.source.client_entity,
-                        ServerMessage::AnnounceChat {
-                            name: Some(npc_name),
-                            text: message.to_string(),
-                        },
-                    )
-                }
+        }
+        AipMessageType::Announce => {
+            if let Some(npc_name) = npc_name {
+                ai_system_parameters.server_messages.send_entity_message(
+                    ai_parameters.source.client_entity,
+                    ServerMessage::AnnounceChat {
+                        name: Some(npc_name),
+                        text: message.to_string(),
+                    },
+                )
             }
         }
     }
@@ -1469,20 +1465,20 @@ fn ai_action_give_item_to_owner(
 fn npc_ai_do_actions(
     ai_system_parameters: &mut AiSystemParameters,
     ai_system_resources: &AiSystemResources,
-    ai_program_event: &AipEvent,
+    actions: &[AiAction],
     ai_parameters: &mut AiParameters,
 ) {
-    for action in ai_program_event.actions.iter() {
+    for action in actions.iter() {
         log::trace!(target: "npc_ai", "  - AI action: {:?}", action);
         match *action {
-            AipAction::Stop => ai_action_stop(ai_system_parameters, ai_parameters),
-            AipAction::MoveAwayFromTarget(move_mode, distance) => ai_action_move_away_from_target(
+            AiAction::Stop => ai_action_stop(ai_system_parameters, ai_parameters),
+            AiAction::MoveAwayFromTarget(move_mode, distance) => ai_action_move_away_from_target(
                 ai_system_parameters,
                 ai_parameters,
                 move_mode,
                 distance,
             ),
-            AipAction::MoveRandomDistance(move_origin, move_mode, distance) => {
+            AiAction::MoveRandomDistance(move_origin, move_mode, distance) => {
                 ai_action_move_random_distance(
                     ai_system_parameters,
                     ai_parameters,
@@ -1491,22 +1487,22 @@ fn npc_ai_do_actions(
                     distance,
                 )
             }
-            AipAction::MoveNearOwner => {
+            AiAction::MoveNearOwner => {
                 ai_action_move_near_owner(ai_system_parameters, ai_parameters)
             }
-            AipAction::AttackNearChar => {
+            AiAction::AttackNearChar => {
                 ai_action_attack_near_char(ai_system_parameters, ai_parameters)
             }
-            AipAction::AttackFindChar => {
+            AiAction::AttackFindChar => {
                 ai_action_attack_find_char(ai_system_parameters, ai_parameters)
             }
-            AipAction::AttackAttacker => {
+            AiAction::AttackAttacker => {
                 ai_action_attack_attacker(ai_system_parameters, ai_parameters)
             }
-            AipAction::AttackOwnerTarget => {
+            AiAction::AttackOwnerTarget => {
                 ai_action_attack_owner_target(ai_system_parameters, ai_parameters)
             }
-            AipAction::AttackNearbyEntityByStat(distance, ability_type, stat_choice) => {
+            AiAction::AttackNearbyEntityByStat(distance, ability_type, stat_choice) => {
                 ai_action_attack_nearby_entity_by_stat(
                     ai_system_parameters,
                     ai_parameters,
@@ -1515,11 +1511,11 @@ fn npc_ai_do_actions(
                     stat_choice,
                 )
             }
-            AipAction::DoQuestTrigger(ref trigger_name) => {
-                ai_action_quest_trigger(ai_system_parameters, ai_parameters, trigger_name)
+            AiAction::DoQuestTrigger(trigger_hash) => {
+                ai_action_quest_trigger(ai_system_parameters, ai_parameters, trigger_hash)
             }
-            AipAction::KillSelf => ai_action_kill_self(ai_system_parameters, ai_parameters),
-            AipAction::NearbyAlliesAttackTarget(distance, nearby_ally_type, limit) => {
+            AiAction::KillSelf => ai_action_kill_self(ai_system_parameters, ai_parameters),
+            AiAction::NearbyAlliesAttackTarget(distance, nearby_ally_type, limit) => {
                 ai_action_nearby_allies_attack_target(
                     ai_system_parameters,
                     ai_parameters,
@@ -1528,7 +1524,7 @@ fn npc_ai_do_actions(
                     limit,
                 )
             }
-            AipAction::SpawnNpc(npc_id, distance, origin, is_owner) => ai_action_spawn_npc(
+            AiAction::SpawnNpc(npc_id, distance, origin, is_owner) => ai_action_spawn_npc(
                 ai_system_parameters,
                 ai_system_resources,
                 ai_parameters,
@@ -1537,23 +1533,26 @@ fn npc_ai_do_actions(
                 origin,
                 is_owner,
             ),
-            AipAction::TransformNpc(npc_id) => {
+            AiAction::TransformNpc(npc_id) => {
                 ai_action_transform_npc(ai_system_parameters, ai_parameters, npc_id)
             }
-            AipAction::Emote(motion_id) => {
+            AiAction::Emote(motion_id) => {
                 ai_action_use_emote(ai_system_parameters, ai_parameters, motion_id)
             }
-            AipAction::UseSkill(target, skill_id, motion_id) => ai_action_use_skill(
-                ai_system_parameters,
-                ai_parameters,
-                target,
-                skill_id,
-                motion_id,
-            ),
-            AipAction::SetMonsterSpawnState(zone, state) => {
+            AiAction::UseSkill(target, skill_id, cast_motion_id, action_motion_id) => {
+                ai_action_use_skill(
+                    ai_system_parameters,
+                    ai_parameters,
+                    target,
+                    skill_id,
+                    cast_motion_id,
+                    action_motion_id,
+                )
+            }
+            AiAction::SetMonsterSpawnState(zone, state) => {
                 ai_action_set_monster_spawn_state(ai_system_parameters, ai_parameters, zone, state)
             }
-            AipAction::SetVariable(variable_type, variable_id, operator, value) => {
+            AiAction::SetVariable(variable_type, variable_id, operator, value) => {
                 ai_action_set_variable(
                     ai_system_parameters,
                     ai_parameters,
@@ -1563,22 +1562,20 @@ fn npc_ai_do_actions(
                     value,
                 )
             }
-            AipAction::Message(message_type, string_id) => ai_action_message(
+            AiAction::Message(message_type, ref message) => ai_action_message(
                 ai_system_parameters,
                 ai_system_resources,
                 ai_parameters,
                 message_type,
-                string_id,
+                message,
             ),
-            AipAction::Say(_) => {}        // This is client side only
-            AipAction::SpecialAttack => {} // This is not actually used, probably an old removed feature
-            AipAction::DropRandomItem(ref items_base1000) => ai_action_drop_random_item(
+            AiAction::DropRandomItem(ref items_base1000) => ai_action_drop_random_item(
                 ai_system_parameters,
                 ai_system_resources,
                 ai_parameters,
                 items_base1000,
             ),
-            AipAction::GiveItemToOwner(item_base1000, quantity) => ai_action_give_item_to_owner(
+            AiAction::GiveItemToOwner(item_base1000, quantity) => ai_action_give_item_to_owner(
                 ai_system_parameters,
                 ai_system_resources,
                 ai_parameters,
@@ -1586,8 +1583,8 @@ fn npc_ai_do_actions(
                 quantity,
             ),
             /*
-            AipAction::RunAway(_) => {},
-            AipAction::SetPvpFlag(_, _) => {}
+            AiAction::RunAway(_) => {},
+            AiAction::SetPvpFlag(_, _) => {}
             */
             _ => {
                 log::warn!(target: "npc_ai_unimplemented", "Unimplemented AI action: {:?}", action);
@@ -1599,7 +1596,8 @@ fn npc_ai_do_actions(
 fn npc_ai_run_trigger(
     ai_system_parameters: &mut AiSystemParameters,
     ai_system_resources: &AiSystemResources,
-    ai_trigger: &AipTrigger,
+    ai_program: &AiProgram,
+    ai_trigger: &AiTrigger,
     source: &NpcQueryItem,
     attacker: Option<AttackerQueryItem>,
     damage: Option<Damage>,
@@ -1617,18 +1615,18 @@ fn npc_ai_run_trigger(
 
     // Do actions for only the first event with valid conditions
     log::trace!(target: "npc_ai", "Running AI trigger");
-    for (index, ai_program_event) in ai_trigger.events.iter().enumerate() {
+    for (index, ai_program_event) in ai_program.get_trigger_events(ai_trigger).iter().enumerate() {
         log::trace!(target: "npc_ai", " - Event {}", index);
         if npc_ai_check_conditions(
             ai_system_parameters,
             ai_system_resources,
-            ai_program_event,
+            ai_program.get_event_conditions(ai_program_event),
             &mut ai_parameters,
         ) {
             npc_ai_do_actions(
                 ai_system_parameters,
                 ai_system_resources,
-                ai_program_event,
+                ai_program.get_event_actions(ai_program_event),
                 &mut ai_parameters,
             );
             break;
@@ -1636,6 +1634,84 @@ fn npc_ai_run_trigger(
     }
 }
 
+/// Returns the attacker credited with the kill, who owns the drop and, unless XP is shared by
+/// damage, receives all of the XP.
+///
+/// Environment damage is never credited. When the killing blow came from the environment the
+/// most recent attacker takes the last hit, and if no attacker damaged the npc recently there is
+/// nobody to credit so no XP or drop is rewarded.
+fn get_kill_credit_entity(
+    kill_credit: KillCreditPolicy,
+    damage_sources: &DamageSources,
+    killer_entity: Option<Entity>,
+    now: Instant,
+) -> Option<Entity> {
+    let mut damage_sources = damage_sources
+        .damage_sources
+        .iter()
+        .filter(|damage_source| now - damage_source.last_damage_time <= DAMAGE_REWARD_EXPIRE_TIME)
+        .filter_map(|damage_source| {
+            damage_source
+                .attacker
+                .entity()
+                .map(|entity| (entity, damage_source))
+        });
+
+    match kill_credit {
+        KillCreditPolicy::Shared | KillCreditPolicy::LastHit => killer_entity.or_else(|| {
+            damage_sources
+                .max_by_key(|(_, damage_source)| damage_source.last_damage_time)
+                .map(|(entity, _)| entity)
+        }),
+        KillCreditPolicy::FirstHit => damage_sources
+            .min_by_key(|(_, damage_source)| damage_source.first_damage_time)
+            .map(|(entity, _)| entity)
+            .or(killer_entity),
+        KillCreditPolicy::MostDamage => damage_sources
+            .max_by_key(|(_, damage_source)| damage_source.total_damage)
+            .map(|(entity, _)| entity)
+            .or(killer_entity),
+    }
+}
+
+/// Characters far above the monster's level receive a fixed percent of its XP, in place of the
+/// level difference scaling of the XP formula.
+fn calculate_reward_xp(
+    ai_system_resources: &AiSystemResources,
+    world_rates: &WorldRates,
+    level: &Level,
+    damage: i32,
+    npc_level: &Level,
+    npc_max_health: i32,
+    npc_reward_xp: i32,
+) -> i32 {
+    let experience_config = &ai_system_resources.game_config.experience;
+    let level_difference = level.level as i32 - npc_level.level as i32;
+    let is_grey = experience_config.grey_level_difference > 0
+        && level_difference >= experience_config.grey_level_difference;
+    let reward_xp = ai_system_resources
+        .game_data
+        .ability_value_calculator
+        .calculate_give_xp(
+            if is_grey {
+                npc_level.level
+            } else {
+                level.level
+            } as i32,
+            damage,
+            npc_level.level as i32,
+            npc_max_health,
+            npc_reward_xp,
+            world_rates.xp_rate,
+        );
+
+    if is_grey {
+        reward_xp * experience_config.grey_xp_percent / 100
+    } else {
+        reward_xp
+    }
+}
+
 pub fn npc_ai_system(
     mut ai_system_parameters: AiSystemParameters,
     ai_system_resources: AiSystemResources,
@@ -1646,14 +1722,25 @@ pub fn npc_ai_system(
     query_party: Query<&Party>,
     world_rates: Res<WorldRates>,
     mut reward_xp_events: EventWriter<RewardXpEvent>,
+    mut statistics_query: Query<&mut CharacterStatistics>,
+    mut title_events: EventWriter<TitleEvent>,
 ) {
     for mut source in npc_query.iter_mut() {
+        let Some(delta) = ai_system_resources
+            .zone_activity
+            .get_delta(source.position.zone_id)
+        else {
+            continue;
+        };
+        let ai_program = ai_system_resources.game_data.ai.get_ai(source.ai.ai_index);
+
         if !source.ai.has_run_created_trigger {
-            if let Some(ai_program) = ai_system_resources.game_data.ai.get_ai(source.ai.ai_index) {
+            if let Some(ai_program) = ai_program {
                 if let Some(trigger_on_created) = ai_program.trigger_on_created.as_ref() {
                     npc_ai_run_trigger(
                         &mut ai_system_parameters,
                         &ai_system_resources,
+                        ai_program,
                         trigger_on_created,
                         &source,
                         None,
@@ -1666,7 +1753,7 @@ pub fn npc_ai_system(
             source.ai.has_run_created_trigger = true;
         }
 
-        if let Some(ai_program) = ai_system_resources.game_data.ai.get_ai(source.ai.ai_index) {
+        if let Some(ai_program) = ai_program {
             if let Some(trigger_on_damaged) = ai_program.trigger_on_damaged.as_ref() {
                 let mut rng = rand::thread_rng();
                 for &(attacker_entity, damage) in source.ai.pending_damage.iter() {
@@ -1680,6 +1767,7 @@ pub fn npc_ai_system(
                         npc_ai_run_trigger(
                             &mut ai_system_parameters,
                             &ai_system_resources,
+                            ai_program,
                             trigger_on_damaged,
                             &source,
                             Some(attacker_data),
@@ -1694,16 +1782,15 @@ pub fn npc_ai_system(
 
         match source.command.command {
             CommandData::Stop { .. } => {
-                if let Some(ai_program) =
-                    ai_system_resources.game_data.ai.get_ai(source.ai.ai_index)
-                {
+                if let Some(ai_program) = ai_program {
                     if let Some(trigger_on_idle) = ai_program.trigger_on_idle.as_ref() {
-                        source.ai.idle_duration += ai_system_resources.time.delta();
+                        source.ai.idle_duration += delta;
 
                         if source.ai.idle_duration > ai_program.idle_trigger_interval {
                             npc_ai_run_trigger(
                                 &mut ai_system_parameters,
                                 &ai_system_resources,
+                                ai_program,
                                 trigger_on_idle,
                                 &source,
                                 None,
@@ -1734,18 +1821,19 @@ pub fn npc_ai_system(
                     }
 
                     // Run on dead AI
-                    if let Some(trigger_on_dead) = ai_system_resources
-                        .game_data
-                        .ai
-                        .get_ai(source.ai.ai_index)
-                        .and_then(|ai_program| ai_program.trigger_on_dead.as_ref())
-                    {
+                    if let Some((ai_program, trigger_on_dead)) = ai_program.and_then(|ai_program| {
+                        ai_program
+                            .trigger_on_dead
+                            .as_ref()
+                            .map(|trigger_on_dead| (ai_program, trigger_on_dead))
+                    }) {
                         let attacker_data = killer_entity
                             .and_then(|killer_entity| attacker_query.get(killer_entity).ok());
 
                         npc_ai_run_trigger(
                             &mut ai_system_parameters,
                             &ai_system_resources,
+                            ai_program,
                             trigger_on_dead,
                             &source,
                             attacker_data,
@@ -1759,6 +1847,16 @@ pub fn npc_ai_system(
                             ai_system_resources.game_data.npcs.get_npc(source.npc.id)
                         {
                             let mut pending_party_xp: Vec<(Entity, i64, Entity)> = Vec::new();
+                            let kill_credit = ai_system_resources
+                                .game_config
+                                .kill_credit
+                                .get_policy(source.npc.id);
+                            let kill_credit_entity = get_kill_credit_entity(
+                                kill_credit,
+                                damage_sources,
+                                killer_entity,
+                                ai_system_resources.time.last_update().unwrap(),
+                            );
 
                             // Reward XP to all attackers
                             for damage_source in damage_sources.damage_sources.iter() {
@@ -1770,7 +1868,23 @@ pub fn npc_ai_system(
                                     continue;
                                 }
 
-                                let attacker = killer_query.get(damage_source.entity);
+                                // Environment damage does not reward XP
+                                let Some(damage_source_entity) = damage_source.attacker.entity()
+                                else {
+                                    continue;
+                                };
+
+                                // Unless XP is shared, the credited attacker is rewarded as if
+                                // they dealt all of the damage
+                                let reward_damage = if kill_credit == KillCreditPolicy::Shared {
+                                    damage_source.total_damage as i32
+                                } else if Some(damage_source_entity) == kill_credit_entity {
+                                    source.ability_values.get_max_health()
+                                } else {
+                                    continue;
+                                };
+
+                                let attacker = killer_query.get(damage_source_entity);
                                 if attacker.is_err() {
                                     continue;
                                 }
@@ -1789,17 +1903,15 @@ pub fn npc_ai_system(
                                     })
                                     .unwrap_or((attacker.entity, attacker.level));
 
-                                let reward_xp = ai_system_resources
-                                    .game_data
-                                    .ability_value_calculator
-                                    .calculate_give_xp(
-                                        reward_xp_entity_level.level as i32,
-                                        damage_source.total_damage as i32,
-                                        source.level.level as i32,
-                                        source.ability_values.get_max_health(),
-                                        npc_data.reward_xp as i32,
-                                        world_rates.xp_rate,
-                                    );
+                                let reward_xp = calculate_reward_xp(
+                                    &ai_system_resources,
+                                    &world_rates,
+                                    reward_xp_entity_level,
+                                    reward_damage,
+                                    source.level,
+                                    source.ability_values.get_max_health(),
+                                    npc_data.reward_xp as i32,
+                                );
 
                                 if reward_xp <= 0 {
                                     continue;
@@ -1834,6 +1946,10 @@ pub fn npc_ai_system(
                             }
 
                             // Reward accumulated party XP
+                            let xp_share_distance = ai_system_resources
+                                .game_config
+                                .experience
+                                .party_share_distance;
                             for (party_entity, total_xp, first_party_member) in
                                 pending_party_xp.drain(..)
                             {
@@ -1850,10 +1966,13 @@ pub fn npc_ai_system(
                                         .filter_map(PartyMember::get_entity)
                                         .filter_map(|entity| killer_query.get(entity).ok())
                                     {
-                                        if source.position.zone_id == party_member.position.zone_id
+                                        // Dead or distant party members do not share XP
+                                        if party_member.dead.is_none()
+                                            && source.position.zone_id
+                                                == party_member.position.zone_id
                                             && source.position.position.xy().distance_squared(
                                                 party_member.position.position.xy(),
-                                            ) < 5000.0 * 5000.0
+                                            ) < xp_share_distance * xp_share_distance
                                         {
                                             party_members_in_range
                                                 .push((party_member.entity, *party_member.level));
@@ -1918,8 +2037,9 @@ pub fn npc_ai_system(
                                 }
                             }
 
-                            // Reward killer with item drop
-                            if let Some(killer_entity) = killer_entity {
+                            // Reward killer with item drop, when killed by the environment the
+                            // credited attacker is treated as the killer
+                            if let Some(killer_entity) = killer_entity.or(kill_credit_entity) {
                                 if let Ok(killer) = killer_query.get(killer_entity) {
                                     // If the killer has an owner then the owner gets the reward
                                     let killer = killer
@@ -1948,9 +2068,29 @@ pub fn npc_ai_system(
                                         }
                                     }
 
-                                    // Drop item owned by killer
+                                    // Drop item owned by whoever is credited with the kill
+                                    let drop_owner = kill_credit_entity
+                                        .and_then(|entity| killer_query.get(entity).ok())
+                                        .map(|drop_owner| {
+                                            drop_owner
+                                                .owner
+                                                .and_then(|drop_owner_owner| {
+                                                    killer_query.get(drop_owner_owner.entity).ok()
+                                                })
+                                                .unwrap_or(drop_owner)
+                                        })
+                                        .unwrap_or(killer);
+                                    if let Ok(mut statistics) =
+                                        statistics_query.get_mut(drop_owner.entity)
+                                    {
+                                        statistics.add_monster_kill(source.npc.id);
+                                        title_events.send(TitleEvent::StatisticsChanged {
+                                            entity: drop_owner.entity,
+                                        });
+                                    }
+
                                     let level_difference =
-                                        killer.level.level as i32 - source.level.level as i32;
+                                        drop_owner.level.level as i32 - source.level.level as i32;
                                     if let Some(drop_item) =
                                         ai_system_resources.game_data.drop_table.get_drop(
                                             world_rates.drop_rate,
@@ -1958,21 +2098,51 @@ pub fn npc_ai_system(
                                             source.npc.id,
                                             source.position.zone_id,
                                             level_difference,
-                                            killer.ability_values.get_drop_rate(),
-                                            killer.ability_values.get_charm(),
+                                            drop_owner.ability_values.get_drop_rate(),
+                                            drop_owner.ability_values.get_charm(),
                                         )
                                     {
-                                        ItemDropBundle::spawn(
-                                            &mut ai_system_parameters.commands,
-                                            &mut ai_system_parameters.client_entity_list,
-                                            drop_item,
-                                            source.position,
-                                            Some(killer_entity),
-                                            killer.party_membership.and_then(|party_membership| {
-                                                party_membership.party
-                                            }),
-                                            &ai_system_resources.time,
-                                        );
+                                        let drop_party = drop_owner
+                                            .party_membership
+                                            .and_then(|party_membership| party_membership.party);
+
+                                        if ai_system_resources
+                                            .game_config
+                                            .item_drops
+                                            .enable_auto_loot
+                                            && drop_owner
+                                                .loot_settings
+                                                .map_or(false, |loot_settings| {
+                                                    loot_settings.auto_loot
+                                                })
+                                        {
+                                            ai_system_parameters.auto_loot_events.send(
+                                                AutoLootEvent {
+                                                    entity: drop_owner.entity,
+                                                    party: drop_party,
+                                                    item: drop_item,
+                                                    position: source.position.clone(),
+                                                },
+                                            );
+                                        } else {
+                                            ItemDropBundle::spawn(
+                                                &mut ai_system_parameters.commands,
+                                                &mut ai_system_parameters.client_entity_list,
+                                                drop_item,
+                                                source.position,
+                                                Some((
+                                                    drop_owner.entity,
+                                                    Duration::from_secs(
+                                                        ai_system_resources
+                                                            .game_config
+                                                            .item_drops
+                                                            .owner_duration_secs,
+                                                    ),
+                                                )),
+                                                drop_party,
+                                                &ai_system_resources.time,
+                                            );
+                                        }
                                     }
                                 }
                             }
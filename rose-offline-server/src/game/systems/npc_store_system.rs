@@ -1,4 +1,4 @@
-use bevy::ecs::prelude::{Entity, EventReader, Mut, Query, Res};
+use bevy::ecs::prelude::{Entity, EventReader, EventWriter, Mut, Query, Res};
 use bevy::math::Vec3Swizzles;
 use log::warn;
 use std::collections::HashSet;
@@ -7,14 +7,15 @@ use rose_data::Item;
 
 use crate::game::{
     components::{
-        AbilityValues, GameClient, Inventory, ItemSlot, Money, Npc, Position, UnionMembership,
+        AbilityValues, CharacterStatistics, Clan, GameClient, Inventory, ItemSlot, Money, Npc,
+        Position, UnionMembership,
     },
-    events::NpcStoreEvent,
+    events::{ClanEvent, NpcStoreEvent, TitleEvent},
     messages::{
         client::NpcStoreBuyItem,
         server::{NpcStoreTransactionError, ServerMessage},
     },
-    resources::WorldRates,
+    resources::{TerritoryList, WorldRates},
     GameData,
 };
 
@@ -31,7 +32,8 @@ fn npc_store_do_transaction(
     inventory: &mut Mut<Inventory>,
     position: &Position,
     _union_membership: &UnionMembership,
-) -> Result<HashSet<ItemSlot>, NpcStoreTransactionError> {
+    tax_rate: i64,
+) -> Result<(HashSet<ItemSlot>, Money, Money), NpcStoreTransactionError> {
     let (npc, npc_position) = npc_query
         .get(store_entity)
         .map_err(|_| NpcStoreTransactionError::NpcNotFound)?;
@@ -146,12 +148,18 @@ fn npc_store_do_transaction(
         .try_add_money(Money(total_sell_value))
         .map_err(|_| NpcStoreTransactionError::NotEnoughMoney)?;
 
+    // Territory tax is paid on top of the store price
+    let total_tax = total_buy_cost * tax_rate / 100;
     transaction_inventory
-        .try_take_money(Money(total_buy_cost))
+        .try_take_money(Money(total_buy_cost + total_tax))
         .map_err(|_| NpcStoreTransactionError::NotEnoughMoney)?;
 
     **inventory = transaction_inventory;
-    Ok(updated_inventory_slots)
+    Ok((
+        updated_inventory_slots,
+        Money(total_sell_value),
+        Money(total_tax),
+    ))
 }
 
 pub fn npc_store_system(
@@ -162,15 +170,35 @@ pub fn npc_store_system(
         &Position,
         &UnionMembership,
         Option<&GameClient>,
+        Option<&mut CharacterStatistics>,
     )>,
+    clan_query: Query<(Entity, &Clan)>,
     mut npc_store_events: EventReader<NpcStoreEvent>,
+    mut clan_events: EventWriter<ClanEvent>,
+    mut title_events: EventWriter<TitleEvent>,
     game_data: Res<GameData>,
+    territory_list: Res<TerritoryList>,
     world_rates: Res<WorldRates>,
 ) {
     for event in npc_store_events.iter() {
-        if let Ok((ability_values, mut inventory, position, union_membership, game_client)) =
-            transaction_entity_query.get_mut(event.transaction_entity)
+        if let Ok((
+            ability_values,
+            mut inventory,
+            position,
+            union_membership,
+            game_client,
+            statistics,
+        )) = transaction_entity_query.get_mut(event.transaction_entity)
         {
+            // Tax is paid to the clan which owns this territory
+            let territory_tax = territory_list.get(position.zone_id).and_then(|territory| {
+                let owner_clan = territory.owner_clan.as_ref()?;
+                let (clan_entity, _) = clan_query
+                    .iter()
+                    .find(|(_, clan)| &clan.name == owner_clan)?;
+                Some((clan_entity, territory.tax_rate))
+            });
+
             match npc_store_do_transaction(
                 &npc_query,
                 &game_data,
@@ -182,8 +210,25 @@ pub fn npc_store_system(
                 &mut inventory,
                 position,
                 union_membership,
+                territory_tax.map_or(0, |(_, tax_rate)| tax_rate),
             ) {
-                Ok(updated_items) => {
+                Ok((updated_items, total_sell_value, total_tax)) => {
+                    if let Some(mut statistics) = statistics {
+                        statistics.add_money_earned(total_sell_value);
+                        title_events.send(TitleEvent::StatisticsChanged {
+                            entity: event.transaction_entity,
+                        });
+                    }
+
+                    if let Some((clan_entity, _)) = territory_tax {
+                        if total_tax.0 > 0 {
+                            clan_events.send(ClanEvent::AddMoney {
+                                clan_entity,
+                                money: total_tax.0,
+                            });
+                        }
+                    }
+
                     if let Some(game_client) = game_client {
                         game_client
                             .server_message_tx
@@ -1,7 +1,13 @@
-use bevy::ecs::{
-    prelude::{Changed, Commands, Entity, EventReader, Or, Query},
-    query::WorldQuery,
+use arrayvec::ArrayVec;
+use bevy::{
+    ecs::{
+        prelude::{Changed, Commands, Entity, EventReader, Local, Or, Query, Res},
+        query::WorldQuery,
+    },
+    time::Time,
 };
+use std::{collections::HashMap, time::Duration};
+
 use rose_game_common::{
     components::Level,
     messages::{PartyItemSharing, PartyRejectInviteReason, PartyXpSharing},
@@ -10,12 +16,13 @@ use rose_game_common::{
 use crate::game::{
     components::{
         AbilityValues, CharacterInfo, CharacterUniqueId, ClientEntity, GameClient, HealthPoints,
-        Party, PartyMember, PartyMembership, Stamina, StatusEffects,
+        Party, PartyMember, PartyMembership, PartyVoteKick, Stamina, StatusEffects,
     },
     events::{PartyEvent, PartyMemberEvent},
     messages::server::{
         PartyMemberInfo, PartyMemberInfoOffline, PartyMemberInfoOnline, ServerMessage,
     },
+    resources::GameConfig,
 };
 
 #[derive(WorldQuery)]
@@ -69,6 +76,66 @@ fn send_message_to_members(
     }
 }
 
+fn find_first_online_member(party: &Party) -> Option<Entity> {
+    party.members.iter().find_map(PartyMember::get_entity)
+}
+
+/// Makes the first online member the party owner, returns false if there is no online member.
+fn transfer_party_owner(
+    party_member_info_query: &Query<PartyMemberInfoQuery>,
+    party: &mut Party,
+) -> bool {
+    let Some((new_owner_entity, new_owner)) = party.members.iter().find_map(|party_member| {
+        let entity = party_member.get_entity()?;
+        party_member_info_query
+            .get(entity)
+            .ok()
+            .map(|new_owner| (entity, new_owner))
+    }) else {
+        return false;
+    };
+
+    party.owner = new_owner_entity;
+    party.owner_disconnected = None;
+    send_message_to_members(
+        party_member_info_query,
+        &party.members,
+        ServerMessage::PartyChangeOwner {
+            entity_id: new_owner.client_entity.id,
+        },
+        None,
+    );
+    true
+}
+
+fn get_party_member_character_id(
+    party_member_info_query: &Query<PartyMemberInfoQuery>,
+    party_member: &PartyMember,
+) -> Option<CharacterUniqueId> {
+    match *party_member {
+        PartyMember::Online(party_member_entity) => party_member_info_query
+            .get(party_member_entity)
+            .ok()
+            .map(|party_member| party_member.character_info.unique_id),
+        PartyMember::Offline(character_id, _) => Some(character_id),
+    }
+}
+
+fn get_party_owner_character_id(
+    party_member_info_query: &Query<PartyMemberInfoQuery>,
+    party: &Party,
+) -> Option<CharacterUniqueId> {
+    // While waiting for the owner to reconnect, the owner entity no longer exists
+    if let Some((owner_character_id, _)) = party.owner_disconnected {
+        return Some(owner_character_id);
+    }
+
+    party_member_info_query
+        .get(party.owner)
+        .ok()
+        .map(|owner| owner.character_info.unique_id)
+}
+
 fn delete_party(
     commands: &mut Commands,
     party_membership_query: &mut Query<PartyMembershipQuery>,
@@ -374,6 +441,7 @@ fn handle_party_leave(
     } else {
         return Err(PartyLeaveError::NotInParty);
     };
+    let leaver_character_id = leaver.character_info.unique_id;
 
     if let Some(leaver_game_client) = leaver.game_client {
         // Send party delete message to leaver
@@ -394,39 +462,30 @@ fn handle_party_leave(
         _ => true,
     });
 
-    if party.owner == leaver_entity {
-        // If owner is leaving, choose first online member to be new owner
-        if let Some(new_owner_entity) =
-            party
-                .members
-                .iter()
-                .find_map(|party_member| match party_member {
-                    PartyMember::Online(party_member_entity) => Some(*party_member_entity),
-                    _ => None,
-                })
-        {
-            party.owner = new_owner_entity;
-        } else {
-            // There are no other online party members, so cause the party to be deleted below
-            party.members.clear();
-        }
+    if party.vote_kick.as_ref().map_or(false, |vote_kick| {
+        vote_kick.character_id == leaver_character_id
+    }) {
+        party.vote_kick = None;
     }
 
-    if party.members.len() <= 1 {
+    // If owner is leaving, choose first online member to be new owner. There must be an
+    // online member left to own the party.
+    if party.members.len() <= 1
+        || find_first_online_member(&party).is_none()
+        || (party.owner == leaver_entity
+            && !transfer_party_owner(party_member_info_query, &mut party))
+    {
         delete_party(commands, party_membership_query, party_entity, &mut party);
-    } else {
-        // Get leaver character id and owner character id for leave message
-        let [leaver, owner] = party_member_info_query
-            .get_many([leaver_entity, party.owner])
-            .unwrap();
-
+    } else if let Some(owner_character_id) =
+        get_party_owner_character_id(party_member_info_query, &party)
+    {
         // Send message to other members informing of leaver and new owner
         send_message_to_members(
             party_member_info_query,
             &party.members,
             ServerMessage::PartyMemberLeave {
-                leaver_character_id: leaver.character_info.unique_id,
-                owner_character_id: owner.character_info.unique_id,
+                leaver_character_id,
+                owner_character_id,
             },
             None,
         );
@@ -476,6 +535,24 @@ fn handle_party_kick(
         return Err(PartyKickError::InvalidKickCharacter);
     }
 
+    remove_party_member(
+        commands,
+        party_membership_query,
+        party_member_info_query,
+        party_entity,
+        &mut party,
+        kick_character_id,
+    )
+}
+
+fn remove_party_member(
+    commands: &mut Commands,
+    party_membership_query: &mut Query<PartyMembershipQuery>,
+    party_member_info_query: &Query<PartyMemberInfoQuery>,
+    party_entity: Entity,
+    party: &mut Party,
+    kick_character_id: CharacterUniqueId,
+) -> Result<(), PartyKickError> {
     // Try to remove kicked member from party
     let mut kicked_online_entity = None;
     let mut kicked_offline = false;
@@ -504,6 +581,12 @@ fn handle_party_kick(
         return Err(PartyKickError::InvalidKickCharacter);
     }
 
+    if party.vote_kick.as_ref().map_or(false, |vote_kick| {
+        vote_kick.character_id == kick_character_id
+    }) {
+        party.vote_kick = None;
+    }
+
     // If the kicked character was online, update party membership
     if let Some(kicked_entity) = kicked_online_entity {
         let mut kicked = party_membership_query.get_mut(kicked_entity).unwrap();
@@ -530,11 +613,198 @@ fn handle_party_kick(
         None,
     );
 
-    // If party is down to 1 member, delete the party
-    if party.members.len() <= 1 {
-        delete_party(commands, party_membership_query, party_entity, &mut party);
+    // If party is down to 1 member, or the owner was removed and there is no online member to
+    // replace them, delete the party
+    let kicked_owner = kicked_online_entity == Some(party.owner)
+        || party
+            .owner_disconnected
+            .map_or(false, |(owner_character_id, _)| {
+                owner_character_id == kick_character_id
+            });
+    if party.members.len() <= 1
+        || (kicked_owner && !transfer_party_owner(party_member_info_query, party))
+    {
+        delete_party(commands, party_membership_query, party_entity, party);
+    }
+
+    Ok(())
+}
+
+enum PartyVoteKickError {
+    InvalidEntity,
+    NotInParty,
+    AlreadyVoting,
+    NotVoting,
+    AlreadyVoted,
+    InvalidKickCharacter,
+}
+
+fn update_party_vote_kick(
+    commands: &mut Commands,
+    party_membership_query: &mut Query<PartyMembershipQuery>,
+    party_member_info_query: &Query<PartyMemberInfoQuery>,
+    party_entity: Entity,
+    party: &mut Party,
+) {
+    let Some(vote_kick) = party.vote_kick.as_ref() else {
+        return;
+    };
+    let kick_character_id = vote_kick.character_id;
+
+    // Every online member except the one being kicked can vote
+    let num_voters = party
+        .members
+        .iter()
+        .filter(|party_member| {
+            matches!(party_member, PartyMember::Online(_))
+                && get_party_member_character_id(party_member_info_query, party_member)
+                    != Some(kick_character_id)
+        })
+        .count();
+    let num_accept = vote_kick.votes.iter().filter(|(_, accept)| *accept).count();
+    let num_reject = vote_kick.votes.len() - num_accept;
+
+    if num_accept * 2 > num_voters {
+        party.vote_kick = None;
+        remove_party_member(
+            commands,
+            party_membership_query,
+            party_member_info_query,
+            party_entity,
+            party,
+            kick_character_id,
+        )
+        .ok();
+    } else if num_voters.saturating_sub(num_reject) * 2 <= num_voters {
+        // Not enough votes remain to reach a majority
+        party.vote_kick = None;
+        send_message_to_members(
+            party_member_info_query,
+            &party.members,
+            ServerMessage::PartyVoteKickFailed {
+                character_id: kick_character_id,
+            },
+            None,
+        );
+    }
+}
+
+fn handle_party_vote_kick(
+    commands: &mut Commands,
+    party_query: &mut Query<&mut Party>,
+    party_membership_query: &mut Query<PartyMembershipQuery>,
+    party_member_info_query: &Query<PartyMemberInfoQuery>,
+    now: Duration,
+    voter_entity: Entity,
+    kick_character_id: CharacterUniqueId,
+) -> Result<(), PartyVoteKickError> {
+    let voter = party_membership_query
+        .get(voter_entity)
+        .map_err(|_| PartyVoteKickError::InvalidEntity)?;
+    let voter_character_id = voter.character_info.unique_id;
+    let party_entity = voter
+        .party_membership
+        .party
+        .ok_or(PartyVoteKickError::NotInParty)?;
+
+    let mut party = party_query
+        .get_mut(party_entity)
+        .expect("PartyMembership pointing to invalid party entity");
+
+    if party.vote_kick.is_some() {
+        return Err(PartyVoteKickError::AlreadyVoting);
+    }
+
+    // The owner cannot be vote kicked, and we cannot vote to kick ourself
+    if kick_character_id == voter_character_id
+        || get_party_owner_character_id(party_member_info_query, &party) == Some(kick_character_id)
+        || !party.members.iter().any(|party_member| {
+            get_party_member_character_id(party_member_info_query, party_member)
+                == Some(kick_character_id)
+        })
+    {
+        return Err(PartyVoteKickError::InvalidKickCharacter);
+    }
+
+    let mut votes = ArrayVec::new();
+    votes.push((voter_character_id, true));
+    party.vote_kick = Some(PartyVoteKick {
+        character_id: kick_character_id,
+        started: now,
+        votes,
+    });
+
+    send_message_to_members(
+        party_member_info_query,
+        &party.members,
+        ServerMessage::PartyVoteKickStarted {
+            character_id: kick_character_id,
+        },
+        None,
+    );
+
+    update_party_vote_kick(
+        commands,
+        party_membership_query,
+        party_member_info_query,
+        party_entity,
+        &mut party,
+    );
+
+    Ok(())
+}
+
+fn handle_party_vote_kick_reply(
+    commands: &mut Commands,
+    party_query: &mut Query<&mut Party>,
+    party_membership_query: &mut Query<PartyMembershipQuery>,
+    party_member_info_query: &Query<PartyMemberInfoQuery>,
+    voter_entity: Entity,
+    accept: bool,
+) -> Result<(), PartyVoteKickError> {
+    let voter = party_membership_query
+        .get(voter_entity)
+        .map_err(|_| PartyVoteKickError::InvalidEntity)?;
+    let voter_character_id = voter.character_info.unique_id;
+    let party_entity = voter
+        .party_membership
+        .party
+        .ok_or(PartyVoteKickError::NotInParty)?;
+
+    let mut party = party_query
+        .get_mut(party_entity)
+        .expect("PartyMembership pointing to invalid party entity");
+
+    let vote_kick = party
+        .vote_kick
+        .as_mut()
+        .ok_or(PartyVoteKickError::NotVoting)?;
+
+    if vote_kick.character_id == voter_character_id {
+        return Err(PartyVoteKickError::InvalidKickCharacter);
     }
 
+    if vote_kick
+        .votes
+        .iter()
+        .any(|(character_id, _)| *character_id == voter_character_id)
+    {
+        return Err(PartyVoteKickError::AlreadyVoted);
+    }
+
+    vote_kick
+        .votes
+        .try_push((voter_character_id, accept))
+        .map_err(|_| PartyVoteKickError::AlreadyVoted)?;
+
+    update_party_vote_kick(
+        commands,
+        party_membership_query,
+        party_member_info_query,
+        party_entity,
+        &mut party,
+    );
+
     Ok(())
 }
 
@@ -648,6 +918,7 @@ fn handle_party_member_disconnect(
     party_query: &mut Query<&mut Party>,
     party_membership_query: &mut Query<PartyMembershipQuery>,
     party_member_info_query: &Query<PartyMemberInfoQuery>,
+    now: Duration,
     party_entity: Entity,
     disconnect_entity: Entity,
     character_id: CharacterUniqueId,
@@ -667,33 +938,16 @@ fn handle_party_member_disconnect(
         }
     }
 
-    // If leader disconnects, change leader to first online member, or disband party if all offline
-    if party.owner == disconnect_entity {
-        let new_owner_entity = party.members.iter().find_map(|party_member| {
-            if let PartyMember::Online(entity) = party_member {
-                Some(*entity)
-            } else {
-                None
-            }
-        });
+    // Disband the party if all members are offline
+    if find_first_online_member(&party).is_none() {
+        delete_party(commands, party_membership_query, party_entity, &mut party);
+        return Ok(());
+    }
 
-        if let Some(new_owner) = new_owner_entity
-            .and_then(|new_owner_entity| party_membership_query.get(new_owner_entity).ok())
-        {
-            party.owner = new_owner_entity.unwrap();
-            send_message_to_members(
-                party_member_info_query,
-                &party.members,
-                ServerMessage::PartyChangeOwner {
-                    entity_id: new_owner.client_entity.id,
-                },
-                None,
-            );
-        } else {
-            // No other online players, delete party
-            delete_party(commands, party_membership_query, party_entity, &mut party);
-            return Ok(());
-        }
+    // If leader disconnects, give them a chance to reconnect before party_timeout_system
+    // changes leader to the first online member
+    if party.owner == disconnect_entity {
+        party.owner_disconnected = Some((character_id, now));
     }
 
     // Send disconnect message to all online members
@@ -712,22 +966,42 @@ enum PartyMemberReconnectError {
 }
 
 fn handle_party_member_reconnect(
-    party_query: &Query<&mut Party>,
+    party_query: &mut Query<&mut Party>,
     party_member_info_query: &Query<PartyMemberInfoQuery>,
     party_entity: Entity,
     reconnect_entity: Entity,
     character_id: CharacterUniqueId,
     name: String,
 ) -> Result<(), PartyMemberDisconnectError> {
-    let party = party_query
-        .get(party_entity)
+    let mut party = party_query
+        .get_mut(party_entity)
         .map_err(|_| PartyMemberDisconnectError::InvalidParty)?;
+    let Ok(reconnect_member) = party_member_info_query.get(reconnect_entity) else {
+        return Ok(());
+    };
 
-    // Send member list to reconnected member
-    if let Ok(reconnect_member) = party_member_info_query.get(reconnect_entity) {
-        let owner = party_member_info_query.get(party.owner).unwrap();
-        let owner_character_id = owner.character_info.unique_id;
+    // The owner reconnected in time, so is still the owner
+    if party
+        .owner_disconnected
+        .map_or(false, |(owner_character_id, _)| {
+            owner_character_id == character_id
+        })
+    {
+        party.owner = reconnect_entity;
+        party.owner_disconnected = None;
+        send_message_to_members(
+            party_member_info_query,
+            &party.members,
+            ServerMessage::PartyChangeOwner {
+                entity_id: reconnect_member.client_entity.id,
+            },
+            Some(reconnect_entity),
+        );
+    }
 
+    // Send member list to reconnected member
+    if let Some(owner_character_id) = get_party_owner_character_id(party_member_info_query, &party)
+    {
         let party_member_infos = get_party_membership_info(&party.members, party_member_info_query);
         let other_members_info = party_member_infos
             .into_iter()
@@ -786,6 +1060,7 @@ fn handle_party_member_update_info(
 
 pub fn party_member_event_system(
     mut commands: Commands,
+    time: Res<Time>,
     mut party_query: Query<&mut Party>,
     mut party_membership_query: Query<PartyMembershipQuery>,
     party_member_info_query: Query<PartyMemberInfoQuery>,
@@ -804,6 +1079,7 @@ pub fn party_member_event_system(
                     &mut party_query,
                     &mut party_membership_query,
                     &party_member_info_query,
+                    time.elapsed(),
                     *party_entity,
                     *disconnect_entity,
                     *character_id,
@@ -818,7 +1094,7 @@ pub fn party_member_event_system(
                 name,
             } => {
                 handle_party_member_reconnect(
-                    &party_query,
+                    &mut party_query,
                     &party_member_info_query,
                     *party_entity,
                     *reconnect_entity,
@@ -833,6 +1109,8 @@ pub fn party_member_event_system(
 
 pub fn party_system(
     mut commands: Commands,
+    time: Res<Time>,
+    game_config: Res<GameConfig>,
     mut party_query: Query<&mut Party>,
     mut party_membership_query: Query<PartyMembershipQuery>,
     party_member_info_query: Query<PartyMemberInfoQuery>,
@@ -897,6 +1175,37 @@ pub fn party_system(
                 )
                 .ok();
             }
+            PartyEvent::VoteKick {
+                voter_entity,
+                kick_character_id,
+            } => {
+                if game_config.party.enable_vote_kick {
+                    handle_party_vote_kick(
+                        &mut commands,
+                        &mut party_query,
+                        &mut party_membership_query,
+                        &party_member_info_query,
+                        time.elapsed(),
+                        voter_entity,
+                        kick_character_id,
+                    )
+                    .ok();
+                }
+            }
+            PartyEvent::VoteKickReply {
+                voter_entity,
+                accept,
+            } => {
+                handle_party_vote_kick_reply(
+                    &mut commands,
+                    &mut party_query,
+                    &mut party_membership_query,
+                    &party_member_info_query,
+                    voter_entity,
+                    accept,
+                )
+                .ok();
+            }
             PartyEvent::ChangeOwner {
                 owner_entity,
                 new_owner_entity,
@@ -929,34 +1238,161 @@ pub fn party_system(
     }
 }
 
+pub fn party_timeout_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    game_config: Res<GameConfig>,
+    mut party_query: Query<(Entity, &mut Party)>,
+    mut party_membership_query: Query<PartyMembershipQuery>,
+    party_member_info_query: Query<PartyMemberInfoQuery>,
+) {
+    let now = time.elapsed();
+    let owner_reconnect_grace = Duration::from_secs(game_config.party.owner_reconnect_grace_secs);
+    let vote_kick_timeout = Duration::from_secs(game_config.party.vote_kick_timeout_secs);
+
+    for (party_entity, mut party) in party_query.iter_mut() {
+        if let Some((_, disconnected)) = party.owner_disconnected {
+            if now.saturating_sub(disconnected) >= owner_reconnect_grace {
+                // The owner did not reconnect in time, change owner to first online member
+                if !transfer_party_owner(&party_member_info_query, &mut party) {
+                    delete_party(
+                        &mut commands,
+                        &mut party_membership_query,
+                        party_entity,
+                        &mut party,
+                    );
+                    continue;
+                }
+            }
+        }
+
+        if let Some(vote_kick) = party.vote_kick.as_ref() {
+            if now.saturating_sub(vote_kick.started) >= vote_kick_timeout {
+                let character_id = vote_kick.character_id;
+                party.vote_kick = None;
+                send_message_to_members(
+                    &party_member_info_query,
+                    &party.members,
+                    ServerMessage::PartyVoteKickFailed { character_id },
+                    None,
+                );
+            }
+        }
+    }
+}
+
+// Health changes constantly during combat, so they are sent to the party at most this often
+const PARTY_MEMBER_HEALTH_UPDATE_INTERVAL: Duration = Duration::from_millis(500);
+
+pub struct ThrottledHealthUpdate {
+    last_sent: Duration,
+    pending: bool,
+}
+
+fn send_party_member_update_info(
+    party_query: &Query<&Party>,
+    party_member_info_query: &Query<PartyMemberInfoQuery>,
+    member_entity: Entity,
+    party_membership: &PartyMembership,
+) -> bool {
+    let Some(party) = party_membership
+        .party
+        .and_then(|party_entity| party_query.get(party_entity).ok())
+    else {
+        return false;
+    };
+
+    let Some(member_info) = get_online_party_member_info(party_member_info_query, member_entity)
+    else {
+        return false;
+    };
+
+    send_message_to_members(
+        party_member_info_query,
+        &party.members,
+        ServerMessage::PartyMemberUpdateInfo { member_info },
+        Some(member_entity),
+    );
+    true
+}
+
 pub fn party_member_update_info_system(
+    time: Res<Time>,
+    mut health_updates: Local<HashMap<Entity, ThrottledHealthUpdate>>,
     party_query: Query<&Party>,
     party_member_info_query: Query<PartyMemberInfoQuery>,
+    party_membership_query: Query<&PartyMembership>,
     party_member_info_changed_query: Query<
         (Entity, &PartyMembership),
         Or<(
             Changed<AbilityValues>,
             Changed<ClientEntity>,
+            Changed<Level>,
             Changed<StatusEffects>,
         )>,
     >,
+    party_member_health_changed_query: Query<(Entity, &PartyMembership), Changed<HealthPoints>>,
 ) {
+    let now = time.elapsed();
+
     for (member_entity, party_membership) in party_member_info_changed_query.iter() {
-        if let Some(party_entity) = party_membership.party {
-            if let Ok(party) = party_query.get(party_entity) {
-                if let Some(member_info) =
-                    get_online_party_member_info(&party_member_info_query, member_entity)
-                {
-                    send_message_to_members(
-                        &party_member_info_query,
-                        &party.members,
-                        ServerMessage::PartyMemberUpdateInfo { member_info },
-                        Some(member_entity),
-                    );
-                }
-            }
+        if send_party_member_update_info(
+            &party_query,
+            &party_member_info_query,
+            member_entity,
+            party_membership,
+        ) {
+            // The update includes current health, so any pending health update is not needed
+            health_updates.insert(
+                member_entity,
+                ThrottledHealthUpdate {
+                    last_sent: now,
+                    pending: false,
+                },
+            );
         }
     }
+
+    for (member_entity, party_membership) in party_member_health_changed_query.iter() {
+        if party_membership.party.is_none() {
+            continue;
+        }
+
+        let health_update = health_updates
+            .entry(member_entity)
+            .or_insert(ThrottledHealthUpdate {
+                last_sent: Duration::ZERO,
+                pending: false,
+            });
+        if health_update.last_sent != now {
+            health_update.pending = true;
+        }
+    }
+
+    health_updates.retain(|&member_entity, health_update| {
+        if health_update.pending
+            && now.saturating_sub(health_update.last_sent) >= PARTY_MEMBER_HEALTH_UPDATE_INTERVAL
+        {
+            let Ok(party_membership) = party_membership_query.get(member_entity) else {
+                return false;
+            };
+
+            if !send_party_member_update_info(
+                &party_query,
+                &party_member_info_query,
+                member_entity,
+                party_membership,
+            ) {
+                return false;
+            }
+
+            health_update.last_sent = now;
+            health_update.pending = false;
+        }
+
+        health_update.pending
+            || now.saturating_sub(health_update.last_sent) < PARTY_MEMBER_HEALTH_UPDATE_INTERVAL
+    });
 }
 
 pub fn party_update_average_level_system(
@@ -1,6 +1,6 @@
 use bevy::{
     ecs::{
-        prelude::{EventReader, Query},
+        prelude::{EventReader, EventWriter, Query, Res},
         query::WorldQuery,
     },
     prelude::Mut,
@@ -13,9 +13,13 @@ use rose_game_common::{
 };
 
 use crate::game::{
-    components::{ClientEntity, GameClient, Inventory, PersonalStore},
-    events::PersonalStoreEvent,
+    components::{
+        CharacterInfo, CharacterStatistics, ClientEntity, GameClient, Inventory, PersonalStore,
+    },
+    events::{PersonalStoreEvent, TitleEvent},
     messages::server::ServerMessage,
+    resources::GameConfig,
+    storage::journal::JournalStorage,
 };
 
 #[derive(WorldQuery)]
@@ -23,10 +27,13 @@ use crate::game::{
 pub struct PersonalStoreEntityQuery<'w> {
     client_entity: &'w ClientEntity,
     inventory: &'w mut Inventory,
+    character_info: Option<&'w CharacterInfo>,
     game_client: Option<&'w GameClient>,
+    statistics: Option<&'w mut CharacterStatistics>,
 }
 
 fn personal_store_list_items(
+    game_config: &GameConfig,
     store: &PersonalStore,
     seller: &PersonalStoreEntityQueryReadOnlyItem,
     buyer: &PersonalStoreEntityQueryReadOnlyItem,
@@ -42,7 +49,11 @@ fn personal_store_list_items(
 
     for (store_slot, slot) in store.sell_items.iter().enumerate() {
         if let Some((item_slot, price)) = slot {
-            if let Some(item) = seller.inventory.get_item(*item_slot) {
+            if let Some(item) = seller
+                .inventory
+                .get_item(*item_slot)
+                .filter(|item| !game_config.is_untradeable_item(item))
+            {
                 sell_items.push((store_slot as u8, item.clone(), *price));
             }
         }
@@ -59,6 +70,23 @@ fn personal_store_list_items(
     }
 }
 
+fn personal_store_journal_transaction(
+    seller: &PersonalStoreEntityQueryItem,
+    buyer: &PersonalStoreEntityQueryItem,
+) {
+    let mut journal = JournalStorage::new();
+    for party in [seller, buyer] {
+        if let (Some(character_info), Some(_)) = (party.character_info, party.game_client) {
+            journal.add_inventory(&character_info.name, &party.inventory);
+        }
+    }
+
+    // Only transfers between two saved characters need to be atomic
+    if journal.entries.len() > 1 {
+        journal.commit();
+    }
+}
+
 enum BuyError {
     InvalidStoreSlotIndex,
     ItemSoldOut,
@@ -67,6 +95,7 @@ enum BuyError {
 }
 
 fn personal_store_buy_item(
+    game_config: &GameConfig,
     store: &mut Mut<PersonalStore>,
     seller: &mut PersonalStoreEntityQueryItem,
     buyer: &mut PersonalStoreEntityQueryItem,
@@ -89,6 +118,10 @@ fn personal_store_buy_item(
         return Err(BuyError::ItemSoldOut);
     }
 
+    if game_config.is_untradeable_item(buy_item) {
+        return Err(BuyError::InvalidStoreSlotIndex);
+    }
+
     let item_price = Money(item_price.0 * buy_item.get_quantity() as i64);
     if buyer.inventory.money < item_price {
         return Err(BuyError::NotEnoughMoney);
@@ -109,7 +142,11 @@ fn personal_store_buy_item(
                 *store.sell_items.get_mut(store_slot_index).unwrap() = None;
             }
 
-            seller.inventory.try_add_money(transaction_money).ok();
+            if seller.inventory.try_add_money(transaction_money).is_ok() {
+                if let Some(statistics) = seller.statistics.as_mut() {
+                    statistics.add_money_earned(transaction_money);
+                }
+            }
 
             Ok((buyer_item_slot, store_item_slot))
         }
@@ -133,6 +170,8 @@ pub fn personal_store_system(
     mut entity_query: Query<PersonalStoreEntityQuery>,
     mut store_query: Query<&mut PersonalStore>,
     mut personal_store_events: EventReader<PersonalStoreEvent>,
+    mut title_events: EventWriter<TitleEvent>,
+    game_config: Res<GameConfig>,
 ) {
     for event in personal_store_events.iter() {
         match *event {
@@ -142,7 +181,7 @@ pub fn personal_store_system(
             } => {
                 if let Ok([seller, buyer]) = entity_query.get_many([store_entity, list_entity]) {
                     if let Ok(store) = store_query.get(store_entity) {
-                        personal_store_list_items(store, &seller, &buyer);
+                        personal_store_list_items(&game_config, store, &seller, &buyer);
                     }
                 }
             }
@@ -157,6 +196,7 @@ pub fn personal_store_system(
                 {
                     if let Ok(mut store) = store_query.get_mut(store_entity) {
                         match personal_store_buy_item(
+                            &game_config,
                             &mut store,
                             &mut seller,
                             &mut buyer,
@@ -164,6 +204,11 @@ pub fn personal_store_system(
                             buy_item,
                         ) {
                             Ok((buyer_item_slot, seller_item_slot)) => {
+                                personal_store_journal_transaction(&seller, &buyer);
+                                title_events.send(TitleEvent::StatisticsChanged {
+                                    entity: store_entity,
+                                });
+
                                 if let Some(seller_game_client) = seller.game_client {
                                     seller_game_client
                                         .server_message_tx
@@ -1,10 +1,10 @@
 use bevy::{
     ecs::query::WorldQuery,
-    prelude::{Commands, EventReader, EventWriter, Query, Res, ResMut},
+    prelude::{Commands, Entity, EventReader, EventWriter, Query, Res, ResMut},
 };
-use rose_data::{ItemClass, ItemType};
+use rose_data::{ItemClass, ItemType, ZoneId};
 use rose_game_common::{
-    components::{DroppedItem, Inventory, ItemDrop, Money},
+    components::{CharacterStatistics, DroppedItem, Inventory, ItemDrop},
     messages::{
         server::{PickupItemDropError, ServerMessage},
         PartyItemSharing,
@@ -14,11 +14,11 @@ use rose_game_common::{
 use crate::game::{
     bundles::client_entity_leave_zone,
     components::{
-        ClientEntity, ClientEntitySector, GameClient, Owner, Party, PartyMember, PartyMembership,
-        PartyOwner, Position,
+        AbilityValues, ClientEntity, ClientEntitySector, GameClient, Owner, Party, PartyMember,
+        PartyMembership, PartyOwner, Position, Weight,
     },
-    events::{PickupItemEvent, UseItemEvent},
-    resources::ClientEntityList,
+    events::{PickupItemEvent, TitleEvent, UseItemEvent},
+    resources::{ClientEntityList, GameConfig, ItemDropConfig},
     GameData,
 };
 
@@ -33,19 +33,59 @@ pub struct PickupItemQuery<'w> {
     party_owner: Option<&'w PartyOwner>,
 }
 
+/// Whether the pickup party can pickup the item drop on behalf of the whole party.
+fn can_party_pickup(
+    item_drop_config: &ItemDropConfig,
+    party_owner: Option<Entity>,
+    pickup_party: Option<Entity>,
+) -> bool {
+    item_drop_config.party_shared && pickup_party.is_some() && pickup_party == party_owner
+}
+
+/// Whether the pickup entity can pickup the item drop for itself.
+fn can_individual_pickup(
+    item_drop_config: &ItemDropConfig,
+    zone_id: ZoneId,
+    owner: Option<Entity>,
+    pickup_entity: Entity,
+) -> bool {
+    owner.map_or(true, |owner| owner == pickup_entity)
+        || item_drop_config.free_for_all_zones.contains(&zone_id)
+}
+
+/// Whether the pickup entity can pickup the item drop, either for itself or its party.
+pub fn can_pickup_item_drop(
+    item_drop_config: &ItemDropConfig,
+    zone_id: ZoneId,
+    owner: Option<Entity>,
+    party_owner: Option<Entity>,
+    pickup_entity: Entity,
+    pickup_party: Option<Entity>,
+) -> bool {
+    can_party_pickup(item_drop_config, party_owner, pickup_party)
+        || can_individual_pickup(item_drop_config, zone_id, owner, pickup_entity)
+}
+
 #[allow(clippy::unnecessary_unwrap)]
 pub fn pickup_item_system(
     mut commands: Commands,
     mut pickup_item_events: EventReader<PickupItemEvent>,
     mut query_pickup_item: Query<PickupItemQuery>,
     mut query_party: Query<&mut Party>,
-    mut query_inventory: Query<(&mut Inventory, Option<&GameClient>)>,
+    mut query_inventory: Query<(
+        &mut Inventory,
+        Option<&GameClient>,
+        Option<&mut CharacterStatistics>,
+        Option<(&AbilityValues, &Weight)>,
+    )>,
     query_game_client: Query<&GameClient>,
     query_client_entity: Query<&ClientEntity>,
     query_party_membership: Query<&PartyMembership>,
     mut client_entity_list: ResMut<ClientEntityList>,
+    game_config: Res<GameConfig>,
     game_data: Res<GameData>,
     mut use_item_events: EventWriter<UseItemEvent>,
+    mut title_events: EventWriter<TitleEvent>,
 ) {
     for pickup_item_event in pickup_item_events.iter() {
         let mut pickup_item =
@@ -68,98 +108,75 @@ pub fn pickup_item_system(
         let mut pickup_entity = None;
 
         // Can we pickup the item on behalf of our party?
-        if pickup_party.is_some()
-            && pickup_party
-                == pickup_item
-                    .party_owner
-                    .map(|party_owner| party_owner.entity)
-        {
+        if can_party_pickup(
+            &game_config.item_drops,
+            pickup_item
+                .party_owner
+                .map(|party_owner| party_owner.entity),
+            pickup_party,
+        ) {
             let pickup_party = pickup_party.unwrap();
 
             if let Ok(mut party) = query_party.get_mut(pickup_party) {
                 // Try pickup using party rules
-                pickup_entity = Some(match party.item_sharing {
+                pickup_entity = match party.item_sharing {
                     PartyItemSharing::EqualLootDistribution => {
-                        match dropped_item {
-                            DroppedItem::Item(_) => {
-                                // Give item to whoever picked it up
-                                pickup_item_event.pickup_entity
-                            }
-                            DroppedItem::Money(money) => {
-                                // Split money evenly
-                                let num_online_party_members = party
-                                    .members
-                                    .iter()
-                                    .filter(|party_member| party_member.get_entity().is_some())
-                                    .count()
-                                    as i64;
-                                let money_per_member = (money.0 / num_online_party_members) + 1;
+                        if let &DroppedItem::Money(money) = dropped_item {
+                            // Split money evenly, the other members are given their share now and
+                            // whoever picked it up receives their share from the drop
+                            for (party_member_entity, share) in party.split_money(money, |_| true) {
+                                if party_member_entity == pickup_item_event.pickup_entity {
+                                    pickup_item.item_drop.item = Some(DroppedItem::Money(share));
+                                    continue;
+                                }
 
-                                for party_member in party.members.iter() {
-                                    if let PartyMember::Online(party_member_entity) = party_member {
-                                        if let Ok((mut inventory, game_client)) =
-                                            query_inventory.get_mut(*party_member_entity)
-                                        {
-                                            if inventory
-                                                .try_add_money(Money(money_per_member))
-                                                .is_ok()
-                                            {
-                                                if let Some(game_client) = &game_client {
-                                                    game_client
-                                                        .server_message_tx
-                                                        .send(ServerMessage::RewardMoney {
-                                                            money: inventory.money,
-                                                        })
-                                                        .ok();
-                                                }
-                                            }
+                                if let Ok((mut inventory, game_client, statistics, _)) =
+                                    query_inventory.get_mut(party_member_entity)
+                                {
+                                    if inventory.try_add_money(share).is_ok() {
+                                        if let Some(mut statistics) = statistics {
+                                            statistics.add_money_earned(share);
+                                            title_events.send(TitleEvent::StatisticsChanged {
+                                                entity: party_member_entity,
+                                            });
+                                        }
+
+                                        if let Some(game_client) = &game_client {
+                                            game_client
+                                                .server_message_tx
+                                                .send(ServerMessage::RewardMoney {
+                                                    money: inventory.money,
+                                                })
+                                                .ok();
                                         }
                                     }
                                 }
-
-                                pickup_item.item_drop.item =
-                                    Some(DroppedItem::Money(Money(money_per_member)));
-                                pickup_item_event.pickup_entity
                             }
                         }
+
+                        // Give item to whoever picked it up
+                        Some(pickup_item_event.pickup_entity)
                     }
                     PartyItemSharing::AcquisitionOrder => match dropped_item {
+                        // Take turns in getting item - per item type
                         DroppedItem::Item(item) => {
-                            // Take turns in getting item - per item type
-                            let party = &mut *party;
-                            let acquire_item_order =
-                                &mut party.acquire_item_order[item.get_item_type().into()];
-
-                            loop {
-                                *acquire_item_order =
-                                    (*acquire_item_order + 1) % party.members.len();
-                                if let PartyMember::Online(party_member_entity) =
-                                    party.members[*acquire_item_order]
-                                {
-                                    break party_member_entity;
-                                }
-                            }
+                            party.next_item_acquirer(item.get_item_type(), |_| true)
                         }
-                        DroppedItem::Money(_) => loop {
-                            // Take turns in getting money
-                            party.acquire_money_order =
-                                (party.acquire_money_order + 1) % party.members.len();
-                            if let PartyMember::Online(party_member_entity) =
-                                party.members[party.acquire_money_order]
-                            {
-                                break party_member_entity;
-                            }
-                        },
+                        // Take turns in getting money
+                        DroppedItem::Money(_) => party.next_money_acquirer(|_| true),
                     },
-                });
+                };
             }
         }
 
         // Can we pickup the item for ourself?
         if pickup_entity.is_none()
-            && (pickup_item.owner.is_none()
-                || pickup_item.owner.map(|owner| owner.entity)
-                    == Some(pickup_item_event.pickup_entity))
+            && can_individual_pickup(
+                &game_config.item_drops,
+                pickup_item.position.zone_id,
+                pickup_item.owner.map(|owner| owner.entity),
+                pickup_item_event.pickup_entity,
+            )
         {
             pickup_entity = Some(pickup_item_event.pickup_entity);
         }
@@ -176,14 +193,31 @@ pub fn pickup_item_system(
                             })
                     {
                         use_item_events.send(UseItemEvent::from_item(pickup_entity, item));
-                    } else if let Ok((mut inventory, game_client)) =
+                    } else if let Ok((mut inventory, game_client, _, weight)) =
                         query_inventory.get_mut(pickup_entity)
                     {
-                        let result = match inventory.try_add_item(item.clone()) {
-                            Ok((slot, item)) => Ok((slot, item.clone())),
-                            Err(item) => {
-                                pickup_item.item_drop.item = Some(DroppedItem::Item(item));
-                                Err(PickupItemDropError::InventoryFull)
+                        let item_weight = game_data
+                            .items
+                            .get_base_item(item.get_item_reference())
+                            .map_or(0, |item_data| item_data.weight)
+                            * item.get_quantity();
+                        let is_too_heavy = weight.map_or(false, |(ability_values, weight)| {
+                            game_config
+                                .overweight
+                                .max_weight_limit(ability_values.max_weight())
+                                .map_or(false, |limit| weight.weight + item_weight > limit)
+                        });
+
+                        let result = if is_too_heavy {
+                            pickup_item.item_drop.item = Some(DroppedItem::Item(item.clone()));
+                            Err(PickupItemDropError::InventoryFull)
+                        } else {
+                            match inventory.try_add_item(item.clone()) {
+                                Ok((slot, item)) => Ok((slot, item.clone())),
+                                Err(item) => {
+                                    pickup_item.item_drop.item = Some(DroppedItem::Item(item));
+                                    Err(PickupItemDropError::InventoryFull)
+                                }
                             }
                         };
 
@@ -240,9 +274,17 @@ pub fn pickup_item_system(
                     }
                 }
                 Some(DroppedItem::Money(money)) => {
-                    if let Ok((mut inventory, game_client)) = query_inventory.get_mut(pickup_entity)
+                    if let Ok((mut inventory, game_client, statistics, _)) =
+                        query_inventory.get_mut(pickup_entity)
                     {
                         if inventory.try_add_money(money).is_ok() {
+                            if let Some(mut statistics) = statistics {
+                                statistics.add_money_earned(money);
+                                title_events.send(TitleEvent::StatisticsChanged {
+                                    entity: pickup_entity,
+                                });
+                            }
+
                             if let Some(game_client) = &game_client {
                                 game_client
                                     .server_message_tx
@@ -281,3 +323,137 @@ pub fn pickup_item_system(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rose_game_common::components::Money;
+
+    use super::*;
+
+    fn zone_id() -> ZoneId {
+        ZoneId::new(1).unwrap()
+    }
+
+    #[test]
+    fn owned_drop_only_picked_up_by_owner() {
+        let config = ItemDropConfig::default();
+        let owner = Entity::from_raw(1);
+        let other = Entity::from_raw(2);
+
+        assert!(can_individual_pickup(
+            &config,
+            zone_id(),
+            Some(owner),
+            owner
+        ));
+        assert!(!can_individual_pickup(
+            &config,
+            zone_id(),
+            Some(owner),
+            other
+        ));
+    }
+
+    #[test]
+    fn unowned_drop_picked_up_by_anyone() {
+        let config = ItemDropConfig::default();
+
+        assert!(can_individual_pickup(
+            &config,
+            zone_id(),
+            None,
+            Entity::from_raw(2)
+        ));
+    }
+
+    #[test]
+    fn free_for_all_zone_ignores_owner() {
+        let config = ItemDropConfig {
+            free_for_all_zones: vec![zone_id()],
+            ..Default::default()
+        };
+        let other_zone_id = ZoneId::new(2).unwrap();
+        let owner = Entity::from_raw(1);
+        let other = Entity::from_raw(2);
+
+        assert!(can_individual_pickup(
+            &config,
+            zone_id(),
+            Some(owner),
+            other
+        ));
+        assert!(!can_individual_pickup(
+            &config,
+            other_zone_id,
+            Some(owner),
+            other
+        ));
+    }
+
+    #[test]
+    fn party_shared_drop_picked_up_by_party() {
+        let config = ItemDropConfig::default();
+        let party = Entity::from_raw(10);
+        let other_party = Entity::from_raw(11);
+
+        assert!(can_party_pickup(&config, Some(party), Some(party)));
+        assert!(!can_party_pickup(&config, Some(party), Some(other_party)));
+        assert!(!can_party_pickup(&config, Some(party), None));
+        assert!(!can_party_pickup(&config, None, None));
+    }
+
+    #[test]
+    fn party_shared_disabled() {
+        let config = ItemDropConfig {
+            party_shared: false,
+            ..Default::default()
+        };
+        let party = Entity::from_raw(10);
+
+        assert!(!can_party_pickup(&config, Some(party), Some(party)));
+    }
+
+    fn party(members: &[PartyMember]) -> Party {
+        Party::new(Entity::from_raw(1), members)
+    }
+
+    #[test]
+    fn acquisition_order_skips_offline_members() {
+        let first = Entity::from_raw(1);
+        let second = Entity::from_raw(2);
+        let mut party = party(&[
+            PartyMember::Online(first),
+            PartyMember::Offline(3, "Offline".to_string()),
+            PartyMember::Online(second),
+        ]);
+
+        assert_eq!(party.next_money_acquirer(|_| true), Some(second));
+        assert_eq!(party.next_money_acquirer(|_| true), Some(first));
+        assert_eq!(
+            party.next_item_acquirer(ItemType::Material, |entity| entity != second),
+            Some(first)
+        );
+        assert_eq!(party.next_money_acquirer(|_| false), None);
+    }
+
+    #[test]
+    fn money_split_between_online_members() {
+        let first = Entity::from_raw(1);
+        let second = Entity::from_raw(2);
+        let party = party(&[
+            PartyMember::Online(first),
+            PartyMember::Offline(3, "Offline".to_string()),
+            PartyMember::Online(second),
+        ]);
+
+        assert_eq!(
+            party.split_money(Money(100), |_| true),
+            vec![(first, Money(51)), (second, Money(51))]
+        );
+        assert_eq!(
+            party.split_money(Money(100), |entity| entity == second),
+            vec![(second, Money(101))]
+        );
+        assert!(party.split_money(Money(100), |_| false).is_empty());
+    }
+}
@@ -0,0 +1,86 @@
+use bevy::ecs::prelude::{DetectChanges, Local, Query, Res};
+
+use crate::game::{
+    components::{GameClient, QuestState},
+    messages::server::ServerMessage,
+    resources::{GameConfig, QuestResetPeriod},
+};
+
+/// Removes the completions from before the last reset and clears the quest switches of the
+/// reset quests, returning true if any switch was cleared.
+fn remove_expired_completions(
+    game_config: &GameConfig,
+    quest_state: &mut QuestState,
+    last_daily_reset: i64,
+    last_weekly_reset: i64,
+) -> bool {
+    let QuestState {
+        repeatable_quest_completions,
+        quest_switches,
+        ..
+    } = quest_state;
+    let mut cleared_switches = false;
+
+    repeatable_quest_completions.retain(|&quest_id, &mut completed_time| {
+        let Some(quest) = game_config.quest_resets.get_quest(quest_id) else {
+            return false;
+        };
+
+        let last_reset = match quest.reset {
+            QuestResetPeriod::Daily => last_daily_reset,
+            QuestResetPeriod::Weekly => last_weekly_reset,
+        };
+        if completed_time >= last_reset {
+            return true;
+        }
+
+        for &switch_id in quest.switches.iter() {
+            if let Some(mut switch) = quest_switches.get_mut(switch_id) {
+                cleared_switches |= *switch;
+                *switch = false;
+            }
+        }
+        false
+    });
+
+    cleared_switches
+}
+
+pub fn quest_reset_system(
+    mut query: Query<(&GameClient, &mut QuestState)>,
+    mut previous_daily_reset: Local<i64>,
+    game_config: Res<GameConfig>,
+) {
+    let now = chrono::Local::now();
+    let last_daily_reset = game_config
+        .quest_resets
+        .get_last_reset(QuestResetPeriod::Daily, now);
+    let last_weekly_reset = game_config
+        .quest_resets
+        .get_last_reset(QuestResetPeriod::Weekly, now);
+
+    // Weekly resets happen at the same hour as daily resets, so every character only needs
+    // checking after a daily reset, otherwise just those which have logged in
+    let reset_passed = *previous_daily_reset != last_daily_reset;
+    *previous_daily_reset = last_daily_reset;
+
+    for (game_client, mut quest_state) in query.iter_mut() {
+        if (reset_passed || quest_state.is_added())
+            && !quest_state.repeatable_quest_completions.is_empty()
+            && remove_expired_completions(
+                &game_config,
+                &mut quest_state,
+                last_daily_reset,
+                last_weekly_reset,
+            )
+        {
+            // The client checks quest conditions with its own copy of the switches
+            game_client
+                .server_message_tx
+                .send(ServerMessage::CharacterDataQuest {
+                    quest_state: Box::new(quest_state.clone()),
+                })
+                .ok();
+        }
+    }
+}
@@ -2,6 +2,7 @@ use std::{
     marker::PhantomData,
     num::{NonZeroU8, NonZeroUsize},
     ops::RangeInclusive,
+    time::Duration,
 };
 
 use bevy::{
@@ -17,7 +18,10 @@ use chrono::{Datelike, Timelike};
 use log::warn;
 use rand::Rng;
 
-use rose_data::{EquipmentItem, Item, NpcId, QuestTrigger, SkillId, WorldTicks, ZoneId};
+use rose_data::{
+    AbilityType, EquipmentItem, Item, NpcId, QuestTrigger, QuestTriggerHash, SkillId,
+    SkillPageKind, WorldTicks, ZoneId,
+};
 use rose_file_readers::{
     QsdAbilityType, QsdClanPoints, QsdCondition, QsdConditionOperator, QsdDistance,
     QsdEquipmentIndex, QsdEventId, QsdItem, QsdNpcId, QsdNpcMessageType, QsdObjectType, QsdQuestId,
@@ -32,15 +36,18 @@ use crate::game::{
         client_entity_teleport_zone, skill_list_try_learn_skill, MonsterBundle, SkillListBundle,
     },
     components::{
-        AbilityValues, ActiveQuest, BasicStats, CharacterInfo, Clan, ClanMembership, ClientEntity,
-        ClientEntitySector, Equipment, ExperiencePoints, GameClient, HealthPoints, Inventory,
-        Level, ManaPoints, Money, MoveSpeed, Npc, ObjectVariables, Party, PartyMembership,
-        Position, QuestState, SkillList, SkillPoints, SpawnOrigin, Stamina, StatPoints, Team,
-        UnionMembership,
+        AbilityValues, ActiveQuest, BasicStats, CharacterInfo, CharacterStatistics, Clan,
+        ClanMembership, ClientEntity, ClientEntitySector, Equipment, ExperiencePoints, GameClient,
+        HealthPoints, Inventory, Level, ManaPoints, Money, MoveSpeed, Npc, ObjectVariables, Party,
+        PartyMembership, Position, QuestState, SkillList, SkillPoints, SpawnOrigin, Stamina,
+        StatPoints, Team, UnionMembership,
     },
-    events::{ClanEvent, QuestTriggerEvent, RewardItemEvent, RewardXpEvent},
+    events::{ClanEvent, QuestTriggerEvent, RewardItemEvent, RewardXpEvent, TitleEvent},
     messages::server::ServerMessage,
-    resources::{ClientEntityList, ServerMessages, WorldRates, WorldTime, ZoneList},
+    resources::{
+        ClientEntityList, DelayedQuestTrigger, DelayedQuestTriggers, GameConfig, ServerMessages,
+        WorldRates, WorldTime, ZoneList,
+    },
     GameData,
 };
 
@@ -53,6 +60,7 @@ pub struct QuestSystemParameters<'w, 's> {
     reward_item_events: EventWriter<'w, RewardItemEvent>,
     reward_xp_events: EventWriter<'w, RewardXpEvent>,
     clan_events: EventWriter<'w, ClanEvent>,
+    title_events: EventWriter<'w, TitleEvent>,
     object_variables_query: Query<'w, 's, (&'static mut ObjectVariables, &'static Position)>,
     party_query: Query<'w, 's, &'static Party>,
     clan_query: Query<'w, 's, &'static Clan>,
@@ -60,6 +68,7 @@ pub struct QuestSystemParameters<'w, 's> {
 
 #[derive(SystemParam)]
 pub struct QuestSystemResources<'w, 's> {
+    game_config: Res<'w, GameConfig>,
     game_data: Res<'w, GameData>,
     time: Res<'w, Time>,
     world_rates: Res<'w, WorldRates>,
@@ -97,6 +106,7 @@ pub struct QuestSourceEntityQuery<'w> {
     team: &'w mut Team,
     union_membership: Option<&'w mut UnionMembership>,
     clan_membership: Option<&'w ClanMembership>,
+    statistics: Option<&'w mut CharacterStatistics>,
 }
 
 struct QuestParameters<'a, 'b, 'w> {
@@ -105,6 +115,9 @@ struct QuestParameters<'a, 'b, 'w> {
     selected_npc: Option<Entity>,
     selected_quest_index: Option<usize>,
     next_trigger_name: Option<String>,
+    delayed_triggers: Vec<DelayedQuestTrigger>,
+    completed_quests: u32,
+    earned_clan_points: bool,
 }
 
 fn quest_condition_operator<T: PartialEq + PartialOrd>(
@@ -145,6 +158,26 @@ fn quest_condition_select_quest(quest_parameters: &mut QuestParameters, quest_id
     false
 }
 
+fn quest_condition_repeatable_quest_completed(
+    quest_system_resources: &QuestSystemResources,
+    quest_parameters: &QuestParameters,
+    quest_id: usize,
+) -> bool {
+    let quest_resets = &quest_system_resources.game_config.quest_resets;
+    let Some(reset_period) = quest_resets.get_reset_period(quest_id) else {
+        return false;
+    };
+
+    quest_parameters
+        .source
+        .quest_state
+        .as_ref()
+        .and_then(|quest_state| quest_state.repeatable_quest_completions.get(&quest_id))
+        .map_or(false, |&completed_time| {
+            completed_time >= quest_resets.get_last_reset(reset_period, chrono::Local::now())
+        })
+}
+
 fn quest_condition_quest_switch(
     quest_parameters: &mut QuestParameters,
     switch_id: usize,
@@ -449,20 +482,25 @@ fn quest_condition_select_npc(
     quest_parameters.selected_npc.is_some()
 }
 
+fn quest_selected_object(
+    object_type: QsdObjectType,
+    selected_event_object: Option<Entity>,
+    selected_npc: Option<Entity>,
+) -> Option<Entity> {
+    match object_type {
+        QsdObjectType::SelectedEvent => selected_event_object,
+        QsdObjectType::SelectedNpc => selected_npc,
+        _ => None,
+    }
+}
+
 fn quest_condition_object_variable(
     quest_system_parameters: &mut QuestSystemParameters,
-    quest_parameters: &mut QuestParameters,
-    object_type: QsdObjectType,
+    entity: Option<Entity>,
     variable_id: usize,
     operator: QsdConditionOperator,
     value: i32,
 ) -> bool {
-    let entity = match object_type {
-        QsdObjectType::SelectedEvent => quest_parameters.selected_event_object,
-        QsdObjectType::SelectedNpc => quest_parameters.selected_npc,
-        _ => return false,
-    };
-
     let variable_value = entity
         .and_then(|entity| {
             quest_system_parameters
@@ -902,8 +940,11 @@ fn quest_trigger_check_conditions(
                 value,
             } => quest_condition_object_variable(
                 quest_system_parameters,
-                quest_parameters,
-                object,
+                quest_selected_object(
+                    object,
+                    quest_parameters.selected_event_object,
+                    quest_parameters.selected_npc,
+                ),
                 variable_id,
                 operator,
                 value,
@@ -1027,6 +1068,23 @@ fn quest_trigger_check_conditions(
         }
     }
 
+    // Repeatable quests can not be given again until their next reset
+    for reward in quest_trigger.rewards.iter() {
+        let quest_id = match *reward {
+            QsdReward::AddQuest { id } | QsdReward::ChangeSelectedQuest { id, .. } => id,
+            _ => continue,
+        };
+
+        if quest_condition_repeatable_quest_completed(
+            quest_system_resources,
+            quest_parameters,
+            quest_id,
+        ) {
+            log::trace!(target: "quest", "Condition Failed repeatable quest {} already completed", quest_id);
+            return false;
+        }
+    }
+
     true
 }
 
@@ -1249,6 +1307,10 @@ fn quest_reward_calculated_money(
 
     if let Some(inventory) = quest_parameters.source.inventory.as_mut() {
         if inventory.try_add_money(money).is_ok() {
+            if let Some(statistics) = quest_parameters.source.statistics.as_mut() {
+                statistics.add_money_earned(money);
+            }
+
             reset_quest_calculated_money_dup_count_var(
                 quest_parameters.selected_quest_index,
                 quest_parameters.source.quest_state.as_mut(),
@@ -1269,13 +1331,30 @@ fn quest_reward_calculated_money(
 }
 
 fn quest_reward_remove_selected_quest(
-    _quest_system_resources: &QuestSystemResources,
+    quest_system_resources: &QuestSystemResources,
     quest_parameters: &mut QuestParameters,
 ) -> bool {
     if let Some(quest_state) = quest_parameters.source.quest_state.as_mut() {
         if let Some(quest_index) = quest_parameters.selected_quest_index {
             if let Some(quest_slot) = quest_state.get_quest_slot_mut(quest_index) {
-                *quest_slot = None;
+                // Quests are removed from their slot once completed
+                if let Some(quest) = quest_slot.take() {
+                    if quest_system_resources
+                        .game_config
+                        .quest_resets
+                        .get_reset_period(quest.quest_id)
+                        .is_some()
+                    {
+                        quest_state
+                            .repeatable_quest_completions
+                            .insert(quest.quest_id, chrono::Local::now().timestamp());
+                    }
+
+                    if let Some(statistics) = quest_parameters.source.statistics.as_mut() {
+                        statistics.quests_completed += 1;
+                    }
+                    quest_parameters.completed_quests += 1;
+                }
                 return true;
             }
         }
@@ -1460,7 +1539,7 @@ fn quest_reward_add_skill(
                 team: Some(&quest_parameters.source.team),
                 character_info: quest_parameters.source.character_info.as_deref(),
                 experience_points: quest_parameters.source.experience_points.as_deref(),
-                inventory: quest_parameters.source.inventory.as_deref(),
+                inventory: quest_parameters.source.inventory.as_deref_mut(),
                 stamina: quest_parameters.source.stamina.as_deref(),
                 stat_points: quest_parameters.source.stat_points.as_deref(),
                 union_membership: quest_parameters.source.union_membership.as_deref(),
@@ -1531,8 +1610,15 @@ fn quest_reward_reset_skills(
     quest_parameters: &mut QuestParameters,
 ) -> bool {
     if let Some(skill_list) = quest_parameters.source.skill_list.as_mut() {
-        // TODO: This is quite irose specific, we should factor it out
-        for page in skill_list.pages[1..].iter_mut() {
+        let data_decoder = &quest_system_resources.game_data.data_decoder;
+        for page in skill_list.pages.iter_mut().filter(|page| {
+            // Basic skills are not bought with skill points so are kept, clan skills are
+            // granted by the clan rather than learnt by the character
+            !matches!(
+                data_decoder.decode_skill_page_kind(page.page_type),
+                Some(SkillPageKind::Basic | SkillPageKind::Clan)
+            )
+        }) {
             for skill in page.skills.iter_mut() {
                 *skill = None;
             }
@@ -1576,6 +1662,7 @@ fn quest_reward_teleport(
 }
 
 fn quest_reward_ability_value(
+    quest_system_parameters: &mut QuestSystemParameters,
     quest_system_resources: &QuestSystemResources,
     quest_parameters: &mut QuestParameters,
     reward_operator: QsdRewardOperator,
@@ -1586,13 +1673,13 @@ fn quest_reward_ability_value(
         .game_data
         .data_decoder
         .decode_ability_type(ability_type.get());
-    if ability_type.is_none() {
+    let Some(ability_type) = ability_type else {
         return false;
-    }
+    };
 
-    match reward_operator {
+    let result = match reward_operator {
         QsdRewardOperator::Set => ability_values_set_value(
-            ability_type.unwrap(),
+            ability_type,
             value,
             Some(quest_parameters.source.ability_values),
             quest_parameters.source.basic_stats.as_mut(),
@@ -1604,7 +1691,7 @@ fn quest_reward_ability_value(
             quest_parameters.source.game_client,
         ),
         QsdRewardOperator::Add => ability_values_add_value(
-            ability_type.unwrap(),
+            ability_type,
             value,
             Some(quest_parameters.source.ability_values),
             quest_parameters.source.basic_stats.as_mut(),
@@ -1619,7 +1706,7 @@ fn quest_reward_ability_value(
             quest_parameters.source.game_client,
         ),
         QsdRewardOperator::Subtract => ability_values_add_value(
-            ability_type.unwrap(),
+            ability_type,
             -value,
             Some(quest_parameters.source.ability_values),
             quest_parameters.source.basic_stats.as_mut(),
@@ -1634,7 +1721,7 @@ fn quest_reward_ability_value(
             quest_parameters.source.game_client,
         ),
         QsdRewardOperator::Zero => ability_values_set_value(
-            ability_type.unwrap(),
+            ability_type,
             0,
             Some(quest_parameters.source.ability_values),
             quest_parameters.source.basic_stats.as_mut(),
@@ -1646,7 +1733,7 @@ fn quest_reward_ability_value(
             quest_parameters.source.game_client,
         ),
         QsdRewardOperator::One => ability_values_set_value(
-            ability_type.unwrap(),
+            ability_type,
             1,
             Some(quest_parameters.source.ability_values),
             quest_parameters.source.basic_stats.as_mut(),
@@ -1657,7 +1744,21 @@ fn quest_reward_ability_value(
             quest_parameters.source.union_membership.as_mut(),
             quest_parameters.source.game_client,
         ),
+    };
+
+    if result && matches!(ability_type, AbilityType::Experience) {
+        // Experience is only checked against the level cap and for level ups when rewarded
+        quest_system_parameters
+            .reward_xp_events
+            .send(RewardXpEvent::new(
+                quest_parameters.source.entity,
+                0,
+                false,
+                None,
+            ));
     }
+
+    result
 }
 
 fn quest_reward_operator(operator: QsdRewardOperator, variable_value: i32, value: i32) -> i32 {
@@ -1751,18 +1852,11 @@ fn quest_reward_set_health_mana_percent(
 
 fn quest_reward_object_variable(
     quest_system_parameters: &mut QuestSystemParameters,
-    quest_parameters: &mut QuestParameters,
-    object_type: QsdObjectType,
+    entity: Option<Entity>,
     variable_id: usize,
     operator: QsdRewardOperator,
     value: i32,
 ) -> bool {
-    let entity = match object_type {
-        QsdObjectType::SelectedEvent => quest_parameters.selected_event_object,
-        QsdObjectType::SelectedNpc => quest_parameters.selected_npc,
-        _ => return false,
-    };
-
     entity
         .and_then(|entity| {
             quest_system_parameters
@@ -1781,6 +1875,39 @@ fn quest_reward_object_variable(
         .unwrap_or(false)
 }
 
+fn quest_reward_trigger_after_delay(
+    quest_system_resources: &QuestSystemResources,
+    source_entity: Option<Entity>,
+    selected_event_object: Option<Entity>,
+    selected_npc: Option<Entity>,
+    object_type: QsdObjectType,
+    delay: Duration,
+    trigger_name: &str,
+) -> Option<DelayedQuestTrigger> {
+    let (selected_event_object, selected_npc) = match object_type {
+        QsdObjectType::SelectedEvent => (selected_event_object, None),
+        QsdObjectType::SelectedNpc => (None, selected_npc),
+        _ => return None,
+    };
+
+    if selected_event_object.is_none() && selected_npc.is_none() {
+        return None;
+    }
+
+    quest_system_resources
+        .game_data
+        .quests
+        .get_trigger_by_name(trigger_name)?;
+
+    Some(DelayedQuestTrigger {
+        source_entity,
+        trigger_hash: QuestTriggerHash::from(trigger_name),
+        selected_event_object,
+        selected_npc,
+        run_at: quest_system_resources.time.elapsed() + delay,
+    })
+}
+
 fn quest_reward_spawn_monster(
     quest_system_parameters: &mut QuestSystemParameters,
     quest_system_resources: &QuestSystemResources,
@@ -2065,7 +2192,9 @@ fn quest_reward_clan_points(
                     .send(ClanEvent::AddPoints {
                         clan_entity,
                         points: value as i64,
+                        contributor: Some(quest_parameters.source.entity),
                     });
+                quest_parameters.earned_clan_points = true;
             }
             QsdRewardOperator::Subtract => {
                 quest_system_parameters
@@ -2073,6 +2202,7 @@ fn quest_reward_clan_points(
                     .send(ClanEvent::AddPoints {
                         clan_entity,
                         points: -value as i64,
+                        contributor: None,
                     });
             }
             _ => {
@@ -2166,6 +2296,7 @@ fn quest_trigger_apply_rewards(
                 operator,
                 value,
             } => quest_reward_ability_value(
+                quest_system_parameters,
                 quest_system_resources,
                 quest_parameters,
                 operator,
@@ -2268,12 +2399,35 @@ fn quest_trigger_apply_rewards(
                 value,
             } => quest_reward_object_variable(
                 quest_system_parameters,
-                quest_parameters,
-                object,
+                quest_selected_object(
+                    object,
+                    quest_parameters.selected_event_object,
+                    quest_parameters.selected_npc,
+                ),
                 variable_id,
                 operator,
                 value,
             ),
+            QsdReward::TriggerAfterDelay {
+                object,
+                delay,
+                ref trigger,
+            } => {
+                if let Some(delayed_trigger) = quest_reward_trigger_after_delay(
+                    quest_system_resources,
+                    Some(quest_parameters.source.entity),
+                    quest_parameters.selected_event_object,
+                    quest_parameters.selected_npc,
+                    object,
+                    delay,
+                    trigger,
+                ) {
+                    quest_parameters.delayed_triggers.push(delayed_trigger);
+                    true
+                } else {
+                    false
+                }
+            }
             QsdReward::SpawnMonster {
                 npc,
                 count,
@@ -2341,7 +2495,6 @@ fn quest_trigger_apply_rewards(
                 warn!("Unimplemented quest reward: {:?}", reward);
                 false
             } /*
-              QsdReward::TriggerAfterDelay(_, _, _) => todo!(),
               QsdReward::FormatAnnounceMessage(_, _) => todo!(),
               QsdReward::TriggerForZoneTeam(_, _, _) => todo!(),
               QsdReward::SetRevivePosition(_) => todo!(),
@@ -2361,81 +2514,277 @@ fn quest_trigger_apply_rewards(
     true
 }
 
+/// Runs a delayed trigger whose character has left the game, or which was restored from
+/// zone storage. Only the conditions and rewards for the selected object can pass, so a
+/// gate opened by the character is still closed again.
+fn quest_run_object_trigger(
+    quest_system_parameters: &mut QuestSystemParameters,
+    quest_system_resources: &QuestSystemResources,
+    trigger_hash: QuestTriggerHash,
+    selected_event_object: Option<Entity>,
+    selected_npc: Option<Entity>,
+) -> Vec<DelayedQuestTrigger> {
+    let mut delayed_triggers = Vec::new();
+    let mut trigger = quest_system_resources
+        .game_data
+        .quests
+        .get_trigger_by_hash(trigger_hash);
+
+    while let Some(quest_trigger) = trigger {
+        let mut next_trigger_name = None;
+
+        let success = quest_trigger
+            .conditions
+            .iter()
+            .all(|condition| match *condition {
+                QsdCondition::ObjectVariable {
+                    object,
+                    variable_id,
+                    operator,
+                    value,
+                } => quest_condition_object_variable(
+                    quest_system_parameters,
+                    quest_selected_object(object, selected_event_object, selected_npc),
+                    variable_id,
+                    operator,
+                    value,
+                ),
+                _ => false,
+            })
+            && quest_trigger.rewards.iter().all(|reward| match *reward {
+                QsdReward::ObjectVariable {
+                    object,
+                    variable_id,
+                    operator,
+                    value,
+                } => quest_reward_object_variable(
+                    quest_system_parameters,
+                    quest_selected_object(object, selected_event_object, selected_npc),
+                    variable_id,
+                    operator,
+                    value,
+                ),
+                QsdReward::TriggerAfterDelay {
+                    object,
+                    delay,
+                    ref trigger,
+                } => quest_reward_trigger_after_delay(
+                    quest_system_resources,
+                    None,
+                    selected_event_object,
+                    selected_npc,
+                    object,
+                    delay,
+                    trigger,
+                )
+                .map(|delayed_trigger| delayed_triggers.push(delayed_trigger))
+                .is_some(),
+                QsdReward::Trigger { ref name } => {
+                    next_trigger_name = Some(name.clone());
+                    true
+                }
+                _ => false,
+            });
+
+        let next_trigger_name = if success {
+            next_trigger_name
+        } else {
+            quest_trigger.next_trigger_name.clone()
+        };
+        trigger = next_trigger_name.and_then(|name| {
+            quest_system_resources
+                .game_data
+                .quests
+                .get_trigger_by_name(&name)
+        });
+    }
+
+    delayed_triggers
+}
+
+#[allow(clippy::too_many_arguments)]
+fn quest_run_trigger(
+    quest_system_parameters: &mut QuestSystemParameters,
+    quest_system_resources: &QuestSystemResources,
+    query: &mut Query<QuestSourceEntityQuery>,
+    trigger_entity: Entity,
+    trigger_hash: QuestTriggerHash,
+    selected_event_object: Option<Entity>,
+    selected_npc: Option<Entity>,
+    send_result: bool,
+) -> Vec<DelayedQuestTrigger> {
+    let Ok(mut quest_source_entity) = query.get_mut(trigger_entity) else {
+        return Vec::new();
+    };
+
+    let mut trigger = quest_system_resources
+        .game_data
+        .quests
+        .get_trigger_by_hash(trigger_hash);
+    let mut success = false;
+    let mut quest_parameters = QuestParameters {
+        source: &mut quest_source_entity,
+        selected_event_object,
+        selected_npc,
+        selected_quest_index: None,
+        next_trigger_name: None,
+        delayed_triggers: Vec::new(),
+        completed_quests: 0,
+        earned_clan_points: false,
+    };
+
+    while trigger.is_some() {
+        let quest_trigger = trigger.unwrap();
+
+        if quest_trigger_check_conditions(
+            quest_system_parameters,
+            quest_system_resources,
+            &mut quest_parameters,
+            quest_trigger,
+        ) && quest_trigger_apply_rewards(
+            quest_system_parameters,
+            quest_system_resources,
+            &mut quest_parameters,
+            quest_trigger,
+        ) {
+            success = true;
+
+            if quest_parameters.next_trigger_name.is_some() {
+                trigger = quest_parameters.next_trigger_name.take().and_then(|name| {
+                    quest_system_resources
+                        .game_data
+                        .quests
+                        .get_trigger_by_name(&name)
+                });
+            } else {
+                trigger = None;
+            }
+        } else {
+            trigger = trigger
+                .unwrap()
+                .next_trigger_name
+                .as_ref()
+                .and_then(|name| {
+                    quest_system_resources
+                        .game_data
+                        .quests
+                        .get_trigger_by_name(name)
+                });
+        }
+    }
+
+    let delayed_triggers = std::mem::take(&mut quest_parameters.delayed_triggers);
+
+    // Quests completed by a trigger which earned clan points count as clan quests
+    if quest_parameters.earned_clan_points {
+        if let Some(clan_entity) = quest_parameters
+            .source
+            .clan_membership
+            .and_then(|clan_membership| clan_membership.clan())
+        {
+            for _ in 0..quest_parameters.completed_quests {
+                quest_system_parameters
+                    .clan_events
+                    .send(ClanEvent::QuestCompleted {
+                        clan_entity,
+                        entity: trigger_entity,
+                    });
+            }
+        }
+    }
+
+    if success {
+        quest_system_parameters
+            .title_events
+            .send(TitleEvent::QuestTriggerSuccess {
+                entity: trigger_entity,
+                trigger_hash,
+            });
+    }
+
+    if send_result {
+        if let Some(game_client) = quest_source_entity.game_client {
+            game_client
+                .server_message_tx
+                .send(ServerMessage::QuestTriggerResult {
+                    success,
+                    trigger_hash,
+                })
+                .ok();
+        }
+    }
+
+    delayed_triggers
+}
+
 pub fn quest_system(
     mut quest_system_parameters: QuestSystemParameters,
     quest_system_resources: QuestSystemResources,
     mut query: Query<QuestSourceEntityQuery>,
     mut quest_trigger_events: EventReader<QuestTriggerEvent>,
+    mut delayed_quest_triggers: ResMut<DelayedQuestTriggers>,
 ) {
     for &QuestTriggerEvent {
         trigger_entity,
         trigger_hash,
     } in quest_trigger_events.iter()
     {
-        let mut trigger = quest_system_resources
-            .game_data
-            .quests
-            .get_trigger_by_hash(trigger_hash);
-        let mut success = false;
-
-        if let Ok(mut quest_source_entity) = query.get_mut(trigger_entity) {
-            let mut quest_parameters = QuestParameters {
-                source: &mut quest_source_entity,
-                selected_event_object: None,
-                selected_npc: None,
-                selected_quest_index: None,
-                next_trigger_name: None,
-            };
-
-            while trigger.is_some() {
-                let quest_trigger = trigger.unwrap();
-
-                if quest_trigger_check_conditions(
-                    &mut quest_system_parameters,
-                    &quest_system_resources,
-                    &mut quest_parameters,
-                    quest_trigger,
-                ) && quest_trigger_apply_rewards(
-                    &mut quest_system_parameters,
-                    &quest_system_resources,
-                    &mut quest_parameters,
-                    quest_trigger,
-                ) {
-                    success = true;
-
-                    if quest_parameters.next_trigger_name.is_some() {
-                        trigger = quest_parameters.next_trigger_name.take().and_then(|name| {
-                            quest_system_resources
-                                .game_data
-                                .quests
-                                .get_trigger_by_name(&name)
-                        });
-                    } else {
-                        trigger = None;
-                    }
-                } else {
-                    trigger = trigger
-                        .unwrap()
-                        .next_trigger_name
-                        .as_ref()
-                        .and_then(|name| {
-                            quest_system_resources
-                                .game_data
-                                .quests
-                                .get_trigger_by_name(name)
-                        });
-                }
-            }
-
-            if let Some(game_client) = quest_source_entity.game_client {
-                game_client
-                    .server_message_tx
-                    .send(ServerMessage::QuestTriggerResult {
-                        success,
-                        trigger_hash,
-                    })
-                    .ok();
-            }
+        let delayed_triggers = quest_run_trigger(
+            &mut quest_system_parameters,
+            &quest_system_resources,
+            &mut query,
+            trigger_entity,
+            trigger_hash,
+            None,
+            None,
+            true,
+        );
+        if !delayed_triggers.is_empty() {
+            delayed_quest_triggers.pending.extend(delayed_triggers);
         }
     }
+
+    // Run any delayed triggers which are now due, the client did not request
+    // these so it is not sent a trigger result. Only touch the resource when a
+    // trigger is due, so its change detection can be used to save the zone.
+    let now = quest_system_resources.time.elapsed();
+    if !delayed_quest_triggers
+        .pending
+        .iter()
+        .any(|delayed_trigger| delayed_trigger.run_at <= now)
+    {
+        return;
+    }
+
+    let (due_triggers, waiting_triggers): (Vec<_>, Vec<_>) =
+        std::mem::take(&mut delayed_quest_triggers.pending)
+            .into_iter()
+            .partition(|delayed_trigger| delayed_trigger.run_at <= now);
+    delayed_quest_triggers.pending = waiting_triggers;
+
+    for delayed_trigger in due_triggers {
+        let delayed_triggers = match delayed_trigger
+            .source_entity
+            .filter(|source_entity| query.contains(*source_entity))
+        {
+            Some(source_entity) => quest_run_trigger(
+                &mut quest_system_parameters,
+                &quest_system_resources,
+                &mut query,
+                source_entity,
+                delayed_trigger.trigger_hash,
+                delayed_trigger.selected_event_object,
+                delayed_trigger.selected_npc,
+                false,
+            ),
+            None => quest_run_object_trigger(
+                &mut quest_system_parameters,
+                &quest_system_resources,
+                delayed_trigger.trigger_hash,
+                delayed_trigger.selected_event_object,
+                delayed_trigger.selected_npc,
+            ),
+        };
+        delayed_quest_triggers.pending.extend(delayed_triggers);
+    }
 }
@@ -3,7 +3,7 @@ use crate::game::{
     components::{DroppedItem, GameClient, Inventory, Position},
     events::RewardItemEvent,
     messages::server::ServerMessage,
-    resources::ClientEntityList,
+    resources::{ClientEntityList, GameConfig},
 };
 use bevy::{
     ecs::{
@@ -12,14 +12,18 @@ use bevy::{
     },
     time::Time,
 };
+use std::time::Duration;
 
 pub fn reward_item_system(
     mut commands: Commands,
     mut query: Query<(&Position, &mut Inventory, Option<&GameClient>)>,
     mut reward_item_events: EventReader<RewardItemEvent>,
     mut client_entity_list: ResMut<ClientEntityList>,
+    game_config: Res<GameConfig>,
     time: Res<Time>,
 ) {
+    let owner_duration = Duration::from_secs(game_config.item_drops.owner_duration_secs);
+
     for event in reward_item_events.iter() {
         if let Ok((position, mut inventory, game_client)) = query.get_mut(event.entity) {
             match inventory.try_add_item(event.item.clone()) {
@@ -40,7 +44,7 @@ pub fn reward_item_system(
                             &mut client_entity_list,
                             DroppedItem::Item(item),
                             position,
-                            Some(event.entity),
+                            Some((event.entity, owner_duration)),
                             None,
                             &time,
                         );
@@ -8,10 +8,11 @@ use log::{error, info};
 use crate::game::{
     bundles::client_entity_leave_zone,
     components::{
-        Account, Bank, BasicStats, CharacterInfo, ClanMembership, ClientEntity, ClientEntitySector,
-        Equipment, ExperiencePoints, HealthPoints, Hotbar, Inventory, Level, ManaPoints,
-        PartyMembership, Position, QuestState, SkillList, SkillPoints, Stamina, StatPoints,
-        UnionMembership,
+        Account, Bank, BasicStats, CharacterInfo, CharacterLootSettings, CharacterPrivacy,
+        CharacterSkillMastery, CharacterStatistics, CharacterTitles, ClanMembership, ClientEntity,
+        ClientEntitySector, Equipment, ExperiencePoints, HealthPoints, Hotbar, Inventory, Level,
+        ManaPoints, NewCharacter, PartyMembership, Position, QuestState, SkillList, SkillPoints,
+        Stamina, StatPoints, UnionMembership,
     },
     events::{ClanEvent, PartyMemberEvent, SaveEvent},
     resources::ClientEntityList,
@@ -40,6 +41,12 @@ pub struct SaveEntityQuery<'w> {
     quest_state: &'w QuestState,
     union_membership: &'w UnionMembership,
     stamina: &'w Stamina,
+    privacy: Option<&'w CharacterPrivacy>,
+    titles: Option<&'w CharacterTitles>,
+    statistics: Option<&'w CharacterStatistics>,
+    loot_settings: Option<&'w CharacterLootSettings>,
+    skill_mastery: Option<&'w CharacterSkillMastery>,
+    new_character: Option<&'w NewCharacter>,
     party_membership: &'w PartyMembership,
     clan_membership: &'w ClanMembership,
 }
@@ -77,6 +84,12 @@ pub fn save_system(
                         quest_state: character.quest_state.clone(),
                         union_membership: character.union_membership.clone(),
                         stamina: *character.stamina,
+                        privacy: character.privacy.copied().unwrap_or_default(),
+                        titles: character.titles.cloned().unwrap_or_default(),
+                        statistics: character.statistics.cloned().unwrap_or_default(),
+                        loot_settings: character.loot_settings.copied().unwrap_or_default(),
+                        skill_mastery: character.skill_mastery.cloned().unwrap_or_default(),
+                        is_new_character: character.new_character.is_some(),
                     };
                     match storage.save() {
                         Ok(_) => info!("Saved character {}", &character.character_info.name),
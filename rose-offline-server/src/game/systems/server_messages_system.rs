@@ -1,15 +1,29 @@
-use bevy::ecs::prelude::{Query, ResMut};
+use bevy::{
+    ecs::prelude::{Query, Res, ResMut},
+    math::Vec3Swizzles,
+    time::Time,
+};
+use std::time::Duration;
 
 use crate::game::{
-    components::{ClientEntityVisibility, GameClient, Position},
-    resources::ServerMessages,
+    components::{ClientEntityVisibility, GameClient, Position, ThrottledMovement},
+    messages::server::ServerMessage,
+    resources::{ClientEntityList, GameConfig, ServerMessages},
 };
 
 pub fn server_messages_system(
-    query: Query<(&GameClient, &Position, &ClientEntityVisibility)>,
+    mut query: Query<(&GameClient, &Position, &mut ClientEntityVisibility)>,
     mut server_messages: ResMut<ServerMessages>,
+    client_entity_list: Res<ClientEntityList>,
+    game_config: Res<GameConfig>,
+    time: Res<Time>,
 ) {
-    for (game_client, position, client_visibility) in query.iter() {
+    let now = time.elapsed();
+    let full_rate_distance = game_config.movement_updates.full_rate_distance;
+    let throttled_interval =
+        Duration::from_millis(game_config.movement_updates.throttled_interval_ms);
+
+    for (game_client, position, mut client_visibility) in query.iter_mut() {
         for message in server_messages.pending_global_messages.iter() {
             game_client
                 .server_message_tx
@@ -26,16 +40,68 @@ pub fn server_messages_system(
             }
         }
 
+        let client_entity_zone = client_entity_list.get_zone(position.zone_id);
+        let client_visibility = &mut *client_visibility;
+
+        // Entities which are no longer visible do not need their pending movement
+        let visible_entities = client_visibility.entities;
+        client_visibility
+            .movement_updates
+            .retain(|index, _| visible_entities.get(*index).map_or(false, |b| *b));
+
         for message in server_messages.pending_entity_messages.iter() {
-            if position.zone_id == message.zone_id
-                && client_visibility
+            if position.zone_id != message.zone_id
+                || !client_visibility
+                    .entities
                     .get(message.entity_id.0)
                     .map_or(false, |b| *b)
             {
-                game_client
-                    .server_message_tx
-                    .send(message.message.clone())
-                    .ok();
+                continue;
+            }
+
+            if matches!(message.message, ServerMessage::MoveEntity { .. }) {
+                let is_far = client_entity_zone
+                    .and_then(|zone| zone.get_entity(message.entity_id))
+                    .map_or(false, |(_, _, entity_position)| {
+                        entity_position.xy().distance(position.position.xy()) > full_rate_distance
+                    });
+                let movement = client_visibility
+                    .movement_updates
+                    .entry(message.entity_id.0)
+                    .or_insert(ThrottledMovement {
+                        last_sent: Duration::ZERO,
+                        pending: None,
+                    });
+
+                if is_far && now < movement.last_sent + throttled_interval {
+                    // Only the latest destination matters, it replaces any pending update
+                    movement.pending = Some(message.message.clone());
+                    continue;
+                }
+
+                movement.last_sent = now;
+                movement.pending = None;
+            } else if let Some(pending) = client_visibility
+                .movement_updates
+                .get_mut(&message.entity_id.0)
+                .and_then(|movement| movement.pending.take())
+            {
+                // Keep message ordering, any pending movement happened before this message
+                game_client.server_message_tx.send(pending).ok();
+            }
+
+            game_client
+                .server_message_tx
+                .send(message.message.clone())
+                .ok();
+        }
+
+        for movement in client_visibility.movement_updates.values_mut() {
+            if now >= movement.last_sent + throttled_interval {
+                if let Some(pending) = movement.pending.take() {
+                    movement.last_sent = now;
+                    game_client.server_message_tx.send(pending).ok();
+                }
             }
         }
     }
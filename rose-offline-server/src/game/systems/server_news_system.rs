@@ -0,0 +1,47 @@
+use std::collections::HashSet;
+
+use bevy::{
+    ecs::prelude::{Added, Entity, Local, Query, Res, ResMut, With},
+    time::Time,
+};
+
+use crate::game::{
+    components::{ClientEntity, GameClient},
+    messages::server::ServerMessage,
+    resources::{ServerMessages, ServerNews},
+};
+
+pub fn server_news_system(
+    query_joined_zone: Query<(Entity, &GameClient), Added<ClientEntity>>,
+    query_game_clients: Query<(), With<GameClient>>,
+    mut server_news: ResMut<ServerNews>,
+    mut server_messages: ResMut<ServerMessages>,
+    mut motd_sent: Local<HashSet<Entity>>,
+    time: Res<Time>,
+) {
+    // Only the first zone joined after logging in receives the message of the day
+    motd_sent.retain(|entity| query_game_clients.contains(*entity));
+
+    for (entity, game_client) in query_joined_zone.iter() {
+        if !motd_sent.insert(entity) {
+            continue;
+        }
+
+        for line in server_news.motd.iter() {
+            game_client
+                .server_message_tx
+                .send(ServerMessage::AnnounceChat {
+                    name: None,
+                    text: line.clone(),
+                })
+                .ok();
+        }
+    }
+
+    if let Some(announcement) = server_news.get_next_announcement(time.elapsed()) {
+        server_messages.send_global_message(ServerMessage::AnnounceChat {
+            name: None,
+            text: announcement.to_string(),
+        });
+    }
+}
@@ -19,15 +19,16 @@ use rose_data::{
 use rose_game_common::{components::Money, data::Damage};
 
 use crate::game::{
-    bundles::{ability_values_get_value, MonsterBundle, GLOBAL_SKILL_COOLDOWN},
+    bundles::{ability_values_get_value, pvp_check, MonsterBundle, GLOBAL_SKILL_COOLDOWN},
     components::{
-        AbilityValues, ClanMembership, ClientEntity, ClientEntityType, Cooldowns, Dead,
-        ExperiencePoints, GameClient, HealthPoints, Inventory, Level, ManaPoints, MoveMode,
-        MoveSpeed, PartyMembership, Position, SpawnOrigin, Stamina, StatusEffects, Team,
+        AbilityValues, CharacterSkillMastery, ClanMembership, ClientEntity, ClientEntityType,
+        Cooldowns, Dead, ExperiencePoints, GameClient, HealthPoints, Inventory, Level, ManaPoints,
+        MoveMode, MoveSpeed, PartyMembership, Position, SkillList, Stamina, StatusEffects,
+        StatusEffectsRegen, Team,
     },
     events::{DamageEvent, ItemLifeEvent, SkillEvent, SkillEventTarget},
     messages::server::{CancelCastingSkillReason, ServerMessage},
-    resources::{ClientEntityList, ServerMessages},
+    resources::{ClientEntityList, GameConfig, ServerMessages},
     GameData,
 };
 
@@ -50,6 +51,7 @@ pub struct SkillSystemParameters<'w, 's> {
 
 #[derive(SystemParam)]
 pub struct SkillSystemResources<'w, 's> {
+    game_config: Res<'w, GameConfig>,
     game_data: Res<'w, GameData>,
     time: Res<'w, Time>,
 
@@ -72,10 +74,12 @@ pub struct SkillCasterQuery<'w> {
     clan_membership: Option<&'w ClanMembership>,
     game_client: Option<&'w GameClient>,
     party_membership: Option<&'w PartyMembership>,
+    skill_list: Option<&'w SkillList>,
 
     experience_points: Option<&'w mut ExperiencePoints>,
     cooldowns: Option<&'w mut Cooldowns>,
     inventory: Option<&'w mut Inventory>,
+    skill_mastery: Option<&'w mut CharacterSkillMastery>,
 }
 
 #[derive(WorldQuery)]
@@ -98,16 +102,29 @@ pub struct SkillTargetQuery<'w> {
     mana_points: Option<&'w mut ManaPoints>,
     stamina: Option<&'w mut Stamina>,
     status_effects: &'w mut StatusEffects,
+    status_effects_regen: &'w mut StatusEffectsRegen,
 }
 
 // TODO: Deduplicate code with skill_use.rs check_skill_target_filter
 fn check_skill_target_filter(
+    game_data: &GameData,
     skill_caster: &SkillCasterQueryItem,
     skill_target: &SkillTargetQueryItem,
     skill_data: &SkillData,
 ) -> bool {
     let target_is_alive = skill_target.health_points.hp > 0;
     let target_is_caster = skill_caster.entity == skill_target.entity;
+    let target_is_enemy = || {
+        !target_is_caster
+            && pvp_check(
+                game_data,
+                skill_caster.client_entity,
+                skill_caster.position,
+                skill_target.client_entity,
+                skill_target.position,
+            )
+            .is_enemy(skill_caster.team, skill_target.team)
+    };
 
     match skill_data.target_filter {
         SkillTargetFilter::OnlySelf => target_is_alive && target_is_caster,
@@ -144,11 +161,11 @@ fn check_skill_target_filter(
         SkillTargetFilter::Enemy => {
             target_is_alive
                 && skill_target.team.id != Team::DEFAULT_NPC_TEAM_ID
-                && skill_caster.team.id != skill_target.team.id
+                && target_is_enemy()
         }
         SkillTargetFilter::EnemyCharacter => {
             target_is_alive
-                && skill_caster.team.id != skill_target.team.id
+                && target_is_enemy()
                 && matches!(
                     skill_target.client_entity.entity_type,
                     ClientEntityType::Character
@@ -179,7 +196,7 @@ fn check_skill_target_filter(
         }
         SkillTargetFilter::EnemyMonster => {
             target_is_alive
-                && skill_caster.team.id != skill_target.team.id
+                && target_is_enemy()
                 && matches!(
                     skill_target.client_entity.entity_type,
                     ClientEntityType::Monster
@@ -195,7 +212,12 @@ fn apply_skill_status_effects_to_entity(
     skill_target: &mut SkillTargetQueryItem,
     skill_data: &SkillData,
 ) -> Result<(), SkillCastError> {
-    if !check_skill_target_filter(skill_caster, skill_target, skill_data) {
+    if !check_skill_target_filter(
+        &skill_system_resources.game_data,
+        skill_caster,
+        skill_target,
+        skill_data,
+    ) {
         return Err(SkillCastError::InvalidTarget);
     }
 
@@ -209,6 +231,8 @@ fn apply_skill_status_effects_to_entity(
     }
 
     let mut effect_success = [false, false];
+    let mut effect_rejected = false;
+    let mut cleared_status_effects = Vec::new();
     for (effect_index, status_effect_data) in skill_data
         .status_effects
         .iter()
@@ -249,6 +273,18 @@ fn apply_skill_status_effects_to_entity(
             }
         }
 
+        if status_effect_data.status_effect_type.is_clear() {
+            // A skill with power can only clear status effects up to that value
+            cleared_status_effects.extend(skill_target.status_effects.clear_status_effects(
+                &mut skill_target.status_effects_regen,
+                &skill_system_resources.game_data.status_effects,
+                status_effect_data.status_effect_type,
+                (skill_data.power > 0).then_some(skill_data.power as i32),
+            ));
+            effect_success[effect_index] = true;
+            continue;
+        }
+
         let adjust_value = if matches!(
             status_effect_data.status_effect_type,
             StatusEffectType::AdditionalDamageRate
@@ -307,6 +343,8 @@ fn apply_skill_status_effects_to_entity(
             }
 
             effect_success[effect_index] = true;
+        } else {
+            effect_rejected = true;
         }
     }
 
@@ -354,7 +392,8 @@ fn apply_skill_status_effects_to_entity(
         }
     }
 
-    if effect_success.iter().any(|x| *x) {
+    // A rejected status effect is still reported so the client can show the failure
+    if effect_rejected || effect_success.iter().any(|x| *x) {
         skill_system_parameters.server_messages.send_entity_message(
             skill_target.client_entity,
             ServerMessage::ApplySkillEffect {
@@ -367,6 +406,38 @@ fn apply_skill_status_effects_to_entity(
         );
     }
 
+    if !cleared_status_effects.is_empty() {
+        let mut updated_values = Vec::new();
+        if cleared_status_effects
+            .iter()
+            .any(|x| matches!(x, StatusEffectType::IncreaseHp))
+        {
+            updated_values.push(skill_target.health_points.hp);
+        }
+
+        if cleared_status_effects
+            .iter()
+            .any(|x| matches!(x, StatusEffectType::IncreaseMp))
+        {
+            updated_values.push(
+                skill_target
+                    .mana_points
+                    .as_ref()
+                    .map(|mp| mp.mp)
+                    .unwrap_or(0),
+            );
+        }
+
+        skill_system_parameters.server_messages.send_entity_message(
+            skill_target.client_entity,
+            ServerMessage::UpdateStatusEffects {
+                entity_id: skill_target.client_entity.id,
+                status_effects: skill_target.status_effects.active.clone(),
+                updated_values,
+            },
+        );
+    }
+
     Ok(())
 }
 
@@ -439,7 +510,12 @@ fn apply_skill_damage_to_entity(
     skill_target: &mut SkillTargetQueryItem,
     skill_data: &SkillData,
 ) -> Result<Damage, SkillCastError> {
-    if !check_skill_target_filter(skill_caster, skill_target, skill_data) {
+    if !check_skill_target_filter(
+        &skill_system_resources.game_data,
+        skill_caster,
+        skill_target,
+        skill_data,
+    ) {
         return Err(SkillCastError::InvalidTarget);
     }
 
@@ -540,6 +616,54 @@ fn apply_skill_damage(
     result
 }
 
+/// Adds skill experience for using a skill, returning the caster's mastery level for it
+fn update_skill_mastery(
+    skill_system_resources: &SkillSystemResources,
+    skill_caster: &mut SkillCasterQueryItem,
+    skill_data: &SkillData,
+) -> u32 {
+    let mastery_config = &skill_system_resources.game_config.skill_mastery;
+    if !mastery_config.enabled {
+        return 0;
+    }
+
+    let (Some(skill_list), Some(skill_mastery)) =
+        (skill_caster.skill_list, skill_caster.skill_mastery.as_mut())
+    else {
+        return 0;
+    };
+    let Some((skill_slot, _)) = skill_list.find_skill_exact(skill_data) else {
+        return 0;
+    };
+
+    // Levels of a skill share its mastery, which is lost if the slot is given a different skill
+    let base_skill_id = skill_data.base_skill_id.unwrap_or(skill_data.id);
+    let previous_level = mastery_config.get_level(skill_mastery.get_xp(skill_slot, base_skill_id));
+    let mastery_level = mastery_config.get_level(skill_mastery.add_xp(
+        skill_slot,
+        base_skill_id,
+        mastery_config.xp_per_use,
+    ));
+
+    if mastery_level > previous_level {
+        // The irose protocol has no field for skill mastery, so tell the player in chat
+        if let Some(game_client) = skill_caster.game_client {
+            game_client
+                .server_message_tx
+                .send(ServerMessage::Whisper {
+                    from: String::from("SERVER"),
+                    text: format!(
+                        "{} reached mastery level {}",
+                        skill_data.name, mastery_level
+                    ),
+                })
+                .ok();
+        }
+    }
+
+    mastery_level
+}
+
 fn subtract_skill_use_cost(
     skill_system_resources: &SkillSystemResources,
     skill_caster_query: &mut Query<SkillCasterQuery>,
@@ -565,17 +689,25 @@ fn subtract_skill_use_cost(
         return;
     };
 
+    let mastery_level =
+        update_skill_mastery(skill_system_resources, &mut skill_caster1, skill_data);
+    let mastery_config = &skill_system_resources.game_config.skill_mastery;
+    let cooldown_rate = 100 - mastery_config.cooldown_reduction(mastery_level);
+
     if let Some(mut cooldowns) = skill_caster1.cooldowns {
         let now = skill_system_resources.time.last_update().unwrap();
         cooldowns.skill_global = Some(now + GLOBAL_SKILL_COOLDOWN);
+        cooldowns.combo = Some((skill_data.base_skill_id.unwrap_or(skill_data.id), now));
 
         match skill_data.cooldown {
             SkillCooldown::Skill { duration } => {
-                cooldowns.skill.insert(skill_data.id, now + duration);
+                cooldowns
+                    .skill
+                    .insert(skill_data.id, now + duration * cooldown_rate / 100);
             }
             SkillCooldown::Group { group, duration } => {
                 if let Some(group_cooldown) = cooldowns.skill_group.get_mut(group.get()) {
-                    *group_cooldown = Some(now + duration);
+                    *group_cooldown = Some(now + duration * cooldown_rate / 100);
                 }
             }
         }
@@ -585,6 +717,9 @@ fn subtract_skill_use_cost(
         if use_ability_type == AbilityType::Mana {
             let use_mana_rate = (100 - skill_caster2.ability_values.get_save_mana()) as f32 / 100.0;
             use_ability_value = (use_ability_value as f32 * use_mana_rate) as i32;
+
+            let mastery_rate = 100 - mastery_config.mana_cost_reduction(mastery_level) as i32;
+            use_ability_value = use_ability_value * mastery_rate / 100;
         }
 
         match use_ability_type {
@@ -781,33 +916,19 @@ pub fn skill_effect_system(
                 }
                 SkillType::SummonPet => {
                     if let Some(npc_id) = skill_data.summon_npc_id {
-                        if let Some(entity) = MonsterBundle::spawn(
+                        if MonsterBundle::spawn_summon(
                             &mut commands,
                             &mut client_entity_list,
                             &skill_system_resources.game_data,
                             npc_id,
-                            skill_caster.position.zone_id,
-                            SpawnOrigin::Summoned(
-                                skill_caster.entity,
-                                skill_caster.position.position,
-                            ),
-                            150,
-                            skill_caster.team.clone(),
-                            Some((skill_caster.entity, skill_caster.level)),
-                            Some(skill_data.level as i32),
-                        ) {
-                            // Apply status effect to decrease summon's life over time
-                            if let Some(status_effect_data) = skill_system_resources
-                                .game_data
-                                .status_effects
-                                .get_decrease_summon_life_status_effect()
-                            {
-                                let mut status_effects = StatusEffects::new();
-                                status_effects
-                                    .apply_summon_decrease_life_status_effect(status_effect_data);
-                                commands.entity(entity).insert(status_effects);
-                            }
-
+                            skill_caster.entity,
+                            skill_caster.position,
+                            skill_caster.team,
+                            skill_caster.level,
+                            skill_data.level as i32,
+                        )
+                        .is_some()
+                        {
                             let summon_point_requirement = skill_system_resources
                                 .game_data
                                 .npcs
@@ -830,6 +951,8 @@ pub fn skill_effect_system(
                 | SkillType::Passive
                 | SkillType::Emote
                 | SkillType::Warp => Ok(()),
+                // Toggle skills are switched on and off by ToggleSkillEvent rather than cast
+                SkillType::Toggle => Err(SkillCastError::InvalidSkill),
                 SkillType::Resurrection => {
                     warn!("Unimplemented skill type used {:?}", skill_data);
                     Ok(())
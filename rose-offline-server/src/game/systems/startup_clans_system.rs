@@ -19,6 +19,7 @@ pub fn startup_clans_system(mut commands: Commands) {
                     name: member.name,
                     position: member.position,
                     contribution: member.contribution,
+                    stats: member.stats,
                     level: Level::new(character.level.level),
                     job: character.info.job,
                 });
@@ -30,12 +31,18 @@ pub fn startup_clans_system(mut commands: Commands) {
                 .unwrap(),
             name: clan_storage.name,
             description: clan_storage.description,
+            notice: clan_storage.notice,
             mark: clan_storage.mark,
             money: clan_storage.money,
             points: clan_storage.points,
             level: clan_storage.level,
             skills: clan_storage.skills,
+            vault: clan_storage.vault,
+            permissions: clan_storage.permissions,
+            recruitment: clan_storage.recruitment,
+            applications: clan_storage.applications,
             members,
+            pending_invites: Vec::new(),
         });
     }
 }
@@ -0,0 +1,5 @@
+use crate::game::storage::journal::JournalStorage;
+
+pub fn startup_journal_system() {
+    JournalStorage::replay_pending();
+}
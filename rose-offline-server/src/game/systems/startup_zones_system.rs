@@ -1,40 +1,112 @@
-use bevy::ecs::prelude::{Commands, Res, ResMut};
+use std::time::Duration;
+
+use bevy::{
+    ecs::prelude::{Commands, Res, ResMut},
+    time::Time,
+};
 use log::warn;
 
+use rose_data::QuestTriggerHash;
+
 use crate::game::{
     bundles::{
         client_entity_join_zone, NpcBundle, EVENT_OBJECT_VARIABLES_COUNT,
         NPC_OBJECT_VARIABLES_COUNT,
     },
     components::{
-        ClientEntityType, Command, EventObject, HealthPoints, Level, MonsterSpawnPoint, MotionData,
-        MoveMode, MoveSpeed, NextCommand, Npc, NpcAi, NpcStandingDirection, ObjectVariables,
-        Position, StatusEffects, StatusEffectsRegen, Team,
+        ClientEntityType, Command, EventObject, GatherNode, HealthPoints, Level, MonsterSpawnPoint,
+        MotionData, MoveMode, MoveSpeed, NextCommand, Npc, NpcAi, NpcStandingDirection,
+        ObjectVariables, Position, StatusEffects, StatusEffectsRegen, Team,
     },
-    resources::{ClientEntityList, GameData, ZoneList},
+    resources::{ClientEntityList, DelayedQuestTrigger, DelayedQuestTriggers, GameData, ZoneList},
+    storage::zone::ZoneStorage,
     GameConfig,
 };
 
 pub fn startup_zones_system(
     mut commands: Commands,
     mut client_entity_list: ResMut<ClientEntityList>,
+    mut delayed_quest_triggers: ResMut<DelayedQuestTriggers>,
     game_config: Res<GameConfig>,
     game_data: Res<GameData>,
+    time: Res<Time>,
     mut zone_list: ResMut<ZoneList>,
 ) {
     for zone_data in game_data.zones.iter() {
         // Add to zone list
         zone_list.add_zone(zone_data.id);
 
+        // Restore any persisted event object state
+        let zone_storage = if ZoneStorage::exists(zone_data.id) {
+            ZoneStorage::try_load(zone_data.id)
+                .map_err(|error| {
+                    warn!(
+                        "Failed to load zone storage for zone {}: {:?}",
+                        zone_data.id.get(),
+                        error
+                    )
+                })
+                .unwrap_or_default()
+        } else {
+            ZoneStorage::default()
+        };
+
         // Create the Event Object entities
         for event_object in zone_data.event_objects.iter() {
-            let entity = commands
-                .spawn((
-                    EventObject::new(event_object.event_id),
-                    Position::new(event_object.position, zone_data.id),
-                    ObjectVariables::new(EVENT_OBJECT_VARIABLES_COUNT),
-                ))
-                .id();
+            let mut object_variables = ObjectVariables::new(EVENT_OBJECT_VARIABLES_COUNT);
+            if let Some(stored) = zone_storage.event_objects.iter().find(|stored| {
+                stored.event_id == event_object.event_id
+                    && stored.map_chunk_x == event_object.map_chunk_x
+                    && stored.map_chunk_y == event_object.map_chunk_y
+            }) {
+                for (variable, value) in object_variables
+                    .variables
+                    .iter_mut()
+                    .zip(stored.variables.iter())
+                {
+                    *variable = *value;
+                }
+            }
+
+            let mut entity_commands = commands.spawn((
+                EventObject::new(
+                    event_object.event_id,
+                    event_object.map_chunk_x,
+                    event_object.map_chunk_y,
+                ),
+                Position::new(event_object.position, zone_data.id),
+                object_variables,
+            ));
+
+            if let Some((node_index, gather_node_data)) = game_data
+                .gather_nodes
+                .find_node(zone_data.id, event_object.event_id)
+                .and_then(|node_index| {
+                    game_data
+                        .gather_nodes
+                        .get_node(node_index)
+                        .map(|gather_node_data| (node_index, gather_node_data))
+                })
+            {
+                entity_commands.insert(GatherNode::new(node_index, gather_node_data.max_gathers));
+            }
+
+            let entity = entity_commands.id();
+
+            // Restore delayed quest triggers, such as a gate which closes after some time
+            for stored in zone_storage.delayed_triggers.iter().filter(|stored| {
+                stored.event_id == event_object.event_id
+                    && stored.map_chunk_x == event_object.map_chunk_x
+                    && stored.map_chunk_y == event_object.map_chunk_y
+            }) {
+                delayed_quest_triggers.pending.push(DelayedQuestTrigger {
+                    source_entity: None,
+                    trigger_hash: QuestTriggerHash::new(stored.trigger_hash),
+                    selected_event_object: Some(entity),
+                    selected_npc: None,
+                    run_at: time.elapsed() + Duration::from_millis(stored.remaining_ms),
+                });
+            }
 
             zone_list.add_event_object(
                 zone_data.id,
@@ -46,35 +118,33 @@ pub fn startup_zones_system(
         }
 
         // Create all Monster Spawn Points
-        if game_config.enable_monster_spawns {
-            for spawn in zone_data.monster_spawns.iter() {
-                // Verify basic_spawns
-                for (npc, _) in &spawn.basic_spawns {
-                    if game_data.npcs.get_npc(*npc).is_none() {
-                        warn!(
-                            "Invalid monster spawn {} in zone {}",
-                            npc.get(),
-                            zone_data.id.get()
-                        );
-                    }
+        for spawn in zone_data.monster_spawns.iter() {
+            // Verify basic_spawns
+            for (npc, _) in &spawn.basic_spawns {
+                if game_data.npcs.get_npc(*npc).is_none() {
+                    warn!(
+                        "Invalid monster spawn {} in zone {}",
+                        npc.get(),
+                        zone_data.id.get()
+                    );
                 }
+            }
 
-                // Verify tactic_spawns
-                for (npc, _) in &spawn.tactic_spawns {
-                    if game_data.npcs.get_npc(*npc).is_none() {
-                        warn!(
-                            "Invalid monster spawn {} in zone {}",
-                            npc.get(),
-                            zone_data.id.get()
-                        );
-                    }
+            // Verify tactic_spawns
+            for (npc, _) in &spawn.tactic_spawns {
+                if game_data.npcs.get_npc(*npc).is_none() {
+                    warn!(
+                        "Invalid monster spawn {} in zone {}",
+                        npc.get(),
+                        zone_data.id.get()
+                    );
                 }
-
-                commands.spawn((
-                    MonsterSpawnPoint::from(spawn),
-                    Position::new(spawn.position, zone_data.id),
-                ));
             }
+
+            commands.spawn((
+                MonsterSpawnPoint::from(spawn),
+                Position::new(spawn.position, zone_data.id),
+            ));
         }
 
         // Spawn all NPCs
@@ -107,9 +177,10 @@ pub fn startup_zones_system(
                     .map(|x| x.index)
                     .unwrap_or(0);
 
-                let npc_ai = Some(npc_data.ai_file_index)
-                    .filter(|ai_file_index| *ai_file_index != 0)
-                    .map(|ai_file_index| NpcAi::new(ai_file_index as usize));
+                let npc_ai = game_data
+                    .ai
+                    .get_npc_ai_index(npc.npc_id, npc_data.ai_file_index as usize)
+                    .map(NpcAi::new);
 
                 let position = Position::new(npc.position, zone_data.id);
                 let move_speed = MoveSpeed::new(ability_values.get_walk_speed());
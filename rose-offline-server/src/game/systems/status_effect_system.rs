@@ -19,14 +19,14 @@ use crate::game::{
     },
     events::DamageEvent,
     messages::server::ServerMessage,
-    resources::ServerMessages,
+    resources::{ServerMessages, ZoneActivity},
     GameData,
 };
 
-fn update_status_effect_regen(regen: &mut ActiveStatusEffectRegen, time: &Time) -> i32 {
+fn update_status_effect_regen(regen: &mut ActiveStatusEffectRegen, delta: Duration) -> i32 {
     let prev_applied_value = regen.applied_value;
 
-    regen.applied_duration += time.delta();
+    regen.applied_duration += delta;
     regen.applied_value = i32::min(
         ((regen.applied_duration.as_micros() as f32 / 1000000.0) * regen.value_per_second as f32)
             as i32,
@@ -50,6 +50,7 @@ pub fn status_effect_system(
     mut server_messages: ResMut<ServerMessages>,
     game_data: Res<GameData>,
     time: Res<Time>,
+    zone_activity: Res<ZoneActivity>,
 ) {
     for (
         entity,
@@ -61,9 +62,13 @@ pub fn status_effect_system(
         mut status_effects_regen,
     ) in query.iter_mut()
     {
+        let Some(delta) = zone_activity.get_delta(client_entity.zone_id) else {
+            continue;
+        };
+
         let mut expired_status_effects: EnumMap<StatusEffectType, bool> = Default::default();
         let apply_per_second_effect = {
-            status_effects_regen.per_second_tick_counter += time.delta();
+            status_effects_regen.per_second_tick_counter += delta;
             if status_effects_regen.per_second_tick_counter > Duration::from_secs(1) {
                 status_effects_regen.per_second_tick_counter -= Duration::from_secs(1);
                 true
@@ -80,7 +85,7 @@ pub fn status_effect_system(
                             &mut status_effects_regen.regens[status_effect_type]
                         {
                             // Calculate regen for this tick
-                            let regen = update_status_effect_regen(status_effect_regen, &time);
+                            let regen = update_status_effect_regen(status_effect_regen, delta);
 
                             // Update hp
                             let max_hp = ability_values.get_max_health();
@@ -98,7 +103,7 @@ pub fn status_effect_system(
                         {
                             if let Some(mana_points) = mana_points.as_mut() {
                                 // Calculate regen for this tick
-                                let regen = update_status_effect_regen(status_effect_regen, &time);
+                                let regen = update_status_effect_regen(status_effect_regen, delta);
 
                                 // Update mp
                                 let max_mp = ability_values.get_max_mana();
@@ -130,8 +135,7 @@ pub fn status_effect_system(
                                     health_points.hp -= data.apply_per_second_value;
                                 } else {
                                     // Apply as damage so the entity dies
-                                    damage_events.send(DamageEvent::Attack {
-                                        attacker: entity,
+                                    damage_events.send(DamageEvent::Environment {
                                         defender: entity,
                                         damage: Damage {
                                             amount: data.apply_per_second_value as u32,
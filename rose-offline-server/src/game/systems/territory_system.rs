@@ -0,0 +1,96 @@
+use bevy::ecs::prelude::{Added, Query, Res, ResMut};
+
+use crate::game::{
+    components::{Clan, ClanMembership, Command, CommandData, Dead, Position},
+    messages::server::ServerMessage,
+    resources::{GameData, ServerMessages, TerritoryList},
+};
+
+pub fn territory_system(
+    clan_query: Query<&Clan>,
+    clan_membership_query: Query<&ClanMembership>,
+    killed_query: Query<(&Position, &Command, &ClanMembership), Added<Dead>>,
+    game_data: Res<GameData>,
+    mut territory_list: ResMut<TerritoryList>,
+    mut server_messages: ResMut<ServerMessages>,
+) {
+    // Start and end siege windows
+    for territory in territory_list.iter_mut() {
+        let siege_active = territory
+            .siege
+            .as_ref()
+            .map_or(false, |siege| siege.is_active_now());
+        if siege_active == territory.siege_active {
+            continue;
+        }
+        territory.siege_active = siege_active;
+
+        let zone_name = game_data
+            .zones
+            .get_zone(territory.zone_id)
+            .map_or("", |zone_data| zone_data.name);
+
+        if siege_active {
+            territory.siege_points.clear();
+            server_messages.send_global_message(ServerMessage::AnnounceChat {
+                name: None,
+                text: format!("The siege of {} has begun!", zone_name),
+            });
+            continue;
+        }
+
+        // The clan with the most kills during the siege captures the territory
+        let winner = territory
+            .siege_points
+            .drain()
+            .max_by_key(|(_, points)| *points)
+            .and_then(|(clan_entity, _)| clan_query.get(clan_entity).ok());
+
+        if let Some(clan) = winner {
+            territory.owner_clan = Some(clan.name.clone());
+            territory.save();
+        }
+
+        server_messages.send_global_message(ServerMessage::AnnounceChat {
+            name: None,
+            text: match territory.owner_clan.as_ref() {
+                Some(owner_clan) => format!(
+                    "The siege of {} has ended, it is held by clan {}.",
+                    zone_name, owner_clan
+                ),
+                None => format!("The siege of {} has ended.", zone_name),
+            },
+        });
+    }
+
+    // Award siege points for killing characters of other clans
+    for (position, command, clan_membership) in killed_query.iter() {
+        let Some(territory) = territory_list.get_mut(position.zone_id) else {
+            continue;
+        };
+
+        if !territory.siege_active {
+            continue;
+        }
+
+        let CommandData::Die {
+            killer: Some(killer_entity),
+            ..
+        } = command.command
+        else {
+            continue;
+        };
+
+        let Some(killer_clan) = clan_membership_query
+            .get(killer_entity)
+            .ok()
+            .and_then(|killer_clan_membership| killer_clan_membership.clan())
+        else {
+            continue;
+        };
+
+        if clan_membership.clan() != Some(killer_clan) {
+            *territory.siege_points.entry(killer_clan).or_default() += 1;
+        }
+    }
+}
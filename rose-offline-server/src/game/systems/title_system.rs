@@ -0,0 +1,172 @@
+use bevy::prelude::{DetectChanges, EventReader, Query, Res, ResMut};
+use log::warn;
+
+use rose_data::QuestTriggerHash;
+
+use crate::game::{
+    components::{CharacterStatistics, CharacterTitles, ClientEntity, GameClient},
+    events::TitleEvent,
+    messages::server::{CharacterTitle, ServerMessage},
+    resources::{GameConfig, ServerMessages, TitleRequirement},
+};
+
+fn is_title_requirement_met(
+    requirement: &TitleRequirement,
+    statistics: &CharacterStatistics,
+) -> bool {
+    match *requirement {
+        TitleRequirement::MonsterKills { count, npc: None } => statistics.monster_kills >= count,
+        TitleRequirement::MonsterKills {
+            count,
+            npc: Some(npc_id),
+        } => statistics.get_npc_kills(npc_id) >= count,
+        TitleRequirement::QuestsCompleted { count } => statistics.quests_completed >= count,
+        TitleRequirement::Deaths { count } => statistics.deaths >= count,
+        TitleRequirement::MoneyEarned { money } => statistics.money_earned.0 >= money,
+        TitleRequirement::DistanceWalked { distance } => statistics.distance_walked >= distance,
+        // Only earned when the trigger succeeds
+        TitleRequirement::QuestTrigger { .. } => false,
+    }
+}
+
+fn award_title(game_client: &GameClient, titles: &mut CharacterTitles, id: u16, name: &str) {
+    titles.earned.push(id);
+    game_client
+        .server_message_tx
+        .send(ServerMessage::Whisper {
+            from: String::from("SERVER"),
+            text: format!("You have earned the title {}", name),
+        })
+        .ok();
+}
+
+fn send_character_titles(
+    game_config: &GameConfig,
+    game_client: &GameClient,
+    titles: &CharacterTitles,
+) {
+    game_client
+        .server_message_tx
+        .send(ServerMessage::CharacterTitles {
+            titles: titles
+                .earned
+                .iter()
+                .filter_map(|id| game_config.get_title(*id))
+                .map(|title| CharacterTitle {
+                    id: title.id,
+                    name: title.name.clone(),
+                })
+                .collect(),
+            active: titles.active,
+        })
+        .ok();
+}
+
+/// Awards every title not yet earned whose requirement is met, and sends the updated list of
+/// titles if any were earned.
+fn award_earned_titles(
+    game_config: &GameConfig,
+    game_client: &GameClient,
+    titles: &mut CharacterTitles,
+    is_requirement_met: impl Fn(&TitleRequirement) -> bool,
+) {
+    let num_earned = titles.earned.len();
+    for title in game_config.titles.iter() {
+        if !titles.has_title(title.id) && is_requirement_met(&title.requirement) {
+            award_title(game_client, titles, title.id, &title.name);
+        }
+    }
+
+    if titles.earned.len() != num_earned {
+        send_character_titles(game_config, game_client, titles);
+    }
+}
+
+pub fn title_system(
+    mut query: Query<(
+        &ClientEntity,
+        &GameClient,
+        &mut CharacterTitles,
+        Option<&CharacterStatistics>,
+    )>,
+    mut title_events: EventReader<TitleEvent>,
+    mut server_messages: ResMut<ServerMessages>,
+    game_config: Res<GameConfig>,
+) {
+    for (_, game_client, titles, _) in query.iter_mut() {
+        if titles.is_added() {
+            send_character_titles(&game_config, game_client, &titles);
+        }
+    }
+
+    for event in title_events.iter() {
+        match *event {
+            TitleEvent::StatisticsChanged { entity } => {
+                let Ok((_, game_client, mut titles, Some(statistics))) = query.get_mut(entity)
+                else {
+                    continue;
+                };
+
+                award_earned_titles(&game_config, game_client, &mut titles, |requirement| {
+                    is_title_requirement_met(requirement, statistics)
+                });
+            }
+            TitleEvent::QuestTriggerSuccess {
+                entity,
+                trigger_hash,
+            } => {
+                let Ok((_, game_client, mut titles, statistics)) = query.get_mut(entity) else {
+                    continue;
+                };
+
+                // Quest rewards can also change the statistics used by other titles
+                award_earned_titles(&game_config, game_client, &mut titles, |requirement| {
+                    match requirement {
+                        TitleRequirement::QuestTrigger { trigger } => {
+                            QuestTriggerHash::from(trigger.as_str()) == trigger_hash
+                        }
+                        requirement => statistics.map_or(false, |statistics| {
+                            is_title_requirement_met(requirement, statistics)
+                        }),
+                    }
+                });
+            }
+            TitleEvent::SetActive { entity, title_id } => {
+                let Ok((client_entity, game_client, mut titles, _)) = query.get_mut(entity) else {
+                    continue;
+                };
+
+                let title = match title_id {
+                    Some(title_id) => {
+                        match game_config
+                            .get_title(title_id)
+                            .filter(|_| titles.has_title(title_id))
+                        {
+                            Some(title) => Some(title),
+                            None => {
+                                warn!(
+                                    "Character {:?} tried to set unearned title {}",
+                                    entity, title_id
+                                );
+                                continue;
+                            }
+                        }
+                    }
+                    None => None,
+                };
+
+                if titles.active != title_id {
+                    titles.active = title_id;
+                    server_messages.send_entity_message(
+                        client_entity,
+                        ServerMessage::UpdateCharacterTitle {
+                            entity_id: client_entity.id,
+                            title: title.map(|title| title.name.clone()),
+                        },
+                    );
+                }
+                send_character_titles(&game_config, game_client, &titles);
+            }
+        }
+    }
+}
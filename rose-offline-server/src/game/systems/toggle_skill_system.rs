@@ -0,0 +1,345 @@
+use std::time::{Duration, Instant};
+
+use bevy::{
+    ecs::query::WorldQuery,
+    math::Vec3Swizzles,
+    prelude::{Commands, Entity, EventReader, Mut, Query, Res, ResMut},
+    time::Time,
+};
+
+use rose_data::{SkillData, SkillTargetFilter, SkillType};
+
+use crate::game::{
+    components::{
+        ActiveToggleSkill, ClientEntity, Dead, HealthPoints, ManaPoints, Party, PartyMembership,
+        Position, StatusEffects,
+    },
+    events::ToggleSkillEvent,
+    messages::server::ServerMessage,
+    resources::ServerMessages,
+    GameData,
+};
+
+// The status effects of a toggle skill expire shortly after they stop being refreshed, so they
+// do not outlive a caster which despawns without switching the skill off
+const TOGGLE_STATUS_EFFECT_LEASE: Duration = Duration::from_secs(5);
+
+#[derive(WorldQuery)]
+#[world_query(mutable)]
+pub struct ToggleSkillCasterQuery<'w> {
+    entity: Entity,
+    client_entity: &'w ClientEntity,
+    health_points: &'w HealthPoints,
+    position: &'w Position,
+    dead: Option<&'w Dead>,
+    party_membership: Option<&'w PartyMembership>,
+    mana_points: Option<&'w mut ManaPoints>,
+    active_toggle_skill: Option<&'w mut ActiveToggleSkill>,
+}
+
+fn apply_toggle_status_effects(
+    game_data: &GameData,
+    skill_data: &SkillData,
+    expire_time: Instant,
+    status_effects: &mut StatusEffects,
+) -> bool {
+    let mut applied = false;
+
+    for status_effect_data in skill_data
+        .status_effects
+        .iter()
+        .filter_map(|id| id.and_then(|id| game_data.status_effects.get_status_effect(id)))
+    {
+        let value = skill_data.power as i32;
+        if status_effects.can_apply(status_effect_data, value) {
+            applied |= status_effects.apply_status_effect(status_effect_data, expire_time, value);
+        }
+    }
+
+    applied
+}
+
+fn disable_toggle_skill(
+    commands: &mut Commands,
+    server_messages: &mut ServerMessages,
+    game_data: &GameData,
+    caster_entity: Entity,
+    caster_client_entity: &ClientEntity,
+    active_toggle_skill: &ActiveToggleSkill,
+    query_status_effects: &mut Query<(&ClientEntity, &Position, &mut StatusEffects)>,
+) {
+    if let Some(skill_data) = game_data.skills.get_skill(active_toggle_skill.skill_id) {
+        for &entity in active_toggle_skill.affected_entities.iter() {
+            let Ok((client_entity, _, mut status_effects)) = query_status_effects.get_mut(entity)
+            else {
+                continue;
+            };
+
+            // Only remove the status effects which were applied by this toggle skill
+            let mut removed = false;
+            for status_effect_data in skill_data
+                .status_effects
+                .iter()
+                .filter_map(|id| id.and_then(|id| game_data.status_effects.get_status_effect(id)))
+            {
+                let status_effect_type = status_effect_data.status_effect_type;
+                if status_effects.active[status_effect_type]
+                    .as_ref()
+                    .map_or(false, |active| active.id == status_effect_data.id)
+                {
+                    status_effects.active[status_effect_type] = None;
+                    status_effects.expire_times[status_effect_type] = None;
+                    removed = true;
+                }
+            }
+
+            if removed {
+                server_messages.send_entity_message(
+                    client_entity,
+                    ServerMessage::UpdateStatusEffects {
+                        entity_id: client_entity.id,
+                        status_effects: status_effects.active.clone(),
+                        updated_values: Vec::new(),
+                    },
+                );
+            }
+        }
+    }
+
+    server_messages.send_entity_message(
+        caster_client_entity,
+        ServerMessage::ToggleSkill {
+            entity_id: caster_client_entity.id,
+            skill_id: active_toggle_skill.skill_id,
+            enabled: false,
+        },
+    );
+    commands.entity(caster_entity).remove::<ActiveToggleSkill>();
+}
+
+fn enable_toggle_skill(
+    commands: &mut Commands,
+    server_messages: &mut ServerMessages,
+    game_data: &GameData,
+    now: Instant,
+    caster: &mut ToggleSkillCasterQueryItem,
+    skill_data: &SkillData,
+    query_party: &Query<&Party>,
+    query_status_effects: &mut Query<(&ClientEntity, &Position, &mut StatusEffects)>,
+) {
+    // The first second of mana drain is paid on activation
+    let Some(mana_points) = caster.mana_points.as_mut() else {
+        return;
+    };
+    if mana_points.mp < skill_data.toggle_mana_drain as i32 {
+        return;
+    }
+    mana_points.mp -= skill_data.toggle_mana_drain as i32;
+
+    let mut target_entities = vec![caster.entity];
+    if matches!(skill_data.target_filter, SkillTargetFilter::Group) {
+        if let Some(party) = caster
+            .party_membership
+            .and_then(|party_membership| party_membership.party)
+            .and_then(|party_entity| query_party.get(party_entity).ok())
+        {
+            target_entities.extend(
+                party
+                    .members
+                    .iter()
+                    .filter_map(|member| member.get_entity())
+                    .filter(|entity| *entity != caster.entity),
+            );
+        }
+    }
+
+    let expire_time = now + TOGGLE_STATUS_EFFECT_LEASE;
+    let mut affected_entities = Vec::new();
+    for entity in target_entities {
+        let Ok((client_entity, position, mut status_effects)) =
+            query_status_effects.get_mut(entity)
+        else {
+            continue;
+        };
+
+        if entity != caster.entity
+            && (position.zone_id != caster.position.zone_id
+                || position
+                    .position
+                    .xy()
+                    .distance(caster.position.position.xy())
+                    > skill_data.scope as f32)
+        {
+            continue;
+        }
+
+        if apply_toggle_status_effects(game_data, skill_data, expire_time, &mut status_effects) {
+            server_messages.send_entity_message(
+                client_entity,
+                ServerMessage::UpdateStatusEffects {
+                    entity_id: client_entity.id,
+                    status_effects: status_effects.active.clone(),
+                    updated_values: Vec::new(),
+                },
+            );
+        }
+        affected_entities.push(entity);
+    }
+
+    server_messages.send_entity_message(
+        caster.client_entity,
+        ServerMessage::ToggleSkill {
+            entity_id: caster.client_entity.id,
+            skill_id: skill_data.id,
+            enabled: true,
+        },
+    );
+    commands
+        .entity(caster.entity)
+        .insert(ActiveToggleSkill::new(skill_data.id, affected_entities));
+}
+
+pub fn toggle_skill_system(
+    mut commands: Commands,
+    mut query_caster: Query<ToggleSkillCasterQuery>,
+    query_party: Query<&Party>,
+    mut query_status_effects: Query<(&ClientEntity, &Position, &mut StatusEffects)>,
+    mut toggle_skill_events: EventReader<ToggleSkillEvent>,
+    mut server_messages: ResMut<ServerMessages>,
+    game_data: Res<GameData>,
+    time: Res<Time>,
+) {
+    let now = time.last_update().unwrap();
+    let mut toggled_entities = Vec::new();
+
+    for event in toggle_skill_events.iter() {
+        let (entity, enable_skill_id) = match *event {
+            ToggleSkillEvent::Enable { entity, skill_id } => (entity, Some(skill_id)),
+            ToggleSkillEvent::Disable { entity } => (entity, None),
+        };
+
+        // The component changes are deferred, so further events this tick are ignored
+        if toggled_entities.contains(&entity) {
+            continue;
+        }
+
+        let Ok(mut caster) = query_caster.get_mut(entity) else {
+            continue;
+        };
+        toggled_entities.push(entity);
+
+        if let Some(active_toggle_skill) = caster.active_toggle_skill.as_ref() {
+            if Some(active_toggle_skill.skill_id) == enable_skill_id {
+                continue;
+            }
+
+            // Only one toggle skill can be active at a time
+            disable_toggle_skill(
+                &mut commands,
+                &mut server_messages,
+                &game_data,
+                caster.entity,
+                caster.client_entity,
+                active_toggle_skill,
+                &mut query_status_effects,
+            );
+        }
+
+        let Some(skill_data) = enable_skill_id
+            .and_then(|skill_id| game_data.skills.get_skill(skill_id))
+            .filter(|skill_data| matches!(skill_data.skill_type, SkillType::Toggle))
+        else {
+            continue;
+        };
+
+        if caster.dead.is_some() || caster.health_points.hp <= 0 {
+            continue;
+        }
+
+        enable_toggle_skill(
+            &mut commands,
+            &mut server_messages,
+            &game_data,
+            now,
+            &mut caster,
+            skill_data,
+            &query_party,
+            &mut query_status_effects,
+        );
+    }
+
+    for mut caster in query_caster.iter_mut() {
+        if toggled_entities.contains(&caster.entity) {
+            continue;
+        }
+
+        let Some(mut active_toggle_skill) = caster.active_toggle_skill.take() else {
+            continue;
+        };
+
+        let Some(skill_data) = game_data.skills.get_skill(active_toggle_skill.skill_id) else {
+            continue;
+        };
+
+        let mut cancel = caster.dead.is_some() || caster.health_points.hp <= 0;
+
+        active_toggle_skill.mana_drain_timer += time.delta();
+        while !cancel && active_toggle_skill.mana_drain_timer >= Duration::from_secs(1) {
+            active_toggle_skill.mana_drain_timer -= Duration::from_secs(1);
+
+            match caster.mana_points.as_mut() {
+                Some(mana_points) if mana_points.mp >= skill_data.toggle_mana_drain as i32 => {
+                    mana_points.mp -= skill_data.toggle_mana_drain as i32;
+
+                    // Extend the status effect lease without triggering change detection
+                    for &entity in active_toggle_skill.affected_entities.iter() {
+                        if let Ok((_, _, status_effects)) = query_status_effects.get_mut(entity) {
+                            refresh_toggle_status_effects(
+                                &game_data,
+                                skill_data,
+                                now + TOGGLE_STATUS_EFFECT_LEASE,
+                                status_effects,
+                            );
+                        }
+                    }
+                }
+                _ => cancel = true,
+            }
+        }
+
+        if cancel {
+            disable_toggle_skill(
+                &mut commands,
+                &mut server_messages,
+                &game_data,
+                caster.entity,
+                caster.client_entity,
+                &active_toggle_skill,
+                &mut query_status_effects,
+            );
+        }
+    }
+}
+
+fn refresh_toggle_status_effects(
+    game_data: &GameData,
+    skill_data: &SkillData,
+    expire_time: Instant,
+    mut status_effects: Mut<StatusEffects>,
+) {
+    let status_effects = status_effects.bypass_change_detection();
+
+    for status_effect_data in skill_data
+        .status_effects
+        .iter()
+        .filter_map(|id| id.and_then(|id| game_data.status_effects.get_status_effect(id)))
+    {
+        let status_effect_type = status_effect_data.status_effect_type;
+        if status_effects.active[status_effect_type]
+            .as_ref()
+            .map_or(false, |active| active.id == status_effect_data.id)
+        {
+            status_effects.expire_times[status_effect_type] = Some(expire_time);
+        }
+    }
+}
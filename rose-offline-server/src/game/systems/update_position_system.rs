@@ -1,14 +1,21 @@
 use bevy::{
-    ecs::prelude::{Entity, Query, Res, ResMut},
+    ecs::prelude::{Entity, EventWriter, Query, Res, ResMut},
     math::Vec3Swizzles,
     time::Time,
 };
 
 use crate::game::{
-    components::{ClientEntity, ClientEntitySector, Command, CommandData, MoveSpeed, Position},
+    components::{
+        CharacterStatistics, ClientEntity, ClientEntitySector, Command, CommandData, MoveSpeed,
+        Position,
+    },
+    events::TitleEvent,
     resources::ClientEntityList,
 };
 
+// Titles for distance walked are checked each time a character walks this far, not every tick
+const TITLE_DISTANCE_CHECK_INTERVAL: f64 = 1000.0;
+
 pub fn update_position_system(
     mut query: Query<(
         Entity,
@@ -17,16 +24,27 @@ pub fn update_position_system(
         &MoveSpeed,
         &mut Position,
         &Command,
+        Option<&mut CharacterStatistics>,
     )>,
     mut client_entity_list: ResMut<ClientEntityList>,
+    mut title_events: EventWriter<TitleEvent>,
     time: Res<Time>,
 ) {
     query.for_each_mut(
-        |(entity, client_entity, client_entity_sector, move_speed, mut position, command)| {
+        |(
+            entity,
+            client_entity,
+            client_entity_sector,
+            move_speed,
+            mut position,
+            command,
+            statistics,
+        )| {
             let CommandData::Move { destination, .. } = command.command else {
                 return;
             };
 
+            let start_position = position.position;
             let direction = destination.xy() - position.position.xy();
             let distance_squared = direction.length_squared();
 
@@ -42,6 +60,19 @@ pub fn update_position_system(
                 }
             }
 
+            let distance_moved = start_position.xy().distance(position.position.xy());
+            if let Some(mut statistics) = statistics.filter(|_| distance_moved > 0.0) {
+                let previous_checks =
+                    (statistics.distance_walked / TITLE_DISTANCE_CHECK_INTERVAL).floor();
+                statistics.distance_walked += distance_moved as f64;
+
+                if (statistics.distance_walked / TITLE_DISTANCE_CHECK_INTERVAL).floor()
+                    != previous_checks
+                {
+                    title_events.send(TitleEvent::StatisticsChanged { entity });
+                }
+            }
+
             if let (Some(client_entity), Some(mut client_entity_sector)) =
                 (client_entity, client_entity_sector)
             {
@@ -1,43 +1,66 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use bevy::{
     ecs::{
-        prelude::{Commands, Entity, EventReader, Query, Res, ResMut},
+        prelude::{Commands, Entity, EventReader, Query, Res, ResMut, Without},
         query::WorldQuery,
         system::SystemParam,
     },
-    math::Vec3,
+    math::{Vec3, Vec3Swizzles},
     time::Time,
 };
 use log::warn;
 
-use rose_data::{AbilityType, ItemClass, ItemType, SkillType, VehiclePartIndex};
+use rose_data::{
+    AbilityType, CapturedNpc, Item, ItemClass, ItemType, SkillType, StackableItem,
+    StatusEffectType, VehiclePartIndex,
+};
 use rose_game_common::components::{Equipment, HealthPoints, ManaPoints};
 
 use crate::game::{
     bundles::{
-        ability_values_add_value, ability_values_get_value, client_entity_teleport_zone,
-        skill_list_try_learn_skill, SkillListBundle,
+        ability_values_add_value, ability_values_get_value, client_entity_leave_zone,
+        client_entity_teleport_zone, skill_list_try_learn_skill, MonsterBundle, SkillListBundle,
     },
     components::{
-        AbilityValues, BasicStats, CharacterInfo, ClientEntity, ClientEntitySector,
-        ExperiencePoints, GameClient, Inventory, ItemSlot, Level, MoveSpeed, NextCommand, Position,
-        SkillList, SkillPoints, Stamina, StatPoints, StatusEffects, StatusEffectsRegen, Team,
-        UnionMembership,
+        AbilityValues, BasicStats, CharacterInfo, ClientEntity, ClientEntitySector, Command,
+        Cooldowns, ExperiencePoints, GameClient, Inventory, ItemSlot, Level, MonsterSpawnPoint,
+        MoveSpeed, NextCommand, Npc, Owner, Position, SkillList, SkillPoints, SpawnOrigin, Stamina,
+        StatPoints, StatusEffects, StatusEffectsRegen, Team, UnionMembership,
     },
     events::UseItemEvent,
     messages::server::ServerMessage,
-    resources::{ClientEntityList, ServerMessages},
+    resources::{CaptureItemConfig, ClientEntityList, GameConfig, ServerMessages},
     GameData,
 };
 
+const CAPTURE_NPC_DISTANCE: f32 = 1000.0;
+
 #[derive(SystemParam)]
 pub struct UseItemSystemParameters<'w, 's> {
     commands: Commands<'w, 's>,
+    game_config: Res<'w, GameConfig>,
     game_data: Res<'w, GameData>,
     client_entity_list: ResMut<'w, ClientEntityList>,
     server_messages: ResMut<'w, ServerMessages>,
     time: Res<'w, Time>,
+    query_capture_target: Query<'w, 's, CaptureTargetQuery<'static>, Without<CharacterInfo>>,
+    query_spawn_point: Query<'w, 's, &'static mut MonsterSpawnPoint>,
+}
+
+#[derive(WorldQuery)]
+pub struct CaptureTargetQuery<'w> {
+    entity: Entity,
+    ability_values: &'w AbilityValues,
+    client_entity: &'w ClientEntity,
+    client_entity_sector: &'w ClientEntitySector,
+    command: &'w Command,
+    health_points: &'w HealthPoints,
+    level: &'w Level,
+    npc: &'w Npc,
+    position: &'w Position,
+    owner: Option<&'w Owner>,
+    spawn_origin: Option<&'w SpawnOrigin>,
 }
 
 #[derive(WorldQuery)]
@@ -49,6 +72,7 @@ pub struct UseItemUserQuery<'w> {
     character_info: &'w CharacterInfo,
     client_entity: &'w ClientEntity,
     client_entity_sector: &'w ClientEntitySector,
+    cooldowns: &'w mut Cooldowns,
     experience_points: &'w mut ExperiencePoints,
     equipment: &'w mut Equipment,
     game_client: Option<&'w GameClient>,
@@ -71,25 +95,63 @@ pub struct UseItemUserQuery<'w> {
 enum UseItemError {
     InvalidItem,
     AbilityRequirement,
+    Cooldown,
 }
 
 fn apply_item_effect(
-    use_item_system_parameters: &UseItemSystemParameters,
+    game_data: &GameData,
+    time: &Time,
+    server_messages: &mut ServerMessages,
     use_item_user: &mut UseItemUserQueryItem,
     item_data: &rose_data::ConsumableItemData,
 ) {
     if let Some((base_status_effect_id, total_potion_value)) = item_data.apply_status_effect {
-        if let Some(base_status_effect) = use_item_system_parameters
-            .game_data
+        if let Some(base_status_effect) = game_data
             .status_effects
             .get_status_effect(base_status_effect_id)
         {
+            // Cure items clear status effects rather than applying one
+            if base_status_effect.status_effect_type.is_clear() {
+                let cleared_status_effects = use_item_user.status_effects.clear_status_effects(
+                    &mut use_item_user.status_effects_regen,
+                    &game_data.status_effects,
+                    base_status_effect.status_effect_type,
+                    None,
+                );
+
+                if !cleared_status_effects.is_empty() {
+                    let mut updated_values = Vec::new();
+                    if cleared_status_effects
+                        .iter()
+                        .any(|x| matches!(x, StatusEffectType::IncreaseHp))
+                    {
+                        updated_values.push(use_item_user.health_points.hp);
+                    }
+
+                    if cleared_status_effects
+                        .iter()
+                        .any(|x| matches!(x, StatusEffectType::IncreaseMp))
+                    {
+                        updated_values.push(use_item_user.mana_points.mp);
+                    }
+
+                    server_messages.send_entity_message(
+                        use_item_user.client_entity,
+                        ServerMessage::UpdateStatusEffects {
+                            entity_id: use_item_user.client_entity.id,
+                            status_effects: use_item_user.status_effects.active.clone(),
+                            updated_values,
+                        },
+                    );
+                }
+                return;
+            }
+
             for (status_effect_data, &potion_value_per_second) in base_status_effect
                 .apply_status_effects
                 .iter()
                 .filter_map(|(id, value)| {
-                    use_item_system_parameters
-                        .game_data
+                    game_data
                         .status_effects
                         .get_status_effect(*id)
                         .map(|data| (data, value))
@@ -102,7 +164,7 @@ fn apply_item_effect(
                     use_item_user.status_effects.apply_potion(
                         &mut use_item_user.status_effects_regen,
                         status_effect_data,
-                        use_item_system_parameters.time.last_update().unwrap()
+                        time.last_update().unwrap()
                             + Duration::from_micros(
                                 total_potion_value as u64 * 1000000
                                     / potion_value_per_second as u64,
@@ -132,6 +194,179 @@ fn apply_item_effect(
     }
 }
 
+fn capture_npc(
+    use_item_system_parameters: &mut UseItemSystemParameters,
+    use_item_user: &mut UseItemUserQueryItem,
+    capture_item: &CaptureItemConfig,
+    target_entity: Option<Entity>,
+) -> bool {
+    let Some(target) = target_entity.and_then(|target_entity| {
+        use_item_system_parameters
+            .query_capture_target
+            .get(target_entity)
+            .ok()
+    }) else {
+        return false;
+    };
+
+    // Summoned monsters can not be captured
+    if target.owner.is_some()
+        || target.command.is_dead()
+        || !capture_item.npcs.contains(&target.npc.id)
+        || target.position.zone_id != use_item_user.position.zone_id
+        || target
+            .position
+            .position
+            .xy()
+            .distance(use_item_user.position.position.xy())
+            > CAPTURE_NPC_DISTANCE
+    {
+        return false;
+    }
+
+    if target.health_points.hp as i64 * 100
+        > target.ability_values.get_max_health() as i64 * capture_item.max_health_percent as i64
+    {
+        return false;
+    }
+
+    let Some(mut summon_item) = use_item_system_parameters
+        .game_data
+        .items
+        .get_consumable_item(capture_item.summon_item)
+        .and_then(|item_data| StackableItem::from_item_data(&item_data.item_data, 1))
+    else {
+        warn!(
+            "Invalid summon item {} for capture item {}",
+            capture_item.summon_item, capture_item.item
+        );
+        return false;
+    };
+    summon_item.captured_npc = Some(CapturedNpc {
+        npc_id: target.npc.id,
+        level: target.level.level,
+    });
+
+    let Ok((summon_item_slot, summon_item)) = use_item_user
+        .inventory
+        .try_add_item(Item::Stackable(summon_item))
+    else {
+        return false;
+    };
+
+    if let Some(game_client) = use_item_user.game_client {
+        game_client
+            .server_message_tx
+            .send(ServerMessage::UpdateInventory {
+                items: vec![(summon_item_slot, Some(summon_item.clone()))],
+                money: None,
+            })
+            .ok();
+    }
+
+    // The captured monster no longer counts towards its spawn point
+    if let Some(&SpawnOrigin::MonsterSpawnPoint(spawn_point_entity, _)) = target.spawn_origin {
+        if let Ok(mut spawn_point) = use_item_system_parameters
+            .query_spawn_point
+            .get_mut(spawn_point_entity)
+        {
+            spawn_point.num_alive_monsters = spawn_point.num_alive_monsters.saturating_sub(1);
+        }
+    }
+
+    client_entity_leave_zone(
+        &mut use_item_system_parameters.commands,
+        &mut use_item_system_parameters.client_entity_list,
+        target.entity,
+        target.client_entity,
+        target.client_entity_sector,
+        target.position,
+    );
+    use_item_system_parameters
+        .commands
+        .entity(target.entity)
+        .despawn();
+
+    true
+}
+
+fn spawn_captured_npc(
+    use_item_system_parameters: &mut UseItemSystemParameters,
+    use_item_user: &UseItemUserQueryItem,
+    captured_npc: &CapturedNpc,
+) -> bool {
+    let Some(npc_data) = use_item_system_parameters
+        .game_data
+        .npcs
+        .get_npc(captured_npc.npc_id)
+    else {
+        return false;
+    };
+
+    // Every living summon owned by the user counts towards their summon points
+    let owner_entity = use_item_user.entity;
+    let used_summon_points: u32 = use_item_system_parameters
+        .query_capture_target
+        .iter()
+        .filter(|summon| !summon.command.is_dead())
+        .filter(|summon| {
+            matches!(
+                summon.spawn_origin,
+                Some(&SpawnOrigin::Summoned(summon_owner, _)) if summon_owner == owner_entity
+            )
+        })
+        .filter_map(|summon| {
+            use_item_system_parameters
+                .game_data
+                .npcs
+                .get_npc(summon.npc.id)
+        })
+        .map(|summon_data| summon_data.summon_point_requirement)
+        .sum();
+    if used_summon_points + npc_data.summon_point_requirement
+        > use_item_user.ability_values.get_max_summon_points().max(0) as u32
+    {
+        return false;
+    }
+
+    // Like other summons the pet's stats scale with its owner, but it keeps the level it was
+    // captured at
+    let Some(summon_entity) = MonsterBundle::spawn_summon(
+        &mut use_item_system_parameters.commands,
+        &mut use_item_system_parameters.client_entity_list,
+        &use_item_system_parameters.game_data,
+        captured_npc.npc_id,
+        owner_entity,
+        use_item_user.position,
+        use_item_user.team,
+        use_item_user.level,
+        1,
+    ) else {
+        return false;
+    };
+    use_item_system_parameters
+        .commands
+        .entity(summon_entity)
+        .insert(Level::new(captured_npc.level));
+
+    true
+}
+
+fn check_item_cooldown(
+    cooldowns: &Cooldowns,
+    now: Instant,
+    item_data: &rose_data::ConsumableItemData,
+) -> bool {
+    if item_data.cooldown_duration.is_zero() {
+        return true;
+    }
+
+    cooldowns
+        .item_group
+        .get(&item_data.cooldown_type_id)
+        .map_or(true, |cooldown_finished| now >= *cooldown_finished)
+}
+
 fn use_inventory_item(
     use_item_system_parameters: &mut UseItemSystemParameters,
     use_item_user: &mut UseItemUserQueryItem,
@@ -154,7 +389,10 @@ fn use_inventory_item(
         .get_consumable_item(item.get_item_number())
         .ok_or(UseItemError::InvalidItem)?;
 
-    // TODO: Check use item cooldown
+    let now = use_item_system_parameters.time.last_update().unwrap();
+    if !check_item_cooldown(use_item_user.cooldowns, now, item_data) {
+        return Err(UseItemError::Cooldown);
+    }
 
     if let Some((require_ability_type, require_ability_value)) = item_data.ability_requirement {
         let ability_value = ability_values_get_value(
@@ -190,144 +428,184 @@ fn use_inventory_item(
         .try_take_quantity(item_slot, 1)
         .ok_or(UseItemError::InvalidItem)?;
 
-    let (consume_item, message_to_nearby) = match item_data.item_data.class {
-        ItemClass::MagicItem => {
-            if let Some((skill_id, skill_data)) = item_data.use_skill_id.and_then(|skill_id| {
-                use_item_system_parameters
-                    .game_data
-                    .skills
-                    .get_skill(skill_id)
-                    .map(|skill_data| (skill_id, skill_data))
-            }) {
-                if skill_data.skill_type.is_self_skill() {
-                    use_item_system_parameters
-                        .commands
-                        .entity(use_item_user.entity)
-                        .insert(NextCommand::with_cast_skill_target_self(
-                            skill_id,
-                            Some((item_slot, item.clone())),
-                        ));
-                    (false, false)
-                } else if skill_data.skill_type.is_target_skill() && target_entity.is_some() {
+    let capture_item = use_item_system_parameters
+        .game_config
+        .taming
+        .get_capture_item(item.get_item_number())
+        .cloned();
+    let captured_npc = match &item {
+        Item::Stackable(stackable_item) => stackable_item.captured_npc.clone(),
+        Item::Equipment(_) => None,
+    };
+
+    let (consume_item, message_to_nearby) = if let Some(capture_item) = capture_item.as_ref() {
+        (
+            capture_npc(
+                use_item_system_parameters,
+                use_item_user,
+                capture_item,
+                target_entity,
+            ),
+            true,
+        )
+    } else if let Some(captured_npc) = captured_npc.as_ref() {
+        (
+            spawn_captured_npc(use_item_system_parameters, use_item_user, captured_npc),
+            true,
+        )
+    } else {
+        match item_data.item_data.class {
+            ItemClass::MagicItem => {
+                if let Some((skill_id, skill_data)) = item_data.use_skill_id.and_then(|skill_id| {
                     use_item_system_parameters
-                        .commands
-                        .entity(use_item_user.entity)
-                        .insert(NextCommand::with_cast_skill_target_entity(
-                            skill_id,
-                            target_entity.unwrap(),
-                            Some((item_slot, item.clone())),
-                        ));
-                    (false, false)
-                } else if matches!(skill_data.skill_type, SkillType::Warp) {
-                    if let Some(zone_id) = skill_data.warp_zone_id {
-                        // TODO: Check skill_data.required_planet
+                        .game_data
+                        .skills
+                        .get_skill(skill_id)
+                        .map(|skill_data| (skill_id, skill_data))
+                }) {
+                    if skill_data.skill_type.is_self_skill() {
+                        use_item_system_parameters
+                            .commands
+                            .entity(use_item_user.entity)
+                            .insert(NextCommand::with_cast_skill_target_self(
+                                skill_id,
+                                Some((item_slot, item.clone())),
+                            ));
+                        (false, false)
+                    } else if skill_data.skill_type.is_target_skill() && target_entity.is_some() {
+                        use_item_system_parameters
+                            .commands
+                            .entity(use_item_user.entity)
+                            .insert(NextCommand::with_cast_skill_target_entity(
+                                skill_id,
+                                target_entity.unwrap(),
+                                Some((item_slot, item.clone())),
+                            ));
+                        (false, false)
+                    } else if matches!(skill_data.skill_type, SkillType::Warp) {
+                        if let Some(zone_id) = skill_data.warp_zone_id {
+                            // TODO: Check skill_data.required_planet
 
-                        // We need to send an update inventory packet before teleporting, otherwise it is lost
-                        if let Some(game_client) = use_item_user.game_client {
-                            game_client
-                                .server_message_tx
-                                .send(ServerMessage::UpdateInventory {
-                                    items: vec![(
-                                        item_slot,
-                                        use_item_user.inventory.get_item(item_slot).cloned(),
-                                    )],
-                                    money: None,
-                                })
-                                .ok();
-                        }
+                            // We need to send an update inventory packet before teleporting, otherwise it is lost
+                            if let Some(game_client) = use_item_user.game_client {
+                                game_client
+                                    .server_message_tx
+                                    .send(ServerMessage::UpdateInventory {
+                                        items: vec![(
+                                            item_slot,
+                                            use_item_user.inventory.get_item(item_slot).cloned(),
+                                        )],
+                                        money: None,
+                                    })
+                                    .ok();
+                            }
 
-                        client_entity_teleport_zone(
-                            &mut use_item_system_parameters.commands,
-                            &mut use_item_system_parameters.client_entity_list,
-                            use_item_user.entity,
-                            use_item_user.client_entity,
-                            use_item_user.client_entity_sector,
-                            use_item_user.position,
-                            Position::new(
-                                Vec3::new(skill_data.warp_zone_x, skill_data.warp_zone_y, 0.0),
-                                zone_id,
-                            ),
-                            use_item_user.game_client,
-                        );
+                            client_entity_teleport_zone(
+                                &mut use_item_system_parameters.commands,
+                                &mut use_item_system_parameters.client_entity_list,
+                                use_item_user.entity,
+                                use_item_user.client_entity,
+                                use_item_user.client_entity_sector,
+                                use_item_user.position,
+                                Position::new(
+                                    Vec3::new(skill_data.warp_zone_x, skill_data.warp_zone_y, 0.0),
+                                    zone_id,
+                                ),
+                                use_item_user.game_client,
+                            );
+                        }
+                        (true, false)
+                    } else {
+                        (false, false)
                     }
-                    (true, false)
                 } else {
                     (false, false)
                 }
-            } else {
-                (false, false)
             }
-        }
-        ItemClass::SkillBook => {
-            if let Some(skill_id) = item_data.learn_skill_id {
-                (
-                    skill_list_try_learn_skill(
-                        &use_item_system_parameters.game_data,
-                        &mut SkillListBundle {
-                            skill_list: &mut use_item_user.skill_list,
-                            skill_points: Some(&mut use_item_user.skill_points),
-                            game_client: use_item_user.game_client,
-                            ability_values: use_item_user.ability_values,
-                            level: use_item_user.level,
-                            move_speed: Some(use_item_user.move_speed),
-                            team: Some(use_item_user.team),
-                            character_info: Some(use_item_user.character_info),
-                            experience_points: Some(&use_item_user.experience_points),
-                            inventory: Some(&use_item_user.inventory),
-                            stamina: Some(&use_item_user.stamina),
-                            stat_points: Some(&use_item_user.stat_points),
-                            union_membership: Some(&use_item_user.union_membership),
-                            health_points: Some(&use_item_user.health_points),
-                            mana_points: Some(&use_item_user.mana_points),
-                        },
-                        skill_id,
+            ItemClass::SkillBook => {
+                if let Some(skill_id) = item_data.learn_skill_id {
+                    (
+                        skill_list_try_learn_skill(
+                            &use_item_system_parameters.game_data,
+                            &mut SkillListBundle {
+                                skill_list: &mut use_item_user.skill_list,
+                                skill_points: Some(&mut use_item_user.skill_points),
+                                game_client: use_item_user.game_client,
+                                ability_values: use_item_user.ability_values,
+                                level: use_item_user.level,
+                                move_speed: Some(use_item_user.move_speed),
+                                team: Some(use_item_user.team),
+                                character_info: Some(use_item_user.character_info),
+                                experience_points: Some(&use_item_user.experience_points),
+                                inventory: Some(&mut use_item_user.inventory),
+                                stamina: Some(&use_item_user.stamina),
+                                stat_points: Some(&use_item_user.stat_points),
+                                union_membership: Some(&use_item_user.union_membership),
+                                health_points: Some(&use_item_user.health_points),
+                                mana_points: Some(&use_item_user.mana_points),
+                            },
+                            skill_id,
+                        )
+                        .is_ok(),
+                        false,
                     )
-                    .is_ok(),
-                    false,
-                )
-            } else {
-                (false, false)
+                } else {
+                    (false, false)
+                }
             }
-        }
-        ItemClass::EngineFuel => {
-            if let Some(engine_item) = use_item_user
-                .equipment
-                .get_vehicle_item_mut(VehiclePartIndex::Engine)
-            {
-                engine_item.life = engine_item
-                    .life
-                    .saturating_add(item_data.add_fuel as u16 * 10)
-                    .min(1000);
+            ItemClass::EngineFuel => {
+                if let Some(engine_item) = use_item_user
+                    .equipment
+                    .get_vehicle_item_mut(VehiclePartIndex::Engine)
+                {
+                    engine_item.life = engine_item
+                        .life
+                        .saturating_add(item_data.add_fuel as u16 * 10)
+                        .min(1000);
 
-                if let Some(game_client) = use_item_user.game_client {
-                    game_client
-                        .server_message_tx
-                        .send(ServerMessage::UpdateItemLife {
-                            item_slot: ItemSlot::Vehicle(VehiclePartIndex::Engine),
-                            life: engine_item.life,
-                        })
-                        .ok();
-                }
+                    if let Some(game_client) = use_item_user.game_client {
+                        game_client
+                            .server_message_tx
+                            .send(ServerMessage::UpdateItemLife {
+                                item_slot: ItemSlot::Vehicle(VehiclePartIndex::Engine),
+                                life: engine_item.life,
+                            })
+                            .ok();
+                    }
 
-                (true, false)
-            } else {
+                    (true, false)
+                } else {
+                    (false, false)
+                }
+            }
+            ItemClass::RepairTool | ItemClass::TimeCoupon => {
+                warn!(
+                    "Unimplemented use item ItemClass {:?} with item {:?}",
+                    item_data.item_data.class, item
+                );
                 (false, false)
             }
-        }
-        ItemClass::RepairTool | ItemClass::TimeCoupon => {
-            warn!(
-                "Unimplemented use item ItemClass {:?} with item {:?}",
-                item_data.item_data.class, item
-            );
-            (false, false)
-        }
-        _ => {
-            apply_item_effect(use_item_system_parameters, use_item_user, item_data);
-            (true, true)
+            _ => {
+                apply_item_effect(
+                    &use_item_system_parameters.game_data,
+                    &use_item_system_parameters.time,
+                    &mut use_item_system_parameters.server_messages,
+                    use_item_user,
+                    item_data,
+                );
+                (true, true)
+            }
         }
     };
 
     if consume_item {
+        if !item_data.cooldown_duration.is_zero() {
+            use_item_user.cooldowns.item_group.insert(
+                item_data.cooldown_type_id,
+                now + item_data.cooldown_duration,
+            );
+        }
+
         if let Some(game_client) = use_item_user.game_client {
             if message_to_nearby {
                 use_item_system_parameters
@@ -388,14 +666,29 @@ pub fn use_item_system(
                 target_entity,
             } => {
                 if let Ok(mut use_item_user) = query_user.get_mut(entity) {
-                    use_inventory_item(
+                    let result = use_inventory_item(
                         &mut use_item_system_parameters,
                         &mut use_item_user,
                         item_slot,
                         target_entity,
                         None, // TODO: Support repair item use
-                    )
-                    .ok();
+                    );
+
+                    if let Err(UseItemError::Cooldown) = result {
+                        // Resend the unchanged slot so the client does not get out of sync
+                        if let Some(game_client) = use_item_user.game_client {
+                            game_client
+                                .server_message_tx
+                                .send(ServerMessage::UpdateInventory {
+                                    items: vec![(
+                                        item_slot,
+                                        use_item_user.inventory.get_item(item_slot).cloned(),
+                                    )],
+                                    money: None,
+                                })
+                                .ok();
+                        }
+                    }
                 }
             }
             UseItemEvent::Item { entity, ref item } => {
@@ -406,7 +699,9 @@ pub fn use_item_system(
                         .get_consumable_item(item.get_item_number())
                     {
                         apply_item_effect(
-                            &use_item_system_parameters,
+                            &use_item_system_parameters.game_data,
+                            &use_item_system_parameters.time,
+                            &mut use_item_system_parameters.server_messages,
                             &mut use_item_user,
                             item_data,
                         );
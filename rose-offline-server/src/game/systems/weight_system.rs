@@ -1,19 +1,122 @@
-use bevy::ecs::prelude::{Changed, Commands, Entity, Or, Query, Res};
+use std::time::Duration;
+
+use bevy::{
+    ecs::prelude::{Changed, Commands, Entity, Or, Query, Res, ResMut},
+    time::Time,
+};
 
 use crate::game::{
-    components::{Equipment, Inventory, Weight},
+    components::{
+        AbilityValues, ClientEntity, Equipment, Inventory, OverweightLevel, StatusEffects, Weight,
+    },
+    messages::server::ServerMessage,
+    resources::{GameConfig, OverweightConfig, ServerMessages},
     GameData,
 };
 
+// The overweight status effect is removed when the character is no longer overweight
+const OVERWEIGHT_STATUS_EFFECT_DURATION: Duration = Duration::from_secs(24 * 60 * 60);
+
+fn get_overweight_level(
+    overweight_config: &OverweightConfig,
+    weight: u32,
+    max_weight: i32,
+) -> OverweightLevel {
+    let max_weight = max_weight.max(1) as u32;
+    let weight_percent = weight.saturating_mul(100) / max_weight;
+
+    if overweight_config.max_percent != 0 && weight_percent >= overweight_config.max_percent {
+        OverweightLevel::MaxOverweight
+    } else if overweight_config.walk_percent != 0
+        && weight_percent >= overweight_config.walk_percent
+    {
+        OverweightLevel::Overweight
+    } else {
+        OverweightLevel::None
+    }
+}
+
+fn update_overweight_status_effect(
+    game_config: &GameConfig,
+    game_data: &GameData,
+    time: &Time,
+    server_messages: &mut ServerMessages,
+    client_entity: &ClientEntity,
+    status_effects: &mut StatusEffects,
+    is_overweight: bool,
+) {
+    let Some(status_effect_data) = game_config
+        .overweight
+        .status_effect_id
+        .and_then(|id| game_data.status_effects.get_status_effect(id))
+    else {
+        return;
+    };
+    let status_effect_type = status_effect_data.status_effect_type;
+    let is_active = status_effects.active[status_effect_type]
+        .as_ref()
+        .map_or(false, |active| active.id == status_effect_data.id);
+
+    if is_overweight == is_active {
+        return;
+    }
+
+    if is_overweight {
+        if !status_effects.apply_status_effect(
+            status_effect_data,
+            time.last_update().unwrap() + OVERWEIGHT_STATUS_EFFECT_DURATION,
+            0,
+        ) {
+            return;
+        }
+    } else {
+        status_effects.active[status_effect_type] = None;
+        status_effects.expire_times[status_effect_type] = None;
+    }
+
+    server_messages.send_entity_message(
+        client_entity,
+        ServerMessage::UpdateStatusEffects {
+            entity_id: client_entity.id,
+            status_effects: status_effects.active.clone(),
+            updated_values: Vec::new(),
+        },
+    );
+}
+
 pub fn weight_system(
     mut commands: Commands,
-    calculate_weight_query: Query<
-        (Entity, &Inventory, &Equipment),
-        Or<(Changed<Inventory>, Changed<Equipment>)>,
+    mut calculate_weight_query: Query<
+        (
+            Entity,
+            &Inventory,
+            &Equipment,
+            &AbilityValues,
+            Option<&ClientEntity>,
+            Option<&Weight>,
+            Option<&mut StatusEffects>,
+        ),
+        Or<(
+            Changed<Inventory>,
+            Changed<Equipment>,
+            Changed<AbilityValues>,
+        )>,
     >,
+    game_config: Res<GameConfig>,
     game_data: Res<GameData>,
+    time: Res<Time>,
+    mut server_messages: ResMut<ServerMessages>,
 ) {
-    calculate_weight_query.for_each(|(entity, inventory, equipment)| {
+    for (
+        entity,
+        inventory,
+        equipment,
+        ability_values,
+        client_entity,
+        previous_weight,
+        status_effects,
+    ) in calculate_weight_query.iter_mut()
+    {
         let mut weight = 0;
 
         for item in inventory.iter().filter_map(|slot| slot.as_ref()) {
@@ -50,6 +153,30 @@ pub fn weight_system(
                 * item.quantity;
         }
 
-        commands.entity(entity).insert(Weight::new(weight));
-    });
+        let overweight =
+            get_overweight_level(&game_config.overweight, weight, ability_values.max_weight());
+
+        if previous_weight.map_or(true, |previous| previous.overweight != overweight) {
+            if let (Some(client_entity), Some(mut status_effects)) = (client_entity, status_effects)
+            {
+                update_overweight_status_effect(
+                    &game_config,
+                    &game_data,
+                    &time,
+                    &mut server_messages,
+                    client_entity,
+                    &mut status_effects,
+                    overweight != OverweightLevel::None,
+                );
+            }
+        }
+
+        if previous_weight.map_or(true, |previous| {
+            previous.weight != weight || previous.overweight != overweight
+        }) {
+            commands
+                .entity(entity)
+                .insert(Weight::new(weight, overweight));
+        }
+    }
 }
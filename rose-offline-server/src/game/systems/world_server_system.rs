@@ -303,6 +303,71 @@ pub fn world_server_system(
                         });
                     }
                 }
+                ClientMessage::ClanInvite { name } => {
+                    if let Some(game_client_entity) = world_client.game_client_entity {
+                        clan_events.send(ClanEvent::Invite {
+                            inviter: game_client_entity,
+                            name,
+                        });
+                    }
+                }
+                ClientMessage::ClanInviteAccept { inviter_name } => {
+                    if let Some(game_client_entity) = world_client.game_client_entity {
+                        clan_events.send(ClanEvent::InviteReply {
+                            invitee: game_client_entity,
+                            inviter_name,
+                            accepted: true,
+                        });
+                    }
+                }
+                ClientMessage::ClanInviteReject { inviter_name } => {
+                    if let Some(game_client_entity) = world_client.game_client_entity {
+                        clan_events.send(ClanEvent::InviteReply {
+                            invitee: game_client_entity,
+                            inviter_name,
+                            accepted: false,
+                        });
+                    }
+                }
+                ClientMessage::ClanKick { name } => {
+                    if let Some(game_client_entity) = world_client.game_client_entity {
+                        clan_events.send(ClanEvent::Kick {
+                            requester: game_client_entity,
+                            name,
+                        });
+                    }
+                }
+                ClientMessage::ClanPromote { name } => {
+                    if let Some(game_client_entity) = world_client.game_client_entity {
+                        clan_events.send(ClanEvent::Promote {
+                            requester: game_client_entity,
+                            name,
+                        });
+                    }
+                }
+                ClientMessage::ClanDemote { name } => {
+                    if let Some(game_client_entity) = world_client.game_client_entity {
+                        clan_events.send(ClanEvent::Demote {
+                            requester: game_client_entity,
+                            name,
+                        });
+                    }
+                }
+                ClientMessage::ClanSetNotice { notice } => {
+                    if let Some(game_client_entity) = world_client.game_client_entity {
+                        clan_events.send(ClanEvent::SetNotice {
+                            requester: game_client_entity,
+                            notice,
+                        });
+                    }
+                }
+                ClientMessage::ClanQuit => {
+                    if let Some(game_client_entity) = world_client.game_client_entity {
+                        clan_events.send(ClanEvent::Quit {
+                            entity: game_client_entity,
+                        });
+                    }
+                }
                 _ => warn!("[WS] Received unimplemented client message {:?}", message),
             }
         }
@@ -0,0 +1,23 @@
+use bevy::{
+    ecs::prelude::{Query, Res, ResMut, With},
+    time::Time,
+};
+use std::collections::HashMap;
+
+use crate::game::{
+    components::{CharacterInfo, Position},
+    resources::ZoneActivity,
+};
+
+pub fn zone_activity_system(
+    query: Query<&Position, With<CharacterInfo>>,
+    mut zone_activity: ResMut<ZoneActivity>,
+    time: Res<Time>,
+) {
+    let mut num_characters = HashMap::new();
+    for position in query.iter() {
+        *num_characters.entry(position.zone_id).or_insert(0) += 1;
+    }
+
+    zone_activity.update(&num_characters, time.delta());
+}
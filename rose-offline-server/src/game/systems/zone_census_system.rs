@@ -0,0 +1,32 @@
+use bevy::ecs::prelude::{Query, ResMut};
+use std::collections::HashMap;
+
+use crate::game::{
+    components::{ClientEntity, ClientEntityType, GameClient, Position},
+    resources::{ZoneCensus, ZoneCensusCounts},
+};
+
+pub fn zone_census_system(
+    query: Query<(&ClientEntity, &Position, Option<&GameClient>)>,
+    mut zone_census: ResMut<ZoneCensus>,
+) {
+    let mut zones: HashMap<_, ZoneCensusCounts> = HashMap::new();
+
+    for (client_entity, position, game_client) in query.iter() {
+        let counts = zones.entry(position.zone_id).or_default();
+        match client_entity.entity_type {
+            ClientEntityType::Character => {
+                if game_client.is_some() {
+                    counts.players += 1;
+                } else {
+                    counts.bots += 1;
+                }
+            }
+            ClientEntityType::Monster => counts.monsters += 1,
+            ClientEntityType::Npc => counts.npcs += 1,
+            ClientEntityType::ItemDrop => counts.item_drops += 1,
+        }
+    }
+
+    zone_census.update(zones);
+}
@@ -12,9 +12,10 @@ use rose_file_readers::{stb_column, StbFile, VirtualFilesystem};
 
 use crate::game::{
     components::{
-        BasicStats, CharacterInfo, Equipment, ExperiencePoints, HealthPoints, Hotbar, Inventory,
-        Level, ManaPoints, Position, QuestState, SkillList, SkillPoints, Stamina, StatPoints,
-        UnionMembership,
+        BasicStats, CharacterInfo, CharacterLootSettings, CharacterPrivacy, CharacterSkillMastery,
+        CharacterStatistics, CharacterTitles, Equipment, ExperiencePoints, HealthPoints, Hotbar,
+        Inventory, Level, ManaPoints, Position, QuestState, SkillList, SkillPoints, Stamina,
+        StatPoints, UnionMembership,
     },
     storage::character::{CharacterCreator, CharacterCreatorError, CharacterStorage},
 };
@@ -141,14 +142,7 @@ impl CharacterCreator for CharacterCreatorData {
             level: Level::new(1),
             experience_points: ExperiencePoints::default(),
             position: self.start_position.clone(),
-            skill_list: SkillList {
-                pages: vec![
-                    SkillPage::new(IroseSkillPageType::Basic as usize, SKILL_PAGE_SIZE),
-                    SkillPage::new(IroseSkillPageType::Active as usize, SKILL_PAGE_SIZE),
-                    SkillPage::new(IroseSkillPageType::Passive as usize, SKILL_PAGE_SIZE),
-                    SkillPage::new(IroseSkillPageType::Clan as usize, SKILL_PAGE_SIZE),
-                ],
-            },
+            skill_list: self.create_skill_list(),
             hotbar: Hotbar::default(),
             delete_time: None,
             health_points: HealthPoints::new(0),
@@ -158,6 +152,12 @@ impl CharacterCreator for CharacterCreatorData {
             quest_state: QuestState::default(),
             union_membership: UnionMembership::default(),
             stamina: Stamina::default(),
+            privacy: CharacterPrivacy::default(),
+            titles: CharacterTitles::default(),
+            statistics: CharacterStatistics::default(),
+            loot_settings: CharacterLootSettings::default(),
+            skill_mastery: CharacterSkillMastery::default(),
+            is_new_character: true,
         };
 
         for &skill_id in &self.skills {
@@ -208,6 +208,17 @@ impl CharacterCreator for CharacterCreatorData {
         let gender_data = &self.gender_data[gender];
         Ok(gender_data.basic_stats.clone())
     }
+
+    fn create_skill_list(&self) -> SkillList {
+        SkillList {
+            pages: vec![
+                SkillPage::new(IroseSkillPageType::Basic as usize, SKILL_PAGE_SIZE),
+                SkillPage::new(IroseSkillPageType::Active as usize, SKILL_PAGE_SIZE),
+                SkillPage::new(IroseSkillPageType::Passive as usize, SKILL_PAGE_SIZE),
+                SkillPage::new(IroseSkillPageType::Clan as usize, SKILL_PAGE_SIZE),
+            ],
+        }
+    }
 }
 
 fn load_gender(data: &StbInitAvatar, id: usize) -> Option<CharacterGenderData> {
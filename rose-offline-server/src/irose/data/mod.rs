@@ -1,12 +1,18 @@
 use std::sync::Arc;
 
-use rose_data::{CharacterMotionDatabaseOptions, NpcDatabaseOptions};
+use rose_data::{
+    AiDatabaseOptions, CharacterMotionDatabaseOptions, EffectDatabaseOptions, NpcDatabaseOptions,
+    QuestDatabaseOptions, StringDatabase, StringDatabaseOptions,
+};
 use rose_data_irose::{
-    get_ai_database, get_character_motion_database, get_data_decoder, get_item_database,
-    get_job_class_database, get_npc_database, get_quest_database, get_skill_database,
-    get_status_effect_database, get_string_database, get_warp_gate_database, get_zone_database,
+    find_missing_ui_strings, get_ai_database, get_character_motion_database, get_data_decoder,
+    get_effect_database, get_gather_database, get_item_database, get_job_class_database,
+    get_npc_database, get_quest_database, get_skill_database, get_status_effect_database,
+    get_string_database, get_teleport_anchor_database, get_ui_string_ids, get_warp_gate_database,
+    get_zone_database,
 };
 use rose_file_readers::VirtualFilesystem;
+use rose_game_common::data::AbilityValueCalculatorOptions;
 use rose_game_irose::data::{get_ability_value_calculator, get_drop_table};
 
 use crate::game::GameData;
@@ -14,8 +20,42 @@ use crate::game::GameData;
 mod character_creator;
 use character_creator::get_character_creator;
 
-pub fn get_game_data(vfs: &VirtualFilesystem) -> GameData {
-    let string_database = get_string_database(vfs, 1).expect("Failed to load string database");
+/// Check the UI_STRID table of the client data against LIST_STRING.STL, so UI strings which
+/// would show empty in the client are noticed at startup. The irose protocol does not send
+/// any UI string ids, so there are no server sent ids to check.
+fn validate_ui_string_ids(vfs: &VirtualFilesystem, string_database: &StringDatabase) {
+    let ui_string_ids = match get_ui_string_ids(vfs) {
+        Ok(ui_string_ids) => ui_string_ids,
+        Err(error) => {
+            log::debug!("Skipping UI string id validation: {:?}", error);
+            return;
+        }
+    };
+
+    let missing = find_missing_ui_strings(string_database, &ui_string_ids);
+    for (name, id) in missing.iter() {
+        log::debug!("Missing UI string {} ({})", name, id);
+    }
+    if !missing.is_empty() {
+        log::warn!(
+            "{} of {} UI string ids have no text in LIST_STRING.STL for language {}",
+            missing.len(),
+            ui_string_ids.len(),
+            string_database.language
+        );
+    }
+}
+
+pub fn get_game_data(
+    vfs: &VirtualFilesystem,
+    ability_value_options: AbilityValueCalculatorOptions,
+    string_database_options: &StringDatabaseOptions,
+    quest_database_options: &QuestDatabaseOptions,
+    ai_database_options: &AiDatabaseOptions,
+) -> GameData {
+    let string_database =
+        get_string_database(vfs, string_database_options).expect("Failed to load string database");
+    validate_ui_string_ids(vfs, &string_database);
     let item_database = Arc::new(
         get_item_database(vfs, string_database.clone()).expect("Failed to load item database"),
     );
@@ -54,10 +94,24 @@ pub fn get_game_data(vfs: &VirtualFilesystem) -> GameData {
             item_database.clone(),
             skill_database.clone(),
             npc_database.clone(),
+            ability_value_options,
         ),
         data_decoder: get_data_decoder(),
         drop_table,
-        ai: Arc::new(get_ai_database(vfs).expect("Failed to load AI database")),
+        ai: Arc::new(
+            get_ai_database(vfs, string_database_options.language, ai_database_options)
+                .expect("Failed to load AI database"),
+        ),
+        effects: get_effect_database(
+            vfs,
+            &EffectDatabaseOptions {
+                load_bullet_durations: true,
+            },
+        )
+        .expect("Failed to load effect database"),
+        gather_nodes: Arc::new(
+            get_gather_database(vfs).expect("Failed to load gather node database"),
+        ),
         items: item_database,
         job_class: job_class_database,
         motions: Arc::new(
@@ -71,7 +125,7 @@ pub fn get_game_data(vfs: &VirtualFilesystem) -> GameData {
         ),
         npcs: npc_database,
         quests: Arc::new(
-            get_quest_database(vfs, string_database.clone())
+            get_quest_database(vfs, string_database.clone(), quest_database_options)
                 .expect("Failed to load quest database"),
         ),
         skills: skill_database,
@@ -80,6 +134,9 @@ pub fn get_game_data(vfs: &VirtualFilesystem) -> GameData {
                 .expect("Failed to load status effect database"),
         ),
         string_database,
+        teleport_anchors: Arc::new(
+            get_teleport_anchor_database(vfs).expect("Failed to load teleport anchor database"),
+        ),
         warp_gates: Arc::new(
             get_warp_gate_database(vfs).expect("Failed to load warp gate database"),
         ),
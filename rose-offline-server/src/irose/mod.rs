@@ -2,4 +2,4 @@ mod data;
 mod protocol;
 
 pub use data::get_game_data;
-pub use protocol::{game_protocol, login_protocol, world_protocol};
+pub use protocol::{game_protocol, load_client_profile, login_protocol, world_protocol};
@@ -7,21 +7,27 @@ use rose_data::QuestTriggerHash;
 use rose_game_common::{
     components::MoveMode,
     data::Password,
-    messages::{client::ClientMessage, server::ServerMessage},
+    messages::{
+        client::ClientMessage,
+        server::{ConnectionRequestError, ServerMessage},
+    },
 };
 use rose_network_common::Packet;
 use rose_network_irose::{game_client_packets::*, game_server_packets::*};
 
 use crate::{
+    game::messages::control::ProtocolCapabilities,
     implement_protocol_server,
     protocol::{Client, ProtocolServer, ProtocolServerError},
 };
 
-pub struct GameServer;
+pub struct GameServer {
+    capabilities: ProtocolCapabilities,
+}
 
 impl GameServer {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(capabilities: ProtocolCapabilities) -> Self {
+        Self { capabilities }
     }
 
     async fn handle_packet(
@@ -30,20 +36,24 @@ impl GameServer {
         packet: &Packet,
     ) -> Result<(), anyhow::Error> {
         match FromPrimitive::from_u16(packet.command) {
+            // Receiving any packet resets the connection idle timeout
+            Some(ClientPackets::Alive) => {}
             Some(ClientPackets::ConnectRequest) => {
                 let request = PacketClientConnectRequest::try_from(packet)?;
                 client
                     .client_message_tx
                     .send(ClientMessage::GameConnectionRequest {
                         login_token: request.login_token,
-                        password: Password::Md5(request.password_md5.into()),
+                        password: Password::Md5(request.password.into()),
                     })?;
             }
             Some(ClientPackets::JoinZone) => {
-                let _request = PacketClientJoinZone::try_from(packet)?;
+                let request = PacketClientJoinZone::try_from(packet)?;
                 client
                     .client_message_tx
-                    .send(ClientMessage::JoinZoneRequest)?;
+                    .send(ClientMessage::JoinZoneRequest {
+                        zone_checksum: request.zone_checksum,
+                    })?;
             }
             Some(ClientPackets::Chat) => {
                 let packet = PacketClientChat::try_from(packet)?;
@@ -109,6 +119,18 @@ impl GameServer {
                         item_slot,
                     })?;
             }
+            Some(ClientPackets::ChangeCostume) => {
+                let PacketClientChangeCostume {
+                    costume_index,
+                    item_slot,
+                } = PacketClientChangeCostume::try_from(packet)?;
+                client
+                    .client_message_tx
+                    .send(ClientMessage::ChangeCostume {
+                        costume_index,
+                        item_slot,
+                    })?;
+            }
             Some(ClientPackets::IncreaseBasicStat) => {
                 let PacketClientIncreaseBasicStat { basic_stat_type } =
                     PacketClientIncreaseBasicStat::try_from(packet)?;
@@ -124,6 +146,11 @@ impl GameServer {
                         target_entity_id: packet.target_entity_id,
                     })?;
             }
+            Some(ClientPackets::PickupNearestItemDrop) => {
+                client
+                    .client_message_tx
+                    .send(ClientMessage::PickupNearestItemDrop)?;
+            }
             Some(ClientPackets::LogoutRequest) => {
                 client.client_message_tx.send(ClientMessage::Logout)?;
             }
@@ -233,6 +260,104 @@ impl GameServer {
                         position: packet.position,
                     })?;
             }
+            Some(ClientPackets::ToggleSkill) => {
+                let packet = PacketClientToggleSkill::try_from(packet)?;
+                client.client_message_tx.send(ClientMessage::ToggleSkill {
+                    skill_slot: packet.skill_slot,
+                    enabled: packet.enabled,
+                })?;
+            }
+            Some(ClientPackets::ClanVault) => {
+                let message = match PacketClientClanVault::try_from(packet)? {
+                    PacketClientClanVault::Open => ClientMessage::ClanVaultOpen,
+                    PacketClientClanVault::Deposit { item_slot, item } => {
+                        ClientMessage::ClanVaultDeposit { item_slot, item }
+                    }
+                    PacketClientClanVault::Withdraw { vault_slot, item } => {
+                        ClientMessage::ClanVaultWithdraw { vault_slot, item }
+                    }
+                    PacketClientClanVault::GetLog => ClientMessage::ClanVaultGetLog,
+                    PacketClientClanVault::DonateMoney { money } => {
+                        ClientMessage::ClanDonateMoney { money }
+                    }
+                };
+                client.client_message_tx.send(message)?;
+            }
+            Some(ClientPackets::ClanPermissions) => {
+                let message = match PacketClientClanPermissions::try_from(packet)? {
+                    PacketClientClanPermissions::Get => ClientMessage::ClanGetPermissions,
+                    PacketClientClanPermissions::Set { permissions } => {
+                        ClientMessage::ClanSetPermissions { permissions }
+                    }
+                };
+                client.client_message_tx.send(message)?;
+            }
+            Some(ClientPackets::ClanRecruitment) => {
+                let message = match PacketClientClanRecruitment::try_from(packet)? {
+                    PacketClientClanRecruitment::SetNotice { notice } => {
+                        ClientMessage::ClanSetRecruitment { notice }
+                    }
+                    PacketClientClanRecruitment::Search { query } => {
+                        ClientMessage::ClanSearchRecruitment { query }
+                    }
+                    PacketClientClanRecruitment::Apply { clan_name, message } => {
+                        ClientMessage::ClanApply { clan_name, message }
+                    }
+                    PacketClientClanRecruitment::GetApplications => {
+                        ClientMessage::ClanGetApplications
+                    }
+                    PacketClientClanRecruitment::ApplicationReply { name, accepted } => {
+                        ClientMessage::ClanApplicationReply { name, accepted }
+                    }
+                };
+                client.client_message_tx.send(message)?;
+            }
+            Some(ClientPackets::InspectCharacter) => {
+                let message = match PacketClientInspectCharacter::try_from(packet)? {
+                    PacketClientInspectCharacter::Inspect { target_entity_id } => {
+                        ClientMessage::InspectCharacter { target_entity_id }
+                    }
+                    PacketClientInspectCharacter::SetAllowInspect { allow_inspect } => {
+                        ClientMessage::SetAllowInspect { allow_inspect }
+                    }
+                };
+                client.client_message_tx.send(message)?;
+            }
+            Some(ClientPackets::SetActiveTitle) => {
+                let packet = PacketClientSetActiveTitle::try_from(packet)?;
+                client
+                    .client_message_tx
+                    .send(ClientMessage::SetActiveTitle {
+                        title_id: packet.title_id,
+                    })?;
+            }
+            Some(ClientPackets::RequestCharacterStatistics) => {
+                client
+                    .client_message_tx
+                    .send(ClientMessage::RequestCharacterStatistics)?;
+            }
+            Some(ClientPackets::InventoryOperation) => {
+                let message = match PacketClientInventoryOperation::try_from(packet)? {
+                    PacketClientInventoryOperation::Sort {
+                        page_type,
+                        sort_type,
+                    } => ClientMessage::SortInventory {
+                        page_type,
+                        sort_type,
+                    },
+                    PacketClientInventoryOperation::BankDepositPage { page_type } => {
+                        ClientMessage::BankDepositInventoryPage { page_type }
+                    }
+                };
+                client.client_message_tx.send(message)?;
+            }
+            Some(ClientPackets::SetAutoLoot) => {
+                let PacketClientSetAutoLoot { auto_loot } =
+                    PacketClientSetAutoLoot::try_from(packet)?;
+                client
+                    .client_message_tx
+                    .send(ClientMessage::SetAutoLoot { auto_loot })?;
+            }
             Some(ClientPackets::NpcStoreTransaction) => {
                 let packet = PacketClientNpcStoreTransaction::try_from(packet)?;
                 client
@@ -289,6 +414,9 @@ impl GameServer {
                     PacketClientPartyRequest::Kick(character_id) => {
                         ClientMessage::PartyKick { character_id }
                     }
+                    PacketClientPartyRequest::VoteKick(character_id) => {
+                        ClientMessage::PartyVoteKick { character_id }
+                    }
                 };
 
                 client.client_message_tx.send(message)?;
@@ -307,6 +435,9 @@ impl GameServer {
                             owner_entity_id,
                         }
                     }
+                    PacketClientPartyReply::VoteKick(accept) => {
+                        ClientMessage::PartyVoteKickReply { accept }
+                    }
                 };
 
                 client.client_message_tx.send(message)?;
@@ -455,11 +586,19 @@ impl GameServer {
                     }))
                     .await?;
             }
-            ServerMessage::ConnectionRequestError { .. } => {
+            ServerMessage::ConnectionRequestError { error } => {
                 client
                     .connection
                     .write_packet(Packet::from(&PacketConnectionReply {
-                        result: ConnectResult::Failed,
+                        result: match error {
+                            ConnectionRequestError::InvalidPassword => {
+                                ConnectResult::InvalidPassword
+                            }
+                            ConnectionRequestError::AlreadyLoggedIn => {
+                                ConnectResult::AlreadyLoggedIn
+                            }
+                            _ => ConnectResult::Failed,
+                        },
                         packet_sequence_id: 0,
                         pay_flags: 0,
                     }))
@@ -515,6 +654,7 @@ impl GameServer {
                 world_price_rate,
                 item_price_rate,
                 town_price_rate,
+                zone_checksum,
             } => {
                 client
                     .connection
@@ -529,6 +669,7 @@ impl GameServer {
                         world_price_rate,
                         item_price_rate,
                         town_price_rate,
+                        zone_checksum,
                     }))
                     .await?;
             }
@@ -672,6 +813,13 @@ impl GameServer {
                     .await?;
             }
             ServerMessage::SpawnEntityCharacter { data } => {
+                let entity_id = data.entity_id;
+                let costumes = if self.capabilities.costume_slots {
+                    data.equipment.equipped_costumes.clone()
+                } else {
+                    Default::default()
+                };
+
                 client
                     .connection
                     .write_packet(Packet::from(&PacketServerSpawnEntityCharacter {
@@ -689,8 +837,26 @@ impl GameServer {
                         team: data.team,
                         personal_store_info: data.personal_store_info,
                         clan_membership: data.clan_membership,
+                        title: if self.capabilities.titles {
+                            data.title
+                        } else {
+                            None
+                        },
                     }))
                     .await?;
+
+                for (costume_index, item) in costumes {
+                    if item.is_some() {
+                        client
+                            .connection
+                            .write_packet(Packet::from(&PacketServerUpdateCostume {
+                                entity_id,
+                                costume_index,
+                                item,
+                            }))
+                            .await?;
+                    }
+                }
             }
             ServerMessage::SpawnEntityItemDrop {
                 entity_id,
@@ -720,6 +886,7 @@ impl GameServer {
                 spawn_command_state: command,
                 move_mode,
                 status_effects,
+                event_status,
             } => {
                 client
                     .connection
@@ -733,6 +900,7 @@ impl GameServer {
                         spawn_command_state: command,
                         move_mode,
                         status_effects,
+                        event_status,
                     }))
                     .await?;
             }
@@ -876,6 +1044,23 @@ impl GameServer {
                     }))
                     .await?;
             }
+            ServerMessage::UpdateCostume {
+                entity_id,
+                costume_index,
+                item,
+            } => {
+                // The official irose client has no costume slots
+                if self.capabilities.costume_slots {
+                    client
+                        .connection
+                        .write_packet(Packet::from(&PacketServerUpdateCostume {
+                            entity_id,
+                            costume_index,
+                            item,
+                        }))
+                        .await?;
+                }
+            }
             ServerMessage::UpdateVehiclePart {
                 entity_id,
                 vehicle_part_index,
@@ -995,6 +1180,9 @@ impl GameServer {
                     .await?;
                 return Err(ProtocolServerError::ServerInitiatedDisconnect.into());
             }
+            ServerMessage::Disconnect => {
+                return Err(ProtocolServerError::ServerInitiatedDisconnect.into());
+            }
             ServerMessage::LogoutFailed { wait_duration } => {
                 client
                     .connection
@@ -1254,6 +1442,20 @@ impl GameServer {
                     }))
                     .await?;
             }
+            ServerMessage::ToggleSkill {
+                entity_id,
+                skill_id,
+                enabled,
+            } => {
+                client
+                    .connection
+                    .write_packet(Packet::from(&PacketServerToggleSkill {
+                        entity_id,
+                        skill_id,
+                        enabled,
+                    }))
+                    .await?;
+            }
             ServerMessage::FinishCastingSkill {
                 entity_id,
                 skill_id,
@@ -1422,6 +1624,22 @@ impl GameServer {
                     )))
                     .await?;
             }
+            ServerMessage::PartyVoteKickStarted { character_id } => {
+                client
+                    .connection
+                    .write_packet(Packet::from(&PacketServerPartyReply::VoteKickStarted(
+                        character_id,
+                    )))
+                    .await?;
+            }
+            ServerMessage::PartyVoteKickFailed { character_id } => {
+                client
+                    .connection
+                    .write_packet(Packet::from(&PacketServerPartyReply::VoteKickFailed(
+                        character_id,
+                    )))
+                    .await?;
+            }
             ServerMessage::PartyMemberUpdateInfo { member_info } => {
                 client
                     .connection
@@ -1637,6 +1855,220 @@ impl GameServer {
                     }))
                     .await?;
             }
+            ServerMessage::ClanInvite { inviter_name } => {
+                client
+                    .connection
+                    .write_packet(Packet::from(&PacketServerClanCommand::ClanInvite {
+                        inviter_name,
+                    }))
+                    .await?;
+            }
+            ServerMessage::ClanInviteRejected { name } => {
+                client
+                    .connection
+                    .write_packet(Packet::from(&PacketServerClanCommand::ClanInviteRejected {
+                        name,
+                    }))
+                    .await?;
+            }
+            ServerMessage::ClanInviteError { error } => {
+                client
+                    .connection
+                    .write_packet(Packet::from(&PacketServerClanCommand::ClanInviteError {
+                        error,
+                    }))
+                    .await?;
+            }
+            ServerMessage::ClanMemberJoined { member } => {
+                client
+                    .connection
+                    .write_packet(Packet::from(&PacketServerClanCommand::ClanMemberJoined {
+                        member,
+                    }))
+                    .await?;
+            }
+            ServerMessage::ClanMemberKicked { name } => {
+                client
+                    .connection
+                    .write_packet(Packet::from(&PacketServerClanCommand::ClanMemberKicked {
+                        name,
+                    }))
+                    .await?;
+            }
+            ServerMessage::ClanMemberQuit { name } => {
+                client
+                    .connection
+                    .write_packet(Packet::from(&PacketServerClanCommand::ClanMemberQuit {
+                        name,
+                    }))
+                    .await?;
+            }
+            ServerMessage::ClanMemberPositionChanged { name, position } => {
+                client
+                    .connection
+                    .write_packet(Packet::from(
+                        &PacketServerClanCommand::ClanMemberPositionChanged { name, position },
+                    ))
+                    .await?;
+            }
+            ServerMessage::ClanNotice { notice } => {
+                client
+                    .connection
+                    .write_packet(Packet::from(&PacketServerClanCommand::ClanNotice {
+                        notice,
+                    }))
+                    .await?;
+            }
+            ServerMessage::CharacterLeaveClan { client_entity_id } => {
+                client
+                    .connection
+                    .write_packet(Packet::from(&PacketServerClanCommand::CharacterLeaveClan {
+                        client_entity_id,
+                    }))
+                    .await?;
+            }
+            ServerMessage::ClanVaultItems { items } => {
+                client
+                    .connection
+                    .write_packet(Packet::from(&PacketServerClanVault::Items { items }))
+                    .await?;
+            }
+            ServerMessage::ClanVaultLog { entries } => {
+                client
+                    .connection
+                    .write_packet(Packet::from(&PacketServerClanVault::Log { entries }))
+                    .await?;
+            }
+            ServerMessage::ClanVaultError { error } => {
+                client
+                    .connection
+                    .write_packet(Packet::from(&PacketServerClanVault::Error { error }))
+                    .await?;
+            }
+            ServerMessage::ClanPermissions { permissions } => {
+                client
+                    .connection
+                    .write_packet(Packet::from(&PacketServerClanPermissions { permissions }))
+                    .await?;
+            }
+            ServerMessage::ClanRecruitmentList { listings } => {
+                client
+                    .connection
+                    .write_packet(Packet::from(&PacketServerClanRecruitment::List {
+                        listings,
+                    }))
+                    .await?;
+            }
+            ServerMessage::ClanApplications { applications } => {
+                client
+                    .connection
+                    .write_packet(Packet::from(&PacketServerClanRecruitment::Applications {
+                        applications,
+                    }))
+                    .await?;
+            }
+            ServerMessage::ClanApplicationReceived { application } => {
+                // Sent without the client asking, so only to clients which understand it
+                if self.capabilities.clan_recruitment {
+                    client
+                        .connection
+                        .write_packet(Packet::from(
+                            &PacketServerClanRecruitment::ApplicationReceived { application },
+                        ))
+                        .await?;
+                }
+            }
+            ServerMessage::ClanApplicationSent { clan_name } => {
+                client
+                    .connection
+                    .write_packet(Packet::from(
+                        &PacketServerClanRecruitment::ApplicationSent { clan_name },
+                    ))
+                    .await?;
+            }
+            ServerMessage::ClanApplicationRejected { clan_name } => {
+                // Sent without the client asking, so only to clients which understand it
+                if self.capabilities.clan_recruitment {
+                    client
+                        .connection
+                        .write_packet(Packet::from(
+                            &PacketServerClanRecruitment::ApplicationRejected { clan_name },
+                        ))
+                        .await?;
+                }
+            }
+            ServerMessage::ClanApplicationError { error } => {
+                client
+                    .connection
+                    .write_packet(Packet::from(&PacketServerClanRecruitment::Error { error }))
+                    .await?;
+            }
+            ServerMessage::InspectCharacter {
+                entity_id,
+                name,
+                level,
+                job,
+                equipment,
+            } => {
+                client
+                    .connection
+                    .write_packet(Packet::from(&PacketServerInspectCharacter::Inspect {
+                        entity_id,
+                        name,
+                        level,
+                        job,
+                        equipment,
+                    }))
+                    .await?;
+            }
+            ServerMessage::InspectCharacterError { error } => {
+                client
+                    .connection
+                    .write_packet(Packet::from(&PacketServerInspectCharacter::Error { error }))
+                    .await?;
+            }
+            ServerMessage::CharacterTitles { titles, active } => {
+                if self.capabilities.titles {
+                    client
+                        .connection
+                        .write_packet(Packet::from(&PacketServerCharacterTitle::List {
+                            titles,
+                            active,
+                        }))
+                        .await?;
+                }
+            }
+            ServerMessage::CharacterStatistics { statistics } => {
+                client
+                    .connection
+                    .write_packet(Packet::from(&PacketServerCharacterStatistics {
+                        statistics,
+                    }))
+                    .await?;
+            }
+            ServerMessage::UpdateCharacterTitle { entity_id, title } => {
+                if self.capabilities.titles {
+                    client
+                        .connection
+                        .write_packet(Packet::from(&PacketServerCharacterTitle::Update {
+                            entity_id,
+                            title,
+                        }))
+                        .await?;
+                }
+            }
+            ServerMessage::SetEventStatus {
+                entity_id,
+                event_status,
+            } => {
+                client
+                    .connection
+                    .write_packet(Packet::from(&PacketServerSetEventStatus {
+                        entity_id,
+                        event_status,
+                    }))
+                    .await?;
+            }
             // These messages are for other servers
             ServerMessage::ReturnToCharacterSelect
             | ServerMessage::LoginSuccess { .. }
@@ -17,11 +17,28 @@ use crate::{
     protocol::{Client, ProtocolServer, ProtocolServerError},
 };
 
-pub struct LoginServer;
+pub struct LoginServer {
+    password_encoding: IrosePasswordEncoding,
+    // The packet sequence id sent in the connect reply, used as the seed of seeded passwords
+    password_seed: Option<u32>,
+}
 
 impl LoginServer {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            password_encoding: IrosePasswordEncoding::default(),
+            password_seed: None,
+        }
+    }
+
+    fn decode_password(&self, password: &str) -> Result<Password, PacketError> {
+        match self.password_encoding {
+            IrosePasswordEncoding::Md5 => Ok(Password::Md5(password.to_string())),
+            IrosePasswordEncoding::Seeded => Ok(Password::Seeded {
+                seed: self.password_seed.ok_or(PacketError::InvalidPacket)?,
+                hash: password.to_string(),
+            }),
+        }
     }
 
     async fn handle_packet(
@@ -30,7 +47,10 @@ impl LoginServer {
         packet: &Packet,
     ) -> Result<(), anyhow::Error> {
         match FromPrimitive::from_u16(packet.command) {
+            // Receiving any packet resets the connection idle timeout
+            Some(ClientPackets::Alive) => {}
             Some(ClientPackets::Connect) => {
+                self.password_encoding = PacketClientConnect::try_from(packet)?.password_encoding;
                 client
                     .client_message_tx
                     .send(ClientMessage::ConnectionRequest {
@@ -42,7 +62,7 @@ impl LoginServer {
                 let request = PacketClientLoginRequest::try_from(packet)?;
                 client.client_message_tx.send(ClientMessage::LoginRequest {
                     username: String::from(request.username),
-                    password: Password::Md5(request.password_md5.into()),
+                    password: self.decode_password(request.password)?,
                 })?;
             }
             Some(ClientPackets::ChannelList) => {
@@ -71,6 +91,7 @@ impl LoginServer {
     ) -> Result<(), anyhow::Error> {
         match message {
             ServerMessage::ConnectionRequestSuccess { packet_sequence_id } => {
+                self.password_seed = Some(packet_sequence_id);
                 client
                     .connection
                     .write_packet(Packet::from(&PacketConnectionReply {
@@ -113,6 +134,9 @@ impl LoginServer {
                     LoginError::InvalidPassword => Packet::from(
                         &PacketServerLoginReply::with_error_result(LoginResult::InvalidPassword),
                     ),
+                    LoginError::TooManyAttempts => Packet::from(
+                        &PacketServerLoginReply::with_error_result(LoginResult::RefusedAccount),
+                    ),
                 };
                 client.connection.write_packet(packet).await?;
             }
@@ -121,13 +145,13 @@ impl LoginServer {
                 channels,
             } => {
                 let mut channel_list: Vec<PacketServerChannelListItem> = Vec::new();
-                for (id, name) in &channels {
+                for channel in &channels {
                     channel_list.push(PacketServerChannelListItem {
-                        id: *id,
+                        id: channel.id,
                         low_age: 0u8,
                         high_age: 100u8,
-                        percent_full: 50u16,
-                        name,
+                        percent_full: channel.percent_full,
+                        name: &channel.name,
                     });
                 }
 
@@ -177,6 +201,9 @@ impl LoginServer {
                 };
                 client.connection.write_packet(packet).await?;
             }
+            ServerMessage::Disconnect => {
+                return Err(ProtocolServerError::ServerInitiatedDisconnect.into());
+            }
             _ => panic!("Received unexpected server message for login server"),
         }
 
@@ -1,8 +1,15 @@
-use std::sync::Arc;
+use anyhow::{anyhow, Context};
+use std::{path::Path, sync::Arc};
 
-use rose_network_irose::{ServerPacketCodec, IROSE_112_TABLE};
+use rose_network_irose::{IroseClientProfile, IroseClientVersion, ServerPacketCodec};
 
-use crate::{game::messages::control::ClientType, protocol::Protocol};
+use crate::{
+    game::{
+        messages::control::{ClientType, ProtocolCapabilities},
+        IroseConfig,
+    },
+    protocol::{Protocol, ProtocolServer},
+};
 
 mod game_server;
 mod login_server;
@@ -12,28 +19,84 @@ use game_server::GameServer;
 use login_server::LoginServer;
 use world_server::WorldServer;
 
-pub fn login_protocol() -> Arc<Protocol> {
+pub fn load_client_profile(config: &IroseConfig) -> Result<IroseClientProfile, anyhow::Error> {
+    let version: IroseClientVersion = config
+        .client_version
+        .parse()
+        .map_err(|error: String| anyhow!(error))?;
+    let packet_table = match config.packet_table.as_deref() {
+        Some(path) => load_packet_table(path)?,
+        None => version.packet_table(),
+    };
+
+    Ok(IroseClientProfile::new(version, packet_table)
+        .with_clan_recruitment(config.enable_clan_recruitment)
+        .with_costume_slots(config.enable_costume_slots)
+        .with_titles(config.enable_titles))
+}
+
+fn get_capabilities(profile: &IroseClientProfile) -> ProtocolCapabilities {
+    ProtocolCapabilities {
+        clans: true,
+        clan_recruitment: profile.clan_recruitment,
+        costume_slots: profile.costume_slots,
+        titles: profile.titles,
+    }
+}
+
+fn load_packet_table(path: &Path) -> Result<&'static [u8; 256], anyhow::Error> {
+    let bytes = std::fs::read(path)
+        .with_context(|| format!("Failed to read packet table {}", path.to_string_lossy()))?;
+    let table: [u8; 256] = bytes.try_into().map_err(|bytes: Vec<u8>| {
+        anyhow!(
+            "Packet table {} must be 256 bytes, found {}",
+            path.to_string_lossy(),
+            bytes.len()
+        )
+    })?;
+
+    // The codec keeps a reference to the table for the lifetime of the server
+    Ok(Box::leak(Box::new(table)))
+}
+
+pub fn login_protocol(profile: IroseClientProfile) -> Arc<Protocol> {
     Arc::new(Protocol {
         client_type: ClientType::Login,
-        packet_codec: Box::new(ServerPacketCodec::default(&IROSE_112_TABLE)),
-        create_server: || Box::new(LoginServer::new()),
+        capabilities: get_capabilities(&profile),
+        packet_codec: Box::new(ServerPacketCodec::default(profile.packet_table)),
+        create_server: Box::new(move || -> Box<dyn ProtocolServer + Send + Sync> {
+            Box::new(LoginServer::new())
+        }),
     })
 }
 
-pub fn world_protocol() -> Arc<Protocol> {
+pub fn world_protocol(profile: IroseClientProfile) -> Arc<Protocol> {
     let packet_codec_seed = 0x12345678; // This can be any non-zero value
     Arc::new(Protocol {
         client_type: ClientType::World,
-        packet_codec: Box::new(ServerPacketCodec::init(&IROSE_112_TABLE, packet_codec_seed)),
-        create_server: || Box::new(WorldServer::new()),
+        capabilities: get_capabilities(&profile),
+        packet_codec: Box::new(ServerPacketCodec::init(
+            profile.packet_table,
+            packet_codec_seed,
+        )),
+        create_server: Box::new(move || -> Box<dyn ProtocolServer + Send + Sync> {
+            Box::new(WorldServer::new())
+        }),
     })
 }
 
-pub fn game_protocol() -> Arc<Protocol> {
+pub fn game_protocol(profile: IroseClientProfile) -> Arc<Protocol> {
     let packet_codec_seed = 0x87654321; // This can be any non-zero value
+    let capabilities = get_capabilities(&profile);
     Arc::new(Protocol {
         client_type: ClientType::Game,
-        packet_codec: Box::new(ServerPacketCodec::init(&IROSE_112_TABLE, packet_codec_seed)),
-        create_server: || Box::new(GameServer::new()),
+        capabilities,
+        packet_codec: Box::new(ServerPacketCodec::init(
+            profile.packet_table,
+            packet_codec_seed,
+        )),
+        create_server: Box::new(move || -> Box<dyn ProtocolServer + Send + Sync> {
+            Box::new(GameServer::new(capabilities))
+        }),
     })
 }
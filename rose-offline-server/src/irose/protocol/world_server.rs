@@ -31,13 +31,15 @@ impl WorldServer {
         packet: &Packet,
     ) -> Result<(), anyhow::Error> {
         match FromPrimitive::from_u16(packet.command) {
+            // Receiving any packet resets the connection idle timeout
+            Some(ClientPackets::Alive) => {}
             Some(ClientPackets::ConnectRequest) => {
                 let request = PacketClientConnectRequest::try_from(packet)?;
                 client
                     .client_message_tx
                     .send(ClientMessage::ConnectionRequest {
                         login_token: request.login_token,
-                        password: Password::Md5(request.password_md5.into()),
+                        password: Password::Md5(request.password.into()),
                     })?;
             }
             Some(ClientPackets::CharacterListRequest) => {
@@ -80,9 +82,33 @@ impl WorldServer {
                     })?;
             }
             Some(ClientPackets::ClanCommand) => match PacketClientClanCommand::try_from(packet)? {
+                PacketClientClanCommand::Invite { name } => client
+                    .client_message_tx
+                    .send(ClientMessage::ClanInvite { name })?,
+                PacketClientClanCommand::InviteAccept { inviter_name } => client
+                    .client_message_tx
+                    .send(ClientMessage::ClanInviteAccept { inviter_name })?,
+                PacketClientClanCommand::InviteReject { inviter_name } => client
+                    .client_message_tx
+                    .send(ClientMessage::ClanInviteReject { inviter_name })?,
+                PacketClientClanCommand::Promote { name } => client
+                    .client_message_tx
+                    .send(ClientMessage::ClanPromote { name })?,
+                PacketClientClanCommand::Demote { name } => client
+                    .client_message_tx
+                    .send(ClientMessage::ClanDemote { name })?,
+                PacketClientClanCommand::Quit => {
+                    client.client_message_tx.send(ClientMessage::ClanQuit)?
+                }
                 PacketClientClanCommand::GetMemberList => client
                     .client_message_tx
                     .send(ClientMessage::ClanGetMemberList)?,
+                PacketClientClanCommand::SetNotice { notice } => client
+                    .client_message_tx
+                    .send(ClientMessage::ClanSetNotice { notice })?,
+                PacketClientClanCommand::Kick { name } => client
+                    .client_message_tx
+                    .send(ClientMessage::ClanKick { name })?,
                 PacketClientClanCommand::UpdateLevelAndJob { .. } => {
                     // Ignore this, we do not need to rely on client reporting of level / job
                 }
@@ -224,6 +250,9 @@ impl WorldServer {
                     }))
                     .await?;
             }
+            ServerMessage::Disconnect => {
+                return Err(ProtocolServerError::ServerInitiatedDisconnect.into());
+            }
             _ => panic!("Received unexpected server message for world server"),
         }
         Ok(())
@@ -0,0 +1,172 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use tracing::{
+    field::{Field, Visit},
+    span, Subscriber,
+};
+use tracing_appender::{non_blocking::WorkerGuard, rolling};
+use tracing_subscriber::{fmt, layer::Context, prelude::*, registry::LookupSpan, EnvFilter, Layer};
+
+use crate::game::{LoggingConfig, LoggingFileRotation};
+
+#[derive(Clone, Copy, Default)]
+pub struct SystemTiming {
+    pub count: u64,
+    pub total: Duration,
+    pub max: Duration,
+}
+
+/// Accumulated durations of bevy system spans, keyed by system name.
+///
+/// Only populated when built with the `trace-systems` feature.
+#[derive(Clone, Default)]
+pub struct SystemTimings(Arc<Mutex<HashMap<String, SystemTiming>>>);
+
+impl SystemTimings {
+    fn record(&self, name: &str, duration: Duration) {
+        let mut timings = self.0.lock().unwrap();
+        let timing = timings.entry(name.to_string()).or_default();
+        timing.count += 1;
+        timing.total += duration;
+        timing.max = timing.max.max(duration);
+    }
+
+    /// Returns all recorded timings, sorted by total duration descending
+    pub fn sorted(&self) -> Vec<(String, SystemTiming)> {
+        let mut timings: Vec<_> = self
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(name, timing)| (name.clone(), *timing))
+            .collect();
+        timings.sort_by(|(_, a), (_, b)| b.total.cmp(&a.total));
+        timings
+    }
+}
+
+struct SystemSpanName(String);
+
+struct SystemSpanEntered(Instant);
+
+struct SystemSpanNameVisitor(Option<String>);
+
+impl Visit for SystemSpanNameVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "name" {
+            self.0 = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "name" {
+            self.0 = Some(format!("{:?}", value).trim_matches('"').to_string());
+        }
+    }
+}
+
+struct SystemTimingLayer(SystemTimings);
+
+impl<S> Layer<S> for SystemTimingLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        // bevy emits a span named "system" around each system run
+        if attrs.metadata().name() != "system" {
+            return;
+        }
+
+        let mut visitor = SystemSpanNameVisitor(None);
+        attrs.record(&mut visitor);
+
+        if let (Some(name), Some(span)) = (visitor.0, ctx.span(id)) {
+            span.extensions_mut().insert(SystemSpanName(name));
+        }
+    }
+
+    fn on_enter(&self, id: &span::Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            let mut extensions = span.extensions_mut();
+            if extensions.get::<SystemSpanName>().is_some() {
+                extensions.replace(SystemSpanEntered(Instant::now()));
+            }
+        }
+    }
+
+    fn on_exit(&self, id: &span::Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            let mut extensions = span.extensions_mut();
+            if let Some(SystemSpanEntered(entered)) = extensions.remove::<SystemSpanEntered>() {
+                if let Some(SystemSpanName(name)) = extensions.get::<SystemSpanName>() {
+                    self.0.record(name, entered.elapsed());
+                }
+            }
+        }
+    }
+}
+
+/// Initialise the global tracing subscriber, `log` records are forwarded to it.
+///
+/// The returned guard must be kept alive to flush file output. When `system_timings` is
+/// provided, the duration of every bevy system span is accumulated into it.
+pub fn init(
+    config: &LoggingConfig,
+    system_timings: Option<SystemTimings>,
+) -> Result<Option<WorkerGuard>, anyhow::Error> {
+    let mut filter = EnvFilter::try_new(&config.level)?;
+    for (target, level) in config.filters.iter() {
+        filter = filter.add_directive(format!("{}={}", target, level).parse()?);
+    }
+
+    let stdout_layer = if config.json {
+        fmt::layer().json().with_current_span(true).boxed()
+    } else {
+        fmt::layer().with_file(true).with_line_number(true).boxed()
+    };
+
+    let mut guard = None;
+    let file_layer = config.file.as_ref().map(|file_config| {
+        let rotation = match file_config.rotation {
+            LoggingFileRotation::Minutely => rolling::Rotation::MINUTELY,
+            LoggingFileRotation::Hourly => rolling::Rotation::HOURLY,
+            LoggingFileRotation::Daily => rolling::Rotation::DAILY,
+            LoggingFileRotation::Never => rolling::Rotation::NEVER,
+        };
+        let appender = rolling::RollingFileAppender::new(
+            rotation,
+            &file_config.directory,
+            &file_config.prefix,
+        );
+        let (writer, writer_guard) = tracing_appender::non_blocking(appender);
+        guard = Some(writer_guard);
+
+        if config.json {
+            fmt::layer()
+                .json()
+                .with_current_span(true)
+                .with_writer(writer)
+                .boxed()
+        } else {
+            fmt::layer()
+                .with_ansi(false)
+                .with_file(true)
+                .with_line_number(true)
+                .with_writer(writer)
+                .boxed()
+        }
+    });
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(stdout_layer)
+        .with(file_layer)
+        .with(system_timings.map(SystemTimingLayer))
+        .try_init()?;
+
+    Ok(guard)
+}
@@ -8,26 +8,39 @@
 
 mod game;
 mod irose;
+mod logging;
 mod protocol;
 
 use std::{
     path::{Path, PathBuf},
-    time::Instant,
+    time::{Duration, Instant},
 };
 
 use clap::{Arg, Command};
 use log::debug;
-use simplelog::*;
 use tokio::net::TcpListener;
 use tokio::runtime::Builder;
+#[cfg(unix)]
+use tokio::signal::unix::{signal, SignalKind};
 
+use rose_data::{AiDatabaseOptions, QuestDatabaseOptions, StringDatabaseOptions, ZoneId};
 use rose_file_readers::{
     HostFilesystemDevice, VfsIndex, VirtualFilesystem, VirtualFilesystemDevice,
 };
 
 use crate::{
-    game::GameConfig,
-    protocol::server::{GameServer, LoginServer, WorldServer},
+    game::{
+        storage::{
+            encryption::{encrypt_account_storage, set_account_storage_key, StorageKey},
+            writer::shutdown_storage_writer,
+        },
+        BenchmarkConfig, BenchmarkReport, GameChannelConfig, GameConfig, WorldServerConfig,
+    },
+    logging::SystemTimings,
+    protocol::{
+        admin_server::AdminServer,
+        server::{GameServer, LoginServer, WorldServer},
+    },
 };
 
 pub enum ProtocolType {
@@ -41,21 +54,13 @@ impl Default for ProtocolType {
 }
 
 async fn async_main() {
-    TermLogger::init(
-        LevelFilter::Trace,
-        ConfigBuilder::new()
-            .set_location_level(LevelFilter::Trace)
-            .add_filter_ignore_str("mio")
-            .add_filter_ignore_str("npc_ai")
-            .add_filter_ignore_str("packets")
-            .add_filter_ignore_str("quest")
-            .build(),
-        TerminalMode::Stdout,
-        ColorChoice::Auto,
-    )
-    .expect("Failed to initialise logging");
-
     let mut command = Command::new("rose-offline")
+        .arg(
+            Arg::new("config")
+                .long("config")
+                .help("Optional path to server config file")
+                .takes_value(true),
+        )
         .arg(
             Arg::new("data-idx")
                 .long("data-idx")
@@ -103,12 +108,90 @@ async fn async_main() {
                 .value_parser(["irose"])
                 .default_value("irose")
                 .help("Select which protocol to use."),
+        )
+        .arg(
+            Arg::new("storage-key")
+                .long("storage-key")
+                .help("Optional path to a key file used to encrypt account storage")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("create-storage-key")
+                .long("create-storage-key")
+                .help("Generate a new key at the --storage-key path if it does not exist")
+                .requires("storage-key"),
+        )
+        .arg(
+            Arg::new("encrypt-account-storage")
+                .long("encrypt-account-storage")
+                .help("Encrypt all existing account storage files with --storage-key and exit")
+                .requires("storage-key"),
+        )
+        .arg(
+            Arg::new("admin-port")
+                .long("admin-port")
+                .help("Optional port for the admin API, which only listens on 127.0.0.1")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("benchmark")
+                .long("benchmark")
+                .help("Run a headless benchmark with bots instead of starting the servers"),
+        )
+        .arg(
+            Arg::new("benchmark-bots")
+                .long("benchmark-bots")
+                .help("Number of bots to spawn in benchmark mode")
+                .takes_value(true)
+                .default_value("200"),
+        )
+        .arg(
+            Arg::new("benchmark-ticks")
+                .long("benchmark-ticks")
+                .help("Number of ticks to run in benchmark mode")
+                .takes_value(true)
+                .default_value("3600"),
+        )
+        .arg(
+            Arg::new("benchmark-zones")
+                .long("benchmark-zones")
+                .help("Comma separated zone ids to spread benchmark bots across, defaults to all zones with monster spawns")
+                .takes_value(true),
         );
     let data_path_error = command.error(
         clap::ErrorKind::ArgumentNotFound,
         "Must specify at least one of --data-idx or --data-path",
     );
     let matches = command.get_matches();
+
+    let game_config = if let Some(config_path) = matches.value_of("config").map(Path::new) {
+        GameConfig::load(config_path)
+            .unwrap_or_else(|error| panic!("Failed to load config: {:?}", error))
+    } else {
+        GameConfig::default()
+    };
+    let system_timings = matches.is_present("benchmark").then(SystemTimings::default);
+    let _log_guard = logging::init(&game_config.logging, system_timings.clone())
+        .expect("Failed to initialise logging");
+
+    if let Some(key_path) = matches.value_of("storage-key").map(Path::new) {
+        let storage_key = if matches.is_present("create-storage-key") && !key_path.exists() {
+            StorageKey::create(key_path)
+        } else {
+            StorageKey::load(key_path)
+        }
+        .unwrap_or_else(|error| panic!("Failed to load storage key: {:?}", error));
+
+        if matches.is_present("encrypt-account-storage") {
+            let num_encrypted = encrypt_account_storage(&storage_key)
+                .unwrap_or_else(|error| panic!("Failed to encrypt account storage: {:?}", error));
+            log::info!("Encrypted {} account storage files", num_encrypted);
+            return;
+        }
+
+        set_account_storage_key(storage_key);
+    }
+
     let listen_ip = matches.value_of("ip").unwrap();
     let login_port = matches.value_of("login-port").unwrap();
     let world_port = matches.value_of("world-port").unwrap();
@@ -119,11 +202,16 @@ async fn async_main() {
     };
 
     let (login_protocol, world_protocol, game_protocol) = match protocol_type {
-        ProtocolType::Irose => (
-            irose::login_protocol(),
-            irose::world_protocol(),
-            irose::game_protocol(),
-        ),
+        ProtocolType::Irose => {
+            let client_profile = irose::load_client_profile(&game_config.irose)
+                .unwrap_or_else(|error| panic!("Failed to load irose client profile: {:?}", error));
+            log::info!("Using irose client version {:?}", client_profile.version);
+            (
+                irose::login_protocol(client_profile),
+                irose::world_protocol(client_profile),
+                irose::game_protocol(client_profile),
+            )
+        }
     };
 
     let mut data_idx_path = matches.value_of("data-idx").map(Path::new);
@@ -170,19 +258,101 @@ async fn async_main() {
     let virtual_filesystem = VirtualFilesystem::new(vfs_devices);
 
     let started_load = Instant::now();
-    let game_data = irose::get_game_data(&virtual_filesystem);
+    let game_data = irose::get_game_data(
+        &virtual_filesystem,
+        game_config.ability_values.clone(),
+        &StringDatabaseOptions {
+            language: game_config.irose.language,
+        },
+        &QuestDatabaseOptions {
+            yaml_qsd_path: data_extracted_path
+                .map(|data_extracted_path| data_extracted_path.join("3DDATA/QUESTDATA")),
+        },
+        &AiDatabaseOptions {
+            npc_ai_override_path: data_extracted_path
+                .map(|data_extracted_path| data_extracted_path.join("3DDATA/AI/NPC")),
+        },
+    );
     debug!("Time take to read game data {:?}", started_load.elapsed());
 
-    let game_config = GameConfig {
-        enable_npc_spawns: true,
-        enable_monster_spawns: true,
+    if matches.is_present("benchmark") {
+        let benchmark_config = BenchmarkConfig {
+            bot_count: matches
+                .value_of("benchmark-bots")
+                .unwrap()
+                .parse()
+                .expect("Invalid value for --benchmark-bots"),
+            ticks: matches
+                .value_of("benchmark-ticks")
+                .unwrap()
+                .parse()
+                .expect("Invalid value for --benchmark-ticks"),
+            zones: matches
+                .value_of("benchmark-zones")
+                .map(|zones| {
+                    zones
+                        .split(',')
+                        .map(|zone| {
+                            zone.trim()
+                                .parse::<u16>()
+                                .ok()
+                                .and_then(ZoneId::new)
+                                .unwrap_or_else(|| panic!("Invalid benchmark zone id {}", zone))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+        };
+
+        let report = game::run_benchmark(game_config, game_data, &benchmark_config);
+        print_benchmark_report(&report, system_timings.as_ref());
+        return;
+    }
+
+    let world_server_configs = if game_config.world_servers.is_empty() {
+        vec![WorldServerConfig {
+            name: String::from("_WorldServer"),
+            port: world_port.parse().expect("Invalid value for --world-port"),
+            channels: vec![GameChannelConfig::new(
+                String::from("GameServer"),
+                game_port.parse().expect("Invalid value for --game-port"),
+            )],
+        }]
+    } else {
+        game_config.world_servers.clone()
     };
+    let idle_timeout = Some(Duration::from_secs(game_config.idle_timeout_secs))
+        .filter(|idle_timeout| !idle_timeout.is_zero());
 
     let (game_control_tx, game_control_rx) = crossbeam_channel::unbounded();
     std::thread::spawn(move || {
         game::GameWorld::new(game_control_rx).run(game_config, game_data);
     });
 
+    #[cfg(unix)]
+    if let Some(config_path) = matches.value_of("config").map(PathBuf::from) {
+        tokio::spawn(reload_config_on_hangup(
+            config_path,
+            game_control_tx.clone(),
+        ));
+    }
+
+    if let Some(admin_port) = matches.value_of("admin-port") {
+        let mut admin_server = AdminServer::new(
+            TcpListener::bind(format!("127.0.0.1:{}", admin_port))
+                .await
+                .unwrap_or_else(|error| {
+                    panic!("Failed to listen on admin port {}: {:?}", admin_port, error)
+                }),
+            game_control_tx.clone(),
+            matches.value_of("config").map(PathBuf::from),
+        );
+
+        tokio::spawn(async move {
+            admin_server.run().await;
+        });
+    }
+
     let mut login_server = LoginServer::new(
         TcpListener::bind(format!("{}:{}", listen_ip, login_port))
             .await
@@ -193,38 +363,150 @@ async fn async_main() {
     .await
     .unwrap();
 
-    let mut world_server = WorldServer::new(
-        String::from("_WorldServer"),
-        TcpListener::bind(format!("{}:{}", listen_ip, world_port))
-            .await
-            .unwrap(),
-        world_protocol,
-        game_control_tx.clone(),
-    )
-    .await
-    .unwrap();
+    for world_server_config in world_server_configs {
+        let mut world_server = WorldServer::new(
+            world_server_config.name.clone(),
+            TcpListener::bind(format!("{}:{}", listen_ip, world_server_config.port))
+                .await
+                .unwrap_or_else(|error| {
+                    panic!(
+                        "Failed to listen on world server {} port {}: {:?}",
+                        world_server_config.name, world_server_config.port, error
+                    )
+                }),
+            world_protocol.clone(),
+            game_control_tx.clone(),
+        )
+        .await
+        .unwrap();
 
-    let mut game_server = GameServer::new(
-        String::from("GameServer"),
-        world_server.get_entity(),
-        TcpListener::bind(format!("{}:{}", listen_ip, game_port))
+        for channel_config in world_server_config.channels {
+            let mut game_server = GameServer::new(
+                channel_config.name.clone(),
+                world_server.get_entity(),
+                channel_config.max_players,
+                TcpListener::bind(format!("{}:{}", listen_ip, channel_config.port))
+                    .await
+                    .unwrap_or_else(|error| {
+                        panic!(
+                            "Failed to listen on game server {} port {}: {:?}",
+                            channel_config.name, channel_config.port, error
+                        )
+                    }),
+                game_protocol.clone(),
+                idle_timeout,
+                game_control_tx.clone(),
+            )
             .await
-            .unwrap(),
-        game_protocol,
-        game_control_tx.clone(),
-    )
-    .await
-    .unwrap();
+            .unwrap();
 
-    tokio::spawn(async move {
-        game_server.run().await;
-    });
+            tokio::spawn(async move {
+                game_server.run().await;
+            });
+        }
 
-    tokio::spawn(async move {
-        world_server.run().await;
-    });
+        tokio::spawn(async move {
+            world_server.run().await;
+        });
+    }
+
+    tokio::select! {
+        _ = login_server.run() => {}
+        _ = shutdown_signal() => log::info!("Shutting down"),
+    }
 
-    login_server.run().await;
+    // Queued storage writes would be lost if the process exited before they were performed
+    shutdown_storage_writer();
+}
+
+/// Completes when the server is asked to stop with ctrl-c, or SIGTERM on unix
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    match signal(SignalKind::terminate()) {
+        Ok(mut terminate) => {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {}
+                _ = terminate.recv() => {}
+            }
+            return;
+        }
+        Err(error) => log::warn!("Failed to listen for SIGTERM: {:?}", error),
+    }
+
+    tokio::signal::ctrl_c().await.ok();
+}
+
+/// Reloads the config file when the server receives SIGHUP
+#[cfg(unix)]
+async fn reload_config_on_hangup(
+    config_path: PathBuf,
+    control_tx: crossbeam_channel::Sender<game::messages::control::ControlMessage>,
+) {
+    let mut hangup = match signal(SignalKind::hangup()) {
+        Ok(hangup) => hangup,
+        Err(error) => {
+            log::warn!(
+                "Failed to listen for SIGHUP, config reload disabled: {:?}",
+                error
+            );
+            return;
+        }
+    };
+
+    while hangup.recv().await.is_some() {
+        match GameConfig::load(&config_path) {
+            Ok(game_config) => {
+                control_tx
+                    .send(game::messages::control::ControlMessage::ReloadConfig {
+                        game_config: Box::new(game_config),
+                    })
+                    .ok();
+            }
+            Err(error) => log::warn!("Failed to reload config: {:?}", error),
+        }
+    }
+}
+
+fn print_benchmark_report(report: &BenchmarkReport, system_timings: Option<&SystemTimings>) {
+    println!(
+        "Benchmark: {} bots across {} zones, {} ticks in {:.2?}",
+        report.bot_count,
+        report.zone_count,
+        report.tick_durations.len(),
+        report.total()
+    );
+    println!(
+        "Tick time: min {:.2?}, mean {:.2?}, p50 {:.2?}, p90 {:.2?}, p99 {:.2?}, max {:.2?}",
+        report.percentile(0.0),
+        report.mean(),
+        report.percentile(0.5),
+        report.percentile(0.9),
+        report.percentile(0.99),
+        report.percentile(1.0)
+    );
+
+    let system_timings = system_timings
+        .map(SystemTimings::sorted)
+        .unwrap_or_default();
+    if system_timings.is_empty() {
+        println!("No per system timings recorded, build with --features trace-systems");
+        return;
+    }
+
+    println!(
+        "{:>12} {:>12} {:>12} {:>8}  system",
+        "total", "mean", "max", "count"
+    );
+    for (name, timing) in system_timings {
+        println!(
+            "{:>12.2?} {:>12.2?} {:>12.2?} {:>8}  {}",
+            timing.total,
+            timing.total / timing.count.max(1) as u32,
+            timing.max,
+            timing.count,
+            name
+        );
+    }
 }
 
 fn main() {
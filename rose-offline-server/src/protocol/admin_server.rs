@@ -0,0 +1,326 @@
+use log::info;
+use serde_json::json;
+use std::{path::PathBuf, sync::Arc};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+    sync::oneshot,
+};
+
+use rose_data::ZoneId;
+use rose_game_common::data::Password;
+
+use crate::game::{
+    components::ClientEntityId,
+    messages::control::{ControlMessage, InspectEntityTarget, ServerNewsEdit},
+    storage::report::ReportStorage,
+    GameConfig,
+};
+
+const DEFAULT_NUM_REPORTS: usize = 20;
+const DEFAULT_NUM_CENSUS_ZONES: usize = 5;
+
+const NEWS_USAGE: &str = "usage: news list | news motd [text] | news add <text> | news remove <index> | news clear | news interval <secs>";
+
+/// Returns the remainder of the line after skipping the given number of words, so free text
+/// arguments keep their spacing.
+fn skip_words(line: &str, num_words: usize) -> &str {
+    let mut rest = line.trim_start();
+    for _ in 0..num_words {
+        rest = rest
+            .trim_start_matches(|c: char| !c.is_whitespace())
+            .trim_start();
+    }
+    rest.trim_end()
+}
+
+fn parse_news_edit(line: &str, args: &[&str]) -> Option<Option<ServerNewsEdit>> {
+    match *args {
+        ["list"] => Some(None),
+        ["motd"] => Some(Some(ServerNewsEdit::Motd(None))),
+        ["motd", ..] => Some(Some(ServerNewsEdit::Motd(Some(
+            skip_words(line, 2).to_string(),
+        )))),
+        ["add", _, ..] => Some(Some(ServerNewsEdit::AddAnnouncement(
+            skip_words(line, 2).to_string(),
+        ))),
+        ["remove", index] => Some(Some(ServerNewsEdit::RemoveAnnouncement(
+            index.parse::<usize>().ok()?,
+        ))),
+        ["clear"] => Some(Some(ServerNewsEdit::ClearAnnouncements)),
+        ["interval", secs] => Some(Some(ServerNewsEdit::AnnouncementInterval(
+            secs.parse::<u64>().ok()?,
+        ))),
+        _ => None,
+    }
+}
+
+fn parse_inspect_target(args: &[&str]) -> Option<InspectEntityTarget> {
+    match *args {
+        [name] => Some(InspectEntityTarget::CharacterName(name.to_string())),
+        [zone_id, entity_id] => Some(InspectEntityTarget::ClientEntity {
+            zone_id: zone_id.parse::<ZoneId>().ok()?,
+            entity_id: ClientEntityId(entity_id.parse::<usize>().ok()?),
+        }),
+        _ => None,
+    }
+}
+
+async fn handle_request(
+    line: &str,
+    control_message_tx: &crossbeam_channel::Sender<ControlMessage>,
+    config_path: Option<&Arc<PathBuf>>,
+) -> serde_json::Value {
+    let args: Vec<&str> = line.split_whitespace().collect();
+    match args.split_first() {
+        Some((&"inspect", args)) => {
+            let Some(target) = parse_inspect_target(args) else {
+                return json!({ "error": "usage: inspect <name> | inspect <zone_id> <entity_id>" });
+            };
+
+            let (response_tx, response_rx) = oneshot::channel();
+            if control_message_tx
+                .send(ControlMessage::InspectEntity {
+                    target,
+                    response_tx,
+                })
+                .is_err()
+            {
+                return json!({ "error": "game world is not running" });
+            }
+
+            match response_rx.await {
+                Ok(Some(snapshot)) => snapshot,
+                Ok(None) => json!({ "error": "entity not found" }),
+                Err(_) => json!({ "error": "game world is not running" }),
+            }
+        }
+        Some((&"save", args)) => {
+            let name = match *args {
+                ["all"] => None,
+                [name] => Some(name.to_string()),
+                _ => return json!({ "error": "usage: save <name> | save all" }),
+            };
+
+            let is_single_character = name.is_some();
+            let (response_tx, response_rx) = oneshot::channel();
+            if control_message_tx
+                .send(ControlMessage::SaveCharacters { name, response_tx })
+                .is_err()
+            {
+                return json!({ "error": "game world is not running" });
+            }
+
+            match response_rx.await {
+                Ok(0) if is_single_character => json!({ "error": "character not found" }),
+                Ok(num_saved) => json!({ "saved": num_saved }),
+                Err(_) => json!({ "error": "game world is not running" }),
+            }
+        }
+        Some((&(request @ ("watch" | "unwatch")), args)) => {
+            let [name] = *args else {
+                return json!({ "error": format!("usage: {} <name>", request) });
+            };
+
+            let (response_tx, response_rx) = oneshot::channel();
+            if control_message_tx
+                .send(ControlMessage::WatchCharacter {
+                    name: name.to_string(),
+                    enabled: request == "watch",
+                    response_tx,
+                })
+                .is_err()
+            {
+                return json!({ "error": "game world is not running" });
+            }
+
+            match response_rx.await {
+                Ok(true) => json!({ "watching": request == "watch" }),
+                Ok(false) => json!({ "error": "character not in game" }),
+                Err(_) => json!({ "error": "game world is not running" }),
+            }
+        }
+        Some((&"news", args)) => {
+            let Some(edit) = parse_news_edit(line, args) else {
+                return json!({ "error": NEWS_USAGE });
+            };
+
+            let (response_tx, response_rx) = oneshot::channel();
+            if control_message_tx
+                .send(ControlMessage::EditServerNews { edit, response_tx })
+                .is_err()
+            {
+                return json!({ "error": "game world is not running" });
+            }
+
+            match response_rx.await {
+                Ok(Ok(news)) => news,
+                Ok(Err(error)) => json!({ "error": error }),
+                Err(_) => json!({ "error": "game world is not running" }),
+            }
+        }
+        Some((&"reports", args)) => {
+            let count = match *args {
+                [] => DEFAULT_NUM_REPORTS,
+                [count] => match count.parse::<usize>() {
+                    Ok(count) => count,
+                    Err(_) => return json!({ "error": "usage: reports [count]" }),
+                },
+                _ => return json!({ "error": "usage: reports [count]" }),
+            };
+
+            // Reports are read straight from storage, so they are available without the game world
+            match tokio::task::spawn_blocking(move || ReportStorage::try_load_recent(count)).await {
+                Ok(Ok(reports)) => json!({ "reports": reports }),
+                Ok(Err(error)) => json!({ "error": format!("{:?}", error) }),
+                Err(_) => json!({ "error": "failed to load reports" }),
+            }
+        }
+        Some((&"reset_password", args)) => {
+            let [name, password] = *args else {
+                return json!({ "error": "usage: reset_password <name> <password>" });
+            };
+
+            let (response_tx, response_rx) = oneshot::channel();
+            if control_message_tx
+                .send(ControlMessage::ResetPassword {
+                    name: name.to_string(),
+                    password: Password::Plaintext(password.to_string()),
+                    response_tx,
+                })
+                .is_err()
+            {
+                return json!({ "error": "game world is not running" });
+            }
+
+            match response_rx.await {
+                Ok(Ok(())) => json!({ "reset": name }),
+                Ok(Err(error)) => json!({ "error": error }),
+                Err(_) => json!({ "error": "game world is not running" }),
+            }
+        }
+        Some((&"lockouts", [])) => {
+            let (response_tx, response_rx) = oneshot::channel();
+            if control_message_tx
+                .send(ControlMessage::GetLoginLockouts { response_tx })
+                .is_err()
+            {
+                return json!({ "error": "game world is not running" });
+            }
+
+            response_rx
+                .await
+                .unwrap_or_else(|_| json!({ "error": "game world is not running" }))
+        }
+        Some((&"census", args)) => {
+            let num_top_zones = match *args {
+                [] => DEFAULT_NUM_CENSUS_ZONES,
+                [count] => match count.parse::<usize>() {
+                    Ok(count) => count,
+                    Err(_) => return json!({ "error": "usage: census [zones]" }),
+                },
+                _ => return json!({ "error": "usage: census [zones]" }),
+            };
+
+            let (response_tx, response_rx) = oneshot::channel();
+            if control_message_tx
+                .send(ControlMessage::GetZoneCensus {
+                    num_top_zones,
+                    response_tx,
+                })
+                .is_err()
+            {
+                return json!({ "error": "game world is not running" });
+            }
+
+            response_rx
+                .await
+                .unwrap_or_else(|_| json!({ "error": "game world is not running" }))
+        }
+        Some((&"reload", [])) => {
+            let Some(config_path) = config_path.cloned() else {
+                return json!({ "error": "server was not started with --config" });
+            };
+
+            let game_config =
+                match tokio::task::spawn_blocking(move || GameConfig::load(&config_path)).await {
+                    Ok(Ok(game_config)) => game_config,
+                    Ok(Err(error)) => return json!({ "error": format!("{:?}", error) }),
+                    Err(_) => return json!({ "error": "failed to load config" }),
+                };
+
+            if control_message_tx
+                .send(ControlMessage::ReloadConfig {
+                    game_config: Box::new(game_config),
+                })
+                .is_err()
+            {
+                return json!({ "error": "game world is not running" });
+            }
+
+            json!({ "reloaded": true })
+        }
+        _ => json!({ "error": "unknown request" }),
+    }
+}
+
+async fn run_connection(
+    stream: TcpStream,
+    control_message_tx: crossbeam_channel::Sender<ControlMessage>,
+    config_path: Option<Arc<PathBuf>>,
+) -> Result<(), anyhow::Error> {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let mut response = handle_request(&line, &control_message_tx, config_path.as_ref())
+            .await
+            .to_string();
+        response.push('\n');
+        writer.write_all(response.as_bytes()).await?;
+    }
+
+    Ok(())
+}
+
+/// A line based admin API, each request line is answered with one line of JSON.
+pub struct AdminServer {
+    listener: TcpListener,
+    control_message_tx: crossbeam_channel::Sender<ControlMessage>,
+    config_path: Option<Arc<PathBuf>>,
+}
+
+impl AdminServer {
+    pub fn new(
+        listener: TcpListener,
+        control_message_tx: crossbeam_channel::Sender<ControlMessage>,
+        config_path: Option<PathBuf>,
+    ) -> Self {
+        Self {
+            listener,
+            control_message_tx,
+            config_path: config_path.map(Arc::new),
+        }
+    }
+
+    pub async fn run(&mut self) {
+        loop {
+            let Ok((socket, addr)) = self.listener.accept().await else {
+                continue;
+            };
+            info!("Admin Server new connection from: {:?}", addr);
+
+            let control_message_tx = self.control_message_tx.clone();
+            let config_path = self.config_path.clone();
+            tokio::spawn(async move {
+                if let Err(err) = run_connection(socket, control_message_tx, config_path).await {
+                    info!("Admin Server connection error: {:?}", err);
+                }
+            });
+        }
+    }
+}
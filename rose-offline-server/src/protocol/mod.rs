@@ -1,16 +1,21 @@
 use async_trait::async_trait;
 use thiserror::Error;
 
-use rose_game_common::messages::{client::ClientMessage, server::ServerMessage};
+use rose_game_common::messages::client::ClientMessage;
 use rose_network_common::{Connection, PacketCodec};
 
-use crate::game::messages::control::ClientType;
+use crate::game::messages::{
+    control::{ClientType, ProtocolCapabilities},
+    server_queue::ServerMessageReceiver,
+    watch::ClientWatch,
+};
 
 pub struct Client<'a> {
     pub entity: bevy::ecs::prelude::Entity,
     pub connection: Connection<'a>,
     pub client_message_tx: crossbeam_channel::Sender<ClientMessage>,
-    pub server_message_rx: tokio::sync::mpsc::UnboundedReceiver<ServerMessage>,
+    pub server_message_rx: ServerMessageReceiver,
+    pub watch: ClientWatch,
 }
 
 #[derive(Debug, Error)]
@@ -24,12 +29,17 @@ pub trait ProtocolServer {
     async fn run_client(&mut self, client: &mut Client) -> Result<(), anyhow::Error>;
 }
 
+pub type CreateProtocolServer =
+    Box<dyn Fn() -> Box<dyn ProtocolServer + Send + Sync> + Send + Sync>;
+
 pub struct Protocol {
     pub client_type: ClientType,
+    pub capabilities: ProtocolCapabilities,
     pub packet_codec: Box<dyn PacketCodec + Send + Sync>,
-    pub create_server: fn() -> Box<dyn ProtocolServer + Send + Sync>,
+    pub create_server: CreateProtocolServer,
 }
 
+pub mod admin_server;
 pub mod server;
 
 #[macro_export]
@@ -43,7 +53,8 @@ macro_rules! implement_protocol_server {
                         packet = client.connection.read_packet() => {
                             match packet {
                                 Ok(packet) => {
-                                    match self.handle_packet(client, &packet).await {
+                                    let span = tracing::trace_span!("handle_packet", command = packet.command);
+                                    match tracing::Instrument::instrument(self.handle_packet(client, &packet), span).await {
                                         Ok(_) => {},
                                         Err(error) => {
                                             log::warn!("RECV [{:03X}] {:02x?}", packet.command, &packet.data[..]);
@@ -58,7 +69,9 @@ macro_rules! implement_protocol_server {
                         },
                         server_message = client.server_message_rx.recv() => {
                             if let Some(message) = server_message {
-                                self.handle_server_message(client, message).await?;
+                                client.watch.log_send(&message);
+                                let span = tracing::trace_span!("handle_server_message");
+                                tracing::Instrument::instrument(self.handle_server_message(client, message), span).await?;
                             } else {
                                 return Err(ProtocolServerError::ServerInitiatedDisconnect.into());
                             }
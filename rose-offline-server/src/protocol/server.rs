@@ -1,42 +1,58 @@
 use bevy::ecs::prelude::Entity;
 use lazy_static::__Deref;
 use log::info;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use tokio::{
     net::{TcpListener, TcpStream},
     sync::oneshot,
 };
+use tracing::Instrument;
 
 use crate::{
-    game::messages::{control::ControlMessage, server::ServerMessage},
+    game::messages::{
+        control::ControlMessage, server_queue::server_message_queue, watch::ClientWatch,
+    },
     protocol::{Client, Connection, Protocol},
 };
 
+/// Only game connections use the idle timeout, the irose client is not known to send keepalive
+/// packets on its world server connection while it is in game
 async fn run_connection(
     stream: TcpStream,
     protocol: &Protocol,
+    idle_timeout: Option<Duration>,
     control_message_tx: crossbeam_channel::Sender<ControlMessage>,
 ) -> Result<(), anyhow::Error> {
     let (client_message_tx, client_message_rx) = crossbeam_channel::unbounded();
-    let (server_message_tx, server_message_rx) =
-        tokio::sync::mpsc::unbounded_channel::<ServerMessage>();
+    let (server_message_tx, server_message_rx) = server_message_queue();
     let (response_tx, response_rx) = oneshot::channel();
+    let watch = ClientWatch::new();
 
     control_message_tx.send(ControlMessage::AddClient {
         client_type: protocol.client_type,
+        capabilities: protocol.capabilities,
+        ip_address: stream.peer_addr().ok().map(|addr| addr.ip()),
         server_message_tx,
         client_message_rx,
+        watch: watch.clone(),
         response_tx,
     })?;
 
     let entity = response_rx.await?;
+    let mut connection = Connection::new(stream, protocol.packet_codec.deref());
+    connection.set_idle_timeout(idle_timeout);
     let mut client = Client {
         entity,
-        connection: Connection::new(stream, protocol.packet_codec.deref()),
+        connection,
         client_message_tx,
         server_message_rx,
+        watch,
     };
-    let result = (protocol.create_server)().run_client(&mut client).await;
+    let span = tracing::info_span!("client", client_type = ?protocol.client_type, entity = ?entity);
+    let result = (protocol.create_server)()
+        .run_client(&mut client)
+        .instrument(span)
+        .await;
 
     control_message_tx
         .send(ControlMessage::RemoveClient {
@@ -79,7 +95,7 @@ impl LoginServer {
                             if let Ok(addr) = socket.peer_addr() {
                                 info!("Login Server new connection from: {:?}", addr);
                             }
-                            if let Err(err) = run_connection(socket, protocol.deref(), control_message_tx).await {
+                            if let Err(err) = run_connection(socket, protocol.deref(), None, control_message_tx).await {
                                 info!("Login Server connection error: {:?}", err);
                             }
                         });
@@ -140,7 +156,7 @@ impl WorldServer {
                             if let Ok(addr) = socket.peer_addr() {
                                 info!("World Server new connection from: {:?}", addr);
                             }
-                            if let Err(err) = run_connection(socket, protocol.deref(), control_message_tx).await {
+                            if let Err(err) = run_connection(socket, protocol.deref(), None, control_message_tx).await {
                                 info!("World Server connection error: {:?}", err);
                             }
                         });
@@ -166,6 +182,7 @@ pub struct GameServer {
 
     listener: TcpListener,
     protocol: Arc<Protocol>,
+    idle_timeout: Option<Duration>,
     control_message_tx: crossbeam_channel::Sender<ControlMessage>,
 }
 
@@ -173,8 +190,10 @@ impl GameServer {
     pub async fn new(
         name: String,
         world_server: Entity,
+        max_players: u32,
         listener: TcpListener,
         protocol: Arc<Protocol>,
+        idle_timeout: Option<Duration>,
         control_message_tx: crossbeam_channel::Sender<ControlMessage>,
     ) -> Result<GameServer, anyhow::Error> {
         let (response_tx, response_rx) = oneshot::channel();
@@ -185,6 +204,7 @@ impl GameServer {
             ip: local_addr.ip().to_string(),
             port: local_addr.port(),
             packet_codec_seed: protocol.packet_codec.get_seed(),
+            max_players,
             response_tx,
         })?;
         let entity = response_rx.await?;
@@ -193,6 +213,7 @@ impl GameServer {
             entity,
             listener,
             protocol,
+            idle_timeout,
             control_message_tx,
         })
     }
@@ -204,12 +225,13 @@ impl GameServer {
                     loop {
                         let (socket, _) = self.listener.accept().await.unwrap();
                         let protocol = self.protocol.clone();
+                        let idle_timeout = self.idle_timeout;
                         let control_message_tx = self.control_message_tx.clone();
                         tokio::spawn(async move {
                             if let Ok(addr) = socket.peer_addr() {
                                 info!("Game Server connection from: {:?}", addr);
                             }
-                            if let Err(err) = run_connection(socket, protocol.deref(), control_message_tx).await {
+                            if let Err(err) = run_connection(socket, protocol.deref(), idle_timeout, control_message_tx).await {
                                 info!("Game Server connection error: {:?}", err);
                             }
                         });
@@ -1,12 +1,205 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use clap::{Arg, Command};
 use schemars::schema_for;
+use serde::Serialize;
 
+use rose_data::{ItemData, NpcDatabaseOptions, SpriteSheetType, StringDatabaseOptions};
+use rose_data_irose::{
+    get_item_database, get_npc_database, get_skill_database, get_sprite_database,
+    get_string_database, get_zone_database,
+};
 use rose_file_readers::{
-    editor_friendly::QsdFile, QsdReadOptions, RoseFile, RoseFileReader, RoseFileWriter,
+    editor_friendly::QsdFile, AipFile, HostFilesystemDevice, QsdReadOptions, RoseFile,
+    RoseFileReader, RoseFileWriter, VfsIndex, VirtualFilesystem, VirtualFilesystemDevice,
 };
 
+fn write_json<T: Serialize + ?Sized>(dst: &Path, value: &T) {
+    let json = match serde_json::to_string_pretty(value) {
+        Ok(json) => json,
+        Err(error) => {
+            println!("Failed to serialize json {}", error);
+            return;
+        }
+    };
+
+    if let Err(error) = std::fs::write(dst, json) {
+        println!("Failed to write to {}: {}", dst.display(), error);
+    }
+}
+
+fn load_vfs(data_path: &Path) -> Option<VirtualFilesystem> {
+    let device: Box<dyn VirtualFilesystemDevice + Send + Sync> = if data_path.is_file() {
+        match VfsIndex::load(data_path) {
+            Ok(vfs_index) => Box::new(vfs_index),
+            Err(error) => {
+                println!("Failed to load vfs {}: {}", data_path.display(), error);
+                return None;
+            }
+        }
+    } else {
+        Box::new(HostFilesystemDevice::new(data_path.to_path_buf()))
+    };
+    Some(VirtualFilesystem::new(vec![device]))
+}
+
+#[derive(Serialize)]
+struct SpriteAtlasEntry<'a> {
+    id: usize,
+    texture: &'a str,
+    left: i32,
+    top: i32,
+    right: i32,
+    bottom: i32,
+    name: &'a str,
+}
+
+#[derive(Serialize)]
+struct SpriteAtlas<'a> {
+    textures: Vec<&'a str>,
+    sprites: Vec<SpriteAtlasEntry<'a>>,
+}
+
+fn export_sprites(data_path: &Path, dst: &Path) {
+    let Some(vfs) = load_vfs(data_path) else {
+        return;
+    };
+
+    let sprite_database = match get_sprite_database(&vfs) {
+        Ok(sprite_database) => sprite_database,
+        Err(error) => {
+            println!("Failed to load sprite database: {}", error);
+            return;
+        }
+    };
+
+    for (sheet_type, sheet) in sprite_database.iter() {
+        let sheet_name = match sheet_type {
+            SpriteSheetType::Item => "item",
+            SpriteSheetType::Skill => "skill",
+            SpriteSheetType::StatusEffect => "status_effect",
+        };
+        let sheet_dst = dst.join(sheet_name);
+        if let Err(error) = std::fs::create_dir_all(&sheet_dst) {
+            println!(
+                "Failed to create directory {}: {}",
+                sheet_dst.display(),
+                error
+            );
+            continue;
+        }
+
+        // Textures are copied as is, the atlas refers to them by file name
+        let texture_names: Vec<&str> = sheet
+            .textures
+            .iter()
+            .map(|texture| {
+                texture
+                    .path()
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .unwrap_or("")
+            })
+            .collect();
+        for (texture, texture_name) in sheet.textures.iter().zip(texture_names.iter()) {
+            let data = match vfs.open_file(texture) {
+                Ok(data) => data,
+                Err(error) => {
+                    println!("Failed to read {}: {}", texture.path().display(), error);
+                    continue;
+                }
+            };
+
+            let texture_dst = sheet_dst.join(texture_name);
+            if let Err(error) = std::fs::write(&texture_dst, data.as_bytes()) {
+                println!("Failed to write to {}: {}", texture_dst.display(), error);
+            }
+        }
+
+        let atlas = SpriteAtlas {
+            textures: texture_names.clone(),
+            sprites: sheet
+                .sprites
+                .iter()
+                .enumerate()
+                .map(|(id, sprite)| SpriteAtlasEntry {
+                    id,
+                    texture: texture_names
+                        .get(sprite.texture_index)
+                        .copied()
+                        .unwrap_or(""),
+                    left: sprite.left,
+                    top: sprite.top,
+                    right: sprite.right,
+                    bottom: sprite.bottom,
+                    name: &sprite.name,
+                })
+                .collect(),
+        };
+        write_json(&dst.join(format!("{}.json", sheet_name)), &atlas);
+    }
+}
+
+fn dump_database(data_path: &Path, dst: &Path) {
+    let Some(vfs) = load_vfs(data_path) else {
+        return;
+    };
+
+    if let Err(error) = std::fs::create_dir_all(dst) {
+        println!("Failed to create directory {}: {}", dst.display(), error);
+        return;
+    }
+
+    let string_database = match get_string_database(&vfs, &StringDatabaseOptions { language: 1 }) {
+        Ok(string_database) => string_database,
+        Err(error) => {
+            println!("Failed to load string database: {}", error);
+            return;
+        }
+    };
+
+    match get_item_database(&vfs, string_database.clone()) {
+        Ok(item_database) => {
+            let items: Vec<ItemData> = item_database
+                .iter_all_items()
+                .filter_map(|item_reference| item_database.get_item(item_reference))
+                .collect();
+            write_json(&dst.join("items.json"), &items);
+        }
+        Err(error) => println!("Failed to load item database: {}", error),
+    }
+
+    match get_npc_database(
+        &vfs,
+        string_database.clone(),
+        &NpcDatabaseOptions {
+            load_frame_data: false,
+        },
+    ) {
+        Ok(npc_database) => {
+            let npcs: Vec<_> = npc_database.iter().collect();
+            write_json(&dst.join("npcs.json"), &npcs);
+        }
+        Err(error) => println!("Failed to load npc database: {}", error),
+    }
+
+    match get_skill_database(&vfs, string_database.clone()) {
+        Ok(skill_database) => {
+            let skills: Vec<_> = skill_database.iter().collect();
+            write_json(&dst.join("skills.json"), &skills);
+        }
+        Err(error) => println!("Failed to load skill database: {}", error),
+    }
+
+    match get_zone_database(&vfs, string_database) {
+        Ok(zone_database) => {
+            let zones: Vec<_> = zone_database.iter().collect();
+            write_json(&dst.join("zones.json"), &zones);
+        }
+        Err(error) => println!("Failed to load zone database: {}", error),
+    }
+}
+
 fn main() {
     let command = Command::new("rose-conv")
         .about("ROSE file format converter")
@@ -43,6 +236,40 @@ fn main() {
                         .takes_value(true)
                         .required(true),
                 ),
+        )
+        .subcommand(
+            Command::new("dump-db")
+                .about("Dump the item, npc, skill and zone databases to JSON")
+                .arg(
+                    Arg::new("data")
+                        .help("Path to data.idx or an extracted data directory")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("dst")
+                        .help("Destination directory path")
+                        .takes_value(true)
+                        .required(true),
+                ),
+        )
+        .subcommand(
+            Command::new("export-sprites")
+                .about(
+                    "Export the item, skill and status effect icon sprite sheets with JSON atlases",
+                )
+                .arg(
+                    Arg::new("data")
+                        .help("Path to data.idx or an extracted data directory")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("dst")
+                        .help("Destination directory path")
+                        .takes_value(true)
+                        .required(true),
+                ),
         );
     let matches = command.get_matches();
 
@@ -126,6 +353,56 @@ fn main() {
                         }
                     }
                 }
+                Some("aip") => {
+                    let Ok(data) = std::fs::read(src) else {
+                        println!("Failed to read file {}", src.display());
+                        return;
+                    };
+                    let aip = match <AipFile as RoseFile>::read(RoseFileReader::from(&data), &()) {
+                        Ok(aip) => aip,
+                        Err(error) => {
+                            println!("Failed to parse AipFile {}: {}", src.display(), error);
+                            return;
+                        }
+                    };
+
+                    match dst_extension {
+                        Some("yaml") => {
+                            let mut yaml_bytes = Vec::with_capacity(1024 * 1024);
+                            match serde_yaml::with::singleton_map_recursive::serialize(
+                                &aip,
+                                &mut serde_yaml::Serializer::new(&mut yaml_bytes),
+                            ) {
+                                Ok(_) => {}
+                                Err(error) => {
+                                    println!("Failed to serialize yaml {}", error);
+                                    return;
+                                }
+                            }
+                            let yaml = String::from_utf8(yaml_bytes).unwrap();
+
+                            if let Err(error) = std::fs::write(dst, yaml) {
+                                println!("Failed to write to {}: {}", dst.display(), error);
+                            }
+                        }
+                        Some("json") => {
+                            let json = match serde_json::to_string(&aip) {
+                                Ok(json) => json,
+                                Err(error) => {
+                                    println!("Failed to serialize json {}", error);
+                                    return;
+                                }
+                            };
+
+                            if let Err(error) = std::fs::write(dst, json) {
+                                println!("Failed to write to {}: {}", dst.display(), error);
+                            }
+                        }
+                        _ => {
+                            println!("Unknown file extension for dest {}", dst.display());
+                        }
+                    }
+                }
                 Some("yaml") => {
                     let Ok(data) = std::fs::read_to_string(src) else {
                         println!("Failed to read file {}", src.display());
@@ -182,8 +459,12 @@ fn main() {
             );
 
             match file_type {
-                "qsd" => {
-                    let schema = schema_for!(QsdFile);
+                "qsd" | "aip" => {
+                    let schema = if file_type == "qsd" {
+                        schema_for!(QsdFile)
+                    } else {
+                        schema_for!(AipFile)
+                    };
                     let json = match serde_json::to_string_pretty(&schema) {
                         Ok(json) => json,
                         Err(error) => {
@@ -201,6 +482,16 @@ fn main() {
                 }
             }
         }
+        Some(("dump-db", sub_matches)) => {
+            let data_path = PathBuf::from(sub_matches.get_one::<String>("data").unwrap());
+            let dst = PathBuf::from(sub_matches.get_one::<String>("dst").unwrap());
+            dump_database(&data_path, &dst);
+        }
+        Some(("export-sprites", sub_matches)) => {
+            let data_path = PathBuf::from(sub_matches.get_one::<String>("data").unwrap());
+            let dst = PathBuf::from(sub_matches.get_one::<String>("dst").unwrap());
+            export_sprites(&data_path, &dst);
+        }
         _ => unimplemented!(),
     }
 }